@@ -0,0 +1,31 @@
+extern crate gltf;
+
+use std::{fs, io};
+
+use gltf::{Glb, Gltf};
+use std::boxed::Box;
+use std::error::Error as StdError;
+
+fn run(path: &str) -> Result<(), Box<dyn StdError>> {
+    use io::Read;
+    let file = fs::File::open(&path)?;
+    let mut data = Vec::with_capacity(file.metadata()?.len() as usize);
+    let mut reader = io::BufReader::new(file);
+    let _ = reader.read_to_end(&mut data)?;
+    let gltf = if gltf::is_binary(&data) {
+        let glb = Glb::from_slice(&data)?;
+        Gltf::from_slice(&glb.json)
+    } else {
+        Gltf::from_slice(&data)
+    }?.validate_completely()?;
+    print!("{}", gltf::print::stats(&gltf));
+    Ok(())
+}
+
+fn main() {
+    if let Some(path) = std::env::args().nth(1) {
+        run(&path).expect("runtime error");
+    } else {
+        println!("usage: gltf-stats <FILE>");
+    }
+}
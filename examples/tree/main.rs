@@ -6,19 +6,6 @@ use gltf::{Glb, Gltf};
 use std::boxed::Box;
 use std::error::Error as StdError;
 
-fn print_tree(node: &gltf::Node, depth: i32) {
-    for _ in 0..(depth - 1) {
-        print!("  ");
-    }
-    print!(" -");
-    let index = node.index();
-    let name = node.name().unwrap_or("<Unnamed>");
-    println!(" Node {} ({})", index, name);
-    for child in node.children() {
-        print_tree(&child, depth + 1);
-    }
-}
-
 fn run(path: &str) -> Result<(), Box<StdError>> {
     use io::Read;
     let file = fs::File::open(&path)?;
@@ -31,14 +18,7 @@ fn run(path: &str) -> Result<(), Box<StdError>> {
     } else {
         Gltf::from_slice(&data)
     }?.validate_completely()?;
-    for scene in gltf.scenes() {
-        let index = scene.index();
-        let name = scene.name().unwrap_or("<Unnamed>");
-        println!("Scene {} ({})", index, name);
-        for node in scene.nodes() {
-            print_tree(&node, 1);
-        }
-    }
+    print!("{}", gltf::print::tree(&gltf));
     Ok(())
 }
 
@@ -0,0 +1,303 @@
+extern crate gltf;
+extern crate gltf_importer;
+extern crate gltf_utils;
+
+use std::{fs, io};
+
+use gltf_utils::{framing, lint, PrimitiveIterators};
+use std::boxed::Box;
+use std::error::Error as StdError;
+
+/// The color of pixels not covered by geometry.
+const BACKGROUND: [f32; 3] = [0.2, 0.2, 0.22];
+
+/// The direction the preview camera looks along.
+const VIEW: [f32; 3] = [-1.0, -0.8, -1.2];
+
+/// The direction towards the light, in view space.
+const LIGHT: [f32; 3] = [0.4, 0.6, 1.0];
+
+/// A software rendered image with a depth buffer.
+struct Canvas {
+    width: usize,
+    height: usize,
+    color: Vec<[f32; 3]>,
+    depth: Vec<f32>,
+}
+
+/// A vertex transformed into screen space.
+#[derive(Clone, Copy)]
+struct Vertex {
+    /// Pixel co-ordinates and view space depth.
+    position: [f32; 3],
+
+    /// View space normal.
+    normal: [f32; 3],
+}
+
+fn normalize(v: [f32; 3]) -> [f32; 3] {
+    let length = dot(v, v).sqrt();
+    if length > 0.0 { [v[0] / length, v[1] / length, v[2] / length] } else { v }
+}
+
+fn dot(a: [f32; 3], b: [f32; 3]) -> f32 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+fn cross(a: [f32; 3], b: [f32; 3]) -> [f32; 3] {
+    [a[1] * b[2] - a[2] * b[1], a[2] * b[0] - a[0] * b[2], a[0] * b[1] - a[1] * b[0]]
+}
+
+fn sub(a: [f32; 3], b: [f32; 3]) -> [f32; 3] {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+/// Rotates a vector by the inverse of an `[x, y, z, w]` unit quaternion.
+fn unrotate(q: [f32; 4], v: [f32; 3]) -> [f32; 3] {
+    let u = [-q[0], -q[1], -q[2]];
+    let t = cross(u, v);
+    let t = [2.0 * t[0], 2.0 * t[1], 2.0 * t[2]];
+    let c = cross(u, t);
+    [v[0] + q[3] * t[0] + c[0], v[1] + q[3] * t[1] + c[1], v[2] + q[3] * t[2] + c[2]]
+}
+
+/// Multiplies a column-major matrix by a point.
+fn transform_point(m: &[[f32; 4]; 4], p: [f32; 3]) -> [f32; 3] {
+    let mut result = [0.0; 3];
+    for (i, r) in result.iter_mut().enumerate() {
+        *r = m[0][i] * p[0] + m[1][i] * p[1] + m[2][i] * p[2] + m[3][i];
+    }
+    result
+}
+
+/// Multiplies a column-major matrix by a vector.
+fn transform_vector(m: &[[f32; 3]; 3], v: [f32; 3]) -> [f32; 3] {
+    let mut result = [0.0; 3];
+    for (i, r) in result.iter_mut().enumerate() {
+        *r = m[0][i] * v[0] + m[1][i] * v[1] + m[2][i] * v[2];
+    }
+    result
+}
+
+/// Converts a linear color component to an 8-bit sRGB value.
+fn srgb(c: f32) -> u8 {
+    let c = c.max(0.0).min(1.0);
+    let c = if c <= 0.003_130_8 { c * 12.92 } else { 1.055 * c.powf(1.0 / 2.4) - 0.055 };
+    (c * 255.0).round() as u8
+}
+
+impl Canvas {
+    fn new(width: usize, height: usize) -> Self {
+        Canvas {
+            width,
+            height,
+            color: vec![BACKGROUND; width * height],
+            depth: vec![::std::f32::INFINITY; width * height],
+        }
+    }
+
+    /// Rasterizes a triangle with interpolated normals and Lambert shading.
+    ///
+    /// Back faces are drawn too, with flipped normals, since many assets are
+    /// not closed or rely on `doubleSided`.
+    fn triangle(&mut self, v: [Vertex; 3], base_color: [f32; 3]) {
+        let [a, b, c] = [v[0].position, v[1].position, v[2].position];
+        let area = (b[0] - a[0]) * (c[1] - a[1]) - (b[1] - a[1]) * (c[0] - a[0]);
+        if area == 0.0 || !area.is_finite() {
+            return;
+        }
+        let min_x = a[0].min(b[0]).min(c[0]).floor().max(0.0) as usize;
+        let min_y = a[1].min(b[1]).min(c[1]).floor().max(0.0) as usize;
+        let max_x = (a[0].max(b[0]).max(c[0]).ceil() as usize).min(self.width);
+        let max_y = (a[1].max(b[1]).max(c[1]).ceil() as usize).min(self.height);
+        let light = normalize(LIGHT);
+
+        for y in min_y..max_y {
+            for x in min_x..max_x {
+                let p = [x as f32 + 0.5, y as f32 + 0.5];
+                let w0 = ((b[0] - p[0]) * (c[1] - p[1]) - (b[1] - p[1]) * (c[0] - p[0])) / area;
+                let w1 = ((c[0] - p[0]) * (a[1] - p[1]) - (c[1] - p[1]) * (a[0] - p[0])) / area;
+                let w2 = 1.0 - w0 - w1;
+                if w0 < 0.0 || w1 < 0.0 || w2 < 0.0 {
+                    continue;
+                }
+                let depth = w0 * a[2] + w1 * b[2] + w2 * c[2];
+                let pixel = y * self.width + x;
+                if depth <= 0.0 || depth >= self.depth[pixel] {
+                    continue;
+                }
+                self.depth[pixel] = depth;
+
+                let mut normal = [0.0; 3];
+                for (i, n) in normal.iter_mut().enumerate() {
+                    *n = w0 * v[0].normal[i] + w1 * v[1].normal[i] + w2 * v[2].normal[i];
+                }
+                let normal = normalize(normal);
+                // The view space normal faces the camera when its z is positive.
+                let facing = if normal[2] < 0.0 { -1.0 } else { 1.0 };
+                let diffuse = (facing * dot(normal, light)).max(0.0);
+                let shade = 0.15 + 0.85 * diffuse;
+                self.color[pixel] = [
+                    base_color[0] * shade,
+                    base_color[1] * shade,
+                    base_color[2] * shade,
+                ];
+            }
+        }
+    }
+
+    /// Encodes the canvas as an 8-bit RGB PNG image.
+    fn png(&self) -> Vec<u8> {
+        let mut raw = Vec::with_capacity((self.width * 3 + 1) * self.height);
+        for row in self.color.chunks(self.width) {
+            // Filter type 0, no filtering.
+            raw.push(0);
+            for pixel in row {
+                raw.extend(pixel.iter().map(|&c| srgb(c)));
+            }
+        }
+
+        let mut header = Vec::with_capacity(13);
+        header.extend(&(self.width as u32).to_be_bytes());
+        header.extend(&(self.height as u32).to_be_bytes());
+        header.extend(&[8, 2, 0, 0, 0]);
+
+        let mut png = b"\x89PNG\r\n\x1a\n".to_vec();
+        chunk(&mut png, b"IHDR", &header);
+        chunk(&mut png, b"IDAT", &zlib_stored(&raw));
+        chunk(&mut png, b"IEND", &[]);
+        png
+    }
+}
+
+/// Appends a PNG chunk with its length and checksum.
+fn chunk(png: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    png.extend(&(data.len() as u32).to_be_bytes());
+    let start = png.len();
+    png.extend(kind);
+    png.extend(data);
+    let crc = gltf::buffer::crc32(&png[start..]);
+    png.extend(&crc.to_be_bytes());
+}
+
+/// Wraps data in a zlib stream of uncompressed deflate blocks.
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    let mut out = vec![0x78, 0x01];
+    let blocks: Vec<&[u8]> = data.chunks(0xffff).collect();
+    for (i, block) in blocks.iter().enumerate() {
+        out.push(if i + 1 == blocks.len() { 1 } else { 0 });
+        let length = block.len() as u16;
+        out.extend(&length.to_le_bytes());
+        out.extend(&(!length).to_le_bytes());
+        out.extend(*block);
+    }
+    if blocks.is_empty() {
+        out.extend(&[1, 0, 0, 0xff, 0xff]);
+    }
+    let (mut a, mut b) = (1u32, 0u32);
+    for &byte in data {
+        a = (a + u32::from(byte)) % 65521;
+        b = (b + a) % 65521;
+    }
+    out.extend(&((b << 16) | a).to_be_bytes());
+    out
+}
+
+fn run(input: &str, output: &str, size: usize) -> Result<(), Box<dyn StdError>> {
+    use io::Write;
+    let (gltf, buffers) = gltf_importer::import(input)?;
+    let scene = gltf
+        .default_scene()
+        .or_else(|| gltf.scenes().next())
+        .ok_or("asset has no scenes")?;
+    let bounds = framing::scene_bounds(&scene).ok_or("scene has no geometry")?;
+    let yfov = 0.8;
+    let camera = framing::frame(&bounds, VIEW, yfov, 1.0);
+    let focal = size as f32 / 2.0 / (yfov / 2.0).tan();
+
+    let mut canvas = Canvas::new(size, size);
+    for (node, world) in scene.walk() {
+        let mesh = match node.mesh() {
+            Some(mesh) => mesh,
+            None => continue,
+        };
+        for primitive in mesh.primitives() {
+            let positions: Vec<[f32; 3]> = match primitive.positions(&buffers) {
+                Some(iter) => iter.collect(),
+                None => continue,
+            };
+            let normals: Option<Vec<[f32; 3]>> =
+                primitive.normals(&buffers).map(|iter| iter.collect());
+            let color = primitive.material().pbr_metallic_roughness().base_color_factor();
+            let color = [color[0], color[1], color[2]];
+
+            // World space to view space, then perspective projection onto pixels.
+            let to_view = |p: [f32; 3]| unrotate(camera.rotation, sub(p, camera.translation));
+            let vertices: Vec<Vertex> = positions
+                .iter()
+                .enumerate()
+                .map(|(i, &p)| {
+                    let view = to_view(transform_point(&world.matrix, p));
+                    let depth = -view[2];
+                    let normal = normals
+                        .as_ref()
+                        .and_then(|normals| normals.get(i))
+                        .map(|&n| transform_vector(&world.normal_matrix, n))
+                        .map(|n| unrotate(camera.rotation, n))
+                        .unwrap_or([0.0; 3]);
+                    Vertex {
+                        position: [
+                            size as f32 / 2.0 + focal * view[0] / depth,
+                            size as f32 / 2.0 - focal * view[1] / depth,
+                            depth,
+                        ],
+                        normal,
+                    }
+                })
+                .collect();
+
+            for triangle in lint::triangles(&primitive, &buffers) {
+                let mut v = [Vertex { position: [0.0; 3], normal: [0.0; 3] }; 3];
+                let mut view = [[0.0; 3]; 3];
+                let mut valid = true;
+                for (k, &index) in triangle.iter().enumerate() {
+                    match vertices.get(index as usize) {
+                        Some(vertex) => v[k] = *vertex,
+                        None => valid = false,
+                    }
+                    if let Some(&p) = positions.get(index as usize) {
+                        view[k] = to_view(transform_point(&world.matrix, p));
+                    }
+                }
+                // Triangles crossing the near plane are skipped rather than clipped.
+                if !valid || v.iter().any(|v| v.position[2] <= camera.znear * 0.5) {
+                    continue;
+                }
+                if normals.is_none() {
+                    let face = normalize(cross(sub(view[1], view[0]), sub(view[2], view[0])));
+                    for vertex in &mut v {
+                        vertex.normal = face;
+                    }
+                }
+                canvas.triangle(v, color);
+            }
+        }
+    }
+
+    let mut file = io::BufWriter::new(fs::File::create(output)?);
+    file.write_all(&canvas.png())?;
+    Ok(())
+}
+
+fn main() {
+    let args: Vec<String> = std::env::args().collect();
+    if args.len() < 3 {
+        println!("usage: gltf-render-preview <FILE> <OUTPUT.png> [SIZE]");
+        return;
+    }
+    let size = match args.get(3) {
+        Some(size) => size.parse().expect("invalid size"),
+        None => 512,
+    };
+    run(&args[1], &args[2], size).expect("runtime error");
+}
@@ -0,0 +1,59 @@
+extern crate gltf;
+extern crate gltf_importer;
+
+use std::{fs, io};
+
+use gltf::json;
+use std::boxed::Box;
+use std::error::Error as StdError;
+use std::path::Path;
+
+/// Returns `true` if the URI refers to a file relative to the asset.
+fn is_relative(uri: &str) -> bool {
+    !uri.starts_with("data:") && !uri.contains("://")
+}
+
+fn run(input: &str, output: &str) -> Result<(), Box<dyn StdError>> {
+    let (gltf, buffers) = gltf_importer::import(input)?;
+    let input_dir = Path::new(input).parent().unwrap_or(Path::new(""));
+    let output = Path::new(output);
+    let output_dir = output.parent().unwrap_or(Path::new(""));
+    let stem = output
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .ok_or("invalid output path")?;
+
+    let mut root: json::Root = gltf.into_json();
+
+    // Every buffer is written next to the output, including the binary chunk
+    // of a `.glb` input, which has no URI of its own.
+    for (index, (buffer, data)) in root.buffers.iter_mut().zip(buffers.take()).enumerate() {
+        let uri = format!("{}{}.bin", stem, index);
+        fs::write(output_dir.join(&uri), &data)?;
+        buffer.uri = Some(uri);
+    }
+
+    // External images keep their URIs and are copied alongside the output.
+    for image in &root.images {
+        if let Some(uri) = image.uri.as_ref().filter(|uri| is_relative(uri)) {
+            let target = output_dir.join(uri);
+            if let Some(parent) = target.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::copy(input_dir.join(uri), target)?;
+        }
+    }
+
+    let file = fs::File::create(output)?;
+    json::to_writer_pretty(io::BufWriter::new(file), &root)?;
+    Ok(())
+}
+
+fn main() {
+    let args: Vec<String> = std::env::args().collect();
+    if args.len() < 3 {
+        println!("usage: gltf-roundtrip <FILE> <OUTPUT.gltf>");
+        return;
+    }
+    run(&args[1], &args[2]).expect("runtime error");
+}
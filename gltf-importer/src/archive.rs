@@ -0,0 +1,187 @@
+use gltf::{self, Gltf};
+use std::collections::HashMap;
+use std::{fs, io};
+use std::path::{Path, PathBuf};
+use zip::ZipArchive;
+use zip::result::ZipError;
+
+use image::{self, Image};
+use config::DEFAULT_MAX_DECOMPRESSED_SIZE;
+use {Base, Buffers, Config, Error};
+
+/// The contents of a zip archive containing a glTF asset along with the
+/// external files it references.
+///
+/// The asset is the `.gltf` or `.glb` file closest to the root of the archive.
+/// Relative URIs are resolved against the directory of the asset within the
+/// archive.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// # fn run() -> Result<(), gltf_importer::Error> {
+/// let archive = gltf_importer::Archive::open("path/to/asset.zip")?;
+/// let (gltf, buffers) = archive.import(&Default::default())?;
+/// for image in gltf.images() {
+///     let image = archive.load_image(&image, &buffers, &Default::default())?;
+///     println!("{}: {} bytes", image.mime_type, image.data.len());
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct Archive {
+    /// The decompressed contents of every file, by path within the archive.
+    entries: HashMap<String, Vec<u8>>,
+
+    /// The path of the asset within the archive.
+    asset: String,
+}
+
+impl From<ZipError> for Error {
+    fn from(err: ZipError) -> Error {
+        match err {
+            ZipError::Io(err) => Error::Io(err),
+            err => Error::Io(io::Error::new(io::ErrorKind::InvalidData, err)),
+        }
+    }
+}
+
+/// Returns whether an archive entry is a glTF asset.
+fn is_asset(name: &str) -> bool {
+    let name = name.to_lowercase();
+    (name.ends_with(".gltf") || name.ends_with(".glb")) && !name.starts_with("__macosx/")
+}
+
+/// Resolves a relative URI against the path of a file within an archive,
+/// returning `None` if the URI refers to a location outside the archive.
+fn resolve(asset: &str, uri: &str) -> Option<String> {
+    let mut segments: Vec<&str> = asset.split('/').collect();
+    let _ = segments.pop();
+    for segment in uri.split('/') {
+        match segment {
+            "" | "." => {},
+            ".." => {
+                segments.pop()?;
+            },
+            segment => segments.push(segment),
+        }
+    }
+    Some(segments.join("/"))
+}
+
+impl Archive {
+    /// Reads a zip archive from a file.
+    ///
+    /// See `from_reader` for the limit on the size of its contents.
+    pub fn open<P>(path: P) -> Result<Archive, Error>
+        where P: AsRef<Path>
+    {
+        let path = path.as_ref();
+        if !path.exists() {
+            return Err(Error::FileNotFound(path.to_path_buf()));
+        }
+        Archive::from_reader(io::BufReader::new(fs::File::open(path)?))
+    }
+
+    /// Reads a zip archive from a reader.
+    ///
+    /// Fails with `Error::DecompressedSizeExceeded` if the files of the archive
+    /// decompress to more than `DEFAULT_MAX_DECOMPRESSED_SIZE` bytes in total.
+    pub fn from_reader<R>(reader: R) -> Result<Archive, Error>
+        where R: io::Read + io::Seek
+    {
+        Archive::from_reader_with_max_size(reader, DEFAULT_MAX_DECOMPRESSED_SIZE)
+    }
+
+    /// Reads a zip archive from a reader, failing with
+    /// `Error::DecompressedSizeExceeded` as soon as its files decompress to
+    /// more than `max_size` bytes in total.
+    ///
+    /// The sizes recorded in the archive are not trusted, which guards against
+    /// decompression bombs.
+    pub fn from_reader_with_max_size<R>(reader: R, max_size: u64) -> Result<Archive, Error>
+        where R: io::Read + io::Seek
+    {
+        use io::Read;
+        let mut zip = ZipArchive::new(reader)?;
+        let mut entries = HashMap::new();
+        let mut remaining = max_size;
+        for index in 0..zip.len() {
+            let file = zip.by_index(index)?;
+            if file.is_dir() {
+                continue;
+            }
+            let name = file.name().to_string();
+            let mut data = vec![];
+            // Read one byte past the limit so that exceeding it shows.
+            let _ = file.take(remaining.saturating_add(1)).read_to_end(&mut data)?;
+            if data.len() as u64 > remaining {
+                return Err(Error::DecompressedSizeExceeded(max_size));
+            }
+            remaining -= data.len() as u64;
+            entries.insert(name, data);
+        }
+        let asset = entries
+            .keys()
+            .filter(|name| is_asset(name))
+            .min_by_key(|name| (name.matches('/').count(), name.to_string()))
+            .cloned()
+            .ok_or_else(|| Error::FileNotFound(PathBuf::from("*.gltf")))?;
+        Ok(Archive { entries, asset })
+    }
+
+    /// Returns the path of the asset within the archive.
+    pub fn asset(&self) -> &str {
+        &self.asset
+    }
+
+    /// Returns the contents of a file by its path within the archive.
+    pub fn entry(&self, name: &str) -> Option<&[u8]> {
+        self.entries.get(name).map(Vec::as_slice)
+    }
+
+    /// Reads the file referenced by a URI relative to the asset at `asset`.
+    pub(crate) fn read(&self, asset: &str, uri: &str) -> Result<Vec<u8>, Error> {
+        resolve(asset, uri)
+            .and_then(|name| self.entry(&name))
+            .map(<[u8]>::to_vec)
+            .ok_or_else(|| Error::FileNotFound(PathBuf::from(uri)))
+    }
+
+    /// Imports the asset with custom configuration.
+    pub fn import(&self, config: &Config) -> Result<(Gltf, Buffers), Error> {
+        let data = &self.entries[&self.asset];
+        let base = Base::Archive(self, &self.asset);
+        ::import_data_impl(data, base, config, None, &mut ::Metrics::default())
+    }
+
+    /// Loads the encoded data of an image of the imported asset.
+    ///
+    /// See `image::load` for details.
+    pub fn load_image(
+        &self,
+        image: &gltf::Image,
+        buffers: &Buffers,
+        config: &Config,
+    ) -> Result<Image, Error> {
+        image::load_from(Base::Archive(self, &self.asset), image, buffers, config)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn relative_uris() {
+        assert_eq!(Some("model/scene.bin".to_string()), resolve("model/scene.gltf", "scene.bin"));
+        assert_eq!(
+            Some("textures/a.png".to_string()),
+            resolve("model/scene.gltf", "./../textures/a.png"),
+        );
+        assert_eq!(None, resolve("scene.gltf", "../a.png"));
+        assert!(is_asset("Model/Scene.GLB"));
+        assert!(!is_asset("__MACOSX/._scene.gltf"));
+    }
+}
@@ -0,0 +1,132 @@
+use std::collections::HashMap;
+use std::panic;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+use gltf::{self, Gltf};
+
+use image::{self, Image};
+use {Base, Buffers, Config, Error, Metrics};
+
+/// An asset imported by `import_all`, along with its images.
+#[derive(Debug)]
+pub struct Imported {
+    /// The imported glTF.
+    pub gltf: Gltf,
+
+    /// The buffer data of the glTF.
+    pub buffers: Buffers,
+
+    /// The images of the glTF, in index order.
+    ///
+    /// Images loaded from the same file by several assets are shared.
+    pub images: Vec<Arc<Image>>,
+}
+
+/// Images loaded from external files, by canonical path.
+type Cache = Mutex<HashMap<PathBuf, Arc<Image>>>;
+
+/// Imports many assets concurrently with default configuration.
+///
+/// See `import_all_with_config`.
+pub fn import_all<I, P>(paths: I, parallelism: usize) -> Vec<Result<Imported, Error>>
+    where I: IntoIterator<Item = P>, P: AsRef<Path>
+{
+    import_all_with_config(paths, parallelism, &Config::default())
+}
+
+/// Imports many assets concurrently on `parallelism` threads, loading their
+/// images too, and returns the result of each in the order of `paths`.
+///
+/// External image files referenced by several assets are loaded once and
+/// shared, keeping the MIME type determined for the first asset loading them.
+/// A failure to import one asset, or to load one of its images, does not
+/// affect the others.
+///
+/// # Panics
+///
+/// Panics if importing any asset panics.
+///
+/// # Examples
+///
+/// ```rust
+/// let paths = ["../examples/Box.gltf", "../examples/Box.glb", "missing.gltf"];
+/// let results = gltf_importer::import_all(&paths, 2);
+/// assert!(results[0].is_ok() && results[1].is_ok());
+/// assert!(results[2].is_err());
+/// ```
+pub fn import_all_with_config<I, P>(
+    paths: I,
+    parallelism: usize,
+    config: &Config,
+) -> Vec<Result<Imported, Error>>
+    where I: IntoIterator<Item = P>, P: AsRef<Path>
+{
+    let paths: Arc<Vec<PathBuf>> = Arc::new(
+        paths.into_iter().map(|path| path.as_ref().to_path_buf()).collect()
+    );
+    let results = Arc::new(Mutex::new((0..paths.len()).map(|_| None).collect::<Vec<_>>()));
+    let next = Arc::new(AtomicUsize::new(0));
+    let cache = Arc::new(Cache::default());
+    let workers: Vec<_> = (0..parallelism.max(1).min(paths.len()))
+        .map(|_| {
+            let (paths, results) = (paths.clone(), results.clone());
+            let (next, cache, config) = (next.clone(), cache.clone(), config.clone());
+            thread::spawn(move || loop {
+                let index = next.fetch_add(1, Ordering::SeqCst);
+                if index >= paths.len() {
+                    break;
+                }
+                let result = import_one(&paths[index], &config, &cache);
+                results.lock().unwrap()[index] = Some(result);
+            })
+        })
+        .collect();
+    for worker in workers {
+        if let Err(payload) = worker.join() {
+            panic::resume_unwind(payload);
+        }
+    }
+    let results = Arc::try_unwrap(results).unwrap_or_else(|_| unreachable!());
+    results.into_inner().unwrap().into_iter().map(Option::unwrap).collect()
+}
+
+/// Imports a single asset and loads its images through the shared cache.
+fn import_one(path: &Path, config: &Config, cache: &Cache) -> Result<Imported, Error> {
+    let (gltf, buffers) = super::import_impl(path, config, None, &mut Metrics::default())?;
+    let images = gltf.images()
+        .map(|image| load_image(path, &image, &buffers, config, cache))
+        .collect::<Result<_, _>>()?;
+    Ok(Imported { gltf, buffers, images })
+}
+
+/// Loads an image, sharing images loaded from external files.
+fn load_image(
+    path: &Path,
+    image: &gltf::Image,
+    buffers: &Buffers,
+    config: &Config,
+    cache: &Cache,
+) -> Result<Arc<Image>, Error> {
+    let key = match image.data() {
+        gltf::image::Data::Uri { uri, .. } if !uri.starts_with("data:") => {
+            let file = super::resolve_path(path, uri, config)?;
+            Some(file.canonicalize().unwrap_or(file))
+        },
+        _ => None,
+    };
+    if let Some(ref key) = key {
+        if let Some(image) = cache.lock().unwrap().get(key) {
+            return Ok(image.clone());
+        }
+    }
+    // Loaded without holding the lock, so the same file may occasionally be
+    // loaded twice by racing threads. The first copy inserted is kept.
+    let loaded = Arc::new(image::load_from(Base::Path(path), image, buffers, config)?);
+    match key {
+        Some(key) => Ok(cache.lock().unwrap().entry(key).or_insert(loaded).clone()),
+        None => Ok(loaded),
+    }
+}
@@ -0,0 +1,80 @@
+use std::io::Read;
+
+use Error;
+
+/// The magic number of gzip streams.
+const GZIP_MAGIC: &'static [u8] = &[0x1F, 0x8B];
+
+/// The magic number of zstd frames.
+const ZSTD_MAGIC: &'static [u8] = &[0x28, 0xB5, 0x2F, 0xFD];
+
+/// A compression format wrapping a whole `.gltf` or `.glb` file.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Format {
+    /// gzip, e.g. `.glb.gz`.
+    Gzip,
+
+    /// Zstandard, e.g. `.gltf.zst`.
+    Zstd,
+}
+
+impl Format {
+    /// Detects the compression format from the first bytes of a file.
+    ///
+    /// Neither magic number can begin glTF JSON or binary glTF, so detection
+    /// never misreads an uncompressed asset.
+    pub fn detect(data: &[u8]) -> Option<Format> {
+        if data.starts_with(GZIP_MAGIC) {
+            Some(Format::Gzip)
+        } else if data.starts_with(ZSTD_MAGIC) {
+            Some(Format::Zstd)
+        } else {
+            None
+        }
+    }
+}
+
+/// Reads the whole of a compressed stream, decompressing it on the fly.
+///
+/// Only a single layer of compression is removed. Returns
+/// `Error::DecompressedSizeExceeded` as soon as more than `max_size` bytes
+/// have been decompressed, and `Error::CompressionDisabled` if the crate
+/// feature of the format is not enabled.
+pub fn decompress<R: Read>(format: Format, reader: R, max_size: u64) -> Result<Vec<u8>, Error> {
+    let mut data = vec![];
+    match format {
+        Format::Gzip => gzip(reader, max_size, &mut data)?,
+        Format::Zstd => zstd(reader, max_size, &mut data)?,
+    }
+    // The decoders read one byte past the limit so that exceeding it shows.
+    if data.len() as u64 > max_size {
+        return Err(Error::DecompressedSizeExceeded(max_size));
+    }
+    Ok(data)
+}
+
+#[cfg(feature = "gzip")]
+fn gzip<R: Read>(reader: R, max_size: u64, data: &mut Vec<u8>) -> Result<(), Error> {
+    let decoder = ::flate2::read::MultiGzDecoder::new(reader);
+    let _ = decoder.take(max_size.saturating_add(1)).read_to_end(data)?;
+    Ok(())
+}
+
+#[cfg(not(feature = "gzip"))]
+fn gzip<R: Read>(_reader: R, _max_size: u64, _data: &mut Vec<u8>) -> Result<(), Error> {
+    Err(Error::CompressionDisabled("gzip".to_string()))
+}
+
+#[cfg(feature = "zstd")]
+fn zstd<R: Read>(reader: R, max_size: u64, data: &mut Vec<u8>) -> Result<(), Error> {
+    use std::io;
+    let decoder = ::ruzstd::decoding::StreamingDecoder::new(reader)
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err.to_string()))?;
+    let _ = decoder.take(max_size.saturating_add(1)).read_to_end(data)?;
+    Ok(())
+}
+
+#[cfg(not(feature = "zstd"))]
+fn zstd<R: Read>(_reader: R, _max_size: u64, _data: &mut Vec<u8>) -> Result<(), Error> {
+    Err(Error::CompressionDisabled("zstd".to_string()))
+}
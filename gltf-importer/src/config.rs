@@ -1,3 +1,9 @@
+use std::{fmt, io};
+use std::sync::Arc;
+
+use gltf;
+use gltf::json;
+
 /// Describes the validation strategy of an `Importer`.
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum ValidationStrategy {
@@ -19,11 +25,231 @@ pub enum ValidationStrategy {
     Skip,
 }
 
+/// Identifies the data passed to a `Decoder`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Chunk {
+    /// The contents of a .gltf file or the JSON chunk of a .glb file.
+    Json,
+
+    /// The BIN chunk of a .glb file.
+    Bin,
+
+    /// The contents of the external file of the buffer with the given index.
+    Buffer(usize),
+
+    /// The contents of the external file of the image with the given index,
+    /// as loaded by `image::load`.
+    Image(usize),
+}
+
+/// A callback that decodes data as it is loaded, e.g. to decrypt assets
+/// wrapped in light encryption.
+///
+/// The decoder is applied to whole files and GLB chunks, so the GLB header and
+/// chunk headers must be left unencoded. Data URIs are not decoded since they
+/// are embedded in the already decoded JSON.
+///
+/// # Examples
+///
+/// ```
+/// use gltf_importer::config::{Config, Decoder};
+///
+/// let key = 0x5A;
+/// let config = Config {
+///     decoder: Some(Decoder::new(move |_chunk, data| {
+///         Ok(data.into_iter().map(|byte| byte ^ key).collect())
+///     })),
+///     ..Default::default()
+/// };
+/// # let _ = config;
+/// ```
+#[derive(Clone)]
+pub struct Decoder(Arc<Fn(Chunk, Vec<u8>) -> io::Result<Vec<u8>> + Send + Sync>);
+
+impl Decoder {
+    /// Constructs a `Decoder` from a callback.
+    pub fn new<F>(decode: F) -> Self
+        where F: Fn(Chunk, Vec<u8>) -> io::Result<Vec<u8>> + Send + Sync + 'static
+    {
+        Decoder(Arc::new(decode))
+    }
+
+    /// Decodes a chunk of data.
+    pub fn decode(&self, chunk: Chunk, data: Vec<u8>) -> io::Result<Vec<u8>> {
+        (self.0)(chunk, data)
+    }
+}
+
+impl fmt::Debug for Decoder {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Decoder")
+    }
+}
+
+/// A predicate selecting nodes to drop during import, e.g. editor helpers or
+/// collision proxies tagged in their `extras`.
+///
+/// Dropped nodes are removed along with their descendants and the meshes,
+/// cameras, and skins used by no other node, as by
+/// `gltf_utils::orphans::remove_nodes`.
+///
+/// # Examples
+///
+/// ```
+/// use gltf_importer::config::{Config, NodeFilter};
+///
+/// let config = Config {
+///     node_filter: Some(NodeFilter::new(|node| node.index() == 0)),
+///     ..Default::default()
+/// };
+/// # let _ = config;
+/// ```
+#[derive(Clone)]
+pub struct NodeFilter(Arc<Fn(&gltf::Node) -> bool + Send + Sync>);
+
+impl NodeFilter {
+    /// Constructs a `NodeFilter` from a predicate returning `true` for nodes
+    /// to drop.
+    pub fn new<F>(drop: F) -> Self
+        where F: Fn(&gltf::Node) -> bool + Send + Sync + 'static
+    {
+        NodeFilter(Arc::new(drop))
+    }
+
+    /// Constructs a `NodeFilter` dropping nodes tagged with any of the given
+    /// tags in their `extras`.
+    ///
+    /// A node is tagged either by a key of its `extras` object set to `true`,
+    /// e.g. `{ "editor_only": true }`, or by a `"tags"` array containing the
+    /// tag, e.g. `{ "tags": ["editor_only"] }`.
+    #[cfg(feature = "extras")]
+    pub fn tags<I, S>(tags: I) -> Self
+        where I: IntoIterator<Item = S>, S: Into<String>
+    {
+        let tags: Vec<String> = tags.into_iter().map(Into::into).collect();
+        NodeFilter::new(move |node| {
+            let extras = match *node.extras() {
+                Some(json::Value::Object(ref extras)) => extras,
+                _ => return false,
+            };
+            let listed: Vec<&str> = match extras.get("tags") {
+                Some(&json::Value::Array(ref values)) => {
+                    values.iter().filter_map(json::Value::as_str).collect()
+                },
+                _ => vec![],
+            };
+            tags.iter().any(|tag| {
+                extras.get(tag) == Some(&json::Value::Bool(true)) || listed.contains(&tag.as_str())
+            })
+        })
+    }
+
+    /// Returns `true` if the node should be dropped.
+    pub fn matches(&self, node: &gltf::Node) -> bool {
+        (self.0)(node)
+    }
+}
+
+impl fmt::Debug for NodeFilter {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "NodeFilter")
+    }
+}
+
+/// The default of `Config::max_decompressed_size`, 1 GiB.
+pub const DEFAULT_MAX_DECOMPRESSED_SIZE: u64 = 1 << 30;
+
 /// A complete import configuration.
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Debug)]
 pub struct Config {
     /// Specifies how imported glTF should be validated.
     pub validation_strategy: ValidationStrategy,
+
+    /// Infer the MIME type of images from their magic bytes instead of
+    /// trusting the declared MIME type.
+    ///
+    /// Many assets in the wild declare PNG images as JPEG or vice versa. This
+    /// only affects `image::load`.
+    pub lenient_mime_types: bool,
+
+    /// Verify buffers against checksums declared with the
+    /// `GLTFRS_buffer_checksum` extension.
+    ///
+    /// Buffers without a checksum are not checked. Partial imports of a single
+    /// scene are never checked.
+    pub verify_checksums: bool,
+
+    /// Reject external files outside the directory of the asset.
+    ///
+    /// URIs that are absolute, that escape the directory through `..`
+    /// segments, or that lead outside it through symbolic links fail with
+    /// `Error::PathOutsideBase`. Enable this when importing untrusted assets.
+    /// Files within archives are always confined to the archive.
+    pub confine_paths: bool,
+
+    /// Truncate buffers and buffer views that extend past the loaded buffer
+    /// data instead of failing with `Error::BufferLength`.
+    ///
+    /// Exporters commonly declare a `byteLength` a few bytes larger than the
+    /// data they write, e.g. by counting the padding of the last buffer view
+    /// or of the GLB BIN chunk. The affected buffers and views are shrunk to
+    /// fit the data, each truncation is recorded in `Metrics::truncated`, and
+    /// with the `tracing` feature a warning is emitted. The shrunk asset is
+    /// validated again, and imports still fail with `Error::BufferLength` if a
+    /// view starts past the end of the data or an accessor no longer fits its
+    /// shrunk view.
+    pub truncate_views: bool,
+
+    /// The material of primitives without one, replacing the default material
+    /// of the specification.
+    ///
+    /// Textures referenced by the material must exist in every imported
+    /// asset, otherwise the import fails with `Error::Validation`.
+    pub default_material: Option<json::Material>,
+
+    /// Normalize object names to Unicode Normalization Form C.
+    ///
+    /// Names exported on macOS are commonly decomposed while names exported on
+    /// Windows are precomposed, which breaks matching objects by name. URIs
+    /// are kept as written, so that external files are still found on file
+    /// systems comparing names byte by byte. See `json::naming::normalize_nfc`.
+    #[cfg(feature = "nfc")]
+    pub normalize_nfc: bool,
+
+    /// Drops the nodes it matches, and the objects only they use, right after
+    /// validation.
+    pub node_filter: Option<NodeFilter>,
+
+    /// Decodes the asset and its external files as they are loaded.
+    ///
+    /// When set, external buffers are always read whole, including for partial
+    /// imports of a single scene.
+    pub decoder: Option<Decoder>,
+
+    /// The maximum size in bytes of a gzip or zstd wrapped asset after
+    /// decompression, `DEFAULT_MAX_DECOMPRESSED_SIZE` by default.
+    ///
+    /// Larger assets fail with `Error::DecompressedSizeExceeded` without being
+    /// decompressed any further, which guards against decompression bombs.
+    pub max_decompressed_size: u64,
+}
+
+impl Default for Config {
+    fn default() -> Config {
+        Config {
+            validation_strategy: Default::default(),
+            lenient_mime_types: false,
+            verify_checksums: false,
+            confine_paths: false,
+            truncate_views: false,
+            default_material: None,
+            #[cfg(feature = "nfc")]
+            normalize_nfc: false,
+            node_filter: None,
+            decoder: None,
+            max_decompressed_size: DEFAULT_MAX_DECOMPRESSED_SIZE,
+        }
+    }
 }
 
 impl Default for ValidationStrategy {
@@ -0,0 +1,144 @@
+use gltf;
+use gltf::json;
+use std::path::Path;
+
+use config::Chunk;
+use {Base, Buffers, Config, Error};
+
+/// The MIME type of PNG images.
+pub const PNG: &'static str = "image/png";
+
+/// The MIME type of JPEG images.
+pub const JPEG: &'static str = "image/jpeg";
+
+/// Image data loaded by `load`.
+#[derive(Clone, Debug)]
+pub struct Image {
+    /// The encoded image data.
+    pub data: Vec<u8>,
+
+    /// The MIME type of the encoded image data.
+    pub mime_type: String,
+}
+
+/// Infers the MIME type of encoded image data from its magic bytes.
+///
+/// Recognizes PNG and JPEG data.
+pub fn sniff(data: &[u8]) -> Option<&'static str> {
+    if data.starts_with(b"\x89PNG\r\n\x1a\n") {
+        Some(PNG)
+    } else if data.starts_with(&[0xFF, 0xD8, 0xFF]) {
+        Some(JPEG)
+    } else {
+        None
+    }
+}
+
+/// Infers the MIME type of an image from the file extension of its URI.
+pub fn from_extension(uri: &str) -> Option<&'static str> {
+    let extension = uri.rsplit('.').next().unwrap_or("").to_lowercase();
+    match extension.as_str() {
+        "png" => Some(PNG),
+        "jpg" | "jpeg" => Some(JPEG),
+        _ => None,
+    }
+}
+
+/// Returns the media type of a data URI, e.g. `"image/png"` for
+/// `"data:image/png;base64,..."`.
+fn data_uri_media_type(uri: &str) -> Option<&str> {
+    let header = uri["data:".len()..].split(',').next().unwrap_or("");
+    match header.split(';').next() {
+        Some("") | None => None,
+        Some(media_type) => Some(media_type),
+    }
+}
+
+/// Loads the encoded data of an image and determines its MIME type.
+///
+/// `path` is the path of the imported asset, against which relative image URIs
+/// are resolved, and `buffers` is the buffer data returned from the import.
+///
+/// The declared MIME type is taken from the `mimeType` property, the media type
+/// of a data URI, or the file extension of an external URI, in that order. If
+/// `Config::lenient_mime_types` is set, the MIME type is instead inferred from
+/// the magic bytes of the image data where possible, falling back to the
+/// declared MIME type for unrecognized data.
+///
+/// Returns `Error::UnknownMimeType` if no MIME type can be determined, and
+/// `Error::BufferNotLoaded` if the image is stored in a buffer whose data is
+/// missing from `buffers`.
+pub fn load<P>(
+    path: P,
+    image: &gltf::Image,
+    buffers: &Buffers,
+    config: &Config,
+) -> Result<Image, Error>
+    where P: AsRef<Path>
+{
+    load_from(Base::Path(path.as_ref()), image, buffers, config)
+}
+
+/// Loads the encoded data of an image, resolving relative URIs against the
+/// given base.
+pub(crate) fn load_from(
+    base: Base,
+    image: &gltf::Image,
+    buffers: &Buffers,
+    config: &Config,
+) -> Result<Image, Error> {
+    let (data, declared) = match image.data() {
+        gltf::image::Data::View { view, mime_type } => {
+            let data = match buffers.view(&view) {
+                Some(data) => data.to_vec(),
+                None => {
+                    let path = json::Path::new()
+                        .field("bufferViews")
+                        .index(view.index())
+                        .field("buffer");
+                    return Err(Error::BufferNotLoaded(path));
+                },
+            };
+            (data, Some(mime_type.to_string()))
+        },
+        gltf::image::Data::Uri { uri, mime_type } => {
+            if uri.starts_with("data:") {
+                let declared = mime_type.or_else(|| data_uri_media_type(uri));
+                (super::parse_data_uri(uri)?, declared.map(str::to_string))
+            } else {
+                let declared = mime_type.or_else(|| from_extension(uri));
+                let data = base.read(uri, config)?;
+                let data = super::decode(config, Chunk::Image(image.index()), data)?;
+                (data, declared.map(str::to_string))
+            }
+        },
+    };
+    let mime_type = if config.lenient_mime_types {
+        sniff(&data).map(str::to_string).or(declared)
+    } else {
+        declared
+    };
+    match mime_type {
+        Some(mime_type) => Ok(Image { data, mime_type }),
+        None => {
+            let path = json::Path::new().field("images").index(image.index());
+            Err(Error::UnknownMimeType(path))
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn infer() {
+        assert_eq!(Some(PNG), sniff(b"\x89PNG\r\n\x1a\n\0\0\0\x0dIHDR"));
+        assert_eq!(Some(JPEG), sniff(&[0xFF, 0xD8, 0xFF, 0xE0]));
+        assert_eq!(None, sniff(b"GIF89a"));
+        assert_eq!(Some(JPEG), from_extension("textures/albedo.JPG"));
+        assert_eq!(None, from_extension("textures/albedo"));
+        assert_eq!(Some("image/png"), data_uri_media_type("data:image/png;base64,AA=="));
+        assert_eq!(None, data_uri_media_type("data:;base64,AA=="));
+    }
+}
@@ -14,24 +14,75 @@
 //!     Err(err) => println!("error: {:?}", err),
 //! }
 //! ```
+//!
+//! ### Compressed assets
+//!
+//! With the `gzip` and `zstd` features, `.gltf` and `.glb` files wrapped in
+//! gzip or Zstandard, e.g. `.glb.gz`, are detected by their magic number and
+//! decompressed while being read. External buffers and images are read as is.
+//!
+//! ### Profiling
+//!
+//! `import_with_metrics` returns the time spent on each stage of an import.
+//! With the `tracing` feature, every import is also instrumented with an
+//! `INFO` span named `import` and `DEBUG` spans for reading, decompression,
+//! parsing, validation, decoding, and each external buffer, ready for
+//! flamegraphs of load profiles.
+
+#![forbid(unsafe_code)]
 
 extern crate base64;
+#[cfg(feature = "gzip")]
+extern crate flate2;
 extern crate gltf;
 extern crate gltf_utils;
+#[cfg(feature = "zstd")]
+extern crate ruzstd;
+#[cfg(feature = "tracing")]
+extern crate tracing;
+#[cfg(feature = "zip")]
+extern crate zip;
 
 use gltf::json::{self, validation};
 use std::{fmt, fs, io, path};
 
 use gltf::Gltf;
 use gltf_utils::Source;
+use std::borrow::Cow;
 use std::error::Error as StdError;
 use std::path::Path;
+use std::time::Instant;
+
+/// Importing of assets distributed as zip archives.
+#[cfg(feature = "zip")]
+pub mod archive;
+
+/// Concurrent importing of many assets with shared images.
+pub mod batch;
+
+/// Detection and decompression of gzip and zstd wrapped assets.
+mod compression;
 
 /// Contains parameters for import configuration.
 pub mod config;
 
+/// Loading of image data with MIME type inference.
+pub mod image;
+
+/// Timing and size metrics of imports.
+#[macro_use]
+pub mod metrics;
+
+/// Computes the buffer data referenced by a subset of an asset.
+mod selection;
+
+#[cfg(feature = "zip")]
+pub use self::archive::Archive;
+pub use self::batch::{import_all, import_all_with_config, Imported};
 pub use self::config::Config;
+use self::config::Chunk;
 pub use self::config::ValidationStrategy;
+pub use self::metrics::Metrics;
 
 /// Error encountered when importing a glTF 2.0 asset.
 #[derive(Debug)]
@@ -39,9 +90,25 @@ pub enum Error {
     /// A loaded glTF buffer is not of the required length.
     BufferLength(json::Path),
 
+    /// The data of a buffer was not loaded, e.g. because `import_scene`
+    /// skipped it, but is required by the buffer view at the given path.
+    BufferNotLoaded(json::Path),
+
     /// Base 64 decoding error.
     Base64Decoding(base64::DecodeError),
 
+    /// A loaded glTF buffer does not match its declared checksum.
+    ChecksumMismatch(json::Path),
+
+    /// The asset is compressed with a format whose crate feature, `gzip` or
+    /// `zstd`, has not been enabled.
+    CompressionDisabled(String),
+
+    /// A compressed asset decompresses to more than
+    /// `Config::max_decompressed_size` bytes, or the files of a zip archive to
+    /// more than the limit of `Archive::from_reader_with_max_size`.
+    DecompressedSizeExceeded(u64),
+
     /// A glTF extension required by the asset has not been enabled by the user.
     ExtensionDisabled(String),
 
@@ -63,70 +130,279 @@ pub enum Error {
     /// Failure when deserializing .gltf or .glb JSON.
     MalformedJson(json::Error),
 
+    /// An external file lies outside the directory of the asset while
+    /// `Config::confine_paths` is set.
+    PathOutsideBase(path::PathBuf),
+
+    /// The requested scene does not exist.
+    SceneNotFound(usize),
+
+    /// The MIME type of an image could not be determined.
+    UnknownMimeType(json::Path),
+
     /// The .gltf data is invalid.
     Validation(Vec<(json::Path, validation::Error)>),
 }
 
-/// Buffer data returned from `import`.
+/// Buffer data returned from `import`, along with the JSON text the asset was
+/// parsed from.
 #[derive(Clone, Debug)]
-pub struct Buffers(Vec<Vec<u8>>);
+pub struct Buffers {
+    /// The data of each buffer, in buffer order.
+    data: Vec<Vec<u8>>,
+
+    /// The JSON text of the asset.
+    json: Vec<u8>,
+
+    /// Whether buffer 0 holds the `BIN` chunk of binary glTF.
+    bin: bool,
+}
 
 impl Source for Buffers {
     fn source_buffer(&self, buffer: &gltf::Buffer) -> &[u8] {
-        &self.0[buffer.index()]
+        &self.data[buffer.index()]
     }
 }
 
 impl Buffers {
     /// Obtain the contents of a loaded buffer.
     pub fn buffer(&self, buffer: &gltf::Buffer) -> Option<&[u8]> {
-        self.0.get(buffer.index()).map(Vec::as_slice)
+        self.data.get(buffer.index()).map(Vec::as_slice)
+    }
+
+    /// Returns the JSON text the asset was parsed from, i.e. the `.gltf` file
+    /// or the JSON chunk of binary glTF, including any chunk padding.
+    ///
+    /// The text is returned as read, after decompression and decoding, so it
+    /// can be hashed or saved again without serializing the `Gltf`. Changes
+    /// made while importing, e.g. by `Config::truncate_views` or the removal
+    /// of nodes, are not reflected.
+    pub fn json(&self) -> &[u8] {
+        &self.json
+    }
+
+    /// Returns the `BIN` chunk of binary glTF, or `None` for `.gltf` files and
+    /// binary glTF without one.
+    ///
+    /// The chunk is the data of buffer 0, including any chunk padding. Like
+    /// `json`, it is returned after decoding by `Config::decoder`, so it
+    /// differs from the chunk stored in the file if a decoder is set.
+    pub fn bin(&self) -> Option<&[u8]> {
+        if self.bin {
+            self.data.first().map(Vec::as_slice)
+        } else {
+            None
+        }
     }
 
     /// Obtain the contents of a loaded buffer view.
+    ///
+    /// Returns `None` if the data of its buffer was not loaded, e.g. because
+    /// `import_scene` skipped it.
     pub fn view(&self, view: &gltf::buffer::View) -> Option<&[u8]> {
         self.buffer(&view.buffer())
-            .map(|data| {
+            .and_then(|data| {
                 let begin = view.offset();
-                let end = begin + view.length();
-                &data[begin..end]
+                let end = begin.checked_add(view.length())?;
+                data.get(begin..end)
             })
     }
 
     /// Take the loaded buffer data.
     pub fn take(self) -> Vec<Vec<u8>> {
-        self.0
+        self.data
+    }
+}
+
+/// The location against which relative URIs are resolved.
+#[derive(Clone, Copy)]
+enum Base<'a> {
+    /// The path of an asset file.
+    Path(&'a Path),
+
+    /// The path of an asset within an archive.
+    #[cfg(feature = "zip")]
+    Archive(&'a Archive, &'a str),
+}
+
+impl<'a> Base<'a> {
+    /// Reads the whole file referenced by a relative URI.
+    fn read(&self, uri: &str, config: &Config) -> Result<Vec<u8>, Error> {
+        match *self {
+            Base::Path(path) => read_to_end(resolve_path(path, uri, config)?),
+            #[cfg(feature = "zip")]
+            Base::Archive(archive, asset) => archive.read(asset, uri),
+        }
+    }
+}
+
+/// Resolves a relative URI against the path of an asset file.
+///
+/// With `Config::confine_paths`, URIs that are absolute or that escape the
+/// directory of the asset through `..` segments or symbolic links are
+/// rejected, and the canonical path that was checked is returned, so that a
+/// symbolic link replacing the file after the check is not followed.
+fn resolve_path(asset: &Path, uri: &str, config: &Config) -> Result<path::PathBuf, Error> {
+    let base = match asset.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("./"),
+    };
+    let path = base.join(uri);
+    if config.confine_paths {
+        let mut depth = 0;
+        for component in Path::new(uri).components() {
+            match component {
+                path::Component::Normal(_) => depth += 1,
+                path::Component::CurDir => {},
+                path::Component::ParentDir if depth > 0 => depth -= 1,
+                _ => return Err(Error::PathOutsideBase(path)),
+            }
+        }
+        let base = base.canonicalize()?;
+        let target = match path.canonicalize() {
+            Ok(target) => target,
+            Err(ref err) if err.kind() == io::ErrorKind::NotFound => {
+                return Err(Error::FileNotFound(path));
+            },
+            Err(err) => return Err(err.into()),
+        };
+        if !target.starts_with(&base) {
+            return Err(Error::PathOutsideBase(path));
+        }
+        return Ok(target);
+    }
+    Ok(path)
+}
+
+fn import_impl(
+    path: &Path,
+    config: &Config,
+    scene: Option<usize>,
+    metrics: &mut Metrics,
+) -> Result<(Gltf, Buffers), Error> {
+    #[cfg(feature = "tracing")]
+    let _span = ::tracing::info_span!("import", path = %path.display()).entered();
+    let start = Instant::now();
+    let data = time!(metrics.read, read_asset(path, config.max_decompressed_size))?;
+    metrics.bytes_read += fs::metadata(path)?.len();
+    let result = import_uncompressed(Cow::Owned(data), Base::Path(path), config, scene, metrics);
+    metrics.total = start.elapsed();
+    result
+}
+
+fn import_data_impl(
+    data: &[u8],
+    base: Base,
+    config: &Config,
+    scene: Option<usize>,
+    metrics: &mut Metrics,
+) -> Result<(Gltf, Buffers), Error> {
+    if let Some(format) = compression::Format::detect(data) {
+        let max_size = config.max_decompressed_size;
+        let data = time!(metrics.decompress, compression::decompress(format, data, max_size))?;
+        import_uncompressed(Cow::Owned(data), base, config, scene, metrics)
+    } else {
+        import_uncompressed(Cow::Borrowed(data), base, config, scene, metrics)
     }
 }
 
-fn import_impl(path: &Path, config: &Config) -> Result<(Gltf, Buffers), Error> {
-    let data = read_to_end(path)?;
-    import_data_slice(&data, path, config)
+/// Imports decompressed data, taking ownership of it when possible since the
+/// text of a `.gltf` file is kept in the returned `Buffers`.
+fn import_uncompressed(
+    data: Cow<[u8]>,
+    base: Base,
+    config: &Config,
+    scene: Option<usize>,
+    metrics: &mut Metrics,
+) -> Result<(Gltf, Buffers), Error> {
+    if data.starts_with(b"glTF") {
+        import_binary(&data, config, base, scene, metrics)
+    } else {
+        import_standard(data.into_owned(), config, base, scene, metrics)
+    }
 }
 
 /// Imports glTF 2.0 from a slice with custom configuration.
 pub fn import_data_slice<'a, P>(data: &'a [u8], path: P, config: &Config) -> Result<(Gltf, Buffers), Error>
     where P: AsRef<Path>
 {
-    if data.starts_with(b"glTF") {
-        import_binary(&data, config, path.as_ref())
-    } else {
-        import_standard(&data, config, path.as_ref())
-    }
+    import_data_impl(data, Base::Path(path.as_ref()), config, None, &mut Metrics::default())
 }
 
 /// Imports glTF 2.0 with custom configuration.
 pub fn import_with_config<P>(path: P, config: &Config) -> Result<(Gltf, Buffers), Error>
     where P: AsRef<Path>
 {
-    import_impl(path.as_ref(), config)
+    import_impl(path.as_ref(), config, None, &mut Metrics::default())
+}
+
+/// Imports glTF 2.0 with custom configuration, also returning the time spent
+/// on each stage of the import and the number of bytes read.
+///
+/// Images are not loaded by the importer, so loading them is not measured.
+///
+/// # Examples
+///
+/// ```rust
+/// use gltf_importer::import_with_metrics;
+///
+/// let (_, _, metrics) = import_with_metrics("../examples/Box.gltf", &Default::default())
+///     .expect("import");
+/// assert_eq!(1, metrics.resources.len());
+/// println!("{}", metrics);
+/// ```
+pub fn import_with_metrics<P>(path: P, config: &Config) -> Result<(Gltf, Buffers, Metrics), Error>
+    where P: AsRef<Path>
+{
+    let mut metrics = Metrics::default();
+    let (gltf, buffers) = import_impl(path.as_ref(), config, None, &mut metrics)?;
+    Ok((gltf, buffers, metrics))
 }
 
 /// Imports glTF 2.0 with default configuration.
 pub fn import<P>(path: P) -> Result<(Gltf, Buffers), Error>
     where P: AsRef<Path>
 {
-    import_impl(path.as_ref(), &Default::default())
+    import_impl(path.as_ref(), &Default::default(), None, &mut Metrics::default())
+}
+
+/// Imports glTF 2.0 from a zip archive with default configuration.
+///
+/// See `Archive` for how the asset is located within the archive. Use
+/// `Archive` directly to load images or to import with custom configuration.
+#[cfg(feature = "zip")]
+pub fn import_zip<P>(path: P) -> Result<(Gltf, Buffers), Error>
+    where P: AsRef<Path>
+{
+    Archive::open(path)?.import(&Default::default())
+}
+
+/// Imports a single scene of a glTF 2.0 asset with custom configuration.
+///
+/// Only the buffer data referenced by the scene is loaded: external buffers
+/// that are not referenced are left empty and only the referenced region of
+/// each external buffer file is read. Data outside the referenced regions
+/// reads as zero.
+///
+/// The returned `Gltf` still describes the whole asset, so objects outside the
+/// selected scene must not be read from the returned buffers.
+pub fn import_scene_with_config<P>(
+    path: P,
+    scene: usize,
+    config: &Config,
+) -> Result<(Gltf, Buffers), Error>
+    where P: AsRef<Path>
+{
+    import_impl(path.as_ref(), config, Some(scene), &mut Metrics::default())
+}
+
+/// Imports a single scene of a glTF 2.0 asset with default configuration.
+///
+/// See `import_scene_with_config` for details.
+pub fn import_scene<P>(path: P, scene: usize) -> Result<(Gltf, Buffers), Error>
+    where P: AsRef<Path>
+{
+    import_impl(path.as_ref(), &Default::default(), Some(scene), &mut Metrics::default())
 }
 
 fn read_to_end_impl(path: &Path) -> Result<Vec<u8>, Error> {
@@ -146,29 +422,119 @@ fn read_to_end<P: AsRef<Path>>(path: P) -> Result<Vec<u8>, Error> {
     read_to_end_impl(path.as_ref())
 }
 
+/// Reads an asset file, decompressing gzip or zstd wrapped files while reading.
+///
+/// Only the asset itself is checked for compression, since the data of
+/// external buffers may begin with any bytes.
+fn read_asset(path: &Path, max_size: u64) -> Result<Vec<u8>, Error> {
+    use io::{BufRead, Read};
+    if !path.exists() {
+        return Err(Error::FileNotFound(path.to_path_buf()));
+    }
+    let mut reader = io::BufReader::new(fs::File::open(path)?);
+    let format = compression::Format::detect(reader.fill_buf()?);
+    match format {
+        Some(format) => compression::decompress(format, reader, max_size),
+        None => {
+            let mut data = vec![];
+            let _ = reader.read_to_end(&mut data)?;
+            Ok(data)
+        },
+    }
+}
+
+/// Applies the configured decoder, if any, to loaded data.
+fn decode(config: &Config, chunk: Chunk, data: Vec<u8>) -> Result<Vec<u8>, Error> {
+    match config.decoder {
+        Some(ref decoder) => Ok(decoder.decode(chunk, data)?),
+        None => Ok(data),
+    }
+}
+
 fn parse_data_uri(uri: &str) -> Result<Vec<u8>, Error> {
     let encoded = uri.split(',').nth(1).unwrap();
     let decoded = base64::decode(&encoded)?;
     Ok(decoded)
 }
 
+/// Reads the byte range `(begin, end)` of a buffer file of the given length,
+/// leaving the remaining bytes zeroed.
+fn read_range(
+    path: &Path,
+    length: usize,
+    range: selection::Range,
+) -> Result<Vec<u8>, Error> {
+    use io::{Read, Seek};
+    if !path.exists() {
+        return Err(Error::FileNotFound(path.to_path_buf()));
+    }
+    let mut file = fs::File::open(path)?;
+    let file_length = file.metadata()?.len() as usize;
+    let mut data = vec![0; file_length.min(length)];
+    if range.1 <= data.len() {
+        let _ = file.seek(io::SeekFrom::Start(range.0 as u64))?;
+        file.read_exact(&mut data[range.0..range.1])?;
+    }
+    Ok(data)
+}
+
+/// Verifies every loaded buffer that declares a checksum.
+fn verify_checksums(gltf: &Gltf, buffers: &[Vec<u8>]) -> Result<(), Error> {
+    for (buffer, data) in gltf.buffers().zip(buffers) {
+        if buffer.verify(data) == Some(false) {
+            let path = json::Path::new().field("buffers").index(buffer.index());
+            return Err(Error::ChecksumMismatch(path));
+        }
+    }
+    Ok(())
+}
+
 fn load_external_buffers(
-    base_path: &Path,
+    base: Base,
     gltf: &Gltf,
     mut bin: Option<Vec<u8>>,
+    ranges: Option<&[Option<selection::Range>]>,
+    (config, metrics): (&Config, &mut Metrics),
 ) -> Result<Vec<Vec<u8>>, Error> {
     let mut buffers = vec![];
     for (index, buffer) in gltf.buffers().enumerate() {
         let uri = buffer.uri();
+        let range = ranges.map(|ranges| ranges[index]);
         let data = if uri == "#bin" {
             Ok(bin.take().unwrap())
         } else if uri.starts_with("data:") {
-            Ok(parse_data_uri(uri)?)
+            Ok(time!(metrics.decode, parse_data_uri(uri))?)
+        } else if range == Some(None) {
+            // Not referenced by the selection.
+            buffers.push(vec![]);
+            continue;
         } else {
-            let path = base_path.parent().unwrap_or_else(|| Path::new("./")).join(uri);
-            read_to_end(&path)
+            #[cfg(feature = "tracing")]
+            let _span = ::tracing::debug_span!("read_buffer", uri).entered();
+            let start = Instant::now();
+            let (data, bytes) = match (base, range) {
+                (Base::Path(path), Some(Some(range))) if config.decoder.is_none() => {
+                    let path = resolve_path(path, uri, config)?;
+                    let data = read_range(&path, buffer.length(), range)?;
+                    (data, (range.1 - range.0) as u64)
+                },
+                _ => {
+                    let data = base.read(uri, config)?;
+                    let length = data.len() as u64;
+                    (data, length)
+                },
+            };
+            metrics.bytes_read += bytes;
+            metrics.resources.push(metrics::Resource {
+                uri: uri.to_string(),
+                read: start.elapsed(),
+                bytes,
+            });
+            #[cfg(feature = "tracing")]
+            ::tracing::debug!(bytes, "read buffer");
+            time!(metrics.decode, decode(config, Chunk::Buffer(index), data))
         }?;
-        if data.len() < buffer.length() {
+        if data.len() < buffer.length() && !config.truncate_views {
             let path = json::Path::new().field("buffers").index(index);
             return Err(Error::BufferLength(path));
         }
@@ -177,6 +543,107 @@ fn load_external_buffers(
     Ok(buffers)
 }
 
+/// Removes the nodes matched by `Config::node_filter`.
+fn filter_nodes(gltf: Gltf, config: &Config) -> Gltf {
+    match config.node_filter {
+        Some(ref filter) => {
+            let nodes: Vec<usize> = gltf.nodes()
+                .filter(|node| filter.matches(node))
+                .map(|node| node.index())
+                .collect();
+            gltf_utils::orphans::remove_nodes(gltf, &nodes).0
+        },
+        None => gltf,
+    }
+}
+
+/// Normalizes names to NFC if `Config::normalize_nfc` is set.
+#[cfg(feature = "nfc")]
+fn normalize_nfc(gltf: Gltf, config: &Config) -> Gltf {
+    if !config.normalize_nfc {
+        return gltf;
+    }
+    let mut root = gltf.into_json();
+    json::naming::normalize_nfc(&mut root);
+    Gltf::from_json(root).skip_validation()
+}
+
+#[cfg(not(feature = "nfc"))]
+fn normalize_nfc(gltf: Gltf, _: &Config) -> Gltf {
+    gltf
+}
+
+/// Shrinks buffers and buffer views that extend past the loaded buffer data.
+///
+/// Buffers not loaded by a partial import are left as is. Views starting past
+/// the end of the data cannot be shrunk and fail with `Error::BufferLength`.
+/// The shrunk asset is validated again by `validate`, and accessors no longer
+/// fitting their shrunk view fail with `Error::BufferLength`.
+fn truncate_views<F>(
+    gltf: Gltf,
+    buffers: &[Vec<u8>],
+    ranges: Option<&[Option<selection::Range>]>,
+    validate: F,
+    metrics: &mut Metrics,
+) -> Result<Gltf, Error>
+    where F: FnOnce(gltf::Unvalidated) -> Result<Gltf, Error>
+{
+    use gltf_utils::AccessorIterators;
+
+    let loaded = |index: usize| ranges.map_or(true, |ranges| ranges[index].is_some());
+    let fits = gltf.buffers().all(|buffer| {
+        !loaded(buffer.index()) || buffers[buffer.index()].len() >= buffer.length()
+    }) && gltf.views().all(|view| {
+        let buffer = view.buffer().index();
+        !loaded(buffer) || view.offset().saturating_add(view.length()) <= buffers[buffer].len()
+    });
+    if fits {
+        return Ok(gltf);
+    }
+
+    let mut root = gltf.into_json();
+    for (index, buffer) in root.buffers.iter_mut().enumerate() {
+        let length = buffers[index].len() as u32;
+        if loaded(index) && buffer.byte_length > length {
+            #[cfg(feature = "tracing")]
+            ::tracing::warn!(index, declared = buffer.byte_length, length, "truncated buffer");
+            buffer.byte_length = length;
+            metrics.truncated.push(json::Path::new().field("buffers").index(index));
+        }
+    }
+    let mut truncated = vec![];
+    for (index, view) in root.buffer_views.iter_mut().enumerate() {
+        let buffer = view.buffer.value();
+        let length = buffers[buffer].len() as u32;
+        if !loaded(buffer) || view.byte_offset.saturating_add(view.byte_length) <= length {
+            continue;
+        }
+        let path = json::Path::new().field("bufferViews").index(index);
+        if view.byte_offset >= length {
+            return Err(Error::BufferLength(path));
+        }
+        #[cfg(feature = "tracing")]
+        ::tracing::warn!(index, declared = view.byte_length, "truncated buffer view");
+        view.byte_length = length - view.byte_offset;
+        metrics.truncated.push(path);
+        truncated.push(index);
+    }
+
+    let unvalidated = Gltf::from_json(root);
+    let gltf = time!(metrics.validation, validate(unvalidated))?;
+    let source = gltf_utils::FnSource::new(|buffer: gltf::Buffer| {
+        buffers.get(buffer.index()).map(Vec::as_slice)
+    });
+    for accessor in gltf.accessors() {
+        let shrunk = accessor.view().is_some_and(|view| truncated.contains(&view.index()));
+        if shrunk && accessor.check_bounds(&source).is_err() {
+            let path = json::Path::new().field("accessors").index(accessor.index());
+            return Err(Error::BufferLength(path));
+        }
+    }
+    Ok(gltf)
+}
+
 fn validate_standard(
     unvalidated: gltf::Unvalidated,
     config: &Config,
@@ -234,35 +701,92 @@ fn validate_binary(
     }
 }
 
-fn import_standard<'a>(
-    data: &'a [u8],
+/// Returns the referenced byte range of each buffer for the given scene.
+fn scene_ranges(
+    gltf: &Gltf,
+    scene: Option<usize>,
+) -> Result<Option<Vec<Option<selection::Range>>>, Error> {
+    match scene {
+        Some(index) => {
+            let scene = gltf.scenes().nth(index).ok_or(Error::SceneNotFound(index))?;
+            Ok(Some(selection::scene(gltf, &scene)))
+        },
+        None => Ok(None),
+    }
+}
+
+fn import_standard(
+    data: Vec<u8>,
     config: &Config,
-    base_path: &Path,
+    base: Base,
+    scene: Option<usize>,
+    metrics: &mut Metrics,
 ) -> Result<(Gltf, Buffers), Error> {
-    let unvalidated = Gltf::from_slice(data)?;
-    let gltf = validate_standard(unvalidated, config)?;
+    let json = if config.decoder.is_some() {
+        time!(metrics.decode, decode(config, Chunk::Json, data))?
+    } else {
+        data
+    };
+    let unvalidated = time!(metrics.parse, Gltf::from_slice(&json))?;
+    let gltf = time!(metrics.validation, validate_standard(unvalidated, config))?;
+    let gltf = filter_nodes(gltf, config);
     let bin = None;
-    let mut buffers = Buffers(vec![]);
-    for buffer in load_external_buffers(base_path, &gltf, bin)? {
-        buffers.0.push(buffer);
+    let ranges = scene_ranges(&gltf, scene)?;
+    let ranges_slice = ranges.as_ref().map(Vec::as_slice);
+    let buffers = load_external_buffers(base, &gltf, bin, ranges_slice, (config, metrics))?;
+    let gltf = if config.truncate_views {
+        let validate = |gltf| validate_standard(gltf, config);
+        truncate_views(gltf, &buffers, ranges_slice, validate, metrics)?
+    } else {
+        gltf
+    };
+    let mut gltf = normalize_nfc(gltf, config);
+    if let Some(ref material) = config.default_material {
+        gltf.set_default_material(material.clone())?;
     }
-    Ok((gltf, buffers))
+    if config.verify_checksums && ranges.is_none() {
+        time!(metrics.validation, verify_checksums(&gltf, &buffers))?;
+    }
+    Ok((gltf, Buffers { data: buffers, json, bin: false }))
 }
 
 fn import_binary<'a>(
     data: &'a [u8],
     config: &Config,
-    base_path: &Path,
+    base: Base,
+    scene: Option<usize>,
+    metrics: &mut Metrics,
 ) -> Result<(Gltf, Buffers), Error> {
-    let gltf::Glb { json, bin, .. } = gltf::Glb::from_slice(data)?;
-    let unvalidated = Gltf::from_slice(&json)?;
-    let bin = bin.map(|x| x.into_owned());
-    let gltf = validate_binary(unvalidated, config, bin.is_some())?;
-    let mut buffers = Buffers(vec![]);
-    for buffer in load_external_buffers(base_path, &gltf, bin)? {
-        buffers.0.push(buffer);
+    let gltf::Glb { json, bin, .. } = time!(metrics.parse, gltf::Glb::from_slice(data))?;
+    let json = time!(metrics.decode, decode(config, Chunk::Json, json.into_owned()))?;
+    let unvalidated = time!(metrics.parse, Gltf::from_slice(&json))?;
+    let bin = match bin {
+        Some(bin) => Some(time!(metrics.decode, decode(config, Chunk::Bin, bin.into_owned()))?),
+        None => None,
+    };
+    let has_bin = bin.is_some();
+    let gltf = time!(metrics.validation, validate_binary(unvalidated, config, has_bin))?;
+    let bin_buffer = has_bin && gltf.buffers().next().map_or(false, |buffer| {
+        buffer.uri() == "#bin"
+    });
+    let gltf = filter_nodes(gltf, config);
+    let ranges = scene_ranges(&gltf, scene)?;
+    let ranges_slice = ranges.as_ref().map(Vec::as_slice);
+    let buffers = load_external_buffers(base, &gltf, bin, ranges_slice, (config, metrics))?;
+    let gltf = if config.truncate_views {
+        let validate = |gltf| validate_binary(gltf, config, has_bin);
+        truncate_views(gltf, &buffers, ranges_slice, validate, metrics)?
+    } else {
+        gltf
+    };
+    let mut gltf = normalize_nfc(gltf, config);
+    if let Some(ref material) = config.default_material {
+        gltf.set_default_material(material.clone())?;
+    }
+    if config.verify_checksums && ranges.is_none() {
+        time!(metrics.validation, verify_checksums(&gltf, &buffers))?;
     }
-    Ok((gltf, buffers))
+    Ok((gltf, Buffers { data: buffers, json, bin: bin_buffer }))
 }
 
 impl From<json::Error> for Error {
@@ -310,7 +834,11 @@ impl StdError for Error {
         use self::Error::*;
         match *self {
             Base64Decoding(_) => "base 64 decoding failed",
+            ChecksumMismatch(_) => "buffer does not match its checksum",
+            CompressionDisabled(_) => "asset is compressed with a disabled format",
+            DecompressedSizeExceeded(_) => "decompressed asset exceeds the maximum size",
             BufferLength(_) => "buffer does not match required length",
+            BufferNotLoaded(_) => "buffer data was not loaded",
             ExtensionDisabled(_) => "asset requires a disabled extension",
             ExtensionUnsupported(_) => "asset requires an unsupported extension",
             FileNotFound(_) => "file not found",
@@ -318,6 +846,9 @@ impl StdError for Error {
             Io(_) => "io error",
             Gltf(_) => "error from gltf crate",
             MalformedJson(_) => "malformed .gltf / .glb JSON",
+            PathOutsideBase(_) => "external file is outside the asset directory",
+            SceneNotFound(_) => "scene not found",
+            UnknownMimeType(_) => "image MIME type could not be determined",
             Validation(_) => "asset failed validation tests",
         }
     }
@@ -0,0 +1,107 @@
+use std::fmt;
+
+use gltf::json;
+use std::time::{Duration, Instant};
+
+/// The time spent on each stage of an import and the number of bytes read.
+///
+/// Stages are timed separately, so the sum of the stages is slightly less than
+/// `total`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Metrics {
+    /// Time spent reading the asset file, including the decompression of gzip
+    /// or zstd wrapped files, which happens while reading.
+    pub read: Duration,
+
+    /// Time spent decompressing gzip or zstd wrapped data already in memory.
+    pub decompress: Duration,
+
+    /// Time spent parsing the JSON and the binary glTF container.
+    pub parse: Duration,
+
+    /// Time spent validating the JSON and verifying buffer checksums.
+    pub validation: Duration,
+
+    /// Time spent in the configured `Decoder` and decoding data URIs.
+    pub decode: Duration,
+
+    /// The external buffers read, in buffer order.
+    pub resources: Vec<Resource>,
+
+    /// The number of bytes read from the asset file and external buffers,
+    /// before decompression and decoding.
+    pub bytes_read: u64,
+
+    /// The time spent on the whole import.
+    pub total: Duration,
+
+    /// The buffers and buffer views shrunk to fit the loaded data under
+    /// `Config::truncate_views`.
+    pub truncated: Vec<json::Path>,
+}
+
+/// The time spent reading a single external buffer.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Resource {
+    /// The URI of the buffer.
+    pub uri: String,
+
+    /// The time spent reading the file.
+    pub read: Duration,
+
+    /// The number of bytes read.
+    pub bytes: u64,
+}
+
+impl Metrics {
+    /// Returns the time spent reading external buffers.
+    pub fn resource_read(&self) -> Duration {
+        self.resources.iter().map(|resource| resource.read).sum()
+    }
+}
+
+/// Returns a duration in milliseconds.
+fn millis(duration: Duration) -> f64 {
+    duration.as_secs() as f64 * 1e3 + duration.subsec_nanos() as f64 * 1e-6
+}
+
+impl fmt::Display for Metrics {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "read        {:>10.3} ms", millis(self.read))?;
+        writeln!(f, "decompress  {:>10.3} ms", millis(self.decompress))?;
+        writeln!(f, "parse       {:>10.3} ms", millis(self.parse))?;
+        writeln!(f, "validation  {:>10.3} ms", millis(self.validation))?;
+        writeln!(f, "decode      {:>10.3} ms", millis(self.decode))?;
+        for resource in &self.resources {
+            writeln!(
+                f,
+                "  {:<30} {:>10.3} ms {:>12} B",
+                resource.uri,
+                millis(resource.read),
+                resource.bytes,
+            )?;
+        }
+        writeln!(f, "resources   {:>10.3} ms", millis(self.resource_read()))?;
+        write!(f, "total       {:>10.3} ms {:>12} B", millis(self.total), self.bytes_read)
+    }
+}
+
+/// Evaluates an expression, adding the time it takes to a stage of `Metrics`.
+///
+/// With the `tracing` feature, the expression is also evaluated within a
+/// `DEBUG` span named after the stage.
+macro_rules! time {
+    ($metrics:ident . $stage:ident, $body:expr) => {{
+        #[cfg(feature = "tracing")]
+        let _span = ::tracing::debug_span!(stringify!($stage)).entered();
+        ::metrics::time(&mut $metrics.$stage, || $body)
+    }};
+}
+
+/// Runs a closure, adding the time it takes to `duration`.
+pub(crate) fn time<T, F: FnOnce() -> T>(duration: &mut Duration, f: F) -> T {
+    let start = Instant::now();
+    let result = f();
+    *duration += start.elapsed();
+    result
+}
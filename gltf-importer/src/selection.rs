@@ -0,0 +1,116 @@
+use gltf::{self, image, Gltf};
+
+/// The byte range `(begin, end)` of a buffer referenced by a selection.
+pub type Range = (usize, usize);
+
+/// Records the buffer data referenced by a subset of a glTF asset.
+struct Selection<'a> {
+    /// The asset being traversed.
+    gltf: &'a Gltf,
+
+    /// Whether each node has been visited.
+    nodes: Vec<bool>,
+
+    /// The referenced range of each buffer, if any.
+    ranges: Vec<Option<Range>>,
+}
+
+impl<'a> Selection<'a> {
+    fn view(&mut self, view: &gltf::buffer::View) {
+        let begin = view.offset();
+        let end = begin + view.length();
+        let range = &mut self.ranges[view.buffer().index()];
+        *range = Some(match *range {
+            Some((b, e)) => (b.min(begin), e.max(end)),
+            None => (begin, end),
+        });
+    }
+
+    fn accessor(&mut self, accessor: &gltf::Accessor) {
+        if let Some(view) = accessor.view() {
+            self.view(&view);
+        }
+        if let Some(sparse) = accessor.sparse() {
+            self.view(&sparse.indices().view());
+            self.view(&sparse.values().view());
+        }
+    }
+
+    fn texture(&mut self, texture: &gltf::Texture) {
+        if let image::Data::View { view, .. } = texture.source().data() {
+            self.view(&view);
+        }
+    }
+
+    fn material(&mut self, material: &gltf::Material) {
+        for texture in material.textures() {
+            self.texture(&texture.texture());
+        }
+    }
+
+    fn mesh(&mut self, mesh: &gltf::Mesh) {
+        for primitive in mesh.primitives() {
+            for (_, accessor) in primitive.attributes() {
+                self.accessor(&accessor);
+            }
+            if let Some(accessor) = primitive.indices() {
+                self.accessor(&accessor);
+            }
+            for target in primitive.morph_targets() {
+                let accessors = vec![target.positions(), target.normals(), target.tangents()];
+                for accessor in accessors.into_iter().filter_map(|a| a) {
+                    self.accessor(&accessor);
+                }
+            }
+            self.material(&primitive.material());
+        }
+    }
+
+    fn node(&mut self, index: usize) {
+        let mut stack = vec![index];
+        while let Some(index) = stack.pop() {
+            if self.nodes[index] {
+                continue;
+            }
+            self.nodes[index] = true;
+            let node = self.gltf.nodes().nth(index).unwrap();
+            if let Some(mesh) = node.mesh() {
+                self.mesh(&mesh);
+            }
+            if let Some(skin) = node.skin() {
+                if let Some(accessor) = skin.inverse_bind_matrices() {
+                    self.accessor(&accessor);
+                }
+                stack.extend(skin.joints().map(|joint| joint.index()));
+            }
+            stack.extend(node.children().map(|child| child.index()));
+        }
+    }
+}
+
+/// Returns the referenced byte range of each buffer when only the given scene
+/// is loaded.
+///
+/// The selection includes every node reachable from the scene, their meshes,
+/// skins, materials, and textures, and every animation channel targeting one
+/// of those nodes.
+pub fn scene(gltf: &Gltf, scene: &gltf::Scene) -> Vec<Option<Range>> {
+    let mut selection = Selection {
+        gltf,
+        nodes: vec![false; gltf.nodes().len()],
+        ranges: vec![None; gltf.buffers().len()],
+    };
+    for node in scene.nodes() {
+        selection.node(node.index());
+    }
+    for animation in gltf.animations() {
+        for channel in animation.channels() {
+            if selection.nodes[channel.target().node().index()] {
+                let sampler = channel.sampler();
+                selection.accessor(&sampler.input());
+                selection.accessor(&sampler.output());
+            }
+        }
+    }
+    selection.ranges
+}
@@ -0,0 +1,46 @@
+extern crate gltf_importer;
+
+use std::{env, fs, process};
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use gltf_importer::import_all;
+
+const TEXTURED: &'static str = r#"{
+    "asset": { "version": "2.0" },
+    "images": [{ "uri": "shared.png" }]
+}"#;
+
+// Creates an empty `<tmp>/gltf-importer-<name>-<pid>` directory, so that
+// concurrent test runs do not share files.
+fn temp_dir(name: &str) -> PathBuf {
+    let dir = env::temp_dir().join(format!("gltf-importer-{}-{}", name, process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+#[test]
+fn import_all_shares_images() {
+    let dir = temp_dir("batch");
+    fs::write(dir.join("shared.png"), b"\x89PNG\r\n\x1a\n").unwrap();
+    fs::write(dir.join("a.gltf"), TEXTURED).unwrap();
+    fs::write(dir.join("b.gltf"), TEXTURED).unwrap();
+
+    let paths = [dir.join("a.gltf"), dir.join("missing.gltf"), dir.join("b.gltf")];
+    let results = import_all(&paths, 2);
+    assert_eq!(3, results.len());
+    assert!(results[1].is_err());
+
+    let a = results[0].as_ref().unwrap();
+    let b = results[2].as_ref().unwrap();
+    assert_eq!("image/png", a.images[0].mime_type);
+    assert!(Arc::ptr_eq(&a.images[0], &b.images[0]));
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn import_all_empty() {
+    let paths: [&str; 0] = [];
+    assert!(import_all(&paths, 4).is_empty());
+}
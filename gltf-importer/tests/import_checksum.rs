@@ -0,0 +1,29 @@
+extern crate gltf_importer;
+
+use gltf_importer::{import_data_slice, Config, Error};
+
+// A single data URI buffer containing `b"123456789"`.
+fn json(crc32: u32) -> String {
+    format!(r#"{{
+        "asset": {{ "version": "2.0" }},
+        "buffers": [{{
+            "byteLength": 9,
+            "uri": "data:application/octet-stream;base64,MTIzNDU2Nzg5",
+            "extensions": {{ "GLTFRS_buffer_checksum": {{ "crc32": {} }} }}
+        }}]
+    }}"#, crc32)
+}
+
+#[test]
+fn verify_checksums() {
+    let config = Config { verify_checksums: true, ..Default::default() };
+    let valid = json(0xCBF43926);
+    assert!(import_data_slice(valid.as_bytes(), "", &config).is_ok());
+
+    let corrupt = json(0);
+    match import_data_slice(corrupt.as_bytes(), "", &config) {
+        Err(Error::ChecksumMismatch(_)) => {},
+        other => panic!("expected ChecksumMismatch, found {:?}", other.map(|_| ())),
+    }
+    assert!(import_data_slice(corrupt.as_bytes(), "", &Config::default()).is_ok());
+}
@@ -0,0 +1,85 @@
+extern crate gltf_importer;
+#[cfg(feature = "gzip")]
+extern crate flate2;
+#[cfg(feature = "zstd")]
+extern crate ruzstd;
+
+use std::{env, fs, process};
+use std::path::PathBuf;
+
+use gltf_importer::import;
+
+// Writes `data` to `<tmp>/gltf-importer-<name>-<pid>/<file>` next to a copy of
+// the external buffer of `Box.gltf`.
+fn setup(name: &str, file: &str, data: &[u8]) -> PathBuf {
+    let dir = env::temp_dir().join(format!("gltf-importer-{}-{}", name, process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    fs::copy("../examples/Box0.bin", dir.join("Box0.bin")).unwrap();
+    let path = dir.join(file);
+    fs::write(&path, data).unwrap();
+    path
+}
+
+#[cfg(feature = "gzip")]
+fn gzip(data: &[u8]) -> Vec<u8> {
+    use std::io::Write;
+    let mut encoder = flate2::write::GzEncoder::new(vec![], flate2::Compression::default());
+    encoder.write_all(data).unwrap();
+    encoder.finish().unwrap()
+}
+
+#[cfg(feature = "gzip")]
+#[test]
+fn gzip_wrapped() {
+    let glb = gzip(&fs::read("../examples/Box.glb").unwrap());
+    let (gltf, _) = import(setup("gzip", "Box.glb.gz", &glb)).unwrap();
+    assert_eq!(1, gltf.meshes().len());
+
+    let text = gzip(&fs::read("../examples/Box.gltf").unwrap());
+    let (gltf, buffers) = import(setup("gzip", "Box.gltf.gz", &text)).unwrap();
+    let buffer = gltf.buffers().next().unwrap();
+    assert_eq!(Some(buffer.length()), buffers.buffer(&buffer).map(|data| data.len()));
+}
+
+#[cfg(feature = "gzip")]
+#[test]
+fn gzip_single_layer() {
+    let twice = gzip(&gzip(&fs::read("../examples/Box.glb").unwrap()));
+    assert!(import(setup("gzip-twice", "Box.glb.gz.gz", &twice)).is_err());
+}
+
+#[cfg(feature = "gzip")]
+#[test]
+fn gzip_size_limit() {
+    use gltf_importer::{import_with_config, Config, Error};
+    let glb = fs::read("../examples/Box.glb").unwrap();
+    let path = setup("gzip-limit", "Box.glb.gz", &gzip(&glb));
+    let config = Config { max_decompressed_size: glb.len() as u64, ..Default::default() };
+    assert!(import_with_config(&path, &config).is_ok());
+    let config = Config { max_decompressed_size: glb.len() as u64 - 1, ..Default::default() };
+    match import_with_config(&path, &config) {
+        Err(Error::DecompressedSizeExceeded(size)) => assert_eq!(glb.len() as u64 - 1, size),
+        other => panic!("unexpected result: {:?}", other.map(|_| ())),
+    }
+}
+
+#[cfg(feature = "zstd")]
+#[test]
+fn zstd_wrapped() {
+    use ruzstd::encoding::{compress_to_vec, CompressionLevel};
+    let glb = fs::read("../examples/Box.glb").unwrap();
+    let data = compress_to_vec(&glb[..], CompressionLevel::Fastest);
+    let path = env::temp_dir().join(format!("Box-{}.glb.zst", process::id()));
+    let (gltf, _) = gltf_importer::import_data_slice(&data, path, &Default::default()).unwrap();
+    assert_eq!(1, gltf.meshes().len());
+}
+
+#[cfg(not(feature = "gzip"))]
+#[test]
+fn gzip_disabled() {
+    let data = [0x1F, 0x8B, 0x08, 0x00];
+    match import(setup("gzip-disabled", "Box.gltf.gz", &data)) {
+        Err(gltf_importer::Error::CompressionDisabled(ref format)) => assert_eq!("gzip", format),
+        other => panic!("unexpected result: {:?}", other.map(|_| ())),
+    }
+}
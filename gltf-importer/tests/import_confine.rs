@@ -0,0 +1,82 @@
+extern crate gltf;
+extern crate gltf_importer;
+
+use std::{env, fs, process};
+use std::path::PathBuf;
+
+use gltf_importer::{import_with_config, Buffers, Config, Error};
+
+// Returns `<tmp>/gltf-importer-confine-<pid>-<name>`, unique to each test run.
+fn temp_dir(name: &str) -> PathBuf {
+    env::temp_dir().join(format!("gltf-importer-confine-{}-{}", process::id(), name))
+}
+
+// Creates `<dir>/asset/` holding a `.gltf` referencing `uri`, a file
+// `inside.bin`, and an empty directory `sub`, plus `<dir>/secret.bin` outside
+// the asset directory, where `<dir>` is `temp_dir(name)`.
+fn setup(name: &str, uri: &str) -> PathBuf {
+    let root = temp_dir(name);
+    let _ = fs::remove_dir_all(&root);
+    fs::create_dir_all(root.join("asset").join("sub")).unwrap();
+    fs::write(root.join("secret.bin"), b"secret").unwrap();
+    fs::write(root.join("asset").join("inside.bin"), b"inside").unwrap();
+    let json = format!(r#"{{
+        "asset": {{ "version": "2.0" }},
+        "buffers": [{{ "byteLength": 6, "uri": "{}" }}]
+    }}"#, uri.replace('\\', "\\\\"));
+    let path = root.join("asset").join("asset.gltf");
+    fs::write(&path, json).unwrap();
+    path
+}
+
+fn confined() -> Config {
+    Config { confine_paths: true, ..Default::default() }
+}
+
+fn is_outside(result: Result<(gltf::Gltf, Buffers), Error>) -> bool {
+    match result {
+        Err(Error::PathOutsideBase(_)) => true,
+        _ => false,
+    }
+}
+
+#[test]
+fn parent_segments() {
+    let path = setup("parent", "../secret.bin");
+    assert!(import_with_config(&path, &Config::default()).is_ok());
+    assert!(is_outside(import_with_config(&path, &confined())));
+
+    let path = setup("parent-inside", "./sub/../inside.bin");
+    assert!(import_with_config(&path, &confined()).is_ok());
+
+    let path = setup("missing", "missing.bin");
+    match import_with_config(&path, &confined()) {
+        Err(Error::FileNotFound(_)) => {},
+        other => panic!("expected FileNotFound, found {:?}", other.map(|_| ())),
+    }
+
+    for name in &["parent", "parent-inside", "missing"] {
+        fs::remove_dir_all(temp_dir(name)).unwrap();
+    }
+}
+
+#[test]
+fn absolute_paths() {
+    let secret = temp_dir("absolute").join("secret.bin");
+    let path = setup("absolute", secret.to_str().unwrap());
+    assert!(import_with_config(&path, &Config::default()).is_ok());
+    assert!(is_outside(import_with_config(&path, &confined())));
+    fs::remove_dir_all(temp_dir("absolute")).unwrap();
+}
+
+#[cfg(unix)]
+#[test]
+fn symbolic_links() {
+    use std::os::unix::fs::symlink;
+    let path = setup("symlink", "link.bin");
+    let dir = path.parent().unwrap();
+    symlink(dir.join("../secret.bin"), dir.join("link.bin")).unwrap();
+    assert!(import_with_config(&path, &Config::default()).is_ok());
+    assert!(is_outside(import_with_config(&path, &confined())));
+    fs::remove_dir_all(temp_dir("symlink")).unwrap();
+}
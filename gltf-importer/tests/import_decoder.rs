@@ -0,0 +1,60 @@
+extern crate gltf_importer;
+
+use gltf_importer::{import_data_slice, Config};
+use gltf_importer::config::{Chunk, Decoder};
+
+const KEY: u8 = 0x5A;
+
+fn xor(data: &[u8]) -> Vec<u8> {
+    data.iter().map(|byte| byte ^ KEY).collect()
+}
+
+fn config() -> Config {
+    Config {
+        decoder: Some(Decoder::new(|_, data| Ok(xor(&data)))),
+        ..Default::default()
+    }
+}
+
+fn chunk(ty: &[u8], data: &[u8]) -> Vec<u8> {
+    let length = data.len() as u32;
+    let mut chunk = vec![length as u8, (length >> 8) as u8, (length >> 16) as u8, (length >> 24) as u8];
+    chunk.extend_from_slice(ty);
+    chunk.extend_from_slice(data);
+    chunk
+}
+
+#[test]
+fn decode_standard() {
+    let json = br#"{ "asset": { "version": "2.0" } }"#;
+    let encoded = xor(json);
+    assert!(import_data_slice(&encoded, "", &Config::default()).is_err());
+    assert!(import_data_slice(&encoded, "", &config()).is_ok());
+}
+
+#[test]
+fn decode_binary() {
+    let json = br#"{"asset":{"version":"2.0"},"buffers":[{"byteLength":4}]}"#;
+    let mut json = json.to_vec();
+    while json.len() % 4 != 0 {
+        json.push(b' ');
+    }
+    let mut body = chunk(b"JSON", &xor(&json));
+    body.extend(chunk(b"BIN\0", &xor(&[1, 2, 3, 4])));
+    let length = 12 + body.len() as u32;
+    let mut glb = b"glTF\x02\0\0\0".to_vec();
+    glb.extend_from_slice(&[length as u8, (length >> 8) as u8, 0, 0]);
+    glb.extend(body);
+
+    let (_, buffers) = {
+        let config = Config {
+            decoder: Some(Decoder::new(move |chunk, data| {
+                assert!(chunk == Chunk::Json || chunk == Chunk::Bin);
+                Ok(xor(&data))
+            })),
+            ..Default::default()
+        };
+        import_data_slice(&glb, "", &config).unwrap()
+    };
+    assert_eq!(vec![vec![1, 2, 3, 4]], buffers.take());
+}
@@ -0,0 +1,43 @@
+extern crate gltf_importer;
+
+use gltf_importer::config::NodeFilter;
+use gltf_importer::{import_data_slice, Config};
+
+const JSON: &str = r#"{
+    "asset": { "version": "2.0" },
+    "meshes": [
+        { "primitives": [{ "attributes": {} }] },
+        { "primitives": [{ "attributes": {} }] }
+    ],
+    "nodes": [
+        { "mesh": 0, "children": [1, 2] },
+        { "mesh": 1, "extras": { "collision_only": true } },
+        { "mesh": 0, "extras": { "tags": ["editor_only"] }, "children": [3] },
+        {}
+    ],
+    "scenes": [{ "nodes": [0] }]
+}"#;
+
+#[test]
+fn filter_nodes() {
+    let config = Config {
+        node_filter: Some(NodeFilter::new(|node| node.index() == 2)),
+        ..Default::default()
+    };
+    let (gltf, _) = import_data_slice(JSON.as_bytes(), "", &config).unwrap();
+    assert_eq!(2, gltf.nodes().len());
+    assert_eq!(2, gltf.meshes().len());
+}
+
+#[cfg(feature = "extras")]
+#[test]
+fn filter_tags() {
+    let config = Config {
+        node_filter: Some(NodeFilter::tags(vec!["collision_only", "editor_only"])),
+        ..Default::default()
+    };
+    let (gltf, _) = import_data_slice(JSON.as_bytes(), "", &config).unwrap();
+    assert_eq!(1, gltf.nodes().len());
+    assert_eq!(1, gltf.meshes().len());
+    assert_eq!(0, gltf.nodes().next().unwrap().children().count());
+}
@@ -0,0 +1,28 @@
+extern crate gltf_importer;
+
+use gltf_importer::{image, import_data_slice, Config};
+
+// A PNG signature mislabelled as JPEG.
+const JSON: &'static str = r#"{
+    "asset": { "version": "2.0" },
+    "images": [{ "uri": "data:image/jpeg;base64,iVBORw0KGgo=" }]
+}"#;
+
+#[test]
+fn load_image_strict() {
+    let config = Config::default();
+    let (gltf, buffers) = import_data_slice(JSON.as_bytes(), "", &config).unwrap();
+    let image = gltf.images().next().unwrap();
+    let loaded = image::load("", &image, &buffers, &config).unwrap();
+    assert_eq!(image::JPEG, loaded.mime_type);
+    assert_eq!(b"\x89PNG\r\n\x1a\n".to_vec(), loaded.data);
+}
+
+#[test]
+fn load_image_lenient() {
+    let config = Config { lenient_mime_types: true, ..Default::default() };
+    let (gltf, buffers) = import_data_slice(JSON.as_bytes(), "", &config).unwrap();
+    let image = gltf.images().next().unwrap();
+    let loaded = image::load("", &image, &buffers, &config).unwrap();
+    assert_eq!(image::PNG, loaded.mime_type);
+}
@@ -0,0 +1,41 @@
+extern crate gltf;
+extern crate gltf_importer;
+
+use gltf::json;
+use gltf_importer::{import_data_slice, Config, Error};
+
+const JSON: &str = r#"{
+    "asset": { "version": "2.0" },
+    "meshes": [{
+        "primitives": [
+            { "attributes": {} },
+            { "attributes": {}, "material": 0 }
+        ]
+    }],
+    "materials": [{}]
+}"#;
+
+fn config(material: json::Material) -> Config {
+    Config { default_material: Some(material), ..Default::default() }
+}
+
+#[test]
+fn default_material() {
+    let mut material = json::Material::default();
+    material.emissive_factor = json::material::EmissiveFactor([1.0, 0.0, 0.0]);
+    let (gltf, _) = import_data_slice(JSON.as_bytes(), "", &config(material)).unwrap();
+    let emissive: Vec<_> = gltf.primitives()
+        .map(|(_, primitive)| primitive.material().emissive_factor())
+        .collect();
+    assert_eq!(vec![[1.0, 0.0, 0.0], [0.0, 0.0, 0.0]], emissive);
+}
+
+#[test]
+fn default_material_missing_texture() {
+    let mut material = json::Material::default();
+    material.emissive_texture = Some(json::texture::Info::new(json::Index::new(0)));
+    match import_data_slice(JSON.as_bytes(), "", &config(material)) {
+        Err(Error::Validation(_)) => {},
+        other => panic!("expected Validation, found {:?}", other.map(|_| ())),
+    }
+}
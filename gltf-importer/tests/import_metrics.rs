@@ -0,0 +1,23 @@
+extern crate gltf_importer;
+
+use gltf_importer::{import_with_metrics, Config};
+
+#[test]
+fn standard() {
+    let (_, _, metrics) = import_with_metrics("../examples/Box.gltf", &Config::default()).unwrap();
+    assert_eq!(1, metrics.resources.len());
+    assert_eq!("Box0.bin", metrics.resources[0].uri);
+    assert_eq!(648, metrics.resources[0].bytes);
+    let asset = std::fs::metadata("../examples/Box.gltf").unwrap().len();
+    assert_eq!(asset + 648, metrics.bytes_read);
+    assert!(metrics.total >= metrics.parse + metrics.validation);
+}
+
+#[test]
+fn binary() {
+    let (_, _, metrics) = import_with_metrics("../examples/Box.glb", &Config::default()).unwrap();
+    assert!(metrics.resources.is_empty());
+    let asset = std::fs::metadata("../examples/Box.glb").unwrap().len();
+    assert_eq!(asset, metrics.bytes_read);
+    assert!(metrics.to_string().contains("validation"));
+}
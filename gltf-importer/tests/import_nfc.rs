@@ -0,0 +1,48 @@
+#![cfg(feature = "nfc")]
+
+extern crate gltf_importer;
+
+use std::{env, fs, process};
+
+use gltf_importer::{import_data_slice, import_with_config, Config};
+
+// "Café" decomposed, as written by macOS exporters.
+const JSON: &str = r#"{
+    "asset": { "version": "2.0" },
+    "nodes": [{ "name": "Cafe\u0301" }],
+    "images": [{ "uri": "cafe\u0301.png" }]
+}"#;
+
+#[test]
+fn normalize_nfc() {
+    let config = Config { normalize_nfc: true, ..Default::default() };
+    let (gltf, _) = import_data_slice(JSON.as_bytes(), "", &config).unwrap();
+    assert_eq!(Some("Caf\u{e9}"), gltf.nodes().next().unwrap().name());
+    // The URI still names the decomposed file.
+    let image = gltf.images().next().unwrap();
+    assert_eq!(Some("cafe\u{301}.png".to_string()), image.as_json().uri);
+}
+
+#[test]
+fn keep_decomposed() {
+    let (gltf, _) = import_data_slice(JSON.as_bytes(), "", &Config::default()).unwrap();
+    assert_eq!(Some("Cafe\u{301}"), gltf.nodes().next().unwrap().name());
+}
+
+#[test]
+fn load_decomposed_file() {
+    let dir = env::temp_dir().join(format!("gltf-importer-nfc-{}", process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("cafe\u{301}.bin"), [1, 2, 3, 4]).unwrap();
+    let path = dir.join("nfc.gltf");
+    fs::write(&path, r#"{
+        "asset": { "version": "2.0" },
+        "buffers": [{ "uri": "cafe\u0301.bin", "byteLength": 4 }]
+    }"#).unwrap();
+
+    let config = Config { normalize_nfc: true, ..Default::default() };
+    let (gltf, buffers) = import_with_config(&path, &config).unwrap();
+    let buffer = gltf.buffers().next().unwrap();
+    assert_eq!(Some(&[1, 2, 3, 4][..]), buffers.buffer(&buffer));
+    fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,25 @@
+extern crate gltf;
+extern crate gltf_importer;
+
+use std::fs;
+
+use gltf_importer::import;
+
+#[test]
+fn raw_standard() {
+    let (_, buffers) = import("../examples/Box.gltf").unwrap();
+    assert_eq!(&fs::read("../examples/Box.gltf").unwrap()[..], buffers.json());
+    assert!(buffers.bin().is_none());
+}
+
+#[test]
+fn raw_binary() {
+    let data = fs::read("../examples/Box.glb").unwrap();
+    let glb = gltf::Glb::from_slice(&data).unwrap();
+    let (gltf, buffers) = import("../examples/Box.glb").unwrap();
+    assert_eq!(&*glb.json, buffers.json());
+    assert_eq!(glb.bin.as_ref().map(|bin| &bin[..]), buffers.bin());
+
+    let buffer = gltf.buffers().next().unwrap();
+    assert_eq!(buffers.buffer(&buffer), buffers.bin());
+}
@@ -0,0 +1,106 @@
+extern crate gltf;
+extern crate gltf_importer;
+
+use std::{env, fs, process};
+use std::path::PathBuf;
+
+use gltf_importer::{import, import_scene, Buffers, Config, Error};
+
+// Two scenes, each with a triangle whose positions are in its own external
+// buffer. The material of the second scene has a texture whose image is in a
+// third external buffer, `images.bin`, which is missing.
+const JSON: &str = r#"{
+    "asset": { "version": "2.0" },
+    "buffers": [
+        { "byteLength": 36, "uri": "first.bin" },
+        { "byteLength": 36, "uri": "second.bin" },
+        { "byteLength": 8, "uri": "images.bin" }
+    ],
+    "bufferViews": [
+        { "buffer": 0, "byteLength": 36 },
+        { "buffer": 1, "byteLength": 36 },
+        { "buffer": 2, "byteLength": 8 }
+    ],
+    "accessors": [
+        {
+            "bufferView": 0, "componentType": 5126, "count": 3, "type": "VEC3",
+            "min": [0, 0, 0], "max": [1, 1, 0]
+        },
+        {
+            "bufferView": 1, "componentType": 5126, "count": 3, "type": "VEC3",
+            "min": [0, 0, 0], "max": [1, 1, 0]
+        }
+    ],
+    "images": [{ "bufferView": 2, "mimeType": "image/png" }],
+    "textures": [{ "source": 0 }],
+    "materials": [{ "pbrMetallicRoughness": { "baseColorTexture": { "index": 0 } } }],
+    "meshes": [
+        { "primitives": [{ "attributes": { "POSITION": 0 } }] },
+        { "primitives": [{ "attributes": { "POSITION": 1 }, "material": 0 }] }
+    ],
+    "nodes": [{ "mesh": 0 }, { "mesh": 1 }],
+    "scenes": [{ "nodes": [0] }, { "nodes": [1] }]
+}"#;
+
+// Writes the asset and its buffers except `images.bin` to
+// `<tmp>/gltf-importer-scene-<pid>/`, returning the path of the asset.
+fn setup() -> PathBuf {
+    let dir = env::temp_dir().join(format!("gltf-importer-scene-{}", process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let triangle: Vec<u8> = [0.0f32, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0]
+        .iter()
+        .flat_map(|x| x.to_le_bytes().to_vec())
+        .collect();
+    fs::write(dir.join("first.bin"), &triangle).unwrap();
+    fs::write(dir.join("second.bin"), &triangle).unwrap();
+    let path = dir.join("asset.gltf");
+    fs::write(&path, JSON).unwrap();
+    path
+}
+
+fn buffer_lengths(gltf: &gltf::Gltf, buffers: &Buffers) -> Vec<usize> {
+    gltf.buffers().map(|buffer| buffers.buffer(&buffer).map_or(0, |data| data.len())).collect()
+}
+
+#[test]
+fn import_scene_matches_import() {
+    let (_, all) = import("../examples/Box.gltf").unwrap();
+    let (_, scene) = import_scene("../examples/Box.gltf", 0).unwrap();
+    assert_eq!(all.take(), scene.take());
+}
+
+#[test]
+fn import_scene_not_found() {
+    match import_scene("../examples/Box.gltf", 1) {
+        Err(Error::SceneNotFound(1)) => {},
+        other => panic!("expected SceneNotFound, found {:?}", other.map(|_| ())),
+    }
+}
+
+#[test]
+fn import_scene_skips_other_scenes() {
+    let path = setup();
+
+    // The buffer of the second scene is not loaded, and the missing image
+    // buffer is never read.
+    let (gltf, buffers) = import_scene(&path, 0).unwrap();
+    assert_eq!(vec![36, 0, 0], buffer_lengths(&gltf, &buffers));
+
+    // Loading the image of the second scene names the buffer view whose data
+    // is missing.
+    let image = gltf.images().next().unwrap();
+    match gltf_importer::image::load(&path, &image, &buffers, &Config::default()) {
+        Err(Error::BufferNotLoaded(ref view)) => {
+            assert_eq!("bufferViews[2].buffer", view.as_str());
+        },
+        other => panic!("expected BufferNotLoaded, found {:?}", other.map(|_| ())),
+    }
+
+    // Both are referenced by the second scene.
+    let result = import_scene(&path, 1);
+    fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    match result {
+        Err(Error::FileNotFound(ref file)) if file.ends_with("images.bin") => {},
+        other => panic!("expected FileNotFound, found {:?}", other.map(|_| ())),
+    }
+}
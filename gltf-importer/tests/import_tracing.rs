@@ -0,0 +1,46 @@
+#![cfg(feature = "tracing")]
+
+extern crate gltf_importer;
+extern crate tracing;
+
+use std::sync::{Arc, Mutex};
+
+use tracing::{span, Event, Metadata, Subscriber};
+
+/// Records the names of new spans.
+struct Spans(Arc<Mutex<Vec<&'static str>>>);
+
+impl Subscriber for Spans {
+    fn enabled(&self, _: &Metadata) -> bool {
+        true
+    }
+
+    fn new_span(&self, span: &span::Attributes) -> span::Id {
+        let mut names = self.0.lock().unwrap();
+        names.push(span.metadata().name());
+        span::Id::from_u64(names.len() as u64)
+    }
+
+    fn record(&self, _: &span::Id, _: &span::Record) {}
+
+    fn record_follows_from(&self, _: &span::Id, _: &span::Id) {}
+
+    fn event(&self, _: &Event) {}
+
+    fn enter(&self, _: &span::Id) {}
+
+    fn exit(&self, _: &span::Id) {}
+}
+
+#[test]
+fn spans() {
+    let names = Arc::new(Mutex::new(vec![]));
+    tracing::subscriber::with_default(Spans(names.clone()), || {
+        gltf_importer::import("../examples/Box.gltf").unwrap();
+    });
+    let names = names.lock().unwrap();
+    assert_eq!("import", names[0]);
+    for name in &["read", "parse", "validation", "read_buffer", "decode"] {
+        assert!(names.contains(name), "missing span {}", name);
+    }
+}
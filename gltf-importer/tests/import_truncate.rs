@@ -0,0 +1,100 @@
+extern crate gltf;
+extern crate gltf_importer;
+
+use std::{env, fs, process};
+use std::path::PathBuf;
+
+use gltf_importer::{import_data_slice, import_with_metrics, Config, Error};
+
+// A data URI buffer containing `b"123456789"` that declares three bytes more,
+// with a view spanning the declared length.
+const JSON: &str = r#"{
+    "asset": { "version": "2.0" },
+    "buffers": [{
+        "byteLength": 12,
+        "uri": "data:application/octet-stream;base64,MTIzNDU2Nzg5"
+    }],
+    "bufferViews": [
+        { "buffer": 0, "byteLength": 12 },
+        { "buffer": 0, "byteOffset": 4, "byteLength": 4 }
+    ]
+}"#;
+
+// Writes `data` to `<tmp>/gltf-importer-truncate-<pid>-<file>`.
+fn write(file: &str, data: &[u8]) -> PathBuf {
+    let path = env::temp_dir().join(format!("gltf-importer-truncate-{}-{}", process::id(), file));
+    fs::write(&path, data).unwrap();
+    path
+}
+
+fn truncating() -> Config {
+    Config { truncate_views: true, ..Default::default() }
+}
+
+fn expect_buffer_length(result: Result<(gltf::Gltf, gltf_importer::Buffers), Error>) -> String {
+    match result {
+        Err(Error::BufferLength(path)) => path.to_string(),
+        other => panic!("expected BufferLength, found {:?}", other.map(|_| ())),
+    }
+}
+
+#[test]
+fn truncate_views() {
+    let result = import_data_slice(JSON.as_bytes(), "", &Config::default());
+    assert_eq!("buffers[0]", expect_buffer_length(result));
+
+    let path = write("views.gltf", JSON.as_bytes());
+    let (gltf, _, metrics) = import_with_metrics(&path, &truncating()).unwrap();
+    fs::remove_file(&path).unwrap();
+    assert_eq!(9, gltf.buffers().next().unwrap().length());
+    let views: Vec<_> = gltf.views().map(|view| (view.offset(), view.length())).collect();
+    assert_eq!(vec![(0, 9), (4, 4)], views);
+    let truncated: Vec<_> = metrics.truncated.iter().map(ToString::to_string).collect();
+    assert_eq!(vec!["buffers[0]", "bufferViews[0]"], truncated);
+}
+
+#[test]
+fn truncate_glb_padding() {
+    // The buffer declares the BIN chunk's length plus two bytes of padding.
+    let json = br#"{
+        "asset": { "version": "2.0" },
+        "buffers": [{ "byteLength": 10 }],
+        "bufferViews": [{ "buffer": 0, "byteLength": 10 }]
+    }"#;
+    let bin = [1, 2, 3, 4, 5, 6, 7, 8];
+    let glb = gltf::Glb::new(json[..].into(), Some(bin[..].into())).unwrap().to_vec().unwrap();
+    let path = write("padding.glb", &glb);
+
+    let result = gltf_importer::import(&path);
+    assert_eq!("buffers[0]", expect_buffer_length(result));
+    let (gltf, buffers, metrics) = import_with_metrics(&path, &truncating()).unwrap();
+    fs::remove_file(&path).unwrap();
+    let view = gltf.views().next().unwrap();
+    assert_eq!(8, view.length());
+    assert_eq!(Some(&bin[..]), buffers.view(&view));
+    assert_eq!(2, metrics.truncated.len());
+}
+
+#[test]
+fn reject_view_past_data() {
+    let json = JSON.replace(r#""byteOffset": 4"#, r#""byteOffset": 10"#);
+    let result = import_data_slice(json.as_bytes(), "", &truncating());
+    assert_eq!("bufferViews[1]", expect_buffer_length(result));
+}
+
+#[test]
+fn reject_accessor_past_truncated_view() {
+    // Three `VEC3` of `u8` need 9 bytes, but only 5 remain after byte 4.
+    let json = JSON
+        .replace(r#""byteLength": 4 }"#, r#""byteLength": 8 }"#)
+        .replacen(
+            r#""bufferViews""#,
+            r#""accessors": [{
+                "bufferView": 1, "componentType": 5121, "count": 3, "type": "VEC3"
+            }],
+            "bufferViews""#,
+            1,
+        );
+    let result = import_data_slice(json.as_bytes(), "", &truncating());
+    assert_eq!("accessors[0]", expect_buffer_length(result));
+}
@@ -0,0 +1,56 @@
+#![cfg(feature = "zip")]
+
+extern crate gltf_importer;
+extern crate zip;
+
+use std::fs;
+use std::io::{Cursor, Write};
+
+use gltf_importer::{Archive, Error};
+use zip::write::{FileOptions, ZipWriter};
+use zip::CompressionMethod;
+
+fn zip(files: &[(&str, Vec<u8>)]) -> Vec<u8> {
+    let mut writer = ZipWriter::new(Cursor::new(vec![]));
+    let options = FileOptions::default().compression_method(CompressionMethod::Deflated);
+    for &(name, ref data) in files {
+        writer.start_file(name, options).unwrap();
+        writer.write_all(data).unwrap();
+    }
+    writer.finish().unwrap().into_inner()
+}
+
+#[test]
+fn import_box() {
+    let data = zip(&[
+        ("Box/textures/readme.txt", b"unused".to_vec()),
+        ("Box/Box0.bin", fs::read("../examples/Box0.bin").unwrap()),
+        ("Box/Box.gltf", fs::read("../examples/Box.gltf").unwrap()),
+    ]);
+    let archive = Archive::from_reader(Cursor::new(data)).unwrap();
+    assert_eq!("Box/Box.gltf", archive.asset());
+    let (gltf, buffers) = archive.import(&Default::default()).unwrap();
+    let buffer = gltf.buffers().next().unwrap();
+    assert_eq!(Some(buffer.length()), buffers.buffer(&buffer).map(|data| data.len()));
+}
+
+#[test]
+fn missing_buffer() {
+    let data = zip(&[("Box.gltf", fs::read("../examples/Box.gltf").unwrap())]);
+    let archive = Archive::from_reader(Cursor::new(data)).unwrap();
+    assert!(archive.import(&Default::default()).is_err());
+}
+
+#[test]
+fn reject_oversized_archive() {
+    let data = zip(&[
+        ("Box.gltf", fs::read("../examples/Box.gltf").unwrap()),
+        ("padding.bin", vec![0; 4096]),
+    ]);
+    let size = fs::metadata("../examples/Box.gltf").unwrap().len();
+    match Archive::from_reader_with_max_size(Cursor::new(data.clone()), size + 4095) {
+        Err(Error::DecompressedSizeExceeded(max_size)) => assert_eq!(size + 4095, max_size),
+        other => panic!("expected DecompressedSizeExceeded, found {:?}", other.map(|_| ())),
+    }
+    assert!(Archive::from_reader_with_max_size(Cursor::new(data), size + 4096).is_ok());
+}
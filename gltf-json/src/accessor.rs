@@ -1,11 +1,11 @@
 use {buffer, extensions, Extras, Index};
-use serde::de;
+use serde::{de, ser};
 use serde_json::Value;
 use std::fmt;
 use validation::Checked;
 
 /// The component data type.
-#[derive(Clone, Copy, Debug, Deserialize)]
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq)]
 pub enum ComponentType {
     /// Corresponds to `GL_BYTE`.
     I8 = 1,
@@ -103,7 +103,7 @@ pub mod sparse {
     use ::extensions;
 
     /// Indices of those attributes that deviate from their initialization value.
-    #[derive(Clone, Debug, Deserialize, Validate)]
+    #[derive(Clone, Debug, Deserialize, Serialize, Validate)]
     pub struct Indices {
         /// The parent buffer view containing the sparse indices.
         ///
@@ -121,14 +121,30 @@ pub mod sparse {
         pub component_type: Checked<IndexComponentType>,
 
         /// Extension specific data.
+        #[serde(default, skip_serializing_if = "::extensions::accessor::sparse::Indices::is_empty")]
         pub extensions: extensions::accessor::sparse::Indices,
 
         /// Optional application specific data.
+        #[serde(skip_serializing_if = "::extras::is_empty")]
         pub extras: Extras,
     }
 
+    impl Indices {
+        /// Creates sparse indices of the given type read from the start of a
+        /// buffer view.
+        pub fn new(buffer_view: Index<buffer::View>, component_type: ComponentType) -> Self {
+            Indices {
+                buffer_view,
+                byte_offset: 0,
+                component_type: Checked::Valid(IndexComponentType(component_type)),
+                extensions: Default::default(),
+                extras: Default::default(),
+            }
+        }
+    }
+
     /// Sparse storage of attributes that deviate from their initialization value.
-    #[derive(Clone, Debug, Deserialize, Validate)]
+    #[derive(Clone, Debug, Deserialize, Serialize, Validate)]
     pub struct Sparse {
         /// The number of attributes encoded in this sparse accessor.
         pub count: u32,
@@ -147,15 +163,30 @@ pub mod sparse {
         pub values: Values,
 
         /// Extension specific data.
+        #[serde(default, skip_serializing_if = "::extensions::accessor::sparse::Sparse::is_empty")]
         pub extensions: extensions::accessor::sparse::Sparse,
 
         /// Optional application specific data.
+        #[serde(skip_serializing_if = "::extras::is_empty")]
         pub extras: Extras,
     }
 
+    impl Sparse {
+        /// Creates sparse storage of `count` substituted attributes.
+        pub fn new(count: u32, indices: Indices, values: Values) -> Self {
+            Sparse {
+                count,
+                indices,
+                values,
+                extensions: Default::default(),
+                extras: Default::default(),
+            }
+        }
+    }
+
     /// Array of size `count * number_of_components` storing the displaced
     /// accessor attributes pointed by `accessor::sparse::Indices`.
-    #[derive(Clone, Debug, Deserialize, Validate)]
+    #[derive(Clone, Debug, Deserialize, Serialize, Validate)]
     pub struct Values {
         /// The parent buffer view containing the sparse indices.
         ///
@@ -169,19 +200,37 @@ pub mod sparse {
         pub byte_offset: u32,
 
         /// Extension specific data.
+        #[serde(default, skip_serializing_if = "::extensions::accessor::sparse::Values::is_empty")]
         pub extensions: extensions::accessor::sparse::Values,
 
         /// Optional application specific data.
+        #[serde(skip_serializing_if = "::extras::is_empty")]
         pub extras: Extras,
     }
+
+    impl Values {
+        /// Creates sparse values read from the start of a buffer view.
+        pub fn new(buffer_view: Index<buffer::View>) -> Self {
+            Values {
+                buffer_view,
+                byte_offset: 0,
+                extensions: Default::default(),
+                extras: Default::default(),
+            }
+        }
+    }
 }
 
 /// A typed view into a buffer view.
-#[derive(Clone, Debug, Deserialize, Validate)]
+#[derive(Clone, Debug, Deserialize, Serialize, Validate)]
 pub struct Accessor {
     /// The parent buffer view this accessor reads from.
-    #[serde(rename = "bufferView")]
-    pub buffer_view: Index<buffer::View>,
+    ///
+    /// Accessors without a buffer view are initialized with zeros, which
+    /// `sparse` may replace, e.g. for morph targets that displace only a few
+    /// vertices.
+    #[serde(default, rename = "bufferView", skip_serializing_if = "Option::is_none")]
+    pub buffer_view: Option<Index<buffer::View>>,
 
     /// The offset relative to the start of the parent `BufferView` in bytes.
     #[serde(default, rename = "byteOffset")]
@@ -196,11 +245,11 @@ pub struct Accessor {
     pub component_type: Checked<GenericComponentType>,
 
     /// Extension specific data.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "::extensions::accessor::Accessor::is_empty")]
     pub extensions: extensions::accessor::Accessor,
 
     /// Optional application specific data.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "::extras::is_empty")]
     pub extras: Extras,
 
     /// Specifies if the attribute is a scalar, vector, or matrix.
@@ -208,35 +257,129 @@ pub struct Accessor {
     pub type_: Checked<Type>,
 
     /// Minimum value of each component in this attribute.
-    #[serde(default)]
+    ///
+    /// Numbers are parsed with correct rounding and written in their shortest
+    /// round-trip form, independent of the locale, so subnormals and `-0.0`
+    /// are preserved bit for bit.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub min: Option<Value>,
 
     /// Maximum value of each component in this attribute.
-    #[serde(default)]
+    ///
+    /// See `min` for how numbers are parsed and written.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub max: Option<Value>,
 
     /// Optional user-defined name for this object.
+    #[serde(skip_serializing_if = "Option::is_none")]
     #[cfg(feature = "names")]
     pub name: Option<String>,
 
+    /// Records a name discarded because the `names` feature is disabled.
+    #[serde(default, skip_serializing_if = "::extras::Void::is_absent")]
+    #[cfg(not(feature = "names"))]
+    pub name: ::extras::Void,
+
     /// Specifies whether integer data values should be normalized.
     #[serde(default)]
     pub normalized: bool,
 
     /// Sparse storage of attributes that deviate from their initialization
     /// value.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub sparse: Option<sparse::Sparse>,
 }
 
+impl Accessor {
+    /// Creates a tightly packed accessor reading `count` elements from the
+    /// start of the given buffer view.
+    pub fn new(
+        buffer_view: Index<buffer::View>,
+        component_type: ComponentType,
+        type_: Type,
+        count: u32,
+    ) -> Self {
+        Accessor::with_view(Some(buffer_view), component_type, type_, count)
+    }
+
+    /// Creates a sparse accessor of `count` elements without a buffer view,
+    /// so that every element not substituted by `sparse` is zero.
+    pub fn sparse(
+        component_type: ComponentType,
+        type_: Type,
+        count: u32,
+        sparse: sparse::Sparse,
+    ) -> Self {
+        let mut accessor = Accessor::with_view(None, component_type, type_, count);
+        accessor.sparse = Some(sparse);
+        accessor
+    }
+
+    fn with_view(
+        buffer_view: Option<Index<buffer::View>>,
+        component_type: ComponentType,
+        type_: Type,
+        count: u32,
+    ) -> Self {
+        Accessor {
+            buffer_view,
+            byte_offset: 0,
+            count,
+            component_type: Checked::Valid(GenericComponentType(component_type)),
+            extensions: Default::default(),
+            extras: Default::default(),
+            type_: Checked::Valid(type_),
+            min: None,
+            max: None,
+            #[cfg(feature = "names")]
+            name: None,
+            #[cfg(not(feature = "names"))]
+            name: Default::default(),
+            normalized: false,
+            sparse: None,
+        }
+    }
+}
+
 /// The data type of an index.
-#[derive(Clone, Copy, Debug, Deserialize)]
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
 pub struct IndexComponentType(pub ComponentType);
 
 /// The data type of a generic vertex attribute.
-#[derive(Clone, Copy, Debug, Deserialize)]
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
 pub struct GenericComponentType(pub ComponentType);
 
+impl ser::Serialize for ComponentType {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: ser::Serializer
+    {
+        serializer.serialize_u32(match *self {
+            ComponentType::I8 => BYTE,
+            ComponentType::U8 => UNSIGNED_BYTE,
+            ComponentType::I16 => SHORT,
+            ComponentType::U16 => UNSIGNED_SHORT,
+            ComponentType::U32 => UNSIGNED_INT,
+            ComponentType::F32 => FLOAT,
+        })
+    }
+}
+
+impl ser::Serialize for Type {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: ser::Serializer
+    {
+        serializer.serialize_str(match *self {
+            Type::Scalar => "SCALAR",
+            Type::Vec2 => "VEC2",
+            Type::Vec3 => "VEC3",
+            Type::Vec4 => "VEC4",
+            Type::Mat2 => "MAT2",
+            Type::Mat3 => "MAT3",
+            Type::Mat4 => "MAT4",
+        })
+    }
+}
+
 impl<'de> de::Deserialize<'de> for Checked<GenericComponentType> {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
         where D: de::Deserializer<'de>
@@ -1,4 +1,4 @@
-use serde::de;
+use serde::{de, ser};
 use std::fmt;
 use validation::{Checked, Error, Validate};
 use {accessor, extensions, scene, Extras, Index, Path, Root};
@@ -73,14 +73,14 @@ pub enum TrsProperty {
 }
 
 /// A keyframe animation.
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
 pub struct Animation {
     /// Extension specific data.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "::extensions::animation::Animation::is_empty")]
     pub extensions: extensions::animation::Animation,
     
     /// Optional application specific data.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "::extras::is_empty")]
     pub extras: Extras,
     
     /// An array of channels, each of which targets an animation's sampler at a
@@ -90,8 +90,14 @@ pub struct Animation {
     pub channels: Vec<Channel>,
     
     /// Optional user-defined name for this object.
+    #[serde(skip_serializing_if = "Option::is_none")]
     #[cfg(feature = "names")]
     pub name: Option<String>,
+
+    /// Records a name discarded because the `names` feature is disabled.
+    #[serde(default, skip_serializing_if = "::extras::Void::is_absent")]
+    #[cfg(not(feature = "names"))]
+    pub name: ::extras::Void,
     
     /// An array of samplers that combine input and output accessors with an
     /// interpolation algorithm to define a keyframe graph (but not its target).
@@ -99,7 +105,7 @@ pub struct Animation {
 }
 
 /// Targets an animation's sampler at a node's property.
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct Channel {
     /// The index of a sampler in this animation used to compute the value for the
     /// target.
@@ -109,23 +115,35 @@ pub struct Channel {
     pub target: Target,
     
     /// Extension specific data.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "::extensions::animation::Channel::is_empty")]
     pub extensions: extensions::animation::Channel,
     
     /// Optional application specific data.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "::extras::is_empty")]
     pub extras: Extras,
 }
 
+impl Channel {
+    /// Creates a channel targeting an animation sampler at a node property.
+    pub fn new(sampler: Index<Sampler>, target: Target) -> Self {
+        Channel {
+            sampler,
+            target,
+            extensions: Default::default(),
+            extras: Default::default(),
+        }
+    }
+}
+
 /// The index of the node and TRS property that an animation channel targets.
-#[derive(Clone, Debug, Deserialize, Validate)]
+#[derive(Clone, Debug, Deserialize, Serialize, Validate)]
 pub struct Target {
     /// Extension specific data.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "::extensions::animation::Target::is_empty")]
     pub extensions: extensions::animation::Target,
     
     /// Optional application specific data.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "::extras::is_empty")]
     pub extras: Extras,
     
     /// The index of the node to target.
@@ -136,15 +154,27 @@ pub struct Target {
     pub path: Checked<TrsProperty>,
 }
 
+impl Target {
+    /// Creates a target for the given property of a node.
+    pub fn new(node: Index<scene::Node>, path: TrsProperty) -> Self {
+        Target {
+            extensions: Default::default(),
+            extras: Default::default(),
+            node,
+            path: Checked::Valid(path),
+        }
+    }
+}
+
 /// Defines a keyframe graph but not its target.
-#[derive(Clone, Debug, Deserialize, Validate)]
+#[derive(Clone, Debug, Deserialize, Serialize, Validate)]
 pub struct Sampler {
     /// Extension specific data.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "::extensions::animation::Sampler::is_empty")]
     pub extensions: extensions::animation::Sampler,
     
     /// Optional application specific data.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "::extras::is_empty")]
     pub extras: Extras,
     
     /// The index of an accessor containing keyframe input values, e.g., time.
@@ -158,6 +188,20 @@ pub struct Sampler {
     pub output: Index<accessor::Accessor>,
 }
 
+impl Sampler {
+    /// Creates a linearly interpolated sampler from keyframe input and output
+    /// accessors.
+    pub fn new(input: Index<accessor::Accessor>, output: Index<accessor::Accessor>) -> Self {
+        Sampler {
+            extensions: Default::default(),
+            extras: Default::default(),
+            input,
+            interpolation: Default::default(),
+            output,
+        }
+    }
+}
+
 impl Validate for Animation {
     fn validate_minimally<P, R>(&self, root: &Root, path: P, report: &mut R)
     where
@@ -180,6 +224,32 @@ impl Default for InterpolationAlgorithm {
     }
 }
 
+impl ser::Serialize for InterpolationAlgorithm {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: ser::Serializer
+    {
+        serializer.serialize_str(match *self {
+            InterpolationAlgorithm::Linear => "LINEAR",
+            InterpolationAlgorithm::Step => "STEP",
+            InterpolationAlgorithm::CatmullRomSpline => "CATMULLROMSPLINE",
+            InterpolationAlgorithm::CubicSpline => "CUBICSPLINE",
+        })
+    }
+}
+
+impl ser::Serialize for TrsProperty {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: ser::Serializer
+    {
+        serializer.serialize_str(match *self {
+            TrsProperty::Translation => "translation",
+            TrsProperty::Rotation => "rotation",
+            TrsProperty::Scale => "scale",
+            TrsProperty::Weights => "weights",
+        })
+    }
+}
+
 impl<'de> de::Deserialize<'de> for Checked<InterpolationAlgorithm> {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
         where D: de::Deserializer<'de>
@@ -1,24 +1,35 @@
 use {extensions, Extras};
 
+#[cfg(feature = "extras")]
+use serde_json::{Map, Number, Value};
+
+/// The `extras` key of the conventional scale of the asset in meters per unit.
+pub const METERS_PER_UNIT: &'static str = "metersPerUnit";
+
+/// The `extras` key of the conventional up axis of the asset, e.g. `"Y"`.
+pub const UP_AXIS: &'static str = "upAxis";
+
 /// Metadata about the glTF asset.
-#[derive(Clone, Debug, Deserialize, Validate)]
+#[derive(Clone, Debug, Deserialize, Serialize, Validate)]
 pub struct Asset {
     /// A copyright message suitable for display to credit the content creator.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub copyright: Option<String>,
     
     /// Extension specific data.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "::extensions::asset::Asset::is_empty")]
     pub extensions: extensions::asset::Asset,
     
     /// Optional application specific data.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "::extras::is_empty")]
     pub extras: Extras,
     
     /// Tool that generated this glTF model.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub generator: Option<String>,
 
     /// The minimum glTF version that this asset targets.
-    #[serde(rename = "minVersion")]
+    #[serde(rename = "minVersion", skip_serializing_if = "Option::is_none")]
     pub min_version: Option<String>,
     
     /// The glTF version of this asset.
@@ -38,3 +49,93 @@ impl Default for Asset {
     }
 }
 
+
+impl Asset {
+    /// Stamps the provenance of the asset, setting the generator and, if
+    /// provided, the copyright message.
+    ///
+    /// An existing copyright message is kept if `copyright` is `None`.
+    pub fn stamp(&mut self, generator: &str, copyright: Option<&str>) {
+        self.generator = Some(generator.to_string());
+        if let Some(copyright) = copyright {
+            self.copyright = Some(copyright.to_string());
+        }
+    }
+
+    /// Returns the scale of the asset in meters per unit, read from the
+    /// `metersPerUnit` property of the asset `extras`.
+    ///
+    /// glTF units are meters, so `None` should be treated as `1.0`.
+    #[cfg(feature = "extras")]
+    pub fn meters_per_unit(&self) -> Option<f64> {
+        self.extra(METERS_PER_UNIT).and_then(Value::as_f64)
+    }
+
+    /// Sets the `metersPerUnit` property of the asset `extras`.
+    ///
+    /// See `extras_mut` for how existing `extras` are treated.
+    #[cfg(feature = "extras")]
+    pub fn set_meters_per_unit(&mut self, meters_per_unit: f64) {
+        if let Some(number) = Number::from_f64(meters_per_unit) {
+            self.extras_mut().insert(METERS_PER_UNIT.to_string(), Value::Number(number));
+        }
+    }
+
+    /// Returns the up axis the asset was authored in, read from the `upAxis`
+    /// property of the asset `extras`.
+    ///
+    /// glTF is Y-up, so this is purely informational, e.g. `"Z"` for assets
+    /// converted from a Z-up tool.
+    #[cfg(feature = "extras")]
+    pub fn up_axis(&self) -> Option<&str> {
+        self.extra(UP_AXIS).and_then(Value::as_str)
+    }
+
+    /// Sets the `upAxis` property of the asset `extras`.
+    ///
+    /// See `extras_mut` for how existing `extras` are treated.
+    #[cfg(feature = "extras")]
+    pub fn set_up_axis(&mut self, up_axis: &str) {
+        self.extras_mut().insert(UP_AXIS.to_string(), Value::String(up_axis.to_string()));
+    }
+
+    /// Returns a mutable reference to the asset `extras` object, creating it if
+    /// absent.
+    ///
+    /// `extras` that are not a JSON object are replaced with an empty object.
+    #[cfg(feature = "extras")]
+    pub fn extras_mut(&mut self) -> &mut Map<String, Value> {
+        match self.extras {
+            Some(Value::Object(_)) => {},
+            _ => self.extras = Some(Value::Object(Map::new())),
+        }
+        match self.extras {
+            Some(Value::Object(ref mut map)) => map,
+            _ => unreachable!(),
+        }
+    }
+
+    #[cfg(feature = "extras")]
+    fn extra(&self, key: &str) -> Option<&Value> {
+        self.extras.as_ref().and_then(|extras| extras.get(key))
+    }
+}
+
+#[cfg(all(test, feature = "extras"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn metadata() {
+        let mut asset = Asset::default();
+        assert_eq!(None, asset.meters_per_unit());
+        asset.extras = Some(Value::Bool(true));
+        asset.set_meters_per_unit(0.01);
+        asset.set_up_axis("Z");
+        asset.stamp("exporter", None);
+        assert_eq!(Some(0.01), asset.meters_per_unit());
+        assert_eq!(Some("Z"), asset.up_axis());
+        assert_eq!(Some("exporter"), asset.generator.as_ref().map(String::as_str));
+        assert_eq!(None, asset.copyright);
+    }
+}
@@ -1,4 +1,4 @@
-use serde::de;
+use serde::{de, ser};
 use std::fmt;
 use validation::{Checked, Error, Validate};
 use {extensions, Extras, Index, Root, Path};
@@ -22,7 +22,7 @@ pub const VALID_TARGETS: &'static [u32] = &[
 ];
 
 /// Specifies the target a GPU buffer should be bound to.
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum Target {
     /// Corresponds to `GL_ARRAY_BUFFER`.
     ArrayBuffer = 1,
@@ -32,31 +32,50 @@ pub enum Target {
 }
 
 /// A buffer points to binary data representing geometry, animations, or skins.
-#[derive(Clone, Debug, Deserialize, Validate)]
+#[derive(Clone, Debug, Default, Deserialize, Serialize, Validate)]
 pub struct Buffer {
     /// The length of the buffer in bytes.
     #[serde(default, rename = "byteLength")]
     pub byte_length: u32,
 
     /// Optional user-defined name for this object.
+    #[serde(skip_serializing_if = "Option::is_none")]
     #[cfg(feature = "names")]
     pub name: Option<String>,
 
+    /// Records a name discarded because the `names` feature is disabled.
+    #[serde(default, skip_serializing_if = "::extras::Void::is_absent")]
+    #[cfg(not(feature = "names"))]
+    pub name: ::extras::Void,
+
     /// The uri of the buffer.  Relative paths are relative to the .gltf file.
     /// Instead of referencing an external file, the uri can also be a data-uri.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub uri: Option<String>,
 
     /// Extension specific data.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "::extensions::buffer::Buffer::is_empty")]
     pub extensions: extensions::buffer::Buffer,
 
     /// Optional application specific data.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "::extras::is_empty")]
     pub extras: Extras,
 }
 
+impl Buffer {
+    /// Creates a buffer of the given length with no URI, i.e. the binary glTF
+    /// `BIN` chunk.
+    pub fn new(byte_length: u32) -> Self {
+        Buffer {
+            byte_length,
+            uri: None,
+            ..Default::default()
+        }
+    }
+}
+
 /// A view into a buffer generally representing a subset of the buffer.
-#[derive(Clone, Debug, Deserialize, Validate)]
+#[derive(Clone, Debug, Deserialize, Serialize, Validate)]
 pub struct View {
     /// The parent `Buffer`.
     pub buffer: Index<Buffer>,
@@ -72,27 +91,54 @@ pub struct View {
     /// The stride in bytes between vertex attributes or other interleavable data.
     ///
     /// When zero, data is assumed to be tightly packed.
-    #[serde(rename = "byteStride")]
+    #[serde(rename = "byteStride", skip_serializing_if = "Option::is_none")]
     pub byte_stride: Option<ByteStride>,
 
     /// Optional user-defined name for this object.
+    #[serde(skip_serializing_if = "Option::is_none")]
     #[cfg(feature = "names")]
     pub name: Option<String>,
 
+    /// Records a name discarded because the `names` feature is disabled.
+    #[serde(default, skip_serializing_if = "::extras::Void::is_absent")]
+    #[cfg(not(feature = "names"))]
+    pub name: ::extras::Void,
+
     /// Optional target the buffer should be bound to.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub target: Option<Checked<Target>>,
 
     /// Extension specific data.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "::extensions::buffer::View::is_empty")]
     pub extensions: extensions::buffer::View,
 
     /// Optional application specific data.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "::extras::is_empty")]
     pub extras: Extras,
 }
 
+impl View {
+    /// Creates a view of `byte_length` bytes starting at `byte_offset` in the
+    /// given buffer.
+    pub fn new(buffer: Index<Buffer>, byte_offset: u32, byte_length: u32) -> Self {
+        View {
+            buffer,
+            byte_length,
+            byte_offset,
+            byte_stride: None,
+            #[cfg(feature = "names")]
+            name: None,
+            #[cfg(not(feature = "names"))]
+            name: Default::default(),
+            target: None,
+            extensions: Default::default(),
+            extras: Default::default(),
+        }
+    }
+}
+
 /// The stride, in bytes, between vertex attributes.
-#[derive(Clone, Copy, Debug, Deserialize)]
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
 pub struct ByteStride(pub u32);
 
 impl Validate for ByteStride {
@@ -112,6 +158,17 @@ impl Validate for ByteStride {
     }
 }
 
+impl ser::Serialize for Target {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: ser::Serializer
+    {
+        serializer.serialize_u32(match *self {
+            Target::ArrayBuffer => ARRAY_BUFFER,
+            Target::ElementArrayBuffer => ELEMENT_ARRAY_BUFFER,
+        })
+    }
+}
+
 impl<'de> de::Deserialize<'de> for Checked<Target> {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
         where D: de::Deserializer<'de>
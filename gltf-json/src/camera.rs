@@ -1,4 +1,4 @@
-use serde::de;
+use serde::{de, ser};
 use std::fmt;
 use validation::{Checked, Error, Validate};
 use {extensions, Extras, Root, Path};
@@ -23,18 +23,26 @@ pub enum Type {
 ///
 /// A node can reference a camera to apply a transform to place the camera in the
 /// scene.
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct Camera {
     /// Optional user-defined name for this object.
+    #[serde(skip_serializing_if = "Option::is_none")]
     #[cfg(feature = "names")]
     pub name: Option<String>,
 
+    /// Records a name discarded because the `names` feature is disabled.
+    #[serde(default, skip_serializing_if = "::extras::Void::is_absent")]
+    #[cfg(not(feature = "names"))]
+    pub name: ::extras::Void,
+
     /// An orthographic camera containing properties to create an orthographic
     /// projection matrix.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub orthographic: Option<Orthographic>,
 
     /// A perspective camera containing properties to create a perspective
     /// projection matrix.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub perspective: Option<Perspective>,
 
     /// Specifies if the camera uses a perspective or orthographic projection.
@@ -42,16 +50,48 @@ pub struct Camera {
     pub type_: Checked<Type>,
 
     /// Extension specific data.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "::extensions::camera::Camera::is_empty")]
     pub extensions: extensions::camera::Camera,
 
     /// Optional application specific data.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "::extras::is_empty")]
     pub extras: Extras,
 }
 
+impl Camera {
+    /// Creates a camera with a perspective projection.
+    pub fn perspective(perspective: Perspective) -> Self {
+        Camera {
+            #[cfg(feature = "names")]
+            name: None,
+            #[cfg(not(feature = "names"))]
+            name: Default::default(),
+            orthographic: None,
+            perspective: Some(perspective),
+            type_: Checked::Valid(Type::Perspective),
+            extensions: Default::default(),
+            extras: Default::default(),
+        }
+    }
+
+    /// Creates a camera with an orthographic projection.
+    pub fn orthographic(orthographic: Orthographic) -> Self {
+        Camera {
+            #[cfg(feature = "names")]
+            name: None,
+            #[cfg(not(feature = "names"))]
+            name: Default::default(),
+            orthographic: Some(orthographic),
+            perspective: None,
+            type_: Checked::Valid(Type::Orthographic),
+            extensions: Default::default(),
+            extras: Default::default(),
+        }
+    }
+}
+
 /// Values for an orthographic camera.
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct Orthographic {
     /// The horizontal magnification of the view.
     pub xmag: f32,
@@ -66,39 +106,69 @@ pub struct Orthographic {
     pub znear: f32,
 
     /// Extension specific data.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "::extensions::camera::Orthographic::is_empty")]
     pub extensions: extensions::camera::Orthographic,
 
     /// Optional application specific data.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "::extras::is_empty")]
     pub extras: Extras,
 }
 
+impl Orthographic {
+    /// Creates an orthographic projection.
+    pub fn new(xmag: f32, ymag: f32, zfar: f32, znear: f32) -> Self {
+        Orthographic {
+            xmag,
+            ymag,
+            zfar,
+            znear,
+            extensions: Default::default(),
+            extras: Default::default(),
+        }
+    }
+}
+
 /// Values for a perspective camera.
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct Perspective {
     /// Aspect ratio of the field of view.
-    #[serde(rename = "aspectRatio")]
+    #[serde(rename = "aspectRatio", skip_serializing_if = "Option::is_none")]
     pub aspect_ratio: Option<f32>,
 
     /// The vertical field of view in radians.
     pub yfov: f32,
 
     /// The distance to the far clipping plane.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub zfar: Option<f32>,
 
     /// The distance to the near clipping plane.
     pub znear: f32,
 
     /// Extension specific data.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "::extensions::camera::Perspective::is_empty")]
     pub extensions: extensions::camera::Perspective,
 
     /// Optional application specific data.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "::extras::is_empty")]
     pub extras: Extras,
 }
 
+impl Perspective {
+    /// Creates an infinite perspective projection with an aspect ratio
+    /// determined by the viewport.
+    pub fn new(yfov: f32, znear: f32) -> Self {
+        Perspective {
+            aspect_ratio: None,
+            yfov,
+            zfar: None,
+            znear,
+            extensions: Default::default(),
+            extras: Default::default(),
+        }
+    }
+}
+
 impl Validate for Camera {
     fn validate_minimally<P, R>(&self, root: &Root, path: P, report: &mut R)
         where P: Fn() -> Path, R: FnMut(&Fn() -> Path, Error)
@@ -170,6 +240,17 @@ impl Validate for Perspective {
     }
 }
 
+impl ser::Serialize for Type {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: ser::Serializer
+    {
+        serializer.serialize_str(match *self {
+            Type::Perspective => "perspective",
+            Type::Orthographic => "orthographic",
+        })
+    }
+}
+
 impl<'de> de::Deserialize<'de> for Checked<Type> {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
         where D: de::Deserializer<'de>
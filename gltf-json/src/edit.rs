@@ -0,0 +1,657 @@
+use serde_json;
+use std::collections::{BTreeMap, BTreeSet};
+use std::{error, fmt};
+
+use pointer::parse_index;
+use {Index, Material, Mesh, Node, Root, Value};
+
+/// A single operation recorded by a `DocumentEdit`.
+#[derive(Clone, Debug)]
+pub enum Edit {
+    /// The material at the given index was replaced.
+    SetMaterial(usize, Box<Material>),
+
+    /// The mesh at the given index was replaced.
+    SetMesh(usize, Box<Mesh>),
+
+    /// The node at the given index was replaced.
+    SetNode(usize, Box<Node>),
+
+    /// The node at the given index was removed along with its descendants.
+    RemoveNode(usize),
+}
+
+/// An error encountered while applying a JSON patch to a `DocumentEdit`.
+#[derive(Debug)]
+pub enum PatchError {
+    /// The patch is not an array of objects with `op` and `path` strings.
+    ///
+    /// Holds the index of the offending operation, or zero if the patch is
+    /// not an array.
+    Malformed(usize),
+
+    /// The operation at the given index is not supported, or its path does
+    /// not refer to an existing material, mesh, or node.
+    Unsupported(usize),
+
+    /// The value of the operation at the given index failed to deserialize.
+    Value(usize, serde_json::Error),
+}
+
+impl fmt::Display for PatchError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            PatchError::Malformed(index) => write!(f, "malformed operation {}", index),
+            PatchError::Unsupported(index) => write!(f, "unsupported operation {}", index),
+            PatchError::Value(index, ref error) => {
+                write!(f, "invalid value in operation {}: {}", index, error)
+            },
+        }
+    }
+}
+
+impl error::Error for PatchError {
+    fn description(&self) -> &str {
+        match *self {
+            PatchError::Malformed(_) => "malformed JSON patch operation",
+            PatchError::Unsupported(_) => "unsupported JSON patch operation",
+            PatchError::Value(..) => "invalid JSON patch value",
+        }
+    }
+
+    fn cause(&self) -> Option<&error::Error> {
+        match *self {
+            PatchError::Value(_, ref error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+/// Records modifications to a document without copying it.
+///
+/// Edits are kept in a log on top of the borrowed original, so each edit only
+/// stores the objects it changes and can be undone. `commit` materializes the
+/// edited document as a new `Root`. Undone edits can be redone until a new
+/// edit is recorded.
+///
+/// Removed nodes take their descendants with them. On commit, references to
+/// removed nodes are dropped from parent nodes, scenes, and skin skeletons,
+/// animation channels targeting them are dropped, along with animations left
+/// without channels, and skins using any of them as a joint are dropped along
+/// with the references to those skins. With the `physics` feature, joints
+/// connected to removed nodes, colliders using their meshes, and the parts of
+/// triggers referencing them are dropped too.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate gltf_json as json;
+/// # fn main() {
+/// use json::edit::DocumentEdit;
+///
+/// let root: json::Root = json::from_str(r#"{
+///     "asset": { "version": "2.0" },
+///     "materials": [{ "doubleSided": false }],
+///     "nodes": [{ "children": [1] }, {}, {}],
+///     "scenes": [{ "nodes": [0, 2] }]
+/// }"#).unwrap();
+///
+/// let mut edit = DocumentEdit::new(&root);
+/// let mut material = edit.material(0).unwrap().clone();
+/// material.double_sided = true;
+/// edit.set_material(0, material);
+/// edit.remove_node(0);
+/// assert!(edit.is_removed(1));
+///
+/// let edited = edit.commit();
+/// assert!(edited.materials[0].double_sided);
+/// assert_eq!(1, edited.nodes.len());
+/// assert_eq!(0, edited.scenes[0].nodes[0].value());
+///
+/// // The original is left untouched.
+/// assert_eq!(3, root.nodes.len());
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct DocumentEdit<'a> {
+    /// The unmodified document.
+    base: &'a Root,
+
+    /// Every edit in the order applied.
+    log: Vec<Edit>,
+
+    /// Undone edits, the most recently undone last.
+    undone: Vec<Edit>,
+}
+
+impl<'a> DocumentEdit<'a> {
+    /// Starts editing a document.
+    pub fn new(base: &'a Root) -> Self {
+        DocumentEdit {
+            base,
+            log: vec![],
+            undone: vec![],
+        }
+    }
+
+    /// Returns the unmodified document.
+    pub fn base(&self) -> &'a Root {
+        self.base
+    }
+
+    /// Returns the recorded edits in the order applied.
+    pub fn edits(&self) -> &[Edit] {
+        &self.log
+    }
+
+    /// Returns whether any edit has been recorded.
+    pub fn is_modified(&self) -> bool {
+        !self.log.is_empty()
+    }
+
+    /// Returns the current version of a material.
+    pub fn material(&self, index: usize) -> Option<&Material> {
+        let edited = self.log.iter().rev().filter_map(|edit| match *edit {
+            Edit::SetMaterial(i, ref material) if i == index => Some(&**material),
+            _ => None,
+        });
+        edited.chain(self.base.materials.get(index)).next()
+    }
+
+    /// Returns the current version of a mesh.
+    pub fn mesh(&self, index: usize) -> Option<&Mesh> {
+        let edited = self.log.iter().rev().filter_map(|edit| match *edit {
+            Edit::SetMesh(i, ref mesh) if i == index => Some(&**mesh),
+            _ => None,
+        });
+        edited.chain(self.base.meshes.get(index)).next()
+    }
+
+    /// Returns the current version of a node, or `None` if it was removed.
+    pub fn node(&self, index: usize) -> Option<&Node> {
+        if self.is_removed(index) {
+            return None;
+        }
+        self.current_node(index)
+    }
+
+    /// Returns the latest version of a node, whether removed or not.
+    fn current_node(&self, index: usize) -> Option<&Node> {
+        let edited = self.log.iter().rev().filter_map(|edit| match *edit {
+            Edit::SetNode(i, ref node) if i == index => Some(&**node),
+            _ => None,
+        });
+        edited.chain(self.base.nodes.get(index)).next()
+    }
+
+    /// Returns whether a node was removed, either directly or as the
+    /// descendant of a removed node.
+    pub fn is_removed(&self, index: usize) -> bool {
+        self.removed_nodes().contains(&index)
+    }
+
+    /// Returns the indices of every removed node.
+    fn removed_nodes(&self) -> BTreeSet<usize> {
+        let mut removed = BTreeSet::new();
+        let mut stack: Vec<usize> = self.log
+            .iter()
+            .filter_map(|edit| match *edit {
+                Edit::RemoveNode(index) => Some(index),
+                _ => None,
+            })
+            .collect();
+        while let Some(index) = stack.pop() {
+            if index < self.base.nodes.len() && removed.insert(index) {
+                let node = self.current_node(index).unwrap();
+                let children = node.children.iter().flat_map(|children| children.iter());
+                stack.extend(children.map(Index::value));
+            }
+        }
+        removed
+    }
+
+    /// Replaces a material.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index is out of range.
+    pub fn set_material(&mut self, index: usize, material: Material) {
+        assert!(index < self.base.materials.len(), "material index out of range");
+        self.push(Edit::SetMaterial(index, Box::new(material)));
+    }
+
+    /// Replaces a mesh.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index is out of range.
+    pub fn set_mesh(&mut self, index: usize, mesh: Mesh) {
+        assert!(index < self.base.meshes.len(), "mesh index out of range");
+        self.push(Edit::SetMesh(index, Box::new(mesh)));
+    }
+
+    /// Replaces a node.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index is out of range.
+    pub fn set_node(&mut self, index: usize, node: Node) {
+        assert!(index < self.base.nodes.len(), "node index out of range");
+        self.push(Edit::SetNode(index, Box::new(node)));
+    }
+
+    /// Removes a node and its descendants.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index is out of range.
+    pub fn remove_node(&mut self, index: usize) {
+        assert!(index < self.base.nodes.len(), "node index out of range");
+        self.push(Edit::RemoveNode(index));
+    }
+
+    /// Records an edit, discarding any undone edits.
+    fn push(&mut self, edit: Edit) {
+        self.undone.clear();
+        self.log.push(edit);
+    }
+
+    /// Reverts the most recent edit, returning it.
+    pub fn undo(&mut self) -> Option<Edit> {
+        let edit = self.log.pop();
+        if let Some(ref edit) = edit {
+            self.undone.push(edit.clone());
+        }
+        edit
+    }
+
+    /// Reapplies the most recently undone edit, returning it.
+    pub fn redo(&mut self) -> Option<&Edit> {
+        let edit = self.undone.pop()?;
+        self.log.push(edit);
+        self.log.last()
+    }
+
+    /// Returns the recorded edits as a JSON patch.
+    ///
+    /// Each edit becomes one operation of an
+    /// [RFC 6902](https://tools.ietf.org/html/rfc6902) patch: a `replace` of
+    /// `/materials/i`, `/meshes/i`, or `/nodes/i` holding the whole object, or a `remove` of
+    /// `/nodes/i`. Paths always refer to the indices of the base document,
+    /// since removals are only carried out on commit, so the patch is meant to
+    /// be replayed with `apply_json_patch` against the same base document
+    /// rather than by a generic JSON patch implementation.
+    pub fn to_json_patch(&self) -> Result<Value, serde_json::Error> {
+        let mut operations = Vec::with_capacity(self.log.len());
+        for edit in &self.log {
+            operations.push(match *edit {
+                Edit::SetMaterial(index, ref material) => json!({
+                    "op": "replace",
+                    "path": format!("/materials/{}", index),
+                    "value": serde_json::to_value(material)?,
+                }),
+                Edit::SetMesh(index, ref mesh) => json!({
+                    "op": "replace",
+                    "path": format!("/meshes/{}", index),
+                    "value": serde_json::to_value(mesh)?,
+                }),
+                Edit::SetNode(index, ref node) => json!({
+                    "op": "replace",
+                    "path": format!("/nodes/{}", index),
+                    "value": serde_json::to_value(node)?,
+                }),
+                Edit::RemoveNode(index) => json!({
+                    "op": "remove",
+                    "path": format!("/nodes/{}", index),
+                }),
+            });
+        }
+        Ok(Value::Array(operations))
+    }
+
+    /// Records the operations of a JSON patch produced by `to_json_patch`.
+    ///
+    /// Either every operation is recorded or, on error, none are.
+    pub fn apply_json_patch(&mut self, patch: &Value) -> Result<(), PatchError> {
+        let operations = patch.as_array().ok_or(PatchError::Malformed(0))?;
+        let mut edits = Vec::with_capacity(operations.len());
+        for (i, operation) in operations.iter().enumerate() {
+            let op = operation.get("op").and_then(Value::as_str);
+            let path = operation.get("path").and_then(Value::as_str);
+            let (op, path) = match (op, path) {
+                (Some(op), Some(path)) => (op, path),
+                _ => return Err(PatchError::Malformed(i)),
+            };
+            let tokens: Vec<&str> = path.split('/').collect();
+            let (collection, index) = match tokens[..] {
+                ["", collection, index] => match parse_index(index) {
+                    Some(index) => (collection, index),
+                    None => return Err(PatchError::Unsupported(i)),
+                },
+                _ => return Err(PatchError::Unsupported(i)),
+            };
+            let value = || {
+                let value = operation.get("value").ok_or(PatchError::Malformed(i))?;
+                Ok(value.clone())
+            };
+            let edit = match (op, collection) {
+                ("replace", "materials") if index < self.base.materials.len() => {
+                    let material = serde_json::from_value(value()?)
+                        .map_err(|error| PatchError::Value(i, error))?;
+                    Edit::SetMaterial(index, material)
+                },
+                ("replace", "meshes") if index < self.base.meshes.len() => {
+                    let mesh = serde_json::from_value(value()?)
+                        .map_err(|error| PatchError::Value(i, error))?;
+                    Edit::SetMesh(index, mesh)
+                },
+                ("replace", "nodes") if index < self.base.nodes.len() => {
+                    let node = serde_json::from_value(value()?)
+                        .map_err(|error| PatchError::Value(i, error))?;
+                    Edit::SetNode(index, node)
+                },
+                ("remove", "nodes") if index < self.base.nodes.len() => Edit::RemoveNode(index),
+                _ => return Err(PatchError::Unsupported(i)),
+            };
+            edits.push(edit);
+        }
+        for edit in edits {
+            self.push(edit);
+        }
+        Ok(())
+    }
+
+    /// Materializes the edited document.
+    pub fn commit(&self) -> Root {
+        let mut root = self.base.clone();
+        for edit in &self.log {
+            match *edit {
+                Edit::SetMaterial(index, ref material) => root.materials[index] = (**material).clone(),
+                Edit::SetMesh(index, ref mesh) => root.meshes[index] = (**mesh).clone(),
+                Edit::SetNode(index, ref node) => root.nodes[index] = (**node).clone(),
+                Edit::RemoveNode(_) => {},
+            }
+        }
+        let removed = self.removed_nodes();
+        if !removed.is_empty() {
+            remove_nodes(&mut root, &removed);
+        }
+        root
+    }
+}
+
+/// Removes nodes from a document, updating every reference to nodes and to
+/// skins left without their joints.
+///
+/// Only the given nodes are removed, not their descendants, which are
+/// detached from the hierarchy instead. See `DocumentEdit` for how references
+/// to removed nodes are handled.
+pub fn remove_nodes(root: &mut Root, removed: &BTreeSet<usize>) {
+    let remap = |count: usize, removed: &BTreeSet<usize>| {
+        let mut map = BTreeMap::new();
+        for index in (0..count).filter(|index| !removed.contains(index)) {
+            let new = map.len() as u32;
+            map.insert(index, new);
+        }
+        map
+    };
+    let nodes = remap(root.nodes.len(), removed);
+    let removed_skins: BTreeSet<usize> = root.skins
+        .iter()
+        .enumerate()
+        .filter(|&(_, skin)| skin.joints.iter().any(|joint| removed.contains(&joint.value())))
+        .map(|(index, _)| index)
+        .collect();
+    let skins = remap(root.skins.len(), &removed_skins);
+
+    let update = |index: &Index<Node>| nodes.get(&index.value()).map(|&i| Index::new(i));
+    let mut index = 0;
+    root.nodes.retain(|_| {
+        index += 1;
+        !removed.contains(&(index - 1))
+    });
+    for node in &mut root.nodes {
+        if let Some(children) = node.children.take() {
+            let children: Vec<_> = children.iter().filter_map(&update).collect();
+            if !children.is_empty() {
+                node.children = Some(children);
+            }
+        }
+        node.skin = node.skin
+            .take()
+            .and_then(|skin| skins.get(&skin.value()).map(|&i| Index::new(i)));
+    }
+    for scene in &mut root.scenes {
+        scene.nodes = scene.nodes.iter().filter_map(&update).collect();
+    }
+    let mut index = 0;
+    root.skins.retain(|_| {
+        index += 1;
+        !removed_skins.contains(&(index - 1))
+    });
+    for skin in &mut root.skins {
+        skin.joints = skin.joints.iter().filter_map(&update).collect();
+        skin.skeleton = skin.skeleton.take().and_then(|skeleton| update(&skeleton));
+    }
+    for animation in &mut root.animations {
+        animation.channels.retain(|channel| nodes.contains_key(&channel.target.node.value()));
+        for channel in &mut animation.channels {
+            channel.target.node = update(&channel.target.node).unwrap();
+        }
+    }
+    root.animations.retain(|animation| !animation.channels.is_empty());
+    #[cfg(feature = "physics")]
+    {
+        for node in &mut root.nodes {
+            if let Some(ref mut body) = node.extensions.physics {
+                remove_physics_nodes(body, &update);
+            }
+        }
+    }
+}
+
+/// Drops the parts of a rigid body referencing removed nodes, renumbering the
+/// remaining references with `update`.
+#[cfg(feature = "physics")]
+fn remove_physics_nodes<F>(body: &mut ::extensions::physics::RigidBody, update: &F)
+    where F: Fn(&Index<Node>) -> Option<Index<Node>>
+{
+    use extensions::physics::Geometry;
+
+    // Returns whether the geometry is still valid.
+    let update_geometry = |geometry: &mut Geometry| match geometry.node.take() {
+        Some(node) => {
+            geometry.node = update(&node);
+            geometry.node.is_some()
+        },
+        None => true,
+    };
+    if body.collider.as_mut().is_some_and(|collider| !update_geometry(&mut collider.geometry)) {
+        body.collider = None;
+    }
+    if let Some(mut trigger) = body.trigger.take() {
+        if trigger.geometry.as_mut().is_some_and(|geometry| !update_geometry(geometry)) {
+            trigger.geometry = None;
+        }
+        trigger.nodes = trigger.nodes.iter().filter_map(update).collect();
+        if trigger.geometry.is_some() || !trigger.nodes.is_empty() {
+            body.trigger = Some(trigger);
+        }
+    }
+    if let Some(mut joint) = body.joint.take() {
+        if let Some(node) = update(&joint.connected_node) {
+            joint.connected_node = node;
+            body.joint = Some(joint);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json;
+    use super::*;
+
+    #[test]
+    fn undo_and_remove() {
+        let root: Root = serde_json::from_str(r#"{
+            "asset": { "version": "2.0" },
+            "nodes": [
+                { "children": [1, 2] },
+                { "skin": 0 },
+                { "children": [3] },
+                {},
+                { "skin": 1 }
+            ],
+            "skins": [{ "joints": [2, 3] }, { "joints": [4], "skeleton": 4 }],
+            "scenes": [{ "nodes": [0, 4] }],
+            "animations": [{
+                "channels": [
+                    { "sampler": 0, "target": { "node": 3, "path": "scale" } },
+                    { "sampler": 0, "target": { "node": 4, "path": "scale" } }
+                ],
+                "samplers": [{ "input": 0, "output": 0 }]
+            }]
+        }"#).unwrap();
+        let mut edit = DocumentEdit::new(&root);
+        edit.remove_node(0);
+        assert!(edit.undo().is_some());
+        assert!(!edit.is_modified());
+
+        let mut node = edit.node(0).unwrap().clone();
+        node.children = Some(vec![Index::new(1)]);
+        edit.set_node(0, node);
+        edit.remove_node(2);
+        assert!(edit.is_removed(3));
+        assert!(edit.node(3).is_none());
+
+        let edited = edit.commit();
+        assert_eq!(3, edited.nodes.len());
+        assert_eq!(1, edited.nodes[0].children.as_ref().unwrap()[0].value());
+        // Skin 0 lost its joints, skin 1 moved to index 0.
+        assert!(edited.nodes[1].skin.is_none());
+        assert_eq!(Some(0), edited.nodes[2].skin.as_ref().map(Index::value));
+        assert_eq!(1, edited.skins.len());
+        assert_eq!(2, edited.skins[0].joints[0].value());
+        assert_eq!(Some(2), edited.skins[0].skeleton.as_ref().map(Index::value));
+        let scene: Vec<_> = edited.scenes[0].nodes.iter().map(Index::value).collect();
+        assert_eq!(vec![0, 2], scene);
+        assert_eq!(1, edited.animations[0].channels.len());
+        assert_eq!(2, edited.animations[0].channels[0].target.node.value());
+    }
+
+    #[test]
+    fn redo_and_patch() {
+        let root: Root = serde_json::from_str(r#"{
+            "asset": { "version": "2.0" },
+            "materials": [{ "alphaMode": "MASK" }],
+            "meshes": [{ "primitives": [{ "attributes": {} }] }],
+            "nodes": [{ "children": [1] }, {}]
+        }"#).unwrap();
+        let mut edit = DocumentEdit::new(&root);
+        let mut material = edit.material(0).unwrap().clone();
+        material.pbr_metallic_roughness.roughness_factor.0 = 0.5;
+        edit.set_material(0, material);
+        edit.remove_node(1);
+        edit.undo();
+        assert!(edit.redo().is_some());
+        assert!(edit.redo().is_none());
+        edit.undo();
+        let mut node = edit.node(1).unwrap().clone();
+        node.translation = [1.0, 2.0, 3.0];
+        edit.set_node(1, node);
+        assert!(edit.redo().is_none());
+        let mut mesh = edit.mesh(0).unwrap().clone();
+        mesh.weights = Some(vec![0.5]);
+        edit.set_mesh(0, mesh);
+
+        let patch = edit.to_json_patch().unwrap();
+        assert_eq!("replace", patch[0]["op"]);
+        assert_eq!("/materials/0", patch[0]["path"]);
+        assert_eq!("MASK", patch[0]["value"]["alphaMode"]);
+        assert!(patch[1]["value"].get("children").is_none());
+        assert_eq!("/meshes/0", patch[2]["path"]);
+
+        let mut replica = DocumentEdit::new(&root);
+        replica.apply_json_patch(&patch).unwrap();
+        let edited = replica.commit();
+        assert_eq!(0.5, edited.materials[0].pbr_metallic_roughness.roughness_factor.0);
+        assert_eq!([1.0, 2.0, 3.0], edited.nodes[1].translation);
+        assert_eq!(Some(vec![0.5]), edited.meshes[0].weights);
+
+        let invalid = json!([
+            { "op": "remove", "path": "/nodes/0" },
+            { "op": "remove", "path": "/nodes/2" }
+        ]);
+        match replica.apply_json_patch(&invalid) {
+            Err(PatchError::Unsupported(1)) => {},
+            result => panic!("unexpected result {:?}", result),
+        }
+        assert_eq!(3, replica.edits().len());
+    }
+
+    #[test]
+    fn remove_empty_animations() {
+        let root: Root = serde_json::from_str(r#"{
+            "asset": { "version": "2.0" },
+            "nodes": [{}, {}],
+            "animations": [
+                {
+                    "channels": [{ "sampler": 0, "target": { "node": 0, "path": "scale" } }],
+                    "samplers": [{ "input": 0, "output": 0 }]
+                },
+                {
+                    "channels": [{ "sampler": 0, "target": { "node": 1, "path": "scale" } }],
+                    "samplers": [{ "input": 0, "output": 0 }]
+                }
+            ]
+        }"#).unwrap();
+        let mut edit = DocumentEdit::new(&root);
+        edit.remove_node(0);
+        let edited = edit.commit();
+        assert_eq!(1, edited.animations.len());
+        assert_eq!(0, edited.animations[0].channels[0].target.node.value());
+    }
+
+    #[cfg(feature = "physics")]
+    #[test]
+    fn remove_physics_references() {
+        let root: Root = serde_json::from_str(r#"{
+            "asset": { "version": "2.0" },
+            "nodes": [
+                {},
+                {},
+                {
+                    "extensions": {
+                        "KHR_physics_rigid_bodies": {
+                            "collider": { "geometry": { "node": 0 } },
+                            "trigger": { "nodes": [0, 1] },
+                            "joint": { "connectedNode": 0, "joint": 0 }
+                        }
+                    }
+                },
+                {
+                    "extensions": {
+                        "KHR_physics_rigid_bodies": {
+                            "collider": { "geometry": { "node": 1, "convexHull": true } },
+                            "joint": { "connectedNode": 1, "joint": 0 }
+                        }
+                    }
+                }
+            ]
+        }"#).unwrap();
+        let mut edit = DocumentEdit::new(&root);
+        edit.remove_node(0);
+        let edited = edit.commit();
+
+        let body = edited.nodes[1].extensions.physics.as_ref().unwrap();
+        assert!(body.collider.is_none());
+        assert!(body.joint.is_none());
+        let nodes: Vec<_> = body.trigger.as_ref().unwrap().nodes.iter().map(Index::value).collect();
+        assert_eq!(vec![0], nodes);
+
+        let body = edited.nodes[2].extensions.physics.as_ref().unwrap();
+        let geometry = &body.collider.as_ref().unwrap().geometry;
+        assert_eq!(Some(0), geometry.node.as_ref().map(Index::value));
+        assert_eq!(0, body.joint.as_ref().unwrap().connected_node.value());
+    }
+}
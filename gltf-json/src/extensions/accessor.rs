@@ -1,19 +1,67 @@
+use serde_json::{Map, Value};
+
 /// Contains data structures for sparse storage.
 pub mod sparse {
+    use serde_json::{Map, Value};
+
     /// Indices of those attributes that deviate from their initialization value.
-    #[derive(Clone, Debug, Default, Deserialize, Validate)]
-    pub struct Indices {}
+    #[derive(Clone, Debug, Default, Deserialize, Serialize, Validate)]
+    pub struct Indices {
+        /// Extensions not modelled by this crate, or disabled by its features, by name.
+        #[serde(flatten)]
+        pub others: Map<String, Value>,
+    }
+
+    impl Indices {
+        /// Returns whether there is no extension data to serialize.
+        pub fn is_empty(&self) -> bool {
+            self.others.is_empty()
+        }
+    }
 
     /// Sparse storage of attributes that deviate from their initialization value.
-    #[derive(Clone, Debug, Default, Deserialize, Validate)]
-    pub struct Sparse {}
+    #[derive(Clone, Debug, Default, Deserialize, Serialize, Validate)]
+    pub struct Sparse {
+        /// Extensions not modelled by this crate, or disabled by its features, by name.
+        #[serde(flatten)]
+        pub others: Map<String, Value>,
+    }
+
+    impl Sparse {
+        /// Returns whether there is no extension data to serialize.
+        pub fn is_empty(&self) -> bool {
+            self.others.is_empty()
+        }
+    }
 
     /// Array of size `count * number_of_components` storing the displaced
     /// accessor attributes pointed by `accessor::sparse::Indices`.
-    #[derive(Clone, Debug, Default, Deserialize, Validate)]
-    pub struct Values {}
+    #[derive(Clone, Debug, Default, Deserialize, Serialize, Validate)]
+    pub struct Values {
+        /// Extensions not modelled by this crate, or disabled by its features, by name.
+        #[serde(flatten)]
+        pub others: Map<String, Value>,
+    }
+
+    impl Values {
+        /// Returns whether there is no extension data to serialize.
+        pub fn is_empty(&self) -> bool {
+            self.others.is_empty()
+        }
+    }
 }
 
 /// A typed view into a buffer view.
-#[derive(Clone, Debug, Default, Deserialize, Validate)]
-pub struct Accessor {}
+#[derive(Clone, Debug, Default, Deserialize, Serialize, Validate)]
+pub struct Accessor {
+    /// Extensions not modelled by this crate, or disabled by its features, by name.
+    #[serde(flatten)]
+    pub others: Map<String, Value>,
+}
+
+impl Accessor {
+    /// Returns whether there is no extension data to serialize.
+    pub fn is_empty(&self) -> bool {
+        self.others.is_empty()
+    }
+}
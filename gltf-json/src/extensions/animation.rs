@@ -1,15 +1,61 @@
+use serde_json::{Map, Value};
+
 /// A keyframe animation.
-#[derive(Clone, Debug, Default, Deserialize)]
-pub struct Animation {}
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct Animation {
+    /// Extensions not modelled by this crate, or disabled by its features, by name.
+    #[serde(flatten)]
+    pub others: Map<String, Value>,
+}
+
+impl Animation {
+    /// Returns whether there is no extension data to serialize.
+    pub fn is_empty(&self) -> bool {
+        self.others.is_empty()
+    }
+}
 
 /// Targets an animation's sampler at a node's property.
-#[derive(Clone, Debug, Default, Deserialize)]
-pub struct Channel {}
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct Channel {
+    /// Extensions not modelled by this crate, or disabled by its features, by name.
+    #[serde(flatten)]
+    pub others: Map<String, Value>,
+}
+
+impl Channel {
+    /// Returns whether there is no extension data to serialize.
+    pub fn is_empty(&self) -> bool {
+        self.others.is_empty()
+    }
+}
 
 /// The index of the node and TRS property that an animation channel targets.
-#[derive(Clone, Debug, Default, Deserialize, Validate)]
-pub struct Target {}
+#[derive(Clone, Debug, Default, Deserialize, Serialize, Validate)]
+pub struct Target {
+    /// Extensions not modelled by this crate, or disabled by its features, by name.
+    #[serde(flatten)]
+    pub others: Map<String, Value>,
+}
+
+impl Target {
+    /// Returns whether there is no extension data to serialize.
+    pub fn is_empty(&self) -> bool {
+        self.others.is_empty()
+    }
+}
 
 /// Defines a keyframe graph but not its target.
-#[derive(Clone, Debug, Default, Deserialize, Validate)]
-pub struct Sampler {}
+#[derive(Clone, Debug, Default, Deserialize, Serialize, Validate)]
+pub struct Sampler {
+    /// Extensions not modelled by this crate, or disabled by its features, by name.
+    #[serde(flatten)]
+    pub others: Map<String, Value>,
+}
+
+impl Sampler {
+    /// Returns whether there is no extension data to serialize.
+    pub fn is_empty(&self) -> bool {
+        self.others.is_empty()
+    }
+}
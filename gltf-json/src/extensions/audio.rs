@@ -0,0 +1,320 @@
+use root::Get;
+use validation::{non_negative, Error, Validate};
+use {buffer, Index, Path, Root};
+
+/// The name of the `KHR_audio` extension.
+pub const AUDIO: &'static str = "KHR_audio";
+
+/// All valid audio MIME types.
+pub const VALID_MIME_TYPES: &'static [&'static str] = &["audio/mpeg"];
+
+/// All valid emitter types.
+pub const VALID_EMITTER_TYPES: &'static [&'static str] = &["global", "positional"];
+
+/// All valid distance attenuation models of positional emitters.
+pub const VALID_DISTANCE_MODELS: &'static [&'static str] = &[
+    "linear",
+    "inverse",
+    "exponential",
+];
+
+/// The document level clips, sources, and emitters of the `KHR_audio`
+/// extension.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, Validate)]
+pub struct Audio {
+    /// Encoded audio data referenced by sources.
+    #[serde(default, rename = "audio", skip_serializing_if = "Vec::is_empty")]
+    pub clips: Vec<Clip>,
+
+    /// Playback settings of clips, referenced by emitters.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sources: Vec<Source>,
+
+    /// Emitters referenced by nodes and scenes.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub emitters: Vec<Emitter>,
+}
+
+/// Encoded audio data, stored in a buffer view or behind a URI.
+///
+/// Exactly one of `uri` and `buffer_view` is set.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct Clip {
+    /// The buffer view containing the audio data.
+    #[serde(rename = "bufferView", skip_serializing_if = "Option::is_none")]
+    pub buffer_view: Option<Index<buffer::View>>,
+
+    /// The audio MIME type, one of `VALID_MIME_TYPES`. Required with
+    /// `buffer_view`.
+    #[serde(rename = "mimeType", skip_serializing_if = "Option::is_none")]
+    pub mime_type: Option<String>,
+
+    /// Optional user-defined name for this object.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg(feature = "names")]
+    pub name: Option<String>,
+
+    /// Records a name discarded because the `names` feature is disabled.
+    #[serde(default, skip_serializing_if = "::extras::Void::is_absent")]
+    #[cfg(not(feature = "names"))]
+    pub name: ::extras::Void,
+
+    /// The URI of the audio data. Relative paths are relative to the .gltf
+    /// file.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub uri: Option<String>,
+}
+
+impl Validate for Clip {
+    fn validate_minimally<P, R>(&self, root: &Root, path: P, report: &mut R)
+        where P: Fn() -> Path, R: FnMut(&Fn() -> Path, Error)
+    {
+        self.buffer_view.validate_minimally(root, || path().field("bufferView"), report);
+    }
+
+    fn validate_completely<P, R>(&self, _: &Root, path: P, report: &mut R)
+        where P: Fn() -> Path, R: FnMut(&Fn() -> Path, Error)
+    {
+        if self.uri.is_some() == self.buffer_view.is_some() {
+            report(&|| path().field("uri"), Error::Invalid);
+        }
+        match self.mime_type {
+            Some(ref mime_type) => if !VALID_MIME_TYPES.contains(&mime_type.as_str()) {
+                report(&|| path().field("mimeType"), Error::Invalid);
+            },
+            None => if self.buffer_view.is_some() {
+                report(&|| path().field("mimeType"), Error::Missing);
+            },
+        }
+    }
+}
+
+/// The playback settings of a clip.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Source {
+    /// The clip played by this source.
+    #[serde(rename = "audio", skip_serializing_if = "Option::is_none")]
+    pub clip: Option<Index<Clip>>,
+
+    /// Whether playback starts when the scene is loaded.
+    #[serde(default, rename = "autoPlay")]
+    pub auto_play: bool,
+
+    /// The linear volume multiplier of the source.
+    #[serde(default = "gain_default")]
+    pub gain: f32,
+
+    /// Whether playback restarts when the clip ends.
+    #[serde(default, rename = "loop")]
+    pub loop_: bool,
+
+    /// Optional user-defined name for this object.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg(feature = "names")]
+    pub name: Option<String>,
+
+    /// Records a name discarded because the `names` feature is disabled.
+    #[serde(default, skip_serializing_if = "::extras::Void::is_absent")]
+    #[cfg(not(feature = "names"))]
+    pub name: ::extras::Void,
+}
+
+fn gain_default() -> f32 {
+    1.0
+}
+
+impl Validate for Source {
+    fn validate_minimally<P, R>(&self, root: &Root, path: P, report: &mut R)
+        where P: Fn() -> Path, R: FnMut(&Fn() -> Path, Error)
+    {
+        self.clip.validate_minimally(root, || path().field("audio"), report);
+    }
+
+    fn validate_completely<P, R>(&self, _: &Root, path: P, report: &mut R)
+        where P: Fn() -> Path, R: FnMut(&Fn() -> Path, Error)
+    {
+        if !non_negative(self.gain) {
+            report(&|| path().field("gain"), Error::Invalid);
+        }
+    }
+}
+
+/// Mixes a set of sources, either globally or at the position of a node.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Emitter {
+    /// The emitter type, one of `VALID_EMITTER_TYPES`.
+    #[serde(rename = "type")]
+    pub type_: String,
+
+    /// The linear volume multiplier of the emitter.
+    #[serde(default = "gain_default")]
+    pub gain: f32,
+
+    /// Optional user-defined name for this object.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg(feature = "names")]
+    pub name: Option<String>,
+
+    /// Records a name discarded because the `names` feature is disabled.
+    #[serde(default, skip_serializing_if = "::extras::Void::is_absent")]
+    #[cfg(not(feature = "names"))]
+    pub name: ::extras::Void,
+
+    /// The spatial properties of a positional emitter.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub positional: Option<Positional>,
+
+    /// The sources mixed by this emitter.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sources: Vec<Index<Source>>,
+}
+
+impl Validate for Emitter {
+    fn validate_minimally<P, R>(&self, root: &Root, path: P, report: &mut R)
+        where P: Fn() -> Path, R: FnMut(&Fn() -> Path, Error)
+    {
+        self.sources.validate_minimally(root, || path().field("sources"), report);
+    }
+
+    fn validate_completely<P, R>(&self, root: &Root, path: P, report: &mut R)
+        where P: Fn() -> Path, R: FnMut(&Fn() -> Path, Error)
+    {
+        if !VALID_EMITTER_TYPES.contains(&self.type_.as_str()) {
+            report(&|| path().field("type"), Error::Invalid);
+        }
+        if self.positional.is_some() && self.type_ != "positional" {
+            report(&|| path().field("positional"), Error::Invalid);
+        }
+        if !non_negative(self.gain) {
+            report(&|| path().field("gain"), Error::Invalid);
+        }
+        self.positional.validate_completely(root, || path().field("positional"), report);
+    }
+}
+
+/// The cone and distance attenuation of a positional emitter.
+///
+/// The cone points along the local -Z axis of the node.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Positional {
+    /// The angle in radians of the cone inside which there is no attenuation.
+    #[serde(default = "cone_angle_default", rename = "coneInnerAngle")]
+    pub cone_inner_angle: f32,
+
+    /// The angle in radians of the cone outside which the volume is
+    /// `cone_outer_gain`.
+    #[serde(default = "cone_angle_default", rename = "coneOuterAngle")]
+    pub cone_outer_angle: f32,
+
+    /// The linear volume multiplier outside the outer cone.
+    #[serde(default, rename = "coneOuterGain")]
+    pub cone_outer_gain: f32,
+
+    /// The distance attenuation model, one of `VALID_DISTANCE_MODELS`.
+    #[serde(default = "distance_model_default", rename = "distanceModel")]
+    pub distance_model: String,
+
+    /// The distance beyond which the volume is no longer attenuated, for the
+    /// `linear` model.
+    #[serde(default = "max_distance_default", rename = "maxDistance")]
+    pub max_distance: f32,
+
+    /// The distance at which attenuation begins.
+    #[serde(default = "ref_distance_default", rename = "refDistance")]
+    pub ref_distance: f32,
+
+    /// How quickly the volume falls off with distance.
+    #[serde(default = "rolloff_factor_default", rename = "rolloffFactor")]
+    pub rolloff_factor: f32,
+}
+
+fn cone_angle_default() -> f32 {
+    2.0 * ::std::f32::consts::PI
+}
+
+fn distance_model_default() -> String {
+    "inverse".to_string()
+}
+
+fn max_distance_default() -> f32 {
+    10000.0
+}
+
+fn ref_distance_default() -> f32 {
+    1.0
+}
+
+fn rolloff_factor_default() -> f32 {
+    1.0
+}
+
+impl Validate for Positional {
+    fn validate_completely<P, R>(&self, _: &Root, path: P, report: &mut R)
+        where P: Fn() -> Path, R: FnMut(&Fn() -> Path, Error)
+    {
+        if !VALID_DISTANCE_MODELS.contains(&self.distance_model.as_str()) {
+            report(&|| path().field("distanceModel"), Error::Invalid);
+        }
+        let full_turn = 2.0 * ::std::f32::consts::PI;
+        let angles = [
+            ("coneInnerAngle", self.cone_inner_angle),
+            ("coneOuterAngle", self.cone_outer_angle),
+        ];
+        for &(field, angle) in &angles {
+            if !non_negative(angle) || angle > full_turn {
+                report(&|| path().field(field), Error::Invalid);
+            }
+        }
+        if self.cone_inner_angle > self.cone_outer_angle {
+            report(&|| path().field("coneInnerAngle"), Error::Invalid);
+        }
+        if !(0.0..=1.0).contains(&self.cone_outer_gain) {
+            report(&|| path().field("coneOuterGain"), Error::Invalid);
+        }
+        if self.max_distance <= 0.0 || self.max_distance.is_nan() {
+            report(&|| path().field("maxDistance"), Error::Invalid);
+        }
+        let factors = [
+            ("refDistance", self.ref_distance),
+            ("rolloffFactor", self.rolloff_factor),
+        ];
+        for &(field, factor) in &factors {
+            if !non_negative(factor) {
+                report(&|| path().field(field), Error::Invalid);
+            }
+        }
+    }
+}
+
+/// The emitter of a node, from the `KHR_audio` extension.
+#[derive(Clone, Debug, Deserialize, Serialize, Validate)]
+pub struct NodeEmitter {
+    /// The positional emitter placed at the node.
+    pub emitter: Index<Emitter>,
+}
+
+/// The global emitters of a scene, from the `KHR_audio` extension.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, Validate)]
+pub struct SceneEmitters {
+    /// The global emitters played while the scene is active.
+    #[serde(default)]
+    pub emitters: Vec<Index<Emitter>>,
+}
+
+impl Get<Clip> for Root {
+    fn get(&self, index: &Index<Clip>) -> Option<&Clip> {
+        self.extensions.audio.as_ref()?.clips.get(index.value())
+    }
+}
+
+impl Get<Source> for Root {
+    fn get(&self, index: &Index<Source>) -> Option<&Source> {
+        self.extensions.audio.as_ref()?.sources.get(index.value())
+    }
+}
+
+impl Get<Emitter> for Root {
+    fn get(&self, index: &Index<Emitter>) -> Option<&Emitter> {
+        self.extensions.audio.as_ref()?.emitters.get(index.value())
+    }
+}
@@ -1,7 +1,43 @@
+use serde_json::{Map, Value};
+
 /// A buffer points to binary data representing geometry, animations, or skins.
-#[derive(Clone, Debug, Default, Deserialize, Validate)]
-pub struct Buffer {}
+#[derive(Clone, Debug, Default, Deserialize, Serialize, Validate)]
+pub struct Buffer {
+    /// Integrity metadata of the `GLTFRS_buffer_checksum` vendor extension.
+    #[serde(default, rename = "GLTFRS_buffer_checksum", skip_serializing_if = "Option::is_none")]
+    pub buffer_checksum: Option<BufferChecksum>,
+
+    /// Extensions not modelled by this crate, or disabled by its features, by name.
+    #[serde(flatten)]
+    pub others: Map<String, Value>,
+}
+
+impl Buffer {
+    /// Returns whether there is no extension data to serialize.
+    pub fn is_empty(&self) -> bool {
+        self.buffer_checksum.is_none() && self.others.is_empty()
+    }
+}
+
+/// Checksum of the first `byteLength` bytes of a buffer, used to detect
+/// corrupted assets at load time.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, Validate)]
+pub struct BufferChecksum {
+    /// The CRC-32 (IEEE 802.3) checksum of the buffer data.
+    pub crc32: u32,
+}
 
 /// A view into a buffer generally representing a subset of the buffer.
-#[derive(Clone, Debug, Default, Deserialize, Validate)]
-pub struct View {}
+#[derive(Clone, Debug, Default, Deserialize, Serialize, Validate)]
+pub struct View {
+    /// Extensions not modelled by this crate, or disabled by its features, by name.
+    #[serde(flatten)]
+    pub others: Map<String, Value>,
+}
+
+impl View {
+    /// Returns whether there is no extension data to serialize.
+    pub fn is_empty(&self) -> bool {
+        self.others.is_empty()
+    }
+}
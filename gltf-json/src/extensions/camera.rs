@@ -1,14 +1,49 @@
+use serde_json::{Map, Value};
+
 /// A camera's projection.
 ///
 /// A node can reference a camera to apply a transform to place the camera in the
 /// scene.
-#[derive(Clone, Debug, Default, Deserialize, Validate)]
-pub struct Camera {}
+#[derive(Clone, Debug, Default, Deserialize, Serialize, Validate)]
+pub struct Camera {
+    /// Extensions not modelled by this crate, or disabled by its features, by name.
+    #[serde(flatten)]
+    pub others: Map<String, Value>,
+}
+
+impl Camera {
+    /// Returns whether there is no extension data to serialize.
+    pub fn is_empty(&self) -> bool {
+        self.others.is_empty()
+    }
+}
 
 /// Values for an orthographic camera.
-#[derive(Clone, Debug, Default, Deserialize, Validate)]
-pub struct Orthographic {}
+#[derive(Clone, Debug, Default, Deserialize, Serialize, Validate)]
+pub struct Orthographic {
+    /// Extensions not modelled by this crate, or disabled by its features, by name.
+    #[serde(flatten)]
+    pub others: Map<String, Value>,
+}
+
+impl Orthographic {
+    /// Returns whether there is no extension data to serialize.
+    pub fn is_empty(&self) -> bool {
+        self.others.is_empty()
+    }
+}
 
 /// Values for a perspective camera.
-#[derive(Clone, Debug, Default, Deserialize, Validate)]
-pub struct Perspective {}
+#[derive(Clone, Debug, Default, Deserialize, Serialize, Validate)]
+pub struct Perspective {
+    /// Extensions not modelled by this crate, or disabled by its features, by name.
+    #[serde(flatten)]
+    pub others: Map<String, Value>,
+}
+
+impl Perspective {
+    /// Returns whether there is no extension data to serialize.
+    pub fn is_empty(&self) -> bool {
+        self.others.is_empty()
+    }
+}
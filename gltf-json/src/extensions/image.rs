@@ -1,3 +1,16 @@
+use serde_json::{Map, Value};
+
 /// Image data used to create a texture.
-#[derive(Clone, Debug, Default, Deserialize, Validate)]
-pub struct Image {}
+#[derive(Clone, Debug, Default, Deserialize, Serialize, Validate)]
+pub struct Image {
+    /// Extensions not modelled by this crate, or disabled by its features, by name.
+    #[serde(flatten)]
+    pub others: Map<String, Value>,
+}
+
+impl Image {
+    /// Returns whether there is no extension data to serialize.
+    pub fn is_empty(&self) -> bool {
+        self.others.is_empty()
+    }
+}
@@ -0,0 +1,305 @@
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+
+use validation::{Error, Validate};
+use {Path, Root};
+
+/// The name of the `KHR_interactivity` extension.
+pub const INTERACTIVITY: &'static str = "KHR_interactivity";
+
+/// Properties not modelled by the typed structures, kept verbatim so that
+/// they are written back unchanged.
+pub type Unknown = Map<String, Value>;
+
+/// The document level behavior graphs of the `KHR_interactivity` extension.
+///
+/// The extension is still a draft, so when its contents do not match the typed
+/// structures they are kept as raw JSON instead of failing to parse.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum Interactivity {
+    /// The behavior graphs, parsed into typed structures.
+    Typed(Graphs),
+
+    /// The extension object, unparsed.
+    Raw(Value),
+}
+
+impl Interactivity {
+    /// Returns the typed behavior graphs, or `None` if the extension could
+    /// not be parsed into them.
+    pub fn typed(&self) -> Option<&Graphs> {
+        match *self {
+            Interactivity::Typed(ref graphs) => Some(graphs),
+            Interactivity::Raw(_) => None,
+        }
+    }
+
+    /// Returns the typed behavior graphs for modification, or `None` if the
+    /// extension could not be parsed into them.
+    pub fn typed_mut(&mut self) -> Option<&mut Graphs> {
+        match *self {
+            Interactivity::Typed(ref mut graphs) => Some(graphs),
+            Interactivity::Raw(_) => None,
+        }
+    }
+}
+
+impl Validate for Interactivity {
+    fn validate_minimally<P, R>(&self, root: &Root, path: P, report: &mut R)
+        where P: Fn() -> Path, R: FnMut(&Fn() -> Path, Error)
+    {
+        if let Interactivity::Typed(ref graphs) = *self {
+            graphs.validate_minimally(root, path, report);
+        }
+    }
+}
+
+/// A set of behavior graphs, one of which is active.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct Graphs {
+    /// The behavior graphs of the asset.
+    #[serde(default)]
+    pub graphs: Vec<Graph>,
+
+    /// The index of the graph to run, defaulting to the first.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub graph: Option<u32>,
+
+    /// Properties not modelled by this struct.
+    #[serde(flatten)]
+    pub unknown: Unknown,
+}
+
+impl Validate for Graphs {
+    fn validate_minimally<P, R>(&self, root: &Root, path: P, report: &mut R)
+        where P: Fn() -> Path, R: FnMut(&Fn() -> Path, Error)
+    {
+        if let Some(graph) = self.graph {
+            if graph as usize >= self.graphs.len() {
+                report(&|| path().field("graph"), Error::IndexOutOfBounds);
+            }
+        }
+        for (index, graph) in self.graphs.iter().enumerate() {
+            graph.validate_minimally(root, || path().field("graphs").index(index), report);
+        }
+    }
+}
+
+/// A behavior graph of typed variables, custom events, and nodes connected by
+/// value and flow sockets.
+///
+/// Indices inside a graph refer to the arrays of the same graph.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct Graph {
+    /// The value types used by the graph.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub types: Vec<Type>,
+
+    /// The variables of the graph.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub variables: Vec<Variable>,
+
+    /// The custom events sent or received by the graph.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub events: Vec<Event>,
+
+    /// The operations instantiated by the nodes of the graph.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub declarations: Vec<Declaration>,
+
+    /// The nodes of the graph.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub nodes: Vec<Node>,
+
+    /// Properties not modelled by this struct.
+    #[serde(flatten)]
+    pub unknown: Unknown,
+}
+
+impl Validate for Graph {
+    fn validate_minimally<P, R>(&self, _: &Root, path: P, report: &mut R)
+        where P: Fn() -> Path, R: FnMut(&Fn() -> Path, Error)
+    {
+        let types = self.types.len();
+        let declarations = self.declarations.len();
+        let nodes = self.nodes.len();
+        let mut check = |index: u32, len: usize, path: &Fn() -> Path| {
+            if index as usize >= len {
+                report(path, Error::IndexOutOfBounds);
+            }
+        };
+
+        for (i, variable) in self.variables.iter().enumerate() {
+            check(variable.type_, types, &|| path().field("variables").index(i).field("type"));
+        }
+        for (i, event) in self.events.iter().enumerate() {
+            for (key, socket) in &event.values {
+                if let Some(type_) = socket.type_ {
+                    check(type_, types, &|| {
+                        path().field("events").index(i).field("values").key(key).field("type")
+                    });
+                }
+            }
+        }
+        for (i, node) in self.nodes.iter().enumerate() {
+            let node_path = || path().field("nodes").index(i);
+            check(node.declaration, declarations, &|| node_path().field("declaration"));
+            for (key, socket) in &node.values {
+                if let Some(type_) = socket.type_ {
+                    check(type_, types, &|| node_path().field("values").key(key).field("type"));
+                }
+                if let Some(source) = socket.node {
+                    check(source, nodes, &|| node_path().field("values").key(key).field("node"));
+                }
+            }
+            for (key, flow) in &node.flows {
+                check(flow.node, nodes, &|| node_path().field("flows").key(key).field("node"));
+            }
+        }
+    }
+}
+
+/// A value type of a graph.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct Type {
+    /// The type signature, for example `float3` or `bool`.
+    pub signature: String,
+
+    /// Properties not modelled by this struct.
+    #[serde(flatten)]
+    pub unknown: Unknown,
+}
+
+/// A variable of a graph.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct Variable {
+    /// The index of the variable type.
+    #[serde(rename = "type")]
+    pub type_: u32,
+
+    /// The initial value of the variable.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value: Option<Value>,
+
+    /// Optional user-defined name for this object.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg(feature = "names")]
+    pub name: Option<String>,
+
+    /// Records a name discarded because the `names` feature is disabled.
+    #[serde(default, skip_serializing_if = "::extras::Void::is_absent")]
+    #[cfg(not(feature = "names"))]
+    pub name: ::extras::Void,
+
+    /// Properties not modelled by this struct.
+    #[serde(flatten)]
+    pub unknown: Unknown,
+}
+
+/// A custom event of a graph.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct Event {
+    /// The identifier shared with the application sending or receiving the
+    /// event.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+
+    /// The typed values carried by the event.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub values: BTreeMap<String, ValueSocket>,
+
+    /// Properties not modelled by this struct.
+    #[serde(flatten)]
+    pub unknown: Unknown,
+}
+
+/// An operation, identified by its operation name.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct Declaration {
+    /// The operation name, for example `math/add` or `event/onStart`.
+    pub op: String,
+
+    /// The extension defining the operation, if it is not a core operation.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub extension: Option<String>,
+
+    /// Properties not modelled by this struct.
+    #[serde(flatten)]
+    pub unknown: Unknown,
+}
+
+/// An instance of a declared operation.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct Node {
+    /// The index of the declaration of the operation.
+    pub declaration: u32,
+
+    /// The input value sockets, by socket name.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub values: BTreeMap<String, ValueSocket>,
+
+    /// The output flow sockets, by socket name.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub flows: BTreeMap<String, Flow>,
+
+    /// The static configuration of the operation, by parameter name.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub configuration: BTreeMap<String, Configuration>,
+
+    /// Properties not modelled by this struct.
+    #[serde(flatten)]
+    pub unknown: Unknown,
+}
+
+/// The input of a value socket, either a constant or the output of another
+/// node.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct ValueSocket {
+    /// The index of the type of a constant.
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub type_: Option<u32>,
+
+    /// The constant value.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value: Option<Value>,
+
+    /// The index of the node providing the value.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub node: Option<u32>,
+
+    /// The output socket of `node` providing the value.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub socket: Option<String>,
+
+    /// Properties not modelled by this struct.
+    #[serde(flatten)]
+    pub unknown: Unknown,
+}
+
+/// A connection from an output flow socket to the input flow socket of
+/// another node.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct Flow {
+    /// The index of the node activated by the flow.
+    pub node: u32,
+
+    /// The input flow socket of `node`, `in` if not given.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub socket: Option<String>,
+
+    /// Properties not modelled by this struct.
+    #[serde(flatten)]
+    pub unknown: Unknown,
+}
+
+/// A configuration parameter of a node.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct Configuration {
+    /// The parameter value.
+    pub value: Value,
+
+    /// Properties not modelled by this struct.
+    #[serde(flatten)]
+    pub unknown: Unknown,
+}
@@ -1,16 +1,169 @@
+use serde_json::{Map, Value};
+use validation::{Error, Validate};
+use {material, texture, Path, Root};
+
+/// The name of the `KHR_materials_clearcoat` extension.
+const MATERIALS_CLEARCOAT: &'static str = "KHR_materials_clearcoat";
+
 /// The material appearance of a primitive.
-#[derive(Clone, Debug, Default, Deserialize, Validate)]
-pub struct Material {}
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct Material {
+    /// A clear coat layer on top of the material, e.g. the lacquer of car
+    /// paint, of the `KHR_materials_clearcoat` extension.
+    #[serde(default, rename = "KHR_materials_clearcoat", skip_serializing_if = "Option::is_none")]
+    pub clearcoat: Option<Clearcoat>,
+
+    /// Extensions not modelled by this crate, or disabled by its features, by name.
+    #[serde(flatten)]
+    pub others: Map<String, Value>,
+}
+
+impl Material {
+    /// Returns whether there is no extension data to serialize.
+    pub fn is_empty(&self) -> bool {
+        self.clearcoat.is_none() && self.others.is_empty()
+    }
+}
+
+impl Validate for Material {
+    fn validate_minimally<P, R>(&self, root: &Root, path: P, report: &mut R)
+        where P: Fn() -> Path, R: FnMut(&Fn() -> Path, Error)
+    {
+        if let Some(ref extension) = self.clearcoat {
+            extension.validate_minimally(root, || path().field(MATERIALS_CLEARCOAT), report);
+        }
+    }
+
+    fn validate_completely<P, R>(&self, root: &Root, path: P, report: &mut R)
+        where P: Fn() -> Path, R: FnMut(&Fn() -> Path, Error)
+    {
+        if let Some(ref extension) = self.clearcoat {
+            extension.validate_completely(root, || path().field(MATERIALS_CLEARCOAT), report);
+        }
+    }
+}
+
+/// A clear coat layer on top of a material.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Clearcoat {
+    /// The intensity of the clear coat layer, from `0.0` to `1.0`.
+    #[serde(rename = "clearcoatFactor")]
+    pub clearcoat_factor: f32,
+
+    /// The clear coat layer intensity texture, sampled from the R channel and
+    /// multiplied by `clearcoat_factor`.
+    #[serde(rename = "clearcoatTexture", skip_serializing_if = "Option::is_none")]
+    pub clearcoat_texture: Option<texture::Info>,
+
+    /// The roughness of the clear coat layer, from `0.0` to `1.0`.
+    #[serde(rename = "clearcoatRoughnessFactor")]
+    pub clearcoat_roughness_factor: f32,
+
+    /// The clear coat layer roughness texture, sampled from the G channel and
+    /// multiplied by `clearcoat_roughness_factor`.
+    #[serde(rename = "clearcoatRoughnessTexture", skip_serializing_if = "Option::is_none")]
+    pub clearcoat_roughness_texture: Option<texture::Info>,
+
+    /// The tangent space normal map of the clear coat layer, independent of
+    /// the normal map of the material.
+    #[serde(rename = "clearcoatNormalTexture", skip_serializing_if = "Option::is_none")]
+    pub clearcoat_normal_texture: Option<material::NormalTexture>,
+}
+
+impl Validate for Clearcoat {
+    fn validate_minimally<P, R>(&self, root: &Root, path: P, report: &mut R)
+        where P: Fn() -> Path, R: FnMut(&Fn() -> Path, Error)
+    {
+        self.clearcoat_texture.validate_minimally(
+            root,
+            || path().field("clearcoatTexture"),
+            report,
+        );
+        self.clearcoat_roughness_texture.validate_minimally(
+            root,
+            || path().field("clearcoatRoughnessTexture"),
+            report,
+        );
+        self.clearcoat_normal_texture.validate_minimally(
+            root,
+            || path().field("clearcoatNormalTexture"),
+            report,
+        );
+    }
+
+    fn validate_completely<P, R>(&self, root: &Root, path: P, report: &mut R)
+        where P: Fn() -> Path, R: FnMut(&Fn() -> Path, Error)
+    {
+        self.clearcoat_texture.validate_completely(
+            root,
+            || path().field("clearcoatTexture"),
+            report,
+        );
+        self.clearcoat_roughness_texture.validate_completely(
+            root,
+            || path().field("clearcoatRoughnessTexture"),
+            report,
+        );
+        self.clearcoat_normal_texture.validate_completely(
+            root,
+            || path().field("clearcoatNormalTexture"),
+            report,
+        );
+        let factors = [
+            ("clearcoatFactor", self.clearcoat_factor),
+            ("clearcoatRoughnessFactor", self.clearcoat_roughness_factor),
+        ];
+        for &(field, factor) in &factors {
+            if !(0.0..=1.0).contains(&factor) {
+                report(&|| path().field(field), Error::Invalid);
+            }
+        }
+    }
+}
 
 /// A set of parameter values that are used to define the metallic-roughness
 /// material model from Physically-Based Rendering (PBR) methodology.
-#[derive(Clone, Debug, Default, Deserialize, Validate)]
-pub struct PbrMetallicRoughness {}
+#[derive(Clone, Debug, Default, Deserialize, Serialize, Validate)]
+pub struct PbrMetallicRoughness {
+    /// Extensions not modelled by this crate, or disabled by its features, by name.
+    #[serde(flatten)]
+    pub others: Map<String, Value>,
+}
+
+impl PbrMetallicRoughness {
+    /// Returns whether there is no extension data to serialize.
+    pub fn is_empty(&self) -> bool {
+        self.others.is_empty()
+    }
+}
 
 /// Defines the normal texture of a material.
-#[derive(Clone, Debug, Default, Deserialize, Validate)]
-pub struct NormalTexture {}
+#[derive(Clone, Debug, Default, Deserialize, Serialize, Validate)]
+pub struct NormalTexture {
+    /// Extensions not modelled by this crate, or disabled by its features, by name.
+    #[serde(flatten)]
+    pub others: Map<String, Value>,
+}
+
+impl NormalTexture {
+    /// Returns whether there is no extension data to serialize.
+    pub fn is_empty(&self) -> bool {
+        self.others.is_empty()
+    }
+}
 
 /// Defines the occlusion texture of a material.
-#[derive(Clone, Debug, Default, Deserialize, Validate)]
-pub struct OcclusionTexture {}
+#[derive(Clone, Debug, Default, Deserialize, Serialize, Validate)]
+pub struct OcclusionTexture {
+    /// Extensions not modelled by this crate, or disabled by its features, by name.
+    #[serde(flatten)]
+    pub others: Map<String, Value>,
+}
+
+impl OcclusionTexture {
+    /// Returns whether there is no extension data to serialize.
+    pub fn is_empty(&self) -> bool {
+        self.others.is_empty()
+    }
+}
@@ -1,10 +1,143 @@
+use serde_json::{Map, Value};
+use validation::{Error, Validate};
+use {texture, Index, Path, Root};
+
 /// A set of primitives to be rendered.
 ///
 /// A node can contain one or more meshes and its transform places the meshes in
 /// the scene.
-#[derive(Clone, Debug, Default, Deserialize, Validate)]
-pub struct Mesh {}
+#[derive(Clone, Debug, Default, Deserialize, Serialize, Validate)]
+pub struct Mesh {
+    /// Extensions not modelled by this crate, or disabled by its features, by name.
+    #[serde(flatten)]
+    pub others: Map<String, Value>,
+}
+
+impl Mesh {
+    /// Returns whether there is no extension data to serialize.
+    pub fn is_empty(&self) -> bool {
+        self.others.is_empty()
+    }
+}
 
 /// Geometry to be rendered with the given material.
-#[derive(Clone, Debug, Default, Deserialize, Validate)]
-pub struct Primitive {}
+#[derive(Clone, Debug, Default, Deserialize, Serialize, Validate)]
+pub struct Primitive {
+    /// Feature IDs of the `EXT_mesh_features` extension.
+    #[serde(default, rename = "EXT_mesh_features", skip_serializing_if = "Option::is_none")]
+    pub mesh_features: Option<MeshFeatures>,
+
+    /// Extensions not modelled by this crate, or disabled by its features, by name.
+    #[serde(flatten)]
+    pub others: Map<String, Value>,
+}
+
+impl Primitive {
+    /// Returns whether there is no extension data to serialize.
+    pub fn is_empty(&self) -> bool {
+        self.mesh_features.is_none() && self.others.is_empty()
+    }
+}
+
+/// Feature IDs identifying the parts of a primitive, e.g. the buildings of a
+/// city tile.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, Validate)]
+pub struct MeshFeatures {
+    /// The sets of feature IDs of the primitive.
+    #[serde(rename = "featureIds")]
+    pub feature_ids: Vec<FeatureId>,
+}
+
+/// A set of feature IDs, assigned per vertex or per texel.
+///
+/// IDs are read from the `_FEATURE_ID_n` vertex attribute given by
+/// `attribute`, or from `texture`. If neither is given, the ID of each vertex
+/// is its index.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct FeatureId {
+    /// The number of unique features, excluding `null_feature_id`.
+    #[serde(rename = "featureCount")]
+    pub feature_count: u32,
+
+    /// The ID marking vertices or texels without a feature.
+    #[serde(default, rename = "nullFeatureId", skip_serializing_if = "Option::is_none")]
+    pub null_feature_id: Option<u32>,
+
+    /// A label identifying the set of feature IDs.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+
+    /// The set index `n` of the `_FEATURE_ID_n` attribute holding the IDs.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub attribute: Option<u32>,
+
+    /// The texture holding the IDs.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub texture: Option<FeatureIdTexture>,
+
+    /// The index of the property table of the `EXT_structural_metadata`
+    /// extension holding the properties of the features.
+    #[serde(default, rename = "propertyTable", skip_serializing_if = "Option::is_none")]
+    pub property_table: Option<u32>,
+}
+
+impl Validate for FeatureId {
+    fn validate_minimally<P, R>(&self, root: &Root, path: P, report: &mut R)
+        where P: Fn() -> Path, R: FnMut(&Fn() -> Path, Error)
+    {
+        self.texture.validate_minimally(root, || path().field("texture"), report);
+        if let Some(table) = self.property_table {
+            let tables = root.extensions.structural_metadata
+                .as_ref()
+                .map(|metadata| metadata.property_tables.len())
+                .unwrap_or(0);
+            if table as usize >= tables {
+                report(&|| path().field("propertyTable"), Error::IndexOutOfBounds);
+            }
+        }
+    }
+
+    fn validate_completely<P, R>(&self, root: &Root, path: P, report: &mut R)
+        where P: Fn() -> Path, R: FnMut(&Fn() -> Path, Error)
+    {
+        self.texture.validate_completely(root, || path().field("texture"), report);
+        if self.feature_count == 0 {
+            report(&|| path().field("featureCount"), Error::Invalid);
+        }
+    }
+}
+
+/// A texture holding feature IDs in some of its channels.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct FeatureIdTexture {
+    /// The index of the texture.
+    pub index: Index<texture::Texture>,
+
+    /// The set index of the `TEXCOORD_n` attribute used for lookups.
+    #[serde(default, rename = "texCoord")]
+    pub tex_coord: u32,
+
+    /// The channels holding the bytes of each ID, least significant first.
+    #[serde(default = "channels_default")]
+    pub channels: Vec<u32>,
+}
+
+fn channels_default() -> Vec<u32> {
+    vec![0]
+}
+
+impl Validate for FeatureIdTexture {
+    fn validate_minimally<P, R>(&self, root: &Root, path: P, report: &mut R)
+        where P: Fn() -> Path, R: FnMut(&Fn() -> Path, Error)
+    {
+        self.index.validate_minimally(root, || path().field("index"), report);
+    }
+
+    fn validate_completely<P, R>(&self, _root: &Root, path: P, report: &mut R)
+        where P: Fn() -> Path, R: FnMut(&Fn() -> Path, Error)
+    {
+        if self.channels.is_empty() || self.channels.iter().any(|&channel| channel > 3) {
+            report(&|| path().field("channels"), Error::Invalid);
+        }
+    }
+}
@@ -0,0 +1,565 @@
+use serde::{de, ser};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fmt;
+
+use validation::{Checked, Error, Validate};
+use {buffer, Index, Path, Root};
+
+/// All valid metadata element types.
+pub const VALID_ELEMENT_TYPES: &'static [&'static str] = &[
+    "SCALAR",
+    "VEC2",
+    "VEC3",
+    "VEC4",
+    "MAT2",
+    "MAT3",
+    "MAT4",
+    "STRING",
+    "BOOLEAN",
+    "ENUM",
+];
+
+/// All valid metadata component types.
+pub const VALID_COMPONENT_TYPES: &'static [&'static str] = &[
+    "INT8",
+    "UINT8",
+    "INT16",
+    "UINT16",
+    "INT32",
+    "UINT32",
+    "INT64",
+    "UINT64",
+    "FLOAT32",
+    "FLOAT64",
+];
+
+/// The element type of a class property.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum ElementType {
+    /// A single number.
+    Scalar,
+
+    /// A vector of two numbers.
+    Vec2,
+
+    /// A vector of three numbers.
+    Vec3,
+
+    /// A vector of four numbers.
+    Vec4,
+
+    /// A 2x2 matrix of numbers stored in column-major order.
+    Mat2,
+
+    /// A 3x3 matrix of numbers stored in column-major order.
+    Mat3,
+
+    /// A 4x4 matrix of numbers stored in column-major order.
+    Mat4,
+
+    /// A UTF-8 string.
+    String,
+
+    /// A boolean stored as a single bit.
+    Boolean,
+
+    /// A value of an enum defined by the schema.
+    Enum,
+}
+
+impl ElementType {
+    /// Returns the number of components of a single element.
+    pub fn components(&self) -> usize {
+        match *self {
+            ElementType::Vec2 => 2,
+            ElementType::Vec3 => 3,
+            ElementType::Vec4 | ElementType::Mat2 => 4,
+            ElementType::Mat3 => 9,
+            ElementType::Mat4 => 16,
+            _ => 1,
+        }
+    }
+}
+
+/// The component type of numeric properties, enum values, and offsets.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum ComponentType {
+    /// Corresponds to `INT8`.
+    I8,
+
+    /// Corresponds to `UINT8`.
+    U8,
+
+    /// Corresponds to `INT16`.
+    I16,
+
+    /// Corresponds to `UINT16`.
+    U16,
+
+    /// Corresponds to `INT32`.
+    I32,
+
+    /// Corresponds to `UINT32`.
+    U32,
+
+    /// Corresponds to `INT64`.
+    I64,
+
+    /// Corresponds to `UINT64`.
+    U64,
+
+    /// Corresponds to `FLOAT32`.
+    F32,
+
+    /// Corresponds to `FLOAT64`.
+    F64,
+}
+
+impl ComponentType {
+    /// Returns the number of bytes this value represents.
+    pub fn size(&self) -> usize {
+        match *self {
+            ComponentType::I8 | ComponentType::U8 => 1,
+            ComponentType::I16 | ComponentType::U16 => 2,
+            ComponentType::I32 | ComponentType::U32 | ComponentType::F32 => 4,
+            ComponentType::I64 | ComponentType::U64 | ComponentType::F64 => 8,
+        }
+    }
+
+    /// Returns the name of the component type, e.g. `"UINT16"`.
+    pub fn as_str(&self) -> &'static str {
+        match *self {
+            ComponentType::I8 => "INT8",
+            ComponentType::U8 => "UINT8",
+            ComponentType::I16 => "INT16",
+            ComponentType::U16 => "UINT16",
+            ComponentType::I32 => "INT32",
+            ComponentType::U32 => "UINT32",
+            ComponentType::I64 => "INT64",
+            ComponentType::U64 => "UINT64",
+            ComponentType::F32 => "FLOAT32",
+            ComponentType::F64 => "FLOAT64",
+        }
+    }
+}
+
+/// Metadata of the `EXT_structural_metadata` extension.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct StructuralMetadata {
+    /// The schema embedded in the asset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schema: Option<Schema>,
+
+    /// The URI of an external schema, used in place of `schema`.
+    #[serde(default, rename = "schemaUri", skip_serializing_if = "Option::is_none")]
+    pub schema_uri: Option<String>,
+
+    /// Tables of per-feature property values stored in buffer views.
+    #[serde(default, rename = "propertyTables", skip_serializing_if = "Vec::is_empty")]
+    pub property_tables: Vec<PropertyTable>,
+}
+
+impl Validate for StructuralMetadata {
+    fn validate_minimally<P, R>(&self, root: &Root, path: P, report: &mut R)
+        where P: Fn() -> Path, R: FnMut(&Fn() -> Path, Error)
+    {
+        self.schema.validate_minimally(root, || path().field("schema"), report);
+        self.property_tables
+            .validate_minimally(root, || path().field("propertyTables"), report);
+    }
+
+    fn validate_completely<P, R>(&self, root: &Root, path: P, report: &mut R)
+        where P: Fn() -> Path, R: FnMut(&Fn() -> Path, Error)
+    {
+        self.schema.validate_completely(root, || path().field("schema"), report);
+        self.property_tables
+            .validate_completely(root, || path().field("propertyTables"), report);
+
+        if self.schema.is_none() && self.schema_uri.is_none() {
+            report(&|| path().field("schema"), Error::Missing);
+        }
+
+        // Tables can only be checked against an embedded schema.
+        let schema = match self.schema {
+            Some(ref schema) => schema,
+            None => return,
+        };
+        for (index, table) in self.property_tables.iter().enumerate() {
+            let path = || path().field("propertyTables").index(index);
+            let class = match schema.classes.get(&table.class) {
+                Some(class) => class,
+                None => {
+                    report(&|| path().field("class"), Error::Invalid);
+                    continue;
+                },
+            };
+            for (name, property) in &table.properties {
+                let path = || path().field("properties").key(name);
+                let definition = match class.properties.get(name) {
+                    Some(definition) => definition,
+                    None => {
+                        report(&path, Error::Invalid);
+                        continue;
+                    },
+                };
+                let type_ = match definition.type_ {
+                    Checked::Valid(type_) => type_,
+                    Checked::Invalid => continue,
+                };
+                if type_ == ElementType::String && property.string_offsets.is_none() {
+                    report(&|| path().field("stringOffsets"), Error::Missing);
+                }
+                if definition.array && definition.count.is_none()
+                    && property.array_offsets.is_none()
+                {
+                    report(&|| path().field("arrayOffsets"), Error::Missing);
+                }
+            }
+            for (name, definition) in &class.properties {
+                if definition.required && !table.properties.contains_key(name) {
+                    report(&|| path().field("properties").key(name), Error::Missing);
+                }
+            }
+        }
+    }
+}
+
+/// A set of classes and enums describing the structure of metadata.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, Validate)]
+pub struct Schema {
+    /// The unique identifier of the schema.
+    pub id: String,
+
+    /// The name of the schema.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+
+    /// The description of the schema.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    /// The application specific version of the schema.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+
+    /// The classes of the schema, by class ID.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub classes: BTreeMap<String, Class>,
+
+    /// The enums of the schema, by enum ID.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub enums: BTreeMap<String, Enum>,
+}
+
+/// A class of entities sharing the same properties.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, Validate)]
+pub struct Class {
+    /// The name of the class.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+
+    /// The description of the class.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    /// The properties of the class, by property ID.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub properties: BTreeMap<String, ClassProperty>,
+}
+
+/// The definition of a property of a class.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ClassProperty {
+    /// The name of the property.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+
+    /// The description of the property.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    /// The element type.
+    #[serde(rename = "type")]
+    pub type_: Checked<ElementType>,
+
+    /// The component type of numeric element types.
+    #[serde(default, rename = "componentType", skip_serializing_if = "Option::is_none")]
+    pub component_type: Option<Checked<ComponentType>>,
+
+    /// The ID of the enum of `ENUM` element types.
+    #[serde(default, rename = "enumType", skip_serializing_if = "Option::is_none")]
+    pub enum_type: Option<String>,
+
+    /// Whether each value is an array of elements.
+    #[serde(default)]
+    pub array: bool,
+
+    /// The number of elements of fixed length arrays.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub count: Option<u32>,
+
+    /// Whether integer values are normalized to the range `[0, 1]` or
+    /// `[-1, 1]`.
+    #[serde(default)]
+    pub normalized: bool,
+
+    /// The offset added to numeric values after normalization.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub offset: Option<Value>,
+
+    /// The scale applied to numeric values after normalization.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scale: Option<Value>,
+
+    /// The maximum value after the offset and scale are applied.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max: Option<Value>,
+
+    /// The minimum value after the offset and scale are applied.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min: Option<Value>,
+
+    /// Whether every entity must provide a value.
+    #[serde(default)]
+    pub required: bool,
+
+    /// The value that marks a missing value.
+    #[serde(default, rename = "noData", skip_serializing_if = "Option::is_none")]
+    pub no_data: Option<Value>,
+
+    /// The value used in place of missing values.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default: Option<Value>,
+}
+
+impl Validate for ClassProperty {
+    fn validate_minimally<P, R>(&self, root: &Root, path: P, report: &mut R)
+        where P: Fn() -> Path, R: FnMut(&Fn() -> Path, Error)
+    {
+        self.type_.validate_minimally(root, || path().field("type"), report);
+        self.component_type
+            .validate_minimally(root, || path().field("componentType"), report);
+    }
+
+    fn validate_completely<P, R>(&self, root: &Root, path: P, report: &mut R)
+        where P: Fn() -> Path, R: FnMut(&Fn() -> Path, Error)
+    {
+        self.validate_minimally(root, &path, report);
+        let numeric = match self.type_ {
+            Checked::Valid(ElementType::String)
+                | Checked::Valid(ElementType::Boolean)
+                | Checked::Valid(ElementType::Enum) => false,
+            Checked::Valid(_) => true,
+            Checked::Invalid => return,
+        };
+        if numeric && self.component_type.is_none() {
+            report(&|| path().field("componentType"), Error::Missing);
+        }
+        if self.type_ == Checked::Valid(ElementType::Enum) && self.enum_type.is_none() {
+            report(&|| path().field("enumType"), Error::Missing);
+        }
+        if self.count.map(|count| count < 2).unwrap_or(false) {
+            report(&|| path().field("count"), Error::Invalid);
+        }
+    }
+}
+
+/// A set of named integer values.
+#[derive(Clone, Debug, Deserialize, Serialize, Validate)]
+pub struct Enum {
+    /// The name of the enum.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+
+    /// The description of the enum.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    /// The integer type of the values, `UINT16` by default.
+    #[serde(default = "value_type_default", rename = "valueType")]
+    pub value_type: Checked<ComponentType>,
+
+    /// The values of the enum.
+    pub values: Vec<EnumValue>,
+}
+
+fn value_type_default() -> Checked<ComponentType> {
+    Checked::Valid(ComponentType::U16)
+}
+
+/// A named value of an enum.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct EnumValue {
+    /// The name of the value.
+    pub name: String,
+
+    /// The description of the value.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    /// The integer value.
+    pub value: i64,
+}
+
+impl Validate for EnumValue {}
+
+/// Property values of a set of features, stored in buffer views.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, Validate)]
+pub struct PropertyTable {
+    /// The name of the table.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+
+    /// The ID of the class of the features.
+    pub class: String,
+
+    /// The number of features, i.e. rows, of the table.
+    pub count: u32,
+
+    /// The columns of the table, by class property ID.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub properties: BTreeMap<String, PropertyTableProperty>,
+}
+
+/// The values of a single property of a property table.
+#[derive(Clone, Debug, Deserialize, Serialize, Validate)]
+pub struct PropertyTableProperty {
+    /// The buffer view holding the values.
+    pub values: Index<buffer::View>,
+
+    /// The buffer view holding the element offsets of variable length arrays.
+    #[serde(default, rename = "arrayOffsets", skip_serializing_if = "Option::is_none")]
+    pub array_offsets: Option<Index<buffer::View>>,
+
+    /// The buffer view holding the byte offsets of strings.
+    #[serde(default, rename = "stringOffsets", skip_serializing_if = "Option::is_none")]
+    pub string_offsets: Option<Index<buffer::View>>,
+
+    /// The integer type of `array_offsets`, `UINT32` by default.
+    #[serde(default = "offset_type_default", rename = "arrayOffsetType")]
+    pub array_offset_type: Checked<ComponentType>,
+
+    /// The integer type of `string_offsets`, `UINT32` by default.
+    #[serde(default = "offset_type_default", rename = "stringOffsetType")]
+    pub string_offset_type: Checked<ComponentType>,
+
+    /// Overrides the offset of the class property.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub offset: Option<Value>,
+
+    /// Overrides the scale of the class property.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scale: Option<Value>,
+
+    /// Overrides the maximum of the class property.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max: Option<Value>,
+
+    /// Overrides the minimum of the class property.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min: Option<Value>,
+}
+
+fn offset_type_default() -> Checked<ComponentType> {
+    Checked::Valid(ComponentType::U32)
+}
+
+impl<'de> de::Deserialize<'de> for Checked<ElementType> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where D: de::Deserializer<'de>
+    {
+        struct Visitor;
+        impl<'de> de::Visitor<'de> for Visitor {
+            type Value = Checked<ElementType>;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "any of: {:?}", VALID_ELEMENT_TYPES)
+            }
+
+            fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+                where E: de::Error
+            {
+                use self::ElementType::*;
+                use validation::Checked::*;
+                Ok(match value {
+                    "SCALAR" => Valid(Scalar),
+                    "VEC2" => Valid(Vec2),
+                    "VEC3" => Valid(Vec3),
+                    "VEC4" => Valid(Vec4),
+                    "MAT2" => Valid(Mat2),
+                    "MAT3" => Valid(Mat3),
+                    "MAT4" => Valid(Mat4),
+                    "STRING" => Valid(String),
+                    "BOOLEAN" => Valid(Boolean),
+                    "ENUM" => Valid(Enum),
+                    _ => Invalid,
+                })
+            }
+        }
+        deserializer.deserialize_str(Visitor)
+    }
+}
+
+impl ser::Serialize for ElementType {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: ser::Serializer
+    {
+        serializer.serialize_str(match *self {
+            ElementType::Scalar => "SCALAR",
+            ElementType::Vec2 => "VEC2",
+            ElementType::Vec3 => "VEC3",
+            ElementType::Vec4 => "VEC4",
+            ElementType::Mat2 => "MAT2",
+            ElementType::Mat3 => "MAT3",
+            ElementType::Mat4 => "MAT4",
+            ElementType::String => "STRING",
+            ElementType::Boolean => "BOOLEAN",
+            ElementType::Enum => "ENUM",
+        })
+    }
+}
+
+impl<'de> de::Deserialize<'de> for Checked<ComponentType> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where D: de::Deserializer<'de>
+    {
+        struct Visitor;
+        impl<'de> de::Visitor<'de> for Visitor {
+            type Value = Checked<ComponentType>;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "any of: {:?}", VALID_COMPONENT_TYPES)
+            }
+
+            fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+                where E: de::Error
+            {
+                use self::ComponentType::*;
+                use validation::Checked::*;
+                Ok(match value {
+                    "INT8" => Valid(I8),
+                    "UINT8" => Valid(U8),
+                    "INT16" => Valid(I16),
+                    "UINT16" => Valid(U16),
+                    "INT32" => Valid(I32),
+                    "UINT32" => Valid(U32),
+                    "INT64" => Valid(I64),
+                    "UINT64" => Valid(U64),
+                    "FLOAT32" => Valid(F32),
+                    "FLOAT64" => Valid(F64),
+                    _ => Invalid,
+                })
+            }
+        }
+        deserializer.deserialize_str(Visitor)
+    }
+}
+
+impl ser::Serialize for ComponentType {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: ser::Serializer
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
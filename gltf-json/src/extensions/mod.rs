@@ -7,6 +7,10 @@ pub mod animation;
 /// Contains `Asset` metadata.
 pub mod asset;
 
+/// Contains the clips, sources, and emitters of `KHR_audio`.
+#[cfg(feature = "audio")]
+pub mod audio;
+
 /// Contains `Buffer`, `View`, and other related data structures.
 pub mod buffer;
 
@@ -16,12 +20,24 @@ pub mod camera;
 /// Contains `Image` and other related data structures.
 pub mod image;
 
+/// Contains the behavior graphs of `KHR_interactivity`.
+#[cfg(feature = "interactivity")]
+pub mod interactivity;
+
 /// Contains `Material` and other related data structures.
 pub mod material;
 
 /// Contains `Mesh` and other related data structures.
 pub mod mesh;
 
+/// Contains the schema and property tables of `EXT_structural_metadata`.
+pub mod metadata;
+
+/// Contains the rigid bodies, colliders, and joints of
+/// `KHR_physics_rigid_bodies` and the shapes of `KHR_implicit_shapes`.
+#[cfg(feature = "physics")]
+pub mod physics;
+
 /// Contains `Root`.
 pub mod root;
 
@@ -40,4 +56,10 @@ pub use self::root::Root;
 pub const ENABLED_EXTENSIONS: &'static [&'static str] = &[];
 
 /// Names of glTF 2.0 extensions supported by the library.
-pub const SUPPORTED_EXTENSIONS: &'static [&'static str] = &[];
+pub const SUPPORTED_EXTENSIONS: &'static [&'static str] = &[
+    "EXT_mesh_features",
+    "EXT_structural_metadata",
+    "EXT_texture_filter_anisotropic",
+    "GLTFRS_buffer_checksum",
+    "KHR_materials_clearcoat",
+];
@@ -0,0 +1,580 @@
+use root::Get;
+use validation::{non_negative, Error, Validate};
+use {Index, Path, Root};
+
+/// The name of the `KHR_implicit_shapes` extension.
+pub const IMPLICIT_SHAPES: &'static str = "KHR_implicit_shapes";
+
+/// The name of the `KHR_physics_rigid_bodies` extension.
+pub const PHYSICS_RIGID_BODIES: &'static str = "KHR_physics_rigid_bodies";
+
+/// All valid implicit shape types.
+pub const VALID_SHAPE_TYPES: &'static [&'static str] = &[
+    "box",
+    "capsule",
+    "cylinder",
+    "sphere",
+];
+
+/// All valid joint drive types.
+pub const VALID_DRIVE_TYPES: &'static [&'static str] = &["linear", "angular"];
+
+/// All valid joint drive modes.
+pub const VALID_DRIVE_MODES: &'static [&'static str] = &["force", "acceleration"];
+
+/// All valid friction and restitution combine modes.
+pub const VALID_COMBINE_MODES: &'static [&'static str] = &[
+    "average",
+    "minimum",
+    "maximum",
+    "multiply",
+];
+
+/// The document level shapes of the `KHR_implicit_shapes` extension.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, Validate)]
+pub struct ImplicitShapes {
+    /// Shapes referenced by colliders and triggers.
+    #[serde(default)]
+    pub shapes: Vec<Shape>,
+}
+
+/// A shape described by a few parameters rather than a mesh.
+///
+/// Exactly one of the properties named by `type_` is present.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Shape {
+    /// The shape type, one of `VALID_SHAPE_TYPES`.
+    #[serde(rename = "type")]
+    pub type_: String,
+
+    /// The parameters of a box shape.
+    #[serde(rename = "box", skip_serializing_if = "Option::is_none")]
+    pub box_: Option<BoxShape>,
+
+    /// The parameters of a capsule shape.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub capsule: Option<CapsuleShape>,
+
+    /// The parameters of a cylinder shape.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cylinder: Option<CapsuleShape>,
+
+    /// The parameters of a sphere shape.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sphere: Option<SphereShape>,
+}
+
+impl Validate for Shape {
+    fn validate_completely<P, R>(&self, root: &Root, path: P, report: &mut R)
+        where P: Fn() -> Path, R: FnMut(&Fn() -> Path, Error)
+    {
+        let present = match self.type_.as_str() {
+            "box" => self.box_.is_some(),
+            "capsule" => self.capsule.is_some(),
+            "cylinder" => self.cylinder.is_some(),
+            "sphere" => self.sphere.is_some(),
+            _ => {
+                report(&|| path().field("type"), Error::Invalid);
+                return;
+            },
+        };
+        if !present {
+            report(&|| path().field(&self.type_), Error::Missing);
+        }
+        self.box_.validate_completely(root, || path().field("box"), report);
+        self.capsule.validate_completely(root, || path().field("capsule"), report);
+        self.cylinder.validate_completely(root, || path().field("cylinder"), report);
+        self.sphere.validate_completely(root, || path().field("sphere"), report);
+    }
+}
+
+/// An axis aligned box centered on the origin.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct BoxShape {
+    /// The extents of the box along each axis.
+    #[serde(default = "box_size_default")]
+    pub size: [f32; 3],
+}
+
+fn box_size_default() -> [f32; 3] {
+    [1.0, 1.0, 1.0]
+}
+
+impl Validate for BoxShape {
+    fn validate_completely<P, R>(&self, _: &Root, path: P, report: &mut R)
+        where P: Fn() -> Path, R: FnMut(&Fn() -> Path, Error)
+    {
+        if !self.size.iter().cloned().all(non_negative) {
+            report(&|| path().field("size"), Error::Invalid);
+        }
+    }
+}
+
+/// A capsule or cylinder along the Y axis, centered on the origin.
+///
+/// The radii may differ to describe tapered shapes.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct CapsuleShape {
+    /// The distance between the centers of the two caps.
+    #[serde(default = "shape_height_default")]
+    pub height: f32,
+
+    /// The radius of the cap at the bottom, in the negative Y direction.
+    #[serde(default = "shape_radius_default", rename = "radiusBottom")]
+    pub radius_bottom: f32,
+
+    /// The radius of the cap at the top, in the positive Y direction.
+    #[serde(default = "shape_radius_default", rename = "radiusTop")]
+    pub radius_top: f32,
+}
+
+fn shape_height_default() -> f32 {
+    0.5
+}
+
+fn shape_radius_default() -> f32 {
+    0.25
+}
+
+impl Validate for CapsuleShape {
+    fn validate_completely<P, R>(&self, _: &Root, path: P, report: &mut R)
+        where P: Fn() -> Path, R: FnMut(&Fn() -> Path, Error)
+    {
+        let lengths = [
+            ("height", self.height),
+            ("radiusBottom", self.radius_bottom),
+            ("radiusTop", self.radius_top),
+        ];
+        for &(field, length) in &lengths {
+            if !non_negative(length) {
+                report(&|| path().field(field), Error::Invalid);
+            }
+        }
+    }
+}
+
+/// A sphere centered on the origin.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct SphereShape {
+    /// The radius of the sphere.
+    #[serde(default = "sphere_radius_default")]
+    pub radius: f32,
+}
+
+fn sphere_radius_default() -> f32 {
+    0.5
+}
+
+impl Validate for SphereShape {
+    fn validate_completely<P, R>(&self, _: &Root, path: P, report: &mut R)
+        where P: Fn() -> Path, R: FnMut(&Fn() -> Path, Error)
+    {
+        if !non_negative(self.radius) {
+            report(&|| path().field("radius"), Error::Invalid);
+        }
+    }
+}
+
+/// The document level objects of the `KHR_physics_rigid_bodies` extension.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, Validate)]
+pub struct Physics {
+    /// Surface properties referenced by colliders.
+    #[serde(default, rename = "physicsMaterials", skip_serializing_if = "Vec::is_empty")]
+    pub physics_materials: Vec<PhysicsMaterial>,
+
+    /// Filters restricting which colliders interact.
+    #[serde(default, rename = "collisionFilters", skip_serializing_if = "Vec::is_empty")]
+    pub collision_filters: Vec<CollisionFilter>,
+
+    /// Joint constraints referenced by the joints of nodes.
+    #[serde(default, rename = "physicsJoints", skip_serializing_if = "Vec::is_empty")]
+    pub physics_joints: Vec<JointDefinition>,
+}
+
+/// The friction and restitution of a collider surface.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct PhysicsMaterial {
+    /// The friction coefficient when at rest.
+    #[serde(default = "friction_default", rename = "staticFriction")]
+    pub static_friction: f32,
+
+    /// The friction coefficient when sliding.
+    #[serde(default = "friction_default", rename = "dynamicFriction")]
+    pub dynamic_friction: f32,
+
+    /// The coefficient of restitution, from `0.0` for no bounce to `1.0`.
+    #[serde(default)]
+    pub restitution: f32,
+
+    /// How the friction of two colliders is combined, one of
+    /// `VALID_COMBINE_MODES`.
+    #[serde(rename = "frictionCombine", skip_serializing_if = "Option::is_none")]
+    pub friction_combine: Option<String>,
+
+    /// How the restitution of two colliders is combined, one of
+    /// `VALID_COMBINE_MODES`.
+    #[serde(rename = "restitutionCombine", skip_serializing_if = "Option::is_none")]
+    pub restitution_combine: Option<String>,
+}
+
+fn friction_default() -> f32 {
+    0.6
+}
+
+impl Validate for PhysicsMaterial {
+    fn validate_completely<P, R>(&self, _: &Root, path: P, report: &mut R)
+        where P: Fn() -> Path, R: FnMut(&Fn() -> Path, Error)
+    {
+        let coefficients = [
+            ("staticFriction", self.static_friction),
+            ("dynamicFriction", self.dynamic_friction),
+            ("restitution", self.restitution),
+        ];
+        for &(field, coefficient) in &coefficients {
+            if !non_negative(coefficient) {
+                report(&|| path().field(field), Error::Invalid);
+            }
+        }
+        let modes = [
+            ("frictionCombine", &self.friction_combine),
+            ("restitutionCombine", &self.restitution_combine),
+        ];
+        for &(field, mode) in &modes {
+            if let Some(ref mode) = *mode {
+                if !VALID_COMBINE_MODES.contains(&mode.as_str()) {
+                    report(&|| path().field(field), Error::Invalid);
+                }
+            }
+        }
+    }
+}
+
+/// Restricts the colliders a collider interacts with by named systems.
+///
+/// At most one of `collide_with_systems` and `not_collide_with_systems` is
+/// non-empty.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, Validate)]
+pub struct CollisionFilter {
+    /// The systems the collider belongs to.
+    #[serde(default, rename = "collisionSystems", skip_serializing_if = "Vec::is_empty")]
+    pub collision_systems: Vec<String>,
+
+    /// The only systems the collider interacts with.
+    #[serde(default, rename = "collideWithSystems", skip_serializing_if = "Vec::is_empty")]
+    pub collide_with_systems: Vec<String>,
+
+    /// The systems the collider does not interact with.
+    #[serde(default, rename = "notCollideWithSystems", skip_serializing_if = "Vec::is_empty")]
+    pub not_collide_with_systems: Vec<String>,
+}
+
+/// The limits and drives of a joint between two nodes.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, Validate)]
+pub struct JointDefinition {
+    /// Limits on the relative motion of the two nodes.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub limits: Vec<JointLimit>,
+
+    /// Motors driving the relative motion of the two nodes.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub drives: Vec<JointDrive>,
+}
+
+/// A limit on the relative translation or rotation of two nodes along some
+/// axes.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct JointLimit {
+    /// The axes, `0` to `2` for X to Z, along which translation is limited.
+    #[serde(default, rename = "linearAxes", skip_serializing_if = "Vec::is_empty")]
+    pub linear_axes: Vec<u32>,
+
+    /// The axes, `0` to `2` for X to Z, around which rotation is limited.
+    #[serde(default, rename = "angularAxes", skip_serializing_if = "Vec::is_empty")]
+    pub angular_axes: Vec<u32>,
+
+    /// The lower limit, in meters or radians.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min: Option<f32>,
+
+    /// The upper limit, in meters or radians.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max: Option<f32>,
+
+    /// The spring stiffness of a soft limit, or `None` for a hard limit.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stiffness: Option<f32>,
+
+    /// The spring damping of a soft limit.
+    #[serde(default)]
+    pub damping: f32,
+}
+
+impl Validate for JointLimit {
+    fn validate_completely<P, R>(&self, _: &Root, path: P, report: &mut R)
+        where P: Fn() -> Path, R: FnMut(&Fn() -> Path, Error)
+    {
+        if self.linear_axes.iter().any(|&axis| axis > 2) {
+            report(&|| path().field("linearAxes"), Error::Invalid);
+        }
+        if self.angular_axes.iter().any(|&axis| axis > 2) {
+            report(&|| path().field("angularAxes"), Error::Invalid);
+        }
+        if let (Some(min), Some(max)) = (self.min, self.max) {
+            if min > max {
+                report(&|| path().field("min"), Error::Invalid);
+            }
+        }
+        if self.stiffness.is_some_and(|stiffness| !non_negative(stiffness)) {
+            report(&|| path().field("stiffness"), Error::Invalid);
+        }
+        if !non_negative(self.damping) {
+            report(&|| path().field("damping"), Error::Invalid);
+        }
+    }
+}
+
+/// A motor driving the relative translation or rotation of two nodes along
+/// a single axis.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct JointDrive {
+    /// Whether the drive is `"linear"` or `"angular"`.
+    #[serde(rename = "type")]
+    pub type_: String,
+
+    /// Whether the drive applies a `"force"` or an `"acceleration"`.
+    pub mode: String,
+
+    /// The axis, `0` to `2` for X to Z, of the drive.
+    pub axis: u32,
+
+    /// The maximum force the drive may apply.
+    #[serde(rename = "maxForce", skip_serializing_if = "Option::is_none")]
+    pub max_force: Option<f32>,
+
+    /// The target position, in meters or radians.
+    #[serde(rename = "positionTarget", skip_serializing_if = "Option::is_none")]
+    pub position_target: Option<f32>,
+
+    /// The target velocity, in meters or radians per second.
+    #[serde(rename = "velocityTarget", skip_serializing_if = "Option::is_none")]
+    pub velocity_target: Option<f32>,
+
+    /// The stiffness towards the target position.
+    #[serde(default)]
+    pub stiffness: f32,
+
+    /// The damping towards the target velocity.
+    #[serde(default)]
+    pub damping: f32,
+}
+
+impl Validate for JointDrive {
+    fn validate_completely<P, R>(&self, _: &Root, path: P, report: &mut R)
+        where P: Fn() -> Path, R: FnMut(&Fn() -> Path, Error)
+    {
+        if !VALID_DRIVE_TYPES.contains(&self.type_.as_str()) {
+            report(&|| path().field("type"), Error::Invalid);
+        }
+        if !VALID_DRIVE_MODES.contains(&self.mode.as_str()) {
+            report(&|| path().field("mode"), Error::Invalid);
+        }
+        if self.axis > 2 {
+            report(&|| path().field("axis"), Error::Invalid);
+        }
+        if self.max_force.is_some_and(|force| !non_negative(force)) {
+            report(&|| path().field("maxForce"), Error::Invalid);
+        }
+        if !non_negative(self.stiffness) {
+            report(&|| path().field("stiffness"), Error::Invalid);
+        }
+        if !non_negative(self.damping) {
+            report(&|| path().field("damping"), Error::Invalid);
+        }
+    }
+}
+
+/// The physics properties of a node, from the `KHR_physics_rigid_bodies`
+/// extension.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, Validate)]
+pub struct RigidBody {
+    /// Makes the node and its descendants without their own motion a
+    /// simulated rigid body.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub motion: Option<Motion>,
+
+    /// A solid shape the node contributes to its rigid body, or to the static
+    /// world if there is none.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub collider: Option<Collider>,
+
+    /// A volume reporting overlaps without a physical response.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trigger: Option<Trigger>,
+
+    /// A constraint between this node and another.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub joint: Option<Joint>,
+}
+
+/// The mass properties and initial velocity of a rigid body.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Motion {
+    /// Whether the body is moved by animation instead of the simulation.
+    #[serde(default, rename = "isKinematic")]
+    pub is_kinematic: bool,
+
+    /// The mass in kilograms, or `None` to derive it from the colliders.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mass: Option<f32>,
+
+    /// The center of mass in the space of the node.
+    #[serde(default, rename = "centerOfMass")]
+    pub center_of_mass: [f32; 3],
+
+    /// The principal moments of inertia, or `None` to derive them from the
+    /// colliders.
+    #[serde(rename = "inertiaDiagonal", skip_serializing_if = "Option::is_none")]
+    pub inertia_diagonal: Option<[f32; 3]>,
+
+    /// The `[x, y, z, w]` rotation of the principal axes of inertia.
+    #[serde(rename = "inertiaOrientation", skip_serializing_if = "Option::is_none")]
+    pub inertia_orientation: Option<[f32; 4]>,
+
+    /// The initial linear velocity in meters per second.
+    #[serde(default, rename = "linearVelocity")]
+    pub linear_velocity: [f32; 3],
+
+    /// The initial angular velocity in radians per second.
+    #[serde(default, rename = "angularVelocity")]
+    pub angular_velocity: [f32; 3],
+
+    /// The factor applied to gravity for this body.
+    #[serde(default = "gravity_factor_default", rename = "gravityFactor")]
+    pub gravity_factor: f32,
+}
+
+fn gravity_factor_default() -> f32 {
+    1.0
+}
+
+impl Validate for Motion {
+    fn validate_completely<P, R>(&self, _: &Root, path: P, report: &mut R)
+        where P: Fn() -> Path, R: FnMut(&Fn() -> Path, Error)
+    {
+        if self.mass.is_some_and(|mass| !non_negative(mass)) {
+            report(&|| path().field("mass"), Error::Invalid);
+        }
+        let inertia = self.inertia_diagonal.unwrap_or_default();
+        if !inertia.iter().cloned().all(non_negative) {
+            report(&|| path().field("inertiaDiagonal"), Error::Invalid);
+        }
+    }
+}
+
+/// The geometry of a collider or trigger.
+///
+/// Exactly one of `shape` and `node` is set.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct Geometry {
+    /// An implicit shape of the `KHR_implicit_shapes` extension.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shape: Option<Index<Shape>>,
+
+    /// A node whose mesh provides the geometry.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub node: Option<Index<::scene::Node>>,
+
+    /// Whether the convex hull of the mesh is used instead of the mesh.
+    #[serde(default, rename = "convexHull")]
+    pub convex_hull: bool,
+}
+
+impl Validate for Geometry {
+    fn validate_minimally<P, R>(&self, root: &Root, path: P, report: &mut R)
+        where P: Fn() -> Path, R: FnMut(&Fn() -> Path, Error)
+    {
+        self.shape.validate_minimally(root, || path().field("shape"), report);
+        self.node.validate_minimally(root, || path().field("node"), report);
+    }
+
+    fn validate_completely<P, R>(&self, _: &Root, path: P, report: &mut R)
+        where P: Fn() -> Path, R: FnMut(&Fn() -> Path, Error)
+    {
+        if self.shape.is_some() == self.node.is_some() {
+            report(&|| path().field("shape"), Error::Invalid);
+        }
+    }
+}
+
+/// A solid shape of a rigid body or of the static world.
+#[derive(Clone, Debug, Deserialize, Serialize, Validate)]
+pub struct Collider {
+    /// The shape of the collider.
+    pub geometry: Geometry,
+
+    /// The surface properties of the collider.
+    #[serde(rename = "physicsMaterial", skip_serializing_if = "Option::is_none")]
+    pub physics_material: Option<Index<PhysicsMaterial>>,
+
+    /// The filter restricting what the collider interacts with.
+    #[serde(rename = "collisionFilter", skip_serializing_if = "Option::is_none")]
+    pub collision_filter: Option<Index<CollisionFilter>>,
+}
+
+/// A volume reporting overlaps with colliders.
+///
+/// A trigger has either its own geometry or a set of child nodes whose
+/// triggers it combines.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, Validate)]
+pub struct Trigger {
+    /// The shape of the trigger.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub geometry: Option<Geometry>,
+
+    /// Nodes whose triggers form a compound trigger.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub nodes: Vec<Index<::scene::Node>>,
+
+    /// The filter restricting what the trigger reports.
+    #[serde(rename = "collisionFilter", skip_serializing_if = "Option::is_none")]
+    pub collision_filter: Option<Index<CollisionFilter>>,
+}
+
+/// A constraint between the node and another node.
+#[derive(Clone, Debug, Deserialize, Serialize, Validate)]
+pub struct Joint {
+    /// The node at the other end of the joint.
+    #[serde(rename = "connectedNode")]
+    pub connected_node: Index<::scene::Node>,
+
+    /// The limits and drives of the joint.
+    pub joint: Index<JointDefinition>,
+
+    /// Whether the colliders of the two bodies collide with each other.
+    #[serde(default, rename = "enableCollision")]
+    pub enable_collision: bool,
+}
+
+impl Get<Shape> for Root {
+    fn get(&self, index: &Index<Shape>) -> Option<&Shape> {
+        self.extensions.implicit_shapes.as_ref()?.shapes.get(index.value())
+    }
+}
+
+impl Get<PhysicsMaterial> for Root {
+    fn get(&self, index: &Index<PhysicsMaterial>) -> Option<&PhysicsMaterial> {
+        self.extensions.physics.as_ref()?.physics_materials.get(index.value())
+    }
+}
+
+impl Get<CollisionFilter> for Root {
+    fn get(&self, index: &Index<CollisionFilter>) -> Option<&CollisionFilter> {
+        self.extensions.physics.as_ref()?.collision_filters.get(index.value())
+    }
+}
+
+impl Get<JointDefinition> for Root {
+    fn get(&self, index: &Index<JointDefinition>) -> Option<&JointDefinition> {
+        self.extensions.physics.as_ref()?.physics_joints.get(index.value())
+    }
+}
@@ -1,3 +1,57 @@
+#[cfg(feature = "audio")]
+use extensions::audio;
+#[cfg(feature = "interactivity")]
+use extensions::interactivity;
+use extensions::metadata;
+#[cfg(feature = "physics")]
+use extensions::physics;
+use serde_json::{Map, Value};
+
 /// The root object of a glTF 2.0 asset.
-#[derive(Clone, Debug, Default, Deserialize, Validate)]
-pub struct Root {}
+#[derive(Clone, Debug, Default, Deserialize, Serialize, Validate)]
+pub struct Root {
+    /// Clips, sources, and emitters of the `KHR_audio` extension.
+    #[cfg(feature = "audio")]
+    #[serde(default, rename = "KHR_audio", skip_serializing_if = "Option::is_none")]
+    pub audio: Option<audio::Audio>,
+
+    /// Behavior graphs of the `KHR_interactivity` extension.
+    #[cfg(feature = "interactivity")]
+    #[serde(default, rename = "KHR_interactivity", skip_serializing_if = "Option::is_none")]
+    pub interactivity: Option<interactivity::Interactivity>,
+
+    /// Metadata of the `EXT_structural_metadata` extension.
+    #[serde(default, rename = "EXT_structural_metadata", skip_serializing_if = "Option::is_none")]
+    pub structural_metadata: Option<metadata::StructuralMetadata>,
+
+    /// Shapes of the `KHR_implicit_shapes` extension.
+    #[cfg(feature = "physics")]
+    #[serde(default, rename = "KHR_implicit_shapes", skip_serializing_if = "Option::is_none")]
+    pub implicit_shapes: Option<physics::ImplicitShapes>,
+
+    /// Materials, filters, and joints of the `KHR_physics_rigid_bodies`
+    /// extension.
+    #[cfg(feature = "physics")]
+    #[serde(default, rename = "KHR_physics_rigid_bodies", skip_serializing_if = "Option::is_none")]
+    pub physics: Option<physics::Physics>,
+
+    /// Extensions not modelled by this crate, or disabled by its features, by name.
+    #[serde(flatten)]
+    pub others: Map<String, Value>,
+}
+
+impl Root {
+    /// Returns whether there is no extension data to serialize.
+    pub fn is_empty(&self) -> bool {
+        let empty = self.structural_metadata.is_none() && self.others.is_empty();
+        #[cfg(feature = "audio")]
+        let empty = empty && self.audio.is_none();
+        #[cfg(feature = "interactivity")]
+        let empty = empty && self.interactivity.is_none();
+        #[cfg(feature = "physics")]
+        let empty = empty && self.implicit_shapes.is_none();
+        #[cfg(feature = "physics")]
+        let empty = empty && self.physics.is_none();
+        empty
+    }
+}
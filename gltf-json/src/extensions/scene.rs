@@ -1,3 +1,5 @@
+use serde_json::{Map, Value};
+
 /// A node in the node hierarchy.  When the node contains `skin`, all
 /// `mesh.primitives` must contain `JOINTS_0` and `WEIGHTS_0` attributes.
 /// A node can have either a `matrix` or any combination of
@@ -8,9 +10,55 @@
 /// identity. When a node is targeted for animation (referenced by an
 /// animation.channel.target), only TRS properties may be present; `matrix` will not
 /// be present.
-#[derive(Clone, Debug, Default, Deserialize, Validate)]
-pub struct Node {}
+#[derive(Clone, Debug, Default, Deserialize, Serialize, Validate)]
+pub struct Node {
+    /// The positional emitter of the `KHR_audio` extension.
+    #[cfg(feature = "audio")]
+    #[serde(default, rename = "KHR_audio", skip_serializing_if = "Option::is_none")]
+    pub audio: Option<::extensions::audio::NodeEmitter>,
+
+    /// The rigid body, collider, trigger, and joint of the
+    /// `KHR_physics_rigid_bodies` extension.
+    #[cfg(feature = "physics")]
+    #[serde(default, rename = "KHR_physics_rigid_bodies", skip_serializing_if = "Option::is_none")]
+    pub physics: Option<::extensions::physics::RigidBody>,
+
+    /// Extensions not modelled by this crate, or disabled by its features, by name.
+    #[serde(flatten)]
+    pub others: Map<String, Value>,
+}
+
+impl Node {
+    /// Returns whether there is no extension data to serialize.
+    pub fn is_empty(&self) -> bool {
+        let empty = self.others.is_empty();
+        #[cfg(feature = "audio")]
+        let empty = empty && self.audio.is_none();
+        #[cfg(feature = "physics")]
+        let empty = empty && self.physics.is_none();
+        empty
+    }
+}
 
 /// The root `Node`s of a scene.
-#[derive(Clone, Debug, Default, Deserialize, Validate)]
-pub struct Scene {}
+#[derive(Clone, Debug, Default, Deserialize, Serialize, Validate)]
+pub struct Scene {
+    /// The global emitters of the `KHR_audio` extension.
+    #[cfg(feature = "audio")]
+    #[serde(default, rename = "KHR_audio", skip_serializing_if = "Option::is_none")]
+    pub audio: Option<::extensions::audio::SceneEmitters>,
+
+    /// Extensions not modelled by this crate, or disabled by its features, by name.
+    #[serde(flatten)]
+    pub others: Map<String, Value>,
+}
+
+impl Scene {
+    /// Returns whether there is no extension data to serialize.
+    pub fn is_empty(&self) -> bool {
+        let empty = self.others.is_empty();
+        #[cfg(feature = "audio")]
+        let empty = empty && self.audio.is_none();
+        empty
+    }
+}
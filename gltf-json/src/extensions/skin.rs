@@ -1,3 +1,16 @@
+use serde_json::{Map, Value};
+
 /// Joints and matrices defining a skin.
-#[derive(Clone, Debug, Default, Deserialize, Validate)]
-pub struct Skin {}
+#[derive(Clone, Debug, Default, Deserialize, Serialize, Validate)]
+pub struct Skin {
+    /// Extensions not modelled by this crate, or disabled by its features, by name.
+    #[serde(flatten)]
+    pub others: Map<String, Value>,
+}
+
+impl Skin {
+    /// Returns whether there is no extension data to serialize.
+    pub fn is_empty(&self) -> bool {
+        self.others.is_empty()
+    }
+}
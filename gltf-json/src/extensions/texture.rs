@@ -1,11 +1,88 @@
+use serde_json::{Map, Value};
+use validation::{Error, Validate};
+use {Path, Root};
+
+/// The name of the `EXT_texture_filter_anisotropic` extension.
+const TEXTURE_FILTER_ANISOTROPIC: &'static str = "EXT_texture_filter_anisotropic";
+
 /// Texture sampler properties for filtering and wrapping modes.
-#[derive(Clone, Debug, Default, Deserialize, Validate)]
-pub struct Sampler {}
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct Sampler {
+    /// Anisotropic filtering hint of the proposed
+    /// `EXT_texture_filter_anisotropic` extension.
+    #[serde(default, rename = "EXT_texture_filter_anisotropic", skip_serializing_if = "Option::is_none")]
+    pub texture_filter_anisotropic: Option<TextureFilterAnisotropic>,
+
+    /// Extensions not modelled by this crate, or disabled by its features, by name.
+    #[serde(flatten)]
+    pub others: Map<String, Value>,
+}
+
+impl Sampler {
+    /// Returns whether there is no extension data to serialize.
+    pub fn is_empty(&self) -> bool {
+        self.texture_filter_anisotropic.is_none() && self.others.is_empty()
+    }
+}
+
+impl Validate for Sampler {
+    fn validate_completely<P, R>(&self, root: &Root, path: P, report: &mut R)
+        where P: Fn() -> Path, R: FnMut(&Fn() -> Path, Error)
+    {
+        if let Some(ref extension) = self.texture_filter_anisotropic {
+            extension.validate_completely(
+                root,
+                || path().field(TEXTURE_FILTER_ANISOTROPIC),
+                report,
+            );
+        }
+    }
+}
+
+/// Anisotropic filtering hint for a texture sampler.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct TextureFilterAnisotropic {
+    /// The maximum degree of anisotropy, where `1.0` disables anisotropic
+    /// filtering.
+    pub anisotropy: f32,
+}
+
+impl Validate for TextureFilterAnisotropic {
+    fn validate_completely<P, R>(&self, _root: &Root, path: P, report: &mut R)
+        where P: Fn() -> Path, R: FnMut(&Fn() -> Path, Error)
+    {
+        if !self.anisotropy.is_finite() || self.anisotropy < 1.0 {
+            report(&|| path().field("anisotropy"), Error::Invalid);
+        }
+    }
+}
 
 /// A texture and its sampler.
-#[derive(Clone, Debug, Default, Deserialize, Validate)]
-pub struct Texture {}
+#[derive(Clone, Debug, Default, Deserialize, Serialize, Validate)]
+pub struct Texture {
+    /// Extensions not modelled by this crate, or disabled by its features, by name.
+    #[serde(flatten)]
+    pub others: Map<String, Value>,
+}
 
-#[derive(Clone, Debug, Default, Deserialize, Validate)]
+impl Texture {
+    /// Returns whether there is no extension data to serialize.
+    pub fn is_empty(&self) -> bool {
+        self.others.is_empty()
+    }
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize, Validate)]
 /// Reference to a `Texture`.
-pub struct Info {}
+pub struct Info {
+    /// Extensions not modelled by this crate, or disabled by its features, by name.
+    #[serde(flatten)]
+    pub others: Map<String, Value>,
+}
+
+impl Info {
+    /// Returns whether there is no extension data to serialize.
+    pub fn is_empty(&self) -> bool {
+        self.others.is_empty()
+    }
+}
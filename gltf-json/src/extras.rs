@@ -1,5 +1,16 @@
 use std::fmt;
 
+#[cfg(feature = "extras")]
+use serde::de::{Deserialize, DeserializeOwned};
+#[cfg(feature = "extras")]
+use std::collections::BTreeMap;
+#[cfg(feature = "extras")]
+use serde_json;
+#[cfg(feature = "extras")]
+use std::error;
+#[cfg(feature = "extras")]
+use Root;
+
 pub use serde_json::Value;
 
 /// Data type of the `extras` attribute on all glTF objects.
@@ -11,10 +22,67 @@ pub type Extras = Option<Value>;
 pub type Extras = Void;
 
 /// Type representing no user-defined data.
-#[derive(Clone, Default, Deserialize, Validate)]
+///
+/// Data deserialized into `Void` is discarded, but its presence is recorded:
+/// serializing a `Void` that discarded data fails, so that writing a document
+/// back out never silently loses `extras` or names because the `extras` or
+/// `names` feature is disabled.
+#[derive(Clone, Default)]
 pub struct Void {
-    #[serde(default)]
-    _allow_unknown_fields: (),
+    /// Whether data was discarded during deserialization.
+    discarded: bool,
+}
+
+impl Void {
+    /// Returns whether data was discarded during deserialization.
+    pub fn is_discarded(&self) -> bool {
+        self.discarded
+    }
+
+    /// Returns whether there is nothing to serialize, i.e. no data was
+    /// discarded.
+    pub fn is_absent(&self) -> bool {
+        !self.discarded
+    }
+}
+
+impl<'de> ::serde::Deserialize<'de> for Void {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where D: ::serde::Deserializer<'de>
+    {
+        <::serde::de::IgnoredAny as ::serde::Deserialize>::deserialize(deserializer)?;
+        Ok(Void { discarded: true })
+    }
+}
+
+impl ::serde::Serialize for Void {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: ::serde::Serializer
+    {
+        use serde::ser::{Error, SerializeMap};
+        if self.discarded {
+            return Err(S::Error::custom(
+                "data was discarded because the `extras` or `names` feature is disabled",
+            ));
+        }
+        serializer.serialize_map(Some(0))?.end()
+    }
+}
+
+impl ::validation::Validate for Void {}
+
+/// Returns whether there is no user-defined data to serialize.
+#[cfg(feature = "extras")]
+pub fn is_empty(extras: &Extras) -> bool {
+    extras.is_none()
+}
+
+/// Returns whether there is no user-defined data to serialize.
+///
+/// Discarded data is not empty, so that serializing it fails.
+#[cfg(not(feature = "extras"))]
+pub fn is_empty(extras: &Extras) -> bool {
+    extras.is_absent()
 }
 
 impl fmt::Debug for Void {
@@ -28,3 +96,509 @@ impl fmt::Display for Void {
         write!(f, "{{}}")
     }
 }
+
+/// An error encountered while deserializing typed `extras` data.
+#[cfg(feature = "extras")]
+#[derive(Debug)]
+pub struct Error {
+    /// JSON pointer to the object whose `extras` failed to deserialize.
+    pub pointer: String,
+
+    /// The underlying deserialization error.
+    pub error: serde_json::Error,
+}
+
+#[cfg(feature = "extras")]
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {}", self.pointer, self.error)
+    }
+}
+
+#[cfg(feature = "extras")]
+impl error::Error for Error {
+    fn description(&self) -> &str {
+        "invalid extras"
+    }
+
+    fn cause(&self) -> Option<&error::Error> {
+        Some(&self.error)
+    }
+}
+
+/// Deserializes the `extras` of a single object into a user-defined type.
+///
+/// Returns `Ok(None)` if the object has no `extras`.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate gltf_json as json;
+/// #[macro_use]
+/// extern crate serde_derive;
+///
+/// #[derive(Deserialize)]
+/// struct Tag {
+///     collision: bool,
+/// }
+///
+/// # fn main() {
+/// let node: json::Node = json::from_str(r#"{"extras": {"collision": true}}"#).unwrap();
+/// let tag: Option<Tag> = json::extras::get(&node.extras).unwrap();
+/// assert!(tag.unwrap().collision);
+/// # }
+/// ```
+#[cfg(feature = "extras")]
+pub fn get<T: DeserializeOwned>(extras: &Extras) -> Result<Option<T>, serde_json::Error> {
+    match *extras {
+        Some(ref value) => T::deserialize(value).map(Some),
+        None => Ok(None),
+    }
+}
+
+/// Deserializes the `extras` of every object in the document that has any
+/// into a user-defined type.
+///
+/// Each value is paired with the JSON pointer of its object, e.g.
+/// `"/nodes/3"`, in document order. Objects without `extras` are skipped.
+///
+/// Use an enum or `Option` fields in `T` when different objects carry
+/// differently shaped `extras`.
+#[cfg(feature = "extras")]
+pub fn collect<T: DeserializeOwned>(root: &Root) -> Result<Vec<(String, T)>, Error> {
+    let mut objects: Vec<(String, &Extras)> = vec![];
+    objects.push((String::new(), &root.extras));
+    objects.push(("/asset".to_string(), &root.asset.extras));
+    macro_rules! push {
+        ($field:ident, $name:expr) => {
+            for (i, object) in root.$field.iter().enumerate() {
+                objects.push((format!("/{}/{}", $name, i), &object.extras));
+            }
+        };
+    }
+    push!(accessors, "accessors");
+    for (i, animation) in root.animations.iter().enumerate() {
+        objects.push((format!("/animations/{}", i), &animation.extras));
+        for (j, channel) in animation.channels.iter().enumerate() {
+            objects.push((format!("/animations/{}/channels/{}", i, j), &channel.extras));
+        }
+        for (j, sampler) in animation.samplers.iter().enumerate() {
+            objects.push((format!("/animations/{}/samplers/{}", i, j), &sampler.extras));
+        }
+    }
+    push!(buffers, "buffers");
+    push!(buffer_views, "bufferViews");
+    push!(cameras, "cameras");
+    push!(images, "images");
+    push!(materials, "materials");
+    for (i, mesh) in root.meshes.iter().enumerate() {
+        objects.push((format!("/meshes/{}", i), &mesh.extras));
+        for (j, primitive) in mesh.primitives.iter().enumerate() {
+            objects.push((format!("/meshes/{}/primitives/{}", i, j), &primitive.extras));
+        }
+    }
+    push!(nodes, "nodes");
+    push!(samplers, "samplers");
+    push!(scenes, "scenes");
+    push!(skins, "skins");
+    push!(textures, "textures");
+
+    let mut values = vec![];
+    for (pointer, extras) in objects {
+        match get(extras) {
+            Ok(Some(value)) => values.push((pointer, value)),
+            Ok(None) => {},
+            Err(error) => return Err(Error { pointer, error }),
+        }
+    }
+    Ok(values)
+}
+
+/// A custom property value as exported by Blender.
+///
+/// Blender writes the custom properties of objects, meshes, and materials to
+/// the `extras` of the corresponding nodes, meshes, and materials.
+#[cfg(feature = "extras")]
+#[derive(Clone, Debug, PartialEq)]
+pub enum Property {
+    /// A boolean property.
+    Bool(bool),
+
+    /// An integer property.
+    Int(i64),
+
+    /// A floating point property.
+    Float(f64),
+
+    /// A string property.
+    String(String),
+
+    /// An array property, e.g. a vector or color.
+    Array(Vec<Property>),
+
+    /// A nested property group.
+    Group(Properties),
+}
+
+/// Custom properties by name, in name order.
+#[cfg(feature = "extras")]
+pub type Properties = BTreeMap<String, Property>;
+
+#[cfg(feature = "extras")]
+impl Property {
+    /// Converts a JSON value, returning `None` for `null`.
+    fn from_value(value: &Value) -> Option<Property> {
+        match *value {
+            Value::Null => None,
+            Value::Bool(value) => Some(Property::Bool(value)),
+            Value::Number(ref number) => Some(match number.as_i64() {
+                Some(value) => Property::Int(value),
+                None => Property::Float(number.as_f64().unwrap()),
+            }),
+            Value::String(ref value) => Some(Property::String(value.clone())),
+            Value::Array(ref values) => {
+                Some(Property::Array(values.iter().filter_map(Property::from_value).collect()))
+            },
+            Value::Object(ref object) => {
+                let group = object
+                    .iter()
+                    .filter_map(|(key, value)| {
+                        Property::from_value(value).map(|value| (key.clone(), value))
+                    })
+                    .collect();
+                Some(Property::Group(group))
+            },
+        }
+    }
+
+    /// Returns the value of a boolean property.
+    ///
+    /// Blender versions predating boolean properties export booleans as the
+    /// integers `0` and `1`, so these are accepted too.
+    pub fn as_bool(&self) -> Option<bool> {
+        match *self {
+            Property::Bool(value) => Some(value),
+            Property::Int(0) => Some(false),
+            Property::Int(1) => Some(true),
+            _ => None,
+        }
+    }
+
+    /// Returns the value of an integer property.
+    pub fn as_i64(&self) -> Option<i64> {
+        match *self {
+            Property::Int(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Returns the value of a numeric property, converting integers.
+    pub fn as_f64(&self) -> Option<f64> {
+        match *self {
+            Property::Int(value) => Some(value as f64),
+            Property::Float(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Returns the value of a string property.
+    pub fn as_str(&self) -> Option<&str> {
+        match *self {
+            Property::String(ref value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Returns the elements of an array property.
+    pub fn as_array(&self) -> Option<&[Property]> {
+        match *self {
+            Property::Array(ref values) => Some(values),
+            _ => None,
+        }
+    }
+
+    /// Returns the elements of a numeric array property, e.g. a vector or
+    /// color, converting integers.
+    pub fn as_f64_array(&self) -> Option<Vec<f64>> {
+        self.as_array()?.iter().map(Property::as_f64).collect()
+    }
+
+    /// Returns the properties of a property group.
+    pub fn as_group(&self) -> Option<&Properties> {
+        match *self {
+            Property::Group(ref group) => Some(group),
+            _ => None,
+        }
+    }
+}
+
+/// Returns the Blender custom properties stored in the `extras` of an object.
+///
+/// Returns an empty map if the object has no `extras` or if its `extras` is
+/// not a JSON object. `null` values are skipped.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate gltf_json as json;
+/// # fn main() {
+/// let node: json::Node = json::from_str(r#"{
+///     "extras": { "mass": 2, "tint": [1.0, 0.5, 0.0], "tag": "crate" }
+/// }"#).unwrap();
+/// let properties = json::extras::properties(&node.extras);
+/// assert_eq!(Some(2.0), properties["mass"].as_f64());
+/// assert_eq!(Some(vec![1.0, 0.5, 0.0]), properties["tint"].as_f64_array());
+/// assert_eq!(Some("crate"), properties["tag"].as_str());
+/// # }
+/// ```
+#[cfg(feature = "extras")]
+pub fn properties(extras: &Extras) -> Properties {
+    match extras.as_ref().and_then(Property::from_value) {
+        Some(Property::Group(group)) => group,
+        _ => Properties::new(),
+    }
+}
+
+/// The `extras` key holding an image based lighting environment, on a scene
+/// or on the root object as the default for every scene.
+#[cfg(feature = "extras")]
+pub const ENVIRONMENT_KEY: &'static str = "environment";
+
+/// A reference to an environment map, either by image index or by URI.
+#[cfg(feature = "extras")]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct EnvironmentMap {
+    /// The index of an image of the asset holding the map.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub image: Option<u32>,
+
+    /// The URI of an image outside the asset, for maps shared between assets.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub uri: Option<String>,
+}
+
+/// An image based lighting environment stored in `extras` under
+/// `ENVIRONMENT_KEY`, for viewers to restore the lighting an asset was
+/// authored with.
+///
+/// Maps are equirectangular images in linear radiance. The `ior` of the
+/// surrounding medium lets viewers evaluate the Fresnel term of materials
+/// using `KHR_materials_ior` relative to it rather than to vacuum.
+#[cfg(feature = "extras")]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Environment {
+    /// The map used for specular reflections.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub specular: Option<EnvironmentMap>,
+
+    /// The pre-convolved map used for diffuse lighting, if any. Viewers
+    /// compute it from `specular` otherwise.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub diffuse: Option<EnvironmentMap>,
+
+    /// The scale applied to the radiance of the maps.
+    #[serde(
+        default = "environment_intensity_default",
+        skip_serializing_if = "environment_intensity_is_default"
+    )]
+    pub intensity: f32,
+
+    /// The counter-clockwise rotation of the maps about the +Y axis in
+    /// radians.
+    #[serde(default, skip_serializing_if = "environment_rotation_is_default")]
+    pub rotation: f32,
+
+    /// The index of refraction of the medium surrounding the scene.
+    #[serde(default = "environment_ior_default", skip_serializing_if = "environment_ior_is_default")]
+    pub ior: f32,
+}
+
+#[cfg(feature = "extras")]
+fn environment_intensity_default() -> f32 {
+    1.0
+}
+
+#[cfg(feature = "extras")]
+fn environment_ior_default() -> f32 {
+    1.0
+}
+
+#[cfg(feature = "extras")]
+fn environment_intensity_is_default(intensity: &f32) -> bool {
+    *intensity == environment_intensity_default()
+}
+
+#[cfg(feature = "extras")]
+fn environment_rotation_is_default(rotation: &f32) -> bool {
+    *rotation == 0.0
+}
+
+#[cfg(feature = "extras")]
+fn environment_ior_is_default(ior: &f32) -> bool {
+    *ior == environment_ior_default()
+}
+
+#[cfg(feature = "extras")]
+impl Default for Environment {
+    fn default() -> Self {
+        Environment {
+            specular: None,
+            diffuse: None,
+            intensity: environment_intensity_default(),
+            rotation: 0.0,
+            ior: environment_ior_default(),
+        }
+    }
+}
+
+/// Reads the environment stored in the `extras` of an object.
+///
+/// Returns `Ok(None)` if the object has no `extras` or no environment.
+#[cfg(feature = "extras")]
+pub fn environment(extras: &Extras) -> Result<Option<Environment>, serde_json::Error> {
+    match extras.as_ref().and_then(|value| value.get(ENVIRONMENT_KEY)) {
+        Some(value) => Environment::deserialize(value).map(Some),
+        None => Ok(None),
+    }
+}
+
+/// Stores an environment in the `extras` of an object, keeping any other
+/// `extras` properties.
+///
+/// Passing `None` removes a stored environment. Returns `false` without
+/// modifying `extras` if it holds a value other than a JSON object.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate gltf_json as json;
+/// # fn main() {
+/// use json::extras::Environment;
+///
+/// let mut scene: json::Scene = json::from_str(r#"{
+///     "nodes": [], "extras": { "author": "me" }
+/// }"#).unwrap();
+/// let environment = Environment { intensity: 2.0, ..Default::default() };
+/// assert!(json::extras::set_environment(&mut scene.extras, Some(&environment)));
+/// let stored = json::extras::environment(&scene.extras).unwrap();
+/// assert_eq!(Some(environment), stored);
+/// # }
+/// ```
+#[cfg(feature = "extras")]
+pub fn set_environment(extras: &mut Extras, environment: Option<&Environment>) -> bool {
+    if extras.is_none() {
+        if environment.is_none() {
+            return true;
+        }
+        *extras = Some(Value::Object(serde_json::Map::new()));
+    }
+    match *extras {
+        Some(Value::Object(ref mut object)) => {
+            match environment {
+                Some(environment) => {
+                    let value = serde_json::to_value(environment).unwrap();
+                    object.insert(ENVIRONMENT_KEY.to_string(), value);
+                },
+                None => {
+                    let _ = object.remove(ENVIRONMENT_KEY);
+                },
+            }
+            true
+        },
+        _ => false,
+    }
+}
+
+#[cfg(all(test, feature = "extras"))]
+mod tests {
+    use super::*;
+    use serde_json;
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Tag {
+        collision: bool,
+    }
+
+    #[test]
+    fn collect_typed() {
+        let root: Root = serde_json::from_str(r#"{
+            "asset": { "version": "2.0" },
+            "nodes": [{}, { "extras": { "collision": true } }],
+            "meshes": [{ "primitives": [{ "attributes": {}, "extras": { "collision": false } }] }]
+        }"#).unwrap();
+        let tags: Vec<(String, Tag)> = collect(&root).unwrap();
+        assert_eq!(
+            vec![
+                ("/meshes/0/primitives/0".to_string(), Tag { collision: false }),
+                ("/nodes/1".to_string(), Tag { collision: true }),
+            ],
+            tags,
+        );
+
+        let root: Root = serde_json::from_str(r#"{
+            "asset": { "version": "2.0" },
+            "scenes": [{ "nodes": [], "extras": { "collision": 1 } }]
+        }"#).unwrap();
+        let error = collect::<Tag>(&root).unwrap_err();
+        assert_eq!("/scenes/0", error.pointer);
+    }
+
+    #[test]
+    fn blender_properties() {
+        let node: ::Node = serde_json::from_str(r#"{
+            "extras": {
+                "visible": 1,
+                "lod": { "distance": 10.5, "unused": null },
+                "layers": [1, 2]
+            }
+        }"#).unwrap();
+        let values = properties(&node.extras);
+        assert_eq!(Some(true), values["visible"].as_bool());
+        let lod = values["lod"].as_group().unwrap();
+        assert_eq!(Some(10.5), lod["distance"].as_f64());
+        assert!(!lod.contains_key("unused"));
+        assert_eq!(
+            Some(&[Property::Int(1), Property::Int(2)][..]),
+            values["layers"].as_array(),
+        );
+
+        let node: ::Node = serde_json::from_str(r#"{ "extras": "text" }"#).unwrap();
+        assert!(properties(&node.extras).is_empty());
+    }
+
+    #[test]
+    fn environment_round_trip() {
+        let mut scene: ::Scene = serde_json::from_str(r#"{
+            "nodes": [],
+            "extras": {
+                "environment": { "specular": { "image": 2 }, "ior": 1.33 },
+                "author": "me"
+            }
+        }"#).unwrap();
+        let mut environment = environment(&scene.extras).unwrap().unwrap();
+        assert_eq!(Some(EnvironmentMap { image: Some(2), uri: None }), environment.specular);
+        assert_eq!(1.0, environment.intensity);
+        assert_eq!(1.33, environment.ior);
+
+        environment.diffuse = Some(EnvironmentMap { image: None, uri: Some("sky.hdr".into()) });
+        assert!(set_environment(&mut scene.extras, Some(&environment)));
+        assert_eq!(Some(environment), super::environment(&scene.extras).unwrap());
+        assert!(scene.extras.as_ref().unwrap().get("author").is_some());
+        let stored = &scene.extras.as_ref().unwrap()[ENVIRONMENT_KEY];
+        assert_eq!(
+            json!({
+                "specular": { "image": 2 },
+                "diffuse": { "uri": "sky.hdr" },
+                "ior": 1.33f32,
+            }),
+            *stored,
+        );
+
+        assert!(set_environment(&mut scene.extras, None));
+        assert_eq!(None, super::environment(&scene.extras).unwrap());
+
+        let mut extras = Some(Value::from("text"));
+        assert!(!set_environment(&mut extras, Some(&Environment::default())));
+    }
+}
@@ -0,0 +1,72 @@
+use serde_json::Value;
+
+/// How floating point numbers are written by `format_floats`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Floats {
+    /// The shortest representation that reads back as the same `f64`.
+    ///
+    /// No value changes, so this is safe for numbers of any precision, such
+    /// as those in `extras` and unmodelled extensions.
+    Shortest,
+
+    /// The shortest representation that reads back as the same `f32`.
+    ///
+    /// The properties defined by glTF are single precision, so this drops the
+    /// noise digits of values widened from `f32`, e.g. `0.10000000149011612`
+    /// becomes `0.1`. Every number is narrowed, so values needing double
+    /// precision, e.g. in `extras`, lose digits.
+    Single,
+
+    /// Rounded to a fixed number of decimal places, then written in the
+    /// shortest representation, e.g. `0.30000001` becomes `0.3` at three
+    /// places.
+    Fixed(u32),
+}
+
+/// Rewrites every floating point number of a JSON value in place.
+///
+/// Integers are left untouched and negative zero becomes zero, so that
+/// repeated exports of the same data produce identical text.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate gltf_json as json;
+/// # fn main() {
+/// use json::floats::{format_floats, Floats};
+///
+/// let mut value: json::Value = json::from_str("[0.10000000149011612, -0.0, 7]").unwrap();
+/// format_floats(&mut value, Floats::Shortest);
+/// assert_eq!("[0.10000000149011612,0.0,7]", value.to_string());
+/// format_floats(&mut value, Floats::Single);
+/// assert_eq!("[0.1,0.0,7]", value.to_string());
+/// # }
+/// ```
+pub fn format_floats(value: &mut Value, floats: Floats) {
+    match *value {
+        Value::Number(ref number) if number.is_f64() => {
+            let x = number.as_f64().unwrap();
+            let x = match floats {
+                Floats::Shortest => x,
+                Floats::Single => (x as f32).to_string().parse().unwrap_or(x),
+                Floats::Fixed(decimals) => {
+                    let scale = 10f64.powi(decimals as i32);
+                    let rounded = (x * scale).round() / scale;
+                    if rounded.is_finite() { rounded } else { x }
+                },
+            };
+            *value = Value::from(if x == 0.0 { 0.0 } else { x });
+        },
+        Value::Array(ref mut values) => {
+            for value in values {
+                format_floats(value, floats);
+            }
+        },
+        Value::Object(ref mut object) => {
+            for (_, value) in object.iter_mut() {
+                format_floats(value, floats);
+            }
+        },
+        _ => {},
+    }
+}
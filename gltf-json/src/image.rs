@@ -8,37 +8,63 @@ pub const VALID_MIME_TYPES: &'static [&'static str] = &[
 ];
 
 /// Image data used to create a texture.
-#[derive(Clone, Debug, Deserialize, Validate)]
+#[derive(Clone, Debug, Default, Deserialize, Serialize, Validate)]
 pub struct Image {
     /// The index of the buffer view that contains the image. Use this instead of
     /// the image's uri property.
-    #[serde(rename = "bufferView")]
+    #[serde(rename = "bufferView", skip_serializing_if = "Option::is_none")]
     pub buffer_view: Option<Index<buffer::View>>,
 
     /// The image's MIME type.
-    #[serde(rename = "mimeType")]
+    #[serde(rename = "mimeType", skip_serializing_if = "Option::is_none")]
     pub mime_type: Option<MimeType>,
 
     /// Optional user-defined name for this object.
+    #[serde(skip_serializing_if = "Option::is_none")]
     #[cfg(feature = "names")]
     pub name: Option<String>,
 
+    /// Records a name discarded because the `names` feature is disabled.
+    #[serde(default, skip_serializing_if = "::extras::Void::is_absent")]
+    #[cfg(not(feature = "names"))]
+    pub name: ::extras::Void,
+
     /// The uri of the image.  Relative paths are relative to the .gltf file.
     /// Instead of referencing an external file, the uri can also be a data-uri.
     /// The image format must be jpg or png.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub uri: Option<String>,
 
     /// Extension specific data.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "::extensions::image::Image::is_empty")]
     pub extensions: extensions::image::Image,
 
     /// Optional application specific data.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "::extras::is_empty")]
     pub extras: Extras,
 }
 
+impl Image {
+    /// Creates an image referencing an external or data URI.
+    pub fn from_uri(uri: String) -> Self {
+        Image {
+            uri: Some(uri),
+            ..Default::default()
+        }
+    }
+
+    /// Creates an image contained in a buffer view.
+    pub fn from_buffer_view(buffer_view: Index<buffer::View>, mime_type: String) -> Self {
+        Image {
+            buffer_view: Some(buffer_view),
+            mime_type: Some(MimeType(mime_type)),
+            ..Default::default()
+        }
+    }
+}
+
 /// An image MIME type.
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct MimeType(pub String);
 
 impl Validate for MimeType {
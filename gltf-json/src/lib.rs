@@ -1,9 +1,14 @@
+#![forbid(unsafe_code)]
+
 #[macro_use]
 extern crate gltf_derive;
 extern crate serde;
 #[macro_use]
 extern crate serde_derive;
+#[macro_use]
 extern crate serde_json;
+#[cfg(feature = "nfc")]
+extern crate unicode_normalization;
 
 /// Contains `Accessor` and other related data structures.
 pub mod accessor;
@@ -20,13 +25,19 @@ pub mod buffer;
 /// Contains `Camera` and other related data structures.
 pub mod camera;
 
+/// Contains `DocumentEdit` for recording changes to a document.
+pub mod edit;
+
 /// Contains extension specific data structures and the names of all
 /// 2.0 extensions supported by the library.
 pub mod extensions;
 
-/// Contains `Extras`.
+/// Contains `Extras` and typed access to user-defined data.
 pub mod extras;
 
+/// Contains `format_floats` for writing numbers in a stable format.
+pub mod floats;
+
 /// Contains `Image` and other related data structures.
 pub mod image;
 
@@ -36,9 +47,19 @@ pub mod material;
 /// Contains `Mesh` and other related data structures.
 pub mod mesh;
 
+/// Contains functions for renaming objects and assigning missing names.
+#[cfg(feature = "names")]
+pub mod naming;
+
+/// Contains functions for applying JSON patches to a `Root`.
+pub mod patch;
+
 /// Contains `Path`.
 pub mod path;
 
+/// Contains `Object` and JSON pointer resolution.
+pub mod pointer;
+
 /// Contains `Root`.
 pub mod root;
 
@@ -54,6 +75,9 @@ pub mod texture;
 /// Contains functions that validate glTF JSON data against the specification.
 pub mod validation;
 
+/// Contains visitors of the indices objects reference each other by.
+pub mod visit;
+
 pub use accessor::Accessor;
 pub use animation::Animation;
 pub use asset::Asset;
@@ -71,3 +95,4 @@ pub use self::path::Path;
 pub use self::root::{Index, Root};
 pub use serde_json::{from_reader, from_slice, from_str, from_value};
 pub use serde_json::{Error, Value};
+pub use serde_json::{to_string, to_string_pretty, to_value, to_vec, to_writer, to_writer_pretty};
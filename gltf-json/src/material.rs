@@ -1,4 +1,4 @@
-use serde::de;
+use serde::{de, ser};
 use std::fmt;
 use validation::{Checked, Error, Validate};
 use {extensions, texture, Extras, Index, Root, Path};
@@ -26,11 +26,11 @@ pub enum AlphaMode {
 }
 
 /// The material appearance of a primitive.
-#[derive(Clone, Debug, Default, Deserialize, Validate)]
+#[derive(Clone, Debug, Default, Deserialize, Serialize, Validate)]
 #[serde(default)]
 pub struct Material {
     /// The alpha cutoff value of the material.
-    #[serde(rename = "alphaCutoff")]
+    #[serde(rename = "alphaCutoff", skip_serializing_if = "AlphaCutoff::is_default")]
     pub alpha_cutoff: AlphaCutoff,
     
     /// The alpha rendering mode of the material.
@@ -65,9 +65,15 @@ pub struct Material {
     pub double_sided: bool,
 
     /// Optional user-defined name for this object.
+    #[serde(skip_serializing_if = "Option::is_none")]
     #[cfg(feature = "names")]
     pub name: Option<String>,
 
+    /// Records a name discarded because the `names` feature is disabled.
+    #[serde(default, skip_serializing_if = "::extras::Void::is_absent")]
+    #[cfg(not(feature = "names"))]
+    pub name: ::extras::Void,
+
     /// A set of parameter values that are used to define the metallic-roughness
     /// material model from Physically-Based Rendering (PBR) methodology. When not
     /// specified, all the default values of `pbrMetallicRoughness` apply.
@@ -80,7 +86,7 @@ pub struct Material {
     /// Y [-1 to 1]. Blue [128 to 255] maps to Z [1/255 to 1]. The normal vectors
     /// use OpenGL conventions where +X is right and +Y is up. +Z points toward the
     /// viewer.
-    #[serde(rename = "normalTexture")]
+    #[serde(rename = "normalTexture", skip_serializing_if = "Option::is_none")]
     pub normal_texture: Option<NormalTexture>,
 
     /// The occlusion map texture. The occlusion values are sampled from the R
@@ -88,13 +94,13 @@ pub struct Material {
     /// lighting and lower values indicate no indirect lighting. These values are
     /// linear. If other channels are present (GBA), they are ignored for occlusion
     /// calculations.
-    #[serde(rename = "occlusionTexture")]
+    #[serde(rename = "occlusionTexture", skip_serializing_if = "Option::is_none")]
     pub occlusion_texture: Option<OcclusionTexture>,
 
     /// The emissive map controls the color and intensity of the light being emitted
     /// by the material. This texture contains RGB components in sRGB color space.
     /// If a fourth component (A) is present, it is ignored.
-    #[serde(rename = "emissiveTexture")]
+    #[serde(rename = "emissiveTexture", skip_serializing_if = "Option::is_none")]
     pub emissive_texture: Option<texture::Info>,
 
     /// The emissive color of the material.
@@ -102,15 +108,17 @@ pub struct Material {
     pub emissive_factor: EmissiveFactor,
 
     /// Extension specific data.
+    #[serde(default, skip_serializing_if = "::extensions::material::Material::is_empty")]
     pub extensions: extensions::material::Material,
 
     /// Optional application specific data.
+    #[serde(skip_serializing_if = "::extras::is_empty")]
     pub extras: Extras,
 }
 
 /// A set of parameter values that are used to define the metallic-roughness
 /// material model from Physically-Based Rendering (PBR) methodology.
-#[derive(Clone, Debug, Default, Deserialize, Validate)]
+#[derive(Clone, Debug, Default, Deserialize, Serialize, Validate)]
 #[serde(default)]
 pub struct PbrMetallicRoughness {
     /// The material's base color factor.
@@ -118,7 +126,7 @@ pub struct PbrMetallicRoughness {
     pub base_color_factor: PbrBaseColorFactor,
 
     /// The base color texture.
-    #[serde(rename = "baseColorTexture")]
+    #[serde(rename = "baseColorTexture", skip_serializing_if = "Option::is_none")]
     pub base_color_texture: Option<texture::Info>,
 
     /// The metalness of the material.
@@ -140,18 +148,20 @@ pub struct PbrMetallicRoughness {
     /// * The second component (G) contains the roughness of the material.
     /// * If the third component (B) and/or the fourth component (A) are present
     ///   then they are ignored.
-    #[serde(rename = "metallicRoughnessTexture")]
+    #[serde(rename = "metallicRoughnessTexture", skip_serializing_if = "Option::is_none")]
     pub metallic_roughness_texture: Option<texture::Info>,
 
     /// Extension specific data.
+    #[serde(default, skip_serializing_if = "::extensions::material::PbrMetallicRoughness::is_empty")]
     pub extensions: extensions::material::PbrMetallicRoughness,
 
     /// Optional application specific data.
+    #[serde(skip_serializing_if = "::extras::is_empty")]
     pub extras: Extras,
 }
 
 /// Defines the normal texture of a material.
-#[derive(Clone, Debug, Deserialize, Validate)]
+#[derive(Clone, Debug, Deserialize, Serialize, Validate)]
 pub struct NormalTexture {
     /// The index of the texture.
     pub index: Index<texture::Texture>,
@@ -167,20 +177,33 @@ pub struct NormalTexture {
     pub tex_coord: u32,
 
     /// Extension specific data.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "::extensions::material::NormalTexture::is_empty")]
     pub extensions: extensions::material::NormalTexture,
 
     /// Optional application specific data.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "::extras::is_empty")]
     pub extras: Extras,
 }
 
+impl NormalTexture {
+    /// Creates a reference to a normal texture with default parameters.
+    pub fn new(index: Index<texture::Texture>) -> Self {
+        NormalTexture {
+            index,
+            scale: material_normal_texture_scale_default(),
+            tex_coord: 0,
+            extensions: Default::default(),
+            extras: Default::default(),
+        }
+    }
+}
+
 fn material_normal_texture_scale_default() -> f32 {
     1.0
 }
 
 /// Defines the occlusion texture of a material.
-#[derive(Clone, Debug, Deserialize, Validate)]
+#[derive(Clone, Debug, Deserialize, Serialize, Validate)]
 pub struct OcclusionTexture {
     /// The index of the texture.
     pub index: Index<texture::Texture>,
@@ -194,28 +217,41 @@ pub struct OcclusionTexture {
     pub tex_coord: u32,
 
     /// Extension specific data.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "::extensions::material::OcclusionTexture::is_empty")]
     pub extensions: extensions::material::OcclusionTexture,
 
     /// Optional application specific data.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "::extras::is_empty")]
     pub extras: Extras,
 }
 
+impl OcclusionTexture {
+    /// Creates a reference to an occlusion texture with default parameters.
+    pub fn new(index: Index<texture::Texture>) -> Self {
+        OcclusionTexture {
+            index,
+            strength: Default::default(),
+            tex_coord: 0,
+            extensions: Default::default(),
+            extras: Default::default(),
+        }
+    }
+}
+
 /// The alpha cutoff value of a material.
-#[derive(Clone, Copy, Debug, Deserialize)]
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
 pub struct AlphaCutoff(pub f32);
 
 /// The emissive color of a material.
-#[derive(Clone, Copy, Debug, Default, Deserialize)]
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize)]
 pub struct EmissiveFactor(pub [f32; 3]);
 
 /// The base color factor of a material.
-#[derive(Clone, Copy, Debug, Deserialize)]
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
 pub struct PbrBaseColorFactor(pub [f32; 4]);
 
 /// A number in the inclusive range [0.0, 1.0] with a default value of 1.0.
-#[derive(Clone, Copy, Debug, Deserialize)]
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
 pub struct StrengthFactor(pub f32);
 
 impl Default for AlphaCutoff {
@@ -224,6 +260,13 @@ impl Default for AlphaCutoff {
     }
 }
 
+impl AlphaCutoff {
+    /// Returns whether this is the default cutoff of 0.5.
+    pub fn is_default(&self) -> bool {
+        self.0 == AlphaCutoff::default().0
+    }
+}
+
 impl Default for AlphaMode {
     fn default() -> Self {
         AlphaMode::Opaque
@@ -269,6 +312,18 @@ impl<'de> de::Deserialize<'de> for Checked<AlphaMode> {
     }
 }
 
+impl ser::Serialize for AlphaMode {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: ser::Serializer
+    {
+        serializer.serialize_str(match *self {
+            AlphaMode::Opaque => "OPAQUE",
+            AlphaMode::Mask => "MASK",
+            AlphaMode::Blend => "BLEND",
+        })
+    }
+}
+
 impl Validate for EmissiveFactor {
     fn validate_completely<P, R>(&self, _: &Root, path: P, report: &mut R)
         where P: Fn() -> Path, R: FnMut(&Fn() -> Path, Error)
@@ -1,8 +1,9 @@
-use serde::de;
+use serde::{de, ser};
 use serde_json::from_value;
 use std::collections::HashMap;
-use std::fmt;
+use std::{fmt, str};
 use validation::{Checked, Error, Validate};
+use accessor::{ComponentType, GenericComponentType, Type};
 use {accessor, extensions, material, Extras, Index};
 
 /// Corresponds to `GL_POINTS`.
@@ -73,46 +74,59 @@ pub enum Mode {
 ///
 /// A node can contain one or more meshes and its transform places the meshes in
 /// the scene.
-#[derive(Clone, Debug, Deserialize, Validate)]
+#[derive(Clone, Debug, Default, Deserialize, Serialize, Validate)]
 pub struct Mesh {
     /// Extension specific data.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "::extensions::mesh::Mesh::is_empty")]
     pub extensions: extensions::mesh::Mesh,
 
     /// Optional application specific data.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "::extras::is_empty")]
     pub extras: Extras,
 
     /// Optional user-defined name for this object.
+    #[serde(skip_serializing_if = "Option::is_none")]
     #[cfg(feature = "names")]
     pub name: Option<String>,
 
+    /// Records a name discarded because the `names` feature is disabled.
+    #[serde(default, skip_serializing_if = "::extras::Void::is_absent")]
+    #[cfg(not(feature = "names"))]
+    pub name: ::extras::Void,
+
     /// Defines the geometry to be renderered with a material.
     pub primitives: Vec<Primitive>,
 
     /// Defines the weights to be applied to the morph targets.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub weights: Option<Vec<f32>>,
 }
 
 /// Geometry to be rendered with the given material.
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
 pub struct Primitive {
     /// Maps attribute semantic names to the `Accessor`s containing the
     /// corresponding attribute data.
+    ///
+    /// Attributes are serialized in order of their names, so that equal
+    /// primitives produce identical JSON.
+    #[serde(serialize_with = "serialize_attributes")]
     pub attributes: HashMap<Checked<Semantic>, Index<accessor::Accessor>>,
 
     /// Extension specific data.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "::extensions::mesh::Primitive::is_empty")]
     pub extensions: extensions::mesh::Primitive,
 
     /// Optional application specific data.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "::extras::is_empty")]
     pub extras: Extras,
 
     /// The index of the accessor that contains the indices.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub indices: Option<Index<accessor::Accessor>>,
 
     /// The index of the material to apply to this primitive when rendering
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub material: Option<Index<material::Material>>,
 
     /// The type of primitives to render.
@@ -122,6 +136,7 @@ pub struct Primitive {
     /// An array of Morph Targets, each  Morph Target is a dictionary mapping
     /// attributes (only `POSITION`, `NORMAL`, and `TANGENT` supported) to their
     /// deviations in the Morph Target.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub targets: Option<Vec<MorphTarget>>,
 }
 
@@ -173,21 +188,74 @@ pub struct Primitive {
                 }
             }
         }
+
+        fn validate_completely<P, R>(&self, root: &::Root, path: P, report: &mut R)
+        where
+            P: Fn() -> ::Path,
+            R: FnMut(&Fn() -> ::Path, ::validation::Error),
+        {
+            // Generated part
+            self.attributes
+                .validate_completely(root, || path().field("attributes"), report);
+            self.extensions
+                .validate_completely(root, || path().field("extensions"), report);
+            self.extras
+                .validate_completely(root, || path().field("extras"), report);
+            self.indices
+                .validate_completely(root, || path().field("indices"), report);
+            self.material
+                .validate_completely(root, || path().field("material"), report);
+            self.mode
+                .validate_completely(root, || path().field("mode"), report);
+            self.targets
+                .validate_completely(root, || path().field("targets"), report);
+
+            // Custom part
+            let mut counts = vec![];
+            for (semantic, index) in &self.attributes {
+                let (semantic, accessor) = match (semantic, root.accessors.get(index.value())) {
+                    (Checked::Valid(semantic), Some(accessor)) => (semantic, accessor),
+                    _ => continue,
+                };
+                if !semantic.allows(accessor) {
+                    let path = || path().field("attributes").key(&semantic.to_string());
+                    report(&path, Error::Invalid);
+                }
+                counts.push(accessor.count);
+            }
+            // spec: All attribute accessors for a given primitive **must** have the same count.
+            if counts.windows(2).any(|pair| pair[0] != pair[1]) {
+                report(&|| path().field("attributes"), Error::Invalid);
+            }
+            let indices = self.indices.as_ref().and_then(|index| root.accessors.get(index.value()));
+            if let Some(accessor) = indices {
+                // spec: indices **must** be unsigned integer scalars.
+                let valid = match (&accessor.type_, &accessor.component_type) {
+                    (&Checked::Valid(Type::Scalar), &Checked::Valid(GenericComponentType(ty))) => {
+                        !accessor.normalized && is_unsigned(ty)
+                    },
+                    _ => false,
+                };
+                if !valid {
+                    report(&|| path().field("indices"), Error::Invalid);
+                }
+            }
+        }
     }
 
 /// A dictionary mapping attributes to their deviations in the Morph Target.
-#[derive(Clone, Debug, Deserialize, Validate)]
+#[derive(Clone, Debug, Default, Deserialize, Serialize, Validate)]
 pub struct MorphTarget {
     /// XYZ vertex position displacements of type `[f32; 3]`.
-    #[serde(rename = "POSITION")]
+    #[serde(rename = "POSITION", skip_serializing_if = "Option::is_none")]
     pub positions: Option<Index<accessor::Accessor>>,
 
     /// XYZ vertex normal displacements of type `[f32; 3]`.
-    #[serde(rename = "NORMAL")]
+    #[serde(rename = "NORMAL", skip_serializing_if = "Option::is_none")]
     pub normals: Option<Index<accessor::Accessor>>,
 
     /// XYZ vertex tangent displacements of type `[f32; 3]`.
-    #[serde(rename = "TANGENT")]
+    #[serde(rename = "TANGENT", skip_serializing_if = "Option::is_none")]
     pub tangents: Option<Index<accessor::Accessor>>,
 }
 
@@ -221,12 +289,59 @@ pub enum Semantic {
     Weights(u32),
 }
 
+/// Returns whether a component type is an unsigned integer.
+fn is_unsigned(ty: ComponentType) -> bool {
+    matches!(ty, ComponentType::U8 | ComponentType::U16 | ComponentType::U32)
+}
+
+impl Semantic {
+    /// Returns whether the specification allows an accessor to hold the
+    /// values of this attribute.
+    fn allows(&self, accessor: &accessor::Accessor) -> bool {
+        let (ty, component_type) = match (&accessor.type_, &accessor.component_type) {
+            (&Checked::Valid(ty), &Checked::Valid(GenericComponentType(component_type))) => {
+                (ty, component_type)
+            },
+            _ => return false,
+        };
+        let small = matches!(component_type, ComponentType::U8 | ComponentType::U16);
+        let float = component_type == ComponentType::F32;
+        let float_or_normalized = float || small && accessor.normalized;
+        match *self {
+            #[cfg(feature = "extras")]
+            Semantic::Extras(_) => true,
+            Semantic::Positions | Semantic::Normals => float && matches!(ty, Type::Vec3),
+            Semantic::Tangents => float && matches!(ty, Type::Vec4),
+            Semantic::TexCoords(_) => float_or_normalized && matches!(ty, Type::Vec2),
+            Semantic::Colors(_) => float_or_normalized && matches!(ty, Type::Vec3 | Type::Vec4),
+            Semantic::Joints(_) => small && !accessor.normalized && matches!(ty, Type::Vec4),
+            Semantic::Weights(_) => float_or_normalized && matches!(ty, Type::Vec4),
+        }
+    }
+}
+
 impl Default for Mode {
     fn default() -> Mode {
         Mode::Triangles
     }
 }
 
+impl ser::Serialize for Mode {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: ser::Serializer
+    {
+        serializer.serialize_u32(match *self {
+            Mode::Points => POINTS,
+            Mode::Lines => LINES,
+            Mode::LineLoop => LINE_LOOP,
+            Mode::LineStrip => LINE_STRIP,
+            Mode::Triangles => TRIANGLES,
+            Mode::TriangleStrip => TRIANGLE_STRIP,
+            Mode::TriangleFan => TRIANGLE_FAN,
+        })
+    }
+}
+
 impl<'de> de::Deserialize<'de> for Checked<Mode> {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
         where D: de::Deserializer<'de>
@@ -260,58 +375,95 @@ impl<'de> de::Deserialize<'de> for Checked<Mode> {
     }
 }
 
+/// Parses the set index of a semantic name, e.g. the `0` of `TEXCOORD_0`.
+///
+/// Signs and leading zeros are rejected so that parsing round-trips.
+fn parse_set(s: &str) -> Option<u32> {
+    if s.is_empty() || (s.len() > 1 && s.starts_with('0')) {
+        return None;
+    }
+    if !s.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    s.parse().ok()
+}
+
 impl Semantic {
     fn checked(s: &str) -> Checked<Self> {
+        match s.parse() {
+            Ok(semantic) => Checked::Valid(semantic),
+            Err(_) => Checked::Invalid,
+        }
+    }
+}
+
+/// Error returned when parsing an invalid semantic name.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ParseSemanticError(pub String);
+
+impl fmt::Display for ParseSemanticError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "invalid semantic name: {:?}", self.0)
+    }
+}
+
+impl ::std::error::Error for ParseSemanticError {
+    fn description(&self) -> &str {
+        "invalid semantic name"
+    }
+}
+
+impl str::FromStr for Semantic {
+    type Err = ParseSemanticError;
+
+    /// Parses a semantic name, e.g. `"TEXCOORD_1"`.
+    ///
+    /// With the `extras` feature, application specific names beginning with an
+    /// underscore, e.g. `"_TEMPERATURE"`, parse as `Semantic::Extras` without
+    /// the leading underscore.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gltf_json::mesh::Semantic;
+    ///
+    /// let semantic: Semantic = "TEXCOORD_1".parse().unwrap();
+    /// assert_eq!(Semantic::TexCoords(1), semantic);
+    /// assert_eq!("TEXCOORD_1", semantic.to_string());
+    /// assert!("TEXCOORD_01".parse::<Semantic>().is_err());
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
         use self::Semantic::*;
-        use validation::Checked::*;
-        match s {
-            "NORMAL" => Valid(Normals),
-            "POSITION" => Valid(Positions),
-            "TANGENT" => Valid(Tangents),
+        let set = |prefix: &str| parse_set(&s[prefix.len()..]);
+        let semantic = match s {
+            "NORMAL" => Some(Normals),
+            "POSITION" => Some(Positions),
+            "TANGENT" => Some(Tangents),
             #[cfg(feature = "extras")]
-            _ if s.starts_with("_") => Valid(Extras(s[1..].to_string())),
-            _ if s.starts_with("COLOR_") => {
-                match s["COLOR_".len()..].parse() {
-                    Ok(set) => Valid(Colors(set)),
-                    Err(_) => Invalid,
-                }
-            },
-            _ if s.starts_with("TEXCOORD_") => {
-                match s["TEXCOORD_".len()..].parse() {
-                    Ok(set) => Valid(TexCoords(set)),
-                    Err(_) => Invalid,
-                }
-            },
-            _ if s.starts_with("JOINTS_") => {
-                match s["JOINTS_".len()..].parse() {
-                    Ok(set) => Valid(Joints(set)),
-                    Err(_) => Invalid,
-                }
-            },
-            _ if s.starts_with("WEIGHTS_") => {
-                match s["WEIGHTS_".len()..].parse() {
-                    Ok(set) => Valid(Weights(set)),
-                    Err(_) => Invalid,
-                }
-            },
-            _ => Invalid,
-        }
+            _ if s.starts_with('_') => Some(Extras(s[1..].to_string())),
+            _ if s.starts_with("COLOR_") => set("COLOR_").map(Colors),
+            _ if s.starts_with("TEXCOORD_") => set("TEXCOORD_").map(TexCoords),
+            _ if s.starts_with("JOINTS_") => set("JOINTS_").map(Joints),
+            _ if s.starts_with("WEIGHTS_") => set("WEIGHTS_").map(Weights),
+            _ => None,
+        };
+        semantic.ok_or_else(|| ParseSemanticError(s.to_string()))
     }
 }
 
-impl ToString for Semantic {
-    fn to_string(&self) -> String {
+impl fmt::Display for Semantic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         use self::Semantic::*;
         match *self {
-            Positions => "POSITION".into(),
-            Normals => "NORMAL".into(),
-            Tangents => "TANGENT".into(),
-            Colors(set) => format!("COLOR_{}", set),
-            TexCoords(set) => format!("TEXCOORD_{}", set),
-            Joints(set) => format!("JOINTS_{}", set),
-            Weights(set) => format!("WEIGHTS_{}", set),
+            Positions => write!(f, "POSITION"),
+            Normals => write!(f, "NORMAL"),
+            Tangents => write!(f, "TANGENT"),
+            Colors(set) => write!(f, "COLOR_{}", set),
+            TexCoords(set) => write!(f, "TEXCOORD_{}", set),
+            Joints(set) => write!(f, "JOINTS_{}", set),
+            Weights(set) => write!(f, "WEIGHTS_{}", set),
             #[cfg(feature = "extras")]
-            Extras(ref name) => format!("_{}", name),
+            Extras(ref name) => write!(f, "_{}", name),
         }
     }
 }
@@ -325,6 +477,14 @@ impl ToString for Checked<Semantic> {
     }
 }
 
+impl ser::Serialize for Semantic {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: ser::Serializer
+    {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
 impl<'de> de::Deserialize<'de> for Checked<Semantic> {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
         where D: de::Deserializer<'de>
@@ -346,3 +506,61 @@ impl<'de> de::Deserialize<'de> for Checked<Semantic> {
         deserializer.deserialize_str(Visitor)
     }
 }
+
+/// Serializes primitive attributes sorted by semantic name.
+fn serialize_attributes<S>(
+    attributes: &HashMap<Checked<Semantic>, Index<accessor::Accessor>>,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+    where S: ser::Serializer
+{
+    use serde::ser::SerializeMap;
+    let mut sorted: Vec<_> = attributes.iter().collect();
+    sorted.sort_by_key(|&(semantic, _)| semantic.to_string());
+    let mut map = serializer.serialize_map(Some(sorted.len()))?;
+    for (semantic, accessor) in sorted {
+        map.serialize_entry(semantic, accessor)?;
+    }
+    map.end()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn semantic_round_trip() {
+        let names = [
+            "POSITION", "NORMAL", "TANGENT", "COLOR_0", "TEXCOORD_12", "JOINTS_1", "WEIGHTS_1",
+        ];
+        for name in names.iter() {
+            assert_eq!(*name, name.parse::<Semantic>().unwrap().to_string());
+        }
+        for name in ["COLOR_", "COLOR_+1", "TEXCOORD_01", "POSITIONS", "texcoord_0"].iter() {
+            assert_eq!(Err(ParseSemanticError(name.to_string())), name.parse::<Semantic>());
+        }
+    }
+
+    #[test]
+    fn attributes_sorted() {
+        let json = r#"{ "attributes": {
+            "TEXCOORD_0": 3, "POSITION": 0, "COLOR_0": 4, "NORMAL": 1, "TANGENT": 2,
+            "JOINTS_0": 5, "WEIGHTS_0": 6, "TEXCOORD_1": 7
+        } }"#;
+        let expected = concat!(
+            r#"{"attributes":{"COLOR_0":4,"JOINTS_0":5,"NORMAL":1,"POSITION":0,"#,
+            r#""TANGENT":2,"TEXCOORD_0":3,"TEXCOORD_1":7,"WEIGHTS_0":6},"mode":4}"#,
+        );
+        for _ in 0..8 {
+            let primitive: Primitive = ::serde_json::from_str(json).unwrap();
+            assert_eq!(expected, ::serde_json::to_string(&primitive).unwrap());
+        }
+    }
+
+    #[cfg(feature = "extras")]
+    #[test]
+    fn semantic_extras() {
+        assert_eq!(Ok(Semantic::Extras("TEMPERATURE".to_string())), "_TEMPERATURE".parse());
+        assert_eq!("_TEMPERATURE", Semantic::Extras("TEMPERATURE".to_string()).to_string());
+    }
+}
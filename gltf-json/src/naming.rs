@@ -0,0 +1,853 @@
+use std::cmp;
+use std::collections::{HashMap, HashSet};
+
+#[cfg(feature = "extras")]
+use serde_json::Value;
+
+use {pointer, visit};
+use {Index, Root};
+
+/// Returns a mutable reference to the name of the object referenced by a JSON
+/// pointer, e.g. `"/nodes/3"`.
+///
+/// Returns `None` if the pointer does not reference a named top-level object.
+pub fn name_mut<'a>(root: &'a mut Root, pointer: &str) -> Option<&'a mut Option<String>> {
+    if !pointer.starts_with('/') {
+        return None;
+    }
+    let tokens: Vec<&str> = pointer[1..].split('/').collect();
+    let (collection, i) = match tokens[..] {
+        [collection, i] => (collection, pointer::parse_index(i)?),
+        _ => return None,
+    };
+    let name = match collection {
+        "accessors" => &mut root.accessors.get_mut(i)?.name,
+        "animations" => &mut root.animations.get_mut(i)?.name,
+        "buffers" => &mut root.buffers.get_mut(i)?.name,
+        "bufferViews" => &mut root.buffer_views.get_mut(i)?.name,
+        "cameras" => &mut root.cameras.get_mut(i)?.name,
+        "images" => &mut root.images.get_mut(i)?.name,
+        "materials" => &mut root.materials.get_mut(i)?.name,
+        "meshes" => &mut root.meshes.get_mut(i)?.name,
+        "nodes" => &mut root.nodes.get_mut(i)?.name,
+        "samplers" => &mut root.samplers.get_mut(i)?.name,
+        "scenes" => &mut root.scenes.get_mut(i)?.name,
+        "skins" => &mut root.skins.get_mut(i)?.name,
+        "textures" => &mut root.textures.get_mut(i)?.name,
+        _ => return None,
+    };
+    Some(name)
+}
+
+/// Renames the object referenced by a JSON pointer, e.g. `"/nodes/3"`.
+///
+/// With the `extras` feature, string values anywhere in the `extras` of the
+/// document that are equal to the previous name are also updated, since
+/// `extras` conventionally reference objects by name. Returns the number of
+/// `extras` values updated, or `None` if the pointer does not reference a
+/// named top-level object.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate gltf_json as json;
+/// # fn main() {
+/// let mut root: json::Root = json::from_str(r#"{
+///     "asset": { "version": "2.0" },
+///     "nodes": [{ "name": "Cube" }]
+/// }"#).unwrap();
+/// json::naming::rename(&mut root, "/nodes/0", "Box").unwrap();
+/// assert_eq!(Some("Box".to_string()), root.nodes[0].name);
+/// # }
+/// ```
+pub fn rename(root: &mut Root, pointer: &str, name: &str) -> Option<usize> {
+    let previous = name_mut(root, pointer)?.replace(name.to_string());
+    match previous {
+        Some(ref previous) if previous != name => Some(update_extras(root, previous, name)),
+        _ => Some(0),
+    }
+}
+
+/// Assigns a name to every unnamed top-level object.
+///
+/// Names are numbered by index and collection, e.g. `"node_3"` or
+/// `"mesh_0"`. If a generated name is already taken within its collection, a
+/// numeric suffix is appended to keep names unique. Returns the number of names
+/// assigned.
+pub fn name_unnamed(root: &mut Root) -> usize {
+    let mut count = 0;
+    macro_rules! assign {
+        ($field:ident, $prefix:expr) => {{
+            let mut taken: HashSet<String> = root.$field
+                .iter()
+                .filter_map(|object| object.name.clone())
+                .collect();
+            for (index, object) in root.$field.iter_mut().enumerate() {
+                if object.name.is_some() {
+                    continue;
+                }
+                let base = format!("{}_{}", $prefix, index);
+                let mut name = base.clone();
+                let mut suffix = 1;
+                while taken.contains(&name) {
+                    name = format!("{}_{}", base, suffix);
+                    suffix += 1;
+                }
+                taken.insert(name.clone());
+                object.name = Some(name);
+                count += 1;
+            }
+        }};
+    }
+    assign!(accessors, "accessor");
+    assign!(animations, "animation");
+    assign!(buffers, "buffer");
+    assign!(buffer_views, "buffer_view");
+    assign!(cameras, "camera");
+    assign!(images, "image");
+    assign!(materials, "material");
+    assign!(meshes, "mesh");
+    assign!(nodes, "node");
+    assign!(samplers, "sampler");
+    assign!(scenes, "scene");
+    assign!(skins, "skin");
+    assign!(textures, "texture");
+    count
+}
+
+/// Rules for sanitizing object names, e.g. for engines that restrict the
+/// characters or length of asset names.
+#[derive(Clone, Debug)]
+pub struct Rules {
+    /// Explicit renames applied before any other rule, by previous name.
+    pub remap: HashMap<String, String>,
+
+    /// Returns whether a character may appear in a name.
+    ///
+    /// The default allows ASCII letters, digits, and `_`.
+    pub allowed: fn(char) -> bool,
+
+    /// The character substituted for disallowed characters, or `None` to
+    /// strip them.
+    ///
+    /// The default is `Some('_')`.
+    pub replacement: Option<char>,
+
+    /// The maximum length of a name in characters, including any suffix
+    /// added for uniqueness.
+    pub max_length: Option<usize>,
+
+    /// Whether to append numeric suffixes to keep names unique within each
+    /// collection.
+    ///
+    /// The default is `true`.
+    pub unique: bool,
+}
+
+fn is_identifier(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_'
+}
+
+impl Default for Rules {
+    fn default() -> Self {
+        Rules {
+            remap: HashMap::new(),
+            allowed: is_identifier,
+            replacement: Some('_'),
+            max_length: None,
+            unique: true,
+        }
+    }
+}
+
+impl Rules {
+    /// Applies the remapping table and character rules to a single name.
+    ///
+    /// The length limit and uniqueness are not enforced here.
+    pub fn clean(&self, name: &str) -> String {
+        let name = self.remap.get(name).map(String::as_str).unwrap_or(name);
+        name.chars()
+            .filter_map(|c| if (self.allowed)(c) { Some(c) } else { self.replacement })
+            .collect()
+    }
+
+    /// Truncates a name so that it fits the length limit along with a suffix.
+    fn fit(&self, name: &str, suffix: &str) -> String {
+        let mut fitted: String = match self.max_length {
+            Some(max) => {
+                let keep = max.saturating_sub(suffix.chars().count());
+                name.chars().take(keep).collect()
+            },
+            None => name.to_string(),
+        };
+        fitted.push_str(suffix);
+        fitted
+    }
+}
+
+/// Sanitizes the name of every named top-level object according to the given
+/// rules.
+///
+/// Names that are empty after sanitization are replaced by numbered names as
+/// in `name_unnamed`. Unnamed objects are left unnamed. As with `rename`,
+/// string values in `extras` equal to a previous name are updated, unless the
+/// previous name was shared by several objects of the same collection. Returns
+/// the number of names changed.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate gltf_json as json;
+/// # fn main() {
+/// let mut root: json::Root = json::from_str(r#"{
+///     "asset": { "version": "2.0" },
+///     "nodes": [{ "name": "Arm.L" }, { "name": "Arm L" }]
+/// }"#).unwrap();
+/// let rules = json::naming::Rules::default();
+/// assert_eq!(2, json::naming::sanitize(&mut root, &rules));
+/// assert_eq!(Some("Arm_L".to_string()), root.nodes[0].name);
+/// assert_eq!(Some("Arm_L_1".to_string()), root.nodes[1].name);
+/// # }
+/// ```
+pub fn sanitize(root: &mut Root, rules: &Rules) -> usize {
+    let mut count = 0;
+    let mut renamed = vec![];
+    macro_rules! sanitize {
+        ($field:ident, $prefix:expr) => {{
+            let mut occurrences = HashMap::new();
+            for name in root.$field.iter().filter_map(|object| object.name.as_ref()) {
+                *occurrences.entry(name.clone()).or_insert(0) += 1;
+            }
+            let mut taken = HashSet::new();
+            for (index, object) in root.$field.iter_mut().enumerate() {
+                let previous = match object.name {
+                    Some(ref name) => name.clone(),
+                    None => continue,
+                };
+                let mut base = rules.clean(&previous);
+                if base.is_empty() {
+                    base = format!("{}_{}", $prefix, index);
+                }
+                let mut name = rules.fit(&base, "");
+                let mut suffix = 1;
+                while rules.unique && taken.contains(&name) {
+                    name = rules.fit(&base, &format!("_{}", suffix));
+                    suffix += 1;
+                }
+                taken.insert(name.clone());
+                if name != previous {
+                    object.name = Some(name.clone());
+                    count += 1;
+                    // References to duplicate names are ambiguous.
+                    if occurrences[&previous] == 1 {
+                        renamed.push((previous, name));
+                    }
+                }
+            }
+        }};
+    }
+    sanitize!(accessors, "accessor");
+    sanitize!(animations, "animation");
+    sanitize!(buffers, "buffer");
+    sanitize!(buffer_views, "buffer_view");
+    sanitize!(cameras, "camera");
+    sanitize!(images, "image");
+    sanitize!(materials, "material");
+    sanitize!(meshes, "mesh");
+    sanitize!(nodes, "node");
+    sanitize!(samplers, "sampler");
+    sanitize!(scenes, "scene");
+    sanitize!(skins, "skin");
+    sanitize!(textures, "texture");
+    for (previous, name) in renamed {
+        update_extras(root, &previous, &name);
+    }
+    count
+}
+
+/// Normalizes the name of every named object to Unicode Normalization Form C.
+///
+/// Exporters differ in the form they write, e.g. decomposed names from macOS
+/// tools and precomposed names from Windows tools, so the same name can be
+/// spelled with different code points. Besides top-level objects, this covers
+/// the clips, sources, and emitters of `KHR_audio`, the variables of
+/// `KHR_interactivity`, the schema and property tables of
+/// `EXT_structural_metadata`, and the named objects of extensions this crate
+/// does not model, such as the lights of `KHR_lights_punctual`. As with
+/// `rename`, string values in `extras` equal to a previous top-level name are
+/// updated. Returns the number of names changed.
+///
+/// URIs are left as they are, since file systems such as ext4 compare file
+/// names byte by byte and would no longer find the files. See
+/// `normalize_uris_nfc`.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate gltf_json as json;
+/// # fn main() {
+/// let mut root: json::Root = json::from_str(r#"{
+///     "asset": { "version": "2.0" },
+///     "nodes": [{ "name": "Cafe\u0301" }],
+///     "extensions": {
+///         "KHR_lights_punctual": {
+///             "lights": [{ "name": "Cafe\u0301", "type": "point" }]
+///         }
+///     }
+/// }"#).unwrap();
+/// assert_eq!(2, json::naming::normalize_nfc(&mut root));
+/// assert_eq!(Some("Caf\u{e9}".to_string()), root.nodes[0].name);
+/// # }
+/// ```
+#[cfg(feature = "nfc")]
+pub fn normalize_nfc(root: &mut Root) -> usize {
+    let mut renamed = vec![];
+    macro_rules! normalize {
+        ($field:ident) => {{
+            for object in &mut root.$field {
+                if let Some(previous) = nfc_option(&mut object.name) {
+                    renamed.push((previous, object.name.clone().unwrap()));
+                }
+            }
+        }};
+    }
+    normalize!(accessors);
+    normalize!(animations);
+    normalize!(buffers);
+    normalize!(buffer_views);
+    normalize!(cameras);
+    normalize!(images);
+    normalize!(materials);
+    normalize!(meshes);
+    normalize!(nodes);
+    normalize!(samplers);
+    normalize!(scenes);
+    normalize!(skins);
+    normalize!(textures);
+    let count = renamed.len();
+    for (previous, name) in renamed {
+        update_extras(root, &previous, &name);
+    }
+    count + normalize_extension_names(root)
+}
+
+/// Normalizes the names of objects defined by extensions to NFC.
+#[cfg(feature = "nfc")]
+fn normalize_extension_names(root: &mut Root) -> usize {
+    let mut count = 0;
+    {
+        let mut normalize = |name: Option<&mut String>| {
+            count += name.and_then(nfc).map_or(0, |_| 1);
+        };
+        let extensions = &mut root.extensions;
+        #[cfg(feature = "audio")]
+        {
+            if let Some(ref mut audio) = extensions.audio {
+                for clip in &mut audio.clips {
+                    normalize(clip.name.as_mut());
+                }
+                for source in &mut audio.sources {
+                    normalize(source.name.as_mut());
+                }
+                for emitter in &mut audio.emitters {
+                    normalize(emitter.name.as_mut());
+                }
+            }
+        }
+        #[cfg(feature = "interactivity")]
+        {
+            let graphs = extensions.interactivity.as_mut().and_then(|x| x.typed_mut());
+            if let Some(graphs) = graphs {
+                for graph in &mut graphs.graphs {
+                    for variable in &mut graph.variables {
+                        normalize(variable.name.as_mut());
+                    }
+                }
+            }
+        }
+        if let Some(ref mut metadata) = extensions.structural_metadata {
+            if let Some(ref mut schema) = metadata.schema {
+                normalize(schema.name.as_mut());
+                for class in schema.classes.values_mut() {
+                    normalize(class.name.as_mut());
+                    for property in class.properties.values_mut() {
+                        normalize(property.name.as_mut());
+                    }
+                }
+                for enum_ in schema.enums.values_mut() {
+                    normalize(enum_.name.as_mut());
+                    for value in &mut enum_.values {
+                        normalize(Some(&mut value.name));
+                    }
+                }
+            }
+            for table in &mut metadata.property_tables {
+                normalize(table.name.as_mut());
+            }
+        }
+        // Extensions not modelled here conventionally keep their objects in
+        // top-level arrays, e.g. `KHR_lights_punctual.lights`.
+        for extension in extensions.others.values_mut() {
+            let arrays = extension.as_object_mut().into_iter()
+                .flat_map(|extension| extension.values_mut())
+                .filter_map(|value| value.as_array_mut());
+            for array in arrays {
+                for object in array.iter_mut().filter_map(|value| value.as_object_mut()) {
+                    if let Some(&mut ::serde_json::Value::String(ref mut name)) =
+                        object.get_mut("name")
+                    {
+                        normalize(Some(name));
+                    }
+                }
+            }
+        }
+    }
+    count
+}
+
+/// Normalizes the URI of every buffer and image, and of every `KHR_audio`
+/// clip, to Unicode Normalization Form C. Returns the number of URIs changed.
+///
+/// Only use this when the referenced files are renamed to match, as on file
+/// systems comparing names byte by byte, such as ext4, the normalized URIs no
+/// longer resolve to files written with a different normalization. Data URIs
+/// are left as they are.
+#[cfg(feature = "nfc")]
+pub fn normalize_uris_nfc(root: &mut Root) -> usize {
+    let mut uris: Vec<&mut Option<String>> = root.buffers.iter_mut()
+        .map(|buffer| &mut buffer.uri)
+        .chain(root.images.iter_mut().map(|image| &mut image.uri))
+        .collect();
+    #[cfg(feature = "audio")]
+    {
+        if let Some(ref mut audio) = root.extensions.audio {
+            uris.extend(audio.clips.iter_mut().map(|clip| &mut clip.uri));
+        }
+    }
+    // Data URIs are ASCII, and may be large.
+    uris.into_iter()
+        .filter(|uri| uri.as_ref().is_some_and(|uri| !uri.starts_with("data:")))
+        .filter_map(nfc_option)
+        .count()
+}
+
+/// Normalizes a string to NFC, returning the previous string if it changed.
+#[cfg(feature = "nfc")]
+fn nfc(string: &mut String) -> Option<String> {
+    use unicode_normalization::UnicodeNormalization;
+
+    let normalized: String = string.nfc().collect();
+    if *string == normalized {
+        return None;
+    }
+    Some(::std::mem::replace(string, normalized))
+}
+
+/// Normalizes an optional string to NFC, returning the previous string if it
+/// changed.
+#[cfg(feature = "nfc")]
+fn nfc_option(string: &mut Option<String>) -> Option<String> {
+    nfc(string.as_mut()?)
+}
+
+/// Where names are required to be unique by `duplicates`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Scope {
+    /// Names may repeat across collections, e.g. a node and its mesh may
+    /// share a name, but not within one.
+    Collection,
+
+    /// Names must be unique across every collection of the document.
+    Document,
+}
+
+/// A name shared by several objects.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Duplicate {
+    /// The shared name.
+    pub name: String,
+
+    /// JSON pointers to the objects sharing the name, e.g. `"/nodes/3"`, in
+    /// document order.
+    pub pointers: Vec<String>,
+}
+
+/// Finds names shared by several top-level objects within the given scope.
+///
+/// Duplicate names are valid glTF, but tools that identify objects by name,
+/// such as DCC applications re-importing an exported asset, may silently
+/// merge or drop them. Duplicates are returned in order of their first
+/// occurrence. Unnamed objects are ignored.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate gltf_json as json;
+/// # fn main() {
+/// use json::naming::{duplicates, Scope};
+///
+/// let root: json::Root = json::from_str(r#"{
+///     "asset": { "version": "2.0" },
+///     "meshes": [{ "name": "Cube", "primitives": [] }],
+///     "nodes": [{ "name": "Cube" }, { "name": "Light" }]
+/// }"#).unwrap();
+/// assert!(duplicates(&root, Scope::Collection).is_empty());
+/// let found = duplicates(&root, Scope::Document);
+/// assert_eq!(vec!["/meshes/0", "/nodes/0"], found[0].pointers);
+/// # }
+/// ```
+pub fn duplicates(root: &Root, scope: Scope) -> Vec<Duplicate> {
+    let mut found: Vec<Duplicate> = vec![];
+    let mut by_name: HashMap<(&str, &str), usize> = HashMap::new();
+    macro_rules! check {
+        ($field:ident, $collection:expr) => {
+            for (index, object) in root.$field.iter().enumerate() {
+                let name = match object.name {
+                    Some(ref name) => name.as_str(),
+                    None => continue,
+                };
+                let key = match scope {
+                    Scope::Collection => ($collection, name),
+                    Scope::Document => ("", name),
+                };
+                let pointer = format!("/{}/{}", $collection, index);
+                match by_name.get(&key) {
+                    Some(&i) => found[i].pointers.push(pointer),
+                    None => {
+                        by_name.insert(key, found.len());
+                        found.push(Duplicate { name: name.to_string(), pointers: vec![pointer] });
+                    },
+                }
+            }
+        };
+    }
+    check!(accessors, "accessors");
+    check!(animations, "animations");
+    check!(buffers, "buffers");
+    check!(buffer_views, "bufferViews");
+    check!(cameras, "cameras");
+    check!(images, "images");
+    check!(materials, "materials");
+    check!(meshes, "meshes");
+    check!(nodes, "nodes");
+    check!(samplers, "samplers");
+    check!(scenes, "scenes");
+    check!(skins, "skins");
+    check!(textures, "textures");
+    found.retain(|duplicate| duplicate.pointers.len() > 1);
+    found
+}
+
+/// Returns the order of objects sorted by name, as old indices by new index.
+///
+/// Named objects come first in name order, followed by unnamed objects in
+/// their original order. Objects with equal names keep their relative order.
+fn name_order(names: &[Option<&String>]) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..names.len()).collect();
+    order.sort_by(|&a, &b| match (names[a], names[b]) {
+        (Some(a), Some(b)) => a.cmp(b),
+        (Some(_), None) => cmp::Ordering::Less,
+        (None, Some(_)) => cmp::Ordering::Greater,
+        (None, None) => cmp::Ordering::Equal,
+    });
+    order
+}
+
+/// Reorders a vector, returning the new index of every old index.
+fn permute<T>(objects: &mut Vec<T>, order: &[usize]) -> Vec<u32> {
+    let mut remap = vec![0; order.len()];
+    for (new, &old) in order.iter().enumerate() {
+        remap[old] = new as u32;
+    }
+    let mut slots: Vec<Option<T>> = objects.drain(..).map(Some).collect();
+    objects.extend(order.iter().map(|&old| slots[old].take().unwrap()));
+    remap
+}
+
+/// Sorts the materials of the document by name and updates the primitives
+/// referencing them.
+///
+/// Sorting keeps the output of pipelines stable when the order in which a
+/// tool emits materials varies between runs. Named materials come first in
+/// name order, followed by unnamed materials in their original order; equal
+/// names keep their relative order. Returns whether the order changed.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate gltf_json as json;
+/// # fn main() {
+/// let mut root: json::Root = json::from_str(r#"{
+///     "asset": { "version": "2.0" },
+///     "materials": [{ "name": "Wood" }, {}, { "name": "Metal" }],
+///     "meshes": [{ "primitives": [{ "attributes": {}, "material": 0 }] }]
+/// }"#).unwrap();
+/// assert!(json::naming::sort_materials(&mut root));
+/// assert_eq!(Some("Metal".to_string()), root.materials[0].name);
+/// assert_eq!(1, root.meshes[0].primitives[0].material.as_ref().unwrap().value());
+/// # }
+/// ```
+pub fn sort_materials(root: &mut Root) -> bool {
+    let order = {
+        let names: Vec<_> = root.materials.iter().map(|m| m.name.as_ref()).collect();
+        name_order(&names)
+    };
+    if order.iter().enumerate().all(|(new, &old)| new == old) {
+        return false;
+    }
+    let remap = permute(&mut root.materials, &order);
+    for mesh in &mut root.meshes {
+        for primitive in &mut mesh.primitives {
+            if let Some(ref mut index) = primitive.material {
+                *index = Index::new(remap[index.value()]);
+            }
+        }
+    }
+    true
+}
+
+/// Sorts the textures of the document by name and updates every reference to
+/// them, see `visit::textures`.
+///
+/// Returns whether the order changed. See `sort_materials`.
+pub fn sort_textures(root: &mut Root) -> bool {
+    let order = {
+        let names: Vec<_> = root.textures.iter().map(|t| t.name.as_ref()).collect();
+        name_order(&names)
+    };
+    if order.iter().enumerate().all(|(new, &old)| new == old) {
+        return false;
+    }
+    let remap = permute(&mut root.textures, &order);
+    visit::textures_mut(root, |index| *index = Index::new(remap[index.value()]));
+    true
+}
+
+/// Replaces string values equal to `from` in the given JSON value.
+#[cfg(feature = "extras")]
+fn replace(value: &mut Value, from: &str, to: &str) -> usize {
+    match *value {
+        Value::String(ref mut string) if string == from => {
+            *string = to.to_string();
+            1
+        },
+        Value::Array(ref mut values) => {
+            values.iter_mut().map(|value| replace(value, from, to)).sum()
+        },
+        Value::Object(ref mut map) => {
+            map.values_mut().map(|value| replace(value, from, to)).sum()
+        },
+        _ => 0,
+    }
+}
+
+/// Replaces string values equal to `from` in every `extras` of the document.
+#[cfg(feature = "extras")]
+fn update_extras(root: &mut Root, from: &str, to: &str) -> usize {
+    let mut count = 0;
+    {
+        let mut visit = |extras: &mut ::Extras| {
+            if let Some(ref mut value) = *extras {
+                count += replace(value, from, to);
+            }
+        };
+        visit(&mut root.extras);
+        visit(&mut root.asset.extras);
+        for accessor in &mut root.accessors {
+            visit(&mut accessor.extras);
+        }
+        for animation in &mut root.animations {
+            visit(&mut animation.extras);
+            for channel in &mut animation.channels {
+                visit(&mut channel.extras);
+            }
+            for sampler in &mut animation.samplers {
+                visit(&mut sampler.extras);
+            }
+        }
+        for buffer in &mut root.buffers {
+            visit(&mut buffer.extras);
+        }
+        for view in &mut root.buffer_views {
+            visit(&mut view.extras);
+        }
+        for camera in &mut root.cameras {
+            visit(&mut camera.extras);
+        }
+        for image in &mut root.images {
+            visit(&mut image.extras);
+        }
+        for material in &mut root.materials {
+            visit(&mut material.extras);
+        }
+        for mesh in &mut root.meshes {
+            visit(&mut mesh.extras);
+            for primitive in &mut mesh.primitives {
+                visit(&mut primitive.extras);
+            }
+        }
+        for node in &mut root.nodes {
+            visit(&mut node.extras);
+        }
+        for sampler in &mut root.samplers {
+            visit(&mut sampler.extras);
+        }
+        for scene in &mut root.scenes {
+            visit(&mut scene.extras);
+        }
+        for skin in &mut root.skins {
+            visit(&mut skin.extras);
+        }
+        for texture in &mut root.textures {
+            visit(&mut texture.extras);
+        }
+    }
+    count
+}
+
+#[cfg(not(feature = "extras"))]
+fn update_extras(_root: &mut Root, _from: &str, _to: &str) -> usize {
+    0
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json;
+    use super::*;
+
+    #[test]
+    fn auto_name() {
+        let mut root: Root = serde_json::from_str(r#"{
+            "asset": { "version": "2.0" },
+            "nodes": [{ "name": "node_1" }, {}, {}]
+        }"#).unwrap();
+        assert_eq!(2, name_unnamed(&mut root));
+        let names: Vec<_> = root.nodes.iter().map(|node| node.name.clone().unwrap()).collect();
+        assert_eq!(vec!["node_1", "node_1_1", "node_2"], names);
+        assert!(name_mut(&mut root, "/nodes/01").is_none());
+        assert!(name_mut(&mut root, "/nodes/3").is_none());
+    }
+
+    #[test]
+    fn sort_by_name() {
+        let mut root: Root = serde_json::from_str(r#"{
+            "asset": { "version": "2.0" },
+            "images": [{ "uri": "a.png" }],
+            "textures": [
+                { "source": 0 },
+                { "source": 0, "name": "B" },
+                { "source": 0, "name": "A" }
+            ],
+            "materials": [{
+                "pbrMetallicRoughness": { "baseColorTexture": { "index": 2 } },
+                "normalTexture": { "index": 0 },
+                "extensions": {
+                    "KHR_materials_clearcoat": { "clearcoatTexture": { "index": 1 } }
+                }
+            }],
+            "meshes": [{ "primitives": [{
+                "attributes": {},
+                "extensions": {
+                    "EXT_mesh_features": {
+                        "featureIds": [{ "featureCount": 1, "texture": { "index": 2 } }]
+                    }
+                }
+            }] }]
+        }"#).unwrap();
+        assert!(sort_textures(&mut root));
+        let names: Vec<_> = root.textures.iter().map(|t| t.name.clone()).collect();
+        assert_eq!(vec![Some("A".to_string()), Some("B".to_string()), None], names);
+        let material = &root.materials[0];
+        let base_color = material.pbr_metallic_roughness.base_color_texture.as_ref().unwrap();
+        assert_eq!(0, base_color.index.value());
+        assert_eq!(2, material.normal_texture.as_ref().unwrap().index.value());
+        let clearcoat = material.extensions.clearcoat.as_ref().unwrap();
+        assert_eq!(1, clearcoat.clearcoat_texture.as_ref().unwrap().index.value());
+        let features = root.meshes[0].primitives[0].extensions.mesh_features.as_ref().unwrap();
+        assert_eq!(0, features.feature_ids[0].texture.as_ref().unwrap().index.value());
+        assert!(!sort_textures(&mut root));
+        assert!(!sort_materials(&mut root));
+    }
+
+    #[test]
+    fn duplicate_names() {
+        let root: Root = serde_json::from_str(r#"{
+            "asset": { "version": "2.0" },
+            "materials": [{ "name": "Arm" }],
+            "nodes": [{ "name": "Arm" }, {}, { "name": "Leg" }, { "name": "Arm" }, {}]
+        }"#).unwrap();
+        let within = duplicates(&root, Scope::Collection);
+        assert_eq!(
+            vec![Duplicate {
+                name: "Arm".to_string(),
+                pointers: vec!["/nodes/0".to_string(), "/nodes/3".to_string()],
+            }],
+            within,
+        );
+        let across = duplicates(&root, Scope::Document);
+        assert_eq!(1, across.len());
+        assert_eq!(vec!["/materials/0", "/nodes/0", "/nodes/3"], across[0].pointers);
+    }
+
+    #[cfg(feature = "extras")]
+    #[test]
+    fn rename_updates_extras() {
+        let mut root: Root = serde_json::from_str(r#"{
+            "asset": { "version": "2.0" },
+            "nodes": [
+                { "name": "Arm.L" },
+                { "extras": { "ik": { "target": "Arm.L", "chain": ["Arm.L", "Hand.L"] } } }
+            ]
+        }"#).unwrap();
+        assert_eq!(Some(2), rename(&mut root, "/nodes/0", "UpperArm.L"));
+        let extras = root.nodes[1].extras.as_ref().unwrap();
+        assert_eq!("UpperArm.L", extras["ik"]["target"]);
+        assert_eq!("UpperArm.L", extras["ik"]["chain"][0]);
+    }
+
+    #[test]
+    fn sanitize_names() {
+        let mut root: Root = serde_json::from_str(r#"{
+            "asset": { "version": "2.0" },
+            "materials": [{ "name": "Mat.001" }, { "name": "Skin Tone" }, { "name": "!!" }],
+            "nodes": [{ "name": "Cube" }, { "name": "Cube" }, {}]
+        }"#).unwrap();
+        let mut rules = Rules::default();
+        rules.remap.insert("Skin Tone".to_string(), "M_Skin".to_string());
+        rules.replacement = None;
+        rules.max_length = Some(5);
+        assert_eq!(4, sanitize(&mut root, &rules));
+        let names: Vec<_> = root.materials.iter().map(|m| m.name.clone().unwrap()).collect();
+        assert_eq!(vec!["Mat00", "M_Ski", "mater"], names);
+        let names: Vec<_> = root.nodes.iter().map(|n| n.name.clone()).collect();
+        assert_eq!(vec![Some("Cube".to_string()), Some("Cub_1".to_string()), None], names);
+    }
+
+    #[test]
+    #[cfg(feature = "nfc")]
+    fn normalize_names_not_uris() {
+        let mut root: Root = serde_json::from_str(r#"{
+            "asset": { "version": "2.0" },
+            "images": [{ "name": "Cafe\u0301", "uri": "cafe\u0301.png" }],
+            "extensions": {
+                "EXT_structural_metadata": {
+                    "schema": {
+                        "id": "schema",
+                        "classes": { "cafe": { "name": "Cafe\u0301" } },
+                        "enums": {
+                            "kind": { "values": [{ "name": "Cafe\u0301", "value": 0 }] }
+                        }
+                    }
+                }
+            }
+        }"#).unwrap();
+        assert_eq!(3, normalize_nfc(&mut root));
+        assert_eq!(Some("cafe\u{301}.png".to_string()), root.images[0].uri);
+        let schema = root.extensions.structural_metadata.as_ref().unwrap().schema.as_ref().unwrap();
+        assert_eq!(Some("Caf\u{e9}".to_string()), schema.classes["cafe"].name);
+        assert_eq!("Caf\u{e9}", schema.enums["kind"].values[0].name);
+
+        assert_eq!(1, normalize_uris_nfc(&mut root));
+        assert_eq!(Some("caf\u{e9}.png".to_string()), root.images[0].uri);
+    }
+}
@@ -0,0 +1,455 @@
+use serde_json::{self, Map};
+use std::{error, fmt};
+use validation::{self, Validate};
+use {Path, Root, Value};
+
+/// An error encountered while patching a `Root`.
+#[derive(Debug)]
+pub enum Error {
+    /// The JSON patch operation at the given index is not an object with a
+    /// known `op`, a `path`, and the `from` or `value` members it requires,
+    /// or the patch is not an array, in which case the index is zero.
+    Malformed(usize),
+
+    /// The location referenced by the JSON patch operation at the given
+    /// index does not exist.
+    Location(usize),
+
+    /// The `test` operation at the given index failed.
+    Test(usize),
+
+    /// The document could not be converted to or from JSON.
+    Json(serde_json::Error),
+
+    /// The patched document is not valid glTF.
+    Validation(Vec<(Path, validation::Error)>),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Error::Malformed(index) => write!(f, "malformed operation {}", index),
+            Error::Location(index) => write!(f, "operation {} references no value", index),
+            Error::Test(index) => write!(f, "test operation {} failed", index),
+            Error::Json(ref error) => error.fmt(f),
+            Error::Validation(ref errors) => {
+                write!(f, "patched document is invalid:")?;
+                for (path, error) in errors {
+                    write!(f, " {}: {};", path, error)?;
+                }
+                Ok(())
+            },
+        }
+    }
+}
+
+impl error::Error for Error {
+    fn description(&self) -> &str {
+        match *self {
+            Error::Malformed(_) => "malformed JSON patch operation",
+            Error::Location(_) => "JSON patch location does not exist",
+            Error::Test(_) => "JSON patch test failed",
+            Error::Json(_) => "JSON conversion error",
+            Error::Validation(_) => "patched document is invalid",
+        }
+    }
+
+    fn cause(&self) -> Option<&error::Error> {
+        match *self {
+            Error::Json(ref error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<serde_json::Error> for Error {
+    fn from(error: serde_json::Error) -> Self {
+        Error::Json(error)
+    }
+}
+
+/// Splits a JSON pointer into its unescaped reference tokens.
+fn tokens(pointer: &str) -> Option<Vec<String>> {
+    if pointer.is_empty() {
+        return Some(vec![]);
+    }
+    if !pointer.starts_with('/') {
+        return None;
+    }
+    let tokens = pointer[1..]
+        .split('/')
+        .map(|token| token.replace("~1", "/").replace("~0", "~"))
+        .collect();
+    Some(tokens)
+}
+
+/// Parses an array index token, rejecting leading zeros and signs.
+fn array_index(token: &str) -> Option<usize> {
+    ::pointer::parse_index(token)
+}
+
+/// Returns the value at the given reference tokens.
+fn get_mut<'a>(mut value: &'a mut Value, tokens: &[String]) -> Option<&'a mut Value> {
+    for token in tokens {
+        value = match *value {
+            Value::Object(ref mut object) => object.get_mut(token)?,
+            Value::Array(ref mut array) => array.get_mut(array_index(token)?)?,
+            _ => return None,
+        };
+    }
+    Some(value)
+}
+
+/// Inserts a value at a location whose parent exists, as the `add`
+/// operation does.
+fn add(document: &mut Value, tokens: &[String], value: Value) -> Option<()> {
+    let (last, parent) = match tokens.split_last() {
+        Some(split) => split,
+        None => {
+            *document = value;
+            return Some(());
+        },
+    };
+    match *get_mut(document, parent)? {
+        Value::Object(ref mut object) => {
+            object.insert(last.clone(), value);
+        },
+        Value::Array(ref mut array) => {
+            let index = if last == "-" { array.len() } else { array_index(last)? };
+            if index > array.len() {
+                return None;
+            }
+            array.insert(index, value);
+        },
+        _ => return None,
+    }
+    Some(())
+}
+
+/// Removes and returns the value at an existing location.
+fn remove(document: &mut Value, tokens: &[String]) -> Option<Value> {
+    let (last, parent) = tokens.split_last()?;
+    match *get_mut(document, parent)? {
+        Value::Object(ref mut object) => object.remove(last),
+        Value::Array(ref mut array) => {
+            let index = array_index(last)?;
+            if index < array.len() {
+                Some(array.remove(index))
+            } else {
+                None
+            }
+        },
+        _ => None,
+    }
+}
+
+/// Compares JSON values, treating numbers as equal if numerically equal.
+fn equal(a: &Value, b: &Value) -> bool {
+    match (a, b) {
+        (Value::Number(a), Value::Number(b)) => a.as_f64() == b.as_f64(),
+        (Value::Array(a), Value::Array(b)) => {
+            a.len() == b.len() && a.iter().zip(b).all(|(a, b)| equal(a, b))
+        },
+        (Value::Object(a), Value::Object(b)) => {
+            a.len() == b.len()
+                && a.iter().all(|(key, a)| b.get(key).map(|b| equal(a, b)).unwrap_or(false))
+        },
+        _ => a == b,
+    }
+}
+
+/// Applies the operations of a JSON patch to a JSON document.
+fn apply_operations(document: &mut Value, patch: &Value) -> Result<(), Error> {
+    let operations = patch.as_array().ok_or(Error::Malformed(0))?;
+    for (i, operation) in operations.iter().enumerate() {
+        let member = |name: &str| operation.get(name).ok_or(Error::Malformed(i));
+        let pointer = |name: &str| {
+            member(name)?.as_str().and_then(tokens).ok_or(Error::Malformed(i))
+        };
+        let op = member("op")?.as_str().ok_or(Error::Malformed(i))?;
+        let path = pointer("path")?;
+        let done = match op {
+            "add" => add(document, &path, member("value")?.clone()),
+            "remove" => remove(document, &path).map(|_| ()),
+            "replace" => {
+                let value = member("value")?.clone();
+                get_mut(document, &path).map(|target| *target = value)
+            },
+            "move" => {
+                let from = pointer("from")?;
+                if path.len() > from.len() && path[..from.len()] == from[..] {
+                    // A location cannot be moved into one of its children.
+                    return Err(Error::Location(i));
+                }
+                match remove(document, &from) {
+                    Some(value) => add(document, &path, value),
+                    None => None,
+                }
+            },
+            "copy" => {
+                let from = pointer("from")?;
+                match get_mut(document, &from).map(|value| value.clone()) {
+                    Some(value) => add(document, &path, value),
+                    None => None,
+                }
+            },
+            "test" => {
+                let value = member("value")?;
+                match get_mut(document, &path) {
+                    Some(ref target) if equal(target, value) => Some(()),
+                    Some(_) => return Err(Error::Test(i)),
+                    None => None,
+                }
+            },
+            _ => return Err(Error::Malformed(i)),
+        };
+        if done.is_none() {
+            return Err(Error::Location(i));
+        }
+    }
+    Ok(())
+}
+
+/// Merges a JSON merge patch into a JSON document.
+fn merge(document: &mut Value, patch: &Value) {
+    let patch = match *patch {
+        Value::Object(ref patch) => patch,
+        _ => {
+            *document = patch.clone();
+            return;
+        },
+    };
+    if !document.is_object() {
+        *document = Value::Object(Map::new());
+    }
+    if let Value::Object(ref mut object) = *document {
+        for (key, value) in patch {
+            if value.is_null() {
+                object.remove(key);
+            } else {
+                merge(object.entry(key.clone()).or_insert(Value::Null), value);
+            }
+        }
+    }
+}
+
+/// Modifies a copy of a JSON document and validates the result as glTF,
+/// returning the modified copy and the root read from it.
+fn patch<F>(document: &Value, f: F) -> Result<(Value, Root), Error>
+    where F: FnOnce(&mut Value) -> Result<(), Error>
+{
+    use serde::Deserialize;
+
+    let mut document = document.clone();
+    f(&mut document)?;
+    let patched = Root::deserialize(&document)?;
+    let mut errors = vec![];
+    patched.validate_minimally(&patched, Path::new, &mut |path, error| {
+        errors.push((path(), error))
+    });
+    patched.validate_completely(&patched, Path::new, &mut |path, error| {
+        errors.push((path(), error))
+    });
+    if !errors.is_empty() {
+        return Err(Error::Validation(errors));
+    }
+    Ok((document, patched))
+}
+
+/// Applies a [JSON patch (RFC 6902)](https://tools.ietf.org/html/rfc6902)
+/// to a raw glTF document, e.g. the parsed text of a `.gltf` file, and
+/// returns the patched root.
+///
+/// The raw document is patched as is, so names, `extras`, and extensions are
+/// kept whether or not they are modelled by `Root` under the enabled
+/// features. The patched document is validated against the glTF 2.0
+/// specification. Either all operations are applied or, on error, the
+/// document is left unchanged.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate gltf_json as json;
+/// # #[macro_use]
+/// # extern crate serde_json;
+/// # fn main() {
+/// let mut document = json!({
+///     "asset": { "version": "2.0" },
+///     "nodes": [{ "name": "Cube", "extras": { "tag": 1 } }]
+/// });
+/// json::patch::json_patch(&mut document, &json!([
+///     { "op": "add", "path": "/nodes/0/translation", "value": [0, 1, 0] }
+/// ])).unwrap();
+/// assert_eq!("Cube", document["nodes"][0]["name"]);
+/// assert_eq!(1, document["nodes"][0]["extras"]["tag"]);
+/// # }
+/// ```
+pub fn json_patch(document: &mut Value, patch: &Value) -> Result<Root, Error> {
+    let (patched, root) = self::patch(document, |document| apply_operations(document, patch))?;
+    *document = patched;
+    Ok(root)
+}
+
+/// Applies a [JSON merge patch (RFC 7396)](https://tools.ietf.org/html/rfc7396)
+/// to a raw glTF document and returns the patched root.
+///
+/// See `json_patch`. Note that merge patches replace arrays as a whole.
+pub fn merge_patch(document: &mut Value, patch: &Value) -> Result<Root, Error> {
+    let (patched, root) = self::patch(document, |document| {
+        merge(document, patch);
+        Ok(())
+    })?;
+    *document = patched;
+    Ok(root)
+}
+
+impl Root {
+    /// Applies a [JSON patch (RFC 6902)](https://tools.ietf.org/html/rfc6902)
+    /// to the document.
+    ///
+    /// The document is converted to JSON, patched, and read back, then
+    /// validated against the glTF 2.0 specification. Either all operations are
+    /// applied or, on error, the document is left unchanged.
+    ///
+    /// Only data held by the `Root` takes part, so names and `extras` need the
+    /// `names` and `extras` features; without them, patching a document that
+    /// had any fails with `Error::Json`. Use `json_patch` to patch the raw
+    /// document instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate gltf_json as json;
+    /// # #[macro_use]
+    /// # extern crate serde_json;
+    /// # fn main() {
+    /// let mut root: json::Root = json::from_str(r#"{
+    ///     "asset": { "version": "2.0" },
+    ///     "materials": [{}, {}, {}]
+    /// }"#).unwrap();
+    /// root.apply_json_patch(&json!([{
+    ///     "op": "replace",
+    ///     "path": "/materials/2/pbrMetallicRoughness/roughnessFactor",
+    ///     "value": 0.5
+    /// }])).unwrap();
+    /// assert_eq!(0.5, root.materials[2].pbr_metallic_roughness.roughness_factor.0);
+    /// # }
+    /// ```
+    pub fn apply_json_patch(&mut self, patch: &Value) -> Result<(), Error> {
+        *self = json_patch(&mut serde_json::to_value(&*self)?, patch)?;
+        Ok(())
+    }
+
+    /// Applies a [JSON merge patch (RFC 7396)](https://tools.ietf.org/html/rfc7396)
+    /// to the document.
+    ///
+    /// Validation works as for `apply_json_patch`. Note that merge patches
+    /// replace arrays as a whole.
+    pub fn apply_merge_patch(&mut self, patch: &Value) -> Result<(), Error> {
+        *self = merge_patch(&mut serde_json::to_value(&*self)?, patch)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const JSON: &'static str = r#"{
+        "asset": { "version": "2.0" },
+        "materials": [{ "alphaMode": "MASK" }],
+        "nodes": [{}, { "translation": [1, 2, 3] }],
+        "scenes": [{ "nodes": [0, 1] }]
+    }"#;
+
+    #[test]
+    fn json_patch() {
+        let mut root: Root = serde_json::from_str(JSON).unwrap();
+        let patch = json!([
+            { "op": "test", "path": "/materials/0/alphaMode", "value": "MASK" },
+            { "op": "add", "path": "/materials/-", "value": { "doubleSided": true } },
+            { "op": "copy", "from": "/nodes/1", "path": "/nodes/-" },
+            { "op": "add", "path": "/scenes/0/nodes/-", "value": 2 },
+            { "op": "test", "path": "/nodes/2/translation", "value": [1.0, 2.0, 3.0] },
+            { "op": "move", "from": "/nodes/1/translation", "path": "/nodes/0/translation" },
+            { "op": "remove", "path": "/materials/0/alphaMode" }
+        ]);
+        root.apply_json_patch(&patch).unwrap();
+        assert_eq!(2, root.materials.len());
+        assert!(root.materials[1].double_sided);
+        assert_eq!(3, root.nodes.len());
+        assert_eq!([1.0, 2.0, 3.0], root.nodes[0].translation);
+        assert_eq!([0.0, 0.0, 0.0], root.nodes[1].translation);
+        assert_eq!(3, root.scenes[0].nodes.len());
+        let value = serde_json::to_value(&root).unwrap();
+        assert_eq!("OPAQUE", value["materials"][0]["alphaMode"]);
+
+        let failures = [
+            (json!({}), 0),
+            (json!([{ "op": "test", "path": "/materials/0/doubleSided", "value": true }]), 0),
+            (json!([{ "op": "replace", "path": "/nodes/9", "value": {} }]), 0),
+            (json!([{ "op": "move", "from": "/nodes/0", "path": "/nodes/0/children" }]), 0),
+            (json!([{ "op": "add", "path": "/nodes/-", "value": {} }, { "op": "copy" }]), 1),
+        ];
+        for &(ref patch, index) in &failures {
+            match root.apply_json_patch(patch) {
+                Err(Error::Malformed(i)) | Err(Error::Location(i)) | Err(Error::Test(i)) => {
+                    assert_eq!(index, i)
+                },
+                result => panic!("unexpected result {:?}", result),
+            }
+        }
+        assert_eq!(3, root.nodes.len());
+    }
+
+    #[test]
+    fn merge_patch_validates() {
+        let mut root: Root = serde_json::from_str(JSON).unwrap();
+        let patch = json!({ "materials": [{ "emissiveFactor": [1, 0.5, 0] }] });
+        root.apply_merge_patch(&patch).unwrap();
+        assert_eq!([1.0, 0.5, 0.0], root.materials[0].emissive_factor.0);
+
+        // Scene 0 would reference a missing node.
+        match root.apply_merge_patch(&json!({ "nodes": [{}] })) {
+            Err(Error::Validation(ref errors)) => {
+                assert_eq!("scenes[0].nodes[1]", errors[0].0.as_str())
+            },
+            result => panic!("unexpected result {:?}", result),
+        }
+        assert_eq!(2, root.nodes.len());
+    }
+
+    #[test]
+    fn raw_patch_keeps_unmodelled_data() {
+        let mut document: Value = serde_json::from_str(JSON).unwrap();
+        document["nodes"][0] = json!({
+            "name": "a",
+            "extras": { "tag": true },
+            "extensions": { "KHR_lights_punctual": { "light": 0 } }
+        });
+        let original = document.clone();
+        let patch = json!([{ "op": "add", "path": "/nodes/0/scale", "value": [2, 2, 2] }]);
+        let root = super::json_patch(&mut document, &patch).unwrap();
+        assert_eq!([2.0, 2.0, 2.0], root.nodes[0].scale);
+        assert_eq!("a", document["nodes"][0]["name"]);
+        assert_eq!(true, document["nodes"][0]["extras"]["tag"]);
+        assert_eq!(0, document["nodes"][0]["extensions"]["KHR_lights_punctual"]["light"]);
+
+        let mut unchanged = original.clone();
+        assert!(merge_patch(&mut unchanged, &json!({ "scenes": [{ "nodes": [5] }] })).is_err());
+        assert_eq!(original, unchanged);
+    }
+
+    #[cfg(not(feature = "names"))]
+    #[test]
+    fn discarded_names_fail_loudly() {
+        let mut root: Root = serde_json::from_str(r#"{
+            "asset": { "version": "2.0" },
+            "nodes": [{ "name": "a" }]
+        }"#).unwrap();
+        match root.apply_merge_patch(&json!({ "scene": null })) {
+            Err(Error::Json(_)) => {},
+            result => panic!("unexpected result {:?}", result),
+        }
+    }
+}
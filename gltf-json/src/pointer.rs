@@ -0,0 +1,130 @@
+use {animation, buffer, mesh, texture};
+use {Accessor, Animation, Asset, Buffer, Camera, Image, Material, Mesh, Node, Root, Scene, Skin, Texture};
+
+/// A reference to a glTF object resolved from a JSON pointer.
+///
+/// See [RFC 6901](https://tools.ietf.org/html/rfc6901) for the JSON pointer
+/// syntax.
+#[derive(Clone, Copy, Debug)]
+pub enum Object<'a> {
+    /// The root object, referenced by the empty pointer `""`.
+    Root(&'a Root),
+
+    /// An accessor, e.g. `"/accessors/0"`.
+    Accessor(&'a Accessor),
+
+    /// An animation, e.g. `"/animations/0"`.
+    Animation(&'a Animation),
+
+    /// An animation channel, e.g. `"/animations/0/channels/1"`.
+    AnimationChannel(&'a animation::Channel),
+
+    /// An animation sampler, e.g. `"/animations/0/samplers/1"`.
+    AnimationSampler(&'a animation::Sampler),
+
+    /// The asset metadata, referenced by `"/asset"`.
+    Asset(&'a Asset),
+
+    /// A buffer, e.g. `"/buffers/0"`.
+    Buffer(&'a Buffer),
+
+    /// A buffer view, e.g. `"/bufferViews/0"`.
+    View(&'a buffer::View),
+
+    /// A camera, e.g. `"/cameras/0"`.
+    Camera(&'a Camera),
+
+    /// An image, e.g. `"/images/0"`.
+    Image(&'a Image),
+
+    /// A material, e.g. `"/materials/0"`.
+    Material(&'a Material),
+
+    /// A mesh, e.g. `"/meshes/0"`.
+    Mesh(&'a Mesh),
+
+    /// A mesh primitive, e.g. `"/meshes/0/primitives/1"`.
+    Primitive(&'a mesh::Primitive),
+
+    /// A node, e.g. `"/nodes/0"`.
+    Node(&'a Node),
+
+    /// A texture sampler, e.g. `"/samplers/0"`.
+    Sampler(&'a texture::Sampler),
+
+    /// A scene, e.g. `"/scenes/0"`.
+    Scene(&'a Scene),
+
+    /// A skin, e.g. `"/skins/0"`.
+    Skin(&'a Skin),
+
+    /// A texture, e.g. `"/textures/0"`.
+    Texture(&'a Texture),
+}
+
+/// Parses a JSON pointer array index, rejecting leading zeros and signs.
+pub(crate) fn parse_index(token: &str) -> Option<usize> {
+    if token.is_empty() || (token.len() > 1 && token.starts_with('0')) {
+        return None;
+    }
+    if !token.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    token.parse().ok()
+}
+
+/// Resolves a JSON pointer against the root object.
+pub(crate) fn resolve<'a>(root: &'a Root, pointer: &str) -> Option<Object<'a>> {
+    if pointer.is_empty() {
+        return Some(Object::Root(root));
+    }
+    if !pointer.starts_with('/') {
+        return None;
+    }
+    let tokens: Vec<&str> = pointer[1..].split('/').collect();
+    let object = match tokens[..] {
+        ["asset"] => Object::Asset(&root.asset),
+        ["accessors", i] => Object::Accessor(root.accessors.get(parse_index(i)?)?),
+        ["animations", i] => Object::Animation(root.animations.get(parse_index(i)?)?),
+        ["animations", i, "channels", j] => {
+            let animation = root.animations.get(parse_index(i)?)?;
+            Object::AnimationChannel(animation.channels.get(parse_index(j)?)?)
+        },
+        ["animations", i, "samplers", j] => {
+            let animation = root.animations.get(parse_index(i)?)?;
+            Object::AnimationSampler(animation.samplers.get(parse_index(j)?)?)
+        },
+        ["buffers", i] => Object::Buffer(root.buffers.get(parse_index(i)?)?),
+        ["bufferViews", i] => Object::View(root.buffer_views.get(parse_index(i)?)?),
+        ["cameras", i] => Object::Camera(root.cameras.get(parse_index(i)?)?),
+        ["images", i] => Object::Image(root.images.get(parse_index(i)?)?),
+        ["materials", i] => Object::Material(root.materials.get(parse_index(i)?)?),
+        ["meshes", i] => Object::Mesh(root.meshes.get(parse_index(i)?)?),
+        ["meshes", i, "primitives", j] => {
+            let mesh = root.meshes.get(parse_index(i)?)?;
+            Object::Primitive(mesh.primitives.get(parse_index(j)?)?)
+        },
+        ["nodes", i] => Object::Node(root.nodes.get(parse_index(i)?)?),
+        ["samplers", i] => Object::Sampler(root.samplers.get(parse_index(i)?)?),
+        ["scenes", i] => Object::Scene(root.scenes.get(parse_index(i)?)?),
+        ["skins", i] => Object::Skin(root.skins.get(parse_index(i)?)?),
+        ["textures", i] => Object::Texture(root.textures.get(parse_index(i)?)?),
+        _ => return None,
+    };
+    Some(object)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn index() {
+        assert_eq!(Some(0), parse_index("0"));
+        assert_eq!(Some(12), parse_index("12"));
+        assert_eq!(None, parse_index("012"));
+        assert_eq!(None, parse_index("+1"));
+        assert_eq!(None, parse_index("-1"));
+        assert_eq!(None, parse_index(""));
+    }
+}
@@ -1,8 +1,10 @@
 use buffer;
+use pointer;
 use extensions;
 use serde;
 use std::{self, fmt, marker};
 use texture;
+use visit;
 
 use path::Path;
 use validation::{Error, Validate};
@@ -14,85 +16,150 @@ pub trait Get<T> {
     fn get(&self, id: &Index<T>) -> Option<&T>;
 }
 
+/// The number of references to each object of a document, by array index.
+///
+/// Only references from other objects are counted, so an object referenced by
+/// none, such as an unused accessor or a node outside every scene hierarchy,
+/// has a count of zero. The default scene counts as a reference to a scene.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ReferenceCounts {
+    /// References to accessors from primitives, morph targets, skins, and
+    /// animation samplers.
+    pub accessors: Vec<usize>,
+
+    /// References to the clips of `KHR_audio` from its sources.
+    #[cfg(feature = "audio")]
+    pub audio_clips: Vec<usize>,
+
+    /// References to the emitters of `KHR_audio` from nodes and scenes.
+    #[cfg(feature = "audio")]
+    pub audio_emitters: Vec<usize>,
+
+    /// References to the sources of `KHR_audio` from its emitters.
+    #[cfg(feature = "audio")]
+    pub audio_sources: Vec<usize>,
+
+    /// References to buffer views from accessors, sparse storage, images, the
+    /// clips of `KHR_audio`, and the property tables of
+    /// `EXT_structural_metadata`.
+    pub buffer_views: Vec<usize>,
+
+    /// References to buffers from buffer views.
+    pub buffers: Vec<usize>,
+
+    /// References to cameras from nodes.
+    pub cameras: Vec<usize>,
+
+    /// References to images from textures, including the `source` of texture
+    /// extensions this crate does not model, e.g. `KHR_texture_basisu`.
+    pub images: Vec<usize>,
+
+    /// References to materials from primitives.
+    pub materials: Vec<usize>,
+
+    /// References to meshes from nodes.
+    pub meshes: Vec<usize>,
+
+    /// References to nodes from parent nodes, scenes, skins, animation
+    /// channels, and the colliders, triggers, and joints of
+    /// `KHR_physics_rigid_bodies`.
+    pub nodes: Vec<usize>,
+
+    /// References to samplers from textures.
+    pub samplers: Vec<usize>,
+
+    /// References to scenes from the root object.
+    pub scenes: Vec<usize>,
+
+    /// References to skins from nodes.
+    pub skins: Vec<usize>,
+
+    /// References to textures from materials, including those of
+    /// `KHR_materials_clearcoat`, and the feature ID textures of
+    /// `EXT_mesh_features`.
+    pub textures: Vec<usize>,
+}
+
 /// Represents an offset into an array of type `T` owned by the root glTF object.
 #[derive(Clone, Copy)]
 pub struct Index<T>(u32, marker::PhantomData<T>);
 
 /// The root object of a glTF 2.0 asset.
-#[derive(Clone, Debug, Default, Deserialize, Validate)]
+#[derive(Clone, Debug, Default, Deserialize, Serialize, Validate)]
 pub struct Root {
     /// An array of accessors.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub accessors: Vec<Accessor>,
     
     /// An array of keyframe animations.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub animations: Vec<Animation>,
 
     /// Metadata about the glTF asset.
     pub asset: Asset,
     
     /// An array of buffers.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub buffers: Vec<Buffer>,
     
     /// An array of buffer views.
-    #[serde(default, rename = "bufferViews")]
+    #[serde(default, rename = "bufferViews", skip_serializing_if = "Vec::is_empty")]
     pub buffer_views: Vec<buffer::View>,
 
     /// The default scene.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub scene: Option<Index<Scene>>,
 
     /// Extension specific data.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "::extensions::root::Root::is_empty")]
     pub extensions: extensions::root::Root,
 
     /// Optional application specific data.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "::extras::is_empty")]
     pub extras: Extras,
     
     /// Names of glTF extensions used somewhere in this asset.
-    #[serde(default, rename = "extensionsUsed")]
+    #[serde(default, rename = "extensionsUsed", skip_serializing_if = "Vec::is_empty")]
     pub extensions_used: Vec<String>,
 
     /// Names of glTF extensions required to properly load this asset.
-    #[serde(default, rename = "extensionsRequired")]
+    #[serde(default, rename = "extensionsRequired", skip_serializing_if = "Vec::is_empty")]
     pub extensions_required: Vec<String>,
     
     /// An array of cameras.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub cameras: Vec<Camera>,
     
     /// An array of images.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub images: Vec<Image>,
     
     /// An array of materials.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub materials: Vec<Material>,
     
     /// An array of meshes.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub meshes: Vec<Mesh>,
     
     /// An array of nodes.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub nodes: Vec<Node>,
     
     /// An array of samplers.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub samplers: Vec<texture::Sampler>,
     
     /// An array of scenes.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub scenes: Vec<Scene>,
     
     /// An array of skins.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub skins: Vec<Skin>,
     
     /// An array of textures.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub textures: Vec<Texture>,
 }
 
@@ -103,11 +170,196 @@ impl Root {
     {
         (self as &Get<T>).get(index)
     }
+
+    /// Resolves a JSON pointer such as `"/meshes/3/primitives/0"` to the
+    /// object it references.
+    ///
+    /// Returns `None` if the pointer is malformed, out of bounds, or does not
+    /// reference a glTF object.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use gltf_json::{pointer, Root};
+    /// let root: Root = gltf_json::from_str(r#"{
+    ///     "asset": { "version": "2.0" },
+    ///     "meshes": [{ "primitives": [{ "attributes": {} }] }]
+    /// }"#).unwrap();
+    /// match root.get_pointer("/meshes/0/primitives/0") {
+    ///     Some(pointer::Object::Primitive(_)) => {},
+    ///     _ => panic!(),
+    /// }
+    /// assert!(root.get_pointer("/meshes/1").is_none());
+    /// ```
+    pub fn get_pointer<'a>(&'a self, pointer: &str) -> Option<pointer::Object<'a>> {
+        pointer::resolve(self, pointer)
+    }
+
+    /// Counts how many times each object is referenced by other objects.
+    ///
+    /// Out of range indices are ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use gltf_json::Root;
+    /// let root: Root = gltf_json::from_str(r#"{
+    ///     "asset": { "version": "2.0" },
+    ///     "materials": [{}, {}],
+    ///     "meshes": [{ "primitives": [
+    ///         { "attributes": {}, "material": 1 },
+    ///         { "attributes": {}, "material": 1 }
+    ///     ] }]
+    /// }"#).unwrap();
+    /// let counts = root.reference_counts();
+    /// assert_eq!(vec![0, 2], counts.materials);
+    /// assert_eq!(vec![0], counts.meshes);
+    /// ```
+    pub fn reference_counts(&self) -> ReferenceCounts {
+        fn count<T>(counts: &mut [usize], index: &Index<T>) {
+            if let Some(count) = counts.get_mut(index.value()) {
+                *count += 1;
+            }
+        }
+
+        #[cfg(feature = "audio")]
+        let audio = self.extensions.audio.as_ref();
+        let mut counts = ReferenceCounts {
+            accessors: vec![0; self.accessors.len()],
+            #[cfg(feature = "audio")]
+            audio_clips: vec![0; audio.map_or(0, |audio| audio.clips.len())],
+            #[cfg(feature = "audio")]
+            audio_emitters: vec![0; audio.map_or(0, |audio| audio.emitters.len())],
+            #[cfg(feature = "audio")]
+            audio_sources: vec![0; audio.map_or(0, |audio| audio.sources.len())],
+            buffer_views: vec![0; self.buffer_views.len()],
+            buffers: vec![0; self.buffers.len()],
+            cameras: vec![0; self.cameras.len()],
+            images: vec![0; self.images.len()],
+            materials: vec![0; self.materials.len()],
+            meshes: vec![0; self.meshes.len()],
+            nodes: vec![0; self.nodes.len()],
+            samplers: vec![0; self.samplers.len()],
+            scenes: vec![0; self.scenes.len()],
+            skins: vec![0; self.skins.len()],
+            textures: vec![0; self.textures.len()],
+        };
+
+        visit::accessors(self, |index| count(&mut counts.accessors, index));
+        visit::buffer_views(self, |index| count(&mut counts.buffer_views, index));
+        visit::textures(self, |index| count(&mut counts.textures, index));
+        for animation in &self.animations {
+            for channel in &animation.channels {
+                count(&mut counts.nodes, &channel.target.node);
+            }
+        }
+        for view in &self.buffer_views {
+            count(&mut counts.buffers, &view.buffer);
+        }
+        for mesh in &self.meshes {
+            for primitive in &mesh.primitives {
+                if let Some(ref index) = primitive.material {
+                    count(&mut counts.materials, index);
+                }
+            }
+        }
+        for node in &self.nodes {
+            if let Some(ref index) = node.camera {
+                count(&mut counts.cameras, index);
+            }
+            for child in node.children.iter().flat_map(|children| children.iter()) {
+                count(&mut counts.nodes, child);
+            }
+            if let Some(ref index) = node.mesh {
+                count(&mut counts.meshes, index);
+            }
+            if let Some(ref index) = node.skin {
+                count(&mut counts.skins, index);
+            }
+            #[cfg(feature = "audio")]
+            {
+                if let Some(ref audio) = node.extensions.audio {
+                    count(&mut counts.audio_emitters, &audio.emitter);
+                }
+            }
+            #[cfg(feature = "physics")]
+            {
+                if let Some(ref body) = node.extensions.physics {
+                    let collider = body.collider.as_ref().map(|collider| &collider.geometry);
+                    let trigger = body.trigger.as_ref();
+                    let geometries = trigger.and_then(|trigger| trigger.geometry.as_ref());
+                    for geometry in collider.into_iter().chain(geometries) {
+                        if let Some(ref index) = geometry.node {
+                            count(&mut counts.nodes, index);
+                        }
+                    }
+                    for index in trigger.iter().flat_map(|trigger| trigger.nodes.iter()) {
+                        count(&mut counts.nodes, index);
+                    }
+                    if let Some(ref joint) = body.joint {
+                        count(&mut counts.nodes, &joint.connected_node);
+                    }
+                }
+            }
+        }
+        if let Some(ref index) = self.scene {
+            count(&mut counts.scenes, index);
+        }
+        for scene in &self.scenes {
+            for node in &scene.nodes {
+                count(&mut counts.nodes, node);
+            }
+            #[cfg(feature = "audio")]
+            {
+                if let Some(ref audio) = scene.extensions.audio {
+                    for index in &audio.emitters {
+                        count(&mut counts.audio_emitters, index);
+                    }
+                }
+            }
+        }
+        for skin in &self.skins {
+            for joint in &skin.joints {
+                count(&mut counts.nodes, joint);
+            }
+            if let Some(ref index) = skin.skeleton {
+                count(&mut counts.nodes, index);
+            }
+        }
+        for texture in &self.textures {
+            if let Some(ref index) = texture.sampler {
+                count(&mut counts.samplers, index);
+            }
+            count(&mut counts.images, &texture.source);
+            for value in texture.extensions.others.values() {
+                let source = value.get("source").and_then(::Value::as_u64);
+                if let Some(source) = source {
+                    count(&mut counts.images, &Index::<Image>::new(source as u32));
+                }
+            }
+        }
+        #[cfg(feature = "audio")]
+        {
+            if let Some(audio) = audio {
+                for source in &audio.sources {
+                    if let Some(ref index) = source.clip {
+                        count(&mut counts.audio_clips, index);
+                    }
+                }
+                for emitter in &audio.emitters {
+                    for index in &emitter.sources {
+                        count(&mut counts.audio_sources, index);
+                    }
+                }
+            }
+        }
+        counts
+    }
 }
 
 impl<T> Index<T> {
     /// Creates a new `Index` representing an offset into an array containing `T`.
-    fn new(value: u32) -> Self {
+    pub fn new(value: u32) -> Self {
         Index(value, std::marker::PhantomData)
     }
 
@@ -194,3 +446,96 @@ impl_get!(texture::Sampler, samplers);
 impl_get!(Scene, scenes);
 impl_get!(Skin, skins);
 impl_get!(Texture, textures);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json;
+
+    #[test]
+    fn reference_counts() {
+        let root: Root = serde_json::from_str(r#"{
+            "asset": { "version": "2.0" },
+            "buffers": [{ "byteLength": 8 }, { "byteLength": 8 }],
+            "bufferViews": [
+                { "buffer": 0, "byteLength": 8 },
+                { "buffer": 0, "byteLength": 4 }
+            ],
+            "accessors": [
+                { "bufferView": 0, "componentType": 5126, "count": 2, "type": "SCALAR" },
+                { "bufferView": 0, "componentType": 5126, "count": 2, "type": "SCALAR" }
+            ],
+            "images": [{ "bufferView": 1, "mimeType": "image/png" }],
+            "textures": [
+                { "source": 0, "extensions": { "KHR_texture_basisu": { "source": 0 } } },
+                { "source": 0, "sampler": 0 }
+            ],
+            "samplers": [{}],
+            "materials": [{
+                "pbrMetallicRoughness": { "baseColorTexture": { "index": 1 } },
+                "emissiveTexture": { "index": 1 }
+            }],
+            "meshes": [{ "primitives": [{
+                "attributes": { "POSITION": 0 },
+                "material": 0,
+                "targets": [{ "POSITION": 0 }]
+            }] }],
+            "nodes": [{ "mesh": 0, "children": [1] }, {}, {}],
+            "scenes": [{ "nodes": [0] }],
+            "scene": 0,
+            "animations": [{
+                "channels": [{ "sampler": 0, "target": { "node": 1, "path": "scale" } }],
+                "samplers": [{ "input": 1, "output": 1 }]
+            }]
+        }"#).unwrap();
+        let counts = root.reference_counts();
+        assert_eq!(vec![2, 0], counts.buffers);
+        assert_eq!(vec![2, 1], counts.buffer_views);
+        assert_eq!(vec![2, 2], counts.accessors);
+        assert_eq!(vec![3], counts.images);
+        assert_eq!(vec![0, 2], counts.textures);
+        assert_eq!(vec![1], counts.samplers);
+        assert_eq!(vec![1], counts.materials);
+        assert_eq!(vec![1], counts.meshes);
+        assert_eq!(vec![1, 2, 0], counts.nodes);
+        assert_eq!(vec![1], counts.scenes);
+    }
+
+    #[cfg(all(feature = "audio", feature = "physics"))]
+    #[test]
+    fn extension_reference_counts() {
+        let root: Root = serde_json::from_str(r#"{
+            "asset": { "version": "2.0" },
+            "buffers": [{ "byteLength": 8 }],
+            "bufferViews": [{ "buffer": 0, "byteLength": 8 }],
+            "extensions": {
+                "KHR_audio": {
+                    "audio": [{ "bufferView": 0, "mimeType": "audio/mpeg" }],
+                    "sources": [{ "audio": 0 }, { "audio": 0 }],
+                    "emitters": [{ "type": "global", "sources": [1] }]
+                },
+                "KHR_physics_rigid_bodies": { "physicsJoints": [{}] }
+            },
+            "nodes": [
+                { "extensions": { "KHR_audio": { "emitter": 0 } } },
+                {
+                    "extensions": {
+                        "KHR_physics_rigid_bodies": {
+                            "collider": { "geometry": { "node": 0 } },
+                            "trigger": { "nodes": [2] },
+                            "joint": { "connectedNode": 2, "joint": 0 }
+                        }
+                    }
+                },
+                {}
+            ],
+            "scenes": [{ "nodes": [], "extensions": { "KHR_audio": { "emitters": [0] } } }]
+        }"#).unwrap();
+        let counts = root.reference_counts();
+        assert_eq!(vec![1], counts.buffer_views);
+        assert_eq!(vec![2], counts.audio_clips);
+        assert_eq!(vec![0, 1], counts.audio_sources);
+        assert_eq!(vec![2], counts.audio_emitters);
+        assert_eq!(vec![1, 0, 2], counts.nodes);
+    }
+}
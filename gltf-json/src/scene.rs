@@ -11,78 +11,131 @@ use {camera, extensions, mesh, scene, skin, Extras, Index, Root, Path};
 /// identity. When a node is targeted for animation (referenced by an
 /// animation.channel.target), only TRS properties may be present; `matrix` will not
 /// be present.
-#[derive(Clone, Debug, Deserialize, Validate)]
+#[derive(Clone, Debug, Deserialize, Serialize, Validate)]
 pub struct Node {
     /// The index of the camera referenced by this node.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub camera: Option<Index<camera::Camera>>,
     
     /// The indices of this node's children.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub children: Option<Vec<Index<scene::Node>>>,
 
     /// Extension specific data.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "::extensions::scene::Node::is_empty")]
     pub extensions: extensions::scene::Node,
     
     /// Optional application specific data.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "::extras::is_empty")]
     pub extras: Extras,
     
     /// 4x4 column-major transformation matrix.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub matrix: Option<[f32; 16]>,
 
     /// The index of the mesh in this node.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub mesh: Option<Index<mesh::Mesh>>,
     
     /// Optional user-defined name for this object.
+    #[serde(skip_serializing_if = "Option::is_none")]
     #[cfg(feature = "names")]
     pub name: Option<String>,
+
+    /// Records a name discarded because the `names` feature is disabled.
+    #[serde(default, skip_serializing_if = "::extras::Void::is_absent")]
+    #[cfg(not(feature = "names"))]
+    pub name: ::extras::Void,
     
     /// The node's unit quaternion rotation in the order (x, y, z, w), where w is
     /// the scalar.
-    #[serde(default)]
+    ///
+    /// Like `scale` and `translation`, it is only serialized when it differs
+    /// from the default, so a node with a `matrix` is written without TRS
+    /// properties.
+    #[serde(default, skip_serializing_if = "UnitQuaternion::is_identity")]
     pub rotation: UnitQuaternion,
 
     /// The node's non-uniform scale.
-    #[serde(default = "node_scale_default")]
+    #[serde(default = "node_scale_default", skip_serializing_if = "node_scale_is_default")]
     pub scale: [f32; 3],
 
     /// The node's translation.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "node_translation_is_default")]
     pub translation: [f32; 3],
     
     /// The index of the skin referenced by this node.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub skin: Option<Index<skin::Skin>>,
     
     /// The weights of the instantiated Morph Target. Number of elements must match
     /// the number of Morph Targets of used mesh.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub weights: Option<Vec<f32>>,
 }
 
+impl Default for Node {
+    fn default() -> Self {
+        Node {
+            camera: None,
+            children: None,
+            extensions: Default::default(),
+            extras: Default::default(),
+            matrix: None,
+            mesh: None,
+            #[cfg(feature = "names")]
+            name: None,
+            #[cfg(not(feature = "names"))]
+            name: Default::default(),
+            rotation: Default::default(),
+            scale: node_scale_default(),
+            translation: [0.0; 3],
+            skin: None,
+            weights: None,
+        }
+    }
+}
+
 fn node_scale_default() -> [f32; 3] {
     [1.0, 1.0, 1.0]
 }
 
+fn node_scale_is_default(scale: &[f32; 3]) -> bool {
+    *scale == node_scale_default()
+}
+
+fn node_translation_is_default(translation: &[f32; 3]) -> bool {
+    *translation == [0.0, 0.0, 0.0]
+}
+
 /// The root `Node`s of a scene.
-#[derive(Clone, Debug, Deserialize, Validate)]
+#[derive(Clone, Debug, Default, Deserialize, Serialize, Validate)]
 pub struct Scene {
     /// Extension specific data.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "::extensions::scene::Scene::is_empty")]
     pub extensions: extensions::scene::Scene,
     
     /// Optional application specific data.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "::extras::is_empty")]
     pub extras: Extras,
     
     /// Optional user-defined name for this object.
+    #[serde(skip_serializing_if = "Option::is_none")]
     #[cfg(feature = "names")]
     pub name: Option<String>,
 
+    /// Records a name discarded because the `names` feature is disabled.
+    #[serde(default, skip_serializing_if = "::extras::Void::is_absent")]
+    #[cfg(not(feature = "names"))]
+    pub name: ::extras::Void,
+
     /// The indices of each root node.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
     pub nodes: Vec<Index<Node>>,
 }
 
 /// Unit quaternion rotation in the order (x, y, z, w), where w is the scalar.
-#[derive(Clone, Copy, Debug, Deserialize)]
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
 pub struct UnitQuaternion(pub [f32; 4]);
 
 impl Default for UnitQuaternion {
@@ -91,6 +144,13 @@ impl Default for UnitQuaternion {
     }
 }
 
+impl UnitQuaternion {
+    /// Returns whether this is the identity rotation, the default.
+    pub fn is_identity(&self) -> bool {
+        self.0 == UnitQuaternion::default().0
+    }
+}
+
 impl Validate for UnitQuaternion {
     fn validate_completely<P, R>(&self, _: &Root, path: P, report: &mut R)
         where P: Fn() -> Path, R: FnMut(&Fn() -> Path, Error)
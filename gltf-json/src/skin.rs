@@ -1,21 +1,21 @@
 use {accessor, extensions, scene, Extras, Index};
 
 /// Joints and matrices defining a skin.
-#[derive(Clone, Debug, Deserialize, Validate)]
+#[derive(Clone, Debug, Default, Deserialize, Serialize, Validate)]
 pub struct Skin {
     /// Extension specific data.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "::extensions::skin::Skin::is_empty")]
     pub extensions: extensions::skin::Skin,
     
     /// Optional application specific data.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "::extras::is_empty")]
     pub extras: Extras,
     
     /// The index of the accessor containing the 4x4 inverse-bind matrices.
     ///
     /// When `None`,each matrix is assumed to be the 4x4 identity matrix
     /// which implies that the inverse-bind matrices were pre-applied.
-    #[serde(rename = "inverseBindMatrices")]
+    #[serde(rename = "inverseBindMatrices", skip_serializing_if = "Option::is_none")]
     pub inverse_bind_matrices: Option<Index<accessor::Accessor>>,
     
     /// Indices of skeleton nodes used as joints in this skin.
@@ -25,11 +25,18 @@ pub struct Skin {
     pub joints: Vec<Index<scene::Node>>,
     
     /// Optional user-defined name for this object.
+    #[serde(skip_serializing_if = "Option::is_none")]
     #[cfg(feature = "names")]
     pub name: Option<String>,
+
+    /// Records a name discarded because the `names` feature is disabled.
+    #[serde(default, skip_serializing_if = "::extras::Void::is_absent")]
+    #[cfg(not(feature = "names"))]
+    pub name: ::extras::Void,
     
     /// The index of the node used as a skeleton root.
     ///
     /// When `None`, joints transforms resolve to scene root.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub skeleton: Option<Index<scene::Node>>,
 }
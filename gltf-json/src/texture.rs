@@ -1,4 +1,4 @@
-use serde::de;
+use serde::{de, ser};
 use std::fmt;
 use validation::Checked;
 use {extensions, image, Extras, Index};
@@ -134,21 +134,27 @@ impl WrappingMode {
 }
 
 /// Texture sampler properties for filtering and wrapping modes.
-#[derive(Clone, Debug, Default, Deserialize, Validate)]
+#[derive(Clone, Debug, Default, Deserialize, Serialize, Validate)]
 #[serde(default)]
 pub struct Sampler {
     /// Magnification filter.
-    #[serde(rename = "magFilter")]
+    #[serde(rename = "magFilter", skip_serializing_if = "Option::is_none")]
     pub mag_filter: Option<Checked<MagFilter>>,
 
     /// Minification filter.
-    #[serde(rename = "minFilter")]
+    #[serde(rename = "minFilter", skip_serializing_if = "Option::is_none")]
     pub min_filter: Option<Checked<MinFilter>>,
 
     /// Optional user-defined name for this object.
+    #[serde(skip_serializing_if = "Option::is_none")]
     #[cfg(feature = "names")]
     pub name: Option<String>,
 
+    /// Records a name discarded because the `names` feature is disabled.
+    #[serde(default, skip_serializing_if = "::extras::Void::is_absent")]
+    #[cfg(not(feature = "names"))]
+    pub name: ::extras::Void,
+
     /// `s` wrapping mode.
     #[serde(default, rename = "wrapS")]
     pub wrap_s: Checked<WrappingMode>,
@@ -158,37 +164,60 @@ pub struct Sampler {
     pub wrap_t: Checked<WrappingMode>,
 
     /// Extension specific data.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "::extensions::texture::Sampler::is_empty")]
     pub extensions: extensions::texture::Sampler,
 
     /// Optional application specific data.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "::extras::is_empty")]
     pub extras: Extras,
 }
 
 /// A texture and its sampler.
-#[derive(Clone, Debug, Deserialize, Validate)]
+#[derive(Clone, Debug, Deserialize, Serialize, Validate)]
 pub struct Texture {
     /// Optional user-defined name for this object.
+    #[serde(skip_serializing_if = "Option::is_none")]
     #[cfg(feature = "names")]
     pub name: Option<String>,
 
+    /// Records a name discarded because the `names` feature is disabled.
+    #[serde(default, skip_serializing_if = "::extras::Void::is_absent")]
+    #[cfg(not(feature = "names"))]
+    pub name: ::extras::Void,
+
     /// The index of the sampler used by this texture.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub sampler: Option<Index<Sampler>>,
 
     /// The index of the image used by this texture.
     pub source: Index<image::Image>,
 
     /// Extension specific data.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "::extensions::texture::Texture::is_empty")]
     pub extensions: extensions::texture::Texture,
 
     /// Optional application specific data.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "::extras::is_empty")]
     pub extras: Extras,
 }
 
-#[derive(Clone, Debug, Deserialize, Validate)]
+impl Texture {
+    /// Creates a texture sampling the given image with the default sampler.
+    pub fn new(source: Index<image::Image>) -> Self {
+        Texture {
+            #[cfg(feature = "names")]
+            name: None,
+            #[cfg(not(feature = "names"))]
+            name: Default::default(),
+            sampler: None,
+            source,
+            extensions: Default::default(),
+            extras: Default::default(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, Validate)]
 /// Reference to a `Texture`.
 pub struct Info {
     /// The index of the texture.
@@ -199,14 +228,50 @@ pub struct Info {
     pub tex_coord: u32,
 
     /// Extension specific data.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "::extensions::texture::Info::is_empty")]
     pub extensions: extensions::texture::Info,
 
     /// Optional application specific data.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "::extras::is_empty")]
     pub extras: Extras,
 }
 
+impl Info {
+    /// Creates a reference to a texture using the first `TEXCOORD` set.
+    pub fn new(index: Index<Texture>) -> Self {
+        Info {
+            index,
+            tex_coord: 0,
+            extensions: Default::default(),
+            extras: Default::default(),
+        }
+    }
+}
+
+impl ser::Serialize for MagFilter {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: ser::Serializer
+    {
+        serializer.serialize_u32(self.as_gl_enum() as u32)
+    }
+}
+
+impl ser::Serialize for MinFilter {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: ser::Serializer
+    {
+        serializer.serialize_u32(self.as_gl_enum() as u32)
+    }
+}
+
+impl ser::Serialize for WrappingMode {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: ser::Serializer
+    {
+        serializer.serialize_u32(self.as_gl_enum() as u32)
+    }
+}
+
 impl<'de> de::Deserialize<'de> for Checked<MagFilter> {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
         where D: de::Deserializer<'de>
@@ -1,7 +1,8 @@
+use serde::{ser, Serialize, Serializer};
 use serde_json;
 use std;
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::hash::Hash;
 
 use {Path, Root};
@@ -63,6 +64,39 @@ impl<T> Checked<T> {
         }
     }
 
+    /// Converts from `Checked<T>` to `Option<T>`, discarding an `Invalid`
+    /// item.
+    pub fn ok(self) -> Option<T> {
+        match self {
+            Checked::Valid(item) => Some(item),
+            Checked::Invalid => None,
+        }
+    }
+
+    /// Takes ownership of the contained item if it is `Valid`, otherwise
+    /// returns an `Invalid` error at the path of the item.
+    ///
+    /// The path is only evaluated on error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gltf_json::Path;
+    /// use gltf_json::validation::{Checked, Error};
+    ///
+    /// let mode: Checked<u32> = Checked::Invalid;
+    /// let result = mode.expect_valid(|| Path::new().field("meshes").index(0));
+    /// assert_eq!(Err((Path::new().field("meshes").index(0), Error::Invalid)), result);
+    /// ```
+    pub fn expect_valid<P>(self, path: P) -> Result<T, (Path, Error)>
+        where P: FnOnce() -> Path
+    {
+        match self {
+            Checked::Valid(item) => Ok(item),
+            Checked::Invalid => Err((path(), Error::Invalid)),
+        }
+    }
+
     /// Takes ownership of the contained item if it is `Valid`.
     ///
     /// # Panics
@@ -76,6 +110,95 @@ impl<T> Checked<T> {
     }
 }
 
+impl<T: Serialize> Serialize for Checked<T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer
+    {
+        match *self {
+            Checked::Valid(ref item) => item.serialize(serializer),
+            Checked::Invalid => Err(ser::Error::custom("invalid item")),
+        }
+    }
+}
+
+/// Specifies how `Policed` iterators treat `Invalid` items.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Policy {
+    /// `Invalid` items are silently skipped.
+    Skip,
+
+    /// `Invalid` items are yielded as errors.
+    Error,
+}
+
+/// Iterator adapter that unwraps `Checked` items according to a `Policy`.
+///
+/// Created by `CheckedIterator::policed`.
+#[derive(Clone, Debug)]
+pub struct Policed<I> {
+    /// The wrapped iterator.
+    iter: I,
+
+    /// The index of the next item of the wrapped iterator.
+    index: usize,
+
+    /// How `Invalid` items are treated.
+    policy: Policy,
+}
+
+impl<I, T> Iterator for Policed<I>
+    where I: Iterator<Item = Checked<T>>
+{
+    /// Either the contained item or the position of an `Invalid` item in the
+    /// wrapped iterator.
+    type Item = Result<T, usize>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let index = self.index;
+            self.index += 1;
+            match self.iter.next()? {
+                Checked::Valid(item) => return Some(Ok(item)),
+                Checked::Invalid => match self.policy {
+                    Policy::Skip => continue,
+                    Policy::Error => return Some(Err(index)),
+                },
+            }
+        }
+    }
+}
+
+/// Extension methods for iterators over `Checked` items.
+pub trait CheckedIterator<T>: Iterator<Item = Checked<T>> + Sized {
+    /// Unwraps each item, skipping or reporting `Invalid` items per `policy`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gltf_json::validation::{Checked, CheckedIterator, Policy};
+    ///
+    /// let items = vec![Checked::Valid(1), Checked::Invalid, Checked::Valid(3)];
+    /// let skipped: Vec<_> = items.clone().into_iter().policed(Policy::Skip).collect();
+    /// assert_eq!(vec![Ok(1), Ok(3)], skipped);
+    /// let checked: Result<Vec<_>, _> = items.into_iter().policed(Policy::Error).collect();
+    /// assert_eq!(Err(1), checked);
+    /// ```
+    fn policed(self, policy: Policy) -> Policed<Self> {
+        Policed {
+            iter: self,
+            index: 0,
+            policy,
+        }
+    }
+
+    /// Unwraps each item, skipping `Invalid` items.
+    fn valid(self) -> ::std::iter::FilterMap<Self, fn(Checked<T>) -> Option<T>> {
+        self.filter_map(Checked::ok)
+    }
+}
+
+impl<I, T> CheckedIterator<T> for I where I: Iterator<Item = Checked<T>> {}
+
 impl<T: Clone> Clone for Checked<T> {
     fn clone(&self) -> Self {
         match *self {
@@ -124,6 +247,26 @@ impl<K: Eq + Hash + ToString + Validate, V: Validate> Validate for HashMap<K, V>
     }
 }
 
+impl<K: Ord + ToString + Validate, V: Validate> Validate for BTreeMap<K, V> {
+    fn validate_minimally<P, R>(&self, root: &Root, path: P, report: &mut R)
+        where P: Fn() -> Path, R: FnMut(&Fn() -> Path, Error)
+    {
+        for (key, value) in self.iter() {
+            key.validate_minimally(root, || path().key(&key.to_string()), report);
+            value.validate_minimally(root, || path().key(&key.to_string()), report);
+        }
+    }
+
+    fn validate_completely<P, R>(&self, root: &Root, path: P, report: &mut R)
+        where P: Fn() -> Path, R: FnMut(&Fn() -> Path, Error)
+    {
+        for (key, value) in self.iter() {
+            key.validate_completely(root, || path().key(&key.to_string()), report);
+            value.validate_completely(root, || path().key(&key.to_string()), report);
+        }
+    }
+}
+
 impl<T: Validate> Validate for Option<T> {
     fn validate_minimally<P, R>(&self, root: &Root, path: P, report: &mut R)
         where P: Fn() -> Path, R: FnMut(&Fn() -> Path, Error)
@@ -160,6 +303,12 @@ impl<T: Validate> Validate for Vec<T> {
     }
 }
 
+/// Returns whether a value is at least zero, which is false for `NaN`.
+#[cfg(any(feature = "audio", feature = "physics"))]
+pub(crate) fn non_negative(value: f32) -> bool {
+    value >= 0.0
+}
+
 impl std::error::Error for Error {
     fn description(&self) -> &str {
         match *self {
@@ -188,3 +337,4 @@ impl Validate for [f32; 16] {}
 impl Validate for () {}
 impl Validate for String {}
 impl Validate for serde_json::Value {}
+impl Validate for serde_json::Map<String, serde_json::Value> {}
@@ -0,0 +1,187 @@
+use texture::Texture;
+use {buffer, Accessor, Index, Root};
+
+// Each visitor is written once and expanded for shared and mutable access.
+// The trailing tokens are either empty or `mut`.
+
+macro_rules! visit_textures {
+    ($root:ident, $f:ident; $($mut_:tt)*) => {
+        for material in & $($mut_)* $root.materials {
+            {
+                let pbr = & $($mut_)* material.pbr_metallic_roughness;
+                if let Some(ref $($mut_)* info) = pbr.base_color_texture {
+                    $f(& $($mut_)* info.index);
+                }
+                if let Some(ref $($mut_)* info) = pbr.metallic_roughness_texture {
+                    $f(& $($mut_)* info.index);
+                }
+            }
+            if let Some(ref $($mut_)* info) = material.normal_texture {
+                $f(& $($mut_)* info.index);
+            }
+            if let Some(ref $($mut_)* info) = material.occlusion_texture {
+                $f(& $($mut_)* info.index);
+            }
+            if let Some(ref $($mut_)* info) = material.emissive_texture {
+                $f(& $($mut_)* info.index);
+            }
+            if let Some(ref $($mut_)* clearcoat) = material.extensions.clearcoat {
+                if let Some(ref $($mut_)* info) = clearcoat.clearcoat_texture {
+                    $f(& $($mut_)* info.index);
+                }
+                if let Some(ref $($mut_)* info) = clearcoat.clearcoat_roughness_texture {
+                    $f(& $($mut_)* info.index);
+                }
+                if let Some(ref $($mut_)* info) = clearcoat.clearcoat_normal_texture {
+                    $f(& $($mut_)* info.index);
+                }
+            }
+        }
+        for mesh in & $($mut_)* $root.meshes {
+            for primitive in & $($mut_)* mesh.primitives {
+                if let Some(ref $($mut_)* features) = primitive.extensions.mesh_features {
+                    for feature_id in & $($mut_)* features.feature_ids {
+                        if let Some(ref $($mut_)* texture) = feature_id.texture {
+                            $f(& $($mut_)* texture.index);
+                        }
+                    }
+                }
+            }
+        }
+    };
+}
+
+/// Calls `f` with every reference to a texture, i.e. the texture slots of
+/// materials, including those of `KHR_materials_clearcoat`, and the feature ID
+/// textures of `EXT_mesh_features`.
+pub fn textures<F>(root: &Root, mut f: F)
+    where F: FnMut(&Index<Texture>)
+{
+    visit_textures!(root, f;);
+}
+
+/// Calls `f` with every reference to a texture, allowing it to be changed.
+///
+/// See `textures`.
+pub fn textures_mut<F>(root: &mut Root, mut f: F)
+    where F: FnMut(&mut Index<Texture>)
+{
+    visit_textures!(root, f; mut);
+}
+
+macro_rules! visit_accessors {
+    ($root:ident, $f:ident; $($mut_:tt)*) => {
+        for mesh in & $($mut_)* $root.meshes {
+            for primitive in & $($mut_)* mesh.primitives {
+                for (_, index) in & $($mut_)* primitive.attributes {
+                    $f(index);
+                }
+                if let Some(ref $($mut_)* index) = primitive.indices {
+                    $f(index);
+                }
+                if let Some(ref $($mut_)* targets) = primitive.targets {
+                    for target in targets {
+                        if let Some(ref $($mut_)* index) = target.positions {
+                            $f(index);
+                        }
+                        if let Some(ref $($mut_)* index) = target.normals {
+                            $f(index);
+                        }
+                        if let Some(ref $($mut_)* index) = target.tangents {
+                            $f(index);
+                        }
+                    }
+                }
+            }
+        }
+        for skin in & $($mut_)* $root.skins {
+            if let Some(ref $($mut_)* index) = skin.inverse_bind_matrices {
+                $f(index);
+            }
+        }
+        for animation in & $($mut_)* $root.animations {
+            for sampler in & $($mut_)* animation.samplers {
+                $f(& $($mut_)* sampler.input);
+                $f(& $($mut_)* sampler.output);
+            }
+        }
+    };
+}
+
+/// Calls `f` with every reference to an accessor, i.e. the attributes,
+/// indices, and morph targets of mesh primitives, the inverse bind matrices of
+/// skins, and the inputs and outputs of animation samplers.
+pub fn accessors<F>(root: &Root, mut f: F)
+    where F: FnMut(&Index<Accessor>)
+{
+    visit_accessors!(root, f;);
+}
+
+/// Calls `f` with every reference to an accessor, allowing it to be changed.
+///
+/// See `accessors`.
+pub fn accessors_mut<F>(root: &mut Root, mut f: F)
+    where F: FnMut(&mut Index<Accessor>)
+{
+    visit_accessors!(root, f; mut);
+}
+
+macro_rules! visit_buffer_views {
+    ($root:ident, $f:ident; $($mut_:tt)*) => {
+        for accessor in & $($mut_)* $root.accessors {
+            if let Some(ref $($mut_)* index) = accessor.buffer_view {
+                $f(index);
+            }
+            if let Some(ref $($mut_)* sparse) = accessor.sparse {
+                $f(& $($mut_)* sparse.indices.buffer_view);
+                $f(& $($mut_)* sparse.values.buffer_view);
+            }
+        }
+        for image in & $($mut_)* $root.images {
+            if let Some(ref $($mut_)* index) = image.buffer_view {
+                $f(index);
+            }
+        }
+        #[cfg(feature = "audio")]
+        {
+            if let Some(ref $($mut_)* audio) = $root.extensions.audio {
+                for clip in & $($mut_)* audio.clips {
+                    if let Some(ref $($mut_)* index) = clip.buffer_view {
+                        $f(index);
+                    }
+                }
+            }
+        }
+        if let Some(ref $($mut_)* metadata) = $root.extensions.structural_metadata {
+            for table in & $($mut_)* metadata.property_tables {
+                for (_, property) in & $($mut_)* table.properties {
+                    $f(& $($mut_)* property.values);
+                    if let Some(ref $($mut_)* index) = property.array_offsets {
+                        $f(index);
+                    }
+                    if let Some(ref $($mut_)* index) = property.string_offsets {
+                        $f(index);
+                    }
+                }
+            }
+        }
+    };
+}
+
+/// Calls `f` with every reference to a buffer view, i.e. the data and sparse
+/// storage of accessors, images, the clips of `KHR_audio`, and the property
+/// tables of `EXT_structural_metadata`.
+pub fn buffer_views<F>(root: &Root, mut f: F)
+    where F: FnMut(&Index<buffer::View>)
+{
+    visit_buffer_views!(root, f;);
+}
+
+/// Calls `f` with every reference to a buffer view, allowing it to be changed.
+///
+/// See `buffer_views`.
+pub fn buffer_views_mut<F>(root: &mut Root, mut f: F)
+    where F: FnMut(&mut Index<buffer::View>)
+{
+    visit_buffer_views!(root, f; mut);
+}
@@ -0,0 +1,90 @@
+#![cfg(feature = "interactivity")]
+
+extern crate gltf_json;
+extern crate serde_json;
+
+use gltf_json::extensions::interactivity::Interactivity;
+use gltf_json::validation::{Error, Validate};
+use gltf_json::{Path, Root, Value};
+
+const GRAPH: &'static str = r#"{
+    "asset": { "version": "2.0" },
+    "extensionsUsed": ["KHR_interactivity"],
+    "extensions": {
+        "KHR_interactivity": {
+            "graphs": [{
+                "types": [{ "signature": "float" }, { "signature": "custom", "precision": "high" }],
+                "variables": [{ "type": 0, "value": [1.5] }],
+                "declarations": [
+                    { "op": "event/onStart" },
+                    { "op": "math/add", "inputValueSockets": { "a": { "type": 0 } } }
+                ],
+                "nodes": [
+                    { "declaration": 0, "flows": { "out": { "node": 1, "socket": "in" } } },
+                    {
+                        "declaration": 1,
+                        "values": {
+                            "a": { "type": 0, "value": [2.0] },
+                            "b": { "node": 0, "socket": "value" }
+                        },
+                        "configuration": { "stopPropagation": { "value": [true] } },
+                        "editorPosition": [120, 40]
+                    }
+                ]
+            }],
+            "graph": 0,
+            "vendorData": { "version": 3 }
+        }
+    }
+}"#;
+
+fn errors(root: &Root) -> Vec<(Path, Error)> {
+    let mut errs = vec![];
+    root.validate_minimally(root, Path::new, &mut |path, err| errs.push((path(), err)));
+    errs
+}
+
+#[test]
+fn test_interactivity_round_trip() {
+    let mut root: Root = gltf_json::from_str(GRAPH).unwrap();
+    assert!(errors(&root).is_empty());
+    {
+        let graphs = root.extensions.interactivity.as_mut().unwrap().typed_mut().unwrap();
+        assert_eq!("math/add", graphs.graphs[0].declarations[1].op);
+        let a = graphs.graphs[0].nodes[1].values.get_mut("a").unwrap();
+        a.value = Some(Value::from(vec![3.0]));
+    }
+
+    let mut expected: Value = gltf_json::from_str(GRAPH).unwrap();
+    expected["extensions"]["KHR_interactivity"]["graphs"][0]["nodes"][1]["values"]["a"]["value"] =
+        Value::from(vec![3.0]);
+    let written: Value = gltf_json::from_str(&serde_json::to_string(&root).unwrap()).unwrap();
+    assert_eq!(expected["extensions"], written["extensions"]);
+}
+
+#[test]
+fn test_interactivity_raw() {
+    let json = GRAPH.replace(r#""declaration": 0,"#, r#""declaration": "start","#);
+    let root: Root = gltf_json::from_str(&json).unwrap();
+    match *root.extensions.interactivity.as_ref().unwrap() {
+        Interactivity::Raw(ref value) => assert_eq!(3, value["vendorData"]["version"]),
+        Interactivity::Typed(_) => panic!("expected raw JSON"),
+    }
+
+    let expected: Value = gltf_json::from_str(&json).unwrap();
+    let written: Value = gltf_json::from_str(&serde_json::to_string(&root).unwrap()).unwrap();
+    assert_eq!(expected["extensions"], written["extensions"]);
+}
+
+#[test]
+fn test_interactivity_validate() {
+    let json = GRAPH.replace(r#""node": 1, "socket": "in""#, r#""node": 2"#);
+    let root: Root = gltf_json::from_str(&json).unwrap();
+    assert_eq!(
+        errors(&root),
+        [(
+            Path("extensions.interactivity.graphs[0].nodes[0].flows[\"out\"].node".into()),
+            Error::IndexOutOfBounds,
+        )],
+    );
+}
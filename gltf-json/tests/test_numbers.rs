@@ -0,0 +1,90 @@
+extern crate gltf_json;
+extern crate serde_json;
+
+use gltf_json::{Root, Value};
+
+// Subnormals, signed zero, extremes, and decimals that are not exactly
+// representable. Parsing and formatting never consult the locale, so these
+// must read back identically on every system.
+const NUMBERS: &'static [&'static str] = &[
+    "-0.0",
+    "1e-45",
+    "1.401298464324817e-45",
+    "1.1754942e-38",
+    "1.17549435e-38",
+    "3.4028235e38",
+    "-3.4028235e38",
+    "5e-324",
+    "2.2250738585072014e-308",
+    "0.30000000000000004",
+    "0.1",
+];
+
+fn accessor(values: &str) -> String {
+    format!(r#"{{
+        "asset": {{ "version": "2.0" }},
+        "buffers": [{{ "byteLength": 44 }}],
+        "bufferViews": [{{ "buffer": 0, "byteLength": 44 }}],
+        "accessors": [{{
+            "bufferView": 0,
+            "componentType": 5126,
+            "count": 11,
+            "type": "SCALAR",
+            "min": [{0}],
+            "max": [{0}]
+        }}]
+    }}"#, values)
+}
+
+fn bits(value: &Option<Value>) -> Vec<u64> {
+    value.as_ref()
+        .and_then(Value::as_array)
+        .unwrap()
+        .iter()
+        .map(|x| x.as_f64().unwrap().to_bits())
+        .collect()
+}
+
+#[test]
+fn test_min_max_parse_exactly() {
+    let root: Root = gltf_json::from_str(&accessor(&NUMBERS.join(", "))).unwrap();
+    let expected: Vec<u64> = NUMBERS.iter()
+        .map(|x| x.parse::<f64>().unwrap().to_bits())
+        .collect();
+    assert_eq!(expected, bits(&root.accessors[0].min));
+    assert_eq!(expected, bits(&root.accessors[0].max));
+}
+
+#[test]
+fn test_min_max_round_trip() {
+    let root: Root = gltf_json::from_str(&accessor(&NUMBERS.join(", "))).unwrap();
+    let written = serde_json::to_string(&root).unwrap();
+    let reread: Root = gltf_json::from_str(&written).unwrap();
+    assert_eq!(bits(&root.accessors[0].min), bits(&reread.accessors[0].min));
+    assert_eq!(bits(&root.accessors[0].max), bits(&reread.accessors[0].max));
+    assert!(written.contains("[-0.0,"));
+    assert!(written.contains(",0.1]"));
+}
+
+#[test]
+fn test_f32_round_trip() {
+    let json = r#"{
+        "asset": { "version": "2.0" },
+        "nodes": [{
+            "translation": [-0.0, 1e-45, 3.4028235e38],
+            "scale": [1.1754942e-38, 0.1, -1e-45]
+        }]
+    }"#;
+    let root: Root = gltf_json::from_str(json).unwrap();
+    let node = &root.nodes[0];
+    let translation = node.translation;
+    assert!(translation[0] == 0.0 && translation[0].is_sign_negative());
+    assert_eq!(1, translation[1].to_bits());
+    assert_eq!(::std::f32::MAX, translation[2]);
+
+    let reread: Root = gltf_json::from_str(&serde_json::to_string(&root).unwrap()).unwrap();
+    let node2 = &reread.nodes[0];
+    let to_bits = |xs: [f32; 3]| [xs[0].to_bits(), xs[1].to_bits(), xs[2].to_bits()];
+    assert_eq!(to_bits(translation), to_bits(node2.translation));
+    assert_eq!(to_bits(node.scale), to_bits(node2.scale));
+}
@@ -24,3 +24,69 @@ fn test_accessor_bounds_validate_minimally() {
         [(Path("meshes[0].primitives[0].attributes[\"POSITION\"].min".into()), Error::Missing),
          (Path("meshes[0].primitives[0].attributes[\"POSITION\"].max".into()), Error::Invalid)]);
 }
+
+#[test]
+fn test_constructed_root_validate_completely() {
+    use gltf_json::accessor::{ComponentType, Type};
+    use gltf_json::{buffer, mesh, scene, validation, Accessor, Buffer, Index, Root};
+
+    let mut accessor = Accessor::new(Index::new(0), ComponentType::F32, Type::Vec3, 3);
+    accessor.min = Some(gltf_json::from_str("[0.0, 0.0, 0.0]").unwrap());
+    accessor.max = Some(gltf_json::from_str("[1.0, 1.0, 0.0]").unwrap());
+    let mut primitive = mesh::Primitive::default();
+    primitive.attributes.insert(validation::Checked::Valid(mesh::Semantic::Positions), Index::new(0));
+    let mut mesh = mesh::Mesh::default();
+    mesh.primitives.push(primitive);
+    let mut node = scene::Node::default();
+    node.mesh = Some(Index::new(0));
+    let mut scene = scene::Scene::default();
+    scene.nodes.push(Index::new(0));
+
+    let json = Root {
+        accessors: vec![accessor],
+        buffers: vec![Buffer::new(36)],
+        buffer_views: vec![buffer::View::new(Index::new(0), 0, 36)],
+        meshes: vec![mesh],
+        nodes: vec![node],
+        scenes: vec![scene],
+        ..Default::default()
+    };
+    let mut errs = vec![];
+    json.validate_minimally(&json, Path::new, &mut |path, err| errs.push((path(), err)));
+    json.validate_completely(&json, Path::new, &mut |path, err| errs.push((path(), err)));
+    assert_eq!(errs, []);
+}
+
+#[test]
+fn test_attribute_types_validate_completely() {
+    let json: gltf_json::Root = gltf_json::from_str(r#"{
+        "asset": { "version": "2.0" },
+        "buffers": [{ "byteLength": 36 }],
+        "bufferViews": [{ "buffer": 0, "byteLength": 36 }],
+        "accessors": [
+            {
+                "bufferView": 0, "componentType": 5126, "count": 3, "type": "VEC3",
+                "min": [0, 0, 0], "max": [1, 1, 1]
+            },
+            { "bufferView": 0, "componentType": 5121, "count": 3, "type": "SCALAR" },
+            {
+                "bufferView": 0, "componentType": 5121, "count": 2, "type": "VEC2",
+                "normalized": true
+            }
+        ],
+        "meshes": [{
+            "primitives": [{
+                "attributes": { "POSITION": 0, "NORMAL": 1, "TEXCOORD_0": 2 },
+                "indices": 0
+            }]
+        }]
+    }"#).unwrap();
+    let mut errs = vec![];
+    json.validate_minimally(&json, Path::new, &mut |path, err| errs.push((path(), err)));
+    json.validate_completely(&json, Path::new, &mut |path, err| errs.push((path(), err)));
+    errs.sort_by(|a, b| a.0.as_str().cmp(b.0.as_str()));
+    assert_eq!(errs,
+        [(Path("meshes[0].primitives[0].attributes".into()), Error::Invalid),
+         (Path("meshes[0].primitives[0].attributes[\"NORMAL\"]".into()), Error::Invalid),
+         (Path("meshes[0].primitives[0].indices".into()), Error::Invalid)]);
+}
@@ -0,0 +1,72 @@
+#[macro_use]
+extern crate criterion;
+extern crate gltf;
+extern crate gltf_utils;
+
+use criterion::{black_box, Criterion, Throughput};
+use gltf::{Glb, Gltf};
+use gltf_utils::fixtures::{self, Fixture};
+use gltf_utils::AccessorIter;
+
+/// Cells along each side of the benchmark grid, about 66,000 vertices.
+const SIZE: usize = 256;
+
+fn parse(c: &mut Criterion) {
+    let fixture = Fixture::grid(SIZE);
+    let json = fixture.to_json();
+    let glb = fixture.to_glb();
+    let mut group = c.benchmark_group("parse");
+    group.throughput(Throughput::Bytes(json.len() as u64));
+    group.bench_function("gltf", |b| b.iter(|| {
+        Gltf::from_slice(black_box(&json)).unwrap().validate_completely().unwrap()
+    }));
+    let lantern = include_bytes!("../../examples/Lantern.gltf");
+    group.throughput(Throughput::Bytes(lantern.len() as u64));
+    group.bench_function("lantern", |b| b.iter(|| {
+        Gltf::from_slice(black_box(lantern)).unwrap().validate_completely().unwrap()
+    }));
+    group.throughput(Throughput::Bytes(glb.len() as u64));
+    group.bench_function("glb", |b| b.iter(|| {
+        let glb = Glb::from_slice(black_box(&glb)).unwrap();
+        let gltf = Gltf::from_glb(&glb).unwrap().validate_completely().unwrap();
+        (gltf, glb.bin.map(|bin| bin.into_owned()))
+    }));
+    group.finish();
+}
+
+// The hot loop of most consumers.
+fn accessor(c: &mut Criterion) {
+    let packed = Fixture::grid(SIZE);
+    let interleaved = Fixture::grid(SIZE).interleaved();
+    let mut group = c.benchmark_group("accessor");
+    for &(name, fixture) in &[("packed", &packed), ("interleaved", &interleaved)] {
+        let gltf = fixture.gltf();
+        let buffers = fixture.buffers();
+        let accessor = fixtures::positions(&gltf);
+        group.throughput(Throughput::Elements(accessor.count() as u64));
+        group.bench_function(format!("positions_{}", name), |b| b.iter(|| {
+            let iter = AccessorIter::<[f32; 3]>::new(accessor.clone(), &buffers);
+            iter.fold(0.0, |sum, position| sum + position[1])
+        }));
+        group.bench_function(format!("positions_{}_checked", name), |b| b.iter(|| {
+            let iter = AccessorIter::<[f32; 3]>::checked(accessor.clone(), &buffers).unwrap();
+            iter.fold(0.0, |sum, position| sum + position[1])
+        }));
+    }
+    group.finish();
+}
+
+fn export(c: &mut Criterion) {
+    let fixture = Fixture::grid(SIZE);
+    let gltf = fixture.gltf();
+    let mut group = c.benchmark_group("export");
+    group.bench_function("gltf", |b| b.iter(|| {
+        let mut json = vec![];
+        gltf.to_writer(&mut json).unwrap();
+        json
+    }));
+    group.finish();
+}
+
+criterion_group!(benches, parse, accessor, export);
+criterion_main!(benches);
@@ -0,0 +1,561 @@
+use std::collections::HashSet;
+
+use gltf::{self, json, Gltf};
+use gltf::animation::{InterpolationAlgorithm, TrsProperty};
+
+use interleave::accessor_buffer;
+use {ChannelIterators, Outputs, Source};
+use write::Writer;
+
+/// The keyframes of an animation sampler.
+#[derive(Clone, Debug)]
+struct Keyframes {
+    /// The input times.
+    times: Vec<f32>,
+
+    /// The output values, `width` components per keyframe.
+    values: Vec<f32>,
+
+    /// The number of components of each output value.
+    width: usize,
+}
+
+impl Keyframes {
+    fn read<S: Source>(channel: &gltf::animation::Channel, source: &S) -> Keyframes {
+        let times: Vec<f32> = channel.inputs(source).collect();
+        let values: Vec<f32> = match channel.outputs(source) {
+            Outputs::Translations(iter) | Outputs::Scales(iter) => {
+                iter.flat_map(|v| v.to_vec()).collect()
+            },
+            Outputs::Rotations(iter) => iter.flat_map(|v| v.to_vec()).collect(),
+            Outputs::Weights(iter) => iter.collect(),
+        };
+        let width = if times.is_empty() { 0 } else { values.len() / times.len() };
+        Keyframes { times, values, width }
+    }
+
+    fn value(&self, key: usize) -> &[f32] {
+        &self.values[key * self.width..(key + 1) * self.width]
+    }
+
+    /// Evaluates the keyframes at the given time.
+    ///
+    /// Times outside the keyframe range are clamped. For cubic spline
+    /// interpolation each keyframe holds an in-tangent, a value, and an
+    /// out-tangent.
+    fn sample(&self, interpolation: InterpolationAlgorithm, rotation: bool, time: f32) -> Vec<f32> {
+        let cubic = matches!(interpolation, InterpolationAlgorithm::CubicSpline);
+        let width = if cubic { self.width / 3 } else { self.width };
+        let value = |key: usize| if cubic {
+            &self.value(key)[width..2 * width]
+        } else {
+            self.value(key)
+        };
+        let last = self.times.len() - 1;
+        let next = self.times.iter().position(|&t| t > time).unwrap_or(last + 1);
+        if next == 0 {
+            return value(0).to_vec();
+        } else if next > last {
+            return value(last).to_vec();
+        }
+        let key = next - 1;
+        let dt = self.times[next] - self.times[key];
+        let s = if dt > 0.0 { (time - self.times[key]) / dt } else { 0.0 };
+        let mut result = match interpolation {
+            InterpolationAlgorithm::Step => value(key).to_vec(),
+            InterpolationAlgorithm::CubicSpline => {
+                let out_tangent = &self.value(key)[2 * width..];
+                let in_tangent = &self.value(next)[..width];
+                let (s2, s3) = (s * s, s * s * s);
+                (0..width)
+                    .map(|i| {
+                        (2.0 * s3 - 3.0 * s2 + 1.0) * value(key)[i]
+                            + (s3 - 2.0 * s2 + s) * dt * out_tangent[i]
+                            + (-2.0 * s3 + 3.0 * s2) * value(next)[i]
+                            + (s3 - s2) * dt * in_tangent[i]
+                    })
+                    .collect()
+            },
+            _ if rotation => slerp(value(key), value(next), s),
+            _ => lerp(value(key), value(next), s),
+        };
+        if rotation {
+            let length = dot(&result, &result).sqrt();
+            if length > 0.0 {
+                for x in &mut result {
+                    *x /= length;
+                }
+            }
+        }
+        result
+    }
+}
+
+/// The animated properties of a node sampled at a fixed frame rate.
+///
+/// Each property is `None` if no channel of the animation targets it.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct BakedNode {
+    /// The index of the node.
+    pub node: usize,
+
+    /// The translation of every frame.
+    pub translations: Option<Vec<[f32; 3]>>,
+
+    /// The rotation quaternion of every frame.
+    pub rotations: Option<Vec<[f32; 4]>>,
+
+    /// The scale of every frame.
+    pub scales: Option<Vec<[f32; 3]>>,
+
+    /// The morph target weights of every frame.
+    pub weights: Option<Vec<Vec<f32>>>,
+}
+
+/// An animation sampled at a fixed frame rate.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Baked {
+    /// The number of frames per second.
+    pub fps: f32,
+
+    /// The number of frames, including the first and the last.
+    pub frame_count: usize,
+
+    /// The animated nodes, in order of their first channel.
+    pub nodes: Vec<BakedNode>,
+}
+
+/// Extra methods for working with `gltf::Animation`.
+pub trait Bake {
+    /// Samples every channel of the animation onto a uniform timeline.
+    ///
+    /// Frames are spaced `1 / fps` seconds apart from time zero, with the last
+    /// frame at the end of the longest channel. Linear rotations use
+    /// spherical linear interpolation. Channels using the Catmull-Rom spline
+    /// interpolation algorithm are skipped.
+    fn bake<S: Source>(&self, fps: f32, source: &S) -> Baked;
+}
+
+impl<'a> Bake for gltf::Animation<'a> {
+    fn bake<S: Source>(&self, fps: f32, source: &S) -> Baked {
+        let mut channels = vec![];
+        let mut duration = 0.0f32;
+        for channel in self.channels() {
+            let interpolation = channel.sampler().interpolation();
+            if let InterpolationAlgorithm::CatmullRomSpline = interpolation {
+                continue;
+            }
+            let keyframes = Keyframes::read(&channel, source);
+            if keyframes.times.is_empty() {
+                continue;
+            }
+            duration = duration.max(keyframes.times[keyframes.times.len() - 1]);
+            channels.push((channel, interpolation, keyframes));
+        }
+
+        let frame_count = (duration * fps).ceil() as usize + 1;
+        let times: Vec<f32> = (0..frame_count)
+            .map(|frame| (frame as f32 / fps).min(duration))
+            .collect();
+        let mut nodes: Vec<BakedNode> = vec![];
+        for (channel, interpolation, keyframes) in channels {
+            let target = channel.target();
+            let node = target.node().index();
+            let position = match nodes.iter().position(|baked| baked.node == node) {
+                Some(position) => position,
+                None => {
+                    nodes.push(BakedNode { node, ..Default::default() });
+                    nodes.len() - 1
+                },
+            };
+            let baked = &mut nodes[position];
+            let path = target.path();
+            let rotation = matches!(path, TrsProperty::Rotation);
+            let frames = times
+                .iter()
+                .map(|&time| keyframes.sample(interpolation, rotation, time));
+            match path {
+                TrsProperty::Translation => {
+                    baked.translations = Some(frames.map(|v| [v[0], v[1], v[2]]).collect());
+                },
+                TrsProperty::Rotation => {
+                    baked.rotations = Some(frames.map(|v| [v[0], v[1], v[2], v[3]]).collect());
+                },
+                TrsProperty::Scale => {
+                    baked.scales = Some(frames.map(|v| [v[0], v[1], v[2]]).collect());
+                },
+                TrsProperty::Weights => baked.weights = Some(frames.collect()),
+            }
+        }
+        Baked { fps, frame_count, nodes }
+    }
+}
+
+fn dot(a: &[f32], b: &[f32]) -> f32 {
+    a.iter().zip(b).map(|(a, b)| a * b).sum()
+}
+
+fn lerp(a: &[f32], b: &[f32], t: f32) -> Vec<f32> {
+    a.iter().zip(b).map(|(a, b)| a + (b - a) * t).collect()
+}
+
+/// Spherical linear interpolation along the shortest path between two unit
+/// quaternions.
+fn slerp(a: &[f32], b: &[f32], t: f32) -> Vec<f32> {
+    let mut cos = dot(a, b);
+    let b: Vec<f32> = if cos < 0.0 {
+        cos = -cos;
+        b.iter().map(|x| -x).collect()
+    } else {
+        b.to_vec()
+    };
+    if cos > 0.9995 {
+        return lerp(a, &b, t);
+    }
+    let angle = cos.min(1.0).acos();
+    let sin = angle.sin();
+    let wa = ((1.0 - t) * angle).sin() / sin;
+    let wb = (t * angle).sin() / sin;
+    a.iter().zip(&b).map(|(a, b)| a * wa + b * wb).collect()
+}
+
+/// The angle in radians between the rotations of two unit quaternions.
+fn angle_between(a: &[f32], b: &[f32]) -> f32 {
+    2.0 * dot(a, b).abs().min(1.0).acos()
+}
+
+fn max_difference(a: &[f32], b: &[f32]) -> f32 {
+    a.iter().zip(b).map(|(a, b)| (a - b).abs()).fold(0.0, f32::max)
+}
+
+/// Returns the indices of the keyframes to keep.
+///
+/// The first and last keyframes are always kept. An interior keyframe is
+/// dropped if interpolating between the surrounding kept keyframes reproduces
+/// it, and every other dropped keyframe in between, within the tolerance.
+fn keep(
+    keyframes: &Keyframes,
+    interpolation: InterpolationAlgorithm,
+    rotation: bool,
+    tolerance: f32,
+) -> Vec<usize> {
+    let count = keyframes.times.len();
+    if count <= 2 {
+        return (0..count).collect();
+    }
+    let error = |a: &[f32], b: &[f32]| if rotation {
+        angle_between(a, b)
+    } else {
+        max_difference(a, b)
+    };
+    let mut kept = vec![0];
+    match interpolation {
+        InterpolationAlgorithm::Step => {
+            for key in 1..count - 1 {
+                let previous = keyframes.value(*kept.last().unwrap());
+                if error(previous, keyframes.value(key)) > tolerance {
+                    kept.push(key);
+                }
+            }
+        },
+        _ => {
+            let mut start = 0;
+            let mut end = 2;
+            while end < count {
+                let (t0, t1) = (keyframes.times[start], keyframes.times[end]);
+                let (v0, v1) = (keyframes.value(start), keyframes.value(end));
+                let fits = (start + 1..end).all(|key| {
+                    let t = if t1 > t0 { (keyframes.times[key] - t0) / (t1 - t0) } else { 0.0 };
+                    let approximation = if rotation { slerp(v0, v1, t) } else { lerp(v0, v1, t) };
+                    error(&approximation, keyframes.value(key)) <= tolerance
+                });
+                if !fits {
+                    start = end - 1;
+                    kept.push(start);
+                }
+                end += 1;
+            }
+        },
+    }
+    kept.push(count - 1);
+    kept
+}
+
+/// Removes redundant keyframes from every linear and step animation sampler.
+///
+/// For rotations the tolerance is the angle in radians between the original
+/// and the interpolated rotation, with spherical linear interpolation. For
+/// other properties it is the largest absolute difference of any component.
+/// Constant samplers are reduced to their first and last keyframes. Cubic
+/// spline samplers are left unchanged.
+///
+/// The reduced keyframes of each sampler are appended as new `f32` accessors
+/// to the buffer containing its input data. Samplers without redundant
+/// keyframes are left unchanged, and the original accessors are left in place.
+pub fn reduce(gltf: Gltf, buffers: &mut Vec<Vec<u8>>, tolerance: f32) -> Gltf {
+    let mut reduced = vec![];
+    let mut seen = HashSet::new();
+    for animation in gltf.animations() {
+        for channel in animation.channels() {
+            let sampler_index = channel.as_json().sampler.value();
+            if !seen.insert((animation.index(), sampler_index)) {
+                continue;
+            }
+            let sampler = channel.sampler();
+            let interpolation = sampler.interpolation();
+            match interpolation {
+                InterpolationAlgorithm::Linear | InterpolationAlgorithm::Step => {},
+                _ => continue,
+            }
+            let rotation = matches!(channel.target().path(), TrsProperty::Rotation);
+            let keyframes = Keyframes::read(&channel, buffers);
+            let kept = keep(&keyframes, interpolation, rotation, tolerance);
+            if kept.len() == keyframes.times.len() {
+                continue;
+            }
+            let times: Vec<f32> = kept.iter().map(|&key| keyframes.times[key]).collect();
+            let values: Vec<f32> = kept
+                .iter()
+                .flat_map(|&key| keyframes.value(key).to_vec())
+                .collect();
+            let output = sampler.output();
+            let buffer = accessor_buffer(&sampler.input());
+            let dimensions = output.dimensions();
+            reduced.push((animation.index(), sampler_index, buffer, times, values, dimensions));
+        }
+    }
+
+    let mut root = gltf.into_json();
+    for (animation, sampler, buffer, times, values, dimensions) in reduced {
+        let scalar = json::accessor::Type::Scalar;
+        let input = Writer::new(buffer).bounds().push_f32(&mut root, buffers, &times, scalar);
+        let output = Writer::new(buffer).push_f32(&mut root, buffers, &values, dimensions);
+        let sampler = &mut root.animations[animation].samplers[sampler];
+        sampler.input = input;
+        sampler.output = output;
+    }
+    Gltf::from_json(root).skip_validation()
+}
+
+/// Normalizes the output quaternions of every linear and step rotation sampler
+/// and negates quaternions as needed so that each keyframe lies on the shortest
+/// path from the previous one.
+///
+/// Exporters that do not ensure continuity produce sign flips between
+/// keyframes, which make the interpolated rotation spin the long way around.
+/// Cubic spline samplers are left unchanged.
+///
+/// The fixed outputs of each sampler are appended as a new `f32` accessor to
+/// the buffer containing its output data. Samplers that need no fixing are
+/// left unchanged, and the original accessors are left in place.
+pub fn fix_rotations(gltf: Gltf, buffers: &mut Vec<Vec<u8>>) -> Gltf {
+    let mut fixed = vec![];
+    let mut seen = HashSet::new();
+    for animation in gltf.animations() {
+        for channel in animation.channels() {
+            match channel.target().path() {
+                TrsProperty::Rotation => {},
+                _ => continue,
+            }
+            let sampler_index = channel.as_json().sampler.value();
+            if !seen.insert((animation.index(), sampler_index)) {
+                continue;
+            }
+            let sampler = channel.sampler();
+            match sampler.interpolation() {
+                InterpolationAlgorithm::Linear | InterpolationAlgorithm::Step => {},
+                _ => continue,
+            }
+            let keyframes = Keyframes::read(&channel, buffers);
+            let mut values = keyframes.values.clone();
+            let mut changed = false;
+            for key in 0..keyframes.times.len() {
+                let (previous, current) = values.split_at_mut(key * 4);
+                let q = &mut current[..4];
+                let length = dot(q, q).sqrt();
+                if length > 0.0 {
+                    for x in q.iter_mut() {
+                        *x /= length;
+                    }
+                }
+                if key > 0 && dot(&previous[previous.len() - 4..], q) < 0.0 {
+                    for x in q.iter_mut() {
+                        *x = -*x;
+                    }
+                }
+                changed |= max_difference(q, keyframes.value(key)) > 1.0e-6;
+            }
+            if changed {
+                let buffer = accessor_buffer(&sampler.output());
+                fixed.push((animation.index(), sampler_index, buffer, values));
+            }
+        }
+    }
+
+    let mut root = gltf.into_json();
+    for (animation, sampler, buffer, values) in fixed {
+        let vec4 = json::accessor::Type::Vec4;
+        let output = Writer::new(buffer).push_f32(&mut root, buffers, &values, vec4);
+        root.animations[animation].samplers[sampler].output = output;
+    }
+    Gltf::from_json(root).skip_validation()
+}
+
+#[cfg(test)]
+mod tests {
+    use byteorder::{LE, ByteOrder};
+    use gltf::Gltf;
+
+    use super::*;
+
+    const JSON: &'static str = r#"{
+        "asset": { "version": "2.0" },
+        "buffers": [{ "byteLength": 100 }],
+        "bufferViews": [
+            { "buffer": 0, "byteOffset": 0, "byteLength": 20 },
+            { "buffer": 0, "byteOffset": 20, "byteLength": 60 },
+            { "buffer": 0, "byteOffset": 80, "byteLength": 20 }
+        ],
+        "accessors": [
+            { "bufferView": 0, "componentType": 5126, "count": 5, "type": "SCALAR" },
+            { "bufferView": 1, "componentType": 5126, "count": 5, "type": "VEC3" },
+            { "bufferView": 2, "componentType": 5126, "count": 5, "type": "SCALAR" }
+        ],
+        "nodes": [{ "mesh": 0 }],
+        "meshes": [{ "primitives": [{ "attributes": {} }], "weights": [0] }],
+        "animations": [{
+            "channels": [
+                { "sampler": 0, "target": { "node": 0, "path": "translation" } },
+                { "sampler": 1, "target": { "node": 0, "path": "weights" } }
+            ],
+            "samplers": [{ "input": 0, "output": 1 }, { "input": 0, "output": 2 }]
+        }]
+    }"#;
+
+    #[test]
+    fn reduce_keyframes() {
+        let mut data = vec![0; 100];
+        LE::write_f32_into(&[0.0, 1.0, 2.0, 3.0, 4.0], &mut data[0..20]);
+        LE::write_f32_into(
+            &[
+                0.0, 0.0, 0.0,
+                1.0, 0.0, 0.0,
+                2.0, 0.0, 0.0,
+                2.0, 1.0, 0.0,
+                2.0, 2.0, 0.0,
+            ],
+            &mut data[20..80],
+        );
+        LE::write_f32_into(&[0.5; 5], &mut data[80..100]);
+        let mut buffers = vec![data];
+
+        let gltf = Gltf::from_str(JSON).unwrap().skip_validation();
+        let gltf = reduce(gltf, &mut buffers, 1.0e-4);
+        let animation = gltf.animations().next().unwrap();
+        let channels: Vec<_> = animation.channels().collect();
+
+        let times: Vec<f32> = channels[0].inputs(&buffers).collect();
+        assert_eq!(vec![0.0, 2.0, 4.0], times);
+        match channels[0].outputs(&buffers) {
+            Outputs::Translations(iter) => {
+                let values: Vec<_> = iter.collect();
+                assert_eq!(vec![[0.0, 0.0, 0.0], [2.0, 0.0, 0.0], [2.0, 2.0, 0.0]], values);
+            },
+            _ => panic!(),
+        }
+
+        let times: Vec<f32> = channels[1].inputs(&buffers).collect();
+        assert_eq!(vec![0.0, 4.0], times);
+    }
+
+    #[test]
+    fn slerp_error() {
+        let a = [0.0, 0.0, 0.0, 1.0];
+        let half = (0.5f32).sqrt();
+        let b = [0.0, 0.0, half, half];
+        let mid = slerp(&a, &b, 0.5);
+        let angle = ::std::f32::consts::PI / 8.0;
+        assert!(angle_between(&mid, &[0.0, 0.0, angle.sin(), angle.cos()]) < 1.0e-3);
+    }
+
+    #[test]
+    fn fix_rotation_continuity() {
+        let json = r#"{
+            "asset": { "version": "2.0" },
+            "buffers": [{ "byteLength": 60 }],
+            "bufferViews": [
+                { "buffer": 0, "byteOffset": 0, "byteLength": 12 },
+                { "buffer": 0, "byteOffset": 12, "byteLength": 48 }
+            ],
+            "accessors": [
+                { "bufferView": 0, "componentType": 5126, "count": 3, "type": "SCALAR" },
+                { "bufferView": 1, "componentType": 5126, "count": 3, "type": "VEC4" }
+            ],
+            "nodes": [{}],
+            "animations": [{
+                "channels": [{ "sampler": 0, "target": { "node": 0, "path": "rotation" } }],
+                "samplers": [{ "input": 0, "output": 1 }]
+            }]
+        }"#;
+        let mut data = vec![0; 60];
+        LE::write_f32_into(&[0.0, 1.0, 2.0], &mut data[0..12]);
+        LE::write_f32_into(
+            &[
+                0.0, 0.0, 0.0, 2.0,
+                0.0, 0.0, 0.0, -1.0,
+                0.0, 1.0, 0.0, 0.0,
+            ],
+            &mut data[12..60],
+        );
+        let mut buffers = vec![data];
+
+        let gltf = Gltf::from_str(json).unwrap().skip_validation();
+        let gltf = fix_rotations(gltf, &mut buffers);
+        let animation = gltf.animations().next().unwrap();
+        let channel = animation.channels().next().unwrap();
+        match channel.outputs(&buffers) {
+            Outputs::Rotations(iter) => {
+                let values: Vec<_> = iter.collect();
+                assert_eq!(
+                    vec![[0.0, 0.0, 0.0, 1.0], [0.0, 0.0, 0.0, 1.0], [0.0, 1.0, 0.0, 0.0]],
+                    values,
+                );
+            },
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn bake_frames() {
+        let mut data = vec![0; 100];
+        LE::write_f32_into(&[0.0, 0.25, 0.5, 0.75, 1.0], &mut data[0..20]);
+        LE::write_f32_into(
+            &[
+                0.0, 0.0, 0.0,
+                1.0, 0.0, 0.0,
+                2.0, 0.0, 0.0,
+                3.0, 0.0, 0.0,
+                4.0, 0.0, 0.0,
+            ],
+            &mut data[20..80],
+        );
+        LE::write_f32_into(&[0.0, 1.0, 0.0, 1.0, 0.0], &mut data[80..100]);
+        let buffers = vec![data];
+
+        let json = JSON.replace(
+            r#""input": 0, "output": 2"#,
+            r#""input": 0, "output": 2, "interpolation": "STEP""#,
+        );
+        let gltf = Gltf::from_str(&json).unwrap().skip_validation();
+        let baked = gltf.animations().next().unwrap().bake(2.0, &buffers);
+        assert_eq!(3, baked.frame_count);
+        assert_eq!(1, baked.nodes.len());
+        let node = &baked.nodes[0];
+        assert_eq!(
+            Some(vec![[0.0, 0.0, 0.0], [2.0, 0.0, 0.0], [4.0, 0.0, 0.0]]),
+            node.translations,
+        );
+        assert_eq!(Some(vec![vec![0.0], vec![0.0], vec![0.0]]), node.weights);
+        assert_eq!(None, node.rotations);
+    }
+}
@@ -0,0 +1,747 @@
+use std::collections::HashMap;
+
+use byteorder::{LE, ByteOrder};
+use gltf::{self, json, Gltf, Semantic};
+use gltf::json::validation::Checked;
+use gltf::accessor::DataType;
+use gltf::mesh::Mode;
+
+use interleave::{accessor_buffer, append, packed};
+use PrimitiveIterators;
+
+/// Splits every mesh whose primitives use more than one material into one mesh
+/// per material.
+///
+/// The primitives using the material of the first primitive stay in the
+/// original mesh and the primitives of every other material move to a new mesh
+/// with the same morph target weights. Every node referencing a split mesh gets
+/// a new child node for each new mesh, with an identity transform and the skin
+/// and morph target weights of its parent.
+///
+/// Returns the transformed asset and the number of meshes created.
+pub fn split_by_material(gltf: Gltf) -> (Gltf, usize) {
+    let mut splits = vec![];
+    for mesh in gltf.meshes() {
+        let mut groups: Vec<(Option<usize>, Vec<usize>)> = vec![];
+        for primitive in mesh.primitives() {
+            let material = primitive.material().index();
+            match groups.iter().position(|&(m, _)| m == material) {
+                Some(group) => groups[group].1.push(primitive.index()),
+                None => groups.push((material, vec![primitive.index()])),
+            }
+        }
+        if groups.len() > 1 {
+            let groups: Vec<_> = groups.into_iter().map(|(_, group)| group).collect();
+            let nodes: Vec<_> = gltf
+                .nodes()
+                .filter(|node| node.mesh().map(|m| m.index()) == Some(mesh.index()))
+                .map(|node| node.index())
+                .collect();
+            splits.push((mesh.index(), groups, nodes));
+        }
+    }
+
+    let mut root = gltf.into_json();
+    let mut created = 0;
+    for (mesh, groups, nodes) in splits {
+        let primitives = root.meshes[mesh].primitives.clone();
+        let take = |group: &[usize]| group.iter().map(|&i| primitives[i].clone()).collect();
+        root.meshes[mesh].primitives = take(&groups[0]);
+        for group in &groups[1..] {
+            let mut split = root.meshes[mesh].clone();
+            split.primitives = take(group);
+            root.meshes.push(split);
+            let split = json::Index::new(root.meshes.len() as u32 - 1);
+            created += 1;
+
+            for &node in &nodes {
+                let child = json::Node {
+                    mesh: Some(split.clone()),
+                    skin: root.nodes[node].skin.clone(),
+                    weights: root.nodes[node].weights.clone(),
+                    ..Default::default()
+                };
+                root.nodes.push(child);
+                let child = json::Index::new(root.nodes.len() as u32 - 1);
+                root.nodes[node].children.get_or_insert_with(Vec::new).push(child);
+            }
+        }
+    }
+    (Gltf::from_json(root).skip_validation(), created)
+}
+
+/// The vertex data of an attribute of a new primitive.
+struct Attribute {
+    /// The accessor to copy the parameters of.
+    accessor: usize,
+
+    /// The elements, each padded to a multiple of four bytes.
+    data: Vec<u8>,
+
+    /// The size of an element without padding.
+    size: usize,
+
+    /// The bounds of the elements, if known.
+    bounds: Option<(Vec<f64>, Vec<f64>)>,
+}
+
+impl Attribute {
+    /// Returns the distance in bytes between the start of each element.
+    fn stride(&self) -> usize {
+        // Vertex attribute elements must start at multiples of four bytes.
+        self.size.div_ceil(4) * 4
+    }
+
+    /// Appends the padded elements of `vertices` to `data`.
+    fn extend(&mut self, elements: &[u8], vertices: &[u32]) {
+        let stride = self.stride();
+        for &vertex in vertices {
+            let start = vertex as usize * self.size;
+            self.data.extend_from_slice(&elements[start..start + self.size]);
+            self.data.resize(self.data.len() + stride - self.size, 0);
+        }
+    }
+
+    /// Appends the attribute as a new accessor, returning its index.
+    fn push(
+        self,
+        root: &mut json::Root,
+        buffers: &mut [Vec<u8>],
+        buffer: usize,
+    ) -> json::Index<json::Accessor> {
+        let stride = self.stride();
+        let offset = append(root, buffers, buffer, &self.data);
+        let mut view = json::buffer::View::new(
+            json::Index::new(buffer as u32),
+            offset as u32,
+            self.data.len() as u32,
+        );
+        view.target = Some(Checked::Valid(json::buffer::Target::ArrayBuffer));
+        if stride != self.size {
+            view.byte_stride = Some(json::buffer::ByteStride(stride as u32));
+        }
+        root.buffer_views.push(view);
+
+        let mut copy = root.accessors[self.accessor].clone();
+        copy.buffer_view = Some(json::Index::new(root.buffer_views.len() as u32 - 1));
+        copy.byte_offset = 0;
+        copy.count = (self.data.len() / stride) as u32;
+        copy.sparse = None;
+        copy.min = self.bounds.as_ref().map(|(min, _)| json::Value::from(min.clone()));
+        copy.max = self.bounds.map(|(_, max)| json::Value::from(max));
+        root.accessors.push(copy);
+        json::Index::new(root.accessors.len() as u32 - 1)
+    }
+}
+
+/// Appends indices as a new `u16` accessor, or `u32` if any index does not fit,
+/// returning its index.
+pub(crate) fn push_indices(
+    root: &mut json::Root,
+    buffers: &mut [Vec<u8>],
+    buffer: usize,
+    indices: &[u32],
+) -> json::Index<json::Accessor> {
+    // The largest value of each index type is reserved for primitive restart.
+    let (component_type, data) = if indices.iter().all(|&index| index < 65535) {
+        let indices: Vec<u16> = indices.iter().map(|&index| index as u16).collect();
+        let mut data = vec![0; indices.len() * 2];
+        LE::write_u16_into(&indices, &mut data);
+        (json::accessor::ComponentType::U16, data)
+    } else {
+        let mut data = vec![0; indices.len() * 4];
+        LE::write_u32_into(indices, &mut data);
+        (json::accessor::ComponentType::U32, data)
+    };
+    let offset = append(root, buffers, buffer, &data);
+    let mut view = json::buffer::View::new(
+        json::Index::new(buffer as u32),
+        offset as u32,
+        data.len() as u32,
+    );
+    view.target = Some(Checked::Valid(json::buffer::Target::ElementArrayBuffer));
+    root.buffer_views.push(view);
+    root.accessors.push(json::Accessor::new(
+        json::Index::new(root.buffer_views.len() as u32 - 1),
+        component_type,
+        json::accessor::Type::Scalar,
+        indices.len() as u32,
+    ));
+    json::Index::new(root.accessors.len() as u32 - 1)
+}
+
+/// Returns the buffer new data of a primitive is appended to.
+fn target_buffer(primitive: &gltf::Primitive) -> usize {
+    let accessor = primitive
+        .get(&Semantic::Positions)
+        .unwrap_or_else(|| primitive.attributes().next().unwrap().1);
+    accessor_buffer(&accessor)
+}
+
+/// Returns the vertex indices of a primitive, generating them for primitives
+/// without indices.
+fn vertex_indices(primitive: &gltf::Primitive, buffers: &Vec<Vec<u8>>) -> Vec<u32> {
+    match primitive.indices_u32(buffers) {
+        Some(iter) => iter.collect(),
+        None => (0..primitive.attributes().next().unwrap().1.count() as u32).collect(),
+    }
+}
+
+/// The vertex data of a merged primitive.
+struct Merged {
+    /// The mesh containing the merged primitives.
+    mesh: usize,
+
+    /// The merged primitives, the first of which is replaced by the result.
+    primitives: Vec<usize>,
+
+    /// The merged data of each attribute.
+    attributes: Vec<(Semantic, Attribute)>,
+
+    /// The concatenated indices, offset to the merged vertices.
+    indices: Vec<u32>,
+
+    /// The buffer to append the merged data to.
+    buffer: usize,
+}
+
+/// Returns whether primitives of a mode can be concatenated.
+fn is_list(mode: Mode) -> bool {
+    matches!(mode, Mode::Points | Mode::Lines | Mode::Triangles)
+}
+
+/// Returns the key under which primitives can be merged, or `None` if the
+/// primitive cannot be merged.
+fn merge_key(primitive: &gltf::Primitive) -> Option<(Option<usize>, u32, Vec<String>)> {
+    let targets = primitive.as_json().targets.as_ref().map_or(0, |targets| targets.len());
+    if !is_list(primitive.mode()) || targets > 0 {
+        return None;
+    }
+    if !primitive.vertex_attribute_counts().is_consistent() {
+        return None;
+    }
+    let mut layout = vec![];
+    for (semantic, accessor) in primitive.attributes() {
+        if accessor.sparse().is_some() {
+            return None;
+        }
+        layout.push(format!(
+            "{}:{}:{}:{}",
+            semantic,
+            accessor.data_type() as u32,
+            accessor.dimensions() as u32,
+            accessor.normalized(),
+        ));
+    }
+    if layout.is_empty() || primitive.indices().is_some_and(|a| a.sparse().is_some()) {
+        return None;
+    }
+    layout.sort();
+    Some((primitive.material().index(), primitive.mode() as u32, layout))
+}
+
+/// Reads the `min` or `max` of an accessor.
+fn bound(value: &Option<json::Value>) -> Option<Vec<f64>> {
+    value
+        .as_ref()
+        .and_then(|value| value.as_array())
+        .and_then(|values| values.iter().map(|value| value.as_f64()).collect())
+}
+
+/// Merges the primitives of every mesh that share a material, mode, and vertex
+/// layout into a single primitive.
+///
+/// Primitives are merged if they have the same material, topology, and set of
+/// attributes with the same component types, dimensions, and normalization.
+/// Triangle strips and fans, primitives with morph targets, and primitives
+/// with sparse accessors are left unchanged.
+///
+/// The vertex data of each merged primitive is concatenated into new accessors
+/// with bounds covering every merged accessor, and its indices are combined
+/// into a new `u16` or `u32` index accessor, generated for primitives without
+/// indices. The data is appended to the buffer containing the positions of the
+/// first merged primitive, and the original accessors are left in place.
+///
+/// Returns the transformed asset and the number of primitives removed.
+pub fn merge_by_material(gltf: Gltf, buffers: &mut Vec<Vec<u8>>) -> (Gltf, usize) {
+    let mut merges = vec![];
+    for mesh in gltf.meshes() {
+        let mut groups: Vec<(_, Vec<gltf::Primitive>)> = vec![];
+        for primitive in mesh.primitives() {
+            let key = match merge_key(&primitive) {
+                Some(key) => key,
+                None => continue,
+            };
+            match groups.iter().position(|(k, _)| *k == key) {
+                Some(group) => groups[group].1.push(primitive),
+                None => groups.push((key, vec![primitive])),
+            }
+        }
+        for (_, group) in groups.into_iter().filter(|(_, group)| group.len() > 1) {
+            merges.push(merge(mesh.index(), &group, &*buffers));
+        }
+    }
+
+    let mut root = gltf.into_json();
+    let mut removals: HashMap<usize, Vec<usize>> = HashMap::new();
+    let mut removed = 0;
+    for merged in merges {
+        let mut attributes = HashMap::new();
+        for (semantic, attribute) in merged.attributes {
+            let index = attribute.push(&mut root, buffers, merged.buffer);
+            attributes.insert(Checked::Valid(semantic), index);
+        }
+        let indices = push_indices(&mut root, buffers, merged.buffer, &merged.indices);
+
+        let primitive = &mut root.meshes[merged.mesh].primitives[merged.primitives[0]];
+        primitive.attributes = attributes;
+        primitive.indices = Some(indices);
+        removed += merged.primitives.len() - 1;
+        removals.entry(merged.mesh).or_default().extend_from_slice(&merged.primitives[1..]);
+    }
+    for (mesh, mut primitives) in removals {
+        primitives.sort();
+        for primitive in primitives.into_iter().rev() {
+            root.meshes[mesh].primitives.remove(primitive);
+        }
+    }
+    (Gltf::from_json(root).skip_validation(), removed)
+}
+
+/// Concatenates the vertex data and indices of a group of primitives.
+fn merge(mesh: usize, group: &[gltf::Primitive], buffers: &Vec<Vec<u8>>) -> Merged {
+    let mut semantics: Vec<_> = group[0].attributes().map(|(semantic, _)| semantic).collect();
+    semantics.sort_by_key(|semantic| semantic.to_string());
+
+    let mut attributes = vec![];
+    for semantic in semantics {
+        let accessors: Vec<_> = group.iter().map(|p| p.get(&semantic).unwrap()).collect();
+        let mut min = bound(&accessors[0].as_json().min);
+        let mut max = bound(&accessors[0].as_json().max);
+        for accessor in &accessors[1..] {
+            let json = accessor.as_json();
+            min = min.and_then(|a| bound(&json.min).map(|b| zip_with(&a, &b, f64::min)));
+            max = max.and_then(|a| bound(&json.max).map(|b| zip_with(&a, &b, f64::max)));
+        }
+        let mut attribute = Attribute {
+            accessor: accessors[0].index(),
+            data: vec![],
+            size: accessors[0].size(),
+            bounds: min.and_then(|min| max.map(|max| (min, max))),
+        };
+        for accessor in &accessors {
+            let vertices: Vec<u32> = (0..accessor.count() as u32).collect();
+            attribute.extend(&packed(accessor, buffers), &vertices);
+        }
+        attributes.push((semantic, attribute));
+    }
+
+    let mut indices = vec![];
+    let mut vertex_count = 0;
+    for primitive in group {
+        indices.extend(vertex_indices(primitive, buffers).iter().map(|i| i + vertex_count));
+        vertex_count += primitive.attributes().next().unwrap().1.count() as u32;
+    }
+
+    Merged {
+        mesh,
+        primitives: group.iter().map(|primitive| primitive.index()).collect(),
+        attributes,
+        indices,
+        buffer: target_buffer(&group[0]),
+    }
+}
+
+/// Reads the components of an element of an accessor as `f64`.
+fn components(data_type: DataType, element: &[u8]) -> Vec<f64> {
+    match data_type {
+        DataType::I8 => element.iter().map(|&x| x as i8 as f64).collect(),
+        DataType::U8 => element.iter().map(|&x| x as f64).collect(),
+        DataType::I16 => element.chunks(2).map(|x| LE::read_i16(x) as f64).collect(),
+        DataType::U16 => element.chunks(2).map(|x| LE::read_u16(x) as f64).collect(),
+        DataType::U32 => element.chunks(4).map(|x| LE::read_u32(x) as f64).collect(),
+        DataType::F32 => element.chunks(4).map(|x| LE::read_f32(x) as f64).collect(),
+    }
+}
+
+/// Copies the given vertices of an accessor into a new attribute, with bounds
+/// if the accessor has bounds.
+fn gather(accessor: &gltf::Accessor, elements: &[u8], vertices: &[u32]) -> Attribute {
+    let size = accessor.size();
+    let mut attribute = Attribute { accessor: accessor.index(), data: vec![], size, bounds: None };
+    attribute.extend(elements, vertices);
+    let json = accessor.as_json();
+    if json.min.is_some() && json.max.is_some() && !vertices.is_empty() {
+        let mut min = vec![f64::INFINITY; accessor.dimensions().multiplicity()];
+        let mut max = vec![f64::NEG_INFINITY; min.len()];
+        for &vertex in vertices {
+            let start = vertex as usize * size;
+            let values = components(accessor.data_type(), &elements[start..start + size]);
+            min = zip_with(&min, &values, f64::min);
+            max = zip_with(&max, &values, f64::max);
+        }
+        attribute.bounds = Some((min, max));
+    }
+    attribute
+}
+
+/// A group of whole points, lines, or triangles of a primitive.
+struct Chunk {
+    /// The original vertices used by the chunk, in order of first use.
+    vertices: Vec<u32>,
+
+    /// The indices of the chunk into `vertices`.
+    indices: Vec<u32>,
+}
+
+/// Splits the indices of a primitive into chunks of at most `max_vertices`
+/// unique vertices each.
+fn chunks(indices: &[u32], vertices_per_element: usize, max_vertices: usize) -> Vec<Chunk> {
+    let mut chunks = vec![];
+    let mut chunk = Chunk { vertices: vec![], indices: vec![] };
+    let mut remap = HashMap::new();
+    for element in indices.chunks(vertices_per_element) {
+        let new = element.iter().filter(|index| !remap.contains_key(*index)).count();
+        if chunk.vertices.len() + new > max_vertices {
+            chunks.push(chunk);
+            chunk = Chunk { vertices: vec![], indices: vec![] };
+            remap.clear();
+        }
+        for &index in element {
+            let vertices = &mut chunk.vertices;
+            let remapped = *remap.entry(index).or_insert_with(|| {
+                vertices.push(index);
+                vertices.len() as u32 - 1
+            });
+            chunk.indices.push(remapped);
+        }
+    }
+    chunks.push(chunk);
+    chunks
+}
+
+/// The vertex data of a chunk of a primitive.
+struct ChunkData {
+    /// The vertex attributes.
+    attributes: Vec<(Semantic, Attribute)>,
+
+    /// The position, normal, and tangent displacements of each morph target.
+    targets: Vec<[Option<Attribute>; 3]>,
+
+    /// The indices into the vertex attributes.
+    indices: Vec<u32>,
+}
+
+/// A primitive split into chunks.
+struct Split {
+    /// The mesh containing the primitive.
+    mesh: usize,
+
+    /// The split primitive, which is replaced by the first chunk.
+    primitive: usize,
+
+    /// The vertex data of each chunk.
+    chunks: Vec<ChunkData>,
+
+    /// The buffer to append the chunks to.
+    buffer: usize,
+}
+
+/// Splits every primitive with more than `max_vertices` vertices into several
+/// primitives of at most `max_vertices` vertices each.
+///
+/// Use a limit of `65535` to make every primitive addressable with `u16`
+/// indices, as required by WebGL 1 without extensions. Each chunk contains
+/// whole points, lines, or triangles, with the vertex attributes and morph
+/// targets of the vertices it uses and a new `u16` or `u32` index accessor.
+/// Triangle strips and fans and primitives with sparse accessors are left
+/// unchanged.
+///
+/// The first chunk replaces the original primitive and the other chunks are
+/// inserted right after it, with the same material. The data is appended to the
+/// buffer containing the positions of the primitive, and the original accessors
+/// are left in place.
+///
+/// Returns the transformed asset and the number of primitives created.
+///
+/// # Panics
+///
+/// Panics if `max_vertices` is less than three.
+pub fn split_by_vertex_count(
+    gltf: Gltf,
+    buffers: &mut Vec<Vec<u8>>,
+    max_vertices: usize,
+) -> (Gltf, usize) {
+    assert!(max_vertices >= 3);
+    let mut splits = vec![];
+    for mesh in gltf.meshes() {
+        for primitive in mesh.primitives() {
+            let vertices_per_element = match primitive.mode() {
+                Mode::Points => 1,
+                Mode::Lines => 2,
+                Mode::Triangles => 3,
+                _ => continue,
+            };
+            let count = match primitive.attributes().next() {
+                Some((_, accessor)) => accessor.count(),
+                None => continue,
+            };
+            let json = primitive.as_json();
+            let targets = json.targets.as_ref().map_or(&[][..], |targets| &targets[..]);
+            let accessors: Vec<_> = primitive
+                .attributes()
+                .map(|(_, accessor)| accessor.index())
+                .chain(primitive.indices().map(|accessor| accessor.index()))
+                .chain(targets.iter().flat_map(|target| {
+                    let indices = vec![&target.positions, &target.normals, &target.tangents];
+                    indices.into_iter().filter_map(|index| index.as_ref().map(|i| i.value()))
+                }))
+                .collect();
+            let sparse = accessors
+                .iter()
+                .any(|&index| gltf.accessors().nth(index).unwrap().sparse().is_some());
+            if count <= max_vertices || sparse {
+                continue;
+            }
+
+            let indices = vertex_indices(&primitive, &*buffers);
+            let read = |index: &Option<json::Index<json::Accessor>>| {
+                index.as_ref().map(|index| {
+                    let accessor = gltf.accessors().nth(index.value()).unwrap();
+                    let elements = packed(&accessor, &*buffers);
+                    (accessor, elements)
+                })
+            };
+            let attributes: Vec<_> = primitive
+                .attributes()
+                .map(|(semantic, accessor)| {
+                    let elements = packed(&accessor, &*buffers);
+                    (semantic, accessor, elements)
+                })
+                .collect();
+            let targets: Vec<_> = targets
+                .iter()
+                .map(|t| [read(&t.positions), read(&t.normals), read(&t.tangents)])
+                .collect();
+
+            let chunks = chunks(&indices, vertices_per_element, max_vertices)
+                .into_iter()
+                .map(|chunk| {
+                    let vertices = &chunk.vertices;
+                    let attributes = attributes
+                        .iter()
+                        .map(|(semantic, accessor, elements)| {
+                            (semantic.clone(), gather(accessor, elements, vertices))
+                        })
+                        .collect();
+                    let targets = targets
+                        .iter()
+                        .map(|target| {
+                            let gather = |data: &Option<(gltf::Accessor, Vec<u8>)>| {
+                                data.as_ref().map(|(accessor, elements)| {
+                                    gather(accessor, elements, vertices)
+                                })
+                            };
+                            [gather(&target[0]), gather(&target[1]), gather(&target[2])]
+                        })
+                        .collect();
+                    ChunkData { attributes, targets, indices: chunk.indices }
+                })
+                .collect();
+            splits.push(Split {
+                mesh: mesh.index(),
+                primitive: primitive.index(),
+                chunks,
+                buffer: target_buffer(&primitive),
+            });
+        }
+    }
+
+    let mut root = gltf.into_json();
+    let mut created = 0;
+    // Splitting from the last primitive keeps the indices of earlier ones valid.
+    for split in splits.into_iter().rev() {
+        let original = root.meshes[split.mesh].primitives[split.primitive].clone();
+        let buffer = split.buffer;
+        let mut primitives = vec![];
+        for ChunkData { attributes, targets, indices } in split.chunks {
+            let mut primitive = original.clone();
+            primitive.attributes = attributes
+                .into_iter()
+                .map(|(semantic, attribute)| {
+                    let index = attribute.push(&mut root, buffers, buffer);
+                    (Checked::Valid(semantic), index)
+                })
+                .collect();
+            if !targets.is_empty() {
+                let mut push = |attribute: Option<Attribute>| {
+                    attribute.map(|attribute| attribute.push(&mut root, buffers, buffer))
+                };
+                primitive.targets = Some(
+                    targets
+                        .into_iter()
+                        .map(|[positions, normals, tangents]| json::mesh::MorphTarget {
+                            positions: push(positions),
+                            normals: push(normals),
+                            tangents: push(tangents),
+                        })
+                        .collect(),
+                );
+            }
+            primitive.indices = Some(push_indices(&mut root, buffers, buffer, &indices));
+            primitives.push(primitive);
+        }
+        created += primitives.len() - 1;
+        let mesh = &mut root.meshes[split.mesh];
+        let _ = mesh.primitives.splice(split.primitive..split.primitive + 1, primitives);
+    }
+    (Gltf::from_json(root).skip_validation(), created)
+}
+
+/// Combines two arrays of bounds component-wise.
+fn zip_with(a: &[f64], b: &[f64], f: fn(f64, f64) -> f64) -> Vec<f64> {
+    a.iter().zip(b).map(|(&a, &b)| f(a, b)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use byteorder::{LE, ByteOrder};
+    use gltf::Gltf;
+
+    use super::*;
+
+    const JSON: &'static str = r#"{
+        "asset": { "version": "2.0" },
+        "buffers": [{ "byteLength": 78 }],
+        "bufferViews": [
+            { "buffer": 0, "byteOffset": 0, "byteLength": 36 },
+            { "buffer": 0, "byteOffset": 36, "byteLength": 36 },
+            { "buffer": 0, "byteOffset": 72, "byteLength": 6 }
+        ],
+        "accessors": [
+            {
+                "bufferView": 0, "componentType": 5126, "count": 3, "type": "VEC3",
+                "min": [0, 0, 0], "max": [1, 1, 0]
+            },
+            {
+                "bufferView": 1, "componentType": 5126, "count": 3, "type": "VEC3",
+                "min": [-1, 0, 0], "max": [0, 1, 2]
+            },
+            { "bufferView": 2, "componentType": 5121, "count": 6, "type": "SCALAR" }
+        ],
+        "materials": [{}, {}],
+        "meshes": [{
+            "primitives": [
+                { "attributes": { "POSITION": 0 }, "indices": 2, "material": 0 },
+                { "attributes": { "POSITION": 1 }, "material": 1 },
+                { "attributes": { "POSITION": 1 }, "material": 0 }
+            ]
+        }],
+        "nodes": [{ "mesh": 0, "translation": [1, 0, 0] }]
+    }"#;
+
+    fn buffers() -> Vec<Vec<u8>> {
+        let mut data = vec![0; 78];
+        LE::write_f32_into(&[0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0], &mut data[0..36]);
+        LE::write_f32_into(&[-1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 2.0], &mut data[36..72]);
+        data[72..78].copy_from_slice(&[0, 1, 2, 2, 1, 0]);
+        vec![data]
+    }
+
+    #[test]
+    fn split() {
+        let gltf = Gltf::from_str(JSON).unwrap().skip_validation();
+        let (gltf, created) = split_by_material(gltf);
+        assert_eq!(1, created);
+        let materials = |mesh: gltf::Mesh| -> Vec<_> {
+            mesh.primitives().map(|p| p.material().index()).collect()
+        };
+        assert_eq!(vec![Some(0), Some(0)], materials(gltf.meshes().next().unwrap()));
+        assert_eq!(vec![Some(1)], materials(gltf.meshes().nth(1).unwrap()));
+
+        let node = gltf.nodes().next().unwrap();
+        let child = node.children().next().unwrap();
+        assert_eq!(Some(1), child.mesh().map(|mesh| mesh.index()));
+        assert_eq!([0.0, 0.0, 0.0], child.transform().decomposed().0);
+    }
+
+    #[test]
+    fn merge() {
+        let mut buffers = buffers();
+        let gltf = Gltf::from_str(JSON).unwrap().skip_validation();
+        let (gltf, removed) = merge_by_material(gltf, &mut buffers);
+        assert_eq!(1, removed);
+
+        let mesh = gltf.meshes().next().unwrap();
+        let primitives: Vec<_> = mesh.primitives().collect();
+        assert_eq!(2, primitives.len());
+        assert_eq!(Some(1), primitives[1].material().index());
+
+        let merged = &primitives[0];
+        let positions: Vec<_> = merged.positions(&buffers).unwrap().collect();
+        assert_eq!(6, positions.len());
+        assert_eq!([0.0, 0.0, 2.0], positions[5]);
+        let indices: Vec<_> = merged.indices_u32(&buffers).unwrap().collect();
+        assert_eq!(vec![0, 1, 2, 2, 1, 0, 3, 4, 5], indices);
+        let bounds = merged.position_bounds().unwrap();
+        assert_eq!([-1.0, 0.0, 0.0], bounds.min);
+        assert_eq!([1.0, 1.0, 2.0], bounds.max);
+    }
+
+    const STRIP: &'static str = r#"{
+        "asset": { "version": "2.0" },
+        "buffers": [{ "byteLength": 210 }],
+        "bufferViews": [
+            { "buffer": 0, "byteOffset": 0, "byteLength": 96 },
+            { "buffer": 0, "byteOffset": 96, "byteLength": 96 },
+            { "buffer": 0, "byteOffset": 192, "byteLength": 18 }
+        ],
+        "accessors": [
+            {
+                "bufferView": 0, "componentType": 5126, "count": 8, "type": "VEC3",
+                "min": [0, 0, 0], "max": [7, 0, 0]
+            },
+            { "bufferView": 1, "componentType": 5126, "count": 8, "type": "VEC3" },
+            { "bufferView": 2, "componentType": 5121, "count": 18, "type": "SCALAR" }
+        ],
+        "meshes": [{
+            "primitives": [{
+                "attributes": { "POSITION": 0 },
+                "indices": 2,
+                "targets": [{ "POSITION": 1 }]
+            }]
+        }]
+    }"#;
+
+    #[test]
+    fn chunk_vertices() {
+        let mut data = vec![0; 210];
+        for i in 0..8 {
+            let offset = i * 12;
+            LE::write_f32_into(&[i as f32, 0.0, 0.0], &mut data[offset..offset + 12]);
+            LE::write_f32_into(&[0.0, i as f32, 0.0], &mut data[96 + offset..108 + offset]);
+        }
+        data[192..210].copy_from_slice(&[0, 1, 2, 2, 1, 3, 2, 3, 4, 4, 3, 5, 4, 5, 6, 6, 5, 7]);
+        let mut buffers = vec![data];
+        let gltf = Gltf::from_str(STRIP).unwrap().skip_validation();
+        let (gltf, created) = split_by_vertex_count(gltf, &mut buffers, 5);
+        assert_eq!(1, created);
+
+        let mesh = gltf.meshes().next().unwrap();
+        let primitives: Vec<_> = mesh.primitives().collect();
+        assert_eq!(2, primitives.len());
+        let x: Vec<_> = primitives[1].positions(&buffers).unwrap().map(|p| p[0]).collect();
+        assert_eq!(vec![4.0, 3.0, 5.0, 6.0, 7.0], x);
+        let indices: Vec<_> = primitives[1].indices_u32(&buffers).unwrap().collect();
+        assert_eq!(vec![0, 1, 2, 0, 2, 3, 3, 2, 4], indices);
+        let bounds = primitives[1].position_bounds().unwrap();
+        assert_eq!(([3.0, 0.0, 0.0], [7.0, 0.0, 0.0]), (bounds.min, bounds.max));
+
+        let target = primitives[1].morph_targets().next().unwrap().positions().unwrap();
+        let mut displacements = [0.0; 15];
+        LE::read_f32_into(&packed(&target, &buffers), &mut displacements);
+        let y: Vec<_> = displacements.chunks(3).map(|d| d[1]).collect();
+        assert_eq!(vec![4.0, 3.0, 5.0, 6.0, 7.0], y);
+        assert_eq!(5, primitives[0].indices_u32(&buffers).unwrap().max().unwrap() + 1);
+    }
+}
@@ -0,0 +1,237 @@
+use gltf::{self, json, Gltf};
+
+/// How buffer views are distributed across binary files.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Strategy {
+    /// A single binary file holding every view.
+    Single,
+
+    /// A binary file per mesh holding the geometry of the mesh, followed by a
+    /// binary file holding every view not used by any mesh, e.g. animations,
+    /// skins, and images.
+    ///
+    /// Views shared between meshes go with the first mesh using them.
+    PerMesh,
+
+    /// Binary files of at most the given number of bytes, filled with views in
+    /// order.
+    ///
+    /// Views larger than the limit get a binary file of their own, since
+    /// views are never split.
+    MaxLength(usize),
+}
+
+/// Distributes the buffer views of an asset across new buffers, each stored in
+/// an external binary file.
+///
+/// Several medium-sized binary files suit web delivery, since they can be
+/// fetched in parallel and with range requests, and since meshes can be
+/// loaded on demand with `Strategy::PerMesh`.
+///
+/// Views keep their indices, data, and strides, so accessors and extensions
+/// referencing views are unaffected; only the buffer and byte offset of each
+/// view change, and every view starts at a multiple of four bytes. The URI of
+/// each new buffer is `uri_template` with `{index}` replaced by the index of
+/// the buffer. Returns the asset and the data of the new buffers. Data not
+/// referenced by any view is dropped, as are the names and extras of the
+/// original buffers, and binary glTF data becomes an external file too.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn run() -> Result<(), Box<std::error::Error>> {
+/// use gltf_utils::bins::{distribute, Strategy};
+///
+/// let gltf = gltf::Gltf::from_slice(include_bytes!("../../examples/Box.gltf"))?
+///     .validate_completely()?;
+/// let buffers = vec![include_bytes!("../../examples/Box0.bin").to_vec()];
+/// let (gltf, buffers) = distribute(gltf, &buffers, Strategy::MaxLength(600), "Box{index}.bin");
+/// assert_eq!(2, buffers.len());
+/// assert_eq!("Box1.bin", gltf.buffers().nth(1).unwrap().uri());
+/// # Ok(())
+/// # }
+/// # fn main() { run().unwrap() }
+/// ```
+///
+/// # Panics
+///
+/// Panics if more than one buffer is written and `uri_template` lacks
+/// `{index}`, or if a view lies outside its buffer data.
+pub fn distribute(
+    gltf: Gltf,
+    buffers: &[Vec<u8>],
+    strategy: Strategy,
+    uri_template: &str,
+) -> (Gltf, Vec<Vec<u8>>) {
+    let groups = match strategy {
+        Strategy::Single => vec![(0..gltf.views().len()).collect()],
+        Strategy::PerMesh => per_mesh(&gltf),
+        Strategy::MaxLength(max_length) => by_length(&gltf, max_length),
+    };
+    let groups: Vec<Vec<usize>> = groups.into_iter().filter(|group| !group.is_empty()).collect();
+    assert!(
+        groups.len() <= 1 || uri_template.contains("{index}"),
+        "URI template lacks an index placeholder",
+    );
+
+    let mut root = gltf.into_json();
+    let mut data = vec![];
+    let mut new_buffers = vec![];
+    for (index, group) in groups.into_iter().enumerate() {
+        let mut bytes = vec![];
+        for view in group {
+            let view = &mut root.buffer_views[view];
+            let begin = view.byte_offset as usize;
+            let end = begin + view.byte_length as usize;
+            while bytes.len() % 4 != 0 {
+                bytes.push(0);
+            }
+            let source = &buffers[view.buffer.value()];
+            bytes.extend_from_slice(&source[begin..end]);
+            view.buffer = json::Index::new(index as u32);
+            view.byte_offset = (bytes.len() - (end - begin)) as u32;
+        }
+        let mut buffer = json::Buffer::new(bytes.len() as u32);
+        buffer.uri = Some(uri_template.replace("{index}", &index.to_string()));
+        new_buffers.push(buffer);
+        data.push(bytes);
+    }
+    root.buffers = new_buffers;
+    (Gltf::from_json(root).skip_validation(), data)
+}
+
+/// Groups views by the first mesh using them, followed by the views not used
+/// by any mesh.
+fn per_mesh(gltf: &Gltf) -> Vec<Vec<usize>> {
+    let mut owner = vec![None; gltf.views().len()];
+    for mesh in gltf.meshes() {
+        let mut claim = |accessor: gltf::Accessor| {
+            for view in accessor_views(&accessor) {
+                if owner[view].is_none() {
+                    owner[view] = Some(mesh.index());
+                }
+            }
+        };
+        for primitive in mesh.primitives() {
+            if let Some(indices) = primitive.indices() {
+                claim(indices);
+            }
+            for (_, accessor) in primitive.attributes() {
+                claim(accessor);
+            }
+            for target in primitive.morph_targets() {
+                let accessors = vec![target.positions(), target.normals(), target.tangents()];
+                for accessor in accessors.into_iter().flat_map(Option::into_iter) {
+                    claim(accessor);
+                }
+            }
+        }
+    }
+
+    let mut groups = vec![vec![]; gltf.meshes().len() + 1];
+    let rest = groups.len() - 1;
+    for (view, owner) in owner.into_iter().enumerate() {
+        groups[owner.unwrap_or(rest)].push(view);
+    }
+    groups
+}
+
+/// Returns the views holding the data of an accessor, including its sparse
+/// indices and values.
+fn accessor_views(accessor: &gltf::Accessor) -> Vec<usize> {
+    let mut views: Vec<usize> = accessor.view().map(|view| view.index()).into_iter().collect();
+    if let Some(sparse) = accessor.sparse() {
+        views.push(sparse.indices().view().index());
+        views.push(sparse.values().view().index());
+    }
+    views
+}
+
+/// Groups consecutive views into groups of at most `max_length` bytes,
+/// including alignment padding.
+fn by_length(gltf: &Gltf, max_length: usize) -> Vec<Vec<usize>> {
+    let mut groups = vec![vec![]];
+    let mut length: usize = 0;
+    for view in gltf.views() {
+        let begin = length.div_ceil(4) * 4;
+        if begin + view.length() > max_length && length > 0 {
+            groups.push(vec![]);
+            length = view.length();
+        } else {
+            length = begin + view.length();
+        }
+        groups.last_mut().unwrap().push(view.index());
+    }
+    groups
+}
+
+#[cfg(test)]
+mod tests {
+    use gltf::Gltf;
+
+    use super::*;
+
+    const JSON: &'static str = r#"{
+        "asset": { "version": "2.0" },
+        "buffers": [{ "byteLength": 24 }],
+        "bufferViews": [
+            { "buffer": 0, "byteLength": 6 },
+            { "buffer": 0, "byteOffset": 6, "byteLength": 6 },
+            { "buffer": 0, "byteOffset": 12, "byteLength": 4 },
+            { "buffer": 0, "byteOffset": 16, "byteLength": 8 }
+        ],
+        "accessors": [
+            { "bufferView": 0, "componentType": 5121, "count": 2, "type": "VEC3" },
+            { "bufferView": 1, "componentType": 5121, "count": 2, "type": "VEC3" },
+            { "bufferView": 2, "componentType": 5126, "count": 1, "type": "SCALAR" }
+        ],
+        "meshes": [
+            { "primitives": [{ "attributes": { "POSITION": 1 } }] },
+            { "primitives": [{ "attributes": { "POSITION": 0 }, "indices": 2 }] }
+        ]
+    }"#;
+
+    fn buffers() -> Vec<Vec<u8>> {
+        vec![(0..24).collect()]
+    }
+
+    #[test]
+    fn single() {
+        let gltf = Gltf::from_str(JSON).unwrap().skip_validation();
+        let (gltf, buffers) = distribute(gltf, &buffers(), Strategy::Single, "a.bin");
+        assert_eq!(1, buffers.len());
+        assert_eq!("a.bin", gltf.buffers().next().unwrap().uri());
+        let offsets: Vec<_> = gltf.views().map(|view| view.offset()).collect();
+        assert_eq!(vec![0, 8, 16, 20], offsets);
+        assert_eq!(&[6, 7, 8, 9, 10, 11], &buffers[0][8..14]);
+        assert_eq!(28, gltf.buffers().next().unwrap().length());
+    }
+
+    #[test]
+    fn per_mesh() {
+        let gltf = Gltf::from_str(JSON).unwrap().skip_validation();
+        let (gltf, buffers) = distribute(gltf, &buffers(), Strategy::PerMesh, "{index}.bin");
+        assert_eq!(3, buffers.len());
+        let owners: Vec<_> = gltf.views().map(|view| view.buffer().index()).collect();
+        assert_eq!(vec![1, 0, 1, 2], owners);
+        assert_eq!(vec![6, 7, 8, 9, 10, 11], buffers[0]);
+        assert_eq!(&[16, 17, 18, 19], &buffers[2][..4]);
+        assert_eq!("2.bin", gltf.buffers().nth(2).unwrap().uri());
+    }
+
+    #[test]
+    fn max_length() {
+        let gltf = Gltf::from_str(JSON).unwrap().skip_validation();
+        let (gltf, buffers) = distribute(gltf, &buffers(), Strategy::MaxLength(16), "{index}.bin");
+        let owners: Vec<_> = gltf.views().map(|view| view.buffer().index()).collect();
+        assert_eq!(vec![0, 0, 1, 1], owners);
+        assert!(buffers.iter().all(|data| data.len() <= 16));
+    }
+
+    #[test]
+    #[should_panic(expected = "URI template")]
+    fn colliding_uris() {
+        let gltf = Gltf::from_str(JSON).unwrap().skip_validation();
+        distribute(gltf, &buffers(), Strategy::PerMesh, "a.bin");
+    }
+}
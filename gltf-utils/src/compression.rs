@@ -0,0 +1,391 @@
+use std::fmt;
+
+use gltf::{self, Gltf};
+use gltf::mesh::Mode;
+
+use Source;
+
+/// Views shorter than this are not worth compressing.
+const MIN_LENGTH: usize = 1024;
+
+/// Geometry views shorter than this are better served by meshopt, whose
+/// decoder is much faster than Draco's.
+const MIN_DRACO_LENGTH: usize = 64 * 1024;
+
+/// Estimated compression ratio below which compression is not suggested.
+const MIN_RATIO: f64 = 1.25;
+
+/// What the data of a buffer view holds.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Content {
+    /// Vertex attributes of mesh primitives.
+    Vertices,
+
+    /// Indices of mesh primitives.
+    Indices,
+
+    /// Keyframe times and values of animations.
+    Animation,
+
+    /// Encoded images.
+    Image,
+
+    /// Anything else, e.g. inverse bind matrices or unreferenced data.
+    Other,
+}
+
+/// The compression extension suggested for a buffer view.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Suggestion {
+    /// Leave the view uncompressed, because it is small, already compressed,
+    /// or close to random.
+    None,
+
+    /// `EXT_meshopt_compression`, which suits vertex, index, and animation
+    /// data of any kind and decodes fast.
+    Meshopt,
+
+    /// `KHR_draco_mesh_compression`, which gives the best ratios for large
+    /// static triangle meshes but does not support morph targets.
+    Draco,
+}
+
+/// The compressibility estimate of a single buffer view.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ViewReport {
+    /// The index of the buffer view.
+    pub view: usize,
+
+    /// The length of the buffer view in bytes.
+    pub length: usize,
+
+    /// What the buffer view holds.
+    pub content: Content,
+
+    /// The order-0 entropy of the data, in bits per byte.
+    pub entropy: f64,
+
+    /// The order-0 entropy of the data after subtracting each byte from the
+    /// byte at the same position of the previous element, in bits per byte.
+    ///
+    /// Smoothly varying attributes such as positions and keyframes have a
+    /// much lower delta entropy, which is what meshopt filters exploit.
+    pub delta_entropy: f64,
+
+    /// The length of the data compressed with zstd at its fastest level, with
+    /// the `zstd` feature.
+    pub zstd_length: Option<usize>,
+
+    /// The suggested compression extension.
+    pub suggestion: Suggestion,
+}
+
+impl ViewReport {
+    /// Returns the length the entropy estimates suggest the data can be
+    /// compressed to.
+    pub fn estimated_length(&self) -> usize {
+        let bits = self.entropy.min(self.delta_entropy);
+        (self.length as f64 * bits / 8.0).ceil() as usize
+    }
+
+    /// Returns the estimated compression ratio.
+    pub fn estimated_ratio(&self) -> f64 {
+        self.length as f64 / self.estimated_length().max(1) as f64
+    }
+}
+
+/// A compressibility report of every buffer view of an asset.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Report {
+    /// The report of each buffer view, ordered by view index.
+    pub views: Vec<ViewReport>,
+}
+
+impl Report {
+    /// Returns the length of all buffer views.
+    pub fn length(&self) -> usize {
+        self.views.iter().map(|view| view.length).sum()
+    }
+
+    /// Returns the estimated length of all buffer views after applying every
+    /// suggestion.
+    pub fn estimated_length(&self) -> usize {
+        self.views
+            .iter()
+            .map(|view| match view.suggestion {
+                Suggestion::None => view.length,
+                _ => view.estimated_length(),
+            })
+            .sum()
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "{:>6} {:<10} {:>12} {:>7} {:>7} {:>12}  suggestion",
+            "view", "content", "length", "H", "H(d)", "zstd",
+        )?;
+        for view in &self.views {
+            let zstd = match view.zstd_length {
+                Some(length) => length.to_string(),
+                None => "-".to_string(),
+            };
+            writeln!(
+                f,
+                "{:>6} {:<10} {:>12} {:>7.3} {:>7.3} {:>12}  {:?}",
+                view.view,
+                format!("{:?}", view.content),
+                view.length,
+                view.entropy,
+                view.delta_entropy,
+                zstd,
+                view.suggestion,
+            )?;
+        }
+        write!(f, "total {} B, estimated {} B", self.length(), self.estimated_length())
+    }
+}
+
+/// Returns the order-0 entropy of some data, in bits per byte.
+///
+/// # Examples
+///
+/// ```rust
+/// use gltf_utils::compression::entropy;
+///
+/// assert_eq!(0.0, entropy(&[7; 16]));
+/// assert_eq!(1.0, entropy(&[0, 1, 0, 1]));
+/// ```
+pub fn entropy(data: &[u8]) -> f64 {
+    let mut histogram = [0usize; 256];
+    for &byte in data {
+        histogram[byte as usize] += 1;
+    }
+    let length = data.len() as f64;
+    histogram
+        .iter()
+        .filter(|&&count| count > 0)
+        .map(|&count| {
+            let p = count as f64 / length;
+            -p * p.log2()
+        })
+        .sum()
+}
+
+/// Returns the order-0 entropy of some data after delta encoding each byte
+/// against the byte `stride` bytes before it, in bits per byte.
+pub fn delta_entropy(data: &[u8], stride: usize) -> f64 {
+    let stride = stride.max(1);
+    let deltas: Vec<u8> = data
+        .iter()
+        .enumerate()
+        .map(|(i, &byte)| if i < stride { byte } else { byte.wrapping_sub(data[i - stride]) })
+        .collect();
+    entropy(&deltas)
+}
+
+/// How the views of an asset are used.
+#[derive(Clone, Copy, Debug)]
+struct Usage {
+    content: Content,
+    stride: usize,
+    draco: bool,
+}
+
+/// Estimates the compressibility of every buffer view of an asset and
+/// suggests a compression extension for each.
+///
+/// Views whose buffer data is missing or too short are skipped.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn run() -> Result<(), Box<std::error::Error>> {
+/// use gltf_utils::compression::{analyze, Content};
+///
+/// let gltf = gltf::Gltf::from_slice(include_bytes!("../../examples/Box.gltf"))?
+///     .validate_completely()?;
+/// let buffers = vec![include_bytes!("../../examples/Box0.bin").to_vec()];
+/// let report = analyze(&gltf, &buffers);
+/// assert_eq!(Content::Indices, report.views[0].content);
+/// println!("{}", report);
+/// # Ok(())
+/// # }
+/// # fn main() { run().unwrap() }
+/// ```
+pub fn analyze<S: Source>(gltf: &Gltf, source: &S) -> Report {
+    let mut usages: Vec<Usage> = gltf
+        .views()
+        .map(|view| Usage {
+            content: Content::Other,
+            stride: view.stride().unwrap_or(1),
+            draco: true,
+        })
+        .collect();
+
+    {
+        let mut mark = |accessor: gltf::Accessor, content: Content, draco: bool| {
+            let view = match accessor.view() {
+                Some(view) => view,
+                None => return,
+            };
+            let usage = &mut usages[view.index()];
+            if usage.content == Content::Other || usage.content == content {
+                usage.content = content;
+            }
+            if view.stride().is_none() {
+                usage.stride = accessor.size();
+            }
+            usage.draco &= draco;
+        };
+        for mesh in gltf.meshes() {
+            for primitive in mesh.primitives() {
+                let draco = primitive.mode() == Mode::Triangles
+                    && primitive.morph_targets().len() == 0;
+                if let Some(indices) = primitive.indices() {
+                    mark(indices, Content::Indices, draco);
+                }
+                for (_, accessor) in primitive.attributes() {
+                    mark(accessor, Content::Vertices, draco);
+                }
+                for target in primitive.morph_targets() {
+                    let accessors = vec![target.positions(), target.normals(), target.tangents()];
+                    for accessor in accessors.into_iter().flat_map(Option::into_iter) {
+                        mark(accessor, Content::Vertices, false);
+                    }
+                }
+            }
+        }
+        for animation in gltf.animations() {
+            for sampler in animation.samplers() {
+                mark(sampler.input(), Content::Animation, false);
+                mark(sampler.output(), Content::Animation, false);
+            }
+        }
+    }
+    for image in gltf.images() {
+        if let gltf::image::Data::View { view, .. } = image.data() {
+            usages[view.index()].content = Content::Image;
+        }
+    }
+
+    let views = gltf
+        .views()
+        .filter_map(|view| {
+            let buffer = source.source_buffer(&view.buffer());
+            let data = buffer.get(view.offset()..view.offset() + view.length())?;
+            let usage = usages[view.index()];
+            let mut report = ViewReport {
+                view: view.index(),
+                length: data.len(),
+                content: usage.content,
+                entropy: entropy(data),
+                delta_entropy: delta_entropy(data, usage.stride),
+                zstd_length: zstd_length(data),
+                suggestion: Suggestion::None,
+            };
+            report.suggestion = suggest(&report, usage.draco);
+            Some(report)
+        })
+        .collect();
+    Report { views }
+}
+
+/// Picks the compression extension for a view.
+fn suggest(report: &ViewReport, draco: bool) -> Suggestion {
+    let compressible = report.length >= MIN_LENGTH && report.estimated_ratio() >= MIN_RATIO;
+    match report.content {
+        _ if !compressible => Suggestion::None,
+        Content::Vertices | Content::Indices if draco && report.length >= MIN_DRACO_LENGTH => {
+            Suggestion::Draco
+        },
+        Content::Vertices | Content::Indices | Content::Animation => Suggestion::Meshopt,
+        Content::Image | Content::Other => Suggestion::None,
+    }
+}
+
+#[cfg(feature = "zstd")]
+fn zstd_length(data: &[u8]) -> Option<usize> {
+    use ruzstd::encoding::{compress_to_vec, CompressionLevel};
+    Some(compress_to_vec(data, CompressionLevel::Fastest).len())
+}
+
+#[cfg(not(feature = "zstd"))]
+fn zstd_length(_data: &[u8]) -> Option<usize> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use byteorder::{LE, ByteOrder};
+    use gltf::Gltf;
+
+    use super::*;
+
+    const JSON: &'static str = r#"{
+        "asset": { "version": "2.0" },
+        "buffers": [{ "byteLength": 73728 }],
+        "bufferViews": [
+            { "buffer": 0, "byteLength": 65536 },
+            { "buffer": 0, "byteOffset": 65536, "byteLength": 4096 },
+            { "buffer": 0, "byteOffset": 69632, "byteLength": 4096 }
+        ],
+        "accessors": [
+            {
+                "bufferView": 0, "componentType": 5126, "count": 5461, "type": "VEC3",
+                "min": [0, 0, 0], "max": [1, 1, 1]
+            },
+            { "bufferView": 1, "componentType": 5126, "count": 1024, "type": "SCALAR" },
+            { "bufferView": 2, "componentType": 5126, "count": 1024, "type": "SCALAR" }
+        ],
+        "meshes": [{ "primitives": [{ "attributes": { "POSITION": 0 } }] }],
+        "nodes": [{ "mesh": 0 }],
+        "animations": [{
+            "channels": [{ "sampler": 0, "target": { "node": 0, "path": "scale" } }],
+            "samplers": [{ "input": 1, "output": 1 }]
+        }]
+    }"#;
+
+    #[test]
+    fn suggestions() {
+        let gltf = Gltf::from_str(JSON).unwrap().skip_validation();
+        let mut data = vec![0; 73728];
+        for i in 0..65536 / 4 {
+            LE::write_f32(&mut data[i * 4..], (i / 3) as f32 * 0.001);
+        }
+        for i in 0..1024 {
+            LE::write_f32(&mut data[65536 + i * 4..], i as f32 / 30.0);
+        }
+        let mut state = 0x2545F491u32;
+        for byte in &mut data[69632..] {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            *byte = state as u8;
+        }
+        let report = analyze(&gltf, &vec![data]);
+        assert_eq!(3, report.views.len());
+
+        let positions = &report.views[0];
+        assert_eq!(Content::Vertices, positions.content);
+        assert!(positions.delta_entropy < positions.entropy);
+        assert_eq!(Suggestion::Draco, positions.suggestion);
+
+        assert_eq!(Content::Animation, report.views[1].content);
+        assert_eq!(Suggestion::Meshopt, report.views[1].suggestion);
+
+        let random = &report.views[2];
+        assert_eq!(Content::Other, random.content);
+        assert!(random.entropy > 7.9);
+        assert_eq!(Suggestion::None, random.suggestion);
+        assert!(report.estimated_length() < report.length());
+    }
+
+    #[cfg(feature = "zstd")]
+    #[test]
+    fn zstd_trial() {
+        assert!(zstd_length(&[0; 4096]).unwrap() < 64);
+    }
+}
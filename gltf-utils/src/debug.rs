@@ -0,0 +1,155 @@
+use gltf::{self, json, Gltf, Semantic};
+use gltf::json::validation::Checked;
+
+use interleave::accessor_buffer;
+use {PrimitiveIterators, Source};
+use write::Writer;
+
+/// Vertex data that can be visualized as vertex colors.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Visualize {
+    /// Vertex normals, mapped from `[-1.0, 1.0]` to `[0.0, 1.0]`.
+    Normals,
+
+    /// Texture co-ordinates of the given set, wrapped to `[0.0, 1.0]` and
+    /// stored in the red and green channels.
+    TexCoords(u32),
+
+    /// The heaviest joint of the given `JOINTS_n` / `WEIGHTS_n` set, with a
+    /// distinct color for each joint index.
+    Joints(u32),
+}
+
+/// Returns a distinct, fully saturated color for the given joint index.
+fn joint_color(joint: u16) -> [f32; 3] {
+    // Stepping the hue by the golden ratio keeps neighbouring joints apart.
+    let hue = (joint as f32 * 0.618_034).fract() * 6.0;
+    let x = 1.0 - (hue % 2.0 - 1.0).abs();
+    match hue as u32 {
+        0 => [1.0, x, 0.0],
+        1 => [x, 1.0, 0.0],
+        2 => [0.0, 1.0, x],
+        3 => [0.0, x, 1.0],
+        4 => [x, 0.0, 1.0],
+        _ => [1.0, 0.0, x],
+    }
+}
+
+/// Returns the debug colors of every vertex of a primitive.
+///
+/// Returns `None` if the primitive lacks the visualized attribute.
+pub fn colors<S: Source>(
+    primitive: &gltf::Primitive,
+    source: &S,
+    visualize: Visualize,
+) -> Option<Vec<[f32; 3]>> {
+    match visualize {
+        Visualize::Normals => {
+            primitive.normals(source).map(|iter| {
+                iter.map(|n| [n[0] * 0.5 + 0.5, n[1] * 0.5 + 0.5, n[2] * 0.5 + 0.5])
+                    .collect()
+            })
+        },
+        Visualize::TexCoords(set) => {
+            primitive.tex_coords_f32(set, source).map(|iter| {
+                iter.map(|uv| [uv[0] - uv[0].floor(), uv[1] - uv[1].floor(), 0.0])
+                    .collect()
+            })
+        },
+        Visualize::Joints(set) => {
+            let joints = primitive.joints_u16(set, source)?;
+            let weights = primitive.weights_f32(set, source)?;
+            let colors = joints
+                .zip(weights)
+                .map(|(j, w)| {
+                    let mut heaviest = 0;
+                    for i in 1..4 {
+                        if w[i] > w[heaviest] {
+                            heaviest = i;
+                        }
+                    }
+                    joint_color(j[heaviest])
+                })
+                .collect();
+            Some(colors)
+        },
+    }
+}
+
+/// Bakes debug data into the `COLOR_0` attribute of every primitive.
+///
+/// Existing `COLOR_0` attributes are replaced and primitives lacking the
+/// visualized attribute are left unchanged. The color data of each primitive
+/// is appended to the buffer containing its vertex positions.
+pub fn bake(gltf: Gltf, buffers: &mut Vec<Vec<u8>>, visualize: Visualize) -> Gltf {
+    let mut baked = vec![];
+    for mesh in gltf.meshes() {
+        for primitive in mesh.primitives() {
+            let positions = match primitive.get(&Semantic::Positions) {
+                Some(accessor) => accessor,
+                None => continue,
+            };
+            if let Some(colors) = colors(&primitive, &*buffers, visualize) {
+                let buffer = accessor_buffer(&positions);
+                baked.push((mesh.index(), primitive.index(), buffer, colors));
+            }
+        }
+    }
+
+    let mut root = gltf.into_json();
+    for (mesh, primitive, buffer, colors) in baked {
+        let colors: Vec<f32> = colors.iter().flat_map(|color| color.iter().cloned()).collect();
+        let accessor = Writer::new(buffer)
+            .target(json::buffer::Target::ArrayBuffer)
+            .push_f32(&mut root, buffers, &colors, json::accessor::Type::Vec3);
+        root.meshes[mesh].primitives[primitive]
+            .attributes
+            .insert(Checked::Valid(Semantic::Colors(0)), accessor);
+    }
+    Gltf::from_json(root).skip_validation()
+}
+
+#[cfg(test)]
+mod tests {
+    use byteorder::{LE, ByteOrder};
+    use gltf::Gltf;
+
+    use super::*;
+
+    const JSON: &'static str = r#"{
+        "asset": { "version": "2.0" },
+        "buffers": [{ "byteLength": 72 }],
+        "bufferViews": [
+            { "buffer": 0, "byteOffset": 0, "byteLength": 36 },
+            { "buffer": 0, "byteOffset": 36, "byteLength": 36 }
+        ],
+        "accessors": [
+            { "bufferView": 0, "componentType": 5126, "count": 3, "type": "VEC3" },
+            { "bufferView": 1, "componentType": 5126, "count": 3, "type": "VEC3" }
+        ],
+        "meshes": [{
+            "primitives": [{ "attributes": { "POSITION": 0, "NORMAL": 1 } }]
+        }]
+    }"#;
+
+    #[test]
+    fn bake_normals() {
+        let mut data = vec![0; 72];
+        LE::write_f32_into(&[0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0], &mut data[0..36]);
+        LE::write_f32_into(&[0.0, 0.0, 1.0, 0.0, 0.0, -1.0, 1.0, 0.0, 0.0], &mut data[36..72]);
+        let mut buffers = vec![data];
+
+        let gltf = Gltf::from_str(JSON).unwrap().skip_validation();
+        let gltf = bake(gltf, &mut buffers, Visualize::Normals);
+        assert_eq!(72 + 36, buffers[0].len());
+        assert_eq!(72 + 36, gltf.buffers().next().unwrap().length());
+
+        let mesh = gltf.meshes().next().unwrap();
+        let primitive = mesh.primitives().next().unwrap();
+        let colors: Vec<_> = primitive.colors_rgba_f32(0, 1.0, &buffers).unwrap().collect();
+        assert_eq!(
+            vec![[0.5, 0.5, 1.0, 1.0], [0.5, 0.5, 0.0, 1.0], [1.0, 0.5, 0.5, 1.0]],
+            colors,
+        );
+    }
+}
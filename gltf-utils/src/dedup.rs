@@ -0,0 +1,407 @@
+use std::collections::{BTreeSet, HashMap};
+
+use gltf::{self, json, Gltf};
+
+use interleave::packed;
+use prune;
+use texture::Level;
+
+/// Merges accessors with identical parameters and element data.
+///
+/// Every reference to a duplicate accessor, from mesh primitives, morph
+/// targets, skins, and animation samplers, is redirected to the first accessor
+/// with the same component type, dimensions, normalization, count, and packed
+/// element bytes. Accessors with sparse storage are never merged.
+///
+/// The duplicates are removed, renumbering the remaining accessors. Buffer
+/// views no longer referenced by anything are removed along with them, and the
+/// buffers that held them are compacted to the data of their remaining views.
+/// Returns the transformed asset and the number of accessors merged.
+///
+/// # Panics
+///
+/// Panics if a view lies outside its buffer data.
+pub fn accessors(gltf: Gltf, buffers: &mut [Vec<u8>]) -> (Gltf, usize) {
+    let mut first = HashMap::new();
+    let mut remap: Vec<usize> = (0..gltf.accessors().len()).collect();
+    let mut removed = BTreeSet::new();
+    let mut views = BTreeSet::new();
+    for accessor in gltf.accessors() {
+        if accessor.sparse().is_some() {
+            continue;
+        }
+        let key = (
+            accessor.data_type() as u32,
+            accessor.dimensions() as u32,
+            accessor.normalized(),
+            accessor.count(),
+            packed(&accessor, buffers),
+        );
+        let index = *first.entry(key).or_insert_with(|| accessor.index());
+        if index != accessor.index() {
+            remap[accessor.index()] = index;
+            removed.insert(accessor.index());
+            views.extend(accessor.view().map(|view| view.index()));
+        }
+    }
+
+    let mut root = gltf.into_json();
+    json::visit::accessors_mut(&mut root, |index| {
+        *index = json::Index::new(remap[index.value()] as u32);
+    });
+    prune::accessors(&mut root, &removed);
+    prune::views(&mut root, buffers, &views);
+    (Gltf::from_json(root).skip_validation(), removed.len())
+}
+
+/// Merges buffer views referenced by images that contain identical bytes.
+///
+/// Every image referencing a duplicate view is redirected to the first view
+/// with the same content. Duplicate views no longer referenced by anything are
+/// removed, and the buffers that held them are compacted as by `accessors`.
+/// Returns the transformed asset and the number of images redirected.
+///
+/// # Panics
+///
+/// Panics if a view lies outside its buffer data.
+pub fn image_views(gltf: Gltf, buffers: &mut [Vec<u8>]) -> (Gltf, usize) {
+    let mut redirected = vec![];
+    {
+        let mut first: HashMap<&[u8], usize> = HashMap::new();
+        for image in gltf.images() {
+            if let gltf::image::Data::View { view, .. } = image.data() {
+                let data = &buffers[view.buffer().index()];
+                let bytes = &data[view.offset()..view.offset() + view.length()];
+                let index = *first.entry(bytes).or_insert_with(|| view.index());
+                if index != view.index() {
+                    redirected.push((image.index(), view.index(), index));
+                }
+            }
+        }
+    }
+
+    let mut root = gltf.into_json();
+    for &(image, _, view) in &redirected {
+        root.images[image].buffer_view = Some(json::Index::new(view as u32));
+    }
+    let views = redirected.iter().map(|&(_, view, _)| view).collect();
+    prune::views(&mut root, buffers, &views);
+    (Gltf::from_json(root).skip_validation(), redirected.len())
+}
+
+/// What identifies the content of an image.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+enum ImageKey<'a> {
+    /// Decoded pixels.
+    Pixels(Level),
+
+    /// Encoded bytes of a buffer view.
+    Bytes(&'a [u8]),
+
+    /// The URI of an external or embedded image.
+    Uri(&'a str),
+}
+
+/// Merges images with identical pixels or identical encoded data.
+///
+/// This crate does not decode images itself. `decode` is called for every
+/// image and may return its pixels, e.g. as decoded by an image library.
+/// Images with equal dimensions and pixels are merged even if they are encoded
+/// differently. Images that `decode` returns `None` for are merged only with
+/// images of identical bytes, for images stored in buffer views, or identical
+/// URIs. Passing `|_| None` therefore detects byte-identical images without
+/// decoding anything.
+///
+/// Every texture sourcing a duplicate image, including through the `source`
+/// of texture extensions such as `KHR_texture_basisu`, is redirected to the
+/// first image with the same content. The duplicates are removed, renumbering
+/// the remaining images, and their buffer views are removed and the buffers
+/// compacted as by `accessors`. Returns the transformed asset and the number
+/// of images merged. Follow with `textures` to merge the textures that become
+/// identical.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn run() -> Result<(), Box<std::error::Error>> {
+/// let gltf = gltf::Gltf::from_slice(include_bytes!("../../examples/Box.gltf"))?
+///     .validate_completely()?;
+/// let mut buffers = vec![include_bytes!("../../examples/Box0.bin").to_vec()];
+/// let (gltf, _) = gltf_utils::dedup::images(gltf, &mut buffers, |_| None);
+/// let (gltf, merged) = gltf_utils::dedup::textures(gltf);
+/// assert_eq!(0, merged);
+/// # let _ = gltf;
+/// # Ok(())
+/// # }
+/// # fn main() { run().unwrap() }
+/// ```
+///
+/// # Panics
+///
+/// Panics if a view lies outside its buffer data.
+pub fn images<F>(gltf: Gltf, buffers: &mut [Vec<u8>], mut decode: F) -> (Gltf, usize)
+    where F: FnMut(&gltf::Image) -> Option<Level>
+{
+    let mut remap: Vec<usize> = (0..gltf.images().len()).collect();
+    let mut removed = BTreeSet::new();
+    let mut views = BTreeSet::new();
+    {
+        let mut first = HashMap::new();
+        for image in gltf.images() {
+            let data = image.data();
+            let view = match data {
+                gltf::image::Data::View { ref view, .. } => Some(view.index()),
+                gltf::image::Data::Uri { .. } => None,
+            };
+            let key = match (decode(&image), data) {
+                (Some(level), _) => ImageKey::Pixels(level),
+                (None, gltf::image::Data::View { view, .. }) => {
+                    let data = &buffers[view.buffer().index()];
+                    ImageKey::Bytes(&data[view.offset()..view.offset() + view.length()])
+                },
+                (None, gltf::image::Data::Uri { uri, .. }) => ImageKey::Uri(uri),
+            };
+            let index = *first.entry(key).or_insert_with(|| image.index());
+            if index != image.index() {
+                remap[image.index()] = index;
+                removed.insert(image.index());
+                views.extend(view);
+            }
+        }
+    }
+
+    let mut root = gltf.into_json();
+    for texture in &mut root.textures {
+        texture.source = json::Index::new(remap[texture.source.value()] as u32);
+    }
+    prune::unmodelled_images(&mut root, |index| remap.get(index).cloned().unwrap_or(index));
+    prune::images(&mut root, &removed);
+    prune::views(&mut root, buffers, &views);
+    (Gltf::from_json(root).skip_validation(), removed.len())
+}
+
+/// Merges textures with the same image and sampler.
+///
+/// Every reference to a duplicate texture, from materials, including the
+/// texture slots of material extensions this crate does not model, and feature
+/// ID textures, is redirected to the first texture with the same image and
+/// sampler, keeping its texture coordinate set and other parameters. The
+/// duplicates are removed, renumbering the remaining textures. Returns the
+/// transformed asset and the number of textures merged.
+///
+/// Textures with extensions are never merged, since they may select a
+/// different image, e.g. through `KHR_texture_basisu`.
+pub fn textures(gltf: Gltf) -> (Gltf, usize) {
+    let mut first = HashMap::new();
+    let mut remap: Vec<usize> = (0..gltf.textures().len()).collect();
+    let mut removed = BTreeSet::new();
+    for texture in gltf.textures() {
+        if !texture.as_json().extensions.is_empty() {
+            continue;
+        }
+        let key = (texture.source().index(), texture.sampler().index());
+        let index = *first.entry(key).or_insert_with(|| texture.index());
+        if index != texture.index() {
+            remap[texture.index()] = index;
+            removed.insert(texture.index());
+        }
+    }
+
+    let mut root = gltf.into_json();
+    json::visit::textures_mut(&mut root, |index| {
+        *index = json::Index::new(remap[index.value()] as u32);
+    });
+    prune::unmodelled_textures(&mut root, |index| remap.get(index).cloned().unwrap_or(index));
+    prune::textures(&mut root, &removed);
+    (Gltf::from_json(root).skip_validation(), removed.len())
+}
+
+/// Applies a function to every accessor reference of a mesh.
+pub(crate) fn mesh_accessors<F>(mesh: &mut json::Mesh, update: &mut F)
+    where F: FnMut(&mut json::Index<json::Accessor>)
+{
+    for primitive in &mut mesh.primitives {
+        for index in primitive.attributes.values_mut() {
+            update(index);
+        }
+        if let Some(ref mut index) = primitive.indices {
+            update(index);
+        }
+        for target in primitive.targets.iter_mut().flat_map(|targets| targets.iter_mut()) {
+            let indices = vec![
+                &mut target.positions,
+                &mut target.normals,
+                &mut target.tangents,
+            ];
+            for index in indices.into_iter().filter_map(|index| index.as_mut()) {
+                update(index);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use byteorder::{LE, ByteOrder};
+    use gltf::{Gltf, Semantic};
+
+    use super::*;
+
+    const JSON: &'static str = r#"{
+        "asset": { "version": "2.0" },
+        "buffers": [{ "byteLength": 108 }],
+        "bufferViews": [
+            { "buffer": 0, "byteOffset": 0, "byteLength": 36 },
+            { "buffer": 0, "byteOffset": 36, "byteLength": 36 },
+            { "buffer": 0, "byteOffset": 72, "byteLength": 36 }
+        ],
+        "accessors": [
+            { "bufferView": 0, "componentType": 5126, "count": 3, "type": "VEC3" },
+            { "bufferView": 1, "componentType": 5126, "count": 3, "type": "VEC3" },
+            { "bufferView": 2, "componentType": 5126, "count": 9, "type": "SCALAR" }
+        ],
+        "meshes": [
+            { "primitives": [{ "attributes": { "POSITION": 0 } }] },
+            { "primitives": [{ "attributes": { "POSITION": 1, "NORMAL": 2 } }] }
+        ]
+    }"#;
+
+    #[test]
+    fn merge_accessors() {
+        let mut data = vec![0; 108];
+        let positions = [0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0];
+        LE::write_f32_into(&positions, &mut data[0..36]);
+        LE::write_f32_into(&positions, &mut data[36..72]);
+        LE::write_f32_into(&[2.0; 9], &mut data[72..108]);
+        let mut buffers = vec![data];
+
+        let gltf = Gltf::from_str(JSON).unwrap().skip_validation();
+        let (gltf, merged) = accessors(gltf, &mut buffers);
+        assert_eq!(1, merged);
+        assert_eq!(2, gltf.accessors().len());
+        let mesh = gltf.meshes().nth(1).unwrap();
+        let primitive = mesh.primitives().next().unwrap();
+        assert_eq!(0, primitive.get(&Semantic::Positions).unwrap().index());
+        let normals = primitive.get(&Semantic::Normals).unwrap();
+        assert_eq!(1, normals.index());
+
+        // The view of the duplicate is gone and the buffer compacted.
+        assert_eq!(2, gltf.views().len());
+        let view = normals.view().unwrap();
+        assert_eq!((1, 36), (view.index(), view.offset()));
+        assert_eq!(72, buffers[0].len());
+        assert_eq!(72, gltf.buffers().next().unwrap().length());
+        assert_eq!(2.0, LE::read_f32(&buffers[0][36..]));
+    }
+
+    #[test]
+    fn merge_image_views() {
+        let json = r#"{
+            "asset": { "version": "2.0" },
+            "buffers": [{
+                "byteLength": 10,
+                "extensions": { "GLTFRS_buffer_checksum": { "crc32": 0 } }
+            }],
+            "bufferViews": [
+                { "buffer": 0, "byteLength": 3 },
+                { "buffer": 0, "byteOffset": 3, "byteLength": 3 },
+                { "buffer": 0, "byteOffset": 6, "byteLength": 4 }
+            ],
+            "images": [
+                { "bufferView": 0, "mimeType": "image/png" },
+                { "bufferView": 1, "mimeType": "image/png" },
+                { "bufferView": 2, "mimeType": "image/png" }
+            ]
+        }"#;
+        let mut buffers = vec![vec![1, 2, 3, 1, 2, 3, 4, 5, 6, 7]];
+        let gltf = Gltf::from_str(json).unwrap().skip_validation();
+        let (gltf, redirected) = image_views(gltf, &mut buffers);
+        assert_eq!(1, redirected);
+        let views: Vec<_> = gltf.images()
+            .map(|image| match image.data() {
+                gltf::image::Data::View { view, .. } => (view.index(), view.offset()),
+                _ => unreachable!(),
+            })
+            .collect();
+        // The last view keeps its offset modulo 4.
+        assert_eq!(vec![(0, 0), (0, 0), (1, 6)], views);
+        assert_eq!(vec![1, 2, 3, 0, 0, 0, 4, 5, 6, 7], buffers[0]);
+        let buffer = gltf.buffers().next().unwrap();
+        assert_eq!(Some(gltf::buffer::crc32(&buffers[0])), buffer.checksum());
+    }
+
+    #[test]
+    fn merge_images() {
+        let json = r#"{
+            "asset": { "version": "2.0" },
+            "buffers": [{ "byteLength": 8 }],
+            "bufferViews": [
+                { "buffer": 0, "byteLength": 4 },
+                { "buffer": 0, "byteOffset": 4, "byteLength": 4 }
+            ],
+            "images": [
+                { "bufferView": 0, "mimeType": "image/png" },
+                { "bufferView": 1, "mimeType": "image/png" },
+                { "uri": "a.png" },
+                { "uri": "b.jpg" }
+            ],
+            "samplers": [{}],
+            "textures": [
+                { "source": 0 },
+                { "source": 1 },
+                { "source": 2 },
+                { "source": 3 },
+                { "source": 3, "sampler": 0 },
+                { "source": 0, "extensions": { "KHR_texture_basisu": { "source": 1 } } }
+            ],
+            "materials": [
+                {
+                    "pbrMetallicRoughness": { "baseColorTexture": { "index": 1 } },
+                    "emissiveTexture": { "index": 4 }
+                },
+                {
+                    "normalTexture": { "index": 3, "texCoord": 1 },
+                    "extensions": {
+                        "KHR_materials_sheen": { "sheenColorTexture": { "index": 3 } }
+                    }
+                }
+            ]
+        }"#;
+        let mut buffers = vec![vec![1, 2, 3, 4, 1, 2, 3, 4]];
+        let decode = |image: &gltf::Image| match image.data() {
+            // Pretend both external images decode to the same single pixel.
+            gltf::image::Data::Uri { .. } => Some(Level {
+                width: 1,
+                height: 1,
+                data: vec![255; 4],
+            }),
+            _ => None,
+        };
+
+        let gltf = Gltf::from_str(json).unwrap().skip_validation();
+        let (gltf, merged) = images(gltf, &mut buffers, decode);
+        assert_eq!(2, merged);
+        assert_eq!(2, gltf.images().len());
+        let sources: Vec<_> = gltf.textures().map(|texture| texture.source().index()).collect();
+        assert_eq!(vec![0, 0, 1, 1, 1, 0], sources);
+        let basisu = gltf.textures().nth(5).unwrap().as_json().extensions.others.clone();
+        assert_eq!(0, basisu["KHR_texture_basisu"]["source"]);
+        // The duplicate view is gone.
+        assert_eq!(1, gltf.views().len());
+        assert_eq!(vec![1, 2, 3, 4], buffers[0]);
+
+        let (gltf, merged) = textures(gltf);
+        assert_eq!(2, merged);
+        assert_eq!(4, gltf.textures().len());
+        let materials: Vec<_> = gltf.materials()
+            .map(|material| {
+                material.textures().map(|texture| texture.texture().index()).collect::<Vec<_>>()
+            })
+            .collect();
+        assert_eq!(vec![vec![0, 2], vec![1]], materials);
+        let normal = gltf.materials().nth(1).unwrap().normal_texture().unwrap();
+        assert_eq!(1, normal.tex_coord());
+        let sheen = gltf.materials().nth(1).unwrap().as_json().extensions.others.clone();
+        assert_eq!(1, sheen["KHR_materials_sheen"]["sheenColorTexture"]["index"]);
+    }
+}
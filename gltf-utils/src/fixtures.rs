@@ -0,0 +1,138 @@
+use gltf::{self, json, Glb, Gltf};
+use gltf::json::accessor::Type;
+use gltf::json::mesh::Semantic;
+
+use interleave;
+use write::Writer;
+
+/// A generated asset for benchmarks, with its JSON and single binary buffer.
+#[derive(Clone, Debug)]
+pub struct Fixture {
+    /// The JSON of the asset.
+    pub root: json::Root,
+
+    /// The data of buffer 0.
+    pub bin: Vec<u8>,
+}
+
+impl Fixture {
+    /// Generates a square grid mesh with `size` cells along each side.
+    ///
+    /// The single primitive has `(size + 1)²` vertices with `POSITION`,
+    /// `NORMAL`, and `TEXCOORD_0` attributes in separate, tightly packed buffer
+    /// views, and `6 * size²` `u32` indices, approximating the layout most
+    /// exporters write.
+    pub fn grid(size: usize) -> Self {
+        let side = size + 1;
+        let mut positions = Vec::with_capacity(side * side * 3);
+        let mut normals = Vec::with_capacity(side * side * 3);
+        let mut tex_coords = Vec::with_capacity(side * side * 2);
+        for z in 0..side {
+            for x in 0..side {
+                let (u, v) = (x as f32 / size as f32, z as f32 / size as f32);
+                positions.extend_from_slice(&[u - 0.5, 0.0, v - 0.5]);
+                normals.extend_from_slice(&[0.0, 1.0, 0.0]);
+                tex_coords.extend_from_slice(&[u, v]);
+            }
+        }
+        let mut indices = Vec::with_capacity(size * size * 6);
+        for z in 0..size {
+            for x in 0..size {
+                let i = (z * side + x) as u32;
+                let below = i + side as u32;
+                indices.extend_from_slice(&[i, below, i + 1, i + 1, below, below + 1]);
+            }
+        }
+
+        let mut root = json::Root::default();
+        root.buffers.push(json::Buffer::new(0));
+        root.meshes.push(json::Mesh::default());
+        root.meshes[0].primitives.push(json::mesh::Primitive::default());
+        let mut buffers = vec![vec![]];
+        let mut writer = Writer::new(0).bounds();
+        let attributes: [(Semantic, &[f32], Type); 3] = [
+            (Semantic::Positions, &positions, Type::Vec3),
+            (Semantic::Normals, &normals, Type::Vec3),
+            (Semantic::TexCoords(0), &tex_coords, Type::Vec2),
+        ];
+        for &(ref semantic, data, type_) in &attributes {
+            writer.push_attribute(&mut root, &mut buffers, (0, 0), semantic.clone(), data, type_);
+        }
+        let index = Writer::new(0).push(&mut root, &mut buffers, &indices, Type::Scalar);
+        root.meshes[0].primitives[0].indices = Some(index);
+        root.nodes.push(json::Node { mesh: Some(json::Index::new(0)), ..Default::default() });
+        root.scenes.push(json::Scene { nodes: vec![json::Index::new(0)], ..Default::default() });
+        root.buffers[0].byte_length = buffers[0].len() as u32;
+        Fixture { root, bin: buffers.remove(0) }
+    }
+
+    /// Interleaves the vertex attributes into a single buffer view.
+    ///
+    /// See `interleave::interleave`.
+    pub fn interleaved(self) -> Self {
+        let mut buffers = vec![self.bin];
+        let gltf = interleave::interleave(validate(self.root), &mut buffers);
+        let mut root = gltf.into_json();
+        root.buffers[0].byte_length = buffers[0].len() as u32;
+        Fixture { root, bin: buffers.remove(0) }
+    }
+
+    /// Returns the validated `Gltf` wrapper of the asset.
+    pub fn gltf(&self) -> Gltf {
+        validate(self.root.clone())
+    }
+
+    /// Returns the buffer data in the form expected by `Source`.
+    pub fn buffers(&self) -> Vec<Vec<u8>> {
+        vec![self.bin.clone()]
+    }
+
+    /// Returns the asset as `.gltf` JSON text, without buffer data.
+    pub fn to_json(&self) -> Vec<u8> {
+        json::to_vec(&self.root).unwrap()
+    }
+
+    /// Returns the asset as binary glTF, with the buffer in the BIN chunk.
+    pub fn to_glb(&self) -> Vec<u8> {
+        Glb::new(self.to_json().into(), Some(self.bin[..].into()))
+            .and_then(|glb| glb.to_vec())
+            .unwrap()
+    }
+}
+
+fn validate(root: json::Root) -> Gltf {
+    Gltf::from_json(root).validate_completely().unwrap()
+}
+
+/// Returns the `POSITION` accessor of the first primitive of a fixture.
+pub fn positions(gltf: &Gltf) -> gltf::Accessor<'_> {
+    let primitive = gltf.meshes().next().unwrap().primitives().next().unwrap();
+    let index = primitive.get(&gltf::Semantic::Positions).unwrap().index();
+    gltf.accessors().nth(index).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use AccessorIter;
+
+    #[test]
+    fn grid() {
+        let fixture = Fixture::grid(2);
+        let gltf = fixture.gltf();
+        assert_eq!(9, positions(&gltf).count());
+
+        let glb = fixture.to_glb();
+        let glb = Glb::from_slice(&glb).unwrap();
+        assert_eq!(Some(&fixture.bin[..]), glb.bin.as_ref().map(|bin| &bin[..]));
+        assert!(Gltf::from_glb(&glb).unwrap().validate_completely().is_ok());
+
+        let interleaved = fixture.clone().interleaved();
+        let read = |fixture: &Fixture| {
+            let gltf = fixture.gltf();
+            let buffers = fixture.buffers();
+            AccessorIter::<[f32; 3]>::new(positions(&gltf), &buffers).collect::<Vec<_>>()
+        };
+        assert_eq!(read(&fixture), read(&interleaved));
+    }
+}
@@ -0,0 +1,229 @@
+use gltf::{self, json, Gltf};
+use gltf::mesh::Bounds;
+
+/// A perspective camera placement framing a bounding box.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Framing {
+    /// The position of the camera.
+    pub translation: [f32; 3],
+
+    /// The orientation of the camera as an `[x, y, z, w]` quaternion.
+    ///
+    /// glTF cameras look down their local -Z axis with +Y up.
+    pub rotation: [f32; 4],
+
+    /// The vertical field of view in radians.
+    pub yfov: f32,
+
+    /// The ratio of the width to the height of the viewport.
+    pub aspect_ratio: f32,
+
+    /// The suggested distance to the near clipping plane.
+    pub znear: f32,
+
+    /// The suggested distance to the far clipping plane.
+    pub zfar: f32,
+}
+
+impl Framing {
+    /// Returns the JSON camera with the framing's projection.
+    pub fn camera(&self) -> json::Camera {
+        let mut perspective = json::camera::Perspective::new(self.yfov, self.znear);
+        perspective.aspect_ratio = Some(self.aspect_ratio);
+        perspective.zfar = Some(self.zfar);
+        json::Camera::perspective(perspective)
+    }
+}
+
+fn normalize(v: [f32; 3]) -> [f32; 3] {
+    let length = (v[0] * v[0] + v[1] * v[1] + v[2] * v[2]).sqrt();
+    [v[0] / length, v[1] / length, v[2] / length]
+}
+
+fn cross(a: [f32; 3], b: [f32; 3]) -> [f32; 3] {
+    [a[1] * b[2] - a[2] * b[1], a[2] * b[0] - a[0] * b[2], a[0] * b[1] - a[1] * b[0]]
+}
+
+/// Returns the quaternion of a rotation matrix given by its columns.
+fn quaternion(x: [f32; 3], y: [f32; 3], z: [f32; 3]) -> [f32; 4] {
+    let trace = x[0] + y[1] + z[2];
+    let q = if trace > 0.0 {
+        let s = (trace + 1.0).sqrt() * 2.0;
+        [(y[2] - z[1]) / s, (z[0] - x[2]) / s, (x[1] - y[0]) / s, s / 4.0]
+    } else if x[0] > y[1] && x[0] > z[2] {
+        let s = (1.0 + x[0] - y[1] - z[2]).sqrt() * 2.0;
+        [s / 4.0, (y[0] + x[1]) / s, (z[0] + x[2]) / s, (y[2] - z[1]) / s]
+    } else if y[1] > z[2] {
+        let s = (1.0 + y[1] - x[0] - z[2]).sqrt() * 2.0;
+        [(y[0] + x[1]) / s, s / 4.0, (z[1] + y[2]) / s, (z[0] - x[2]) / s]
+    } else {
+        let s = (1.0 + z[2] - x[0] - y[1]).sqrt() * 2.0;
+        [(z[0] + x[2]) / s, (z[1] + y[2]) / s, s / 4.0, (x[1] - y[0]) / s]
+    };
+    let length = q.iter().map(|c| c * c).sum::<f32>().sqrt();
+    [q[0] / length, q[1] / length, q[2] / length, q[3] / length]
+}
+
+/// Places a perspective camera so that the bounding sphere of a box fits the
+/// field of view.
+///
+/// The camera looks along `direction` with +Y up, or +Z up when looking
+/// straight up or down. The near and far planes enclose the bounding sphere.
+///
+/// # Panics
+///
+/// Panics if `direction` is zero.
+pub fn frame(
+    bounds: &Bounds<[f32; 3]>,
+    direction: [f32; 3],
+    yfov: f32,
+    aspect_ratio: f32,
+) -> Framing {
+    let center = [
+        (bounds.min[0] + bounds.max[0]) / 2.0,
+        (bounds.min[1] + bounds.max[1]) / 2.0,
+        (bounds.min[2] + bounds.max[2]) / 2.0,
+    ];
+    let extent = [
+        bounds.max[0] - bounds.min[0],
+        bounds.max[1] - bounds.min[1],
+        bounds.max[2] - bounds.min[2],
+    ];
+    let radius = (extent[0] * extent[0] + extent[1] * extent[1] + extent[2] * extent[2])
+        .sqrt() / 2.0;
+    let radius = if radius > 0.0 { radius } else { 1.0 };
+
+    // The narrower of the vertical and horizontal fields of view decides the
+    // distance at which the sphere fits.
+    let xfov = 2.0 * ((yfov / 2.0).tan() * aspect_ratio).atan();
+    let fov = yfov.min(xfov);
+    let distance = radius / (fov / 2.0).sin();
+
+    let forward = normalize(direction);
+    assert!(forward.iter().all(|c| c.is_finite()), "direction must not be zero");
+    let z = [-forward[0], -forward[1], -forward[2]];
+    let up = if z[1].abs() > 0.999 { [0.0, 0.0, 1.0] } else { [0.0, 1.0, 0.0] };
+    let x = normalize(cross(up, z));
+    let y = cross(z, x);
+
+    Framing {
+        translation: [
+            center[0] + z[0] * distance,
+            center[1] + z[1] * distance,
+            center[2] + z[2] * distance,
+        ],
+        rotation: quaternion(x, y, z),
+        yfov,
+        aspect_ratio,
+        znear: (distance - radius).max(radius * 0.01),
+        zfar: distance + radius,
+    }
+}
+
+/// Returns the world space bounding box of the meshes of a scene, computed
+/// from the `POSITION` bounds of their primitives.
+///
+/// Morph targets and skinning are not taken into account. Returns `None` if
+/// the scene contains no geometry.
+pub fn scene_bounds(scene: &gltf::Scene) -> Option<Bounds<[f32; 3]>> {
+    let mut result: Option<Bounds<[f32; 3]>> = None;
+    for (node, world) in scene.walk() {
+        let mesh = match node.mesh() {
+            Some(mesh) => mesh,
+            None => continue,
+        };
+        let m = world.matrix;
+        for primitive in mesh.primitives() {
+            let bounds = match primitive.position_bounds() {
+                Some(bounds) => bounds,
+                None => continue,
+            };
+            for corner in 0..8 {
+                let p = [
+                    if corner & 1 == 0 { bounds.min[0] } else { bounds.max[0] },
+                    if corner & 2 == 0 { bounds.min[1] } else { bounds.max[1] },
+                    if corner & 4 == 0 { bounds.min[2] } else { bounds.max[2] },
+                ];
+                let mut w = [0.0; 3];
+                for (i, w) in w.iter_mut().enumerate() {
+                    *w = m[0][i] * p[0] + m[1][i] * p[1] + m[2][i] * p[2] + m[3][i];
+                }
+                result = Some(match result {
+                    Some(b) => Bounds {
+                        min: [b.min[0].min(w[0]), b.min[1].min(w[1]), b.min[2].min(w[2])],
+                        max: [b.max[0].max(w[0]), b.max[1].max(w[1]), b.max[2].max(w[2])],
+                    },
+                    None => Bounds { min: w, max: w },
+                });
+            }
+        }
+    }
+    result
+}
+
+/// Adds a camera with the given framing to the root nodes of a scene.
+///
+/// Returns the transformed asset and the index of the camera node.
+pub fn insert(gltf: Gltf, scene: usize, framing: &Framing) -> (Gltf, usize) {
+    let mut root = gltf.into_json();
+    root.cameras.push(framing.camera());
+    root.nodes.push(json::Node {
+        camera: Some(json::Index::new(root.cameras.len() as u32 - 1)),
+        translation: framing.translation,
+        rotation: json::scene::UnitQuaternion(framing.rotation),
+        ..Default::default()
+    });
+    let node = root.nodes.len() - 1;
+    root.scenes[scene].nodes.push(json::Index::new(node as u32));
+    (Gltf::from_json(root).skip_validation(), node)
+}
+
+#[cfg(test)]
+mod tests {
+    use gltf::Gltf;
+
+    use super::*;
+
+    const JSON: &'static str = r#"{
+        "asset": { "version": "2.0" },
+        "buffers": [{ "byteLength": 36 }],
+        "bufferViews": [{ "buffer": 0, "byteLength": 36 }],
+        "accessors": [{
+            "bufferView": 0, "componentType": 5126, "count": 3, "type": "VEC3",
+            "min": [-1, -1, -1], "max": [1, 1, 1]
+        }],
+        "meshes": [{ "primitives": [{ "attributes": { "POSITION": 0 } }] }],
+        "nodes": [{ "mesh": 0, "translation": [10, 0, 0], "scale": [2, 1, 1] }],
+        "scenes": [{ "nodes": [0] }]
+    }"#;
+
+    fn approx(a: &[f32], b: &[f32]) -> bool {
+        a.iter().zip(b).all(|(a, b)| (a - b).abs() < 1e-4)
+    }
+
+    #[test]
+    fn frame_scene() {
+        let gltf = Gltf::from_str(JSON).unwrap().skip_validation();
+        let bounds = scene_bounds(&gltf.scenes().next().unwrap()).unwrap();
+        assert_eq!(Bounds { min: [8.0, -1.0, -1.0], max: [12.0, 1.0, 1.0] }, bounds);
+
+        let yfov = ::std::f32::consts::FRAC_PI_2;
+        let framing = frame(&bounds, [0.0, 0.0, -1.0], yfov, 2.0);
+        let radius = 6.0f32.sqrt();
+        let distance = radius / (yfov / 2.0).sin();
+        assert!(approx(&[10.0, 0.0, distance], &framing.translation));
+        assert!(approx(&[0.0, 0.0, 0.0, 1.0], &framing.rotation));
+        assert!(approx(&[distance - radius, distance + radius], &[framing.znear, framing.zfar]));
+
+        // Looking down +X turns the camera -90 degrees about Y.
+        let framing = frame(&bounds, [1.0, 0.0, 0.0], yfov, 1.0);
+        let half = ::std::f32::consts::FRAC_1_SQRT_2;
+        assert!(approx(&[0.0, -half, 0.0, half], &framing.rotation));
+        assert!(approx(&[10.0 - distance, 0.0, 0.0], &framing.translation));
+
+        let (gltf, node) = insert(gltf, 0, &framing);
+        let node = gltf.nodes().nth(node).unwrap();
+        assert!(node.camera().is_some());
+        assert_eq!(2, gltf.scenes().next().unwrap().nodes().count());
+    }
+}
@@ -0,0 +1,150 @@
+use std::collections::{BTreeMap, HashMap};
+
+use gltf::{json, Gltf};
+
+use dedup::mesh_accessors;
+use interleave::{append, packed};
+
+/// Gives every node its own copy of the mesh it references.
+///
+/// The first node referencing a mesh keeps it, and every other node gets a copy
+/// of the mesh with its own copies of the vertex attribute, index, and morph
+/// target accessors, so that per-instance data can be baked into the vertices.
+/// The packed element data of each copied accessor is appended to the buffer
+/// it was read from, in a new view with the target of the original view.
+///
+/// Returns the transformed asset and the number of meshes created.
+pub fn expand(gltf: Gltf, buffers: &mut [Vec<u8>]) -> (Gltf, usize) {
+    let mut instances: BTreeMap<usize, Vec<usize>> = BTreeMap::new();
+    for node in gltf.nodes() {
+        if let Some(mesh) = node.mesh() {
+            instances.entry(mesh.index()).or_default().push(node.index());
+        }
+    }
+    instances.retain(|_, nodes| nodes.len() > 1);
+
+    let mut elements = HashMap::new();
+    for &mesh in instances.keys() {
+        let mut json = gltf.as_json().meshes[mesh].clone();
+        mesh_accessors(&mut json, &mut |index| {
+            let accessor = gltf.accessors().nth(index.value()).unwrap();
+            elements.entry(index.value()).or_insert_with(|| packed(&accessor, buffers));
+        });
+    }
+
+    let mut root = gltf.into_json();
+    let mut created = 0;
+    for (mesh, nodes) in instances {
+        for node in nodes.into_iter().skip(1) {
+            let mut copies = HashMap::new();
+            let mut copy = root.meshes[mesh].clone();
+            mesh_accessors(&mut copy, &mut |index| {
+                let original = index.value();
+                let accessor = *copies.entry(original).or_insert_with(|| {
+                    copy_accessor(&mut root, buffers, original, &elements[&original])
+                });
+                *index = json::Index::new(accessor as u32);
+            });
+            root.meshes.push(copy);
+            root.nodes[node].mesh = Some(json::Index::new(root.meshes.len() as u32 - 1));
+            created += 1;
+        }
+    }
+    (Gltf::from_json(root).skip_validation(), created)
+}
+
+/// Appends a copy of an accessor with its packed element data, returning the
+/// index of the copy.
+fn copy_accessor(
+    root: &mut json::Root,
+    buffers: &mut [Vec<u8>],
+    original: usize,
+    elements: &[u8],
+) -> usize {
+    let mut accessor = root.accessors[original].clone();
+    let view = match accessor.buffer_view {
+        Some(ref view) => view.value(),
+        // There is no data to copy.
+        None => {
+            root.accessors.push(accessor);
+            return root.accessors.len() - 1;
+        },
+    };
+    let buffer = root.buffer_views[view].buffer.value();
+    let offset = append(root, buffers, buffer, elements);
+    let mut copied_view = json::buffer::View::new(
+        json::Index::new(buffer as u32),
+        offset as u32,
+        elements.len() as u32,
+    );
+    copied_view.target = root.buffer_views[view].target;
+    root.buffer_views.push(copied_view);
+
+    accessor.buffer_view = Some(json::Index::new(root.buffer_views.len() as u32 - 1));
+    accessor.byte_offset = 0;
+    root.accessors.push(accessor);
+    root.accessors.len() - 1
+}
+
+#[cfg(test)]
+mod tests {
+    use byteorder::{LE, ByteOrder};
+    use gltf::{Gltf, Semantic};
+    use gltf::buffer::Target;
+
+    use super::*;
+    use PrimitiveIterators;
+
+    const JSON: &'static str = r#"{
+        "asset": { "version": "2.0" },
+        "buffers": [{ "byteLength": 42 }],
+        "bufferViews": [
+            { "buffer": 0, "byteOffset": 0, "byteLength": 36, "target": 34962 },
+            { "buffer": 0, "byteOffset": 36, "byteLength": 6, "target": 34963 }
+        ],
+        "accessors": [
+            { "bufferView": 0, "componentType": 5126, "count": 3, "type": "VEC3" },
+            { "bufferView": 1, "componentType": 5123, "count": 3, "type": "SCALAR" }
+        ],
+        "meshes": [
+            {
+                "primitives": [
+                    { "attributes": { "POSITION": 0 }, "indices": 1 },
+                    { "attributes": { "POSITION": 0 } }
+                ]
+            }
+        ],
+        "nodes": [{ "mesh": 0 }, { "children": [2] }, { "mesh": 0 }, { "mesh": 0 }]
+    }"#;
+
+    #[test]
+    fn expand_instances() {
+        let mut data = vec![0; 42];
+        LE::write_f32_into(&[0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0], &mut data[0..36]);
+        LE::write_u16_into(&[0, 1, 2], &mut data[36..42]);
+        let mut buffers = vec![data];
+        let gltf = Gltf::from_str(JSON).unwrap().skip_validation();
+        let (gltf, created) = expand(gltf, &mut buffers);
+        assert_eq!(2, created);
+        assert_eq!(3, gltf.meshes().count());
+
+        let meshes: Vec<_> = gltf.nodes().map(|node| node.mesh().map(|m| m.index())).collect();
+        assert_eq!(vec![Some(0), None, Some(1), Some(2)], meshes);
+
+        // Both primitives of a copy share the same copied positions.
+        let copy = gltf.meshes().nth(2).unwrap();
+        let primitives: Vec<_> = copy.primitives().collect();
+        let positions: Vec<_> = primitives
+            .iter()
+            .map(|primitive| primitive.get(&Semantic::Positions).unwrap().index())
+            .collect();
+        assert_eq!(vec![4, 4], positions);
+        assert_eq!(6, gltf.accessors().count());
+        let indices = primitives[0].indices().unwrap();
+        assert_eq!(Some(Target::ElementArrayBuffer), indices.view().unwrap().target());
+
+        let copied: Vec<_> = primitives[0].positions(&buffers).unwrap().collect();
+        assert_eq!(vec![[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]], copied);
+        assert_eq!(buffers[0].len() as u32, gltf.as_json().buffers[0].byte_length);
+    }
+}
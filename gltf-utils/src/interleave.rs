@@ -0,0 +1,205 @@
+use std::collections::HashSet;
+
+use gltf::{self, json, Gltf};
+use gltf::json::validation::Checked;
+
+/// Returns the tightly packed elements of an accessor.
+///
+/// Accessors without a buffer view have all-zero elements.
+pub(crate) fn packed(accessor: &gltf::Accessor, buffers: &[Vec<u8>]) -> Vec<u8> {
+    let size = accessor.size();
+    let view = match accessor.view() {
+        Some(view) => view,
+        None => return vec![0; accessor.count() * size],
+    };
+    let stride = view.stride().unwrap_or(size);
+    let begin = view.offset() + accessor.offset();
+    let data = &buffers[view.buffer().index()];
+    let mut elements = Vec::with_capacity(accessor.count() * size);
+    for i in 0..accessor.count() {
+        let offset = begin + i * stride;
+        elements.extend_from_slice(&data[offset..offset + size]);
+    }
+    elements
+}
+
+/// Returns the buffer holding the data of an accessor, which new data derived
+/// from it is appended to, or the first buffer for accessors without a buffer
+/// view.
+pub(crate) fn accessor_buffer(accessor: &gltf::Accessor) -> usize {
+    accessor.view().map_or(0, |view| view.buffer().index())
+}
+
+/// Appends data to a buffer at a 4-byte aligned offset, returning the offset.
+pub(crate) fn append(
+    root: &mut json::Root,
+    buffers: &mut [Vec<u8>],
+    buffer: usize,
+    bytes: &[u8],
+) -> usize {
+    let data = &mut buffers[buffer];
+    while !data.len().is_multiple_of(4) {
+        data.push(0);
+    }
+    let offset = data.len();
+    data.extend_from_slice(bytes);
+    root.buffers[buffer].byte_length = data.len() as u32;
+    offset
+}
+
+/// Moves every accessor of an interleaved buffer view into its own tightly
+/// packed buffer view.
+///
+/// The data of each accessor is appended to the buffer of its original view
+/// and the new views keep the target of the original view. The interleaved
+/// views are left in place but are no longer referenced by any accessor.
+pub fn deinterleave(gltf: Gltf, buffers: &mut [Vec<u8>]) -> Gltf {
+    let mut moved = vec![];
+    for accessor in gltf.accessors() {
+        let view = match accessor.view() {
+            Some(view) => view,
+            None => continue,
+        };
+        match view.stride() {
+            Some(stride) if stride != accessor.size() => {},
+            _ => continue,
+        }
+        moved.push((accessor.index(), view.index(), packed(&accessor, buffers)));
+    }
+
+    let mut root = gltf.into_json();
+    for (accessor, view, elements) in moved {
+        let buffer = root.buffer_views[view].buffer.value();
+        let offset = append(&mut root, buffers, buffer, &elements);
+        let mut packed_view = json::buffer::View::new(
+            json::Index::new(buffer as u32),
+            offset as u32,
+            elements.len() as u32,
+        );
+        packed_view.target = root.buffer_views[view].target;
+        root.buffer_views.push(packed_view);
+        let accessor = &mut root.accessors[accessor];
+        accessor.buffer_view = Some(json::Index::new(root.buffer_views.len() as u32 - 1));
+        accessor.byte_offset = 0;
+    }
+    Gltf::from_json(root).skip_validation()
+}
+
+/// Interleaves the vertex attributes of every primitive into a single buffer
+/// view per primitive.
+///
+/// Each attribute occupies a 4-byte aligned slot of every vertex. The
+/// interleaved data is appended to the buffer containing the vertex positions
+/// of the primitive. Primitives sharing an attribute accessor with a previously
+/// interleaved primitive are left unchanged, as are morph targets. The
+/// original views are left in place.
+pub fn interleave(gltf: Gltf, buffers: &mut [Vec<u8>]) -> Gltf {
+    let mut interleaved = vec![];
+    let mut seen = HashSet::new();
+    for mesh in gltf.meshes() {
+        for primitive in mesh.primitives() {
+            let accessors: Vec<_> = primitive.attributes().map(|(_, accessor)| accessor).collect();
+            if accessors.is_empty() || accessors.iter().any(|a| seen.contains(&a.index())) {
+                continue;
+            }
+            let count = accessors[0].count();
+            if accessors.iter().any(|a| a.count() != count) {
+                continue;
+            }
+            let mut offsets = vec![];
+            let mut stride = 0;
+            for accessor in &accessors {
+                offsets.push(stride);
+                stride += accessor.size().div_ceil(4) * 4;
+            }
+            let mut data = vec![0; stride * count];
+            for (accessor, &offset) in accessors.iter().zip(&offsets) {
+                let size = accessor.size();
+                let elements = packed(accessor, buffers);
+                for i in 0..count {
+                    let dst = i * stride + offset;
+                    data[dst..dst + size].copy_from_slice(&elements[i * size..(i + 1) * size]);
+                }
+                seen.insert(accessor.index());
+            }
+            let buffer = accessor_buffer(&accessors[0]);
+            let accessors: Vec<_> = accessors.iter().map(|a| a.index()).collect();
+            interleaved.push((buffer, stride, data, accessors, offsets));
+        }
+    }
+
+    let mut root = gltf.into_json();
+    for (buffer, stride, data, accessors, offsets) in interleaved {
+        let offset = append(&mut root, buffers, buffer, &data);
+        let mut view = json::buffer::View::new(
+            json::Index::new(buffer as u32),
+            offset as u32,
+            data.len() as u32,
+        );
+        view.byte_stride = Some(json::buffer::ByteStride(stride as u32));
+        view.target = Some(Checked::Valid(json::buffer::Target::ArrayBuffer));
+        root.buffer_views.push(view);
+        let view = root.buffer_views.len() as u32 - 1;
+        for (accessor, offset) in accessors.into_iter().zip(offsets) {
+            root.accessors[accessor].buffer_view = Some(json::Index::new(view));
+            root.accessors[accessor].byte_offset = offset as u32;
+        }
+    }
+    Gltf::from_json(root).skip_validation()
+}
+
+#[cfg(test)]
+mod tests {
+    use byteorder::{LE, ByteOrder};
+    use gltf::Gltf;
+
+    use super::*;
+    use PrimitiveIterators;
+
+    const JSON: &'static str = r#"{
+        "asset": { "version": "2.0" },
+        "buffers": [{ "byteLength": 40 }],
+        "bufferViews": [
+            { "buffer": 0, "byteOffset": 0, "byteLength": 40, "byteStride": 20 }
+        ],
+        "accessors": [
+            { "bufferView": 0, "byteOffset": 0, "componentType": 5126, "count": 2, "type": "VEC3" },
+            { "bufferView": 0, "byteOffset": 12, "componentType": 5126, "count": 2, "type": "VEC2" }
+        ],
+        "meshes": [{
+            "primitives": [{ "attributes": { "POSITION": 0, "TEXCOORD_0": 1 } }]
+        }]
+    }"#;
+
+    fn data() -> Vec<u8> {
+        let mut data = vec![0; 40];
+        LE::write_f32_into(&[1.0, 2.0, 3.0, 0.25, 0.5], &mut data[0..20]);
+        LE::write_f32_into(&[4.0, 5.0, 6.0, 0.75, 1.0], &mut data[20..40]);
+        data
+    }
+
+    fn check(gltf: &Gltf, buffers: &Vec<Vec<u8>>) {
+        let mesh = gltf.meshes().next().unwrap();
+        let primitive = mesh.primitives().next().unwrap();
+        let positions: Vec<_> = primitive.positions(buffers).unwrap().collect();
+        let tex_coords: Vec<_> = primitive.tex_coords_f32(0, buffers).unwrap().collect();
+        assert_eq!(vec![[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]], positions);
+        assert_eq!(vec![[0.25, 0.5], [0.75, 1.0]], tex_coords);
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut buffers = vec![data()];
+        let gltf = Gltf::from_str(JSON).unwrap().skip_validation();
+        let gltf = deinterleave(gltf, &mut buffers);
+        assert!(gltf.accessors().all(|a| a.view().unwrap().stride().is_none()));
+        assert_eq!(40 + 24 + 16, buffers[0].len());
+        check(&gltf, &buffers);
+
+        let gltf = interleave(gltf, &mut buffers);
+        let views: Vec<_> = gltf.accessors().map(|a| a.view().unwrap().index()).collect();
+        assert_eq!(vec![3, 3], views);
+        assert_eq!(Some(20), gltf.views().nth(3).unwrap().stride());
+        check(&gltf, &buffers);
+    }
+}
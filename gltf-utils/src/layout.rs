@@ -0,0 +1,302 @@
+use gltf::json;
+use gltf::json::buffer::Target;
+use gltf::json::validation::Checked;
+
+/// Where planned data is placed.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Placement {
+    /// The index of the buffer view holding the data.
+    pub view: u32,
+
+    /// The byte offset of the data within the buffer view.
+    pub offset: u32,
+}
+
+impl Placement {
+    /// Points an accessor at the placed data.
+    pub fn apply(&self, accessor: &mut json::Accessor) {
+        accessor.buffer_view = Some(json::Index::new(self.view));
+        accessor.byte_offset = self.offset;
+    }
+}
+
+/// A buffer view being planned.
+#[derive(Clone, Debug)]
+struct PlannedView {
+    /// The target of the view.
+    target: Option<Target>,
+
+    /// The byte stride of interleaved views.
+    stride: Option<usize>,
+
+    /// The largest alignment of any data in the view.
+    alignment: usize,
+
+    /// The data of the view.
+    data: Vec<u8>,
+}
+
+/// Plans the buffer views and accessor offsets of data appended to a buffer.
+///
+/// Data is grouped into one tightly packed view per target unless grouping
+/// is disabled, and interleaved vertex attributes get a strided view of their
+/// own. Every view starts at a multiple of four bytes, or of the largest
+/// alignment of the data it holds, so the output is valid whatever order
+/// data is added in. Placements are final as soon as they are returned, so
+/// accessors can be created while planning, but no other views may be added
+/// to the root until `finish` is called.
+///
+/// `write::Writer` places each accessor it writes with a planner of its own.
+///
+/// # Examples
+///
+/// ```rust
+/// # extern crate gltf;
+/// # extern crate gltf_utils;
+/// # fn main() {
+/// use gltf::json;
+/// use gltf_utils::layout::LayoutPlanner;
+///
+/// let mut root = json::Root::default();
+/// root.buffers.push(json::Buffer::new(0));
+/// let mut buffers = vec![vec![]];
+///
+/// let mut planner = LayoutPlanner::new(&root, 0);
+/// let target = Some(json::buffer::Target::ElementArrayBuffer);
+/// // Three `u16` indices followed by three `u32` indices.
+/// let first = planner.add(&[0, 0, 1, 0, 2, 0], 2, target);
+/// let second = planner.add(&[0, 0, 0, 0, 1, 0, 0, 0, 2, 0, 0, 0], 4, target);
+/// planner.finish(&mut root, &mut buffers);
+///
+/// assert_eq!(first.view, second.view);
+/// assert_eq!(8, second.offset);
+/// assert_eq!(1, root.buffer_views.len());
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct LayoutPlanner {
+    /// The index of the buffer to append data to.
+    buffer: usize,
+
+    /// The index the first planned view will have.
+    first_view: usize,
+
+    /// Whether data of the same target shares a view.
+    grouped: bool,
+
+    /// The planned views.
+    views: Vec<PlannedView>,
+}
+
+impl LayoutPlanner {
+    /// Creates a planner appending views to the given buffer of `root`.
+    pub fn new(root: &json::Root, buffer: usize) -> Self {
+        LayoutPlanner {
+            buffer,
+            first_view: root.buffer_views.len(),
+            grouped: true,
+            views: vec![],
+        }
+    }
+
+    /// Sets whether data of the same target shares a buffer view, which is
+    /// the default.
+    ///
+    /// Separate views cost a few bytes of JSON each but let data be replaced
+    /// or streamed one view at a time.
+    pub fn grouped(mut self, grouped: bool) -> Self {
+        self.grouped = grouped;
+        self
+    }
+
+    /// Plans tightly packed data, e.g. the elements of an accessor, whose
+    /// offset must be a multiple of `alignment` bytes.
+    ///
+    /// The alignment of accessor data must be a multiple of the size of its
+    /// component type.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `alignment` is zero.
+    pub fn add(&mut self, data: &[u8], alignment: usize, target: Option<Target>) -> Placement {
+        assert!(alignment > 0, "zero alignment");
+        let existing = if self.grouped {
+            self.views.iter().position(|view| view.stride.is_none() && view.target == target)
+        } else {
+            None
+        };
+        let index = existing.unwrap_or_else(|| self.push_view(target, None));
+        let view = &mut self.views[index];
+        let offset = align(view.data.len(), alignment);
+        view.data.resize(offset, 0);
+        view.data.extend_from_slice(data);
+        view.alignment = view.alignment.max(alignment);
+        Placement {
+            view: (self.first_view + index) as u32,
+            offset: offset as u32,
+        }
+    }
+
+    /// Plans interleaved vertex attributes in a strided view of their own,
+    /// returning a placement per attribute.
+    ///
+    /// Each attribute is given as its tightly packed elements and the size of
+    /// an element in bytes. Every element starts at a multiple of four bytes,
+    /// as the specification requires for vertex attributes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the attributes have different element counts, or if the
+    /// resulting stride exceeds the 252 bytes glTF allows.
+    pub fn add_interleaved(&mut self, attributes: &[(&[u8], usize)]) -> Vec<Placement> {
+        let count = attributes.first().map_or(0, |&(data, size)| data.len() / size.max(1));
+        let mut offsets = vec![];
+        let mut stride = 0;
+        for &(data, size) in attributes {
+            assert_eq!(count * size, data.len(), "attribute count mismatch");
+            offsets.push(stride);
+            stride += align(size, 4);
+        }
+        assert!(stride <= 252, "stride exceeds 252 bytes");
+
+        let index = self.push_view(Some(Target::ArrayBuffer), Some(stride));
+        let view = &mut self.views[index];
+        view.data.resize(stride * count, 0);
+        for (&(data, size), &offset) in attributes.iter().zip(&offsets) {
+            for (i, element) in data.chunks(size.max(1)).enumerate() {
+                let start = i * stride + offset;
+                view.data[start..start + size].copy_from_slice(element);
+            }
+        }
+        offsets
+            .into_iter()
+            .map(|offset| Placement {
+                view: (self.first_view + index) as u32,
+                offset: offset as u32,
+            })
+            .collect()
+    }
+
+    /// Appends the planned views to the buffer and `root`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if views were added to `root` since the planner was created.
+    pub fn finish(self, root: &mut json::Root, buffers: &mut [Vec<u8>]) {
+        assert_eq!(self.first_view, root.buffer_views.len(), "views added while planning");
+        let bytes = &mut buffers[self.buffer];
+        for planned in self.views {
+            let offset = align(bytes.len(), planned.alignment);
+            bytes.resize(offset, 0);
+            bytes.extend_from_slice(&planned.data);
+            let mut view = json::buffer::View::new(
+                json::Index::new(self.buffer as u32),
+                offset as u32,
+                planned.data.len() as u32,
+            );
+            view.byte_stride = planned.stride.map(|stride| json::buffer::ByteStride(stride as u32));
+            view.target = planned.target.map(Checked::Valid);
+            root.buffer_views.push(view);
+        }
+        root.buffers[self.buffer].byte_length = bytes.len() as u32;
+    }
+
+    /// Adds an empty view, returning its planner index.
+    fn push_view(&mut self, target: Option<Target>, stride: Option<usize>) -> usize {
+        self.views.push(PlannedView {
+            target,
+            stride,
+            alignment: 4,
+            data: vec![],
+        });
+        self.views.len() - 1
+    }
+}
+
+/// Rounds `x` up to a multiple of `alignment`.
+fn align(x: usize, alignment: usize) -> usize {
+    x.div_ceil(alignment) * alignment
+}
+
+#[cfg(test)]
+mod tests {
+    use byteorder::{LE, ByteOrder};
+    use gltf::Gltf;
+
+    use super::*;
+    use PrimitiveIterators;
+
+    fn root() -> json::Root {
+        let mut root = json::Root::default();
+        root.buffers.push(json::Buffer::new(0));
+        root
+    }
+
+    #[test]
+    fn grouping() {
+        let mut root = root();
+        let mut buffers = vec![vec![1]];
+        let mut planner = LayoutPlanner::new(&root, 0);
+        let a = planner.add(&[1, 2, 3], 1, None);
+        let b = planner.add(&[4; 8], 8, None);
+        let c = planner.add(&[5; 2], 2, Some(Target::ElementArrayBuffer));
+        planner.finish(&mut root, &mut buffers);
+
+        assert_eq!(Placement { view: 0, offset: 0 }, a);
+        assert_eq!(Placement { view: 0, offset: 8 }, b);
+        assert_eq!(Placement { view: 1, offset: 0 }, c);
+        assert_eq!(8, root.buffer_views[0].byte_offset);
+        assert_eq!(16, root.buffer_views[0].byte_length);
+        assert_eq!(24, root.buffer_views[1].byte_offset);
+        assert_eq!(26, root.buffers[0].byte_length);
+        assert_eq!(&[5, 5], &buffers[0][24..]);
+
+        let mut root = self::root();
+        let mut planner = LayoutPlanner::new(&root, 0).grouped(false);
+        planner.add(&[1], 1, None);
+        planner.add(&[2], 1, None);
+        planner.finish(&mut root, &mut [vec![]]);
+        assert_eq!(2, root.buffer_views.len());
+    }
+
+    #[test]
+    fn interleaved() {
+        let mut root: json::Root = json::from_str(r#"{
+            "asset": { "version": "2.0" },
+            "accessors": [
+                { "bufferView": 0, "componentType": 5126, "count": 2, "type": "VEC3" },
+                { "bufferView": 0, "componentType": 5121, "count": 2, "type": "SCALAR" }
+            ],
+            "meshes": [{ "primitives": [{ "attributes": { "POSITION": 0, "_ID": 1 } }] }]
+        }"#).unwrap();
+        root.buffers.push(json::Buffer::new(0));
+        let mut positions = vec![0; 24];
+        LE::write_f32_into(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0], &mut positions);
+
+        let mut buffers = vec![vec![]];
+        let mut planner = LayoutPlanner::new(&root, 0);
+        let placements = planner.add_interleaved(&[(&positions, 12), (&[7, 8], 1)]);
+        placements[0].apply(&mut root.accessors[0]);
+        placements[1].apply(&mut root.accessors[1]);
+        planner.finish(&mut root, &mut buffers);
+
+        assert_eq!(Some(16), root.buffer_views[0].byte_stride.map(|stride| stride.0 as usize));
+        assert_eq!(32, buffers[0].len());
+        assert_eq!(&[7, 0, 0, 0], &buffers[0][12..16]);
+        let gltf = Gltf::from_json(root).skip_validation();
+        let mesh = gltf.meshes().next().unwrap();
+        let primitive = mesh.primitives().next().unwrap();
+        let positions: Vec<_> = primitive.positions(&buffers).unwrap().collect();
+        assert_eq!(vec![[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]], positions);
+    }
+
+    #[test]
+    #[should_panic(expected = "views added while planning")]
+    fn stale_plan() {
+        let mut root = root();
+        let mut planner = LayoutPlanner::new(&root, 0);
+        planner.add(&[1], 1, None);
+        root.buffer_views.push(json::buffer::View::new(json::Index::new(0), 0, 0));
+        planner.finish(&mut root, &mut [vec![]]);
+    }
+}
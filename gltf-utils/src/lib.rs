@@ -1,15 +1,86 @@
 #![allow(unknown_lints)]
+#![forbid(unsafe_code)]
 #![allow(cast_lossless)]
 
 extern crate byteorder;
 extern crate gltf;
+#[cfg(feature = "zstd")]
+extern crate ruzstd;
 
-use std::{fmt, marker};
+use std::{error, fmt, marker, ops};
 use std::mem::size_of;
 
 use byteorder::{LE, ByteOrder};
 
 use gltf::accessor::{DataType, Dimensions};
+use gltf::texture::WrappingMode;
+
+/// Animation keyframe reduction, rotation fixes, and baking.
+pub mod animation;
+
+/// Splitting and merging of primitives by material and vertex count.
+pub mod batching;
+
+/// Distribution of buffer data across binary files.
+pub mod bins;
+
+/// Compressibility analysis of buffer views.
+pub mod compression;
+
+/// Baking of debug data into vertex colors.
+pub mod debug;
+
+/// Deduplication of accessor, image, and texture data.
+pub mod dedup;
+
+/// Generated assets for benchmarks.
+#[cfg(feature = "fixtures")]
+pub mod fixtures;
+
+/// Camera placement for framing geometry.
+pub mod framing;
+
+/// Expansion of instanced meshes into unique copies.
+pub mod instancing;
+
+/// Conversion between interleaved and deinterleaved vertex data.
+pub mod interleave;
+
+/// Planning of buffer view layouts for export.
+pub mod layout;
+
+/// Geometry and content health checks.
+pub mod lint;
+
+/// Reading of feature IDs and per-feature metadata.
+pub mod metadata;
+
+/// Detection and handling of nodes outside every scene.
+pub mod orphans;
+
+/// Buffer layouts for progressive display.
+pub mod progressive;
+
+/// Removal of unreferenced objects and compaction of buffer data.
+mod prune;
+
+/// JSON minification and asset size reports.
+pub mod size;
+
+/// Skin joint count and vertex influence analysis.
+pub mod skinning;
+
+/// Mipmap generation and KTX2 encoding.
+pub mod texture;
+
+/// Triangle winding consistency fixes.
+pub mod winding;
+
+/// Generation of material and texture variants from a base asset.
+pub mod variants;
+
+/// Accessor authoring with optional sparse storage, and in-place write-back.
+pub mod write;
 
 /// Helper trait for denormalizing integer types.
 ///
@@ -49,6 +120,85 @@ pub trait Source: fmt::Debug {
     fn source_buffer(&self, buffer: &gltf::Buffer) -> &[u8];
 }
 
+impl Source for Vec<Vec<u8>> {
+    fn source_buffer(&self, buffer: &gltf::Buffer) -> &[u8] {
+        &self[buffer.index()]
+    }
+}
+
+/// A `Source` backed by a closure returning the data of each buffer.
+///
+/// This lets engines that already own buffer memory, such as staging arenas,
+/// decode attributes in place instead of copying the data into one `Vec` per
+/// buffer.
+///
+/// # Panics
+///
+/// Readers panic if the closure returns `None` for a buffer they read.
+///
+/// # Examples
+///
+/// ```rust
+/// # extern crate gltf;
+/// # extern crate gltf_utils;
+/// # fn run() -> Result<(), Box<std::error::Error>> {
+/// use gltf_utils::{FnSource, PrimitiveIterators};
+///
+/// let gltf = gltf::Gltf::from_slice(&std::fs::read("../examples/Box.gltf")?)?
+///     .validate_minimally()?;
+///
+/// // Buffer data owned elsewhere, e.g. a single arena holding every buffer.
+/// let mut arena = vec![0; 16];
+/// arena.extend(std::fs::read("../examples/Box0.bin")?);
+/// let ranges = vec![(16, arena.len())];
+///
+/// let source = FnSource::new(|buffer: gltf::Buffer| {
+///     let (begin, end) = *ranges.get(buffer.index())?;
+///     arena.get(begin..end)
+/// });
+/// let mesh = gltf.meshes().next().unwrap();
+/// let primitive = mesh.primitives().next().unwrap();
+/// assert_eq!(24, primitive.positions(&source).unwrap().count());
+/// # Ok(())
+/// # }
+/// # fn main() {
+/// #     run().unwrap();
+/// # }
+/// ```
+pub struct FnSource<'s, F> {
+    /// Returns the data of a buffer.
+    source: F,
+
+    /// The lifetime of the returned data.
+    _phantom: marker::PhantomData<&'s [u8]>,
+}
+
+impl<'s, F> FnSource<'s, F>
+    where F: Fn(gltf::Buffer) -> Option<&'s [u8]>
+{
+    /// Creates a source from a closure returning the data of a buffer.
+    pub fn new(source: F) -> Self {
+        FnSource { source, _phantom: marker::PhantomData }
+    }
+}
+
+impl<'s, F> fmt::Debug for FnSource<'s, F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "FnSource")
+    }
+}
+
+impl<'s, F> Source for FnSource<'s, F>
+    where F: Fn(gltf::Buffer) -> Option<&'s [u8]>
+{
+    fn source_buffer(&self, buffer: &gltf::Buffer) -> &[u8] {
+        match (self.source)(buffer.clone()) {
+            Some(data) => data,
+            None => panic!("no data for buffer {}", buffer.index()),
+        }
+    }
+}
+
 /// Extra methods for working with `gltf::Primitive`.
 pub trait PrimitiveIterators<'a> {
     /// Visits the vertex positions of a primitive.
@@ -60,6 +210,13 @@ pub trait PrimitiveIterators<'a> {
     /// Visits the vertex tangents of a primitive.
     fn tangents<'s, S: Source>(&'a self, source: &'s S) -> Option<Tangents<'s>>;
 
+    /// Visits the vertex texture co-ordinates of a primitive in their stored
+    /// format.
+    ///
+    /// Use `TexCoords::into_f32` to convert co-ordinates of any format to
+    /// `f32`.
+    fn tex_coords<'s, S: Source>(&'a self, set: u32, source: &'s S) -> Option<TexCoords<'s>>;
+
     /// Visits the vertex texture co-ordinates of a primitive.
     fn tex_coords_f32<'s, S: Source>(
         &'a self,
@@ -67,6 +224,12 @@ pub trait PrimitiveIterators<'a> {
         source: &'s S,
     ) -> Option<TexCoordsF32<'s>>;
 
+    /// Visits the vertex colors of a primitive in their stored format.
+    ///
+    /// Use `Colors::into_rgba_f32` to convert colors of any format to
+    /// normalized RGBA.
+    fn colors<'s, S: Source>(&'a self, set: u32, source: &'s S) -> Option<Colors<'s>>;
+
     /// Visits the vertex colors of a primitive.
     fn colors_rgba_f32<'s, S: Source>(
         &'a self,
@@ -78,6 +241,16 @@ pub trait PrimitiveIterators<'a> {
     /// Visits the vertex draw sequence of a primitive.
     fn indices_u32<'s, S: Source>(&'a self, source: &'s S) -> Option<IndicesU32<'s>>;
 
+    /// Visits the joint indices of the primitive in their stored format.
+    ///
+    /// Use `Joints::into_u16x4` to convert joints of any format to `u16`.
+    fn joints<'s, S: Source>(&'a self, set: u32, source: &'s S) -> Option<Joints<'s>>;
+
+    /// Visits the joint weights of the primitive in their stored format.
+    ///
+    /// Use `Weights::into_f32x4` to convert weights of any format to `f32`.
+    fn weights<'s, S: Source>(&'a self, set: u32, source: &'s S) -> Option<Weights<'s>>;
+
     /// Visits the joint indices of the primitive.
     fn joints_u16<'s, S: Source>(
         &'a self,
@@ -91,6 +264,97 @@ pub trait PrimitiveIterators<'a> {
         set: u32,
         source: &'s S
     ) -> Option<WeightsF32<'s>>;
+
+    /// Verifies that the vertex attributes, indices, and morph target
+    /// displacements of the primitive lie within their buffer views and
+    /// buffers.
+    ///
+    /// The readers above skip this check for speed and panic on malformed
+    /// data. Prefer the `try_` readers below for untrusted data, which check
+    /// each accessor as it is opened.
+    fn check_bounds<S: Source>(&self, source: &S) -> Result<(), BoundsError>;
+
+    /// Visits the vertex positions of a primitive after checking them against
+    /// the bounds of their buffer view and buffer.
+    fn try_positions<'s, S: Source>(
+        &'a self,
+        source: &'s S,
+    ) -> Result<Option<Positions<'s>>, BoundsError>;
+
+    /// Checked version of `normals`.
+    fn try_normals<'s, S: Source>(
+        &'a self,
+        source: &'s S,
+    ) -> Result<Option<Normals<'s>>, BoundsError>;
+
+    /// Checked version of `tangents`.
+    fn try_tangents<'s, S: Source>(
+        &'a self,
+        source: &'s S,
+    ) -> Result<Option<Tangents<'s>>, BoundsError>;
+
+    /// Checked version of `tex_coords`.
+    fn try_tex_coords<'s, S: Source>(
+        &'a self,
+        set: u32,
+        source: &'s S,
+    ) -> Result<Option<TexCoords<'s>>, BoundsError>;
+
+    /// Checked version of `tex_coords_f32`.
+    fn try_tex_coords_f32<'s, S: Source>(
+        &'a self,
+        set: u32,
+        source: &'s S,
+    ) -> Result<Option<TexCoordsF32<'s>>, BoundsError>;
+
+    /// Checked version of `colors`.
+    fn try_colors<'s, S: Source>(
+        &'a self,
+        set: u32,
+        source: &'s S,
+    ) -> Result<Option<Colors<'s>>, BoundsError>;
+
+    /// Checked version of `colors_rgba_f32`.
+    fn try_colors_rgba_f32<'s, S: Source>(
+        &'a self,
+        set: u32,
+        default_alpha: f32,
+        source: &'s S,
+    ) -> Result<Option<ColorsRgbaF32<'s>>, BoundsError>;
+
+    /// Checked version of `indices_u32`.
+    fn try_indices_u32<'s, S: Source>(
+        &'a self,
+        source: &'s S,
+    ) -> Result<Option<IndicesU32<'s>>, BoundsError>;
+
+    /// Checked version of `joints`.
+    fn try_joints<'s, S: Source>(
+        &'a self,
+        set: u32,
+        source: &'s S,
+    ) -> Result<Option<Joints<'s>>, BoundsError>;
+
+    /// Checked version of `weights`.
+    fn try_weights<'s, S: Source>(
+        &'a self,
+        set: u32,
+        source: &'s S,
+    ) -> Result<Option<Weights<'s>>, BoundsError>;
+
+    /// Checked version of `joints_u16`.
+    fn try_joints_u16<'s, S: Source>(
+        &'a self,
+        set: u32,
+        source: &'s S,
+    ) -> Result<Option<JointsU16<'s>>, BoundsError>;
+
+    /// Checked version of `weights_f32`.
+    fn try_weights_f32<'s, S: Source>(
+        &'a self,
+        set: u32,
+        source: &'s S,
+    ) -> Result<Option<WeightsF32<'s>>, BoundsError>;
 }
 
 impl<'a> PrimitiveIterators<'a> for gltf::Primitive<'a> {
@@ -109,11 +373,19 @@ impl<'a> PrimitiveIterators<'a> for gltf::Primitive<'a> {
             .map(|accessor| Tangents(AccessorIter::new(accessor, source)))
     }
 
+    fn tex_coords<'s, S: Source>(&self, set: u32, source: &'s S) -> Option<TexCoords<'s>> {
+        self.get(&gltf::Semantic::TexCoords(set)).map(|accessor| TexCoords::new(accessor, source))
+    }
+
     fn tex_coords_f32<'s, S: Source>(&self, set: u32, source: &'s S) -> Option<TexCoordsF32<'s>> {
         self.get(&gltf::Semantic::TexCoords(set))
             .map(|accessor| TexCoordsF32(TexCoords::new(accessor, source)))
     }
 
+    fn colors<'s, S: Source>(&self, set: u32, source: &'s S) -> Option<Colors<'s>> {
+        self.get(&gltf::Semantic::Colors(set)).map(|accessor| Colors::new(accessor, source))
+    }
+
     fn colors_rgba_f32<'s, S: Source>(
         &self,
         set: u32,
@@ -133,6 +405,14 @@ impl<'a> PrimitiveIterators<'a> for gltf::Primitive<'a> {
         self.indices().map(|accessor| IndicesU32(Indices::new(accessor, source)))
     }
 
+    fn joints<'s, S: Source>(&self, set: u32, source: &'s S) -> Option<Joints<'s>> {
+        self.get(&gltf::Semantic::Joints(set)).map(|accessor| Joints::new(accessor, source))
+    }
+
+    fn weights<'s, S: Source>(&self, set: u32, source: &'s S) -> Option<Weights<'s>> {
+        self.get(&gltf::Semantic::Weights(set)).map(|accessor| Weights::new(accessor, source))
+    }
+
     fn joints_u16<'s, S: Source>(&self, set: u32, source: &'s S) -> Option<JointsU16<'s>> {
         self.get(&gltf::Semantic::Joints(set))
             .map(|accessor| JointsU16(Joints::new(accessor, source)))
@@ -142,6 +422,209 @@ impl<'a> PrimitiveIterators<'a> for gltf::Primitive<'a> {
         self.get(&gltf::Semantic::Weights(set))
             .map(|accessor| WeightsF32(Weights::new(accessor, source)))
     }
+
+    fn check_bounds<S: Source>(&self, source: &S) -> Result<(), BoundsError> {
+        for (_, accessor) in self.attributes() {
+            accessor.check_bounds(source)?;
+        }
+        if let Some(accessor) = self.indices() {
+            accessor.check_bounds(source)?;
+        }
+        for target in self.morph_targets() {
+            let accessors = vec![target.positions(), target.normals(), target.tangents()];
+            for accessor in accessors.into_iter().flatten() {
+                accessor.check_bounds(source)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn try_positions<'s, S: Source>(
+        &self,
+        source: &'s S,
+    ) -> Result<Option<Positions<'s>>, BoundsError> {
+        self.get(&gltf::Semantic::Positions)
+            .map(|accessor| AccessorIter::checked(accessor, source).map(Positions))
+            .transpose()
+    }
+
+    fn try_normals<'s, S: Source>(
+        &self,
+        source: &'s S,
+    ) -> Result<Option<Normals<'s>>, BoundsError> {
+        self.get(&gltf::Semantic::Normals)
+            .map(|accessor| AccessorIter::checked(accessor, source).map(Normals))
+            .transpose()
+    }
+
+    fn try_tangents<'s, S: Source>(
+        &self,
+        source: &'s S,
+    ) -> Result<Option<Tangents<'s>>, BoundsError> {
+        self.get(&gltf::Semantic::Tangents)
+            .map(|accessor| AccessorIter::checked(accessor, source).map(Tangents))
+            .transpose()
+    }
+
+    fn try_tex_coords<'s, S: Source>(
+        &self,
+        set: u32,
+        source: &'s S,
+    ) -> Result<Option<TexCoords<'s>>, BoundsError> {
+        self.get(&gltf::Semantic::TexCoords(set))
+            .map(|accessor| TexCoords::checked(accessor, source))
+            .transpose()
+    }
+
+    fn try_tex_coords_f32<'s, S: Source>(
+        &self,
+        set: u32,
+        source: &'s S,
+    ) -> Result<Option<TexCoordsF32<'s>>, BoundsError> {
+        self.try_tex_coords(set, source).map(|iter| iter.map(TexCoordsF32))
+    }
+
+    fn try_colors<'s, S: Source>(
+        &self,
+        set: u32,
+        source: &'s S,
+    ) -> Result<Option<Colors<'s>>, BoundsError> {
+        self.get(&gltf::Semantic::Colors(set))
+            .map(|accessor| Colors::checked(accessor, source))
+            .transpose()
+    }
+
+    fn try_colors_rgba_f32<'s, S: Source>(
+        &self,
+        set: u32,
+        default_alpha: f32,
+        source: &'s S,
+    ) -> Result<Option<ColorsRgbaF32<'s>>, BoundsError> {
+        self.try_colors(set, source)
+            .map(|iter| iter.map(|iter| ColorsRgbaF32 { iter, default_alpha }))
+    }
+
+    fn try_indices_u32<'s, S: Source>(
+        &self,
+        source: &'s S,
+    ) -> Result<Option<IndicesU32<'s>>, BoundsError> {
+        self.indices()
+            .map(|accessor| Indices::checked(accessor, source).map(IndicesU32))
+            .transpose()
+    }
+
+    fn try_joints<'s, S: Source>(
+        &self,
+        set: u32,
+        source: &'s S,
+    ) -> Result<Option<Joints<'s>>, BoundsError> {
+        self.get(&gltf::Semantic::Joints(set))
+            .map(|accessor| Joints::checked(accessor, source))
+            .transpose()
+    }
+
+    fn try_weights<'s, S: Source>(
+        &self,
+        set: u32,
+        source: &'s S,
+    ) -> Result<Option<Weights<'s>>, BoundsError> {
+        self.get(&gltf::Semantic::Weights(set))
+            .map(|accessor| Weights::checked(accessor, source))
+            .transpose()
+    }
+
+    fn try_joints_u16<'s, S: Source>(
+        &self,
+        set: u32,
+        source: &'s S,
+    ) -> Result<Option<JointsU16<'s>>, BoundsError> {
+        self.try_joints(set, source).map(|iter| iter.map(JointsU16))
+    }
+
+    fn try_weights_f32<'s, S: Source>(
+        &self,
+        set: u32,
+        source: &'s S,
+    ) -> Result<Option<WeightsF32<'s>>, BoundsError> {
+        self.try_weights(set, source).map(|iter| iter.map(WeightsF32))
+    }
+}
+
+/// Extra methods for working with `gltf::mesh::MorphTarget`.
+pub trait MorphTargetIterators<'a> {
+    /// Visits the XYZ vertex position displacements of a morph target.
+    fn position_displacements<'s, S: Source>(&'a self, source: &'s S) -> Option<Positions<'s>>;
+
+    /// Visits the XYZ vertex normal displacements of a morph target.
+    fn normal_displacements<'s, S: Source>(&'a self, source: &'s S) -> Option<Normals<'s>>;
+
+    /// Visits the XYZ vertex tangent displacements of a morph target.
+    fn tangent_displacements<'s, S: Source>(
+        &'a self,
+        source: &'s S,
+    ) -> Option<TangentDisplacements<'s>>;
+
+    /// Checked version of `position_displacements`.
+    fn try_position_displacements<'s, S: Source>(
+        &'a self,
+        source: &'s S,
+    ) -> Result<Option<Positions<'s>>, BoundsError>;
+
+    /// Checked version of `normal_displacements`.
+    fn try_normal_displacements<'s, S: Source>(
+        &'a self,
+        source: &'s S,
+    ) -> Result<Option<Normals<'s>>, BoundsError>;
+
+    /// Checked version of `tangent_displacements`.
+    fn try_tangent_displacements<'s, S: Source>(
+        &'a self,
+        source: &'s S,
+    ) -> Result<Option<TangentDisplacements<'s>>, BoundsError>;
+}
+
+impl<'a> MorphTargetIterators<'a> for gltf::mesh::MorphTarget<'a> {
+    fn position_displacements<'s, S: Source>(&self, source: &'s S) -> Option<Positions<'s>> {
+        self.positions().map(|accessor| Positions(AccessorIter::new(accessor, source)))
+    }
+
+    fn normal_displacements<'s, S: Source>(&self, source: &'s S) -> Option<Normals<'s>> {
+        self.normals().map(|accessor| Normals(AccessorIter::new(accessor, source)))
+    }
+
+    fn tangent_displacements<'s, S: Source>(
+        &self,
+        source: &'s S,
+    ) -> Option<TangentDisplacements<'s>> {
+        self.tangents().map(|accessor| TangentDisplacements(AccessorIter::new(accessor, source)))
+    }
+
+    fn try_position_displacements<'s, S: Source>(
+        &self,
+        source: &'s S,
+    ) -> Result<Option<Positions<'s>>, BoundsError> {
+        self.positions()
+            .map(|accessor| AccessorIter::checked(accessor, source).map(Positions))
+            .transpose()
+    }
+
+    fn try_normal_displacements<'s, S: Source>(
+        &self,
+        source: &'s S,
+    ) -> Result<Option<Normals<'s>>, BoundsError> {
+        self.normals()
+            .map(|accessor| AccessorIter::checked(accessor, source).map(Normals))
+            .transpose()
+    }
+
+    fn try_tangent_displacements<'s, S: Source>(
+        &self,
+        source: &'s S,
+    ) -> Result<Option<TangentDisplacements<'s>>, BoundsError> {
+        self.tangents()
+            .map(|accessor| AccessorIter::checked(accessor, source).map(TangentDisplacements))
+            .transpose()
+    }
 }
 
 /// Extra methods for working with `gltf::Skin`.
@@ -188,6 +671,116 @@ impl<'a> ChannelIterators<'a> for gltf::animation::Channel<'a> {
     }
 }
 
+/// Extra methods for working with `gltf::Accessor`.
+pub trait AccessorIterators<'a> {
+    /// Visits every component of every element of an accessor as `f64`, in
+    /// element order and whatever the component type.
+    ///
+    /// Normalized integers are converted to `[0, 1]` or `[-1, 1]` as the
+    /// specification requires. Sparse substitutions are not applied.
+    fn iter_f64<'s, S: Source>(&'a self, source: &'s S) -> ComponentsF64<'s>;
+
+    /// Verifies that every element of an accessor lies within both its
+    /// buffer view and the data of its buffer.
+    ///
+    /// Readers slice buffer data without this check and panic on malformed
+    /// accessors, so call it first when the data is untrusted.
+    fn check_bounds<S: Source>(&self, source: &S) -> Result<(), BoundsError>;
+}
+
+impl<'a> AccessorIterators<'a> for gltf::Accessor<'a> {
+    fn iter_f64<'s, S: Source>(&self, source: &'s S) -> ComponentsF64<'s> {
+        ComponentsF64::new(self, source)
+    }
+
+    fn check_bounds<S: Source>(&self, source: &S) -> Result<(), BoundsError> {
+        data_range(self, layout(self).1, source).map(|_| ())
+    }
+}
+
+/// An accessor whose elements extend past the end of its buffer view or of
+/// the data of its buffer.
+///
+/// Returned by `AccessorIter::checked`, the `try_` readers of
+/// `PrimitiveIterators` and `MorphTargetIterators`, and the `check_bounds`
+/// methods.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BoundsError {
+    /// The index of the accessor.
+    pub accessor: usize,
+
+    /// The offset one past the last byte the accessor reads, relative to the
+    /// start of the buffer, or `None` if it overflows `usize`.
+    pub end: Option<usize>,
+
+    /// The number of bytes from the start of the buffer that may be read,
+    /// i.e. the end of the buffer view or of the buffer data, whichever comes
+    /// first.
+    pub available: usize,
+}
+
+impl fmt::Display for BoundsError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.end {
+            Some(end) => write!(
+                f,
+                "accessor {} reads up to byte {} but only {} bytes are available",
+                self.accessor,
+                end,
+                self.available,
+            ),
+            None => write!(f, "accessor {} has an overflowing byte range", self.accessor),
+        }
+    }
+}
+
+impl error::Error for BoundsError {
+    fn description(&self) -> &str {
+        "accessor data out of bounds"
+    }
+}
+
+/// Returns the byte range of the buffer data read by an accessor with
+/// elements of the given size, after checking it against the buffer view and
+/// the buffer data.
+///
+/// Accessors without a buffer view read no buffer data, so their range is
+/// empty.
+fn data_range<S: Source>(
+    accessor: &gltf::Accessor,
+    element_size: usize,
+    source: &S,
+) -> Result<ops::Range<usize>, BoundsError> {
+    let view = match accessor.view() {
+        Some(view) => view,
+        None => return Ok(0..0),
+    };
+    let buffer = source.source_buffer(&view.buffer());
+    let available = view.offset()
+        .saturating_add(view.length())
+        .min(buffer.len());
+    let start = view.offset() + accessor.offset();
+    // A stride shorter than an element would make consecutive reads overlap
+    // the end of the data, so elements are read at least `element_size` apart.
+    let stride = view.stride().unwrap_or(element_size).max(element_size);
+    let end = match accessor.count() {
+        0 => Some(start),
+        count => stride
+            .checked_mul(count - 1)
+            .and_then(|x| x.checked_add(element_size))
+            .and_then(|x| x.checked_add(start)),
+    };
+    match end {
+        Some(end) if end <= available => Ok(start..end),
+        _ => Err(BoundsError { accessor: accessor.index(), end, available }),
+    }
+}
+
+/// The bytes of the largest element, a 4x4 matrix of `f32`, all zero.
+///
+/// Elements of accessors without a buffer view are read from here.
+static ZEROS: [u8; 64] = [0; 64];
+
 /// Visits the items in an `Accessor`.
 #[derive(Clone, Debug)]
 pub struct AccessorIter<'a, T> {
@@ -196,24 +789,78 @@ pub struct AccessorIter<'a, T> {
 
     /// The data we're iterating over.
     data: &'a [u8],
-    
+
+    /// The number of all-zero items left to visit, for accessors without a
+    /// buffer view.
+    zeros: usize,
+
     /// Consumes the data type we're returning at each iteration.
     _phantom: marker::PhantomData<T>,
 }
 
 impl<'a, T> AccessorIter<'a, T> {
+    /// Creates an iterator over the elements of an accessor without checking
+    /// them against the bounds of the buffer view.
+    ///
+    /// This is the fast path for trusted data.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the elements extend past the end of the buffer data.
     pub fn new<S>(accessor: gltf::Accessor, source: &'a S) -> AccessorIter<'a, T>
         where S: Source
     {
         debug_assert_eq!(size_of::<T>(), accessor.size());
         debug_assert!(size_of::<T>() > 0);
-        let view = accessor.view();
+        let view = match accessor.view() {
+            Some(view) => view,
+            None => return AccessorIter::zeros(accessor.count()),
+        };
         let stride = view.stride().unwrap_or(size_of::<T>());
         debug_assert!(stride >= size_of::<T>());
-        let start = view.offset() + accessor.offset();
-        let end = start + stride * (accessor.count() - 1) + size_of::<T>();
-        let data = &source.source_buffer(&view.buffer())[start .. end];
-        AccessorIter { stride, data, _phantom: marker::PhantomData }
+        let data = match accessor.count() {
+            0 => &[],
+            count => {
+                let start = view.offset() + accessor.offset();
+                let end = start + stride * (count - 1) + size_of::<T>();
+                &source.source_buffer(&view.buffer())[start .. end]
+            },
+        };
+        AccessorIter { stride, data, zeros: 0, _phantom: marker::PhantomData }
+    }
+
+    /// Creates an iterator over the elements of an accessor after checking
+    /// that every element lies within the buffer view and the buffer data.
+    ///
+    /// Iteration over the result never panics.
+    pub fn checked<S>(
+        accessor: gltf::Accessor,
+        source: &'a S,
+    ) -> Result<AccessorIter<'a, T>, BoundsError>
+        where S: Source
+    {
+        debug_assert_eq!(size_of::<T>(), accessor.size());
+        debug_assert!(size_of::<T>() > 0);
+        let range = data_range(&accessor, size_of::<T>(), source)?;
+        let view = match accessor.view() {
+            Some(view) => view,
+            None => return Ok(AccessorIter::zeros(accessor.count())),
+        };
+        let stride = view.stride().unwrap_or(size_of::<T>()).max(size_of::<T>());
+        let data = &source.source_buffer(&view.buffer())[range];
+        Ok(AccessorIter { stride, data, zeros: 0, _phantom: marker::PhantomData })
+    }
+
+    /// Creates an iterator visiting `count` all-zero items, the contents of
+    /// an accessor without a buffer view. Sparse values are not applied.
+    fn zeros(count: usize) -> AccessorIter<'a, T> {
+        debug_assert!(size_of::<T>() <= ZEROS.len());
+        AccessorIter {
+            stride: size_of::<T>(),
+            data: &[],
+            zeros: count,
+            _phantom: marker::PhantomData,
+        }
     }
 }
 
@@ -221,6 +868,10 @@ impl<'a, T: AccessorItem> Iterator for AccessorIter<'a, T> {
     type Item = T;
 
     fn next(&mut self) -> Option<Self::Item> {
+        if self.zeros > 0 {
+            self.zeros -= 1;
+            return Some(T::from_slice(&ZEROS));
+        }
         let stride = if self.data.len() >= self.stride {
             Some(self.stride)
         } else if self.data.len() >= size_of::<T>() {
@@ -239,18 +890,32 @@ impl<'a, T: AccessorItem> Iterator for AccessorIter<'a, T> {
     }
 
     fn nth(&mut self, nth: usize) -> Option<Self::Item> {
-        if self.data.len() > 0 {
-            let val_data = &self.data[nth * self.stride ..];
-            let val = T::from_slice(val_data);
-            self.data = &val_data[self.stride.min(val_data.len()) ..];
-            Some(val)
-        } else {
-            None
+        if self.zeros > 0 {
+            return if nth < self.zeros {
+                self.zeros -= nth + 1;
+                Some(T::from_slice(&ZEROS))
+            } else {
+                self.zeros = 0;
+                None
+            };
+        }
+        match nth.checked_mul(self.stride).and_then(|offset| self.data.get(offset ..)) {
+            Some(val_data) if val_data.len() >= size_of::<T>() => {
+                let val = T::from_slice(val_data);
+                self.data = &val_data[self.stride.min(val_data.len()) ..];
+                Some(val)
+            },
+            _ => {
+                self.data = &[];
+                None
+            },
         }
     }
 
     fn last(self) -> Option<Self::Item> {
-        if self.data.len() > 0 {
+        if self.zeros > 0 {
+            Some(T::from_slice(&ZEROS))
+        } else if !self.data.is_empty() {
             self.data
                 .get((self.data.len() - 1) / self.stride * self.stride ..)
                 .map(T::from_slice)
@@ -264,7 +929,8 @@ impl<'a, T: AccessorItem> Iterator for AccessorIter<'a, T> {
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
-        let hint = self.data.len() / self.stride
+        let hint = self.zeros
+            + self.data.len() / self.stride
             + (self.data.len() % self.stride > 0) as usize;
         (hint, Some(hint))
     }
@@ -273,7 +939,16 @@ impl<'a, T: AccessorItem> Iterator for AccessorIter<'a, T> {
 impl<'a, T: AccessorItem> ExactSizeIterator for AccessorIter<'a, T> {}
 
 /// Any type that can appear in an Accessor.
+///
+/// glTF buffer data is always little-endian. Implementations decode values
+/// byte by byte with `byteorder::LE` rather than casting the buffer, so reads
+/// are correct on big-endian hosts and never require the buffer to be aligned.
 pub trait AccessorItem: Sized {
+    /// Decodes a value from the start of a little-endian byte slice.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the slice is shorter than `size_of::<Self>()`.
     fn from_slice(buf: &[u8]) -> Self;
 }
 
@@ -353,10 +1028,41 @@ pub struct Positions<'a>(AccessorIter<'a, [f32; 3]>);
 #[derive(Clone, Debug)]
 pub struct Tangents<'a>(AccessorIter<'a, [f32; 4]>);
 
+/// XYZ morph target tangent displacements of type `[f32; 3]`, which unlike
+/// vertex tangents lack a handedness component.
+#[derive(Clone, Debug)]
+pub struct TangentDisplacements<'a>(AccessorIter<'a, [f32; 3]>);
+
 /// Inverse Bind Matrices of type [[f32; 4]; 4].
 #[derive(Clone, Debug)]
 pub struct InverseBindMatrices<'a>(AccessorIter<'a, [[f32; 4]; 4]>);
 
+/// The components of an accessor of any type as `f64`.
+#[derive(Clone, Debug)]
+pub struct ComponentsF64<'a> {
+    /// The data from the first element onwards.
+    data: &'a [u8],
+
+    /// The component type.
+    data_type: DataType,
+
+    /// Whether integer components are normalized.
+    normalized: bool,
+
+    /// The number of bytes between each element.
+    stride: usize,
+
+    /// The byte offset of each component within an element, which accounts
+    /// for the column padding of small matrices.
+    offsets: Vec<usize>,
+
+    /// The index of the next component.
+    index: usize,
+
+    /// The number of components of all elements.
+    len: usize,
+}
+
 /// Animation input sampler values of type `f32`.
 #[derive(Clone, Debug)]
 pub struct Inputs<'a>(AccessorIter<'a, f32>);
@@ -376,9 +1082,9 @@ pub enum Outputs<'a> {
     Weights(MorphWeightsF32<'a>),
 }
 
-/// Vertex colors.
+/// Vertex colors in their stored format.
 #[derive(Clone, Debug)]
-enum Colors<'a> {
+pub enum Colors<'a> {
     /// RGB vertex color of type `[u8; 3]>`.
     RgbU8(AccessorIter<'a, [u8; 3]>),
 
@@ -409,9 +1115,9 @@ enum Indices<'a> {
     U32(AccessorIter<'a, u32>),
 }
 
-/// Vertex joints.
+/// Vertex joints in their stored format.
 #[derive(Clone, Debug)]
-enum Joints<'a> {
+pub enum Joints<'a> {
     /// Joints of type `[u8; 4]`.
     /// Refer to the documentation on morph targets and skins for more
     /// information.
@@ -423,9 +1129,9 @@ enum Joints<'a> {
     U16(AccessorIter<'a, [u16; 4]>),
 }
 
-/// UV texture co-ordinates.
+/// UV texture co-ordinates in their stored format.
 #[derive(Clone, Debug)]
-enum TexCoords<'a> {
+pub enum TexCoords<'a> {
     /// UV texture co-ordinates of type `[f32; 2]`.
     F32(AccessorIter<'a, [f32; 2]>),
 
@@ -436,9 +1142,9 @@ enum TexCoords<'a> {
     U16(AccessorIter<'a, [u16; 2]>),
 }
 
-/// Weights.
+/// Joint weights in their stored format.
 #[derive(Clone, Debug)]
-enum Weights<'a> {
+pub enum Weights<'a> {
     /// Weights of type `[f32; 4]`.
     F32(AccessorIter<'a, [f32; 4]>),
 
@@ -489,6 +1195,20 @@ pub struct IndicesU32<'a>(Indices<'a>);
 #[derive(Clone, Debug)]
 pub struct TexCoordsF32<'a>(TexCoords<'a>);
 
+/// Texture co-ordinates of type `[f32; 2]` wrapped into the range `0.0` to
+/// `1.0` by the wrap modes of a sampler.
+#[derive(Clone, Debug)]
+pub struct TexCoordsWrapped<'a> {
+    /// Internal iterator type.
+    iter: TexCoordsF32<'a>,
+
+    /// The wrap mode of the `s` (U) co-ordinate.
+    wrap_s: WrappingMode,
+
+    /// The wrap mode of the `t` (V) co-ordinate.
+    wrap_t: WrappingMode,
+}
+
 /// Joint indices co-coerced into `[u16; 4]` values.
 #[derive(Clone, Debug)]
 pub struct JointsU16<'a>(Joints<'a>);
@@ -516,6 +1236,15 @@ pub struct RotationsF32<'a>(Rotations<'a>);
 pub struct MorphWeightsF32<'a>(MorphWeights<'a>);
 
 impl<'a> Colors<'a> {
+    /// Converts colors of any format to normalized RGBA, with an alpha of
+    /// `1.0` for RGB colors.
+    pub fn into_rgba_f32(self) -> ColorsRgbaF32<'a> {
+        ColorsRgbaF32 {
+            iter: self,
+            default_alpha: 1.0,
+        }
+    }
+
     fn new<S: Source>(accessor: gltf::Accessor, source: &'a S) -> Colors<'a> {
         match (accessor.dimensions(), accessor.data_type()) {
             (Dimensions::Vec3, DataType::U8) => {
@@ -539,9 +1268,44 @@ impl<'a> Colors<'a> {
             _ => unreachable!(),
         }
     }
+
+    /// Like `new`, but checks the elements against the bounds of the buffer
+    /// view and buffer.
+    fn checked<S: Source>(
+        accessor: gltf::Accessor,
+        source: &'a S,
+    ) -> Result<Colors<'a>, BoundsError> {
+        Ok(match (accessor.dimensions(), accessor.data_type()) {
+            (Dimensions::Vec3, DataType::U8) => {
+                Colors::RgbU8(AccessorIter::checked(accessor, source)?)
+            },
+            (Dimensions::Vec4, DataType::U8) => {
+                Colors::RgbaU8(AccessorIter::checked(accessor, source)?)
+            },
+            (Dimensions::Vec3, DataType::U16) => {
+                Colors::RgbU16(AccessorIter::checked(accessor, source)?)
+            },
+            (Dimensions::Vec4, DataType::U16) => {
+                Colors::RgbaU16(AccessorIter::checked(accessor, source)?)
+            },
+            (Dimensions::Vec3, DataType::F32) => {
+                Colors::RgbF32(AccessorIter::checked(accessor, source)?)
+            },
+            (Dimensions::Vec4, DataType::F32) => {
+                Colors::RgbaF32(AccessorIter::checked(accessor, source)?)
+            },
+            _ => unreachable!(),
+        })
+    }
 }
 
 impl<'a> TexCoords<'a> {
+    /// Converts texture co-ordinates of any format to `f32`, mapping
+    /// normalized `u8` and `u16` co-ordinates to the range `0.0` to `1.0`.
+    pub fn into_f32(self) -> TexCoordsF32<'a> {
+        TexCoordsF32(self)
+    }
+
     fn new<S: Source>(accessor: gltf::Accessor, source: &'a S) -> TexCoords<'a> {
         match accessor.data_type() {
             DataType::U8 => TexCoords::U8(AccessorIter::new(accessor, source)),
@@ -550,6 +1314,20 @@ impl<'a> TexCoords<'a> {
             _ => unreachable!(),
         }
     }
+
+    /// Like `new`, but checks the elements against the bounds of the buffer
+    /// view and buffer.
+    fn checked<S: Source>(
+        accessor: gltf::Accessor,
+        source: &'a S,
+    ) -> Result<TexCoords<'a>, BoundsError> {
+        Ok(match accessor.data_type() {
+            DataType::U8 => TexCoords::U8(AccessorIter::checked(accessor, source)?),
+            DataType::U16 => TexCoords::U16(AccessorIter::checked(accessor, source)?),
+            DataType::F32 => TexCoords::F32(AccessorIter::checked(accessor, source)?),
+            _ => unreachable!(),
+        })
+    }
 }
 
 impl<'a> Indices<'a> {
@@ -561,9 +1339,28 @@ impl<'a> Indices<'a> {
             _ => unreachable!(),
         }
     }
+
+    /// Like `new`, but checks the elements against the bounds of the buffer
+    /// view and buffer.
+    fn checked<S: Source>(
+        accessor: gltf::Accessor,
+        source: &'a S,
+    ) -> Result<Indices<'a>, BoundsError> {
+        Ok(match accessor.data_type() {
+            DataType::U8 => Indices::U8(AccessorIter::checked(accessor, source)?),
+            DataType::U16 => Indices::U16(AccessorIter::checked(accessor, source)?),
+            DataType::U32 => Indices::U32(AccessorIter::checked(accessor, source)?),
+            _ => unreachable!(),
+        })
+    }
 }
 
 impl<'a> Joints<'a> {
+    /// Converts joint indices of any format to `u16`.
+    pub fn into_u16x4(self) -> JointsU16<'a> {
+        JointsU16(self)
+    }
+
     fn new<S: Source>(accessor: gltf::Accessor, source: &'a S) -> Joints<'a> {
         match accessor.data_type() {
             DataType::U8 => Joints::U8(AccessorIter::new(accessor, source)),
@@ -571,9 +1368,29 @@ impl<'a> Joints<'a> {
             _ => unreachable!(),
         }
     }
+
+    /// Like `new`, but checks the elements against the bounds of the buffer
+    /// view and buffer.
+    fn checked<S: Source>(
+        accessor: gltf::Accessor,
+        source: &'a S,
+    ) -> Result<Joints<'a>, BoundsError> {
+        Ok(match accessor.data_type() {
+            DataType::U8 => Joints::U8(AccessorIter::checked(accessor, source)?),
+            DataType::U16 => Joints::U16(AccessorIter::checked(accessor, source)?),
+            _ => unreachable!(),
+        })
+    }
 }
 
 impl<'a> Weights<'a> {
+    /// Converts weights of any format to `f32`, mapping normalized `u8` and
+    /// `u16` weights to the range `0.0` to `1.0` as the specification
+    /// requires.
+    pub fn into_f32x4(self) -> WeightsF32<'a> {
+        WeightsF32(self)
+    }
+
     fn new<S: Source>(accessor: gltf::Accessor, source: &'a S) -> Weights<'a> {
         match accessor.data_type() {
             DataType::U8 => Weights::U8(AccessorIter::new(accessor, source)),
@@ -582,6 +1399,20 @@ impl<'a> Weights<'a> {
             _ => unreachable!(),
         }
     }
+
+    /// Like `new`, but checks the elements against the bounds of the buffer
+    /// view and buffer.
+    fn checked<S: Source>(
+        accessor: gltf::Accessor,
+        source: &'a S,
+    ) -> Result<Weights<'a>, BoundsError> {
+        Ok(match accessor.data_type() {
+            DataType::U8 => Weights::U8(AccessorIter::checked(accessor, source)?),
+            DataType::U16 => Weights::U16(AccessorIter::checked(accessor, source)?),
+            DataType::F32 => Weights::F32(AccessorIter::checked(accessor, source)?),
+            _ => unreachable!(),
+        })
+    }
 }
 
 impl<'a> Rotations<'a> {
@@ -634,6 +1465,102 @@ impl<'a> MorphWeights<'a> {
     }
 }
 
+/// Returns the byte offset of every component within an element of an
+/// accessor, and the length of an element in bytes.
+///
+/// Matrix columns start on 4-byte boundaries, so matrices of `i8`, `u8`,
+/// `i16`, and `u16` components have padding between columns.
+pub(crate) fn layout(accessor: &gltf::Accessor) -> (Vec<usize>, usize) {
+    element_layout(accessor.data_type(), accessor.dimensions())
+}
+
+/// Returns the byte offset of every component within an element of the given
+/// component type and dimensions, and the length of an element in bytes, as
+/// `layout` does for an accessor.
+pub(crate) fn element_layout(data_type: DataType, dimensions: Dimensions) -> (Vec<usize>, usize) {
+    let size = data_type.size();
+    let (columns, rows) = match dimensions {
+        Dimensions::Mat2 => (2, 2),
+        Dimensions::Mat3 => (3, 3),
+        Dimensions::Mat4 => (4, 4),
+        dimensions => (1, dimensions.multiplicity()),
+    };
+    let column_stride = if columns > 1 { (rows * size).div_ceil(4) * 4 } else { rows * size };
+    let offsets = (0..columns)
+        .flat_map(|column| (0..rows).map(move |row| column * column_stride + row * size))
+        .collect();
+    (offsets, (columns - 1) * column_stride + rows * size)
+}
+
+impl<'a> ComponentsF64<'a> {
+    fn new<S: Source>(accessor: &gltf::Accessor, source: &'a S) -> Self {
+        let data_type = accessor.data_type();
+        let (offsets, element_size) = layout(accessor);
+        let count = accessor.count();
+        let (data, stride) = match accessor.view() {
+            // Every element reads the same zeros.
+            None => (&ZEROS[..element_size], 0),
+            Some(_) if count == 0 => (&[][..], element_size),
+            Some(view) => {
+                let stride = view.stride().unwrap_or(element_size);
+                let start = view.offset() + accessor.offset();
+                let end = start + stride * (count - 1) + element_size;
+                (&source.source_buffer(&view.buffer())[start..end], stride)
+            },
+        };
+        ComponentsF64 {
+            data,
+            data_type,
+            normalized: accessor.normalized(),
+            stride,
+            len: count * offsets.len(),
+            offsets,
+            index: 0,
+        }
+    }
+
+    /// Visits integer components as stored, even if normalized.
+    pub(crate) fn unnormalized(mut self) -> Self {
+        self.normalized = false;
+        self
+    }
+}
+
+impl<'a> ExactSizeIterator for ComponentsF64<'a> {}
+impl<'a> Iterator for ComponentsF64<'a> {
+    type Item = f64;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index >= self.len {
+            return None;
+        }
+        let components = self.offsets.len();
+        let element = self.index / components;
+        let offset = element * self.stride + self.offsets[self.index % components];
+        self.index += 1;
+        let bytes = &self.data[offset..];
+        let x = match self.data_type {
+            DataType::I8 => bytes[0] as i8 as f64,
+            DataType::U8 => bytes[0] as f64,
+            DataType::I16 => LE::read_i16(bytes) as f64,
+            DataType::U16 => LE::read_u16(bytes) as f64,
+            DataType::U32 => LE::read_u32(bytes) as f64,
+            DataType::F32 => LE::read_f32(bytes) as f64,
+        };
+        Some(match (self.normalized, self.data_type) {
+            (true, DataType::I8) => (x / 127.0).max(-1.0),
+            (true, DataType::U8) => x / 255.0,
+            (true, DataType::I16) => (x / 32767.0).max(-1.0),
+            (true, DataType::U16) => x / 65535.0,
+            _ => x,
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let hint = self.len - self.index;
+        (hint, Some(hint))
+    }
+}
+
 impl<'a> ExactSizeIterator for IndicesU32<'a> {}
 impl<'a> Iterator for IndicesU32<'a> {
     type Item = u32;
@@ -714,6 +1641,54 @@ impl<'a> Iterator for ColorsRgbaF32<'a> {
     }
 }
 
+impl<'a> TexCoordsF32<'a> {
+    /// Wraps the co-ordinates into the range `0.0` to `1.0` as a sampler with
+    /// the given wrap modes would, e.g. for placing them in a texture atlas.
+    ///
+    /// See `wrap` for how each mode maps co-ordinates.
+    pub fn wrapped(self, sampler: &gltf::texture::Sampler) -> TexCoordsWrapped<'a> {
+        TexCoordsWrapped {
+            iter: self,
+            wrap_s: sampler.wrap_s(),
+            wrap_t: sampler.wrap_t(),
+        }
+    }
+}
+
+/// Maps a texture co-ordinate into the range `0.0` to `1.0` as a sampler with
+/// the given wrap mode would.
+///
+/// * `ClampToEdge` clamps the co-ordinate.
+/// * `Repeat` keeps the fractional part, so `1.25` becomes `0.25`.
+/// * `MirroredRepeat` mirrors every other repetition, so `1.25` becomes
+///   `0.75`.
+///
+/// The co-ordinates `1.0` and `0.0` are equivalent when repeating; `1.0` maps
+/// to `0.0`.
+pub fn wrap(coordinate: f32, mode: WrappingMode) -> f32 {
+    match mode {
+        WrappingMode::ClampToEdge => coordinate.clamp(0.0, 1.0),
+        WrappingMode::Repeat => coordinate - coordinate.floor(),
+        WrappingMode::MirroredRepeat => {
+            let period = coordinate - 2.0 * (coordinate / 2.0).floor();
+            if period > 1.0 { 2.0 - period } else { period }
+        },
+    }
+}
+
+impl<'a> ExactSizeIterator for TexCoordsWrapped<'a> {}
+impl<'a> Iterator for TexCoordsWrapped<'a> {
+    type Item = [f32; 2];
+    fn next(&mut self) -> Option<Self::Item> {
+        let (wrap_s, wrap_t) = (self.wrap_s, self.wrap_t);
+        self.iter.next().map(|uv| [wrap(uv[0], wrap_s), wrap(uv[1], wrap_t)])
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
 impl<'a> ExactSizeIterator for TexCoordsF32<'a> {}
 impl<'a> Iterator for TexCoordsF32<'a> {
     type Item = [f32; 2];
@@ -790,6 +1765,18 @@ impl<'a> Iterator for Tangents<'a> {
     }
 }
 
+impl<'a> ExactSizeIterator for TangentDisplacements<'a> {}
+impl<'a> Iterator for TangentDisplacements<'a> {
+    type Item = [f32; 3];
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
 impl<'a> ExactSizeIterator for InverseBindMatrices<'a> {}
 impl<'a> Iterator for InverseBindMatrices<'a> {
     type Item = [[f32; 4]; 4];
@@ -918,13 +1905,264 @@ impl<T: Denormalize> Denormalize for [T; 4] {
 
 #[cfg(test)]
 mod tests {
-    use super::{AccessorItem, AccessorIter};
+    use byteorder::{LE, ByteOrder};
+    use gltf;
+    use gltf::texture::WrappingMode;
+
+    use super::{AccessorItem, AccessorIter, AccessorIterators, MorphTargetIterators};
+    use super::{BoundsError, PrimitiveIterators};
+    use super::{Colors, Joints, TexCoords, Weights};
+
+    #[test]
+    fn components_f64() {
+        let gltf = gltf::Gltf::from_str(r#"{
+            "asset": { "version": "2.0" },
+            "buffers": [{ "byteLength": 16 }],
+            "bufferViews": [
+                { "buffer": 0, "byteLength": 8, "byteStride": 4 },
+                { "buffer": 0, "byteOffset": 8, "byteLength": 8 }
+            ],
+            "accessors": [
+                {
+                    "bufferView": 0, "componentType": 5120, "count": 2, "type": "VEC2",
+                    "normalized": true
+                },
+                { "bufferView": 1, "componentType": 5121, "count": 1, "type": "MAT2" }
+            ]
+        }"#).unwrap().validate_minimally().unwrap();
+        let buffers = vec![vec![127, 0x80, 9, 9, 0, 0xC1, 9, 9, 1, 2, 9, 9, 3, 4, 9, 9]];
+        let mut accessors = gltf.accessors();
+        let normalized: Vec<f64> = accessors.next().unwrap().iter_f64(&buffers).collect();
+        assert_eq!(vec![1.0, -1.0, 0.0, -63.0 / 127.0], normalized);
+        let matrix = accessors.next().unwrap();
+        assert_eq!(4, matrix.iter_f64(&buffers).len());
+        let matrix: Vec<f64> = matrix.iter_f64(&buffers).collect();
+        assert_eq!(vec![1.0, 2.0, 3.0, 4.0], matrix);
+    }
+
+    #[test]
+    fn bounds_checks() {
+        let gltf = gltf::Gltf::from_str(r#"{
+            "asset": { "version": "2.0" },
+            "buffers": [{ "byteLength": 24 }],
+            "bufferViews": [
+                { "buffer": 0, "byteLength": 24 },
+                { "buffer": 0, "byteOffset": 12, "byteLength": 12 }
+            ],
+            "accessors": [
+                { "bufferView": 0, "componentType": 5126, "count": 2, "type": "VEC3",
+                  "min": [0, 0, 0], "max": [0, 0, 0] },
+                { "bufferView": 1, "componentType": 5126, "count": 2, "type": "VEC3" },
+                { "bufferView": 0, "componentType": 5126, "count": 0, "type": "VEC3" }
+            ],
+            "meshes": [{
+                "primitives": [
+                    { "attributes": { "POSITION": 0 } },
+                    { "attributes": { "POSITION": 0, "NORMAL": 1 } }
+                ]
+            }]
+        }"#).unwrap().validate_minimally().unwrap();
+        let buffers = vec![vec![0; 24]];
+        let accessors: Vec<_> = gltf.accessors().collect();
+        assert_eq!(Ok(()), accessors[0].check_bounds(&buffers));
+        assert_eq!(
+            Err(BoundsError { accessor: 1, end: Some(36), available: 24 }),
+            accessors[1].check_bounds(&buffers),
+        );
+        assert_eq!(Ok(()), accessors[2].check_bounds(&buffers));
+        assert_eq!(0, AccessorIter::<[f32; 3]>::new(accessors[2].clone(), &buffers).count());
+
+        let mut iter = AccessorIter::<[f32; 3]>::checked(accessors[0].clone(), &buffers).unwrap();
+        assert_eq!(None, iter.nth(2));
+        assert!(AccessorIter::<[f32; 3]>::checked(accessors[1].clone(), &buffers).is_err());
+
+        // The buffer data may be shorter than the buffer declares.
+        let short = vec![vec![0; 20]];
+        assert_eq!(
+            Err(BoundsError { accessor: 0, end: Some(24), available: 20 }),
+            accessors[0].check_bounds(&short),
+        );
+
+        let mesh = gltf.meshes().next().unwrap();
+        let results: Vec<_> = mesh.primitives().map(|p| p.check_bounds(&buffers)).collect();
+        assert_eq!(Ok(()), results[0]);
+        assert_eq!(1, results[1].clone().unwrap_err().accessor);
+
+        let primitives: Vec<_> = mesh.primitives().collect();
+        assert_eq!(2, primitives[1].try_positions(&buffers).unwrap().unwrap().count());
+        assert_eq!(1, primitives[1].try_normals(&buffers).unwrap_err().accessor);
+        assert!(primitives[0].try_normals(&buffers).unwrap().is_none());
+        assert!(primitives[0].try_tex_coords_f32(0, &buffers).unwrap().is_none());
+        assert_eq!(0, primitives[0].try_positions(&short).unwrap_err().accessor);
+    }
+
+    #[test]
+    fn accessor_without_view() {
+        let gltf = gltf::Gltf::from_str(r#"{
+            "asset": { "version": "2.0" },
+            "accessors": [{ "componentType": 5126, "count": 3, "type": "VEC2" }]
+        }"#).unwrap().validate_completely().unwrap();
+        let buffers: Vec<Vec<u8>> = vec![];
+        let accessor = gltf.accessors().next().unwrap();
+        assert!(accessor.view().is_none());
+        assert_eq!(Ok(()), accessor.check_bounds(&buffers));
+
+        let iter = AccessorIter::<[f32; 2]>::checked(accessor.clone(), &buffers).unwrap();
+        assert_eq!(3, iter.len());
+        assert_eq!(vec![[0.0; 2]; 3], iter.clone().collect::<Vec<_>>());
+        assert_eq!(Some([0.0; 2]), iter.clone().nth(2));
+        assert_eq!(None, iter.clone().nth(3));
+        assert_eq!(vec![0.0; 6], accessor.iter_f64(&buffers).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn colors_into_rgba_f32() {
+        let gltf = gltf::Gltf::from_str(r#"{
+            "asset": { "version": "2.0" },
+            "buffers": [{ "byteLength": 12 }],
+            "bufferViews": [{ "buffer": 0, "byteLength": 12 }],
+            "accessors": [{
+                "bufferView": 0, "componentType": 5123, "normalized": true,
+                "count": 2, "type": "VEC3"
+            }],
+            "meshes": [{ "primitives": [{ "attributes": { "COLOR_0": 0 } }] }]
+        }"#).unwrap().validate_minimally().unwrap();
+        let buffers = vec![vec![0xFF, 0xFF, 0, 0, 0, 0, 0, 0, 0xFF, 0xFF, 0, 0]];
+        let mesh = gltf.meshes().next().unwrap();
+        let primitive = mesh.primitives().next().unwrap();
+        let colors = primitive.colors(0, &buffers).unwrap();
+        match colors {
+            Colors::RgbU16(ref i) => assert_eq!(2, i.len()),
+            _ => panic!("expected RGB u16 colors"),
+        }
+        let rgba: Vec<[f32; 4]> = colors.into_rgba_f32().collect();
+        assert_eq!(vec![[1.0, 0.0, 0.0, 1.0], [0.0, 1.0, 0.0, 1.0]], rgba);
+        assert!(primitive.colors(1, &buffers).is_none());
+    }
+
+    #[test]
+    fn skinning_into_uniform_types() {
+        let gltf = gltf::Gltf::from_str(r#"{
+            "asset": { "version": "2.0" },
+            "buffers": [{ "byteLength": 12 }],
+            "bufferViews": [
+                { "buffer": 0, "byteLength": 4 },
+                { "buffer": 0, "byteOffset": 4, "byteLength": 8 }
+            ],
+            "accessors": [
+                { "bufferView": 0, "componentType": 5121, "count": 1, "type": "VEC4" },
+                {
+                    "bufferView": 1, "componentType": 5123, "normalized": true,
+                    "count": 1, "type": "VEC4"
+                }
+            ],
+            "meshes": [{
+                "primitives": [{ "attributes": { "JOINTS_0": 0, "WEIGHTS_0": 1 } }]
+            }]
+        }"#).unwrap().validate_minimally().unwrap();
+        let buffers = vec![vec![1, 2, 3, 255, 0xFF, 0xFF, 0, 0, 0, 0, 0, 0]];
+        let mesh = gltf.meshes().next().unwrap();
+        let primitive = mesh.primitives().next().unwrap();
+
+        let joints = primitive.joints(0, &buffers).unwrap();
+        match joints {
+            Joints::U8(_) => {},
+            _ => panic!("expected u8 joints"),
+        }
+        let joints: Vec<[u16; 4]> = joints.into_u16x4().collect();
+        assert_eq!(vec![[1, 2, 3, 255]], joints);
+
+        let weights = primitive.weights(0, &buffers).unwrap();
+        match weights {
+            Weights::U16(_) => {},
+            _ => panic!("expected u16 weights"),
+        }
+        let weights: Vec<[f32; 4]> = weights.into_f32x4().collect();
+        assert_eq!(vec![[1.0, 0.0, 0.0, 0.0]], weights);
+    }
+
+    #[test]
+    fn tex_coords_wrapped() {
+        let gltf = gltf::Gltf::from_str(r#"{
+            "asset": { "version": "2.0" },
+            "buffers": [{ "byteLength": 8 }],
+            "bufferViews": [{ "buffer": 0, "byteLength": 8 }],
+            "accessors": [{
+                "bufferView": 0, "componentType": 5123, "normalized": true,
+                "count": 2, "type": "VEC2"
+            }],
+            "samplers": [{ "wrapS": 33071, "wrapT": 33648 }],
+            "meshes": [{ "primitives": [{ "attributes": { "TEXCOORD_0": 0 } }] }]
+        }"#).unwrap().validate_minimally().unwrap();
+        let buffers = vec![vec![0xFF, 0xFF, 0, 0, 0, 0, 0xFF, 0xFF]];
+        let mesh = gltf.meshes().next().unwrap();
+        let primitive = mesh.primitives().next().unwrap();
+        let tex_coords = primitive.tex_coords(0, &buffers).unwrap();
+        match tex_coords {
+            TexCoords::U16(_) => {},
+            _ => panic!("expected u16 texture co-ordinates"),
+        }
+        let uvs: Vec<[f32; 2]> = tex_coords.clone().into_f32().collect();
+        assert_eq!(vec![[1.0, 0.0], [0.0, 1.0]], uvs);
+
+        let sampler = gltf.samplers().next().unwrap();
+        let uvs: Vec<[f32; 2]> = tex_coords.into_f32().wrapped(&sampler).collect();
+        assert_eq!(vec![[1.0, 0.0], [0.0, 1.0]], uvs);
+
+        assert_eq!(0.25, super::wrap(-0.75, WrappingMode::Repeat));
+        assert_eq!(0.75, super::wrap(1.25, WrappingMode::MirroredRepeat));
+        assert_eq!(0.75, super::wrap(-0.75, WrappingMode::MirroredRepeat));
+        assert_eq!(1.0, super::wrap(3.0, WrappingMode::MirroredRepeat));
+        assert_eq!(0.0, super::wrap(-2.0, WrappingMode::ClampToEdge));
+    }
+
+    #[test]
+    fn morph_target_displacements() {
+        let gltf = gltf::Gltf::from_str(r#"{
+            "asset": { "version": "2.0" },
+            "buffers": [{ "byteLength": 12 }],
+            "bufferViews": [{ "buffer": 0, "byteLength": 12 }],
+            "accessors": [{
+                "bufferView": 0, "componentType": 5126, "count": 1, "type": "VEC3",
+                "min": [1, 2, 3], "max": [1, 2, 3]
+            }],
+            "meshes": [{
+                "primitives": [{
+                    "attributes": { "POSITION": 0 },
+                    "targets": [{ "POSITION": 0, "TANGENT": 0 }, { "NORMAL": 0 }]
+                }]
+            }]
+        }"#).unwrap().validate_minimally().unwrap();
+        let mut buffers = vec![vec![0; 12]];
+        LE::write_f32_into(&[1.0, 2.0, 3.0], &mut buffers[0]);
+        let mesh = gltf.meshes().next().unwrap();
+        let targets: Vec<_> = mesh.primitives().next().unwrap().morph_targets().collect();
+        assert_eq!(2, targets.len());
+        let positions: Vec<_> = targets[0].position_displacements(&buffers).unwrap().collect();
+        assert_eq!(vec![[1.0, 2.0, 3.0]], positions);
+        let tangents: Vec<_> = targets[0].tangent_displacements(&buffers).unwrap().collect();
+        assert_eq!(vec![[1.0, 2.0, 3.0]], tangents);
+        assert!(targets[0].normal_displacements(&buffers).is_none());
+        assert_eq!(1, targets[1].normal_displacements(&buffers).unwrap().len());
+    }
+
+    #[test]
+    fn accessor_item_little_endian() {
+        let data = [0x01, 0x02, 0x03, 0x04, 0x00, 0x00, 0x80, 0xbf];
+        assert_eq!(0x0201u16, u16::from_slice(&data));
+        assert_eq!(0x0201i16, i16::from_slice(&data));
+        assert_eq!(0x04030201u32, u32::from_slice(&data));
+        assert_eq!(-1.0f32, f32::from_slice(&data[4..]));
+        assert_eq!([0x0201u16, 0x0403], <[u16; 2]>::from_slice(&data));
+        assert_eq!([0x04030201u32, 0xbf800000], <[u32; 2]>::from_slice(&data));
+    }
 
     #[test]
     fn accessor_empty() {
         let i: AccessorIter<f32> = AccessorIter {
             stride: 4,
             data: &[],
+            zeros: 0,
             _phantom: Default::default(),
         };
         assert_eq!(None, i.clone().next());
@@ -939,6 +2177,7 @@ mod tests {
         let i: AccessorIter<f32> = AccessorIter {
             stride: 4,
             data: &data,
+            zeros: 0,
             _phantom: Default::default(),
         };
         assert_eq!(Some(1.0), i.clone().next());
@@ -953,6 +2192,7 @@ mod tests {
         let i: AccessorIter<f32> = AccessorIter {
             stride: 7,
             data: &data,
+            zeros: 0,
             _phantom: Default::default(),
         };
         assert_eq!(Some(1.0), i.clone().next());
@@ -969,6 +2209,7 @@ mod tests {
         let i: AccessorIter<f32> = AccessorIter {
             stride: 4,
             data: &data,
+            zeros: 0,
             _phantom: Default::default(),
         };
         assert_eq!(Some(1.0),      i.clone().nth(0));
@@ -986,6 +2227,7 @@ mod tests {
         let i: AccessorIter<f32> = AccessorIter {
             stride: 8,
             data: &data,
+            zeros: 0,
             _phantom: Default::default(),
         };
         assert_eq!(Some(1.0),      i.clone().nth(0));
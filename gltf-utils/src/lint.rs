@@ -0,0 +1,730 @@
+use std::{f64, fmt};
+
+use gltf::{self, Gltf, Semantic};
+use gltf::mesh::Mode;
+
+use {AccessorIterators, PrimitiveIterators, Source};
+
+/// The kind of problem found in the geometry of a primitive.
+#[derive(Clone, Debug, PartialEq)]
+pub enum GeometryKind {
+    /// A triangle with repeated vertices or zero area.
+    DegenerateTriangle {
+        /// The index of the triangle in draw order.
+        triangle: usize,
+
+        /// The vertex indices of the triangle.
+        vertices: [u32; 3],
+    },
+
+    /// A vertex position with a `NaN` or infinite component.
+    NonFinitePosition {
+        /// The index of the vertex.
+        vertex: usize,
+    },
+
+    /// A vertex normal with a `NaN` or infinite component.
+    NonFiniteNormal {
+        /// The index of the vertex.
+        vertex: usize,
+    },
+
+    /// A vertex normal with zero length.
+    ZeroLengthNormal {
+        /// The index of the vertex.
+        vertex: usize,
+    },
+}
+
+/// A problem found in the geometry of a primitive.
+#[derive(Clone, Debug, PartialEq)]
+pub struct GeometryIssue {
+    /// The index of the parent mesh.
+    pub mesh: usize,
+
+    /// The index of the primitive within its parent mesh.
+    pub primitive: usize,
+
+    /// The kind of problem.
+    pub kind: GeometryKind,
+}
+
+/// An accessor whose data is not aligned as required by the specification.
+///
+/// The readers in this crate decode values byte by byte and so tolerate
+/// misaligned data, but graphics APIs generally do not.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Misalignment {
+    /// The index of the accessor.
+    pub accessor: usize,
+
+    /// The byte offset of the accessor data from the start of its buffer.
+    pub offset: usize,
+
+    /// The byte stride of the buffer view, if specified.
+    pub stride: Option<usize>,
+
+    /// The required alignment in bytes, i.e. the size of a component.
+    pub alignment: usize,
+}
+
+/// Which bound of an accessor.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Bound {
+    /// The `min` bound.
+    Min,
+
+    /// The `max` bound.
+    Max,
+}
+
+/// A component of an accessor whose declared bound differs from the bound of
+/// its data.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BoundsDeviation {
+    /// The index of the accessor.
+    pub accessor: usize,
+
+    /// The bound that differs.
+    pub bound: Bound,
+
+    /// The index of the component within an element.
+    pub component: usize,
+
+    /// The declared value, or `None` if the bound lacks the component or is
+    /// not a number.
+    pub declared: Option<f64>,
+
+    /// The value computed from the accessor data.
+    pub computed: f64,
+}
+
+impl BoundsDeviation {
+    /// Returns the absolute difference between the declared and computed
+    /// values, or infinity if no value is declared.
+    pub fn deviation(&self) -> f64 {
+        self.declared.map_or(f64::INFINITY, |declared| (declared - self.computed).abs())
+    }
+}
+
+impl fmt::Display for BoundsDeviation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let bound = match self.bound {
+            Bound::Min => "min",
+            Bound::Max => "max",
+        };
+        write!(f, "accessors[{}].{}[{}]: ", self.accessor, bound, self.component)?;
+        match self.declared {
+            Some(declared) => write!(
+                f,
+                "declared {}, computed {} (off by {})",
+                declared,
+                self.computed,
+                self.deviation(),
+            ),
+            None => write!(f, "missing, computed {}", self.computed),
+        }
+    }
+}
+
+/// A structurally valid construct that cannot be rendered as intended.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ContentIssue {
+    /// A node with a skin but without a mesh whose primitives have joints and
+    /// weights to skin.
+    SkinWithoutSkinnedMesh {
+        /// The index of the node.
+        node: usize,
+
+        /// The index of the skin.
+        skin: usize,
+    },
+
+    /// A primitive without a `POSITION` attribute, which renders nothing.
+    MissingPosition {
+        /// The index of the parent mesh.
+        mesh: usize,
+
+        /// The index of the primitive within its parent mesh.
+        primitive: usize,
+    },
+
+    /// Morph target weights, of a mesh or of a node instantiating it, whose
+    /// count differs from the number of morph targets of a primitive.
+    MorphWeightMismatch {
+        /// The index of the node whose weights differ, or `None` for the
+        /// default weights of the mesh.
+        node: Option<usize>,
+
+        /// The index of the mesh.
+        mesh: usize,
+
+        /// The index of the primitive within the mesh.
+        primitive: usize,
+
+        /// The number of morph targets of the primitive.
+        targets: usize,
+
+        /// The number of weights.
+        weights: usize,
+    },
+}
+
+impl ContentIssue {
+    /// Returns a stable, machine-readable code identifying the kind of issue,
+    /// e.g. for filtering lints or reporting them in CI.
+    pub fn code(&self) -> &'static str {
+        match *self {
+            ContentIssue::SkinWithoutSkinnedMesh { .. } => "skin-without-skinned-mesh",
+            ContentIssue::MissingPosition { .. } => "missing-position",
+            ContentIssue::MorphWeightMismatch { .. } => "morph-weight-mismatch",
+        }
+    }
+}
+
+impl fmt::Display for ContentIssue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: ", self.code())?;
+        match *self {
+            ContentIssue::SkinWithoutSkinnedMesh { node, skin } => write!(
+                f,
+                "nodes[{}] has skins[{}] but no mesh with joints and weights",
+                node,
+                skin,
+            ),
+            ContentIssue::MissingPosition { mesh, primitive } => write!(
+                f,
+                "meshes[{}].primitives[{}] has no POSITION attribute",
+                mesh,
+                primitive,
+            ),
+            ContentIssue::MorphWeightMismatch { node, mesh, primitive, targets, weights } => {
+                match node {
+                    Some(node) => write!(f, "nodes[{}] has ", node)?,
+                    None => write!(f, "meshes[{}] has ", mesh)?,
+                }
+                write!(
+                    f,
+                    "{} weights but meshes[{}].primitives[{}] has {} morph targets",
+                    weights,
+                    mesh,
+                    primitive,
+                    targets,
+                )
+            },
+        }
+    }
+}
+
+/// Squared lengths and areas at or below this value are considered zero.
+const EPSILON: f32 = 1.0e-12;
+
+fn is_finite(v: &[f32; 3]) -> bool {
+    v.iter().all(|x| x.is_finite())
+}
+
+fn sub(a: &[f32; 3], b: &[f32; 3]) -> [f32; 3] {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+fn cross(a: &[f32; 3], b: &[f32; 3]) -> [f32; 3] {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+fn length_squared(v: &[f32; 3]) -> f32 {
+    v[0] * v[0] + v[1] * v[1] + v[2] * v[2]
+}
+
+/// Returns the vertex indices of every triangle of a primitive in draw order.
+///
+/// Returns an empty list for point and line primitives.
+pub fn triangles<S: Source>(primitive: &gltf::Primitive, source: &S) -> Vec<[u32; 3]> {
+    let indices: Vec<u32> = match primitive.indices_u32(source) {
+        Some(iter) => iter.collect(),
+        None => {
+            let count = primitive.get(&Semantic::Positions).map(|a| a.count()).unwrap_or(0);
+            (0..count as u32).collect()
+        },
+    };
+    match primitive.mode() {
+        Mode::Triangles => {
+            indices
+                .chunks(3)
+                .filter(|c| c.len() == 3)
+                .map(|c| [c[0], c[1], c[2]])
+                .collect()
+        },
+        Mode::TriangleStrip => {
+            (2..indices.len())
+                .map(|i| if i % 2 == 0 {
+                    [indices[i - 2], indices[i - 1], indices[i]]
+                } else {
+                    [indices[i - 1], indices[i - 2], indices[i]]
+                })
+                .collect()
+        },
+        Mode::TriangleFan => {
+            (2..indices.len())
+                .map(|i| [indices[0], indices[i - 1], indices[i]])
+                .collect()
+        },
+        _ => vec![],
+    }
+}
+
+/// Checks the geometry of a single primitive.
+///
+/// Detects degenerate triangles, non-finite positions and normals, and
+/// zero-length normals.
+pub fn primitive_geometry<S: Source>(
+    primitive: &gltf::Primitive,
+    source: &S,
+) -> Vec<GeometryKind> {
+    let mut issues = vec![];
+    let positions: Vec<[f32; 3]> = match primitive.positions(source) {
+        Some(iter) => iter.collect(),
+        None => vec![],
+    };
+    for (vertex, position) in positions.iter().enumerate() {
+        if !is_finite(position) {
+            issues.push(GeometryKind::NonFinitePosition { vertex });
+        }
+    }
+    if let Some(normals) = primitive.normals(source) {
+        for (vertex, normal) in normals.enumerate() {
+            if !is_finite(&normal) {
+                issues.push(GeometryKind::NonFiniteNormal { vertex });
+            } else if length_squared(&normal) <= EPSILON {
+                issues.push(GeometryKind::ZeroLengthNormal { vertex });
+            }
+        }
+    }
+    for (triangle, vertices) in triangles(primitive, source).into_iter().enumerate() {
+        let [a, b, c] = vertices;
+        let degenerate = if a == b || b == c || a == c {
+            true
+        } else {
+            let get = |i: u32| positions.get(i as usize).filter(|p| is_finite(p));
+            match (get(a), get(b), get(c)) {
+                (Some(pa), Some(pb), Some(pc)) => {
+                    length_squared(&cross(&sub(pb, pa), &sub(pc, pa))) <= EPSILON
+                },
+                _ => false,
+            }
+        };
+        if degenerate {
+            issues.push(GeometryKind::DegenerateTriangle { triangle, vertices });
+        }
+    }
+    issues
+}
+
+/// Checks the geometry of every primitive of an asset.
+///
+/// See `primitive_geometry` for the problems detected.
+pub fn geometry<S: Source>(gltf: &Gltf, source: &S) -> Vec<GeometryIssue> {
+    let mut issues = vec![];
+    for mesh in gltf.meshes() {
+        for primitive in mesh.primitives() {
+            for kind in primitive_geometry(&primitive, source) {
+                issues.push(GeometryIssue {
+                    mesh: mesh.index(),
+                    primitive: primitive.index(),
+                    kind,
+                });
+            }
+        }
+    }
+    issues
+}
+
+/// Finds accessors whose offset or stride is not a multiple of their
+/// component size.
+///
+/// The specification requires the sum of the accessor and buffer view offsets
+/// and the buffer view stride to be multiples of the component size.
+pub fn alignment(gltf: &Gltf) -> Vec<Misalignment> {
+    let mut misaligned = vec![];
+    for accessor in gltf.accessors() {
+        let view = match accessor.view() {
+            Some(view) => view,
+            None => continue,
+        };
+        let offset = view.offset() + accessor.offset();
+        let stride = view.stride();
+        let alignment = accessor.data_type().size();
+        if offset % alignment != 0 || stride.is_some_and(|stride| stride % alignment != 0) {
+            misaligned.push(Misalignment {
+                accessor: accessor.index(),
+                offset,
+                stride,
+                alignment,
+            });
+        }
+    }
+    misaligned
+}
+
+/// Compares the declared `min` and `max` of an accessor against the bounds of
+/// its data, reporting every component that differs by more than `epsilon`.
+///
+/// Only the bounds the accessor declares are checked. As the specification
+/// requires, bounds of normalized integer accessors are compared against the
+/// stored integers. Bounds written as decimal JSON rarely round trip exactly
+/// through `f32`, so `epsilon` should not be zero for float accessors; about
+/// `1e-6` times the magnitude of the bounds is typical. Sparse accessors and accessors
+/// without elements are not checked.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn run() -> Result<(), Box<std::error::Error>> {
+/// use gltf_utils::lint::accessor_bounds;
+///
+/// let gltf = gltf::Gltf::from_slice(include_bytes!("../../examples/Box.gltf"))?
+///     .validate_completely()?;
+/// let buffers = vec![include_bytes!("../../examples/Box0.bin").to_vec()];
+/// for accessor in gltf.accessors() {
+///     for deviation in accessor_bounds(&accessor, &buffers, 1e-6) {
+///         println!("{}", deviation);
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// # fn main() { run().unwrap() }
+/// ```
+pub fn accessor_bounds<S: Source>(
+    accessor: &gltf::Accessor,
+    source: &S,
+    epsilon: f64,
+) -> Vec<BoundsDeviation> {
+    let (min, max) = (accessor.min(), accessor.max());
+    if (min.is_none() && max.is_none()) || accessor.sparse().is_some() || accessor.count() == 0 {
+        return vec![];
+    }
+
+    let components = accessor.dimensions().multiplicity();
+    let mut computed_min = vec![f64::INFINITY; components];
+    let mut computed_max = vec![f64::NEG_INFINITY; components];
+    for (i, x) in accessor.iter_f64(source).unnormalized().enumerate() {
+        computed_min[i % components] = computed_min[i % components].min(x);
+        computed_max[i % components] = computed_max[i % components].max(x);
+    }
+
+    let mut deviations = vec![];
+    for (bound, declared, computed) in [
+        (Bound::Min, min, computed_min),
+        (Bound::Max, max, computed_max),
+    ] {
+        let declared = match declared {
+            Some(declared) => declared,
+            None => continue,
+        };
+        for (component, computed) in computed.into_iter().enumerate() {
+            let declared = declared.get(component).and_then(|value| value.as_f64());
+            let deviation = BoundsDeviation {
+                accessor: accessor.index(),
+                bound,
+                component,
+                declared,
+                computed,
+            };
+            let difference = deviation.deviation();
+            if difference > epsilon || difference.is_nan() {
+                deviations.push(deviation);
+            }
+        }
+    }
+    deviations
+}
+
+/// Compares the declared bounds of every accessor of an asset against the
+/// bounds of their data.
+///
+/// See `accessor_bounds` for details.
+pub fn bounds<S: Source>(gltf: &Gltf, source: &S, epsilon: f64) -> Vec<BoundsDeviation> {
+    gltf.accessors()
+        .flat_map(|accessor| accessor_bounds(&accessor, source, epsilon))
+        .collect()
+}
+
+/// Finds structurally valid but nonsensical content: skins on nodes without
+/// skinned meshes, primitives without positions, and morph target weights
+/// whose count differs from the number of morph targets.
+///
+/// Unlike validation, these checks never reject an asset; viewers typically
+/// render such content as nothing, or ignore the offending data.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn run() -> Result<(), Box<std::error::Error>> {
+/// let gltf = gltf::Gltf::from_slice(include_bytes!("../../examples/Box.gltf"))?
+///     .validate_completely()?;
+/// for issue in gltf_utils::lint::content(&gltf) {
+///     println!("{}", issue);
+/// }
+/// # Ok(())
+/// # }
+/// # fn main() { run().unwrap() }
+/// ```
+pub fn content(gltf: &Gltf) -> Vec<ContentIssue> {
+    let mut issues = vec![];
+    for mesh in gltf.meshes() {
+        for primitive in mesh.primitives() {
+            if primitive.get(&Semantic::Positions).is_none() {
+                issues.push(ContentIssue::MissingPosition {
+                    mesh: mesh.index(),
+                    primitive: primitive.index(),
+                });
+            }
+        }
+        if let Some(weights) = mesh.weights() {
+            issues.extend(morph_weights(&mesh, None, weights.len()));
+        }
+    }
+    for node in gltf.nodes() {
+        let mesh = node.mesh();
+        if let Some(skin) = node.skin() {
+            let skinned = mesh.as_ref().is_some_and(|mesh| {
+                mesh.primitives().any(|primitive| {
+                    primitive.get(&Semantic::Joints(0)).is_some()
+                        && primitive.get(&Semantic::Weights(0)).is_some()
+                })
+            });
+            if !skinned {
+                issues.push(ContentIssue::SkinWithoutSkinnedMesh {
+                    node: node.index(),
+                    skin: skin.index(),
+                });
+            }
+        }
+        if let (Some(mesh), Some(weights)) = (mesh, node.weights()) {
+            issues.extend(morph_weights(&mesh, Some(node.index()), weights.len()));
+        }
+    }
+    issues
+}
+
+/// Compares a number of weights against the morph targets of every primitive
+/// of a mesh.
+fn morph_weights(mesh: &gltf::Mesh, node: Option<usize>, weights: usize) -> Vec<ContentIssue> {
+    mesh.primitives()
+        .map(|primitive| (primitive.index(), primitive.morph_targets().len()))
+        .filter(|&(_, targets)| targets != weights)
+        .map(|(primitive, targets)| ContentIssue::MorphWeightMismatch {
+            node,
+            mesh: mesh.index(),
+            primitive,
+            targets,
+            weights,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use byteorder::{LE, ByteOrder};
+    use gltf::Gltf;
+    use std::f32;
+
+    use super::*;
+
+    const JSON: &'static str = r#"{
+        "asset": { "version": "2.0" },
+        "buffers": [{ "byteLength": 108 }],
+        "bufferViews": [
+            { "buffer": 0, "byteOffset": 0, "byteLength": 48 },
+            { "buffer": 0, "byteOffset": 48, "byteLength": 48 },
+            { "buffer": 0, "byteOffset": 96, "byteLength": 12 }
+        ],
+        "accessors": [
+            { "bufferView": 0, "componentType": 5126, "count": 4, "type": "VEC3" },
+            { "bufferView": 1, "componentType": 5126, "count": 4, "type": "VEC3" },
+            { "bufferView": 2, "componentType": 5123, "count": 6, "type": "SCALAR" }
+        ],
+        "meshes": [{
+            "primitives": [{
+                "attributes": { "POSITION": 0, "NORMAL": 1 },
+                "indices": 2
+            }]
+        }]
+    }"#;
+
+    #[test]
+    fn detect() {
+        let positions = [
+            0.0, 0.0, 0.0,
+            1.0, 0.0, 0.0,
+            0.0, 1.0, 0.0,
+            f32::NAN, 0.0, 0.0,
+        ];
+        let normals = [
+            0.0, 0.0, 1.0,
+            0.0, 0.0, 1.0,
+            0.0, 0.0, 0.0,
+            0.0, 0.0, 1.0,
+        ];
+        let indices = [0, 1, 2, 0, 0, 1];
+        let mut data = vec![0; 108];
+        LE::write_f32_into(&positions, &mut data[0..48]);
+        LE::write_f32_into(&normals, &mut data[48..96]);
+        LE::write_u16_into(&indices, &mut data[96..108]);
+        let buffers = vec![data];
+
+        let gltf = Gltf::from_str(JSON).unwrap().skip_validation();
+        let issues: Vec<_> = geometry(&gltf, &buffers)
+            .into_iter()
+            .map(|issue| issue.kind)
+            .collect();
+        assert_eq!(
+            vec![
+                GeometryKind::NonFinitePosition { vertex: 3 },
+                GeometryKind::ZeroLengthNormal { vertex: 2 },
+                GeometryKind::DegenerateTriangle { triangle: 1, vertices: [0, 0, 1] },
+            ],
+            issues,
+        );
+    }
+
+    #[test]
+    fn declared_bounds() {
+        let json = r#"{
+            "asset": { "version": "2.0" },
+            "buffers": [{ "byteLength": 24 }],
+            "bufferViews": [
+                { "buffer": 0, "byteLength": 16 },
+                { "buffer": 0, "byteOffset": 16, "byteLength": 8 }
+            ],
+            "accessors": [
+                {
+                    "bufferView": 0, "componentType": 5126, "count": 2, "type": "VEC2",
+                    "min": [0.0, -1.0], "max": [2.5]
+                },
+                {
+                    "bufferView": 1, "componentType": 5121, "normalized": true,
+                    "count": 2, "type": "VEC4", "min": [0, 0, 0, 0], "max": [255, 128, 0, 255]
+                }
+            ]
+        }"#;
+        let mut data = vec![0; 24];
+        LE::write_f32_into(&[0.0, -1.0, 2.0, 1.0], &mut data[0..16]);
+        data[16..24].copy_from_slice(&[255, 128, 0, 255, 0, 0, 0, 0]);
+        let buffers = vec![data];
+
+        let gltf = Gltf::from_str(json).unwrap().skip_validation();
+        let deviations = bounds(&gltf, &buffers, 1e-6);
+        assert_eq!(
+            vec![
+                BoundsDeviation {
+                    accessor: 0,
+                    bound: Bound::Max,
+                    component: 0,
+                    declared: Some(2.5),
+                    computed: 2.0,
+                },
+                BoundsDeviation {
+                    accessor: 0,
+                    bound: Bound::Max,
+                    component: 1,
+                    declared: None,
+                    computed: 1.0,
+                },
+            ],
+            deviations,
+        );
+        assert_eq!(0.5, deviations[0].deviation());
+        assert_eq!(
+            "accessors[0].max[0]: declared 2.5, computed 2 (off by 0.5)",
+            deviations[0].to_string(),
+        );
+        assert_eq!(1, bounds(&gltf, &buffers, 1.0).len());
+    }
+
+    #[test]
+    fn misaligned() {
+        let json = r#"{
+            "asset": { "version": "2.0" },
+            "buffers": [{ "byteLength": 13 }],
+            "bufferViews": [{ "buffer": 0, "byteOffset": 1, "byteLength": 12 }],
+            "accessors": [
+                { "bufferView": 0, "componentType": 5126, "count": 1, "type": "VEC3" }
+            ],
+            "meshes": [{ "primitives": [{ "attributes": { "POSITION": 0 } }] }]
+        }"#;
+        let mut data = vec![0; 13];
+        LE::write_f32_into(&[1.0, 2.0, 3.0], &mut data[1..13]);
+        let buffers = vec![data];
+
+        let gltf = Gltf::from_str(json).unwrap().skip_validation();
+        let misaligned = Misalignment { accessor: 0, offset: 1, stride: None, alignment: 4 };
+        assert_eq!(vec![misaligned], alignment(&gltf));
+
+        let mesh = gltf.meshes().next().unwrap();
+        let primitive = mesh.primitives().next().unwrap();
+        let positions: Vec<_> = primitive.positions(&buffers).unwrap().collect();
+        assert_eq!(vec![[1.0, 2.0, 3.0]], positions);
+    }
+
+    #[test]
+    fn nonsensical_content() {
+        let json = r#"{
+            "asset": { "version": "2.0" },
+            "buffers": [{ "byteLength": 4 }],
+            "bufferViews": [{ "buffer": 0, "byteLength": 4 }],
+            "accessors": [{ "bufferView": 0, "componentType": 5126, "count": 1, "type": "SCALAR" }],
+            "meshes": [
+                {
+                    "primitives": [
+                        { "attributes": { "POSITION": 0 }, "targets": [{}, {}] },
+                        { "attributes": { "NORMAL": 0 }, "targets": [{}, {}] }
+                    ],
+                    "weights": [0.5]
+                },
+                {
+                    "primitives": [
+                        { "attributes": { "POSITION": 0, "JOINTS_0": 0, "WEIGHTS_0": 0 } }
+                    ]
+                }
+            ],
+            "skins": [{ "joints": [2] }],
+            "nodes": [
+                { "mesh": 0, "skin": 0, "weights": [0.5, 0.5] },
+                { "mesh": 1, "skin": 0, "weights": [0.5] },
+                {}
+            ]
+        }"#;
+        let gltf = Gltf::from_str(json).unwrap().skip_validation();
+        let issues = content(&gltf);
+        let codes: Vec<_> = issues.iter().map(ContentIssue::code).collect();
+        assert_eq!(
+            vec![
+                "missing-position",
+                "morph-weight-mismatch",
+                "morph-weight-mismatch",
+                "skin-without-skinned-mesh",
+                "morph-weight-mismatch",
+            ],
+            codes,
+        );
+        assert_eq!(
+            ContentIssue::MorphWeightMismatch {
+                node: Some(1),
+                mesh: 1,
+                primitive: 0,
+                targets: 0,
+                weights: 1,
+            },
+            issues[4],
+        );
+        assert_eq!(
+            "morph-weight-mismatch: meshes[0] has 1 weights but meshes[0].primitives[1] \
+             has 2 morph targets",
+            issues[2].to_string(),
+        );
+    }
+}
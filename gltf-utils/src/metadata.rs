@@ -0,0 +1,478 @@
+use byteorder::{LE, ByteOrder};
+use gltf::{self, json};
+use gltf::accessor::DataType;
+use gltf::metadata::{ComponentType, ElementType, FeatureId, Property};
+
+use {AccessorIter, Source};
+
+/// A property value of a single feature.
+///
+/// Numeric values of properties with `normalized`, `offset`, or `scale` are
+/// returned transformed, as `Float`. Other values are returned as stored.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Value {
+    /// A `BOOLEAN` value.
+    Bool(bool),
+
+    /// A value of a signed integer component type.
+    Int(i64),
+
+    /// A value of an unsigned integer component type.
+    UInt(u64),
+
+    /// A value of a floating point component type.
+    Float(f64),
+
+    /// A `STRING` value.
+    String(String),
+
+    /// The name of an `ENUM` value.
+    Enum(String),
+
+    /// An array, vector, or matrix, with matrices in column-major order.
+    Array(Vec<Value>),
+}
+
+impl Value {
+    /// Returns the value of a numeric scalar as `f64`.
+    pub fn as_f64(&self) -> Option<f64> {
+        match *self {
+            Value::Int(x) => Some(x as f64),
+            Value::UInt(x) => Some(x as f64),
+            Value::Float(x) => Some(x),
+            _ => None,
+        }
+    }
+
+    /// Returns the value of a `BOOLEAN`.
+    pub fn as_bool(&self) -> Option<bool> {
+        match *self {
+            Value::Bool(x) => Some(x),
+            _ => None,
+        }
+    }
+
+    /// Returns the value of a `STRING` or the name of an `ENUM` value.
+    pub fn as_str(&self) -> Option<&str> {
+        match *self {
+            Value::String(ref x) | Value::Enum(ref x) => Some(x),
+            _ => None,
+        }
+    }
+
+    /// Returns the elements of an array, vector, or matrix.
+    pub fn as_array(&self) -> Option<&[Value]> {
+        match *self {
+            Value::Array(ref x) => Some(x),
+            _ => None,
+        }
+    }
+}
+
+/// Reads the feature ID of every vertex of a primitive.
+///
+/// Returns `None` for IDs stored in textures, which must be sampled by the
+/// caller, and for IDs stored in an attribute not available to the reader,
+/// i.e. without the `extras` feature of the `gltf` crate.
+pub fn feature_ids<'a, S>(
+    primitive: &gltf::Primitive<'a>,
+    feature_id: &FeatureId<'a>,
+    source: &S,
+) -> Option<Vec<u32>>
+    where S: Source
+{
+    if feature_id.is_implicit() {
+        let count = primitive.get(&gltf::Semantic::Positions)?.count() as u32;
+        return Some((0..count).collect());
+    }
+    let accessor = feature_id.accessor()?;
+    let ids = match accessor.data_type() {
+        DataType::U8 => AccessorIter::<u8>::new(accessor, source).map(u32::from).collect(),
+        DataType::U16 => AccessorIter::<u16>::new(accessor, source).map(u32::from).collect(),
+        DataType::U32 => AccessorIter::<u32>::new(accessor, source).collect(),
+        DataType::F32 => AccessorIter::<f32>::new(accessor, source).map(|x| x as u32).collect(),
+        _ => return None,
+    };
+    Some(ids)
+}
+
+/// Reads the values of a property for every feature of its table.
+///
+/// Returns `None` if the property is not defined by an embedded schema or its
+/// buffer views are too short for the table.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// # fn run() -> Result<(), Box<std::error::Error>> {
+/// use gltf_utils::metadata;
+///
+/// let gltf = gltf::Gltf::from_slice(&std::fs::read("path/to/tile.gltf")?)?
+///     .validate_completely()?;
+/// let buffers = vec![std::fs::read("path/to/tile.bin")?];
+/// for table in gltf.property_tables() {
+///     for property in table.properties() {
+///         let values = metadata::read(&property, &buffers).unwrap_or_default();
+///         println!("{}: {:?}", property.id(), values);
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// # fn main() {}
+/// ```
+pub fn read<S: Source>(property: &Property, source: &S) -> Option<Vec<Value>> {
+    let definition = property.definition()?;
+    let type_ = match definition.type_ {
+        json::validation::Checked::Valid(type_) => type_,
+        json::validation::Checked::Invalid => return None,
+    };
+    let component_type = match type_ {
+        ElementType::Enum => Some(property.enum_type()?.value_type.unwrap()),
+        ElementType::String | ElementType::Boolean => None,
+        _ => Some(definition.component_type.as_ref()?.unwrap()),
+    };
+    let values = data(&property.values(), source)?;
+    let array_offsets = match property.array_offsets() {
+        Some(view) => Some(data(&view, source)?),
+        None => None,
+    };
+    let string_offsets = match property.string_offsets() {
+        Some(view) => Some(data(&view, source)?),
+        None => None,
+    };
+    let numeric = !matches!(type_, ElementType::Boolean | ElementType::String | ElementType::Enum);
+    let normalized = if definition.normalized { component_type } else { None };
+    let value_offset = property.as_json().offset.as_ref().or(definition.offset.as_ref());
+    let value_scale = property.as_json().scale.as_ref().or(definition.scale.as_ref());
+    let transformed =
+        numeric && (normalized.is_some() || value_offset.is_some() || value_scale.is_some());
+
+    let element = |index: usize| -> Option<Value> {
+        match type_ {
+            ElementType::Boolean => {
+                let byte = *values.get(index / 8)?;
+                Some(Value::Bool(byte & (1 << (index % 8)) != 0))
+            },
+            ElementType::String => {
+                let offsets = string_offsets?;
+                let offset_type = property.string_offset_type();
+                let begin = offset(offsets, offset_type, index)?;
+                let end = offset(offsets, offset_type, index + 1)?;
+                let bytes = values.get(begin..end)?;
+                String::from_utf8(bytes.to_vec()).ok().map(Value::String)
+            },
+            ElementType::Enum => {
+                let value = match number(values, component_type?, index)? {
+                    Value::Int(x) => x,
+                    Value::UInt(x) => x as i64,
+                    _ => return None,
+                };
+                let enum_type = property.enum_type()?;
+                Some(match enum_type.values.iter().find(|v| v.value == value) {
+                    Some(v) => Value::Enum(v.name.clone()),
+                    None => Value::Int(value),
+                })
+            },
+            _ => {
+                let components = type_.components();
+                if components == 1 {
+                    return number(values, component_type?, index);
+                }
+                let begin = index * components;
+                (begin..begin + components)
+                    .map(|i| number(values, component_type?, i))
+                    .collect::<Option<_>>()
+                    .map(Value::Array)
+            },
+        }
+    };
+
+    let value = |feature: usize| -> Option<Value> {
+        if !definition.array {
+            return element(feature);
+        }
+        let (begin, end) = match (definition.count, array_offsets) {
+            (Some(count), _) => {
+                let count = count as usize;
+                (feature * count, (feature + 1) * count)
+            },
+            (None, Some(offsets)) => {
+                let offset_type = property.array_offset_type();
+                let begin = offset(offsets, offset_type, feature)?;
+                (begin, offset(offsets, offset_type, feature + 1)?)
+            },
+            (None, None) => return None,
+        };
+        (begin..end).map(&element).collect::<Option<_>>().map(Value::Array)
+    };
+
+    (0..property.table().count())
+        .map(|feature| {
+            let value = value(feature)?;
+            if transformed {
+                Some(transform(value, normalized, value_offset, value_scale))
+            } else {
+                Some(value)
+            }
+        })
+        .collect()
+}
+
+/// Applies the `normalized`, `offset`, and `scale` transforms of a property to
+/// every component of a value.
+///
+/// The offset and scale have the shape of the value, i.e. they are arrays for
+/// vectors, matrices, and fixed length arrays.
+fn transform(
+    value: Value,
+    normalized: Option<ComponentType>,
+    offset: Option<&json::Value>,
+    scale: Option<&json::Value>,
+) -> Value {
+    if let Value::Array(values) = value {
+        let values = values.into_iter().enumerate().map(|(i, value)| {
+            transform(value, normalized, offset.and_then(|x| x.get(i)), scale.and_then(|x| x.get(i)))
+        });
+        return Value::Array(values.collect());
+    }
+    let mut x = match value.as_f64() {
+        Some(x) => x,
+        None => return value,
+    };
+    if let Some(component_type) = normalized {
+        x = match component_type {
+            ComponentType::I8 => (x / i8::MAX as f64).max(-1.0),
+            ComponentType::U8 => x / u8::MAX as f64,
+            ComponentType::I16 => (x / i16::MAX as f64).max(-1.0),
+            ComponentType::U16 => x / u16::MAX as f64,
+            ComponentType::I32 => (x / i32::MAX as f64).max(-1.0),
+            ComponentType::U32 => x / u32::MAX as f64,
+            ComponentType::I64 => (x / i64::MAX as f64).max(-1.0),
+            ComponentType::U64 => x / u64::MAX as f64,
+            ComponentType::F32 | ComponentType::F64 => x,
+        };
+    }
+    if let Some(scale) = scale.and_then(json::Value::as_f64) {
+        x *= scale;
+    }
+    if let Some(offset) = offset.and_then(json::Value::as_f64) {
+        x += offset;
+    }
+    Value::Float(x)
+}
+
+/// Returns the data of a buffer view, if the buffer is long enough.
+fn data<'s, S: Source>(view: &gltf::buffer::View, source: &'s S) -> Option<&'s [u8]> {
+    let buffer = source.source_buffer(&view.buffer());
+    buffer.get(view.offset()..view.offset() + view.length())
+}
+
+/// Reads the number at the given component index.
+fn number(data: &[u8], component_type: ComponentType, index: usize) -> Option<Value> {
+    let size = component_type.size();
+    let bytes = data.get(index * size..(index + 1) * size)?;
+    Some(match component_type {
+        ComponentType::I8 => Value::Int(bytes[0] as i8 as i64),
+        ComponentType::U8 => Value::UInt(bytes[0] as u64),
+        ComponentType::I16 => Value::Int(LE::read_i16(bytes) as i64),
+        ComponentType::U16 => Value::UInt(LE::read_u16(bytes) as u64),
+        ComponentType::I32 => Value::Int(LE::read_i32(bytes) as i64),
+        ComponentType::U32 => Value::UInt(LE::read_u32(bytes) as u64),
+        ComponentType::I64 => Value::Int(LE::read_i64(bytes)),
+        ComponentType::U64 => Value::UInt(LE::read_u64(bytes)),
+        ComponentType::F32 => Value::Float(LE::read_f32(bytes) as f64),
+        ComponentType::F64 => Value::Float(LE::read_f64(bytes)),
+    })
+}
+
+/// Reads the array or string offset at the given index.
+fn offset(data: &[u8], component_type: ComponentType, index: usize) -> Option<usize> {
+    match number(data, component_type, index)? {
+        Value::UInt(x) => Some(x as usize),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use gltf::Gltf;
+
+    use super::*;
+
+    const JSON: &'static str = r#"{
+        "asset": { "version": "2.0" },
+        "extensionsUsed": ["EXT_mesh_features", "EXT_structural_metadata"],
+        "extensions": {
+            "EXT_structural_metadata": {
+                "schema": {
+                    "id": "city",
+                    "classes": {
+                        "building": {
+                            "properties": {
+                                "height": { "type": "SCALAR", "componentType": "UINT16" },
+                                "name": { "type": "STRING" },
+                                "kind": { "type": "ENUM", "enumType": "kind" },
+                                "listed": { "type": "BOOLEAN" },
+                                "floors": {
+                                    "type": "SCALAR",
+                                    "componentType": "UINT8",
+                                    "array": true
+                                },
+                                "origin": { "type": "VEC2", "componentType": "INT8" }
+                            }
+                        }
+                    },
+                    "enums": {
+                        "kind": {
+                            "valueType": "UINT8",
+                            "values": [
+                                { "name": "house", "value": 0 },
+                                { "name": "shop", "value": 1 }
+                            ]
+                        }
+                    }
+                },
+                "propertyTables": [{
+                    "class": "building",
+                    "count": 2,
+                    "properties": {
+                        "height": { "values": 0 },
+                        "name": {
+                            "values": 1,
+                            "stringOffsets": 2,
+                            "stringOffsetType": "UINT8"
+                        },
+                        "kind": { "values": 3 },
+                        "listed": { "values": 4 },
+                        "floors": {
+                            "values": 5,
+                            "arrayOffsets": 6,
+                            "arrayOffsetType": "UINT8"
+                        },
+                        "origin": { "values": 7 }
+                    }
+                }]
+            }
+        },
+        "buffers": [{ "byteLength": 26 }],
+        "bufferViews": [
+            { "buffer": 0, "byteOffset": 0, "byteLength": 4 },
+            { "buffer": 0, "byteOffset": 4, "byteLength": 5 },
+            { "buffer": 0, "byteOffset": 9, "byteLength": 3 },
+            { "buffer": 0, "byteOffset": 12, "byteLength": 2 },
+            { "buffer": 0, "byteOffset": 14, "byteLength": 1 },
+            { "buffer": 0, "byteOffset": 15, "byteLength": 3 },
+            { "buffer": 0, "byteOffset": 18, "byteLength": 3 },
+            { "buffer": 0, "byteOffset": 21, "byteLength": 4 }
+        ],
+        "accessors": [{
+            "bufferView": 0, "componentType": 5126, "count": 3, "type": "VEC3",
+            "min": [0, 0, 0], "max": [1, 1, 1]
+        }],
+        "meshes": [{
+            "primitives": [{
+                "attributes": { "POSITION": 0 },
+                "extensions": {
+                    "EXT_mesh_features": {
+                        "featureIds": [{ "featureCount": 3, "propertyTable": 0 }]
+                    }
+                }
+            }]
+        }]
+    }"#;
+
+    fn buffer() -> Vec<u8> {
+        let mut data = vec![10, 0, 0, 1];
+        data.extend_from_slice(b"shops");
+        data.extend_from_slice(&[0, 4, 5]);
+        data.extend_from_slice(&[1, 7]);
+        data.extend_from_slice(&[0b10]);
+        data.extend_from_slice(&[3, 1, 2]);
+        data.extend_from_slice(&[0, 1, 3]);
+        data.extend_from_slice(&[0xFF, 2, 4, 0x80, 0]);
+        data
+    }
+
+    #[test]
+    fn property_table() {
+        let gltf = Gltf::from_str(JSON).unwrap().validate_completely().unwrap();
+        let buffers = vec![buffer()];
+        let table = gltf.property_tables().next().unwrap();
+        assert_eq!(2, table.count());
+        assert_eq!("building", table.class_id());
+        let read = |id: &str| read(&table.get(id).unwrap(), &buffers).unwrap();
+        assert_eq!(vec![Value::UInt(10), Value::UInt(256)], read("height"));
+        let names = read("name");
+        assert_eq!(Some("shop"), names[0].as_str());
+        assert_eq!(Some("s"), names[1].as_str());
+        assert_eq!(vec![Value::Enum("shop".to_string()), Value::Int(7)], read("kind"));
+        assert_eq!(vec![Value::Bool(false), Value::Bool(true)], read("listed"));
+        let floors = read("floors");
+        assert_eq!(Some(&[Value::UInt(3)][..]), floors[0].as_array());
+        assert_eq!(Some(&[Value::UInt(1), Value::UInt(2)][..]), floors[1].as_array());
+        let origin = vec![Value::Int(-1), Value::Int(2)];
+        assert_eq!(Value::Array(origin), read("origin")[0]);
+    }
+
+    #[test]
+    fn transformed_values() {
+        let json = JSON
+            .replace(
+                r#""componentType": "UINT16" }"#,
+                r#""componentType": "UINT16", "offset": 5, "scale": 0.5 }"#,
+            )
+            .replace(
+                r#""componentType": "INT8" }"#,
+                r#""componentType": "INT8", "normalized": true }"#,
+            )
+            .replace(r#""origin": { "values": 7 }"#, r#""origin": { "values": 7, "offset": [1, 0] }"#);
+        let gltf = Gltf::from_str(&json).unwrap().validate_completely().unwrap();
+        let buffers = vec![buffer()];
+        let table = gltf.property_tables().next().unwrap();
+        let read = |id: &str| read(&table.get(id).unwrap(), &buffers).unwrap();
+        assert_eq!(vec![Value::Float(10.0), Value::Float(133.0)], read("height"));
+        let origin = vec![Value::Float(1.0 - 1.0 / 127.0), Value::Float(2.0 / 127.0)];
+        assert_eq!(Value::Array(origin), read("origin")[0]);
+        let origin = vec![Value::Float(1.0 + 4.0 / 127.0), Value::Float(-1.0)];
+        assert_eq!(Value::Array(origin), read("origin")[1]);
+        assert_eq!(vec![Value::Enum("shop".to_string()), Value::Int(7)], read("kind"));
+    }
+
+    #[test]
+    fn implicit_feature_ids() {
+        let gltf = Gltf::from_str(JSON).unwrap().validate_completely().unwrap();
+        let buffers = vec![buffer()];
+        let mesh = gltf.meshes().next().unwrap();
+        let primitive = mesh.primitives().next().unwrap();
+        let feature_id = primitive.feature_ids().next().unwrap();
+        assert!(feature_id.is_implicit());
+        assert_eq!(0, feature_id.property_table().unwrap().index());
+        assert_eq!(Some(vec![0, 1, 2]), feature_ids(&primitive, &feature_id, &buffers));
+    }
+
+    #[cfg(feature = "extras")]
+    #[test]
+    fn attribute_feature_ids() {
+        let json = JSON
+            .replace(r#""POSITION": 0 }"#, r#""POSITION": 0, "_FEATURE_ID_0": 1 }"#)
+            .replace(r#""featureCount": 3"#, r#""featureCount": 3, "attribute": 0"#)
+            .replace(r#""max": [1, 1, 1]
+        }]"#, r#""max": [1, 1, 1]
+        }, { "bufferView": 5, "componentType": 5121, "count": 3, "type": "SCALAR" }]"#);
+        let gltf = Gltf::from_str(&json).unwrap().validate_completely().unwrap();
+        let buffers = vec![buffer()];
+        let mesh = gltf.meshes().next().unwrap();
+        let primitive = mesh.primitives().next().unwrap();
+        let feature_id = primitive.feature_ids().next().unwrap();
+        assert_eq!(Some(0), feature_id.attribute());
+        assert_eq!(Some(vec![3, 1, 2]), feature_ids(&primitive, &feature_id, &buffers));
+    }
+
+    #[test]
+    fn unknown_property_table() {
+        let json = JSON.replace(r#""propertyTable": 0"#, r#""propertyTable": 1"#);
+        assert!(Gltf::from_str(&json).unwrap().validate_minimally().is_err());
+        let json = JSON.replace(r#""class": "building""#, r#""class": "tree""#);
+        assert!(Gltf::from_str(&json).unwrap().validate_completely().is_err());
+    }
+}
@@ -0,0 +1,413 @@
+use std::collections::BTreeSet;
+
+use gltf::{json, Gltf};
+
+use prune;
+
+/// The name of the scene orphan nodes are attached to by `Policy::Attach`,
+/// with the `names` feature.
+pub const SCENE_NAME: &'static str = "orphans";
+
+/// The nodes outside every scene and the objects attached only to them.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Report {
+    /// The indices of the nodes not reachable from any scene.
+    pub nodes: Vec<usize>,
+
+    /// The indices of the meshes attached only to orphan nodes.
+    pub meshes: Vec<usize>,
+
+    /// The indices of the cameras attached only to orphan nodes.
+    pub cameras: Vec<usize>,
+
+    /// The indices of the skins attached only to orphan nodes.
+    pub skins: Vec<usize>,
+}
+
+impl Report {
+    /// Returns `true` if every node is reachable from a scene.
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+}
+
+/// What to do with orphan nodes.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Policy {
+    /// Leave the asset unchanged.
+    Keep,
+
+    /// Remove orphan nodes and the meshes, cameras, and skins attached only to
+    /// them, along with the animation channels targeting them.
+    Prune,
+
+    /// Attach the root orphan nodes to a new scene, named `SCENE_NAME` with
+    /// the `names` feature, so that they survive tools that drop nodes outside
+    /// every scene. The default scene is unchanged.
+    Attach,
+}
+
+/// Finds the nodes not reachable from any scene and the meshes, cameras, and
+/// skins attached only to them.
+///
+/// Nodes are reachable from the root nodes of every scene through their
+/// children. Nodes used as joints or skeleton roots by the skins of reachable
+/// nodes count as reachable too, since those skins would break without them.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn run() -> Result<(), Box<std::error::Error>> {
+/// let gltf = gltf::Gltf::from_slice(include_bytes!("../../examples/Box.gltf"))?
+///     .validate_completely()?;
+/// assert!(gltf_utils::orphans::find(&gltf).is_empty());
+/// # Ok(())
+/// # }
+/// # fn main() { run().unwrap() }
+/// ```
+pub fn find(gltf: &Gltf) -> Report {
+    let root = gltf.as_json();
+    let reachable = reachable(root);
+
+    let mut report = Report::default();
+    let mut meshes = vec![(false, false); root.meshes.len()];
+    let mut cameras = vec![(false, false); root.cameras.len()];
+    let mut skins = vec![(false, false); root.skins.len()];
+    for (index, node) in root.nodes.iter().enumerate() {
+        let attached = |used: &mut Vec<(bool, bool)>, object: Option<usize>| {
+            if let Some(object) = object {
+                if reachable[index] {
+                    used[object].0 = true;
+                } else {
+                    used[object].1 = true;
+                }
+            }
+        };
+        attached(&mut meshes, node.mesh.as_ref().map(|mesh| mesh.value()));
+        attached(&mut cameras, node.camera.as_ref().map(|camera| camera.value()));
+        attached(&mut skins, node.skin.as_ref().map(|skin| skin.value()));
+        if !reachable[index] {
+            report.nodes.push(index);
+        }
+    }
+    report.meshes = only_removed(meshes);
+    report.cameras = only_removed(cameras);
+    report.skins = only_removed(skins);
+    report
+}
+
+/// Returns the indices of the objects used by removed nodes and no others,
+/// given whether each object is used by `(kept, removed)` nodes.
+fn only_removed(used: Vec<(bool, bool)>) -> Vec<usize> {
+    used.into_iter()
+        .enumerate()
+        .filter(|&(_, (kept, removed))| removed && !kept)
+        .map(|(index, _)| index)
+        .collect()
+}
+
+/// Returns whether each node is reachable from a scene.
+fn reachable(root: &json::Root) -> Vec<bool> {
+    let mut reachable = vec![false; root.nodes.len()];
+    let mut stack: Vec<usize> = root.scenes
+        .iter()
+        .flat_map(|scene| scene.nodes.iter().map(|node| node.value()))
+        .collect();
+    while let Some(index) = stack.pop() {
+        if reachable[index] {
+            continue;
+        }
+        reachable[index] = true;
+        let node = &root.nodes[index];
+        if let Some(ref children) = node.children {
+            stack.extend(children.iter().map(|child| child.value()));
+        }
+        if let Some(ref skin) = node.skin {
+            let skin = &root.skins[skin.value()];
+            stack.extend(skin.joints.iter().map(|joint| joint.value()));
+            stack.extend(skin.skeleton.as_ref().map(|skeleton| skeleton.value()));
+        }
+    }
+    reachable
+}
+
+/// Finds the orphan nodes of an asset and applies a policy to them, returning
+/// the transformed asset and the report of what was found.
+///
+/// Pruning renumbers the remaining nodes, meshes, cameras, and skins.
+/// Accessors used only by pruned meshes, skins, and animations are removed
+/// too, renumbering the rest. Materials and buffer views are left in place
+/// even if they are no longer referenced, and animations left without
+/// channels are removed.
+pub fn resolve(gltf: Gltf, policy: Policy) -> (Gltf, Report) {
+    let report = find(&gltf);
+    if report.is_empty() || policy == Policy::Keep {
+        return (gltf, report);
+    }
+    let mut root = gltf.into_json();
+    match policy {
+        Policy::Keep => unreachable!(),
+        Policy::Prune => prune(&mut root, &report),
+        Policy::Attach => attach(&mut root, &report),
+    }
+    (Gltf::from_json(root).skip_validation(), report)
+}
+
+/// Removes nodes along with their descendants and the meshes, cameras, and
+/// skins attached only to them, returning the transformed asset and a report
+/// of everything removed.
+///
+/// Nodes that were outside every scene beforehand are left in place, as are
+/// descendants still used as joints by the skin of a remaining node, which
+/// are detached from the hierarchy instead. Renumbering follows `resolve`
+/// with `Policy::Prune`.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn run() -> Result<(), Box<std::error::Error>> {
+/// let gltf = gltf::Gltf::from_slice(include_bytes!("../../examples/Box.gltf"))?
+///     .validate_completely()?;
+/// let (gltf, report) = gltf_utils::orphans::remove_nodes(gltf, &[1]);
+/// assert_eq!(vec![1], report.nodes);
+/// assert_eq!(vec![0], report.meshes);
+/// assert_eq!(1, gltf.nodes().len());
+/// # Ok(())
+/// # }
+/// # fn main() { run().unwrap() }
+/// ```
+pub fn remove_nodes(gltf: Gltf, nodes: &[usize]) -> (Gltf, Report) {
+    if nodes.is_empty() {
+        return (gltf, Report::default());
+    }
+    let before = find(&gltf);
+    let mut root = gltf.into_json();
+    let keep = |node: &json::Index<json::Node>| !nodes.contains(&node.value());
+    for scene in &mut root.scenes {
+        scene.nodes.retain(&keep);
+    }
+    for node in &mut root.nodes {
+        if let Some(mut children) = node.children.take() {
+            children.retain(&keep);
+            if !children.is_empty() {
+                node.children = Some(children);
+            }
+        }
+    }
+
+    let reachable = reachable(&root);
+    let removed: Vec<usize> = (0..root.nodes.len())
+        .filter(|index| !reachable[*index] && !before.nodes.contains(index))
+        .collect();
+
+    // Objects are removed only if no remaining node, orphan or not, uses them.
+    let mut meshes = vec![(false, false); root.meshes.len()];
+    let mut cameras = vec![(false, false); root.cameras.len()];
+    let mut skins = vec![(false, false); root.skins.len()];
+    for (index, node) in root.nodes.iter().enumerate() {
+        let is_removed = removed.contains(&index);
+        let attached = |used: &mut Vec<(bool, bool)>, object: Option<usize>| {
+            if let Some(object) = object {
+                if is_removed {
+                    used[object].1 = true;
+                } else {
+                    used[object].0 = true;
+                }
+            }
+        };
+        attached(&mut meshes, node.mesh.as_ref().map(|mesh| mesh.value()));
+        attached(&mut cameras, node.camera.as_ref().map(|camera| camera.value()));
+        attached(&mut skins, node.skin.as_ref().map(|skin| skin.value()));
+    }
+    let report = Report {
+        nodes: removed,
+        meshes: only_removed(meshes),
+        cameras: only_removed(cameras),
+        skins: only_removed(skins),
+    };
+    prune(&mut root, &report);
+    (Gltf::from_json(root).skip_validation(), report)
+}
+
+/// Removes orphan nodes and the objects attached only to them.
+///
+/// Nodes are removed with `json::edit::remove_nodes`, which also drops skins
+/// left without joints, animations left without channels, and the references
+/// of extensions to removed nodes. Accessors that lose their last reference
+/// are removed afterwards.
+fn prune(root: &mut json::Root, report: &Report) {
+    let before = root.reference_counts();
+    let set = |indices: &[usize]| indices.iter().cloned().collect::<BTreeSet<usize>>();
+    let (meshes, cameras, skins) = (set(&report.meshes), set(&report.cameras), set(&report.skins));
+    let mesh_map = prune::remap(root.meshes.len(), &meshes);
+    let camera_map = prune::remap(root.cameras.len(), &cameras);
+    let skin_map = prune::remap(root.skins.len(), &skins);
+    prune::remove(&mut root.meshes, &meshes);
+    prune::remove(&mut root.cameras, &cameras);
+    prune::remove(&mut root.skins, &skins);
+
+    fn index<T>(map: &[Option<u32>], index: &Option<json::Index<T>>) -> Option<json::Index<T>> {
+        index.as_ref().and_then(|index| map[index.value()]).map(json::Index::new)
+    }
+    for node in &mut root.nodes {
+        node.mesh = index(&mesh_map, &node.mesh);
+        node.camera = index(&camera_map, &node.camera);
+        node.skin = index(&skin_map, &node.skin);
+    }
+    json::edit::remove_nodes(root, &set(&report.nodes));
+
+    let after = root.reference_counts();
+    let accessors: BTreeSet<usize> = (0..root.accessors.len())
+        .filter(|&index| before.accessors[index] > 0 && after.accessors[index] == 0)
+        .collect();
+    if !accessors.is_empty() {
+        prune::accessors(root, &accessors);
+    }
+}
+
+/// Attaches the root orphan nodes to a new scene.
+fn attach(root: &mut json::Root, report: &Report) {
+    let mut is_child = vec![false; root.nodes.len()];
+    for node in &root.nodes {
+        for child in node.children.iter().flat_map(|children| children.iter()) {
+            is_child[child.value()] = true;
+        }
+    }
+    let scene = json::Scene {
+        nodes: report.nodes
+            .iter()
+            .filter(|&&node| !is_child[node])
+            .map(|&node| json::Index::new(node as u32))
+            .collect(),
+        #[cfg(feature = "names")]
+        name: Some(SCENE_NAME.to_string()),
+        ..Default::default()
+    };
+    root.scenes.push(scene);
+}
+
+#[cfg(test)]
+mod tests {
+    use gltf::Gltf;
+
+    use super::*;
+
+    const JSON: &'static str = r#"{
+        "asset": { "version": "2.0" },
+        "meshes": [
+            { "primitives": [{ "attributes": {} }] },
+            { "primitives": [{ "attributes": {} }] }
+        ],
+        "cameras": [{ "type": "perspective", "perspective": { "yfov": 1.0, "znear": 0.1 } }],
+        "skins": [{ "joints": [3] }],
+        "nodes": [
+            { "mesh": 0, "children": [1], "skin": 0 },
+            { "mesh": 1 },
+            { "mesh": 1, "camera": 0, "children": [4] },
+            {},
+            { "mesh": 0 }
+        ],
+        "animations": [
+            {
+                "channels": [{ "sampler": 0, "target": { "node": 2, "path": "scale" } }],
+                "samplers": [{ "input": 0, "output": 0 }]
+            },
+            {
+                "channels": [
+                    { "sampler": 0, "target": { "node": 4, "path": "scale" } },
+                    { "sampler": 0, "target": { "node": 1, "path": "scale" } }
+                ],
+                "samplers": [{ "input": 0, "output": 0 }]
+            }
+        ],
+        "scenes": [{ "nodes": [0] }]
+    }"#;
+
+    #[test]
+    fn report() {
+        let gltf = Gltf::from_str(JSON).unwrap().skip_validation();
+        let report = find(&gltf);
+        assert_eq!(vec![2, 4], report.nodes);
+        assert!(report.meshes.is_empty());
+        assert_eq!(vec![0], report.cameras);
+        assert!(report.skins.is_empty());
+    }
+
+    #[test]
+    fn prune() {
+        let gltf = Gltf::from_str(JSON).unwrap().skip_validation();
+        let (gltf, _) = resolve(gltf, Policy::Prune);
+        let root = gltf.as_json();
+        assert_eq!(3, root.nodes.len());
+        assert_eq!(0, root.cameras.len());
+        assert_eq!(2, root.skins[0].joints[0].value());
+        assert_eq!(1, root.animations.len());
+        assert_eq!(1, root.animations[0].channels[0].target.node.value());
+        assert!(find(&gltf).is_empty());
+    }
+
+    #[test]
+    fn remove_nodes() {
+        let gltf = Gltf::from_str(JSON).unwrap().skip_validation();
+        let (gltf, report) = super::remove_nodes(gltf, &[1]);
+        assert_eq!(vec![1], report.nodes);
+        assert!(report.meshes.is_empty());
+        let root = gltf.as_json();
+        assert_eq!(4, root.nodes.len());
+        assert!(root.nodes[0].children.is_none());
+        assert_eq!(1, root.animations[1].channels.len());
+        assert_eq!(vec![1, 3], find(&gltf).nodes);
+    }
+
+    #[test]
+    fn prune_unused_skin_joints() {
+        // The skin is used by no node, and its only joint is an orphan.
+        let json = r#"{
+            "asset": { "version": "2.0" },
+            "skins": [{ "joints": [1] }],
+            "nodes": [{}, {}],
+            "scenes": [{ "nodes": [0] }]
+        }"#;
+        let gltf = Gltf::from_str(json).unwrap().skip_validation();
+        let (gltf, report) = resolve(gltf, Policy::Prune);
+        assert_eq!(vec![1], report.nodes);
+        assert_eq!(1, gltf.nodes().len());
+        assert_eq!(0, gltf.skins().len());
+    }
+
+    #[test]
+    fn prune_unused_accessors() {
+        // Accessor 1 is used only by the orphan mesh, accessor 2 by nothing.
+        let json = r#"{
+            "asset": { "version": "2.0" },
+            "accessors": [
+                { "componentType": 5126, "count": 1, "type": "SCALAR" },
+                { "componentType": 5126, "count": 1, "type": "SCALAR" },
+                { "componentType": 5126, "count": 1, "type": "SCALAR" }
+            ],
+            "meshes": [
+                { "primitives": [{ "attributes": { "_A": 1 } }] },
+                { "primitives": [{ "attributes": { "_A": 0 }, "indices": 0 }] }
+            ],
+            "nodes": [{ "mesh": 1 }, { "mesh": 0 }],
+            "scenes": [{ "nodes": [0] }]
+        }"#;
+        let gltf = Gltf::from_str(json).unwrap().skip_validation();
+        let (gltf, report) = resolve(gltf, Policy::Prune);
+        assert_eq!(vec![0], report.meshes);
+        let root = gltf.as_json();
+        assert_eq!(2, root.accessors.len());
+        assert_eq!(Some(0), root.meshes[0].primitives[0].indices.as_ref().map(|i| i.value()));
+    }
+
+    #[test]
+    fn attach() {
+        let gltf = Gltf::from_str(JSON).unwrap().skip_validation();
+        let (gltf, _) = resolve(gltf, Policy::Attach);
+        let scene = gltf.scenes().nth(1).unwrap();
+        let nodes: Vec<_> = scene.nodes().map(|node| node.index()).collect();
+        assert_eq!(vec![2], nodes);
+        assert!(find(&gltf).is_empty());
+        assert!(gltf.as_json().scene.is_none());
+    }
+}
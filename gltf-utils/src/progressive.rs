@@ -0,0 +1,241 @@
+use std::collections::VecDeque;
+
+use gltf::{self, Gltf};
+
+/// The buffer views of an asset in the order they are needed for display.
+#[derive(Clone, Debug)]
+struct Order {
+    /// The views in order.
+    views: Vec<usize>,
+
+    /// Whether each view has been ordered.
+    seen: Vec<bool>,
+}
+
+impl Order {
+    /// Appends a view unless already ordered.
+    fn push(&mut self, view: usize) {
+        if !self.seen[view] {
+            self.seen[view] = true;
+            self.views.push(view);
+        }
+    }
+
+    /// Appends the views of an accessor.
+    fn accessor(&mut self, accessor: gltf::Accessor) {
+        if let Some(view) = accessor.view() {
+            self.push(view.index());
+        }
+        if let Some(sparse) = accessor.sparse() {
+            self.push(sparse.indices().view().index());
+            self.push(sparse.values().view().index());
+        }
+    }
+
+    /// Appends the views of the geometry of a mesh, indices and positions
+    /// first.
+    fn geometry(&mut self, mesh: &gltf::Mesh) {
+        for primitive in mesh.primitives() {
+            if let Some(indices) = primitive.indices() {
+                self.accessor(indices);
+            }
+            if let Some(positions) = primitive.get(&gltf::Semantic::Positions) {
+                self.accessor(positions);
+            }
+        }
+        for primitive in mesh.primitives() {
+            for (_, accessor) in primitive.attributes() {
+                self.accessor(accessor);
+            }
+            for target in primitive.morph_targets() {
+                let accessors = vec![target.positions(), target.normals(), target.tangents()];
+                for accessor in accessors.into_iter().flat_map(Option::into_iter) {
+                    self.accessor(accessor);
+                }
+            }
+        }
+    }
+
+    /// Appends the views of the images of a material, base color first.
+    fn material(&mut self, material: &gltf::Material) {
+        for texture in material.textures() {
+            if let gltf::image::Data::View { view, .. } = texture.texture().source().data() {
+                self.push(view.index());
+            }
+        }
+    }
+}
+
+/// Lays out the data of every buffer so that the data needed to display the
+/// default scene comes first, letting viewers show the scene progressively
+/// while the rest of a binary glTF file streams in.
+///
+/// Views are ordered as follows:
+///
+/// 1. The geometry of `coarse` meshes, in the given order, e.g. the coarsest
+///    level of detail of each `MSFT_lod` chain.
+/// 2. The geometry of the meshes of the default scene, or the first scene,
+///    breadth first so that nodes near the root come first. Indices and
+///    positions of each mesh precede its other attributes.
+/// 3. The images of the materials of those meshes, base color first.
+/// 4. The skins and animations of the scene.
+/// 5. Everything else, in view order.
+///
+/// Views keep their indices, buffers, and strides; only their byte offsets
+/// are re-planned, so accessors and extensions are unaffected, and every view
+/// starts at a multiple of four bytes. The JSON chunk of binary glTF is always
+/// read as a whole before any binary data, so JSON arrays are left in order.
+/// Returns the asset and the data of the rearranged buffers. Data not
+/// referenced by any view is dropped.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn run() -> Result<(), Box<std::error::Error>> {
+/// let gltf = gltf::Gltf::from_slice(include_bytes!("../../examples/Box.gltf"))?
+///     .validate_completely()?;
+/// let buffers = vec![include_bytes!("../../examples/Box0.bin").to_vec()];
+/// let (gltf, buffers) = gltf_utils::progressive::order(gltf, &buffers, &[]);
+///
+/// // The indices of the box now come first.
+/// let mesh = gltf.meshes().next().unwrap();
+/// let primitive = mesh.primitives().next().unwrap();
+/// assert_eq!(0, primitive.indices().unwrap().view().unwrap().offset());
+/// # let _ = buffers;
+/// # Ok(())
+/// # }
+/// # fn main() { run().unwrap() }
+/// ```
+///
+/// # Panics
+///
+/// Panics if a coarse mesh does not exist, or if a view lies outside its
+/// buffer data.
+pub fn order(gltf: Gltf, buffers: &[Vec<u8>], coarse: &[usize]) -> (Gltf, Vec<Vec<u8>>) {
+    let views = {
+        let mut order = Order {
+            views: vec![],
+            seen: vec![false; gltf.views().len()],
+        };
+        for &mesh in coarse {
+            order.geometry(&gltf.meshes().nth(mesh).expect("coarse mesh out of range"));
+        }
+
+        let scene = gltf.default_scene().or_else(|| gltf.scenes().next());
+        let mut meshes = vec![];
+        let mut skins = vec![];
+        let mut nodes = vec![false; gltf.nodes().len()];
+        let mut queue: VecDeque<usize> = scene
+            .iter()
+            .flat_map(|scene| scene.nodes().map(|node| node.index()))
+            .collect();
+        while let Some(index) = queue.pop_front() {
+            if nodes[index] {
+                continue;
+            }
+            nodes[index] = true;
+            let node = gltf.nodes().nth(index).unwrap();
+            if let Some(mesh) = node.mesh() {
+                order.geometry(&mesh);
+                meshes.push(mesh.index());
+            }
+            skins.extend(node.skin().map(|skin| skin.index()));
+            queue.extend(node.children().map(|child| child.index()));
+        }
+
+        for &mesh in &meshes {
+            for primitive in gltf.meshes().nth(mesh).unwrap().primitives() {
+                order.material(&primitive.material());
+            }
+        }
+        for skin in gltf.skins().filter(|skin| skins.contains(&skin.index())) {
+            if let Some(accessor) = skin.inverse_bind_matrices() {
+                order.accessor(accessor);
+            }
+        }
+        for animation in gltf.animations() {
+            let mut channels = animation.channels();
+            if channels.any(|channel| nodes[channel.target().node().index()]) {
+                for sampler in animation.samplers() {
+                    order.accessor(sampler.input());
+                    order.accessor(sampler.output());
+                }
+            }
+        }
+        for view in 0..gltf.views().len() {
+            order.push(view);
+        }
+        order.views
+    };
+
+    let mut root = gltf.into_json();
+    let mut data = vec![vec![]; root.buffers.len()];
+    for view in views {
+        let view = &mut root.buffer_views[view];
+        let begin = view.byte_offset as usize;
+        let end = begin + view.byte_length as usize;
+        let bytes = &mut data[view.buffer.value()];
+        while bytes.len() % 4 != 0 {
+            bytes.push(0);
+        }
+        view.byte_offset = bytes.len() as u32;
+        bytes.extend_from_slice(&buffers[view.buffer.value()][begin..end]);
+    }
+    for (buffer, bytes) in root.buffers.iter_mut().zip(&data) {
+        buffer.byte_length = bytes.len() as u32;
+    }
+    (Gltf::from_json(root).skip_validation(), data)
+}
+
+#[cfg(test)]
+mod tests {
+    use gltf::Gltf;
+
+    use super::*;
+
+    const JSON: &'static str = r#"{
+        "asset": { "version": "2.0" },
+        "buffers": [{ "byteLength": 20 }],
+        "bufferViews": [
+            { "buffer": 0, "byteLength": 4 },
+            { "buffer": 0, "byteOffset": 4, "byteLength": 4 },
+            { "buffer": 0, "byteOffset": 8, "byteLength": 4 },
+            { "buffer": 0, "byteOffset": 12, "byteLength": 4 },
+            { "buffer": 0, "byteOffset": 16, "byteLength": 3 }
+        ],
+        "accessors": [
+            { "bufferView": 0, "componentType": 5126, "count": 1, "type": "SCALAR" },
+            { "bufferView": 1, "componentType": 5126, "count": 1, "type": "SCALAR" },
+            { "bufferView": 2, "componentType": 5126, "count": 1, "type": "SCALAR" },
+            { "bufferView": 3, "componentType": 5126, "count": 1, "type": "SCALAR" }
+        ],
+        "images": [{ "bufferView": 4, "mimeType": "image/png" }],
+        "textures": [{ "source": 0 }],
+        "materials": [{ "pbrMetallicRoughness": { "baseColorTexture": { "index": 0 } } }],
+        "meshes": [
+            { "primitives": [{ "attributes": { "POSITION": 0 } }] },
+            { "primitives": [{ "attributes": { "POSITION": 1 }, "material": 0 }] },
+            { "primitives": [{ "attributes": { "POSITION": 2 } }] },
+            { "primitives": [{ "attributes": { "POSITION": 3 } }] }
+        ],
+        "nodes": [
+            { "mesh": 2, "children": [1] },
+            { "mesh": 1 },
+            { "mesh": 0 }
+        ],
+        "scenes": [{ "nodes": [2] }, { "nodes": [0] }],
+        "scene": 1
+    }"#;
+
+    #[test]
+    fn scene_first() {
+        let gltf = Gltf::from_str(JSON).unwrap().skip_validation();
+        let buffers = vec![(0..20).collect()];
+        let (gltf, buffers) = order(gltf, &buffers, &[3]);
+        let offsets: Vec<_> = gltf.views().map(|view| view.offset()).collect();
+        assert_eq!(vec![16, 8, 4, 0, 12], offsets);
+        assert_eq!(&[12, 13, 14, 15, 8], &buffers[0][..5]);
+        assert_eq!(&[16, 17, 18], &buffers[0][12..15]);
+        assert_eq!(20, gltf.buffers().next().unwrap().length());
+    }
+}
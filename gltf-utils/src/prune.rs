@@ -0,0 +1,215 @@
+use std::collections::BTreeSet;
+
+use gltf::{self, json};
+
+/// Returns the new index of every object after removing some, or `None` for
+/// removed objects.
+pub(crate) fn remap(len: usize, removed: &BTreeSet<usize>) -> Vec<Option<u32>> {
+    let mut next = 0;
+    (0..len)
+        .map(|index| {
+            if removed.contains(&index) {
+                None
+            } else {
+                next += 1;
+                Some(next - 1)
+            }
+        })
+        .collect()
+}
+
+/// Removes the objects at the given indices.
+pub(crate) fn remove<T>(objects: &mut Vec<T>, removed: &BTreeSet<usize>) {
+    let mut index = 0;
+    objects.retain(|_| {
+        index += 1;
+        !removed.contains(&(index - 1))
+    });
+}
+
+/// Points a reference at the new index of the object it references.
+///
+/// # Panics
+///
+/// Panics if the object was removed.
+fn renumber<T>(map: &[Option<u32>], index: &mut json::Index<T>) {
+    *index = json::Index::new(map[index.value()].expect("reference to a removed object"));
+}
+
+/// Removes accessors that are no longer referenced, renumbering the rest.
+pub(crate) fn accessors(root: &mut json::Root, removed: &BTreeSet<usize>) {
+    let map = remap(root.accessors.len(), removed);
+    remove(&mut root.accessors, removed);
+    json::visit::accessors_mut(root, |index| renumber(&map, index));
+}
+
+/// Removes textures that are no longer referenced, renumbering the rest.
+pub(crate) fn textures(root: &mut json::Root, removed: &BTreeSet<usize>) {
+    let map = remap(root.textures.len(), removed);
+    remove(&mut root.textures, removed);
+    json::visit::textures_mut(root, |index| renumber(&map, index));
+    unmodelled_textures(root, |index| renumbered(&map, index));
+}
+
+/// Removes images that are no longer referenced, renumbering the rest.
+pub(crate) fn images(root: &mut json::Root, removed: &BTreeSet<usize>) {
+    let map = remap(root.images.len(), removed);
+    remove(&mut root.images, removed);
+    for texture in &mut root.textures {
+        renumber(&map, &mut texture.source);
+    }
+    unmodelled_images(root, |index| renumbered(&map, index));
+}
+
+/// Returns the new index of an object referenced by an extension this crate
+/// does not model, leaving out of range indices unchanged.
+fn renumbered(map: &[Option<u32>], index: usize) -> usize {
+    match map.get(index) {
+        Some(&Some(new)) => new as usize,
+        Some(&None) => panic!("reference to a removed object"),
+        None => index,
+    }
+}
+
+/// Replaces an index stored in raw extension JSON.
+fn replace<F: FnMut(usize) -> usize>(value: &mut json::Value, f: &mut F) {
+    if let Some(index) = value.as_u64() {
+        *value = json::Value::from(f(index as usize) as u64);
+    }
+}
+
+/// Calls `f` with every texture index in the material extensions this crate
+/// does not model, replacing it with the result.
+///
+/// Texture references are found by the naming convention of the glTF
+/// extensions, as the `index` of properties named `...Texture`, e.g. the
+/// `sheenColorTexture` of `KHR_materials_sheen`.
+pub(crate) fn unmodelled_textures<F>(root: &mut json::Root, mut f: F)
+    where F: FnMut(usize) -> usize
+{
+    fn visit<F: FnMut(usize) -> usize>(value: &mut json::Value, f: &mut F) {
+        match *value {
+            json::Value::Object(ref mut object) => {
+                for (key, value) in object.iter_mut() {
+                    if key.ends_with("Texture") {
+                        if let Some(index) = value.get_mut("index") {
+                            replace(index, f);
+                        }
+                    }
+                    visit(value, f);
+                }
+            },
+            json::Value::Array(ref mut values) => {
+                for value in values {
+                    visit(value, f);
+                }
+            },
+            _ => {},
+        }
+    }
+    for material in &mut root.materials {
+        for value in material.extensions.others.values_mut() {
+            visit(value, &mut f);
+        }
+    }
+}
+
+/// Calls `f` with every image index in the texture extensions this crate
+/// does not model, e.g. the `source` of `KHR_texture_basisu`, replacing it
+/// with the result.
+pub(crate) fn unmodelled_images<F>(root: &mut json::Root, mut f: F)
+    where F: FnMut(usize) -> usize
+{
+    for texture in &mut root.textures {
+        for value in texture.extensions.others.values_mut() {
+            if let Some(source) = value.get_mut("source") {
+                replace(source, &mut f);
+            }
+        }
+    }
+}
+
+/// Removes the buffer views among `candidates` that nothing references any
+/// more, renumbering the rest, and compacts the buffers they were in.
+///
+/// See `compact`.
+pub(crate) fn views(
+    root: &mut json::Root,
+    buffers: &mut [Vec<u8>],
+    candidates: &BTreeSet<usize>,
+) {
+    let counts = root.reference_counts();
+    let removed: BTreeSet<usize> = candidates
+        .iter()
+        .cloned()
+        .filter(|&view| counts.buffer_views[view] == 0)
+        .collect();
+    if removed.is_empty() {
+        return;
+    }
+    let affected: BTreeSet<usize> = removed
+        .iter()
+        .map(|&view| root.buffer_views[view].buffer.value())
+        .collect();
+    let map = remap(root.buffer_views.len(), &removed);
+    remove(&mut root.buffer_views, &removed);
+    json::visit::buffer_views_mut(root, |index| renumber(&map, index));
+    for buffer in affected {
+        compact(root, buffers, buffer);
+    }
+}
+
+/// Rewrites a buffer to hold only the bytes covered by its buffer views, in
+/// their original order.
+///
+/// Overlapping views keep sharing their bytes, and each run of data keeps its
+/// offset modulo 4 so that accessors stay aligned. A declared
+/// `GLTFRS_buffer_checksum` is updated. Buffers without any view are left
+/// unchanged, since a buffer may not be empty.
+///
+/// # Panics
+///
+/// Panics if a view lies outside the buffer data.
+fn compact(root: &mut json::Root, buffers: &mut [Vec<u8>], buffer: usize) {
+    let mut views: Vec<usize> = (0..root.buffer_views.len())
+        .filter(|&view| root.buffer_views[view].buffer.value() == buffer)
+        .collect();
+    if views.is_empty() {
+        return;
+    }
+    views.sort_by_key(|&view| root.buffer_views[view].byte_offset);
+
+    // Merge overlapping views into runs of data to copy.
+    let mut runs: Vec<(usize, usize)> = vec![];
+    let mut run_of = vec![];
+    for &view in &views {
+        let begin = root.buffer_views[view].byte_offset as usize;
+        let end = begin + root.buffer_views[view].byte_length as usize;
+        match runs.last_mut() {
+            Some(run) if begin < run.1 => run.1 = run.1.max(end),
+            _ => runs.push((begin, end)),
+        }
+        run_of.push(runs.len() - 1);
+    }
+
+    let mut data = vec![];
+    let mut starts = vec![];
+    for &(begin, end) in &runs {
+        while data.len() % 4 != begin % 4 {
+            data.push(0);
+        }
+        starts.push(data.len());
+        data.extend_from_slice(&buffers[buffer][begin..end]);
+    }
+    for (&view, &run) in views.iter().zip(&run_of) {
+        let offset = &mut root.buffer_views[view].byte_offset;
+        *offset = (starts[run] + *offset as usize - runs[run].0) as u32;
+    }
+    buffers[buffer] = data;
+
+    let json = &mut root.buffers[buffer];
+    json.byte_length = buffers[buffer].len() as u32;
+    if let Some(ref mut checksum) = json.extensions.buffer_checksum {
+        checksum.crc32 = gltf::buffer::crc32(&buffers[buffer]);
+    }
+}
@@ -0,0 +1,242 @@
+use std::{fmt, mem};
+
+use gltf::{self, json, Gltf};
+
+pub use gltf::json::floats::{format_floats, Floats};
+
+/// Removes all insignificant whitespace from glTF JSON.
+///
+/// Object keys are written in sorted order and arrays in their original
+/// order, so equal documents produce identical text. Numbers are written in
+/// their shortest round-trip representation, so values such as `1.0` may be
+/// written as `1.0` or `1` depending on how they were parsed.
+///
+/// # Examples
+///
+/// ```rust
+/// let json = br#"{
+///     "asset": { "version": "2.0" },
+///     "scenes": [ { "nodes": [] } ]
+/// }"#;
+/// let minified = gltf_utils::size::minify(json).unwrap();
+/// assert_eq!(r#"{"asset":{"version":"2.0"},"scenes":[{"nodes":[]}]}"#, minified);
+/// ```
+pub fn minify(json: &[u8]) -> Result<String, json::Error> {
+    let mut value: json::Value = json::from_slice(json)?;
+    sort_keys(&mut value);
+    Ok(value.to_string())
+}
+
+/// Sorts the keys of every object of a JSON value.
+///
+/// Objects are sorted already unless `serde_json` is built with its
+/// `preserve_order` feature, which another crate in the build may enable.
+fn sort_keys(value: &mut json::Value) {
+    match *value {
+        json::Value::Array(ref mut values) => {
+            for value in values {
+                sort_keys(value);
+            }
+        },
+        json::Value::Object(_) => {
+            let mut entries: Vec<(String, json::Value)> =
+                match mem::replace(value, json::Value::Null) {
+                    json::Value::Object(object) => object.into_iter().collect(),
+                    _ => unreachable!(),
+                };
+            entries.sort_by(|a, b| a.0.cmp(&b.0));
+            for entry in &mut entries {
+                sort_keys(&mut entry.1);
+            }
+            *value = json::Value::Object(entries.into_iter().collect());
+        },
+        _ => {},
+    }
+}
+
+/// Removes all insignificant whitespace from glTF JSON and formats its
+/// floating point numbers.
+///
+/// # Examples
+///
+/// ```rust
+/// use gltf_utils::size::{minify_with, Floats};
+///
+/// let json = br#"{ "scale": [0.10000000149011612, 2, -0.0] }"#;
+/// let shortest = r#"{"scale":[0.10000000149011612,2,0.0]}"#;
+/// assert_eq!(shortest, minify_with(json, Floats::Shortest).unwrap());
+/// assert_eq!(r#"{"scale":[0.1,2,0.0]}"#, minify_with(json, Floats::Single).unwrap());
+/// let json = br#"{ "scale": [0.123456, 1e-9] }"#;
+/// assert_eq!(r#"{"scale":[0.123,0.0]}"#, minify_with(json, Floats::Fixed(3)).unwrap());
+/// ```
+pub fn minify_with(json: &[u8], floats: Floats) -> Result<String, json::Error> {
+    let mut value: json::Value = json::from_slice(json)?;
+    sort_keys(&mut value);
+    format_floats(&mut value, floats);
+    Ok(value.to_string())
+}
+
+/// A breakdown of the size of a glTF asset by section, in bytes.
+///
+/// Embedded `data:` URIs are counted towards the buffers or images they hold,
+/// not towards the JSON, so the report shows what dominates the asset however
+/// it is packaged. Binary data is counted by its decoded size.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Report {
+    /// The JSON excluding embedded `data:` URIs.
+    pub json: usize,
+
+    /// The buffer data not holding images.
+    pub buffers: usize,
+
+    /// The encoded images stored in buffer views or `data:` URIs, plus the
+    /// external images whose sizes were given.
+    pub images: usize,
+
+    /// The number of external images of unknown size.
+    pub unknown_images: usize,
+}
+
+impl Report {
+    /// Returns the size of all sections.
+    pub fn total(&self) -> usize {
+        self.json + self.buffers + self.images
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let total = self.total();
+        let sections = [("json", self.json), ("buffers", self.buffers), ("images", self.images)];
+        for &(name, size) in &sections {
+            let percent = if total > 0 { size as f64 * 100.0 / total as f64 } else { 0.0 };
+            writeln!(f, "{:<8} {:>12} B {:>5.1}%", name, size, percent)?;
+        }
+        write!(f, "{:<8} {:>12} B", "total", total)?;
+        if self.unknown_images > 0 {
+            write!(f, " ({} external images of unknown size)", self.unknown_images)?;
+        }
+        Ok(())
+    }
+}
+
+/// Returns whether a URI embeds its data.
+fn is_data_uri(uri: &str) -> bool {
+    uri.starts_with("data:")
+}
+
+/// Returns the decoded length of the base64 payload of a `data:` URI.
+fn decoded_length(uri: &str) -> usize {
+    let payload = uri.rsplit(',').next().unwrap_or("");
+    let padding = payload.bytes().rev().take_while(|&b| b == b'=').count();
+    (payload.len() * 3 / 4).saturating_sub(padding)
+}
+
+/// Reports the size of an asset given its JSON text, which may be minified
+/// with `minify` beforehand to report the exported size.
+///
+/// `external_image` returns the size of the image file at a relative URI,
+/// if known.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// # fn run() -> Result<(), Box<std::error::Error>> {
+/// use std::fs;
+///
+/// let text = fs::read("path/to/asset.gltf")?;
+/// let minified = gltf_utils::size::minify(&text)?;
+/// let gltf = gltf::Gltf::from_slice(minified.as_bytes())?.validate_minimally()?;
+/// let report = gltf_utils::size::report(&minified, &gltf, |uri| {
+///     fs::metadata(format!("path/to/{}", uri)).ok().map(|m| m.len() as usize)
+/// });
+/// println!("{}", report);
+/// # Ok(())
+/// # }
+/// # fn main() {}
+/// ```
+pub fn report<F>(json: &str, gltf: &Gltf, mut external_image: F) -> Report
+    where F: FnMut(&str) -> Option<usize>
+{
+    let mut report = Report { json: json.len(), ..Default::default() };
+    for buffer in gltf.buffers() {
+        report.buffers += buffer.length();
+        if let Some(ref uri) = buffer.as_json().uri {
+            if is_data_uri(uri) {
+                report.json -= uri.len().min(report.json);
+            }
+        }
+    }
+    for image in gltf.images() {
+        match image.data() {
+            gltf::image::Data::View { view, .. } => {
+                report.buffers -= view.length().min(report.buffers);
+                report.images += view.length();
+            },
+            gltf::image::Data::Uri { uri, .. } if is_data_uri(uri) => {
+                report.json -= uri.len().min(report.json);
+                report.images += decoded_length(uri);
+            },
+            gltf::image::Data::Uri { uri, .. } => match external_image(uri) {
+                Some(size) => report.images += size,
+                None => report.unknown_images += 1,
+            },
+        }
+    }
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use gltf::Gltf;
+
+    use super::*;
+
+    const JSON: &'static str = r#"{
+        "asset": { "version": "2.0" },
+        "buffers": [
+            { "byteLength": 100 },
+            { "byteLength": 3, "uri": "data:application/octet-stream;base64,AAAA" }
+        ],
+        "bufferViews": [{ "buffer": 0, "byteOffset": 60, "byteLength": 40 }],
+        "images": [
+            { "bufferView": 0, "mimeType": "image/png" },
+            { "uri": "a.png" },
+            { "uri": "b.png" },
+            { "uri": "data:image/png;base64,AAAAAA==" }
+        ]
+    }"#;
+
+    #[test]
+    fn sections() {
+        let minified = minify(JSON.as_bytes()).unwrap();
+        assert!(!minified.contains(' '));
+        let gltf = Gltf::from_str(&minified).unwrap().skip_validation();
+        let data_uris = "data:application/octet-stream;base64,AAAA".len()
+            + "data:image/png;base64,AAAAAA==".len();
+        let report = report(&minified, &gltf, |uri| if uri == "a.png" { Some(7) } else { None });
+        assert_eq!(minified.len() - data_uris, report.json);
+        assert_eq!(60 + 3, report.buffers);
+        assert_eq!(40 + 7 + 4, report.images);
+        assert_eq!(1, report.unknown_images);
+        assert!(report.to_string().contains("1 external images of unknown size"));
+    }
+
+    #[test]
+    fn sorted_keys() {
+        let json = br#"{ "b": [{ "z": 1, "y": 2 }], "a": 0 }"#;
+        assert_eq!(r#"{"a":0,"b":[{"y":2,"z":1}]}"#, minify(json).unwrap());
+    }
+
+    #[test]
+    fn stable_floats() {
+        let json = br#"[1.5, 0.30000001192092896, 16777217.0, 7]"#;
+        let once = minify_with(json, Floats::Shortest).unwrap();
+        assert_eq!("[1.5,0.30000001192092896,16777217.0,7]", once);
+        assert_eq!(once, minify_with(once.as_bytes(), Floats::Shortest).unwrap());
+        let once = minify_with(json, Floats::Single).unwrap();
+        assert_eq!("[1.5,0.3,16777216.0,7]", once);
+        assert_eq!(once, minify_with(once.as_bytes(), Floats::Single).unwrap());
+        assert_eq!("[1.5,0.3,16777217.0,7]", minify_with(json, Floats::Fixed(2)).unwrap());
+    }
+}
@@ -0,0 +1,447 @@
+use byteorder::{LE, ByteOrder};
+use gltf::{self, Gltf, Semantic};
+use gltf::accessor::DataType;
+
+use {PrimitiveIterators, Source};
+
+/// The maximum number of influences per vertex supported by the fixed-function
+/// skinning path, i.e. one `JOINTS_0` / `WEIGHTS_0` pair.
+pub const MAX_PACKED_INFLUENCES: usize = 4;
+
+/// The `JOINTS_0` and `WEIGHTS_0` data of a primitive, one element per vertex,
+/// as returned by `repack`.
+pub type Packed = (Vec<[u16; 4]>, Vec<[f32; 4]>);
+
+/// Skinning statistics of a single primitive.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PrimitiveInfluences {
+    /// The index of the parent mesh.
+    pub mesh: usize,
+
+    /// The index of the primitive within its parent mesh.
+    pub primitive: usize,
+
+    /// The number of `JOINTS_n` / `WEIGHTS_n` attribute pairs.
+    pub sets: u32,
+
+    /// The largest number of non-zero weights found on any one vertex.
+    pub max_influences: usize,
+}
+
+/// Summary of the skinning data of a glTF asset.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Report {
+    /// The number of joints of each skin, indexed by skin.
+    pub joints_per_skin: Vec<usize>,
+
+    /// The largest number of joints referenced by any one skin.
+    pub max_joints_per_skin: usize,
+
+    /// The largest number of non-zero weights found on any one vertex.
+    pub max_influences_per_vertex: usize,
+
+    /// Per-primitive statistics for every primitive with skinning attributes.
+    pub primitives: Vec<PrimitiveInfluences>,
+}
+
+/// Fixed limits imposed by a GPU skinning implementation.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Limits {
+    /// The maximum number of joints per skin, e.g. the size of a uniform array.
+    pub max_joints_per_skin: usize,
+
+    /// The maximum number of influences per vertex.
+    pub max_influences_per_vertex: usize,
+}
+
+/// A skinning limit exceeded by an asset.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Violation {
+    /// The skin at the given index references too many joints.
+    Joints {
+        /// The index of the skin.
+        skin: usize,
+
+        /// The number of joints referenced by the skin.
+        count: usize,
+    },
+
+    /// The primitive has vertices with too many non-zero weights.
+    Influences {
+        /// The index of the parent mesh.
+        mesh: usize,
+
+        /// The index of the primitive within its parent mesh.
+        primitive: usize,
+
+        /// The largest number of non-zero weights found on any one vertex.
+        count: usize,
+    },
+}
+
+impl Report {
+    /// Returns every limit exceeded by the asset.
+    pub fn check(&self, limits: &Limits) -> Vec<Violation> {
+        let mut violations = vec![];
+        for (skin, &count) in self.joints_per_skin.iter().enumerate() {
+            if count > limits.max_joints_per_skin {
+                violations.push(Violation::Joints { skin, count });
+            }
+        }
+        for info in &self.primitives {
+            if info.max_influences > limits.max_influences_per_vertex {
+                violations.push(Violation::Influences {
+                    mesh: info.mesh,
+                    primitive: info.primitive,
+                    count: info.max_influences,
+                });
+            }
+        }
+        violations
+    }
+}
+
+/// Returns the number of `JOINTS_n` / `WEIGHTS_n` attribute pairs of a primitive.
+pub fn influence_sets(primitive: &gltf::Primitive) -> u32 {
+    let mut set = 0;
+    while primitive.get(&Semantic::Joints(set)).is_some()
+        && primitive.get(&Semantic::Weights(set)).is_some()
+    {
+        set += 1;
+    }
+    set
+}
+
+/// Collects the `(joint, weight)` influences of every vertex of a primitive
+/// across all of its `JOINTS_n` / `WEIGHTS_n` sets.
+///
+/// Influences with a weight of zero are omitted.
+pub fn influences<S: Source>(
+    primitive: &gltf::Primitive,
+    source: &S,
+) -> Vec<Vec<(u16, f32)>> {
+    let mut vertices: Vec<Vec<(u16, f32)>> = vec![];
+    for set in 0..influence_sets(primitive) {
+        let joints = primitive.joints_u16(set, source).unwrap();
+        let weights = primitive.weights_f32(set, source).unwrap();
+        for (vertex, (j, w)) in joints.zip(weights).enumerate() {
+            if vertices.len() <= vertex {
+                vertices.push(vec![]);
+            }
+            for i in 0..4 {
+                if w[i] != 0.0 {
+                    vertices[vertex].push((j[i], w[i]));
+                }
+            }
+        }
+    }
+    vertices
+}
+
+/// Analyzes the skins and skinned primitives of an asset.
+pub fn analyze<S: Source>(gltf: &Gltf, source: &S) -> Report {
+    let mut report = Report::default();
+    for skin in gltf.skins() {
+        let count = skin.joints().count();
+        report.max_joints_per_skin = report.max_joints_per_skin.max(count);
+        report.joints_per_skin.push(count);
+    }
+    for mesh in gltf.meshes() {
+        for primitive in mesh.primitives() {
+            let sets = influence_sets(&primitive);
+            if sets == 0 {
+                continue;
+            }
+            let max_influences = influences(&primitive, source)
+                .iter()
+                .map(Vec::len)
+                .max()
+                .unwrap_or(0);
+            report.max_influences_per_vertex = report.max_influences_per_vertex.max(max_influences);
+            report.primitives.push(PrimitiveInfluences {
+                mesh: mesh.index(),
+                primitive: primitive.index(),
+                sets,
+                max_influences,
+            });
+        }
+    }
+    report
+}
+
+/// Re-packs the influences of a primitive into a single set of four joints and
+/// weights per vertex.
+///
+/// The four heaviest influences of each vertex are kept and renormalized so
+/// their weights sum to `1.0`. Unused slots have joint `0` and weight `0.0`.
+///
+/// Returns `None` if the primitive has no skinning attributes.
+pub fn repack<S: Source>(
+    primitive: &gltf::Primitive,
+    source: &S,
+) -> Option<Packed> {
+    if influence_sets(primitive) == 0 {
+        return None;
+    }
+    let vertices = influences(primitive, source);
+    let mut joints = Vec::with_capacity(vertices.len());
+    let mut weights = Vec::with_capacity(vertices.len());
+    for mut vertex in vertices {
+        vertex.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(::std::cmp::Ordering::Equal));
+        vertex.truncate(MAX_PACKED_INFLUENCES);
+        let sum: f32 = vertex.iter().map(|&(_, w)| w).sum();
+        let mut j = [0; 4];
+        let mut w = [0.0; 4];
+        for (i, &(joint, weight)) in vertex.iter().enumerate() {
+            j[i] = joint;
+            w[i] = if sum > 0.0 { weight / sum } else { 0.0 };
+        }
+        joints.push(j);
+        weights.push(w);
+    }
+    Some((joints, weights))
+}
+
+/// Renormalizes the skin weights of every skinned primitive in place.
+///
+/// Influences with a weight below `threshold` are pruned by setting both their
+/// joint and weight to zero, and the remaining weights of each vertex are
+/// scaled to sum to `1.0` across all of its `JOINTS_n` / `WEIGHTS_n` sets. The
+/// heaviest influence of a vertex is never pruned.
+///
+/// Accessors shared by several primitives are rewritten once per primitive,
+/// which yields the same result since the pass is idempotent. Primitives with
+/// a `JOINTS_n` or `WEIGHTS_n` accessor without a buffer view are left
+/// unchanged, since there is no data to rewrite.
+pub fn renormalize_weights(gltf: &Gltf, buffers: &mut Vec<Vec<u8>>, threshold: f32) {
+    for mesh in gltf.meshes() {
+        for primitive in mesh.primitives() {
+            renormalize_primitive(&primitive, buffers, threshold);
+        }
+    }
+}
+
+fn renormalize_primitive(
+    primitive: &gltf::Primitive,
+    buffers: &mut Vec<Vec<u8>>,
+    threshold: f32,
+) {
+    let sets = influence_sets(primitive);
+    if sets == 0 {
+        return;
+    }
+    let viewless = (0..sets).any(|set| {
+        primitive.get(&Semantic::Joints(set)).unwrap().view().is_none()
+            || primitive.get(&Semantic::Weights(set)).unwrap().view().is_none()
+    });
+    if viewless {
+        return;
+    }
+
+    let mut joints = vec![];
+    let mut weights = vec![];
+    for set in 0..sets {
+        joints.push(primitive.joints_u16(set, &*buffers).unwrap().collect::<Vec<_>>());
+        weights.push(primitive.weights_f32(set, &*buffers).unwrap().collect::<Vec<_>>());
+    }
+
+    let count = weights.iter().map(Vec::len).min().unwrap_or(0);
+    for vertex in 0..count {
+        let mut heaviest = (0, 0);
+        for set in 0..sets as usize {
+            for i in 0..4 {
+                let (s, j) = heaviest;
+                if weights[set][vertex][i] > weights[s][vertex][j] {
+                    heaviest = (set, i);
+                }
+            }
+        }
+
+        let mut sum = 0.0;
+        for set in 0..sets as usize {
+            for i in 0..4 {
+                let weight = weights[set][vertex][i];
+                if (set, i) != heaviest && weight < threshold {
+                    weights[set][vertex][i] = 0.0;
+                    joints[set][vertex][i] = 0;
+                } else {
+                    sum += weight;
+                }
+            }
+        }
+
+        if sum > 0.0 {
+            for set in &mut weights {
+                for weight in &mut set[vertex] {
+                    *weight /= sum;
+                }
+            }
+        }
+    }
+
+    for set in 0..sets {
+        let accessor = primitive.get(&Semantic::Joints(set)).unwrap();
+        for (vertex, value) in joints[set as usize].iter().enumerate() {
+            let value = [value[0] as u32, value[1] as u32, value[2] as u32, value[3] as u32];
+            write_vec4(&accessor, buffers, vertex, value);
+        }
+
+        let accessor = primitive.get(&Semantic::Weights(set)).unwrap();
+        let scale = match accessor.data_type() {
+            DataType::U8 => Some(255.0),
+            DataType::U16 => Some(65535.0),
+            _ => None,
+        };
+        for (vertex, value) in weights[set as usize].iter().enumerate() {
+            match scale {
+                Some(scale) => {
+                    let value = quantize(value, scale);
+                    write_vec4(&accessor, buffers, vertex, value);
+                },
+                None => write_vec4_f32(&accessor, buffers, vertex, value),
+            }
+        }
+    }
+}
+
+/// Quantizes normalized weights, distributing rounding error onto the largest
+/// weight so that the integer weights of a single set still sum to `scale`.
+fn quantize(weights: &[f32; 4], scale: f32) -> [u32; 4] {
+    let mut result = [0; 4];
+    let mut largest = 0;
+    for i in 0..4 {
+        result[i] = (weights[i] * scale).round() as u32;
+        if weights[i] > weights[largest] {
+            largest = i;
+        }
+    }
+    let sum: f32 = weights.iter().sum();
+    if (sum - 1.0).abs() < 1.0e-4 {
+        let total: u32 = result.iter().sum();
+        let scale = scale as u32;
+        if total > scale {
+            result[largest] -= total - scale;
+        } else {
+            result[largest] += scale - total;
+        }
+    }
+    result
+}
+
+/// Returns the byte offset of the given element of an accessor.
+fn element_offset(accessor: &gltf::Accessor, index: usize) -> usize {
+    let view = accessor.view().unwrap();
+    let stride = view.stride().unwrap_or(accessor.size());
+    view.offset() + accessor.offset() + stride * index
+}
+
+/// Writes an unsigned integer `VEC4` element.
+fn write_vec4(
+    accessor: &gltf::Accessor,
+    buffers: &mut [Vec<u8>],
+    index: usize,
+    value: [u32; 4],
+) {
+    let offset = element_offset(accessor, index);
+    let data = &mut buffers[accessor.view().unwrap().buffer().index()][offset..];
+    let size = accessor.data_type().size();
+    for i in 0..4 {
+        let data = &mut data[i * size..];
+        match accessor.data_type() {
+            DataType::U8 => data[0] = value[i] as u8,
+            DataType::U16 => LE::write_u16(data, value[i] as u16),
+            DataType::U32 => LE::write_u32(data, value[i]),
+            _ => unreachable!(),
+        }
+    }
+}
+
+/// Writes a floating point `VEC4` element.
+fn write_vec4_f32(
+    accessor: &gltf::Accessor,
+    buffers: &mut [Vec<u8>],
+    index: usize,
+    value: &[f32; 4],
+) {
+    let offset = element_offset(accessor, index);
+    let data = &mut buffers[accessor.view().unwrap().buffer().index()][offset..];
+    LE::write_f32_into(value, &mut data[..16]);
+}
+
+#[cfg(test)]
+mod tests {
+    use byteorder::{LE, ByteOrder};
+    use gltf::Gltf;
+
+    use super::*;
+
+    const JSON: &'static str = r#"{
+        "asset": { "version": "2.0" },
+        "buffers": [{ "byteLength": 48 }],
+        "bufferViews": [
+            { "buffer": 0, "byteOffset": 0, "byteLength": 16 },
+            { "buffer": 0, "byteOffset": 16, "byteLength": 32 }
+        ],
+        "accessors": [
+            { "bufferView": 0, "componentType": 5123, "count": 2, "type": "VEC4" },
+            { "bufferView": 1, "componentType": 5126, "count": 2, "type": "VEC4" }
+        ],
+        "meshes": [{
+            "primitives": [{ "attributes": { "JOINTS_0": 0, "WEIGHTS_0": 1 } }]
+        }],
+        "nodes": [{ "mesh": 0, "skin": 0 }, {}, {}, {}, {}, {}],
+        "skins": [{ "joints": [1, 2, 3, 4, 5] }]
+    }"#;
+
+    fn data() -> Vec<Vec<u8>> {
+        let joints = [0, 1, 2, 3, 4, 0, 0, 0];
+        let weights = [0.5, 0.25, 0.125, 0.125, 1.0, 0.0, 0.0, 0.0];
+        let mut data = vec![0; 48];
+        LE::write_u16_into(&joints, &mut data[0..16]);
+        LE::write_f32_into(&weights, &mut data[16..48]);
+        vec![data]
+    }
+
+    #[test]
+    fn analyze_and_check() {
+        let gltf = Gltf::from_str(JSON).unwrap().skip_validation();
+        let report = analyze(&gltf, &data());
+        assert_eq!(vec![5], report.joints_per_skin);
+        assert_eq!(4, report.max_influences_per_vertex);
+        let limits = Limits {
+            max_joints_per_skin: 4,
+            max_influences_per_vertex: 4,
+        };
+        assert_eq!(
+            vec![Violation::Joints { skin: 0, count: 5 }],
+            report.check(&limits),
+        );
+    }
+
+    #[test]
+    fn repack_four() {
+        let gltf = Gltf::from_str(JSON).unwrap().skip_validation();
+        let mesh = gltf.meshes().next().unwrap();
+        let primitive = mesh.primitives().next().unwrap();
+        let (joints, weights) = repack(&primitive, &data()).unwrap();
+        assert_eq!(vec![[0, 1, 2, 3], [4, 0, 0, 0]], joints);
+        assert_eq!(vec![[0.5, 0.25, 0.125, 0.125], [1.0, 0.0, 0.0, 0.0]], weights);
+    }
+
+    #[test]
+    fn renormalize() {
+        let gltf = Gltf::from_str(JSON).unwrap().skip_validation();
+        let mut buffers = data();
+        LE::write_f32_into(&[0.5, 0.3, 0.15, 0.05], &mut buffers[0][16..32]);
+        renormalize_weights(&gltf, &mut buffers, 0.1);
+        let mesh = gltf.meshes().next().unwrap();
+        let primitive = mesh.primitives().next().unwrap();
+        let joints: Vec<_> = primitive.joints_u16(0, &buffers).unwrap().collect();
+        let weights: Vec<_> = primitive.weights_f32(0, &buffers).unwrap().collect();
+        assert_eq!(vec![[0, 1, 2, 0], [4, 0, 0, 0]], joints);
+        let sum: f32 = weights[0].iter().sum();
+        assert!((sum - 1.0).abs() < 1.0e-6);
+        assert_eq!(0.0, weights[0][3]);
+        assert_eq!([1.0, 0.0, 0.0, 0.0], weights[1]);
+    }
+}
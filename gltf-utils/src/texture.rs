@@ -0,0 +1,183 @@
+use byteorder::{LE, ByteOrder};
+use gltf::color::{linear_to_srgb, srgb_to_linear};
+
+/// The KTX2 file identifier.
+const KTX2_IDENTIFIER: [u8; 12] = [
+    0xAB, 0x4B, 0x54, 0x58, 0x20, 0x32, 0x30, 0xBB, 0x0D, 0x0A, 0x1A, 0x0A,
+];
+
+/// `VK_FORMAT_R8G8B8A8_UNORM`.
+const VK_FORMAT_R8G8B8A8_UNORM: u32 = 37;
+
+/// `VK_FORMAT_R8G8B8A8_SRGB`.
+const VK_FORMAT_R8G8B8A8_SRGB: u32 = 43;
+
+/// A single level of a mip chain of 8-bit RGBA pixels.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Level {
+    /// The width in pixels.
+    pub width: u32,
+
+    /// The height in pixels.
+    pub height: u32,
+
+    /// Tightly packed rows of RGBA pixels, top row first.
+    pub data: Vec<u8>,
+}
+
+/// Halves the dimensions of a level with a box filter.
+fn downsample(level: &Level, srgb: bool) -> Level {
+    let width = (level.width / 2).max(1);
+    let height = (level.height / 2).max(1);
+    let mut data = Vec::with_capacity((width * height * 4) as usize);
+    for y in 0..height {
+        for x in 0..width {
+            for channel in 0..4 {
+                let mut sum = 0.0;
+                for &(dx, dy) in &[(0, 0), (1, 0), (0, 1), (1, 1)] {
+                    let sx = (x * 2 + dx).min(level.width - 1);
+                    let sy = (y * 2 + dy).min(level.height - 1);
+                    let index = ((sy * level.width + sx) * 4 + channel) as usize;
+                    let value = level.data[index] as f32 / 255.0;
+                    // Alpha is always linear.
+                    sum += if srgb && channel < 3 { srgb_to_linear(value) } else { value };
+                }
+                let mut average = sum / 4.0;
+                if srgb && channel < 3 {
+                    average = linear_to_srgb(average);
+                }
+                data.push((average * 255.0).round().clamp(0.0, 255.0) as u8);
+            }
+        }
+    }
+    Level { width, height, data }
+}
+
+/// Generates a full mip chain from 8-bit RGBA pixels, down to a single pixel.
+///
+/// The first level is a copy of the input. Each subsequent level is produced
+/// with a 2x2 box filter. When `srgb` is set the color channels are averaged
+/// in linear space, as is appropriate for base color and emissive textures.
+///
+/// # Panics
+///
+/// Panics if `pixels` does not hold exactly `width * height` RGBA pixels or if
+/// either dimension is zero.
+pub fn mip_chain(pixels: &[u8], width: u32, height: u32, srgb: bool) -> Vec<Level> {
+    assert!(width > 0 && height > 0);
+    assert_eq!((width * height * 4) as usize, pixels.len());
+    let mut levels = vec![Level { width, height, data: pixels.to_vec() }];
+    loop {
+        let next = {
+            let last = &levels[levels.len() - 1];
+            if last.width == 1 && last.height == 1 {
+                break;
+            }
+            downsample(last, srgb)
+        };
+        levels.push(next);
+    }
+    levels
+}
+
+/// Returns the data format descriptor of an 8-bit RGBA format.
+fn data_format_descriptor(srgb: bool) -> Vec<u8> {
+    let mut dfd = vec![0; 92];
+    // Total size, then the basic descriptor block header.
+    LE::write_u32(&mut dfd[0..4], 92);
+    LE::write_u32(&mut dfd[4..8], 0);
+    LE::write_u16(&mut dfd[8..10], 2);
+    LE::write_u16(&mut dfd[10..12], 88);
+    // RGBSDA color model, BT.709 primaries, transfer function, straight alpha.
+    dfd[12] = 1;
+    dfd[13] = 1;
+    dfd[14] = if srgb { 2 } else { 1 };
+    dfd[15] = 0;
+    // 1x1x1 texel blocks of 4 bytes in a single plane.
+    dfd[20] = 4;
+    for (i, &channel) in [0u8, 1, 2, 15].iter().enumerate() {
+        let sample = &mut dfd[28 + i * 16..44 + i * 16];
+        LE::write_u16(&mut sample[0..2], i as u16 * 8);
+        sample[2] = 7;
+        // Alpha is linear even in sRGB formats.
+        sample[3] = if srgb && channel == 15 { channel | 0x10 } else { channel };
+        LE::write_u32(&mut sample[8..12], 0);
+        LE::write_u32(&mut sample[12..16], 255);
+    }
+    dfd
+}
+
+/// Encodes a mip chain of 8-bit RGBA pixels as a KTX2 container.
+///
+/// The texture uses the `R8G8B8A8_SRGB` format if `srgb` is set and
+/// `R8G8B8A8_UNORM` otherwise, without supercompression. The levels are
+/// stored uncompressed, not as Basis Universal data, so the result must not be
+/// referenced through `KHR_texture_basisu`, and core glTF only allows PNG and
+/// JPEG images. It suits runtimes loading KTX2 files directly, or can be
+/// passed to a Basis Universal encoder. This crate does not decode images, so
+/// the pixels of PNG or JPEG sources must be decoded by the caller.
+///
+/// # Panics
+///
+/// Panics if `levels` is empty.
+pub fn encode_ktx2(levels: &[Level], srgb: bool) -> Vec<u8> {
+    assert!(!levels.is_empty());
+    let level_index = 80;
+    let dfd_offset = level_index + levels.len() * 24;
+    let dfd = data_format_descriptor(srgb);
+    let data_offset = dfd_offset + dfd.len();
+    let total = data_offset + levels.iter().map(|level| level.data.len()).sum::<usize>();
+
+    let mut out = vec![0; total];
+    out[0..12].copy_from_slice(&KTX2_IDENTIFIER);
+    let format = if srgb { VK_FORMAT_R8G8B8A8_SRGB } else { VK_FORMAT_R8G8B8A8_UNORM };
+    LE::write_u32_into(
+        &[format, 1, levels[0].width, levels[0].height, 0, 0, 1, levels.len() as u32, 0],
+        &mut out[12..48],
+    );
+    LE::write_u32_into(&[dfd_offset as u32, dfd.len() as u32, 0, 0], &mut out[48..64]);
+    out[dfd_offset..data_offset].copy_from_slice(&dfd);
+
+    // Level data is stored from the smallest level to the largest, each at a
+    // multiple of four bytes since every level is a whole number of pixels.
+    let mut offset = data_offset;
+    for (i, level) in levels.iter().enumerate().rev() {
+        let length = level.data.len();
+        out[offset..offset + length].copy_from_slice(&level.data);
+        let entry = level_index + i * 24;
+        LE::write_u64_into(
+            &[offset as u64, length as u64, length as u64],
+            &mut out[entry..entry + 24],
+        );
+        offset += length;
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use byteorder::{LE, ByteOrder};
+
+    use super::*;
+
+    #[test]
+    fn mips() {
+        let pixels = [
+            255, 0, 0, 255, 0, 0, 0, 255, 255, 255, 255, 0, 255, 255, 255, 0,
+            255, 0, 0, 255, 0, 0, 0, 255, 255, 255, 255, 0, 255, 255, 255, 0,
+        ];
+        let levels = mip_chain(&pixels, 4, 2, false);
+        let sizes: Vec<_> = levels.iter().map(|level| (level.width, level.height)).collect();
+        assert_eq!(vec![(4, 2), (2, 1), (1, 1)], sizes);
+        assert_eq!(vec![128, 0, 0, 255, 255, 255, 255, 0], levels[1].data);
+        assert_eq!(vec![192, 128, 128, 128], levels[2].data);
+
+        let ktx2 = encode_ktx2(&levels, true);
+        assert_eq!(&KTX2_IDENTIFIER, &ktx2[0..12]);
+        assert_eq!(VK_FORMAT_R8G8B8A8_SRGB, LE::read_u32(&ktx2[12..16]));
+        assert_eq!(3, LE::read_u32(&ktx2[40..44]));
+        let offset = LE::read_u64(&ktx2[80..88]) as usize;
+        assert_eq!(&levels[0].data[..], &ktx2[offset..offset + 32]);
+        assert_eq!(ktx2.len(), offset + 32);
+    }
+}
@@ -0,0 +1,164 @@
+use gltf::{json, Gltf};
+
+/// Factor overrides for a single material.
+///
+/// Factors left as `None` keep the value of the base asset.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Factors {
+    /// The base color factor.
+    pub base_color: Option<[f32; 4]>,
+
+    /// The metalness factor.
+    pub metallic: Option<f32>,
+
+    /// The roughness factor.
+    pub roughness: Option<f32>,
+
+    /// The emissive factor.
+    pub emissive: Option<[f32; 3]>,
+}
+
+/// One row of a variant table, e.g. one colorway of a product.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Variant {
+    /// Replacement URIs of images, by image index.
+    ///
+    /// Images stored in buffer views are pointed at the URI instead.
+    pub images: Vec<(usize, String)>,
+
+    /// Factor overrides, by material index.
+    pub materials: Vec<(usize, Factors)>,
+}
+
+/// Stamps out one document per variant of a base asset.
+///
+/// Every output shares the buffers of the base asset. Buffers that already
+/// reference an external file keep their URI, and the others, i.e. the binary
+/// chunk of a `.glb` file and embedded `data:` URIs, are pointed at the URI
+/// returned by `buffer_uri` for their index, where the caller writes the buffer
+/// data once for all variants.
+///
+/// # Panics
+///
+/// Panics if a variant references an image or material that does not exist.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn run() -> Result<(), Box<std::error::Error>> {
+/// use gltf_utils::variants::{generate, Factors, Variant};
+///
+/// let gltf = gltf::Gltf::from_slice(include_bytes!("../../examples/Box.gltf"))?
+///     .validate_completely()?;
+/// let colorways = [[0.8, 0.0, 0.0, 1.0], [0.0, 0.0, 0.8, 1.0]];
+/// let variants: Vec<Variant> = colorways
+///     .iter()
+///     .map(|&color| Variant {
+///         materials: vec![(0, Factors { base_color: Some(color), ..Default::default() })],
+///         ..Default::default()
+///     })
+///     .collect();
+/// let outputs = generate(&gltf, &variants, |_| "Box0.bin".to_string());
+/// assert_eq!(2, outputs.len());
+/// let material = outputs[1].materials().next().unwrap();
+/// assert_eq!([0.0, 0.0, 0.8, 1.0], material.pbr_metallic_roughness().base_color_factor());
+/// # Ok(())
+/// # }
+/// # fn main() { run().unwrap() }
+/// ```
+pub fn generate<F>(gltf: &Gltf, variants: &[Variant], mut buffer_uri: F) -> Vec<Gltf>
+    where F: FnMut(usize) -> String
+{
+    let mut base = gltf.as_json().clone();
+    for (index, buffer) in base.buffers.iter_mut().enumerate() {
+        let external = match buffer.uri {
+            Some(ref uri) => !uri.starts_with("data:"),
+            None => false,
+        };
+        if !external {
+            buffer.uri = Some(buffer_uri(index));
+        }
+    }
+
+    variants
+        .iter()
+        .map(|variant| {
+            let mut root = base.clone();
+            for &(index, ref uri) in &variant.images {
+                let image = &mut root.images[index];
+                image.uri = Some(uri.clone());
+                image.buffer_view = None;
+                image.mime_type = None;
+            }
+            for &(index, ref factors) in &variant.materials {
+                apply(&mut root.materials[index], factors);
+            }
+            Gltf::from_json(root).skip_validation()
+        })
+        .collect()
+}
+
+/// Overrides the factors of a material.
+fn apply(material: &mut json::Material, factors: &Factors) {
+    let pbr = &mut material.pbr_metallic_roughness;
+    if let Some(base_color) = factors.base_color {
+        pbr.base_color_factor = json::material::PbrBaseColorFactor(base_color);
+    }
+    if let Some(metallic) = factors.metallic {
+        pbr.metallic_factor = json::material::StrengthFactor(metallic);
+    }
+    if let Some(roughness) = factors.roughness {
+        pbr.roughness_factor = json::material::StrengthFactor(roughness);
+    }
+    if let Some(emissive) = factors.emissive {
+        material.emissive_factor = json::material::EmissiveFactor(emissive);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const JSON: &'static str = r#"{
+        "asset": { "version": "2.0" },
+        "buffers": [
+            { "byteLength": 4 },
+            { "byteLength": 4, "uri": "shared.bin" },
+            { "byteLength": 3, "uri": "data:application/octet-stream;base64,AAAA" }
+        ],
+        "bufferViews": [{ "buffer": 0, "byteLength": 4 }],
+        "images": [{ "bufferView": 0, "mimeType": "image/png" }, { "uri": "a.png" }],
+        "materials": [{ "pbrMetallicRoughness": { "metallicFactor": 0.5 } }, {}]
+    }"#;
+
+    #[test]
+    fn colorways() {
+        let gltf = Gltf::from_str(JSON).unwrap().skip_validation();
+        let variants = vec![
+            Variant::default(),
+            Variant {
+                images: vec![(0, "red.png".to_string())],
+                materials: vec![(0, Factors { roughness: Some(0.25), ..Default::default() })],
+            },
+        ];
+        let outputs = generate(&gltf, &variants, |index| format!("base{}.bin", index));
+        assert_eq!(2, outputs.len());
+        for output in &outputs {
+            let uris: Vec<_> = output.buffers().map(|b| b.as_json().uri.clone()).collect();
+            let uris: Vec<_> = uris.into_iter().map(Option::unwrap).collect();
+            assert_eq!(vec!["base0.bin", "shared.bin", "base2.bin"], uris);
+        }
+
+        let image = &outputs[0].as_json().images[0];
+        assert_eq!(Some(0), image.buffer_view.as_ref().map(json::Index::value));
+        let image = &outputs[1].as_json().images[0];
+        assert!(image.buffer_view.is_none() && image.mime_type.is_none());
+        assert_eq!(Some("red.png".to_string()), image.uri);
+
+        let pbr = &outputs[1].as_json().materials[0].pbr_metallic_roughness;
+        assert_eq!(0.5, pbr.metallic_factor.0);
+        assert_eq!(0.25, pbr.roughness_factor.0);
+        let pbr = &outputs[0].as_json().materials[0].pbr_metallic_roughness;
+        assert_eq!(1.0, pbr.roughness_factor.0);
+    }
+}
@@ -0,0 +1,490 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use gltf::{self, json, Gltf};
+use gltf::mesh::Mode;
+
+use batching::push_indices;
+use interleave::accessor_buffer;
+use lint::triangles;
+use write::Writer;
+use PrimitiveIterators;
+
+/// The changes made by `fix`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Report {
+    /// The number of triangles flipped to agree with their neighbors.
+    pub inconsistent: usize,
+
+    /// The number of connected groups of triangles flipped to face outward.
+    pub inside_out: usize,
+
+    /// The number of connected groups of triangles whose vertex normals and
+    /// tangent handedness were negated to face outward.
+    pub reversed_normals: usize,
+
+    /// The `(mesh, primitive)` indices of every rewritten primitive.
+    pub primitives: Vec<(usize, usize)>,
+
+    /// The indices of the nodes with a mirroring world transform.
+    pub mirrored_nodes: Vec<usize>,
+
+    /// The indices of the meshes flipped because every node instantiating them
+    /// is mirrored.
+    pub mirrored_meshes: Vec<usize>,
+}
+
+/// Returns the vertices of a triangle in winding order.
+fn wound(triangle: [u32; 3], flipped: bool) -> [u32; 3] {
+    if flipped {
+        [triangle[0], triangle[2], triangle[1]]
+    } else {
+        triangle
+    }
+}
+
+/// Returns the undirected key of an edge.
+fn edge_key(a: u32, b: u32) -> (u32, u32) {
+    if a < b { (a, b) } else { (b, a) }
+}
+
+/// Flips triangles so that every pair of triangles sharing an edge traverse it
+/// in opposite directions, as far as possible.
+///
+/// The first triangle of each group of connected triangles keeps its winding
+/// and the winding spreads from it to its neighbors. Returns the groups of
+/// connected triangles and the number of triangles flipped.
+pub fn orient(triangles: &mut [[u32; 3]]) -> (Vec<Vec<usize>>, usize) {
+    let mut edges: HashMap<(u32, u32), Vec<usize>> = HashMap::new();
+    for (i, t) in triangles.iter().enumerate() {
+        for &(a, b) in &[(t[0], t[1]), (t[1], t[2]), (t[2], t[0])] {
+            edges.entry(edge_key(a, b)).or_default().push(i);
+        }
+    }
+
+    let mut flipped: Vec<Option<bool>> = vec![None; triangles.len()];
+    let mut groups = vec![];
+    for seed in 0..triangles.len() {
+        if flipped[seed].is_some() {
+            continue;
+        }
+        flipped[seed] = Some(false);
+        let mut group = vec![];
+        let mut queue = VecDeque::new();
+        queue.push_back(seed);
+        while let Some(current) = queue.pop_front() {
+            group.push(current);
+            let t = wound(triangles[current], flipped[current].unwrap());
+            for &(a, b) in &[(t[0], t[1]), (t[1], t[2]), (t[2], t[0])] {
+                for &neighbor in &edges[&edge_key(a, b)] {
+                    if flipped[neighbor].is_some() {
+                        continue;
+                    }
+                    // A consistent neighbor traverses the edge from `b` to `a`.
+                    let n = triangles[neighbor];
+                    let same_direction = (0..3).any(|k| n[k] == a && n[(k + 1) % 3] == b);
+                    flipped[neighbor] = Some(same_direction);
+                    queue.push_back(neighbor);
+                }
+            }
+        }
+        groups.push(group);
+    }
+
+    let mut count = 0;
+    for (triangle, flipped) in triangles.iter_mut().zip(flipped) {
+        if flipped == Some(true) {
+            *triangle = wound(*triangle, true);
+            count += 1;
+        }
+    }
+    (groups, count)
+}
+
+fn sub(a: [f32; 3], b: [f32; 3]) -> [f32; 3] {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+fn cross(a: [f32; 3], b: [f32; 3]) -> [f32; 3] {
+    [a[1] * b[2] - a[2] * b[1], a[2] * b[0] - a[0] * b[2], a[0] * b[1] - a[1] * b[0]]
+}
+
+fn dot(a: [f32; 3], b: [f32; 3]) -> f32 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+/// Returns whether a group of triangles forms a closed surface, on which
+/// every directed edge of the consistently wound triangles has a reversed twin.
+fn is_closed(triangles: &[[u32; 3]], group: &[usize]) -> bool {
+    let mut edges = HashSet::new();
+    for &i in group {
+        let t = triangles[i];
+        for &(a, b) in &[(t[0], t[1]), (t[1], t[2]), (t[2], t[0])] {
+            if !edges.remove(&(b, a)) {
+                edges.insert((a, b));
+            }
+        }
+    }
+    edges.is_empty()
+}
+
+/// Returns whether the winding and whether the vertex normals of a group of
+/// consistently wound triangles must be flipped for both to face outward.
+///
+/// Closed groups are judged by the sign of their enclosed volume, and their
+/// vertex normals by whether they agree with the outward winding. Open groups
+/// are judged against the vertex normals if present, which are kept.
+fn reorientation(
+    triangles: &[[u32; 3]],
+    group: &[usize],
+    positions: &[[f32; 3]],
+    normals: Option<&[[f32; 3]]>,
+) -> (bool, bool) {
+    let face = |t: [u32; 3]| {
+        let a = positions[t[0] as usize];
+        let b = positions[t[1] as usize];
+        let c = positions[t[2] as usize];
+        (a, cross(sub(b, a), sub(c, a)))
+    };
+    let agreement = normals.map(|normals| {
+        group
+            .iter()
+            .map(|&i| {
+                let t = triangles[i];
+                let (_, normal) = face(t);
+                let vertex_normals = t.iter().fold([0.0; 3], |sum, &v| {
+                    let n = normals[v as usize];
+                    [sum[0] + n[0], sum[1] + n[1], sum[2] + n[2]]
+                });
+                dot(normal, vertex_normals)
+            })
+            .sum::<f32>()
+    });
+    if !is_closed(triangles, group) {
+        return (agreement.is_some_and(|agreement| agreement < 0.0), false);
+    }
+    let volume: f32 = group
+        .iter()
+        .map(|&i| {
+            let (a, normal) = face(triangles[i]);
+            dot(a, normal)
+        })
+        .sum();
+    let inside_out = volume < 0.0;
+    // Normals agreeing with an inward winding face inward as well.
+    let inward_normals = agreement.is_some_and(|agreement| (agreement < 0.0) != inside_out);
+    (inside_out, inward_normals)
+}
+
+/// Returns the indices of every node whose world transform mirrors geometry,
+/// that is whose transform has a negative determinant.
+///
+/// The glTF specification requires renderers to reverse the winding order of
+/// meshes instantiated by such nodes. Nodes are visited from the root nodes of
+/// every scene.
+pub fn mirrored_nodes(gltf: &Gltf) -> Vec<usize> {
+    let mut mirrored = vec![false; gltf.nodes().len()];
+    for scene in gltf.scenes() {
+        for (node, world) in scene.walk() {
+            mirrored[node.index()] |= world.is_mirrored();
+        }
+    }
+    (0..mirrored.len()).filter(|&i| mirrored[i]).collect()
+}
+
+/// Makes the triangle winding of every primitive consistent and outward
+/// facing.
+///
+/// Triangles are flipped to agree with the winding of their neighbors, then
+/// connected groups of triangles are flipped to face outward, judged by
+/// enclosed volume for closed groups and against the vertex normals for open
+/// ones. The vertex normals of closed groups facing inward are negated in the
+/// same pass, together with the `w` component of their tangents to keep the
+/// bitangents, while those of open groups are taken as authoritative. Morph
+/// target normals are left unchanged.
+///
+/// If `unmirror` is set, meshes instantiated only by nodes with a mirroring
+/// world transform are flipped as well. This suits renderers that do not
+/// reverse the winding for such nodes, but makes other renderers draw the
+/// meshes inside out.
+///
+/// The indices, normals, and tangents of rewritten primitives are appended as
+/// new accessors to the buffer containing their positions, and strips and fans
+/// are rewritten as triangle lists. The original accessors are left in place.
+pub fn fix(gltf: Gltf, buffers: &mut Vec<Vec<u8>>, unmirror: bool) -> (Gltf, Report) {
+    let mut report = Report {
+        mirrored_nodes: mirrored_nodes(&gltf),
+        ..Default::default()
+    };
+    if unmirror {
+        let mut instances: HashMap<usize, bool> = HashMap::new();
+        for node in gltf.nodes() {
+            if let Some(mesh) = node.mesh() {
+                let mirrored = report.mirrored_nodes.contains(&node.index());
+                *instances.entry(mesh.index()).or_insert(true) &= mirrored;
+            }
+        }
+        report.mirrored_meshes = instances
+            .into_iter()
+            .filter(|&(_, mirrored)| mirrored)
+            .map(|(mesh, _)| mesh)
+            .collect();
+        report.mirrored_meshes.sort();
+    }
+
+    let mut rewritten = vec![];
+    for mesh in gltf.meshes() {
+        let mirrored = report.mirrored_meshes.contains(&mesh.index());
+        for primitive in mesh.primitives() {
+            let positions: Vec<_> = match primitive.positions(&*buffers) {
+                Some(positions) => positions.collect(),
+                None => continue,
+            };
+            let mut normals: Option<Vec<_>> = primitive.normals(&*buffers).map(Iterator::collect);
+            let original = triangles(&primitive, &*buffers);
+            if original.iter().any(|t| t.iter().any(|&v| v as usize >= positions.len())) {
+                continue;
+            }
+
+            let mut fixed = original.clone();
+            let (groups, inconsistent) = orient(&mut fixed);
+            let mut inside_out = 0;
+            let mut reversed = vec![false; positions.len()];
+            let mut reversed_normals = 0;
+            for group in &groups {
+                let (winding, normals) =
+                    reorientation(&fixed, group, &positions, normals.as_deref());
+                if winding {
+                    for &i in group {
+                        fixed[i] = wound(fixed[i], true);
+                    }
+                    inside_out += 1;
+                }
+                if normals {
+                    for &i in group {
+                        for &v in &fixed[i] {
+                            reversed[v as usize] = true;
+                        }
+                    }
+                    reversed_normals += 1;
+                }
+            }
+            if mirrored {
+                for triangle in &mut fixed {
+                    *triangle = wound(*triangle, true);
+                }
+            }
+
+            let rewind = fixed != original || (mirrored && !fixed.is_empty());
+            if !rewind && reversed_normals == 0 {
+                continue;
+            }
+            report.inconsistent += inconsistent;
+            report.inside_out += inside_out;
+            report.reversed_normals += reversed_normals;
+            let indices = if rewind {
+                Some(fixed.iter().flat_map(|t| t.iter().cloned()).collect())
+            } else {
+                None
+            };
+            let mut tangents = None;
+            if reversed_normals > 0 {
+                tangents = primitive.tangents(&*buffers).map(Iterator::collect::<Vec<_>>);
+                for (v, _) in reversed.iter().enumerate().filter(|&(_, &reversed)| reversed) {
+                    if let Some(normal) = normals.as_mut().and_then(|n| n.get_mut(v)) {
+                        *normal = [-normal[0], -normal[1], -normal[2]];
+                    }
+                    if let Some(tangent) = tangents.as_mut().and_then(|t| t.get_mut(v)) {
+                        tangent[3] = -tangent[3];
+                    }
+                }
+            } else {
+                normals = None;
+            }
+            rewritten.push(Rewritten {
+                mesh: mesh.index(),
+                primitive: primitive.index(),
+                buffer: accessor_buffer(&primitive.get(&gltf::Semantic::Positions).unwrap()),
+                indices,
+                normals,
+                tangents,
+            });
+        }
+    }
+
+    let mut root = gltf.into_json();
+    for rewritten in rewritten {
+        let buffer = rewritten.buffer;
+        let indices = rewritten
+            .indices
+            .map(|indices| push_indices(&mut root, buffers, buffer, &indices));
+        let mut writer = Writer::new(buffer).target(json::buffer::Target::ArrayBuffer);
+        let normals = rewritten.normals.map(|normals| {
+            let data: Vec<f32> = normals.iter().flat_map(|n| n.iter().cloned()).collect();
+            writer.push_f32(&mut root, buffers, &data, json::accessor::Type::Vec3)
+        });
+        let tangents = rewritten.tangents.map(|tangents| {
+            let data: Vec<f32> = tangents.iter().flat_map(|t| t.iter().cloned()).collect();
+            writer.push_f32(&mut root, buffers, &data, json::accessor::Type::Vec4)
+        });
+
+        let primitive = &mut root.meshes[rewritten.mesh].primitives[rewritten.primitive];
+        if indices.is_some() {
+            primitive.indices = indices;
+            primitive.mode = json::validation::Checked::Valid(Mode::Triangles);
+        }
+        if let Some(normals) = normals {
+            let semantic = json::validation::Checked::Valid(gltf::Semantic::Normals);
+            primitive.attributes.insert(semantic, normals);
+        }
+        if let Some(tangents) = tangents {
+            let semantic = json::validation::Checked::Valid(gltf::Semantic::Tangents);
+            primitive.attributes.insert(semantic, tangents);
+        }
+        report.primitives.push((rewritten.mesh, rewritten.primitive));
+    }
+    (Gltf::from_json(root).skip_validation(), report)
+}
+
+/// The data of a primitive rewritten by `fix`.
+struct Rewritten {
+    mesh: usize,
+    primitive: usize,
+
+    /// The buffer holding the positions of the primitive, which the new data
+    /// is appended to.
+    buffer: usize,
+
+    /// The new triangle list, if the winding changed.
+    indices: Option<Vec<u32>>,
+
+    /// The new vertex normals, if any were negated.
+    normals: Option<Vec<[f32; 3]>>,
+
+    /// The new vertex tangents, if any normals were negated.
+    tangents: Option<Vec<[f32; 4]>>,
+}
+
+#[cfg(test)]
+mod tests {
+    use byteorder::{LE, ByteOrder};
+    use gltf::Gltf;
+
+    use super::*;
+
+    const JSON: &'static str = r#"{
+        "asset": { "version": "2.0" },
+        "buffers": [{ "byteLength": 60 }],
+        "bufferViews": [
+            { "buffer": 0, "byteOffset": 0, "byteLength": 48 },
+            { "buffer": 0, "byteOffset": 48, "byteLength": 12 }
+        ],
+        "accessors": [
+            { "bufferView": 0, "componentType": 5126, "count": 4, "type": "VEC3" },
+            { "bufferView": 1, "componentType": 5121, "count": 12, "type": "SCALAR" }
+        ],
+        "meshes": [{ "primitives": [{ "attributes": { "POSITION": 0 }, "indices": 1 }] }],
+        "nodes": [
+            { "children": [1], "scale": [-1, 1, 1] },
+            { "mesh": 0 },
+            { "scale": [-1, -1, 1] }
+        ],
+        "scenes": [{ "nodes": [0, 2] }]
+    }"#;
+
+    fn buffers(indices: &[u8]) -> Vec<Vec<u8>> {
+        let mut data = vec![0; 60];
+        let positions = [0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0];
+        LE::write_f32_into(&positions, &mut data[0..48]);
+        data[48..60].copy_from_slice(indices);
+        vec![data]
+    }
+
+    #[test]
+    fn orient_neighbors() {
+        let mut triangles = [[0, 1, 2], [1, 3, 2], [3, 2, 4], [5, 6, 7]];
+        let (groups, flipped) = orient(&mut triangles);
+        assert_eq!(1, flipped);
+        assert_eq!(vec![vec![0, 1, 2], vec![3]], groups);
+        assert_eq!([3, 4, 2], triangles[2]);
+    }
+
+    #[test]
+    fn fix_inside_out() {
+        // A tetrahedron wound inward, with one triangle wound outward.
+        let mut buffers = buffers(&[0, 1, 2, 0, 3, 1, 0, 2, 3, 1, 2, 3]);
+        let gltf = Gltf::from_str(JSON).unwrap().skip_validation();
+        let (gltf, report) = fix(gltf, &mut buffers, false);
+        assert_eq!(1, report.inconsistent);
+        assert_eq!(1, report.inside_out);
+        assert_eq!(vec![(0, 0)], report.primitives);
+        assert_eq!(vec![0, 1], report.mirrored_nodes);
+
+        let mesh = gltf.meshes().next().unwrap();
+        let primitive = mesh.primitives().next().unwrap();
+        let fixed = triangles(&primitive, &buffers);
+        assert_eq!(vec![[0, 2, 1], [0, 1, 3], [0, 3, 2], [1, 2, 3]], fixed);
+
+        let (_, report) = fix(gltf, &mut buffers, false);
+        assert!(report.primitives.is_empty());
+    }
+
+    #[test]
+    fn fix_inward_normals() {
+        // A tetrahedron wound inward, with normals and tangents of that
+        // orientation appended to the buffer.
+        let json = JSON
+            .replace(r#""byteLength": 60 }"#, r#""byteLength": 172 }"#)
+            .replace(
+                r#"{ "buffer": 0, "byteOffset": 48, "byteLength": 12 }"#,
+                r#"{ "buffer": 0, "byteOffset": 48, "byteLength": 12 },
+                { "buffer": 0, "byteOffset": 60, "byteLength": 48 },
+                { "buffer": 0, "byteOffset": 108, "byteLength": 64 }"#,
+            )
+            .replace(
+                r#""count": 12, "type": "SCALAR" }"#,
+                r#""count": 12, "type": "SCALAR" },
+                { "bufferView": 2, "componentType": 5126, "count": 4, "type": "VEC3" },
+                { "bufferView": 3, "componentType": 5126, "count": 4, "type": "VEC4" }"#,
+            )
+            .replace(r#""POSITION": 0 }"#, r#""POSITION": 0, "NORMAL": 2, "TANGENT": 3 }"#);
+        let mut buffers = buffers(&[0, 1, 2, 0, 3, 1, 0, 2, 3, 1, 3, 2]);
+        let normals = [
+            0.25, 0.25, 0.25,
+            -0.75, 0.25, 0.25,
+            0.25, -0.75, 0.25,
+            0.25, 0.25, -0.75,
+        ];
+        let mut data = vec![0; 112];
+        LE::write_f32_into(&normals, &mut data[0..48]);
+        LE::write_f32_into(&[1.0, 0.0, 0.0, 1.0].repeat(4), &mut data[48..112]);
+        buffers[0].extend(data);
+        let gltf = Gltf::from_str(&json).unwrap().skip_validation();
+        let (gltf, report) = fix(gltf, &mut buffers, false);
+        assert_eq!((0, 1, 1), (report.inconsistent, report.inside_out, report.reversed_normals));
+
+        let mesh = gltf.meshes().next().unwrap();
+        let primitive = mesh.primitives().next().unwrap();
+        let fixed = triangles(&primitive, &buffers);
+        assert_eq!(vec![[0, 2, 1], [0, 1, 3], [0, 3, 2], [1, 2, 3]], fixed);
+        let normals: Vec<_> = primitive.normals(&buffers).unwrap().collect();
+        assert_eq!([-0.25, -0.25, -0.25], normals[0]);
+        assert_eq!([0.75, -0.25, -0.25], normals[1]);
+        let tangents: Vec<_> = primitive.tangents(&buffers).unwrap().collect();
+        assert!(tangents.iter().all(|&t| t == [1.0, 0.0, 0.0, -1.0]));
+
+        let (_, report) = fix(gltf, &mut buffers, false);
+        assert!(report.primitives.is_empty());
+    }
+
+    #[test]
+    fn fix_mirrored() {
+        let mut buffers = buffers(&[0, 2, 1, 0, 1, 3, 0, 3, 2, 1, 2, 3]);
+        let gltf = Gltf::from_str(JSON).unwrap().skip_validation();
+        let (gltf, report) = fix(gltf, &mut buffers, true);
+        assert_eq!(vec![0], report.mirrored_meshes);
+        assert_eq!((0, 0), (report.inconsistent, report.inside_out));
+        let mesh = gltf.meshes().next().unwrap();
+        let primitive = mesh.primitives().next().unwrap();
+        assert_eq!([0, 1, 2], triangles(&primitive, &buffers)[0]);
+    }
+}
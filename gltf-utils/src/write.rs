@@ -0,0 +1,681 @@
+
+use byteorder::{LE, ByteOrder};
+use gltf;
+use gltf::json;
+use gltf::json::accessor::{ComponentType, Type};
+use gltf::json::mesh::Semantic;
+use gltf::json::validation::Checked;
+
+use layout::{LayoutPlanner, Placement};
+
+/// A component type accessors can be written with.
+pub trait Component: Copy {
+    /// The component type of accessors holding this type.
+    const COMPONENT_TYPE: ComponentType;
+
+    /// Writes the value in little endian byte order.
+    fn write(self, bytes: &mut [u8]);
+
+    /// Converts the value for numeric comparison.
+    fn to_f64(self) -> f64;
+
+    /// Converts the value to a JSON number for accessor bounds.
+    fn to_value(self) -> json::Value;
+}
+
+macro_rules! impl_component {
+    ($ty:ty, $component_type:ident, |$x:ident, $bytes:ident| $write:expr) => {
+        impl Component for $ty {
+            const COMPONENT_TYPE: ComponentType = ComponentType::$component_type;
+
+            fn write(self, $bytes: &mut [u8]) {
+                let $x = self;
+                $write
+            }
+
+            fn to_f64(self) -> f64 {
+                self as f64
+            }
+
+            fn to_value(self) -> json::Value {
+                json::Value::from(self)
+            }
+        }
+    };
+}
+
+impl_component!(i8, I8, |x, bytes| bytes[0] = x as u8);
+impl_component!(u8, U8, |x, bytes| bytes[0] = x);
+impl_component!(i16, I16, |x, bytes| LE::write_i16(bytes, x));
+impl_component!(u16, U16, |x, bytes| LE::write_u16(bytes, x));
+impl_component!(u32, U32, |x, bytes| LE::write_u32(bytes, x));
+impl_component!(f32, F32, |x, bytes| LE::write_f32(bytes, x));
+
+/// Appends `f32` accessors to a buffer, switching to sparse storage when it
+/// saves enough space.
+///
+/// Sparse accessors are written without a buffer view, so every element not
+/// stored in the sparse values reads as zero. Sparse storage therefore pays
+/// off for mostly-zero accessors, as is the case for the morph targets of a
+/// mesh.
+///
+/// # Examples
+///
+/// ```rust
+/// # extern crate gltf;
+/// # extern crate gltf_utils;
+/// # fn main() {
+/// use gltf::json;
+/// use gltf_utils::write::Writer;
+///
+/// let mut root = json::Root::default();
+/// root.buffers.push(json::Buffer::new(0));
+/// let mut buffers = vec![vec![]];
+///
+/// let mut displacements = vec![0.0; 300];
+/// displacements[42] = 1.0;
+/// let mut writer = Writer::new(0).sparse(0.5);
+/// let vec3 = json::accessor::Type::Vec3;
+/// let index = writer.push_f32(&mut root, &mut buffers, &displacements, vec3);
+/// assert!(root.accessors[index.value()].sparse.is_some());
+/// assert!(root.accessors[index.value()].buffer_view.is_none());
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct Writer {
+    /// The index of the buffer to append data to.
+    buffer: usize,
+
+    /// The target of dense buffer views.
+    target: Option<json::buffer::Target>,
+
+    /// Whether to compute the `min` and `max` of every accessor.
+    bounds: bool,
+
+    /// The largest ratio of sparse to dense size at which sparse storage is
+    /// used, if enabled.
+    sparse_threshold: Option<f32>,
+}
+
+impl Writer {
+    /// Creates a writer appending dense accessors to the given buffer.
+    pub fn new(buffer: usize) -> Self {
+        Writer {
+            buffer,
+            target: None,
+            bounds: false,
+            sparse_threshold: None,
+        }
+    }
+
+    /// Sets the target of the buffer views holding vertex data.
+    ///
+    /// Views holding sparse indices and values never have a target.
+    pub fn target(mut self, target: json::buffer::Target) -> Self {
+        self.target = Some(target);
+        self
+    }
+
+    /// Computes the `min` and `max` of every written accessor, as required for
+    /// `POSITION` attributes and animation sampler inputs.
+    pub fn bounds(mut self) -> Self {
+        self.bounds = true;
+        self
+    }
+
+    /// Enables sparse storage for accessors whose sparse encoding takes at most
+    /// `threshold` times the bytes of their dense encoding, i.e. the bytes of
+    /// the sparse indices and values.
+    pub fn sparse(mut self, threshold: f32) -> Self {
+        self.sparse_threshold = Some(threshold);
+        self
+    }
+
+    /// Appends `data` as a new accessor of the given type, returning its index.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the length of `data` is not a multiple of the number of
+    /// components of `type_`.
+    pub fn push_f32(
+        &mut self,
+        root: &mut json::Root,
+        buffers: &mut [Vec<u8>],
+        data: &[f32],
+        type_: Type,
+    ) -> json::Index<json::Accessor> {
+        let components = components(type_);
+        assert_eq!(0, data.len() % components);
+        let count = data.len() / components;
+        let dense_length = data.len() * 4;
+
+        let nonzero: Vec<usize> = (0..count)
+            .filter(|&i| data[i * components..(i + 1) * components].iter().any(|&x| x != 0.0))
+            .collect();
+        let (_, index_size) = index_type(count);
+
+        let mut accessor = match self.sparse_threshold {
+            Some(threshold) if !nonzero.is_empty() && nonzero.len() < count => {
+                let sparse_length = nonzero.len() * (index_size + components * 4);
+                if sparse_length as f32 <= threshold * dense_length as f32 {
+                    Some(self.push_sparse(root, buffers, data, type_, &nonzero))
+                } else {
+                    None
+                }
+            },
+            _ => None,
+        }.unwrap_or_else(|| {
+            let placement = self.push_view(root, buffers, &f32_bytes(data), self.target);
+            let view = json::Index::new(placement.view);
+            json::Accessor::new(view, ComponentType::F32, type_, count as u32)
+        });
+
+        if self.bounds && count > 0 {
+            let mut min = data[..components].to_vec();
+            let mut max = min.clone();
+            for element in data.chunks(components) {
+                for (i, &x) in element.iter().enumerate() {
+                    min[i] = min[i].min(x);
+                    max[i] = max[i].max(x);
+                }
+            }
+            accessor.min = Some(json::Value::from(min));
+            accessor.max = Some(json::Value::from(max));
+        }
+        root.accessors.push(accessor);
+        json::Index::new(root.accessors.len() as u32 - 1)
+    }
+
+    /// Appends `data` as a new tightly packed accessor of the given type,
+    /// returning its index.
+    ///
+    /// Unlike `push_f32`, this never uses sparse storage. The columns of
+    /// matrices of one and two byte components are padded to four byte
+    /// boundaries, so such matrices get a byte stride.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the length of `data` is not a multiple of the number of
+    /// components of `type_`.
+    pub fn push<T: Component>(
+        &mut self,
+        root: &mut json::Root,
+        buffers: &mut [Vec<u8>],
+        data: &[T],
+        type_: Type,
+    ) -> json::Index<json::Accessor> {
+        self.push_dense(root, buffers, data, type_, false)
+    }
+
+    /// Appends `data` as a new vertex attribute of the primitive at
+    /// `(mesh, primitive)`, returning the index of its accessor.
+    ///
+    /// Any semantic may be written, including application specific ones such
+    /// as `_FEATURE_ID_0` or `_BATCHID`, which are parsed from their names with
+    /// the `extras` feature. Elements not a multiple of four bytes long are
+    /// padded with a byte stride, as the specification requires for vertex
+    /// attributes. Integer colors, texture coordinates, and joint weights are
+    /// marked as normalized, as the specification requires. An existing
+    /// attribute of the same semantic is replaced.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the mesh or primitive does not exist, or if the length of
+    /// `data` is not a multiple of the number of components of `type_`.
+    pub fn push_attribute<T: Component>(
+        &mut self,
+        root: &mut json::Root,
+        buffers: &mut [Vec<u8>],
+        (mesh, primitive): (usize, usize),
+        semantic: Semantic,
+        data: &[T],
+        type_: Type,
+    ) -> json::Index<json::Accessor> {
+        assert!(primitive < root.meshes[mesh].primitives.len(), "primitive out of range");
+        let index = self.push_dense(root, buffers, data, type_, true);
+        root.accessors[index.value()].normalized = match semantic {
+            Semantic::Colors(_) | Semantic::TexCoords(_) | Semantic::Weights(_) => {
+                T::COMPONENT_TYPE != ComponentType::F32
+            },
+            _ => false,
+        };
+        let attributes = &mut root.meshes[mesh].primitives[primitive].attributes;
+        attributes.insert(Checked::Valid(semantic), json::Index::new(index.value() as u32));
+        index
+    }
+
+    /// Writes a vertex attribute holding the same element for every vertex of
+    /// every primitive of a node's mesh, e.g. a per-node feature ID.
+    ///
+    /// The vertex count of each primitive is taken from its `POSITION`
+    /// accessor, and primitives without one are skipped. Every node sharing
+    /// the mesh gets the attribute too, so meshes may need to be expanded with
+    /// `instancing::expand` first. Returns the number of primitives written.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the node does not exist, or if the length of `element` is not
+    /// the number of components of `type_`.
+    pub fn fill_node_attribute<T: Component>(
+        &mut self,
+        root: &mut json::Root,
+        buffers: &mut [Vec<u8>],
+        node: usize,
+        semantic: Semantic,
+        element: &[T],
+        type_: Type,
+    ) -> usize {
+        assert_eq!(components(type_), element.len());
+        let mesh = match root.nodes[node].mesh {
+            Some(ref mesh) => mesh.value(),
+            None => return 0,
+        };
+        let mut written = 0;
+        for primitive in 0..root.meshes[mesh].primitives.len() {
+            let positions = Checked::Valid(Semantic::Positions);
+            let attributes = &root.meshes[mesh].primitives[primitive].attributes;
+            let count = match attributes.get(&positions) {
+                Some(accessor) => root.accessors[accessor.value()].count as usize,
+                None => continue,
+            };
+            let length = count * element.len();
+            let data: Vec<T> = element.iter().cloned().cycle().take(length).collect();
+            let semantic = semantic.clone();
+            self.push_attribute(root, buffers, (mesh, primitive), semantic, &data, type_);
+            written += 1;
+        }
+        written
+    }
+
+    /// Appends a dense accessor, optionally padding each element to a four
+    /// byte boundary.
+    fn push_dense<T: Component>(
+        &mut self,
+        root: &mut json::Root,
+        buffers: &mut [Vec<u8>],
+        data: &[T],
+        type_: Type,
+        aligned: bool,
+    ) -> json::Index<json::Accessor> {
+        let components = components(type_);
+        assert_eq!(0, data.len() % components);
+        let count = data.len() / components;
+        let (offsets, element) = ::element_layout(T::COMPONENT_TYPE, type_);
+        // Matrix columns start on four byte boundaries, so consecutive
+        // matrices must too.
+        let matrix = matches!(type_, Type::Mat2 | Type::Mat3 | Type::Mat4);
+        let stride = if aligned || matrix { element.next_multiple_of(4) } else { element };
+
+        let length = if count > 0 { (count - 1) * stride + element } else { 0 };
+        let mut bytes = vec![0; length];
+        for (i, element) in data.chunks(components).enumerate() {
+            for (&offset, &x) in offsets.iter().zip(element) {
+                x.write(&mut bytes[i * stride + offset..]);
+            }
+        }
+        let view = self.push_view(root, buffers, &bytes, self.target).view;
+        if stride != element {
+            let view = &mut root.buffer_views[view as usize];
+            view.byte_stride = Some(json::buffer::ByteStride(stride as u32));
+        }
+
+        let mut accessor =
+            json::Accessor::new(json::Index::new(view), T::COMPONENT_TYPE, type_, count as u32);
+        if self.bounds {
+            if let Some((min, max)) = bounds(data, type_) {
+                accessor.min = Some(min);
+                accessor.max = Some(max);
+            }
+        }
+        root.accessors.push(accessor);
+        json::Index::new(root.accessors.len() as u32 - 1)
+    }
+
+    /// Creates a sparse accessor storing only the given elements.
+    fn push_sparse(
+        &self,
+        root: &mut json::Root,
+        buffers: &mut [Vec<u8>],
+        data: &[f32],
+        type_: Type,
+        nonzero: &[usize],
+    ) -> json::Accessor {
+        let components = components(type_);
+        let (index_type, index_size) = index_type(data.len() / components);
+        let mut bytes = vec![0; nonzero.len() * index_size];
+        for (n, &i) in nonzero.iter().enumerate() {
+            match index_type {
+                ComponentType::U8 => bytes[n] = i as u8,
+                ComponentType::U16 => LE::write_u16(&mut bytes[n * 2..], i as u16),
+                _ => LE::write_u32(&mut bytes[n * 4..], i as u32),
+            }
+        }
+        let indices_view = self.push_view(root, buffers, &bytes, None).view;
+
+        let values: Vec<f32> = nonzero
+            .iter()
+            .flat_map(|&i| data[i * components..(i + 1) * components].iter().cloned())
+            .collect();
+        let values_view = self.push_view(root, buffers, &f32_bytes(&values), None).view;
+
+        let count = (data.len() / components) as u32;
+        let sparse = json::accessor::sparse::Sparse::new(
+            nonzero.len() as u32,
+            json::accessor::sparse::Indices::new(json::Index::new(indices_view), index_type),
+            json::accessor::sparse::Values::new(json::Index::new(values_view)),
+        );
+        json::Accessor::sparse(ComponentType::F32, type_, count, sparse)
+    }
+
+    /// Appends `data` as a buffer view of its own at a four byte boundary,
+    /// as planned by `LayoutPlanner`.
+    fn push_view(
+        &self,
+        root: &mut json::Root,
+        buffers: &mut [Vec<u8>],
+        data: &[u8],
+        target: Option<json::buffer::Target>,
+    ) -> Placement {
+        let mut planner = LayoutPlanner::new(root, self.buffer).grouped(false);
+        let placement = planner.add(data, 4, target);
+        planner.finish(root, buffers);
+        placement
+    }
+}
+
+/// Overwrites the elements of an existing accessor in place within loaded
+/// buffer data, e.g. after recomputing normals.
+///
+/// Each element is written at the view offset plus the accessor offset plus
+/// its index times the byte stride, so interleaved attributes and matrix
+/// column padding are respected and the bytes between elements are left
+/// untouched. Nothing is appended and no buffer is resized, so every other
+/// accessor stays valid. Sparse substitutions are not written, and `min` and
+/// `max` are not updated; use `bounds` to recompute them.
+///
+/// # Examples
+///
+/// ```rust
+/// # extern crate gltf;
+/// # extern crate gltf_utils;
+/// # fn run() -> Result<(), Box<std::error::Error>> {
+/// use gltf_utils::PrimitiveIterators;
+/// use gltf_utils::write::overwrite;
+///
+/// let gltf = gltf::Gltf::from_slice(include_bytes!("../../examples/Box.gltf"))?
+///     .validate_completely()?;
+/// let mut buffers = vec![include_bytes!("../../examples/Box0.bin").to_vec()];
+/// let mesh = gltf.meshes().next().unwrap();
+/// let primitive = mesh.primitives().next().unwrap();
+///
+/// // Flip every normal.
+/// let normals: Vec<f32> = primitive
+///     .normals(&buffers)
+///     .unwrap()
+///     .flat_map(|[x, y, z]| vec![-x, -y, -z])
+///     .collect();
+/// let accessor = primitive.get(&gltf::Semantic::Normals).unwrap();
+/// overwrite(&accessor, &mut buffers, &normals);
+/// assert_eq!(normals[..3], primitive.normals(&buffers).unwrap().next().unwrap());
+/// # Ok(())
+/// # }
+/// # fn main() { run().unwrap() }
+/// ```
+///
+/// # Panics
+///
+/// Panics if `T` is not the component type of the accessor, if the length of
+/// `data` is not the number of components of the accessor, if the accessor has
+/// no buffer view, or if the buffer data is too short to hold the accessor.
+pub fn overwrite<T: Component>(accessor: &gltf::Accessor, buffers: &mut [Vec<u8>], data: &[T]) {
+    assert_eq!(T::COMPONENT_TYPE, accessor.data_type(), "component type mismatch");
+    let (offsets, element_size) = ::layout(accessor);
+    assert_eq!(accessor.count() * offsets.len(), data.len(), "component count mismatch");
+    if data.is_empty() {
+        return;
+    }
+
+    let view = accessor.view().expect("accessor without a buffer view");
+    let stride = view.stride().unwrap_or(element_size);
+    let start = view.offset() + accessor.offset();
+    let end = start + stride * (accessor.count() - 1) + element_size;
+    let bytes = &mut buffers[view.buffer().index()][start..end];
+    for (i, element) in data.chunks(offsets.len()).enumerate() {
+        for (&offset, &x) in offsets.iter().zip(element) {
+            x.write(&mut bytes[i * stride + offset..]);
+        }
+    }
+}
+
+/// Returns the component-wise `min` and `max` of tightly packed elements of
+/// the given type, as JSON arrays for accessor bounds.
+///
+/// Returns `None` if `data` is empty.
+///
+/// # Panics
+///
+/// Panics if the length of `data` is not a multiple of the number of
+/// components of `type_`.
+pub fn bounds<T: Component>(data: &[T], type_: Type) -> Option<(json::Value, json::Value)> {
+    let components = components(type_);
+    assert_eq!(0, data.len() % components);
+    if data.is_empty() {
+        return None;
+    }
+    let mut min = data[..components].to_vec();
+    let mut max = min.clone();
+    for element in data.chunks(components) {
+        for (i, &x) in element.iter().enumerate() {
+            if x.to_f64() < min[i].to_f64() {
+                min[i] = x;
+            }
+            if x.to_f64() > max[i].to_f64() {
+                max[i] = x;
+            }
+        }
+    }
+    Some((
+        json::Value::Array(min.into_iter().map(T::to_value).collect()),
+        json::Value::Array(max.into_iter().map(T::to_value).collect()),
+    ))
+}
+
+/// Returns the number of components of an accessor type.
+fn components(type_: Type) -> usize {
+    match type_ {
+        Type::Scalar => 1,
+        Type::Vec2 => 2,
+        Type::Vec3 => 3,
+        Type::Vec4 | Type::Mat2 => 4,
+        Type::Mat3 => 9,
+        Type::Mat4 => 16,
+    }
+}
+
+/// Returns the smallest sparse index type able to address `count` elements and
+/// its size in bytes.
+fn index_type(count: usize) -> (ComponentType, usize) {
+    if count <= 1 << 8 {
+        (ComponentType::U8, 1)
+    } else if count <= 1 << 16 {
+        (ComponentType::U16, 2)
+    } else {
+        (ComponentType::U32, 4)
+    }
+}
+
+/// Returns the little endian bytes of `f32` values.
+fn f32_bytes(data: &[f32]) -> Vec<u8> {
+    let mut bytes = vec![0; data.len() * 4];
+    LE::write_f32_into(data, &mut bytes);
+    bytes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn root() -> json::Root {
+        let mut root = json::Root::default();
+        root.buffers.push(json::Buffer::new(0));
+        root
+    }
+
+    #[test]
+    fn sparse_targets() {
+        let mut root = root();
+        let mut buffers = vec![vec![]];
+        let mut writer = Writer::new(0).sparse(0.5).bounds();
+
+        let mut first = vec![0.0; 30];
+        first[4] = 2.0;
+        let mut second = vec![0.0; 30];
+        second[27] = -1.0;
+        let dense = vec![1.0; 30];
+        let a = writer.push_f32(&mut root, &mut buffers, &first, Type::Vec3);
+        let b = writer.push_f32(&mut root, &mut buffers, &second, Type::Vec3);
+        let c = writer.push_f32(&mut root, &mut buffers, &dense, Type::Vec3);
+
+        assert!(root.accessors[a.value()].sparse.is_some());
+        let sparse = root.accessors[b.value()].sparse.as_ref().unwrap();
+        assert_eq!(1, sparse.count);
+        assert!(root.accessors[c.value()].sparse.is_none());
+        assert_eq!(Some(json::Value::from(vec![-1.0, 0.0, 0.0])), root.accessors[b.value()].min);
+
+        assert_eq!(10, root.accessors[b.value()].count);
+    }
+
+    #[test]
+    fn sparse_without_view() {
+        let mut root = root();
+        let mut buffers = vec![vec![]];
+        let mut writer = Writer::new(0).sparse(1.0);
+        let mut data = vec![0.0; 300];
+        data[0] = 1.0;
+        let b = writer.push_f32(&mut root, &mut buffers, &data, Type::Scalar);
+        assert!(root.accessors[b.value()].buffer_view.is_none());
+        assert_eq!(2, root.buffer_views.len());
+        let indices = &root.accessors[b.value()].sparse.as_ref().unwrap().indices;
+        match indices.component_type {
+            Checked::Valid(json::accessor::IndexComponentType(ComponentType::U16)) => {},
+            _ => panic!("expected `u16` sparse indices"),
+        }
+        assert_eq!(buffers[0].len() as u32, root.buffers[0].byte_length);
+    }
+
+    #[test]
+    fn custom_attributes() {
+        let mut root: json::Root = json::from_str(r#"{
+            "asset": { "version": "2.0" },
+            "accessors": [{ "bufferView": 0, "componentType": 5126, "count": 3, "type": "VEC3" }],
+            "buffers": [{ "byteLength": 0 }],
+            "bufferViews": [{ "buffer": 0, "byteLength": 36 }],
+            "meshes": [{
+                "primitives": [{ "attributes": { "POSITION": 0 } }, { "attributes": {} }]
+            }],
+            "nodes": [{ "mesh": 0 }]
+        }"#).unwrap();
+        let mut buffers = vec![vec![]];
+        let mut writer = Writer::new(0).bounds();
+        let colors = [255u8, 0, 0, 0, 255, 0, 0, 0, 255];
+        let semantic = Semantic::Colors(0);
+        writer.push_attribute(&mut root, &mut buffers, (0, 1), semantic, &colors, Type::Vec3);
+        let view = &root.buffer_views[root.accessors[1].buffer_view.as_ref().unwrap().value()];
+        assert_eq!(Some(4), view.byte_stride.map(|stride| stride.0));
+        assert_eq!(11, view.byte_length);
+        assert_eq!(&[0, 0, 255], &buffers[0][view.byte_offset as usize + 8..][..3]);
+        assert_eq!(Some(json::Value::from(vec![0, 0, 0])), root.accessors[1].min);
+        assert!(root.accessors[1].normalized);
+
+        let written = writer.fill_node_attribute(
+            &mut root,
+            &mut buffers,
+            0,
+            Semantic::TexCoords(3),
+            &[7u16],
+            Type::Scalar,
+        );
+        assert_eq!(1, written);
+        let attributes = &root.meshes[0].primitives[0].attributes;
+        let accessor = &root.accessors[attributes[&Checked::Valid(Semantic::TexCoords(3))].value()];
+        assert_eq!(3, accessor.count);
+        assert!(accessor.normalized);
+        let view = &root.buffer_views[accessor.buffer_view.as_ref().unwrap().value()];
+        let offset = view.byte_offset as usize;
+        assert_eq!(7, LE::read_u16(&buffers[0][offset + 8..]));
+    }
+
+    #[test]
+    fn padded_matrices() {
+        let mut root = root();
+        let mut buffers = vec![vec![]];
+        let mut writer = Writer::new(0);
+        let data = [1u8, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18];
+        let index = writer.push(&mut root, &mut buffers, &data, Type::Mat3);
+        let view = root.accessors[index.value()].buffer_view.as_ref().unwrap();
+        let view = &root.buffer_views[view.value()];
+        assert_eq!(Some(12), view.byte_stride.map(|stride| stride.0));
+        assert_eq!(23, view.byte_length);
+        assert_eq!(&[1, 2, 3, 0, 4, 5, 6, 0, 7, 8, 9, 0], &buffers[0][..12]);
+        assert_eq!(&[10, 11, 12, 0, 13, 14, 15, 0, 16, 17, 18], &buffers[0][12..23]);
+    }
+
+    #[test]
+    fn overwrite_interleaved() {
+        let json = r#"{
+            "asset": { "version": "2.0" },
+            "buffers": [{ "byteLength": 40 }],
+            "bufferViews": [{ "buffer": 0, "byteOffset": 8, "byteLength": 32, "byteStride": 16 }],
+            "accessors": [
+                { "bufferView": 0, "componentType": 5126, "count": 2, "type": "VEC3" },
+                {
+                    "bufferView": 0, "byteOffset": 12, "componentType": 5121,
+                    "normalized": true, "count": 2, "type": "VEC4"
+                }
+            ]
+        }"#;
+        let gltf = ::gltf::Gltf::from_str(json).unwrap().skip_validation();
+        let mut buffers = vec![vec![0xAA; 40]];
+        let normals = gltf.accessors().next().unwrap();
+        overwrite(&normals, &mut buffers, &[1.0f32, 0.0, 0.0, 0.0, -1.0, 0.0]);
+        let colors = gltf.accessors().nth(1).unwrap();
+        overwrite(&colors, &mut buffers, &[1u8, 2, 3, 4, 5, 6, 7, 8]);
+
+        assert_eq!(&[0xAA; 8], &buffers[0][..8]);
+        assert_eq!(1.0, LE::read_f32(&buffers[0][8..]));
+        assert_eq!(&[1, 2, 3, 4], &buffers[0][20..24]);
+        assert_eq!(-1.0, LE::read_f32(&buffers[0][28..]));
+        assert_eq!(&[5, 6, 7, 8], &buffers[0][36..40]);
+        assert_eq!(40, buffers[0].len());
+    }
+
+    #[test]
+    #[should_panic(expected = "component type mismatch")]
+    fn overwrite_mismatch() {
+        let gltf = ::gltf::Gltf::from_str(r#"{
+            "asset": { "version": "2.0" },
+            "buffers": [{ "byteLength": 12 }],
+            "bufferViews": [{ "buffer": 0, "byteLength": 12 }],
+            "accessors": [{ "bufferView": 0, "componentType": 5126, "count": 1, "type": "VEC3" }]
+        }"#).unwrap().skip_validation();
+        let accessor = gltf.accessors().next().unwrap();
+        overwrite(&accessor, &mut [vec![0; 12]], &[1u16, 2, 3]);
+    }
+
+    #[cfg(feature = "extras")]
+    #[test]
+    fn application_specific_semantics() {
+        let mut root: json::Root = json::from_str(r#"{
+            "asset": { "version": "2.0" },
+            "buffers": [{ "byteLength": 0 }],
+            "meshes": [{ "primitives": [{ "attributes": {} }] }]
+        }"#).unwrap();
+        let mut buffers = vec![vec![]];
+        let semantic: Semantic = "_BATCHID".parse().unwrap();
+        let ids = [1.0f32, 1.0, 2.0];
+        let mut writer = Writer::new(0);
+        writer.push_attribute(&mut root, &mut buffers, (0, 0), semantic, &ids, Type::Scalar);
+        let key = root.meshes[0].primitives[0].attributes.keys().next().unwrap().to_string();
+        assert_eq!("_BATCHID", key);
+    }
+}
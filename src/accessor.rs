@@ -17,8 +17,8 @@ pub struct Accessor<'a> {
     /// The corresponding JSON struct.
     json: &'a json::accessor::Accessor,
 
-    /// The buffer view this accessor reads from.
-    view: buffer::View<'a>,
+    /// The buffer view this accessor reads from, if any.
+    view: Option<buffer::View<'a>>,
 }
 
 impl<'a> Accessor<'a> {
@@ -28,7 +28,7 @@ impl<'a> Accessor<'a> {
         index: usize,
         json: &'a json::accessor::Accessor,
     ) -> Self {
-        let view = gltf.views().nth(json.buffer_view.value()).unwrap();
+        let view = json.buffer_view.as_ref().map(|view| gltf.views().nth(view.value()).unwrap());
         Self {
             gltf,
             index,
@@ -54,8 +54,11 @@ impl<'a> Accessor<'a> {
     }
 
     /// Returns the buffer view this accessor reads from.
-    pub fn view(&self) -> buffer::View<'a> {
-        self.gltf.views().nth(self.json.buffer_view.value()).unwrap()
+    ///
+    /// Returns `None` if the accessor has no buffer view, in which case its
+    /// elements are all zero, except for those replaced by `sparse` values.
+    pub fn view(&self) -> Option<buffer::View<'a>> {
+        self.view.clone()
     }
 
     /// Returns the offset relative to the start of the parent buffer view in bytes.
@@ -1,8 +1,16 @@
 use std::slice;
 use {accessor, json, scene, Gltf};
 
+#[cfg(feature = "extras")]
+use material::Material;
+
 pub use json::animation::{InterpolationAlgorithm, TrsProperty};
 
+/// The `extras` keys that conventionally hold a JSON pointer to an animated
+/// property, e.g. `"/materials/0/emissiveFactor"`, in assets predating
+/// `KHR_animation_pointer`.
+pub const POINTER_KEYS: &'static [&'static str] = &["pointer", "targetPointer", "targetPath"];
+
 /// A keyframe animation.
 #[derive(Clone, Debug)]
 pub struct Animation<'a> {
@@ -137,6 +145,48 @@ impl<'a> Channel<'a> {
         Target::new(self.anim.clone(), &self.json.target)
     }
 
+    /// Returns the JSON pointer to the animated property stored in the
+    /// `extras` of the channel or its target under one of the `POINTER_KEYS`.
+    ///
+    /// When present, the channel animates the referenced property instead of
+    /// its node, and the sampler output holds values of that property.
+    #[cfg(feature = "extras")]
+    pub fn pointer(&self) -> Option<&'a str> {
+        let json = self.json;
+        let find = |extras: &'a json::Extras| {
+            let object = extras.as_ref()?.as_object()?;
+            POINTER_KEYS
+                .iter()
+                .filter_map(|key| object.get(*key).and_then(|value| value.as_str()))
+                .find(|pointer| pointer.starts_with('/'))
+        };
+        find(&json.extras).or_else(|| find(&json.target.extras))
+    }
+
+    /// Returns the material and property path, relative to the material, of
+    /// an animated material property.
+    ///
+    /// For example, the pointer `"/materials/0/pbrMetallicRoughness/baseColorFactor"`
+    /// yields the first material and `"pbrMetallicRoughness/baseColorFactor"`.
+    ///
+    /// Returns `None` if the channel has no pointer or if it does not reference
+    /// a property of an existing material.
+    #[cfg(feature = "extras")]
+    pub fn material_target(&self) -> Option<(Material<'a>, &'a str)> {
+        let mut tokens = self.pointer()?[1..].splitn(3, '/');
+        if tokens.next() != Some("materials") {
+            return None;
+        }
+        let index = tokens.next()?;
+        let property = tokens.next()?;
+        let digits = index.bytes().all(|b| b.is_ascii_digit());
+        if !digits || (index.len() > 1 && index.starts_with('0')) {
+            return None;
+        }
+        let material = self.anim.gltf.materials().nth(index.parse().ok()?)?;
+        Some((material, property))
+    }
+
     /// Optional application specific data.
     pub fn extras(&self) -> &json::Extras {
         &self.json.extras
@@ -0,0 +1,62 @@
+use json;
+
+/// Metadata about the glTF asset.
+#[derive(Clone, Debug)]
+pub struct Asset<'a> {
+    /// The corresponding JSON struct.
+    json: &'a json::asset::Asset,
+}
+
+impl<'a> Asset<'a> {
+    /// Constructs an `Asset`.
+    pub(crate) fn new(json: &'a json::asset::Asset) -> Self {
+        Self {
+            json,
+        }
+    }
+
+    /// Returns the internal JSON item.
+    #[doc(hidden)]
+    pub fn as_json(&self) -> &json::asset::Asset {
+        self.json
+    }
+
+    /// A copyright message suitable for display to credit the content creator.
+    pub fn copyright(&self) -> Option<&str> {
+        self.json.copyright.as_deref()
+    }
+
+    /// Optional application specific data.
+    pub fn extras(&self) -> &json::Extras {
+        &self.json.extras
+    }
+
+    /// Tool that generated this glTF model.
+    pub fn generator(&self) -> Option<&str> {
+        self.json.generator.as_deref()
+    }
+
+    /// The minimum glTF version that this asset targets.
+    pub fn min_version(&self) -> Option<&str> {
+        self.json.min_version.as_deref()
+    }
+
+    /// The glTF version of this asset.
+    pub fn version(&self) -> &str {
+        &self.json.version
+    }
+
+    /// The scale of the asset in meters per unit, read from the conventional
+    /// `metersPerUnit` property of the asset `extras`.
+    #[cfg(feature = "extras")]
+    pub fn meters_per_unit(&self) -> Option<f64> {
+        self.json.meters_per_unit()
+    }
+
+    /// The up axis the asset was authored in, read from the conventional
+    /// `upAxis` property of the asset `extras`.
+    #[cfg(feature = "extras")]
+    pub fn up_axis(&self) -> Option<&str> {
+        self.json.up_axis()
+    }
+}
@@ -0,0 +1,291 @@
+use std::{iter, slice};
+
+use json;
+use json::extensions::audio as ext;
+
+use buffer;
+use Gltf;
+
+pub use json::extensions::audio::Positional;
+
+/// Encoded audio data, from the `KHR_audio` extension.
+#[derive(Clone, Debug)]
+pub struct Clip<'a> {
+    /// The parent `Gltf` struct.
+    gltf: &'a Gltf,
+
+    /// The corresponding JSON index.
+    index: usize,
+
+    /// The corresponding JSON struct.
+    json: &'a ext::Clip,
+}
+
+/// The data referenced by a `Clip`.
+pub enum Data<'a> {
+    /// Audio data is contained in a buffer view.
+    View {
+        /// The buffer view containing the encoded audio data.
+        view: buffer::View<'a>,
+
+        /// The audio data MIME type.
+        mime_type: &'a str,
+    },
+
+    /// Audio data is contained in an external data source.
+    Uri {
+        /// The URI of the external data source.
+        uri: &'a str,
+
+        /// The audio data MIME type, if provided.
+        mime_type: Option<&'a str>,
+    },
+}
+
+/// The playback settings of a `Clip`.
+#[derive(Clone, Debug)]
+pub struct Source<'a> {
+    /// The parent `Gltf` struct.
+    gltf: &'a Gltf,
+
+    /// The corresponding JSON index.
+    index: usize,
+
+    /// The corresponding JSON struct.
+    json: &'a ext::Source,
+}
+
+/// Mixes a set of sources, either globally or at the position of a node.
+#[derive(Clone, Debug)]
+pub struct Emitter<'a> {
+    /// The parent `Gltf` struct.
+    gltf: &'a Gltf,
+
+    /// The corresponding JSON index.
+    index: usize,
+
+    /// The corresponding JSON struct.
+    json: &'a ext::Emitter,
+}
+
+/// An `Iterator` that visits every audio clip in a glTF asset.
+#[derive(Clone, Debug)]
+pub struct Clips<'a> {
+    /// The parent `Gltf` struct.
+    pub(crate) gltf: &'a Gltf,
+
+    /// The internal JSON iterator.
+    pub(crate) iter: iter::Enumerate<slice::Iter<'a, ext::Clip>>,
+}
+
+/// An `Iterator` that visits every audio source in a glTF asset.
+#[derive(Clone, Debug)]
+pub struct Sources<'a> {
+    /// The parent `Gltf` struct.
+    pub(crate) gltf: &'a Gltf,
+
+    /// The internal JSON iterator.
+    pub(crate) iter: iter::Enumerate<slice::Iter<'a, ext::Source>>,
+}
+
+/// An `Iterator` that visits every audio emitter in a glTF asset.
+#[derive(Clone, Debug)]
+pub struct Emitters<'a> {
+    /// The parent `Gltf` struct.
+    pub(crate) gltf: &'a Gltf,
+
+    /// The internal JSON iterator.
+    pub(crate) iter: iter::Enumerate<slice::Iter<'a, ext::Emitter>>,
+}
+
+/// Returns the document level `KHR_audio` extension object.
+fn audio(gltf: &Gltf) -> &ext::Audio {
+    gltf.as_json().extensions.audio.as_ref().unwrap()
+}
+
+impl<'a> Clip<'a> {
+    /// Constructs a `Clip`.
+    pub(crate) fn new(gltf: &'a Gltf, index: usize, json: &'a ext::Clip) -> Self {
+        Self {
+            gltf,
+            index,
+            json,
+        }
+    }
+
+    /// Returns the internal JSON index.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Returns the internal JSON item.
+    #[doc(hidden)]
+    pub fn as_json(&self) -> &ext::Clip {
+        self.json
+    }
+
+    /// Optional user-defined name for this object.
+    #[cfg(feature = "names")]
+    pub fn name(&self) -> Option<&str> {
+        self.json.name.as_deref()
+    }
+
+    /// Returns references to the audio data.
+    pub fn data(&self) -> Data<'a> {
+        let mime_type = self.json.mime_type.as_deref();
+        if let Some(index) = self.json.buffer_view.as_ref() {
+            let view = self.gltf
+                .views()
+                .nth(index.value())
+                .unwrap();
+            Data::View { view, mime_type: mime_type.unwrap() }
+        } else {
+            let uri = self.json.uri.as_ref().unwrap();
+            Data::Uri { uri, mime_type }
+        }
+    }
+}
+
+impl<'a> Source<'a> {
+    /// Constructs a `Source`.
+    pub(crate) fn new(gltf: &'a Gltf, index: usize, json: &'a ext::Source) -> Self {
+        Self {
+            gltf,
+            index,
+            json,
+        }
+    }
+
+    /// Returns the internal JSON index.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Returns the internal JSON item.
+    #[doc(hidden)]
+    pub fn as_json(&self) -> &ext::Source {
+        self.json
+    }
+
+    /// Optional user-defined name for this object.
+    #[cfg(feature = "names")]
+    pub fn name(&self) -> Option<&str> {
+        self.json.name.as_deref()
+    }
+
+    /// Returns the clip played by this source, if any.
+    pub fn clip(&self) -> Option<Clip<'a>> {
+        self.json.clip.as_ref().map(|index| {
+            let json = &audio(self.gltf).clips[index.value()];
+            Clip::new(self.gltf, index.value(), json)
+        })
+    }
+
+    /// Returns whether playback starts when the scene is loaded.
+    pub fn auto_play(&self) -> bool {
+        self.json.auto_play
+    }
+
+    /// Returns the linear volume multiplier of the source.
+    pub fn gain(&self) -> f32 {
+        self.json.gain
+    }
+
+    /// Returns whether playback restarts when the clip ends.
+    pub fn looping(&self) -> bool {
+        self.json.loop_
+    }
+}
+
+impl<'a> Emitter<'a> {
+    /// Constructs an `Emitter`.
+    pub(crate) fn new(gltf: &'a Gltf, index: usize, json: &'a ext::Emitter) -> Self {
+        Self {
+            gltf,
+            index,
+            json,
+        }
+    }
+
+    /// Constructs the `Emitter` at the given JSON index.
+    pub(crate) fn from_index(gltf: &'a Gltf, index: &json::Index<ext::Emitter>) -> Self {
+        Emitter::new(gltf, index.value(), &audio(gltf).emitters[index.value()])
+    }
+
+    /// Returns the internal JSON index.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Returns the internal JSON item.
+    #[doc(hidden)]
+    pub fn as_json(&self) -> &ext::Emitter {
+        self.json
+    }
+
+    /// Optional user-defined name for this object.
+    #[cfg(feature = "names")]
+    pub fn name(&self) -> Option<&str> {
+        self.json.name.as_deref()
+    }
+
+    /// Returns the linear volume multiplier of the emitter.
+    pub fn gain(&self) -> f32 {
+        self.json.gain
+    }
+
+    /// Returns the cone and distance attenuation of a positional emitter, or
+    /// `None` for a global emitter.
+    pub fn positional(&self) -> Option<&'a Positional> {
+        if self.json.type_ == "positional" {
+            self.json.positional.as_ref()
+        } else {
+            None
+        }
+    }
+
+    /// Returns the sources mixed by this emitter.
+    pub fn sources(&self) -> Vec<Source<'a>> {
+        let sources = &audio(self.gltf).sources;
+        self.json.sources
+            .iter()
+            .map(|index| Source::new(self.gltf, index.value(), &sources[index.value()]))
+            .collect()
+    }
+}
+
+impl<'a> ExactSizeIterator for Clips<'a> {}
+impl<'a> Iterator for Clips<'a> {
+    type Item = Clip<'a>;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|(index, json)| Clip::new(self.gltf, index, json))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<'a> ExactSizeIterator for Sources<'a> {}
+impl<'a> Iterator for Sources<'a> {
+    type Item = Source<'a>;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|(index, json)| Source::new(self.gltf, index, json))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<'a> ExactSizeIterator for Emitters<'a> {}
+impl<'a> Iterator for Emitters<'a> {
+    type Item = Emitter<'a>;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|(index, json)| Emitter::new(self.gltf, index, json))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
@@ -33,6 +33,16 @@ pub struct View<'a> {
     parent: Buffer<'a>,
 }
 
+/// An `Iterator` that visits every accessor reading from a buffer view.
+#[derive(Clone, Debug)]
+pub struct Accessors<'a> {
+    /// Internal accessor iterator.
+    iter: ::gltf::Accessors<'a>,
+
+    /// The index of the buffer view.
+    view: usize,
+}
+
 impl<'a> Buffer<'a> {
     /// Constructs a `Buffer`.
     pub(crate) fn new(
@@ -84,6 +94,47 @@ impl<'a> Buffer<'a> {
     pub fn extras(&self) -> &json::Extras {
         &self.json.extras
     }
+
+    /// Returns the CRC-32 checksum of the buffer data declared by the
+    /// `GLTFRS_buffer_checksum` extension, if any.
+    pub fn checksum(&self) -> Option<u32> {
+        self.json.extensions.buffer_checksum.as_ref().map(|ext| ext.crc32)
+    }
+
+    /// Verifies loaded buffer data against the declared checksum.
+    ///
+    /// Only the first `length()` bytes are checked, so the padding of a GLB
+    /// `BIN` chunk is ignored. Returns `None` if no checksum is declared or the
+    /// data is shorter than the buffer.
+    pub fn verify(&self, data: &[u8]) -> Option<bool> {
+        let expected = self.checksum()?;
+        let data = data.get(..self.length())?;
+        Some(crc32(data) == expected)
+    }
+}
+
+/// Computes the CRC-32 (IEEE 802.3) checksum of some data, as used by the
+/// `GLTFRS_buffer_checksum` extension.
+///
+/// # Examples
+///
+/// ```
+/// assert_eq!(0xCBF43926, gltf::buffer::crc32(b"123456789"));
+/// ```
+pub fn crc32(data: &[u8]) -> u32 {
+    let mut table = [0u32; 256];
+    for (i, entry) in table.iter_mut().enumerate() {
+        let mut crc = i as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 != 0 { 0xEDB88320 ^ (crc >> 1) } else { crc >> 1 };
+        }
+        *entry = crc;
+    }
+    let mut crc = !0u32;
+    for &byte in data {
+        crc = table[((crc ^ byte as u32) & 0xFF) as usize] ^ (crc >> 8);
+    }
+    !crc
 }
 
 impl<'a> View<'a> {
@@ -145,8 +196,66 @@ impl<'a> View<'a> {
         self.json.target.map(|target| target.unwrap())
     }
 
+    /// Returns the target the buffer view should be bound to, inferring it
+    /// from how mesh primitives use the view if `target` is not specified.
+    ///
+    /// Returns `None` if the view is not used by any mesh primitive.
+    pub fn target_hint(&self) -> Option<Target> {
+        if let Some(target) = self.target() {
+            return Some(target);
+        }
+        for mesh in self.gltf.meshes() {
+            for primitive in mesh.primitives() {
+                if let Some(indices) = primitive.indices() {
+                    if indices.view().map(|view| view.index()) == Some(self.index) {
+                        return Some(Target::ElementArrayBuffer);
+                    }
+                }
+                for (_, accessor) in primitive.attributes() {
+                    if accessor.view().map(|view| view.index()) == Some(self.index) {
+                        return Some(Target::ArrayBuffer);
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    /// Returns the accessors that read from this buffer view.
+    ///
+    /// Sparse accessor index and value views are not considered.
+    pub fn accessors(&self) -> Accessors<'a> {
+        Accessors {
+            iter: self.gltf.accessors(),
+            view: self.index,
+        }
+    }
+
+    /// Returns the part of the parent buffer data covered by this buffer view.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `buffer` is shorter than the buffer view requires.
+    pub fn slice<'s>(&self, buffer: &'s [u8]) -> &'s [u8] {
+        let begin = self.offset();
+        let end = begin + self.length();
+        &buffer[begin..end]
+    }
+
     /// Optional application specific data.
     pub fn extras(&self) -> &json::Extras {
         &self.json.extras
     }
 }
+
+impl<'a> Iterator for Accessors<'a> {
+    type Item = ::Accessor<'a>;
+    fn next(&mut self) -> Option<Self::Item> {
+        let view = self.view;
+        self.iter.by_ref().find(|accessor| accessor.view().map(|view| view.index()) == Some(view))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.iter.size_hint().1)
+    }
+}
@@ -0,0 +1,537 @@
+use byteorder::{LE, WriteBytesExt};
+use std::slice;
+
+use json;
+use json::accessor::{ComponentType, Type};
+use json::buffer::Target;
+use json::mesh::{Mode, Semantic};
+use json::validation::Checked;
+
+use {Error, Gltf};
+
+/// A component type accessors can be built from.
+pub trait Component: Copy + PartialOrd {
+    /// The component type of accessors holding this type.
+    const COMPONENT_TYPE: ComponentType;
+
+    /// Appends the value in little endian byte order.
+    fn write(self, bytes: &mut Vec<u8>);
+
+    /// Converts the value to a JSON number for accessor bounds.
+    fn to_value(self) -> json::Value;
+}
+
+/// An accessor element, i.e. a scalar or a vector of up to four components.
+pub trait Element: Copy {
+    /// The type of the components of the element.
+    type Component: Component;
+
+    /// The accessor type of the element.
+    const TYPE: Type;
+
+    /// Returns the components of the element.
+    fn components(&self) -> &[Self::Component];
+}
+
+/// A component type vertex indices can be built from, i.e. `u8`, `u16`, or
+/// `u32`.
+pub trait IndexComponent: Component + Element + sealed::Sealed {}
+
+mod sealed {
+    /// Prevents `IndexComponent` from being implemented outside this module.
+    pub trait Sealed {}
+
+    impl Sealed for u8 {}
+    impl Sealed for u16 {}
+    impl Sealed for u32 {}
+}
+
+impl IndexComponent for u8 {}
+impl IndexComponent for u16 {}
+impl IndexComponent for u32 {}
+
+macro_rules! impl_component {
+    ($ty:ty, $component_type:ident, |$x:ident, $bytes:ident| $write:expr) => {
+        impl Component for $ty {
+            const COMPONENT_TYPE: ComponentType = ComponentType::$component_type;
+
+            fn write(self, $bytes: &mut Vec<u8>) {
+                let $x = self;
+                $write.unwrap()
+            }
+
+            fn to_value(self) -> json::Value {
+                json::Value::from(self)
+            }
+        }
+
+        impl Element for $ty {
+            type Component = $ty;
+            const TYPE: Type = Type::Scalar;
+
+            fn components(&self) -> &[$ty] {
+                slice::from_ref(self)
+            }
+        }
+    };
+}
+
+impl_component!(i8, I8, |x, bytes| bytes.write_i8(x));
+impl_component!(u8, U8, |x, bytes| bytes.write_u8(x));
+impl_component!(i16, I16, |x, bytes| bytes.write_i16::<LE>(x));
+impl_component!(u16, U16, |x, bytes| bytes.write_u16::<LE>(x));
+impl_component!(u32, U32, |x, bytes| bytes.write_u32::<LE>(x));
+impl_component!(f32, F32, |x, bytes| bytes.write_f32::<LE>(x));
+
+macro_rules! impl_vector {
+    ($n:expr, $type_:ident) => {
+        impl<T: Component> Element for [T; $n] {
+            type Component = T;
+            const TYPE: Type = Type::$type_;
+
+            fn components(&self) -> &[T] {
+                &self[..]
+            }
+        }
+    };
+}
+
+impl_vector!(2, Vec2);
+impl_vector!(3, Vec3);
+impl_vector!(4, Vec4);
+
+/// Tightly packed accessor data, encoded ahead of being written to the buffer.
+#[derive(Clone, Debug)]
+struct Data {
+    /// The component type of the elements.
+    component_type: ComponentType,
+
+    /// The accessor type of the elements.
+    type_: Type,
+
+    /// The number of elements.
+    count: usize,
+
+    /// The encoded elements.
+    bytes: Vec<u8>,
+
+    /// The `min` and `max` of the elements, or `None` if there are none.
+    bounds: Option<(json::Value, json::Value)>,
+
+    /// Whether integer components are normalized to `[0, 1]` or `[-1, 1]`.
+    normalized: bool,
+}
+
+impl Data {
+    /// Encodes the given elements.
+    fn new<E: Element>(data: &[E]) -> Self {
+        let size = E::Component::COMPONENT_TYPE.size() * E::TYPE.multiplicity();
+        let mut bytes = Vec::with_capacity(data.len() * size);
+        for element in data {
+            for &x in element.components() {
+                x.write(&mut bytes);
+            }
+        }
+        let bounds = data.first().map(|first| {
+            let mut min = first.components().to_vec();
+            let mut max = min.clone();
+            for element in data {
+                for (i, &x) in element.components().iter().enumerate() {
+                    if x < min[i] {
+                        min[i] = x;
+                    }
+                    if x > max[i] {
+                        max[i] = x;
+                    }
+                }
+            }
+            let to_value = |xs: Vec<E::Component>| {
+                json::Value::from(xs.into_iter().map(Component::to_value).collect::<Vec<_>>())
+            };
+            (to_value(min), to_value(max))
+        });
+        Data {
+            component_type: E::Component::COMPONENT_TYPE,
+            type_: E::TYPE,
+            count: data.len(),
+            bytes,
+            bounds,
+            normalized: false,
+        }
+    }
+}
+
+/// Assembles a glTF asset from scratch, assigning indices and storing
+/// accessor data in a single binary buffer.
+///
+/// Every accessor gets its own tightly packed buffer view aligned to four
+/// bytes, along with its `min` and `max`. Vertex attributes whose elements are
+/// not a multiple of four bytes long are padded with a byte stride, as the
+/// specification requires.
+///
+/// # Examples
+///
+/// ```rust
+/// use gltf::build::{MeshBuilder, NodeBuilder, PrimitiveBuilder, RootBuilder};
+///
+/// let mut builder = RootBuilder::new();
+/// let triangle = PrimitiveBuilder::new()
+///     .positions(&[[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]])
+///     .indices(&[0u16, 1, 2]);
+/// let mesh = builder.mesh(MeshBuilder::new().primitive(triangle));
+/// let node = builder.node(NodeBuilder::new().mesh(mesh).translation([0.0, 0.0, -1.0]));
+/// builder.scene(&[node]);
+///
+/// let (root, bin) = builder.build().unwrap();
+/// assert_eq!(2, root.accessors.len());
+/// assert_eq!(root.buffers[0].byte_length as usize, bin.len());
+/// let glb = gltf::Gltf::from_json(root).validate_minimally().unwrap().to_glb(Some(&bin));
+/// assert!(glb.is_ok());
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct RootBuilder {
+    /// The JSON under construction.
+    root: json::Root,
+
+    /// The data of the binary buffer.
+    bin: Vec<u8>,
+
+    /// The index of the binary buffer, once data has been written to it.
+    buffer: Option<json::Index<json::Buffer>>,
+}
+
+impl RootBuilder {
+    /// Creates a builder of an empty asset.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Returns the JSON under construction, e.g. to add objects without a
+    /// builder of their own.
+    ///
+    /// Accessor data is written to the first buffer the builder creates, which
+    /// is buffer 0 unless buffers are added here beforehand.
+    pub fn as_json_mut(&mut self) -> &mut json::Root {
+        &mut self.root
+    }
+
+    /// Appends a new accessor holding `data`, e.g. the input or output of an
+    /// animation sampler, returning its index.
+    pub fn accessor<E: Element>(&mut self, data: &[E]) -> json::Index<json::Accessor> {
+        self.push(Data::new(data), None)
+    }
+
+    /// Appends a material, returning its index.
+    pub fn material(&mut self, material: json::Material) -> json::Index<json::Material> {
+        self.root.materials.push(material);
+        json::Index::new(self.root.materials.len() as u32 - 1)
+    }
+
+    /// Appends a mesh, writing the data of its primitives, and returns its
+    /// index.
+    pub fn mesh(&mut self, mesh: MeshBuilder) -> json::Index<json::Mesh> {
+        let mut json_mesh = mesh.json;
+        for primitive in mesh.primitives {
+            let mut json_primitive = primitive.json;
+            for (semantic, data) in primitive.attributes {
+                let index = self.push(data, Some(Target::ArrayBuffer));
+                json_primitive.attributes.insert(Checked::Valid(semantic), index);
+            }
+            if let Some(data) = primitive.indices {
+                json_primitive.indices = Some(self.push(data, Some(Target::ElementArrayBuffer)));
+            }
+            json_mesh.primitives.push(json_primitive);
+        }
+        self.root.meshes.push(json_mesh);
+        json::Index::new(self.root.meshes.len() as u32 - 1)
+    }
+
+    /// Appends a node, returning its index.
+    pub fn node(&mut self, node: NodeBuilder) -> json::Index<json::Node> {
+        self.root.nodes.push(node.json);
+        json::Index::new(self.root.nodes.len() as u32 - 1)
+    }
+
+    /// Appends a scene with the given root nodes, returning its index.
+    ///
+    /// The first scene appended becomes the default scene.
+    pub fn scene(&mut self, nodes: &[json::Index<json::Node>]) -> json::Index<json::Scene> {
+        self.root.scenes.push(json::Scene { nodes: nodes.to_vec(), ..Default::default() });
+        let index = json::Index::new(self.root.scenes.len() as u32 - 1);
+        if self.root.scene.is_none() {
+            self.root.scene = Some(index.clone());
+        }
+        index
+    }
+
+    /// Completes the asset, returning its validated JSON and the data of its
+    /// binary buffer.
+    ///
+    /// The buffer has no URI, so the data can be stored as the `BIN` chunk of
+    /// binary glTF with `Gltf::to_glb`, or the URI set before writing `.gltf`
+    /// JSON.
+    pub fn build(self) -> Result<(json::Root, Vec<u8>), Error> {
+        let RootBuilder { mut root, mut bin, buffer } = self;
+        if let Some(buffer) = buffer {
+            pad(&mut bin);
+            root.buffers[buffer.value()].byte_length = bin.len() as u32;
+        }
+        let gltf = Gltf::from_json(root).validate_completely()?;
+        Ok((gltf.into_json(), bin))
+    }
+
+    /// Writes the data of a new accessor to the binary buffer, returning the
+    /// index of the accessor.
+    fn push(&mut self, data: Data, target: Option<Target>) -> json::Index<json::Accessor> {
+        let root = &mut self.root;
+        let buffer = self.buffer.get_or_insert_with(|| {
+            root.buffers.push(json::Buffer::new(0));
+            json::Index::new(root.buffers.len() as u32 - 1)
+        }).clone();
+
+        let element = data.component_type.size() * data.type_.multiplicity();
+        let stride = match target {
+            Some(Target::ArrayBuffer) => element.next_multiple_of(4),
+            _ => element,
+        };
+        pad(&mut self.bin);
+        let offset = self.bin.len();
+        for (i, bytes) in data.bytes.chunks(element).enumerate() {
+            if i > 0 {
+                let end = self.bin.len() + stride - element;
+                self.bin.resize(end, 0);
+            }
+            self.bin.extend_from_slice(bytes);
+        }
+
+        let length = (self.bin.len() - offset) as u32;
+        let mut view = json::buffer::View::new(buffer, offset as u32, length);
+        view.target = target.map(Checked::Valid);
+        if stride != element {
+            view.byte_stride = Some(json::buffer::ByteStride(stride as u32));
+        }
+        root.buffer_views.push(view);
+
+        let view = json::Index::new(root.buffer_views.len() as u32 - 1);
+        let mut accessor =
+            json::Accessor::new(view, data.component_type, data.type_, data.count as u32);
+        accessor.normalized = data.normalized;
+        if let Some((min, max)) = data.bounds {
+            accessor.min = Some(min);
+            accessor.max = Some(max);
+        }
+        root.accessors.push(accessor);
+        json::Index::new(root.accessors.len() as u32 - 1)
+    }
+}
+
+/// Pads the binary buffer to a four byte boundary.
+fn pad(bin: &mut Vec<u8>) {
+    while !bin.len().is_multiple_of(4) {
+        bin.push(0);
+    }
+}
+
+/// Assembles a mesh to be appended with `RootBuilder::mesh`.
+#[derive(Clone, Debug, Default)]
+pub struct MeshBuilder {
+    /// The JSON of the mesh, without its primitives.
+    json: json::Mesh,
+
+    /// The primitives of the mesh.
+    primitives: Vec<PrimitiveBuilder>,
+}
+
+impl MeshBuilder {
+    /// Creates a builder of a mesh without primitives.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Sets the user-defined name of the mesh.
+    #[cfg(feature = "names")]
+    pub fn name<S: Into<String>>(mut self, name: S) -> Self {
+        self.json.name = Some(name.into());
+        self
+    }
+
+    /// Appends a primitive to the mesh.
+    pub fn primitive(mut self, primitive: PrimitiveBuilder) -> Self {
+        self.primitives.push(primitive);
+        self
+    }
+
+    /// Sets the default morph target weights of the mesh.
+    pub fn weights(mut self, weights: &[f32]) -> Self {
+        self.json.weights = Some(weights.to_vec());
+        self
+    }
+}
+
+/// Assembles a primitive from typed vertex data, to be appended to a mesh with
+/// `MeshBuilder::primitive`.
+#[derive(Clone, Debug, Default)]
+pub struct PrimitiveBuilder {
+    /// The JSON of the primitive, without its attributes and indices.
+    json: json::mesh::Primitive,
+
+    /// The vertex attributes of the primitive.
+    attributes: Vec<(Semantic, Data)>,
+
+    /// The vertex indices of the primitive.
+    indices: Option<Data>,
+}
+
+impl PrimitiveBuilder {
+    /// Creates a builder of a primitive without attributes.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Sets the `POSITION` attribute.
+    ///
+    /// # Panics
+    ///
+    /// See `attribute`.
+    pub fn positions(self, data: &[[f32; 3]]) -> Self {
+        self.attribute(Semantic::Positions, data)
+    }
+
+    /// Sets the `NORMAL` attribute.
+    ///
+    /// # Panics
+    ///
+    /// See `attribute`.
+    pub fn normals(self, data: &[[f32; 3]]) -> Self {
+        self.attribute(Semantic::Normals, data)
+    }
+
+    /// Sets the `TANGENT` attribute.
+    ///
+    /// # Panics
+    ///
+    /// See `attribute`.
+    pub fn tangents(self, data: &[[f32; 4]]) -> Self {
+        self.attribute(Semantic::Tangents, data)
+    }
+
+    /// Sets the `TEXCOORD_n` attribute of the given set.
+    ///
+    /// # Panics
+    ///
+    /// See `attribute`.
+    pub fn tex_coords<E: Element>(self, set: u32, data: &[E]) -> Self {
+        self.attribute(Semantic::TexCoords(set), data)
+    }
+
+    /// Sets the `COLOR_n` attribute of the given set.
+    ///
+    /// # Panics
+    ///
+    /// See `attribute`.
+    pub fn colors<E: Element>(self, set: u32, data: &[E]) -> Self {
+        self.attribute(Semantic::Colors(set), data)
+    }
+
+    /// Sets a vertex attribute, replacing any earlier attribute of the same
+    /// semantic.
+    ///
+    /// Integer components of `COLOR_n`, `TEXCOORD_n`, and `WEIGHTS_n` are
+    /// marked as normalized, as the specification requires.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of elements differs from that of the other
+    /// attributes of the primitive.
+    pub fn attribute<E: Element>(mut self, semantic: Semantic, data: &[E]) -> Self {
+        self.attributes.retain(|&(ref existing, _)| *existing != semantic);
+        if let Some(&(_, ref other)) = self.attributes.first() {
+            assert_eq!(other.count, data.len(), "attribute vertex count mismatch");
+        }
+        let mut data = Data::new(data);
+        data.normalized = match semantic {
+            Semantic::Colors(_) | Semantic::TexCoords(_) | Semantic::Weights(_) => {
+                data.component_type != ComponentType::F32
+            },
+            _ => false,
+        };
+        self.attributes.push((semantic, data));
+        self
+    }
+
+    /// Sets the vertex indices, which glTF restricts to unsigned integers.
+    pub fn indices<T: IndexComponent>(mut self, data: &[T]) -> Self {
+        self.indices = Some(Data::new(data));
+        self
+    }
+
+    /// Sets the material of the primitive.
+    pub fn material(mut self, material: json::Index<json::Material>) -> Self {
+        self.json.material = Some(material);
+        self
+    }
+
+    /// Sets the topology of the primitive, triangles by default.
+    pub fn mode(mut self, mode: Mode) -> Self {
+        self.json.mode = Checked::Valid(mode);
+        self
+    }
+}
+
+/// Assembles a node to be appended with `RootBuilder::node`.
+#[derive(Clone, Debug, Default)]
+pub struct NodeBuilder {
+    /// The JSON of the node.
+    json: json::Node,
+}
+
+impl NodeBuilder {
+    /// Creates a builder of a node with the identity transform.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Sets the user-defined name of the node.
+    #[cfg(feature = "names")]
+    pub fn name<S: Into<String>>(mut self, name: S) -> Self {
+        self.json.name = Some(name.into());
+        self
+    }
+
+    /// Sets the mesh of the node.
+    pub fn mesh(mut self, mesh: json::Index<json::Mesh>) -> Self {
+        self.json.mesh = Some(mesh);
+        self
+    }
+
+    /// Sets the camera of the node.
+    pub fn camera(mut self, camera: json::Index<json::Camera>) -> Self {
+        self.json.camera = Some(camera);
+        self
+    }
+
+    /// Appends child nodes, which must be appended to the builder beforehand.
+    pub fn children(mut self, children: &[json::Index<json::Node>]) -> Self {
+        self.json.children.get_or_insert_with(Vec::new).extend_from_slice(children);
+        self
+    }
+
+    /// Sets the translation of the node.
+    pub fn translation(mut self, translation: [f32; 3]) -> Self {
+        self.json.translation = translation;
+        self
+    }
+
+    /// Sets the rotation of the node as a unit quaternion in the order
+    /// `(x, y, z, w)`.
+    pub fn rotation(mut self, rotation: [f32; 4]) -> Self {
+        self.json.rotation = json::scene::UnitQuaternion(rotation);
+        self
+    }
+
+    /// Sets the scale of the node.
+    pub fn scale(mut self, scale: [f32; 3]) -> Self {
+        self.json.scale = scale;
+        self
+    }
+}
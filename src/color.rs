@@ -0,0 +1,101 @@
+//! All color factors in glTF, e.g. the base color and emissive factors of
+//! materials, are linear. Textures holding colors, i.e. the base color and
+//! emissive textures, are sRGB encoded, and all other textures are linear.
+//!
+//! Multiply factors with texels only after decoding the texels to linear
+//! space, and do not convert factors again before passing them to a shader.
+
+/// Converts an sRGB encoded color component to linear space.
+pub fn srgb_to_linear(c: f32) -> f32 {
+    if c <= 0.04045 {
+        c / 12.92
+    } else {
+        ((c + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+/// Converts a linear color component to sRGB encoding.
+pub fn linear_to_srgb(c: f32) -> f32 {
+    if c <= 0.003_130_8 {
+        c * 12.92
+    } else {
+        1.055 * c.powf(1.0 / 2.4) - 0.055
+    }
+}
+
+/// An RGB color.
+///
+/// Whether the components are linear or sRGB encoded depends on where the
+/// color came from; see the module documentation.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Rgb(pub [f32; 3]);
+
+/// An RGBA color with straight alpha.
+///
+/// Whether the color components are linear or sRGB encoded depends on where
+/// the color came from; see the module documentation. Alpha is always linear.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Rgba(pub [f32; 4]);
+
+impl Rgb {
+    /// Converts sRGB encoded components to linear space.
+    pub fn to_linear(self) -> Rgb {
+        let [r, g, b] = self.0;
+        Rgb([srgb_to_linear(r), srgb_to_linear(g), srgb_to_linear(b)])
+    }
+
+    /// Converts linear components to sRGB encoding.
+    pub fn to_srgb(self) -> Rgb {
+        let [r, g, b] = self.0;
+        Rgb([linear_to_srgb(r), linear_to_srgb(g), linear_to_srgb(b)])
+    }
+}
+
+impl Rgba {
+    /// Converts sRGB encoded color components to linear space, leaving alpha
+    /// unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gltf::color::Rgba;
+    /// let color = Rgba([1.0, 0.5, 0.0, 0.5]).to_linear();
+    /// assert!((color.0[1] - 0.214).abs() < 1.0e-3);
+    /// assert_eq!(0.5, color.0[3]);
+    /// ```
+    pub fn to_linear(self) -> Rgba {
+        let [r, g, b, a] = self.0;
+        Rgba([srgb_to_linear(r), srgb_to_linear(g), srgb_to_linear(b), a])
+    }
+
+    /// Converts linear color components to sRGB encoding, leaving alpha
+    /// unchanged.
+    pub fn to_srgb(self) -> Rgba {
+        let [r, g, b, a] = self.0;
+        Rgba([linear_to_srgb(r), linear_to_srgb(g), linear_to_srgb(b), a])
+    }
+}
+
+impl From<[f32; 3]> for Rgb {
+    fn from(color: [f32; 3]) -> Rgb {
+        Rgb(color)
+    }
+}
+
+impl From<Rgb> for [f32; 3] {
+    fn from(color: Rgb) -> [f32; 3] {
+        color.0
+    }
+}
+
+impl From<[f32; 4]> for Rgba {
+    fn from(color: [f32; 4]) -> Rgba {
+        Rgba(color)
+    }
+}
+
+impl From<Rgba> for [f32; 4] {
+    fn from(color: Rgba) -> [f32; 4] {
+        color.0
+    }
+}
@@ -0,0 +1,473 @@
+use json;
+use json::edit::{DocumentEdit, Edit};
+use json::validation::Validate;
+
+use material::AlphaMode;
+use mesh::{Mode, Semantic};
+use {Error, Gltf};
+
+/// Records modifications to a `Gltf`, obtained with `Gltf::edit`.
+///
+/// Edits are recorded by a `json::edit::DocumentEdit`, so they can be undone,
+/// redone, and exported as a JSON patch, and the original asset is left
+/// untouched until `commit` produces the edited one.
+///
+/// Every modification made through the editors is validated against the
+/// specification and rejected with `Error::Validation` if it would leave an
+/// index out of range or an object invalid, e.g. a `POSITION` attribute whose
+/// accessor does not hold `[f32; 3]` vectors, so the read-only wrappers stay
+/// safe to use on the committed asset.
+pub struct GltfEdit<'a> {
+    /// The asset being edited.
+    gltf: &'a Gltf,
+
+    /// The recorded edits.
+    edit: DocumentEdit<'a>,
+}
+
+/// Mutable access to a node, obtained with `GltfEdit::node_mut`.
+#[derive(Debug)]
+pub struct NodeMut<'e, 'a: 'e> {
+    /// The recorded edits.
+    edit: &'e mut DocumentEdit<'a>,
+
+    /// The corresponding JSON index.
+    index: usize,
+}
+
+/// Mutable access to a mesh, obtained with `GltfEdit::mesh_mut`.
+#[derive(Debug)]
+pub struct MeshMut<'e, 'a: 'e> {
+    /// The recorded edits.
+    edit: &'e mut DocumentEdit<'a>,
+
+    /// The corresponding JSON index.
+    index: usize,
+}
+
+/// Mutable access to a primitive, obtained with `MeshMut::primitive_mut`.
+#[derive(Debug)]
+pub struct PrimitiveMut<'e, 'a: 'e> {
+    /// The recorded edits.
+    edit: &'e mut DocumentEdit<'a>,
+
+    /// The index of the parent mesh.
+    mesh: usize,
+
+    /// The corresponding JSON index within the parent mesh.
+    index: usize,
+}
+
+/// Mutable access to a material, obtained with `GltfEdit::material_mut`.
+#[derive(Debug)]
+pub struct MaterialMut<'e, 'a: 'e> {
+    /// The recorded edits.
+    edit: &'e mut DocumentEdit<'a>,
+
+    /// The corresponding JSON index.
+    index: usize,
+}
+
+/// Validates `value` minimally and completely as the object at `path`.
+///
+/// Edits never add or remove the objects referenced by index, so `root` may
+/// be the unedited document.
+fn validate<T: Validate>(root: &json::Root, value: &T, path: &json::Path) -> Result<(), Error> {
+    let mut errs = vec![];
+    value.validate_minimally(root, || path.clone(), &mut |path, err| errs.push((path(), err)));
+    if errs.is_empty() {
+        value.validate_completely(root, || path.clone(), &mut |path, err| errs.push((path(), err)));
+    }
+    if errs.is_empty() {
+        Ok(())
+    } else {
+        Err(Error::Validation(errs))
+    }
+}
+
+/// Validates a primitive as the object at `path`.
+fn validate_primitive(
+    root: &json::Root,
+    primitive: &json::mesh::Primitive,
+    path: &json::Path,
+) -> Result<(), Error> {
+    // The bounds of the `POSITION` accessor are read while validating the
+    // primitive, so its index must be checked first.
+    validate(root, &primitive.attributes, &path.field("attributes"))?;
+    validate(root, primitive, path)
+}
+
+impl<'a> GltfEdit<'a> {
+    /// Constructs a `GltfEdit`.
+    pub(crate) fn new(gltf: &'a Gltf) -> Self {
+        Self {
+            gltf,
+            edit: DocumentEdit::new(gltf.as_json()),
+        }
+    }
+
+    /// Returns the underlying `DocumentEdit`, e.g. to export the recorded
+    /// edits with `DocumentEdit::to_json_patch`.
+    pub fn as_document_edit(&self) -> &DocumentEdit<'a> {
+        &self.edit
+    }
+
+    /// Returns mutable access to a node, or `None` if the index is out of
+    /// range.
+    pub fn node_mut<'e>(&'e mut self, index: usize) -> Option<NodeMut<'e, 'a>> {
+        if self.edit.node(index).is_some() {
+            Some(NodeMut::new(&mut self.edit, index))
+        } else {
+            None
+        }
+    }
+
+    /// Returns mutable access to a mesh, or `None` if the index is out of
+    /// range.
+    pub fn mesh_mut<'e>(&'e mut self, index: usize) -> Option<MeshMut<'e, 'a>> {
+        if self.edit.mesh(index).is_some() {
+            Some(MeshMut::new(&mut self.edit, index))
+        } else {
+            None
+        }
+    }
+
+    /// Returns mutable access to a material, or `None` if the index is out of
+    /// range.
+    pub fn material_mut<'e>(&'e mut self, index: usize) -> Option<MaterialMut<'e, 'a>> {
+        if self.edit.material(index).is_some() {
+            Some(MaterialMut::new(&mut self.edit, index))
+        } else {
+            None
+        }
+    }
+
+    /// Reverts the most recent edit, returning it.
+    pub fn undo(&mut self) -> Option<Edit> {
+        self.edit.undo()
+    }
+
+    /// Reapplies the most recently undone edit, returning it.
+    pub fn redo(&mut self) -> Option<&Edit> {
+        self.edit.redo()
+    }
+
+    /// Materializes the edited asset, keeping the default material of the
+    /// original.
+    pub fn commit(&self) -> Gltf {
+        self.gltf.with_json(self.edit.commit())
+    }
+}
+
+impl<'e, 'a> NodeMut<'e, 'a> {
+    /// Constructs a `NodeMut`.
+    fn new(edit: &'e mut DocumentEdit<'a>, index: usize) -> Self {
+        Self {
+            edit,
+            index,
+        }
+    }
+
+    /// Returns the internal JSON index.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Returns the internal JSON item.
+    #[doc(hidden)]
+    pub fn as_json(&self) -> &json::scene::Node {
+        self.edit.node(self.index).unwrap()
+    }
+
+    /// Sets the user-defined name of the node.
+    #[cfg(feature = "names")]
+    pub fn set_name(&mut self, name: Option<String>) {
+        let mut node = self.as_json().clone();
+        node.name = name;
+        self.edit.set_node(self.index, node);
+    }
+
+    /// Sets the index of the mesh instantiated by the node.
+    pub fn set_mesh(&mut self, mesh: Option<usize>) -> Result<(), Error> {
+        self.update(|node| node.mesh = mesh.map(|index| json::Index::new(index as u32)))
+    }
+
+    /// Sets the index of the camera attached to the node.
+    pub fn set_camera(&mut self, camera: Option<usize>) -> Result<(), Error> {
+        self.update(|node| node.camera = camera.map(|index| json::Index::new(index as u32)))
+    }
+
+    /// Sets the index of the skin of the node.
+    pub fn set_skin(&mut self, skin: Option<usize>) -> Result<(), Error> {
+        self.update(|node| node.skin = skin.map(|index| json::Index::new(index as u32)))
+    }
+
+    /// Modifies a copy of the node, recording it only if it is valid.
+    fn update<F>(&mut self, f: F) -> Result<(), Error>
+        where F: FnOnce(&mut json::scene::Node)
+    {
+        let mut node = self.as_json().clone();
+        f(&mut node);
+        let path = json::Path::new().field("nodes").index(self.index);
+        validate(self.edit.base(), &node, &path)?;
+        self.edit.set_node(self.index, node);
+        Ok(())
+    }
+}
+
+impl<'e, 'a> MeshMut<'e, 'a> {
+    /// Constructs a `MeshMut`.
+    fn new(edit: &'e mut DocumentEdit<'a>, index: usize) -> Self {
+        Self {
+            edit,
+            index,
+        }
+    }
+
+    /// Returns the internal JSON index.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Returns the internal JSON item.
+    #[doc(hidden)]
+    pub fn as_json(&self) -> &json::mesh::Mesh {
+        self.edit.mesh(self.index).unwrap()
+    }
+
+    /// Sets the user-defined name of the mesh.
+    #[cfg(feature = "names")]
+    pub fn set_name(&mut self, name: Option<String>) {
+        let mut mesh = self.as_json().clone();
+        mesh.name = name;
+        self.edit.set_mesh(self.index, mesh);
+    }
+
+    /// Returns mutable access to a primitive of the mesh, or `None` if the
+    /// index is out of range.
+    pub fn primitive_mut<'p>(&'p mut self, index: usize) -> Option<PrimitiveMut<'p, 'a>> {
+        if index < self.as_json().primitives.len() {
+            Some(PrimitiveMut::new(self.edit, self.index, index))
+        } else {
+            None
+        }
+    }
+
+    /// Appends a primitive to the mesh, returning its index.
+    pub fn push_primitive(&mut self, primitive: json::mesh::Primitive) -> Result<usize, Error> {
+        let mut mesh = self.as_json().clone();
+        let index = mesh.primitives.len();
+        let path = json::Path::new()
+            .field("meshes")
+            .index(self.index)
+            .field("primitives")
+            .index(index);
+        validate_primitive(self.edit.base(), &primitive, &path)?;
+        mesh.primitives.push(primitive);
+        self.edit.set_mesh(self.index, mesh);
+        Ok(index)
+    }
+
+    /// Removes a primitive from the mesh, returning it.
+    ///
+    /// The primitives after it move down by one. Fails with
+    /// `Error::Validation` if the index is out of range, or if it is the last
+    /// primitive, since a mesh must have at least one.
+    pub fn remove_primitive(&mut self, index: usize) -> Result<json::mesh::Primitive, Error> {
+        use json::validation::Error::{IndexOutOfBounds, Invalid};
+        let path = json::Path::new().field("meshes").index(self.index).field("primitives");
+        let mut mesh = self.as_json().clone();
+        if index >= mesh.primitives.len() {
+            Err(Error::Validation(vec![(path.index(index), IndexOutOfBounds)]))
+        } else if mesh.primitives.len() == 1 {
+            Err(Error::Validation(vec![(path, Invalid)]))
+        } else {
+            let primitive = mesh.primitives.remove(index);
+            self.edit.set_mesh(self.index, mesh);
+            Ok(primitive)
+        }
+    }
+}
+
+impl<'e, 'a> MaterialMut<'e, 'a> {
+    /// Constructs a `MaterialMut`.
+    fn new(edit: &'e mut DocumentEdit<'a>, index: usize) -> Self {
+        Self {
+            edit,
+            index,
+        }
+    }
+
+    /// Returns the internal JSON index.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Returns the internal JSON item.
+    #[doc(hidden)]
+    pub fn as_json(&self) -> &json::material::Material {
+        self.edit.material(self.index).unwrap()
+    }
+
+    /// Sets the user-defined name of the material.
+    #[cfg(feature = "names")]
+    pub fn set_name(&mut self, name: Option<String>) {
+        self.modify(|material| material.name = name)
+    }
+
+    /// Sets the alpha rendering mode of the material.
+    pub fn set_alpha_mode(&mut self, mode: AlphaMode) {
+        self.modify(|material| material.alpha_mode = json::validation::Checked::Valid(mode))
+    }
+
+    /// Sets the alpha cutoff value of the material, which must not be
+    /// negative.
+    pub fn set_alpha_cutoff(&mut self, cutoff: f32) -> Result<(), Error> {
+        self.update(|material| material.alpha_cutoff = json::material::AlphaCutoff(cutoff))
+    }
+
+    /// Sets whether the material is double-sided.
+    pub fn set_double_sided(&mut self, double_sided: bool) {
+        self.modify(|material| material.double_sided = double_sided)
+    }
+
+    /// Sets the base color factor, with components in the range [0.0, 1.0].
+    pub fn set_base_color_factor(&mut self, factor: [f32; 4]) -> Result<(), Error> {
+        self.update(|material| {
+            material.pbr_metallic_roughness.base_color_factor =
+                json::material::PbrBaseColorFactor(factor);
+        })
+    }
+
+    /// Sets the base color texture, or `None` to remove it.
+    pub fn set_base_color_texture(
+        &mut self,
+        texture: Option<json::texture::Info>,
+    ) -> Result<(), Error> {
+        self.update(|material| material.pbr_metallic_roughness.base_color_texture = texture)
+    }
+
+    /// Sets the metalness factor, in the range [0.0, 1.0].
+    pub fn set_metallic_factor(&mut self, factor: f32) -> Result<(), Error> {
+        self.update(|material| {
+            material.pbr_metallic_roughness.metallic_factor =
+                json::material::StrengthFactor(factor);
+        })
+    }
+
+    /// Sets the roughness factor, in the range [0.0, 1.0].
+    pub fn set_roughness_factor(&mut self, factor: f32) -> Result<(), Error> {
+        self.update(|material| {
+            material.pbr_metallic_roughness.roughness_factor =
+                json::material::StrengthFactor(factor);
+        })
+    }
+
+    /// Sets the emissive factor, with components in the range [0.0, 1.0].
+    pub fn set_emissive_factor(&mut self, factor: [f32; 3]) -> Result<(), Error> {
+        self.update(|material| material.emissive_factor = json::material::EmissiveFactor(factor))
+    }
+
+    /// Records a modified copy of the material.
+    fn modify<F>(&mut self, f: F)
+        where F: FnOnce(&mut json::material::Material)
+    {
+        let mut material = self.as_json().clone();
+        f(&mut material);
+        self.edit.set_material(self.index, material);
+    }
+
+    /// Modifies a copy of the material, recording it only if it is valid.
+    fn update<F>(&mut self, f: F) -> Result<(), Error>
+        where F: FnOnce(&mut json::material::Material)
+    {
+        let mut material = self.as_json().clone();
+        f(&mut material);
+        let path = json::Path::new().field("materials").index(self.index);
+        validate(self.edit.base(), &material, &path)?;
+        self.edit.set_material(self.index, material);
+        Ok(())
+    }
+}
+
+impl<'e, 'a> PrimitiveMut<'e, 'a> {
+    /// Constructs a `PrimitiveMut`.
+    fn new(edit: &'e mut DocumentEdit<'a>, mesh: usize, index: usize) -> Self {
+        Self {
+            edit,
+            mesh,
+            index,
+        }
+    }
+
+    /// Returns the internal JSON index.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Returns the internal JSON item.
+    #[doc(hidden)]
+    pub fn as_json(&self) -> &json::mesh::Primitive {
+        &self.edit.mesh(self.mesh).unwrap().primitives[self.index]
+    }
+
+    /// Sets the index of the material of the primitive, or `None` to use the
+    /// default material.
+    pub fn set_material(&mut self, material: Option<usize>) -> Result<(), Error> {
+        self.update(|primitive| {
+            primitive.material = material.map(|index| json::Index::new(index as u32));
+        })
+    }
+
+    /// Sets the index of the accessor containing the vertex indices, or `None`
+    /// for non-indexed geometry.
+    pub fn set_indices(&mut self, indices: Option<usize>) -> Result<(), Error> {
+        self.update(|primitive| {
+            primitive.indices = indices.map(|index| json::Index::new(index as u32));
+        })
+    }
+
+    /// Sets the index of the accessor of a vertex attribute, replacing any
+    /// existing accessor of the same semantic.
+    ///
+    /// The accessor must hold the type of data the specification allows for
+    /// the semantic, with as many elements as the other attributes.
+    pub fn set_attribute(&mut self, semantic: Semantic, accessor: usize) -> Result<(), Error> {
+        self.update(|primitive| {
+            let semantic = json::validation::Checked::Valid(semantic);
+            primitive.attributes.insert(semantic, json::Index::new(accessor as u32));
+        })
+    }
+
+    /// Removes a vertex attribute, returning the index of its accessor.
+    pub fn remove_attribute(&mut self, semantic: &Semantic) -> Option<usize> {
+        let semantic = json::validation::Checked::Valid(semantic.clone());
+        let mut mesh = self.edit.mesh(self.mesh).unwrap().clone();
+        let removed = mesh.primitives[self.index].attributes.remove(&semantic)?;
+        self.edit.set_mesh(self.mesh, mesh);
+        Some(removed.value())
+    }
+
+    /// Sets the topology of the primitive.
+    pub fn set_mode(&mut self, mode: Mode) {
+        let mut mesh = self.edit.mesh(self.mesh).unwrap().clone();
+        mesh.primitives[self.index].mode = json::validation::Checked::Valid(mode);
+        self.edit.set_mesh(self.mesh, mesh);
+    }
+
+    /// Modifies a copy of the primitive, recording it only if it is valid.
+    fn update<F>(&mut self, f: F) -> Result<(), Error>
+        where F: FnOnce(&mut json::mesh::Primitive)
+    {
+        let mut mesh = self.edit.mesh(self.mesh).unwrap().clone();
+        f(&mut mesh.primitives[self.index]);
+        let path = json::Path::new()
+            .field("meshes")
+            .index(self.mesh)
+            .field("primitives")
+            .index(self.index);
+        validate_primitive(self.edit.base(), &mesh.primitives[self.index], &path)?;
+        self.edit.set_mesh(self.mesh, mesh);
+        Ok(())
+    }
+}
@@ -1,4 +1,4 @@
-use byteorder::{LE, ReadBytesExt};
+use byteorder::{LE, ReadBytesExt, WriteBytesExt};
 use std::{fmt, io};
 use std::borrow::Cow;
 
@@ -31,6 +31,9 @@ pub enum Error {
     ChunkType(ChunkType),
     /// Unknown chunk type.
     UnknownChunkType([u8; 4]),
+    /// Length of the padded contents exceeds what the 32-bit length fields
+    /// can describe.
+    TooLarge(usize),
 }
 
 /// The contents of a .glb file.
@@ -93,6 +96,12 @@ impl Header {
     }
 
     fn size_of() -> usize { 12 }
+
+    fn to_writer<W: io::Write>(&self, mut writer: W) -> io::Result<()> {
+        writer.write_all(&self.magic)?;
+        writer.write_u32::<LE>(self.version)?;
+        writer.write_u32::<LE>(self.length)
+    }
 }
 
 impl ChunkHeader {
@@ -108,9 +117,89 @@ impl ChunkHeader {
         }?;
         Ok(Self { length, ty })
     }
+
+    fn size_of() -> usize { 8 }
+
+    fn to_writer<W: io::Write>(&self, mut writer: W) -> io::Result<()> {
+        writer.write_u32::<LE>(self.length)?;
+        writer.write_all(match self.ty {
+            ChunkType::Json => b"JSON",
+            ChunkType::Bin => b"BIN\0",
+        })
+    }
+}
+
+/// Returns the length of chunk data padded to a multiple of four bytes.
+fn padded_length(length: usize) -> usize {
+    (length + 3) & !3
+}
+
+/// Converts a length to the `u32` of a GLB length field.
+fn length_field(length: usize) -> Result<u32, Error> {
+    if length > u32::MAX as usize {
+        Err(Error::TooLarge(length))
+    } else {
+        Ok(length as u32)
+    }
+}
+
+/// Writes a chunk, padding its data to a multiple of four bytes.
+fn write_chunk<W: io::Write>(
+    mut writer: W,
+    ty: ChunkType,
+    data: &[u8],
+    padding: u8,
+) -> Result<(), Error> {
+    let length = padded_length(data.len());
+    ChunkHeader { length: length_field(length)?, ty }.to_writer(&mut writer)
+        .and_then(|_| writer.write_all(data))
+        .and_then(|_| writer.write_all(&[padding; 3][..length - data.len()]))
+        .map_err(Error::Io)
 }
 
 impl<'a> Glb<'a> {
+    /// Assembles a version 2 GLB from JSON text and optional binary data.
+    ///
+    /// The header length accounts for the padding added by `Glb::to_writer`.
+    /// The JSON must describe the binary data as its first buffer, without a
+    /// `uri`. Fails with `glb::Error::TooLarge` when the container would not
+    /// fit the 4 GiB limit of the format.
+    pub fn new(json: Cow<'a, [u8]>, bin: Option<Cow<'a, [u8]>>) -> Result<Self, ::Error> {
+        let mut length = Header::size_of() + ChunkHeader::size_of() + padded_length(json.len());
+        if let Some(ref bin) = bin {
+            length += ChunkHeader::size_of() + padded_length(bin.len());
+        }
+        let length = length_field(length).map_err(::Error::Glb)?;
+        let header = Header { magic: *b"glTF", version: 2, length };
+        Ok(Glb { header, json, bin })
+    }
+
+    /// Writes the GLB container.
+    ///
+    /// The JSON chunk is padded with spaces and the BIN chunk with zeros to
+    /// four byte alignment, as the specification requires. The header is
+    /// written as is, so construct the `Glb` with `Glb::new` when the chunks
+    /// have been modified.
+    pub fn to_writer<W: io::Write>(&self, mut writer: W) -> Result<(), ::Error> {
+        self.header.to_writer(&mut writer)
+            .map_err(Error::Io)
+            .and_then(|_| write_chunk(&mut writer, ChunkType::Json, &self.json, b' '))
+            .and_then(|_| match self.bin {
+                Some(ref bin) => write_chunk(&mut writer, ChunkType::Bin, bin, 0),
+                None => Ok(()),
+            })
+            .map_err(::Error::Glb)
+    }
+
+    /// Returns the GLB container as bytes.
+    ///
+    /// See `Glb::to_writer`.
+    pub fn to_vec(&self) -> Result<Vec<u8>, ::Error> {
+        let mut data = Vec::with_capacity(self.header.length as usize);
+        self.to_writer(&mut data)?;
+        Ok(data)
+    }
+
     /// Splits loaded GLB into its three chunks.
     ///
     /// * Mandatory GLB header.
@@ -150,7 +239,7 @@ impl<'a> Glb<'a> {
                 if let Err(e) = reader.read_exact(&mut buf).map_err(Error::Io) {
                     Err(::Error::Glb(e))
                 } else {
-                    Self::from_v2(&buf)
+                    Glb::from_v2(&buf)
                         .map(|(json, bin)| Glb {
                             header,
                             json: json.to_vec().into(),
@@ -233,6 +322,7 @@ impl ::std::error::Error for Error {
                  ChunkType::Bin => "was not expecting BIN\\0 chunk",
              },
              Error::UnknownChunkType(_) => "unknown chunk type",
+             Error::TooLarge(_) => "contents exceed the 4 GiB limit of GLB",
         }
     }
 }
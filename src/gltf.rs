@@ -3,12 +3,17 @@ use std::{fmt, io, iter, slice};
 
 use accessor::Accessor;
 use animation::Animation;
+use asset::Asset;
+#[cfg(feature = "audio")]
+use audio;
 use buffer::{Buffer, View};
 use camera::Camera;
+use edit::GltfEdit;
 use glb::Glb;
 use image::Image;
 use material::Material;
-use mesh::Mesh;
+use mesh::{self, Mesh, Primitive};
+use metadata::PropertyTables;
 use scene::{Node, Scene};
 use skin::Skin;
 use texture::{Sampler, Texture};
@@ -19,6 +24,12 @@ use Error;
 pub struct Gltf {
     /// The JSON root object.
     root: json::root::Root,
+
+    /// The material used by primitives without one.
+    default_material: json::material::Material,
+
+    /// The sampler used by textures without one.
+    default_sampler: json::texture::Sampler,
 }
 
 /// An `Iterator` that visits extension strings.
@@ -108,6 +119,24 @@ pub struct Meshes<'a> {
     gltf: &'a Gltf,
 }
 
+/// An `Iterator` that visits every primitive of every mesh in a glTF asset.
+#[derive(Clone, Debug)]
+pub struct Primitives<'a> {
+    /// Internal mesh iterator.
+    meshes: Meshes<'a>,
+
+    /// The primitives of the current mesh.
+    primitives: Option<mesh::Primitives<'a>>,
+}
+
+/// An `Iterator` that visits every node in a glTF asset that instantiates a
+/// mesh.
+#[derive(Clone, Debug)]
+pub struct NodesWithMeshes<'a> {
+    /// Internal node iterator.
+    nodes: Nodes<'a>,
+}
+
 /// An `Iterator` that visits every node in a glTF asset.
 #[derive(Clone, Debug)]
 pub struct Nodes<'a> {
@@ -158,6 +187,74 @@ pub struct Textures<'a> {
     gltf: &'a Gltf,
 }
 
+/// The 64-bit FNV-1a hash of written text.
+struct Fnv1a(u64);
+
+impl fmt::Write for Fnv1a {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for &byte in s.as_bytes() {
+            self.0 ^= u64::from(byte);
+            self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+        }
+        Ok(())
+    }
+}
+
+/// Writes a JSON value minified and with object keys in sorted order, so that
+/// equal values are always written the same way.
+fn write_canonical(value: &json::Value, hasher: &mut Fnv1a) {
+    use std::fmt::Write;
+    let _ = match *value {
+        json::Value::Array(ref values) => {
+            let _ = hasher.write_char('[');
+            for (i, value) in values.iter().enumerate() {
+                if i > 0 {
+                    let _ = hasher.write_char(',');
+                }
+                write_canonical(value, hasher);
+            }
+            hasher.write_char(']')
+        },
+        json::Value::Object(ref map) => {
+            let mut keys: Vec<&String> = map.keys().collect();
+            keys.sort();
+            let _ = hasher.write_char('{');
+            for (i, key) in keys.into_iter().enumerate() {
+                if i > 0 {
+                    let _ = hasher.write_char(',');
+                }
+                let _ = write!(hasher, "{}:", json::Value::from(key.as_str()));
+                write_canonical(&map[key], hasher);
+            }
+            hasher.write_char('}')
+        },
+        ref scalar => write!(hasher, "{}", scalar),
+    };
+}
+
+/// Converts every number of a JSON value to `f64`, so that `1` and `1.0`
+/// compare equal.
+fn normalize_numbers(value: &mut json::Value) {
+    match *value {
+        json::Value::Number(_) => {
+            if let Some(x) = value.as_f64() {
+                *value = json::Value::from(x);
+            }
+        },
+        json::Value::Array(ref mut values) => {
+            for value in values {
+                normalize_numbers(value);
+            }
+        },
+        json::Value::Object(ref mut object) => {
+            for (_, value) in object.iter_mut() {
+                normalize_numbers(value);
+            }
+        },
+        _ => {},
+    }
+}
+
 impl Unvalidated {
     /// Returns the unvalidated JSON.
     pub fn as_json(&self) -> &json::Root {
@@ -222,12 +319,269 @@ impl Unvalidated {
 
 impl Gltf {
     /// Constructs the `Gltf` wrapper from deserialized JSON.
-    fn from_json(json: json::Root) -> Self {
+    pub fn from_json(json: json::Root) -> Unvalidated {
+        Unvalidated(Gltf {
+            root: json,
+            default_material: Default::default(),
+            default_sampler: Default::default(),
+        })
+    }
+
+    /// Constructs a `Gltf` from edited JSON, keeping the default material of
+    /// this one.
+    pub(crate) fn with_json(&self, json: json::Root) -> Gltf {
         Gltf {
             root: json,
+            default_material: self.default_material.clone(),
+            default_sampler: self.default_sampler.clone(),
+        }
+    }
+
+    /// Returns the material used by primitives without one.
+    pub(crate) fn default_material(&self) -> &json::material::Material {
+        &self.default_material
+    }
+
+    /// Replaces the material used by primitives without one, e.g. to apply a
+    /// house style to untextured assets.
+    ///
+    /// Textures referenced by the material must exist in the asset.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn run() -> Result<(), Box<std::error::Error>> {
+    /// use gltf::json;
+    ///
+    /// let mut gltf = gltf::Gltf::from_str(r#"{
+    ///     "asset": { "version": "2.0" },
+    ///     "meshes": [{ "primitives": [{ "attributes": {} }] }]
+    /// }"#)?.validate_minimally()?;
+    /// let mut material = json::Material::default();
+    /// material.pbr_metallic_roughness.base_color_factor =
+    ///     json::material::PbrBaseColorFactor([0.5, 0.5, 0.5, 1.0]);
+    /// gltf.set_default_material(material)?;
+    ///
+    /// let (_, primitive) = gltf.primitives().next().unwrap();
+    /// let color = primitive.material().pbr_metallic_roughness().base_color_factor();
+    /// assert_eq!([0.5, 0.5, 0.5, 1.0], color);
+    /// # Ok(())
+    /// # }
+    /// # fn main() { run().unwrap() }
+    /// ```
+    pub fn set_default_material(&mut self, material: json::material::Material) -> Result<(), Error> {
+        use json::validation::Validate;
+        let mut errs = vec![];
+        material.validate_minimally(
+            &self.root,
+            || json::Path::new().field("defaultMaterial"),
+            &mut |path, err| errs.push((path(), err)),
+        );
+        if errs.is_empty() {
+            self.default_material = material;
+            Ok(())
+        } else {
+            Err(Error::Validation(errs))
         }
     }
 
+    /// Normalizes object names to Unicode Normalization Form C, e.g. before
+    /// writing the asset with `Gltf::to_writer`, so that tools reading it can
+    /// match names regardless of the platform it was authored on. Returns the
+    /// number of names changed.
+    ///
+    /// URIs are left as they are. See `json::naming::normalize_nfc` and
+    /// `json::naming::normalize_uris_nfc`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fn run() -> Result<(), gltf::Error> {
+    /// let json = br#"{"asset":{"version":"2.0"},"nodes":[{"name":"Cafe\u0301"}]}"#;
+    /// let mut gltf = gltf::Gltf::from_slice(json)?.validate_completely()?;
+    /// assert_eq!(1, gltf.normalize_nfc());
+    /// assert!(gltf.to_string()?.contains("Caf\u{e9}"));
+    /// # Ok(())
+    /// # }
+    /// # fn main() {
+    /// #     run().unwrap();
+    /// # }
+    /// ```
+    #[cfg(feature = "nfc")]
+    pub fn normalize_nfc(&mut self) -> usize {
+        json::naming::normalize_nfc(&mut self.root)
+    }
+
+    /// Returns the sampler used by textures without one.
+    pub(crate) fn default_sampler(&self) -> &json::texture::Sampler {
+        &self.default_sampler
+    }
+
+    /// Consumes the `Gltf` wrapper, returning the internal JSON.
+    pub fn into_json(self) -> json::Root {
+        self.root
+    }
+
+    /// Returns a copy of the internal JSON, e.g. to modify it and construct a
+    /// new `Gltf` with `Gltf::from_json`.
+    pub fn to_json(&self) -> json::Root {
+        self.root.clone()
+    }
+
+    /// Writes the glTF asset as compact JSON text, e.g. to a `.gltf` file.
+    ///
+    /// Only the JSON is written. Buffers and images keep their URIs, so
+    /// external files must be written alongside. The default material set by
+    /// `Gltf::set_default_material` is not part of the asset and is not
+    /// written.
+    ///
+    /// Extensions are written as read, including those not modelled by this
+    /// crate or disabled by its features. Names and `extras` are only kept
+    /// with the `names` and `extras` features; without them, writing an asset
+    /// that had any fails with `Error::Serialize` instead of silently dropping
+    /// them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fn run() -> Result<(), gltf::Error> {
+    /// let json = br#"{"asset":{"version":"2.0"},"nodes":[{"matrix":[
+    ///     1,0,0,0, 0,1,0,0, 0,0,1,0, 1,2,3,1
+    /// ]}]}"#;
+    /// let gltf = gltf::Gltf::from_slice(json)?.validate_completely()?;
+    /// let mut root = gltf.into_json();
+    /// root.asset.generator = Some("example".to_string());
+    /// let gltf = gltf::Gltf::from_json(root).validate_completely()?;
+    ///
+    /// let mut file = vec![];
+    /// gltf.to_writer(&mut file)?;
+    /// assert!(gltf::Gltf::from_slice(&file)?.validate_completely().is_ok());
+    /// # Ok(())
+    /// # }
+    /// # fn main() {
+    /// #     run().unwrap();
+    /// # }
+    /// ```
+    pub fn to_writer<W>(&self, writer: W) -> Result<(), Error>
+        where W: io::Write
+    {
+        json::to_writer(writer, &self.root).map_err(Error::Serialize)
+    }
+
+    /// Writes the glTF asset as indented JSON text.
+    ///
+    /// See `Gltf::to_writer`.
+    pub fn to_writer_pretty<W>(&self, writer: W) -> Result<(), Error>
+        where W: io::Write
+    {
+        json::to_writer_pretty(writer, &self.root).map_err(Error::Serialize)
+    }
+
+    /// Returns the glTF asset as compact JSON text.
+    ///
+    /// See `Gltf::to_writer`.
+    pub fn to_string(&self) -> Result<String, Error> {
+        json::to_string(&self.root).map_err(Error::Serialize)
+    }
+
+    /// Returns the glTF asset as indented JSON text.
+    ///
+    /// See `Gltf::to_writer`.
+    pub fn to_string_pretty(&self) -> Result<String, Error> {
+        json::to_string_pretty(&self.root).map_err(Error::Serialize)
+    }
+
+    /// Writes the glTF asset as compact JSON text with its floating point
+    /// numbers formatted by `json::floats::format_floats`, so that repeated
+    /// exports of the same data produce identical text.
+    ///
+    /// See `Gltf::to_writer`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fn run() -> Result<(), gltf::Error> {
+    /// use gltf::json::floats::Floats;
+    ///
+    /// let json = br#"{"asset":{"version":"2.0"},"nodes":[{"scale":[0.10000000149011612,1,-0.0]}]}"#;
+    /// let gltf = gltf::Gltf::from_slice(json)?.validate_completely()?;
+    /// let text = gltf.to_string_with(Floats::Single)?;
+    /// assert!(text.contains(r#""scale":[0.1,1.0,0.0]"#));
+    /// # Ok(())
+    /// # }
+    /// # fn main() {
+    /// #     run().unwrap();
+    /// # }
+    /// ```
+    pub fn to_writer_with<W>(&self, writer: W, floats: json::floats::Floats) -> Result<(), Error>
+        where W: io::Write
+    {
+        let value = self.to_value_with(floats)?;
+        json::to_writer(writer, &value).map_err(Error::Serialize)
+    }
+
+    /// Returns the glTF asset as compact JSON text with its floating point
+    /// numbers formatted by `json::floats::format_floats`.
+    ///
+    /// See `Gltf::to_writer_with`.
+    pub fn to_string_with(&self, floats: json::floats::Floats) -> Result<String, Error> {
+        let value = self.to_value_with(floats)?;
+        json::to_string(&value).map_err(Error::Serialize)
+    }
+
+    /// Serializes the asset with its floating point numbers formatted.
+    fn to_value_with(&self, floats: json::floats::Floats) -> Result<json::Value, Error> {
+        let mut value = json::to_value(&self.root).map_err(Error::Serialize)?;
+        json::floats::format_floats(&mut value, floats);
+        Ok(value)
+    }
+
+    /// Returns the glTF asset as binary glTF, with `bin` as the BIN chunk.
+    ///
+    /// With `bin`, the first buffer must have no `uri` and a `byteLength` at
+    /// most three bytes shorter than `bin`, as the specification allows for
+    /// padding, otherwise `Error::Validation` is returned. Other buffers and
+    /// images keep their URIs. The JSON is written as by `Gltf::to_writer`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fn run() -> Result<(), gltf::Error> {
+    /// let json = br#"{"asset":{"version":"2.0"},"buffers":[{"byteLength":5}]}"#;
+    /// let gltf = gltf::Gltf::from_slice(json)?.validate_completely()?;
+    /// let bin = [1, 2, 3, 4, 5];
+    /// let written = gltf.to_glb(Some(&bin))?;
+    ///
+    /// let (_, reread) = gltf::from_bytes(&written)?;
+    /// assert!(reread.unwrap().starts_with(&bin));
+    /// # Ok(())
+    /// # }
+    /// # fn main() {
+    /// #     run().unwrap();
+    /// # }
+    /// ```
+    pub fn to_glb(&self, bin: Option<&[u8]>) -> Result<Vec<u8>, Error> {
+        if let Some(bin) = bin {
+            use json::validation::Error::{Invalid, Missing};
+            let path = json::Path::new().field("buffers").index(0);
+            let err = match self.root.buffers.first() {
+                None => Some((path, Missing)),
+                Some(buffer) if buffer.uri.is_some() => Some((path.field("uri"), Invalid)),
+                Some(buffer) if buffer.byte_length as usize > bin.len() ||
+                    bin.len() - buffer.byte_length as usize > 3 =>
+                {
+                    Some((path.field("byteLength"), Invalid))
+                },
+                Some(_) => None,
+            };
+            if let Some(err) = err {
+                return Err(Error::Validation(vec![err]));
+            }
+        }
+        let json = self.to_string()?.into_bytes();
+        Glb::new(json.into(), bin.map(Into::into))?.to_vec()
+    }
+
     /// Constructs the `Gltf` wrapper from binary glTF.
     pub fn from_glb(glb: &Glb) -> Result<Unvalidated, Error> {
         Gltf::from_slice(&glb.json)
@@ -238,26 +592,26 @@ impl Gltf {
         where R: io::Read
     {
         let json: json::Root = json::from_reader(reader)?;
-        Ok(Unvalidated(Gltf::from_json(json)))
+        Ok(Gltf::from_json(json))
     }
 
     /// Constructs the `Gltf` wrapper from a slice of bytes.
     pub fn from_slice(slice: &[u8]) -> Result<Unvalidated, Error> {
         let json: json::Root = json::from_slice(slice)?;
-        Ok(Unvalidated(Gltf::from_json(json)))
+        Ok(Gltf::from_json(json))
     }
 
     /// Constructs the `Gltf` wrapper from a string slice.
     #[allow(should_implement_trait)]
     pub fn from_str(slice: &str) -> Result<Unvalidated, Error> {
         let json: json::Root = json::from_str(slice)?;
-        Ok(Unvalidated(Gltf::from_json(json)))
+        Ok(Gltf::from_json(json))
     }
 
     /// Constructs the `Gltf` wrapper from a `gltf_json::Value`.
     pub fn from_value(value: json::Value) -> Result<Unvalidated, Error> {
         let json: json::Root = json::from_value(value)?;
-        Ok(Unvalidated(Gltf::from_json(json)))
+        Ok(Gltf::from_json(json))
     }
 
     /// Returns an `Iterator` that visits the accessors of the glTF asset.
@@ -268,6 +622,21 @@ impl Gltf {
         }
     }
 
+    /// Returns the accessors of the glTF asset grouped by the buffer view they
+    /// read from, indexed by buffer view.
+    ///
+    /// Useful for uploading each buffer view as a single GPU buffer. Accessors
+    /// without a buffer view are left out.
+    pub fn accessors_by_view(&self) -> Vec<Vec<Accessor>> {
+        let mut groups = vec![vec![]; self.views().len()];
+        for accessor in self.accessors() {
+            if let Some(view) = accessor.view() {
+                groups[view.index()].push(accessor);
+            }
+        }
+        groups
+    }
+
     /// Returns an `Iterator` that visits the animations of the glTF asset.
     pub fn animations(&self) -> Animations {
         Animations {
@@ -282,6 +651,49 @@ impl Gltf {
         &self.root
     }
 
+    /// Returns a stable 64-bit hash of the semantic content of the document,
+    /// e.g. for caching data derived from an asset.
+    ///
+    /// The hash is taken over the document serialized as minified JSON with
+    /// sorted keys and every number converted to `f64`, so documents that
+    /// differ only in whitespace, key order, or the formatting of numbers,
+    /// such as `1` and `1.0`, have equal hashes, including numbers in `extras`
+    /// and extensions.
+    /// Like `Gltf::to_writer`, this fails with `Error::Serialize` if names or
+    /// `extras` were discarded because the `names` or `extras` feature is
+    /// disabled, since the hash would not reflect them.
+    /// Buffer and image data are not included; combine the hash with hashes
+    /// of the loaded data, e.g. `buffer::crc32`, to detect changes to it.
+    ///
+    /// The hash is stable across runs and platforms but may change between
+    /// versions of this crate.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn run() -> Result<(), gltf::Error> {
+    /// let a = gltf::Gltf::from_str(r#"{
+    ///     "asset": { "version": "2.0" },
+    ///     "nodes": [{ "translation": [1, 0, 0] }]
+    /// }"#)?.validate_completely()?;
+    /// let b = gltf::Gltf::from_str(r#"{
+    ///     "nodes": [{ "translation": [1.0, 0.00, 0e0] }], "asset": { "version": "2.0" }
+    /// }"#)?.validate_completely()?;
+    /// assert_eq!(a.content_hash()?, b.content_hash()?);
+    /// # Ok(())
+    /// # }
+    /// # fn main() {
+    /// #     run().unwrap();
+    /// # }
+    /// ```
+    pub fn content_hash(&self) -> Result<u64, Error> {
+        let mut value = json::to_value(&self.root).map_err(Error::Serialize)?;
+        normalize_numbers(&mut value);
+        let mut hasher = Fnv1a(0xcbf2_9ce4_8422_2325);
+        write_canonical(&value, &mut hasher);
+        Ok(hasher.0)
+    }
+
     /// Returns an `Iterator` that visits the pre-loaded buffers of the glTF asset.
     pub fn buffers(&self) -> Buffers {
         Buffers {
@@ -290,6 +702,11 @@ impl Gltf {
         }
     }
 
+    /// Returns the metadata of the glTF asset.
+    pub fn asset(&self) -> Asset {
+        Asset::new(&self.as_json().asset)
+    }
+
     /// Returns an `Iterator` that visits the cameras of the glTF asset.
     pub fn cameras(&self) -> Cameras {
         Cameras {
@@ -306,6 +723,45 @@ impl Gltf {
             .map(|index| self.scenes().nth(index.value()).unwrap())
     }
 
+    /// Starts recording validated modifications of the asset.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn run() -> Result<(), gltf::Error> {
+    /// # let path = "examples/Box.gltf";
+    /// # let gltf = gltf::Gltf::from_slice(&std::fs::read(path).unwrap())?;
+    /// let gltf = gltf.validate_minimally()?;
+    /// let mut edit = gltf.edit();
+    /// edit.node_mut(0).unwrap().set_mesh(None)?;
+    /// assert!(edit.node_mut(0).unwrap().set_mesh(Some(7)).is_err());
+    ///
+    /// {
+    ///     let mut mesh = edit.mesh_mut(0).unwrap();
+    ///     assert!(mesh.remove_primitive(0).is_err());
+    ///     let primitive = mesh.primitive_mut(0).unwrap().as_json().clone();
+    ///     mesh.push_primitive(primitive)?;
+    ///     mesh.remove_primitive(0)?;
+    ///     mesh.primitive_mut(0).unwrap().set_material(None)?;
+    /// }
+    ///
+    /// let mut material = edit.material_mut(0).unwrap();
+    /// material.set_metallic_factor(0.5)?;
+    /// assert!(material.set_roughness_factor(2.0).is_err());
+    ///
+    /// let edited = edit.commit();
+    /// let material = edited.materials().next().unwrap();
+    /// assert_eq!(0.5, material.pbr_metallic_roughness().metallic_factor());
+    /// # Ok(())
+    /// # }
+    /// # fn main() {
+    /// #    run().unwrap();
+    /// # }
+    /// ```
+    pub fn edit(&self) -> GltfEdit<'_> {
+        GltfEdit::new(self)
+    }
+
     /// Returns the extensions referenced in this .gltf file.
     pub fn extensions_used(&self) -> Extensions {
         Extensions(self.root.extensions_used.iter())
@@ -348,6 +804,35 @@ impl Gltf {
         }
     }
 
+    /// Returns an `Iterator` that visits every node instantiating a mesh,
+    /// together with the mesh.
+    pub fn nodes_with_meshes(&self) -> NodesWithMeshes {
+        NodesWithMeshes { nodes: self.nodes() }
+    }
+
+    /// Returns an `Iterator` that visits every primitive of every mesh of the
+    /// glTF asset, together with its mesh, in mesh order.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn run() -> Result<(), Box<std::error::Error>> {
+    /// let gltf = gltf::Gltf::from_slice(include_bytes!("../examples/Box.gltf"))?
+    ///     .validate_completely()?;
+    /// for (mesh, primitive) in gltf.primitives() {
+    ///     println!("meshes[{}].primitives[{}]", mesh.index(), primitive.index());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// # fn main() { run().unwrap() }
+    /// ```
+    pub fn primitives(&self) -> Primitives {
+        Primitives {
+            meshes: self.meshes(),
+            primitives: None,
+        }
+    }
+
     /// Returns an `Iterator` that visits the scenes of the glTF asset.
     pub fn samplers(&self) -> Samplers {
         Samplers {
@@ -380,6 +865,85 @@ impl Gltf {
         }
     }
 
+    /// Returns the metadata schema embedded by the `EXT_structural_metadata`
+    /// extension.
+    pub fn metadata_schema(&self) -> Option<&json::extensions::metadata::Schema> {
+        self.as_json().extensions.structural_metadata.as_ref()?.schema.as_ref()
+    }
+
+    /// Returns an `Iterator` that visits the property tables of the
+    /// `EXT_structural_metadata` extension.
+    pub fn property_tables(&self) -> PropertyTables {
+        let metadata = self.as_json().extensions.structural_metadata.as_ref();
+        PropertyTables {
+            iter: metadata
+                .map(|ext| ext.property_tables.iter())
+                .unwrap_or_else(|| [].iter())
+                .enumerate(),
+            gltf: self,
+        }
+    }
+
+    /// Returns an `Iterator` that visits the audio clips of the `KHR_audio`
+    /// extension.
+    #[cfg(feature = "audio")]
+    pub fn audio_clips(&self) -> audio::Clips {
+        let ext = self.as_json().extensions.audio.as_ref();
+        audio::Clips {
+            iter: ext.map(|ext| ext.clips.iter()).unwrap_or_else(|| [].iter()).enumerate(),
+            gltf: self,
+        }
+    }
+
+    /// Returns an `Iterator` that visits the audio sources of the `KHR_audio`
+    /// extension.
+    #[cfg(feature = "audio")]
+    pub fn audio_sources(&self) -> audio::Sources {
+        let ext = self.as_json().extensions.audio.as_ref();
+        audio::Sources {
+            iter: ext.map(|ext| ext.sources.iter()).unwrap_or_else(|| [].iter()).enumerate(),
+            gltf: self,
+        }
+    }
+
+    /// Returns an `Iterator` that visits the audio emitters of the `KHR_audio`
+    /// extension.
+    #[cfg(feature = "audio")]
+    pub fn audio_emitters(&self) -> audio::Emitters {
+        let ext = self.as_json().extensions.audio.as_ref();
+        audio::Emitters {
+            iter: ext.map(|ext| ext.emitters.iter()).unwrap_or_else(|| [].iter()).enumerate(),
+            gltf: self,
+        }
+    }
+
+    /// Returns the behavior graphs of the `KHR_interactivity` extension.
+    ///
+    /// Use `Gltf::into_json` to modify the graphs; properties not modelled by
+    /// the typed structures are written back unchanged.
+    #[cfg(feature = "interactivity")]
+    pub fn interactivity(&self) -> Option<&json::extensions::interactivity::Interactivity> {
+        self.as_json().extensions.interactivity.as_ref()
+    }
+
+    /// Returns the shapes of the `KHR_implicit_shapes` extension, referenced
+    /// by the colliders and triggers of `Node::physics`.
+    #[cfg(feature = "physics")]
+    pub fn implicit_shapes(&self) -> &[json::extensions::physics::Shape] {
+        self.as_json()
+            .extensions
+            .implicit_shapes
+            .as_ref()
+            .map_or(&[], |ext| ext.shapes.as_slice())
+    }
+
+    /// Returns the physics materials, collision filters, and joint definitions
+    /// of the `KHR_physics_rigid_bodies` extension.
+    #[cfg(feature = "physics")]
+    pub fn physics(&self) -> Option<&json::extensions::physics::Physics> {
+        self.as_json().extensions.physics.as_ref()
+    }
+
     /// Returns an `Iterator` that visits the pre-loaded buffer views of the glTF
     /// asset.
     pub fn views(&self) -> Views {
@@ -506,6 +1070,39 @@ impl<'a> Iterator for Meshes<'a> {
     }
 }
 
+impl<'a> Iterator for Primitives<'a> {
+    type Item = (Mesh<'a>, Primitive<'a>);
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(primitive) = self.primitives.as_mut().and_then(|iter| iter.next()) {
+                return Some((primitive.mesh(), primitive));
+            }
+            match self.meshes.next() {
+                Some(mesh) => self.primitives = Some(mesh.primitives()),
+                None => return None,
+            }
+        }
+    }
+}
+
+impl<'a> Iterator for NodesWithMeshes<'a> {
+    type Item = (Node<'a>, Mesh<'a>);
+    fn next(&mut self) -> Option<Self::Item> {
+        let gltf = self.nodes.gltf;
+        for node in &mut self.nodes {
+            if let Some(index) = node.as_json().mesh.as_ref() {
+                let mesh = gltf.meshes().nth(index.value()).unwrap();
+                return Some((node, mesh));
+            }
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.nodes.size_hint().1)
+    }
+}
+
 impl<'a> ExactSizeIterator for Nodes<'a> {}
 impl<'a> Iterator for Nodes<'a> {
     type Item = Node<'a>;
@@ -1,4 +1,4 @@
-use {buffer, json};
+use {buffer, json, material};
 use Gltf;
 
 /// Image data used to create a texture.
@@ -84,6 +84,24 @@ impl<'a> Image<'a> {
         }
     }
 
+    /// Returns the material slots referencing this image through any texture,
+    /// in `Slot` order and without duplicates.
+    ///
+    /// See `Texture::usages`.
+    pub fn usages(&self) -> Vec<material::Slot> {
+        let mut slots = vec![];
+        let textures = self.gltf.textures().filter(|texture| texture.source().index() == self.index);
+        for texture in textures {
+            for slot in texture.usages() {
+                if !slots.contains(&slot) {
+                    slots.push(slot);
+                }
+            }
+        }
+        slots.sort();
+        slots
+    }
+
     /// Optional application specific data.
     pub fn extras(&self) -> &json::Extras {
         &self.json.extras
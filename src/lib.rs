@@ -1,5 +1,6 @@
 #![deny(missing_docs)]
 #![allow(unknown_lints)]
+#![cfg_attr(feature = "forbid-unsafe", forbid(unsafe_code))]
 
 //! glTF 2.0 loader
 //!
@@ -56,12 +57,8 @@
 extern crate approx;
 extern crate byteorder;
 extern crate cgmath;
-#[macro_use]
-extern crate lazy_static;
 
 /// Contains (de)serializable data structures that match the glTF JSON text.
-#[deprecated(since = "0.9.1", note = "Will be removed in 1.0.0")]
-#[doc(hidden)]
 pub extern crate gltf_json as json;
 
 /// Accessors for reading vertex attributes from buffer views.
@@ -70,12 +67,29 @@ pub mod accessor;
 /// Animations, their channels, targets, and samplers.
 pub mod animation;
 
+/// Metadata about the glTF asset.
+pub mod asset;
+
+/// Audio clips, sources, and emitters of the `KHR_audio` extension.
+#[cfg(feature = "audio")]
+pub mod audio;
+
 /// Buffers and buffer views.
 pub mod buffer;
 
+/// Builders for authoring glTF assets from scratch.
+pub mod build;
+
 /// Cameras and their projections.
 pub mod camera;
 
+/// Color space conversions and the color spaces of glTF colors.
+pub mod color;
+
+/// Recording validated modifications of nodes, meshes, primitives, and
+/// materials.
+pub mod edit;
+
 /// Primitives for working with binary glTF.
 pub mod glb;
 
@@ -91,6 +105,12 @@ pub mod material;
 /// Meshes and their primitives.
 pub mod mesh;
 
+/// Feature IDs and per-feature metadata of 3D Tiles content.
+pub mod metadata;
+
+/// Human-readable summaries of glTF assets.
+pub mod print;
+
 /// The glTF node heirarchy.
 pub mod scene;
 
@@ -105,6 +125,8 @@ pub use self::animation::Animation;
 #[doc(inline)]
 pub use self::accessor::Accessor;
 #[doc(inline)]
+pub use self::asset::Asset;
+#[doc(inline)]
 pub use self::buffer::Buffer;
 #[doc(inline)]
 pub use self::camera::Camera;
@@ -132,6 +154,8 @@ pub enum Error {
     Deserialize(json::Error),
     /// GLB parsing error.
     Glb(self::glb::Error),
+    /// JSON serialization error.
+    Serialize(json::Error),
     /// `glTF` validation error.
     Validation(Vec<(json::Path, json::validation::Error)>),
 }
@@ -149,6 +173,41 @@ pub fn is_binary(slice: &[u8]) -> bool {
     slice.starts_with(b"glTF")
 }
 
+/// Parses either binary or JSON `glTF`, detected from the content of the slice
+/// rather than a file extension.
+///
+/// Returns the document along with the `BIN` chunk of binary `glTF`, if any. A
+/// leading UTF-8 byte order mark is ignored in JSON `glTF`.
+///
+/// # Examples
+///
+/// ```rust
+/// # use std::fs;
+/// # fn run() -> Result<(), gltf::Error> {
+/// # let path = "examples/Box.glb";
+/// let data = fs::read(path).unwrap();
+/// let (gltf, bin) = gltf::from_bytes(&data)?;
+/// let gltf = gltf.validate_minimally()?;
+/// assert_eq!(1, gltf.meshes().count());
+/// assert!(bin.is_some());
+/// # Ok(())
+/// # }
+/// # fn main() {
+/// #    run().unwrap();
+/// # }
+/// ```
+pub fn from_bytes(slice: &[u8]) -> Result<(Unvalidated, Option<Vec<u8>>), Error> {
+    if is_binary(slice) {
+        let glb = Glb::from_slice(slice)?;
+        let gltf = Gltf::from_glb(&glb)?;
+        Ok((gltf, glb.bin.map(|bin| bin.into_owned())))
+    } else if slice.starts_with(b"\xEF\xBB\xBF") {
+        Ok((Gltf::from_slice(&slice[3..])?, None))
+    } else {
+        Ok((Gltf::from_slice(slice)?, None))
+    }
+}
+
 impl std::fmt::Display for Error {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         use std::error::Error;
@@ -161,6 +220,7 @@ impl std::error::Error for Error {
          match *self {
              Error::Deserialize(_) => "deserialization error",
              Error::Glb(ref e) => e.description(),
+             Error::Serialize(_) => "serialization error",
              Error::Validation(_) => "invalid glTF JSON",
         }
     }
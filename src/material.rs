@@ -1,9 +1,97 @@
+use std::vec;
+
 use {json, texture, Gltf};
+use color::{Rgb, Rgba};
 
 pub use json::material::AlphaMode;
 
-lazy_static! {
-    static ref DEFAULT_MATERIAL: json::material::Material = Default::default();
+/// The semantic of a texture used by a material.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Slot {
+    /// The base color texture of the metallic-roughness model.
+    BaseColor,
+
+    /// The metallic-roughness texture, often packed into the same image as
+    /// the occlusion texture.
+    MetallicRoughness,
+
+    /// The tangent space normal map.
+    Normal,
+
+    /// The occlusion map.
+    Occlusion,
+
+    /// The emissive map.
+    Emissive,
+
+    /// The clear coat layer intensity texture of `KHR_materials_clearcoat`.
+    Clearcoat,
+
+    /// The clear coat layer roughness texture of `KHR_materials_clearcoat`.
+    ClearcoatRoughness,
+
+    /// The clear coat layer normal map of `KHR_materials_clearcoat`.
+    ClearcoatNormal,
+}
+
+impl Slot {
+    /// Returns `true` if textures in this slot hold color data in sRGB color
+    /// space, or `false` if they hold linear data.
+    pub fn is_srgb(&self) -> bool {
+        match *self {
+            Slot::BaseColor | Slot::Emissive => true,
+            _ => false,
+        }
+    }
+}
+
+/// A texture used by a material, with its semantic and texture coordinates.
+#[derive(Clone, Debug)]
+pub struct SlotTexture<'a> {
+    /// The semantic of the texture.
+    slot: Slot,
+
+    /// The referenced texture.
+    texture: texture::Texture<'a>,
+
+    /// The set index of the `TEXCOORD` attribute.
+    tex_coord: u32,
+}
+
+impl<'a> SlotTexture<'a> {
+    /// Returns the semantic of the texture.
+    pub fn slot(&self) -> Slot {
+        self.slot
+    }
+
+    /// Returns the referenced `Texture`.
+    pub fn texture(&self) -> texture::Texture<'a> {
+        self.texture.clone()
+    }
+
+    /// Returns the set index of the texture's `TEXCOORD` attribute.
+    pub fn tex_coord(&self) -> u32 {
+        self.tex_coord
+    }
+}
+
+/// An `Iterator` that visits the textures used by a `Material`.
+#[derive(Clone, Debug)]
+pub struct Textures<'a> {
+    /// The internal iterator.
+    iter: vec::IntoIter<SlotTexture<'a>>,
+}
+
+impl<'a> ExactSizeIterator for Textures<'a> {}
+impl<'a> Iterator for Textures<'a> {
+    type Item = SlotTexture<'a>;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
 }
 
 /// The material appearance of a primitive.
@@ -37,7 +125,7 @@ impl<'a> Material<'a> {
         Self {
             gltf: gltf,
             index: None,
-            json: &DEFAULT_MATERIAL,
+            json: gltf.default_material(),
         }
     }
 
@@ -145,17 +233,89 @@ impl<'a> Material<'a> {
         })
     }
 
-    /// The emissive color of the material.
+    /// The emissive color of the material, in linear space.
     ///
     /// The default value is `[0.0, 0.0, 0.0]`.
     pub fn emissive_factor(&self) -> [f32; 3] {
         self.json.emissive_factor.0
     }
 
+    /// The emissive color of the material, in linear space.
+    pub fn emissive_color(&self) -> Rgb {
+        Rgb(self.emissive_factor())
+    }
+
+    /// The clear coat layer of the material, from the `KHR_materials_clearcoat`
+    /// extension.
+    pub fn clearcoat(&self) -> Option<Clearcoat<'a>> {
+        self.json.extensions.clearcoat.as_ref().map(|json| Clearcoat::new(self.gltf, json))
+    }
+
+    /// Returns an `Iterator` that visits every texture used by the material,
+    /// in `Slot` order.
+    ///
+    /// A texture used in several slots, e.g. an occlusion-roughness-metallic
+    /// texture packed into one image, is visited once per slot.
+    pub fn textures(&self) -> Textures<'a> {
+        let mut textures = vec![];
+        {
+            let mut push = |slot, index: &json::Index<json::texture::Texture>, tex_coord| {
+                textures.push(SlotTexture {
+                    slot,
+                    texture: self.gltf.textures().nth(index.value()).unwrap(),
+                    tex_coord,
+                });
+            };
+            let pbr = &self.json.pbr_metallic_roughness;
+            if let Some(ref info) = pbr.base_color_texture {
+                push(Slot::BaseColor, &info.index, info.tex_coord);
+            }
+            if let Some(ref info) = pbr.metallic_roughness_texture {
+                push(Slot::MetallicRoughness, &info.index, info.tex_coord);
+            }
+            if let Some(ref normal) = self.json.normal_texture {
+                push(Slot::Normal, &normal.index, normal.tex_coord);
+            }
+            if let Some(ref occlusion) = self.json.occlusion_texture {
+                push(Slot::Occlusion, &occlusion.index, occlusion.tex_coord);
+            }
+            if let Some(ref info) = self.json.emissive_texture {
+                push(Slot::Emissive, &info.index, info.tex_coord);
+            }
+            if let Some(ref clearcoat) = self.json.extensions.clearcoat {
+                if let Some(ref info) = clearcoat.clearcoat_texture {
+                    push(Slot::Clearcoat, &info.index, info.tex_coord);
+                }
+                if let Some(ref info) = clearcoat.clearcoat_roughness_texture {
+                    push(Slot::ClearcoatRoughness, &info.index, info.tex_coord);
+                }
+                if let Some(ref normal) = clearcoat.clearcoat_normal_texture {
+                    push(Slot::ClearcoatNormal, &normal.index, normal.tex_coord);
+                }
+            }
+        }
+        Textures { iter: textures.into_iter() }
+    }
+
+    /// Returns `true` if any texture of the material reads the `TEXCOORD_n`
+    /// attribute with the given set index `n`.
+    ///
+    /// Renderers can check this against the attributes of each primitive
+    /// using the material.
+    pub fn uses_texcoord_set(&self, set: u32) -> bool {
+        self.textures().any(|texture| texture.tex_coord() == set)
+    }
+
     /// Optional application specific data.
     pub fn extras(&self) -> &json::Extras {
         &self.json.extras
     }
+
+    /// Blender custom properties stored in `extras`.
+    #[cfg(feature = "extras")]
+    pub fn custom_properties(&self) -> json::extras::Properties {
+        json::extras::properties(&self.json.extras)
+    }
 }
 
 /// A set of parameter values that are used to define the metallic-roughness
@@ -186,13 +346,18 @@ impl<'a> PbrMetallicRoughness<'a> {
         self.json
     }
 
-    /// Returns the material's base color factor.
+    /// Returns the material's base color factor, in linear space.
     ///
     /// The default value is `[1.0, 1.0, 1.0, 1.0]`.
     pub fn base_color_factor(&self) -> [f32; 4] {
         self.json.base_color_factor.0
     }
 
+    /// Returns the material's base color factor, in linear space.
+    pub fn base_color(&self) -> Rgba {
+        Rgba(self.base_color_factor())
+    }
+
     /// Returns the base color texture.
     pub fn base_color_texture(&self) -> Option<texture::Info<'a>> {
         self.json.base_color_texture.as_ref().map(|json| {
@@ -239,6 +404,82 @@ impl<'a> PbrMetallicRoughness<'a> {
     }
 }
 
+/// A clear coat layer on top of a material, e.g. the lacquer of car paint.
+pub struct Clearcoat<'a> {
+    /// The parent `Gltf` struct.
+    gltf: &'a Gltf,
+
+    /// The corresponding JSON struct.
+    json: &'a json::extensions::material::Clearcoat,
+}
+
+impl<'a> Clearcoat<'a> {
+    /// Constructs a `Clearcoat`.
+    pub(crate) fn new(
+        gltf: &'a Gltf,
+        json: &'a json::extensions::material::Clearcoat,
+    ) -> Self {
+        Self {
+            gltf: gltf,
+            json: json,
+        }
+    }
+
+    /// Returns the internal JSON item.
+    #[doc(hidden)]
+    pub fn as_json(&self) -> &json::extensions::material::Clearcoat {
+        self.json
+    }
+
+    /// Returns the intensity of the clear coat layer.
+    ///
+    /// The default value is `0.0`, which disables the layer.
+    pub fn clearcoat_factor(&self) -> f32 {
+        self.json.clearcoat_factor
+    }
+
+    /// The clear coat layer intensity texture.
+    ///
+    /// The intensity is sampled from the R channel and multiplied by
+    /// `clearcoat_factor`.
+    pub fn clearcoat_texture(&self) -> Option<texture::Info<'a>> {
+        self.json.clearcoat_texture.as_ref().map(|json| {
+            let texture = self.gltf.textures().nth(json.index.value()).unwrap();
+            texture::Info::new(texture, json)
+        })
+    }
+
+    /// Returns the roughness of the clear coat layer.
+    ///
+    /// The default value is `0.0`.
+    pub fn clearcoat_roughness_factor(&self) -> f32 {
+        self.json.clearcoat_roughness_factor
+    }
+
+    /// The clear coat layer roughness texture.
+    ///
+    /// The roughness is sampled from the G channel and multiplied by
+    /// `clearcoat_roughness_factor`.
+    pub fn clearcoat_roughness_texture(&self) -> Option<texture::Info<'a>> {
+        self.json.clearcoat_roughness_texture.as_ref().map(|json| {
+            let texture = self.gltf.textures().nth(json.index.value()).unwrap();
+            texture::Info::new(texture, json)
+        })
+    }
+
+    /// A tangent space normal map for the clear coat layer.
+    ///
+    /// The map is independent of the normal map of the material, which
+    /// applies only to the base layer, and is interpreted like it, including
+    /// its `scale` and `tex_coord`.
+    pub fn clearcoat_normal_texture(&self) -> Option<NormalTexture<'a>> {
+        self.json.clearcoat_normal_texture.as_ref().map(|json| {
+            let texture = self.gltf.textures().nth(json.index.value()).unwrap();
+            NormalTexture::new(texture, json)
+        })
+    }
+}
+
 /// Defines the normal texture of a material.
 pub struct NormalTexture<'a> {
     /// The parent `Texture` struct.
@@ -1,6 +1,7 @@
-use std::{collections, iter, slice};
+use std::{collections, iter, ops, slice};
 use json;
 
+use metadata::FeatureIds;
 use {Accessor, Gltf, Material};
 
 pub use json::mesh::{Mode, Semantic};
@@ -9,6 +10,159 @@ use json::validation::Checked;
 /// Vertex attribute data.
 pub type Attribute<'a> = (Semantic, Accessor<'a>);
 
+/// A set of vertex attribute semantics, e.g. to select a shader permutation.
+///
+/// Only the first two sets of the `COLOR_n`, `TEXCOORD_n`, `JOINTS_n`, and
+/// `WEIGHTS_n` attributes are represented.
+///
+/// # Examples
+///
+/// ```
+/// use gltf::mesh::AttributeFlags;
+///
+/// let flags = AttributeFlags::POSITION | AttributeFlags::TEXCOORD_0;
+/// assert!(flags.contains(AttributeFlags::POSITION));
+/// assert!(!flags.contains(AttributeFlags::POSITION | AttributeFlags::NORMAL));
+/// ```
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct AttributeFlags(u32);
+
+impl AttributeFlags {
+    /// `POSITION`.
+    pub const POSITION: AttributeFlags = AttributeFlags(1 << 0);
+
+    /// `NORMAL`.
+    pub const NORMAL: AttributeFlags = AttributeFlags(1 << 1);
+
+    /// `TANGENT`.
+    pub const TANGENT: AttributeFlags = AttributeFlags(1 << 2);
+
+    /// `COLOR_0`.
+    pub const COLOR_0: AttributeFlags = AttributeFlags(1 << 3);
+
+    /// `COLOR_1`.
+    pub const COLOR_1: AttributeFlags = AttributeFlags(1 << 4);
+
+    /// `TEXCOORD_0`.
+    pub const TEXCOORD_0: AttributeFlags = AttributeFlags(1 << 5);
+
+    /// `TEXCOORD_1`.
+    pub const TEXCOORD_1: AttributeFlags = AttributeFlags(1 << 6);
+
+    /// `JOINTS_0`.
+    pub const JOINTS_0: AttributeFlags = AttributeFlags(1 << 7);
+
+    /// `JOINTS_1`.
+    pub const JOINTS_1: AttributeFlags = AttributeFlags(1 << 8);
+
+    /// `WEIGHTS_0`.
+    pub const WEIGHTS_0: AttributeFlags = AttributeFlags(1 << 9);
+
+    /// `WEIGHTS_1`.
+    pub const WEIGHTS_1: AttributeFlags = AttributeFlags(1 << 10);
+
+    /// Returns the empty set.
+    pub fn empty() -> Self {
+        AttributeFlags(0)
+    }
+
+    /// Returns the flag of a semantic, if represented.
+    pub fn from_semantic(semantic: &Semantic) -> Option<Self> {
+        let flag = match *semantic {
+            Semantic::Positions => Self::POSITION,
+            Semantic::Normals => Self::NORMAL,
+            Semantic::Tangents => Self::TANGENT,
+            Semantic::Colors(set) if set < 2 => AttributeFlags(Self::COLOR_0.0 << set),
+            Semantic::TexCoords(set) if set < 2 => AttributeFlags(Self::TEXCOORD_0.0 << set),
+            Semantic::Joints(set) if set < 2 => AttributeFlags(Self::JOINTS_0.0 << set),
+            Semantic::Weights(set) if set < 2 => AttributeFlags(Self::WEIGHTS_0.0 << set),
+            _ => return None,
+        };
+        Some(flag)
+    }
+
+    /// Returns the raw bits of the set.
+    pub fn bits(&self) -> u32 {
+        self.0
+    }
+
+    /// Returns `true` if every flag of `other` is in the set.
+    pub fn contains(&self, other: AttributeFlags) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// Returns `true` if the set is empty.
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+}
+
+impl ops::BitOr for AttributeFlags {
+    type Output = Self;
+    fn bitor(self, other: Self) -> Self {
+        AttributeFlags(self.0 | other.0)
+    }
+}
+
+impl ops::BitOrAssign for AttributeFlags {
+    fn bitor_assign(&mut self, other: Self) {
+        self.0 |= other.0;
+    }
+}
+
+impl ops::BitAnd for AttributeFlags {
+    type Output = Self;
+    fn bitand(self, other: Self) -> Self {
+        AttributeFlags(self.0 & other.0)
+    }
+}
+
+impl ops::Sub for AttributeFlags {
+    type Output = Self;
+    fn sub(self, other: Self) -> Self {
+        AttributeFlags(self.0 & !other.0)
+    }
+}
+
+/// The number of elements of each vertex attribute of a primitive.
+///
+/// Returned by `Primitive::vertex_attribute_counts`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AttributeCounts {
+    /// The element count of each attribute, ordered by semantic name.
+    pub counts: Vec<(Semantic, usize)>,
+}
+
+impl AttributeCounts {
+    /// Returns the vertex count of the primitive, i.e. the count of the
+    /// `POSITION` attribute, or of the first attribute if there is none.
+    pub fn vertex_count(&self) -> Option<usize> {
+        self.counts
+            .iter()
+            .find(|&&(ref semantic, _)| *semantic == Semantic::Positions)
+            .or_else(|| self.counts.first())
+            .map(|&(_, count)| count)
+    }
+
+    /// Returns the attributes whose count differs from the vertex count.
+    ///
+    /// Iterating such attributes in step with the others produces misaligned
+    /// vertex data.
+    pub fn mismatches(&self) -> Vec<(Semantic, usize)> {
+        let vertex_count = self.vertex_count();
+        self.counts
+            .iter()
+            .filter(|&&(_, count)| Some(count) != vertex_count)
+            .cloned()
+            .collect()
+    }
+
+    /// Returns `true` if every attribute has the same count.
+    pub fn is_consistent(&self) -> bool {
+        self.mismatches().is_empty()
+    }
+}
+
 /// A single morph target for a mesh primitive.
 #[derive(Clone, Debug)]
 pub struct MorphTarget<'a> {
@@ -49,7 +203,7 @@ pub struct Mesh<'a>  {
 #[derive(Clone, Debug)]
 pub struct Primitive<'a>  {
     /// The parent `Mesh` struct.
-    mesh: &'a Mesh<'a>,
+    mesh: Mesh<'a>,
 
     /// The corresponding JSON index.
     index: usize,
@@ -79,7 +233,7 @@ pub struct Attributes<'a> {
 #[derive(Clone, Debug)]
 pub struct Primitives<'a>  {
     /// The parent `Mesh` struct.
-    mesh: &'a Mesh<'a>,
+    mesh: Mesh<'a>,
 
     /// The internal JSON primitive iterator.
     iter: iter::Enumerate<slice::Iter<'a, json::mesh::Primitive>>,
@@ -125,6 +279,12 @@ impl<'a> Mesh<'a>  {
         &self.json.extras
     }
 
+    /// Blender custom properties stored in `extras`.
+    #[cfg(feature = "extras")]
+    pub fn custom_properties(&self) -> json::extras::Properties {
+        json::extras::properties(&self.json.extras)
+    }
+
     /// Optional user-defined name for this object.
     #[cfg(feature = "names")]
     pub fn name(&self) -> Option<&str> {
@@ -132,9 +292,9 @@ impl<'a> Mesh<'a>  {
     }
 
     /// Defines the geometry to be renderered with a material.
-    pub fn primitives(&'a self) -> Primitives<'a> {
+    pub fn primitives(&self) -> Primitives<'a> {
         Primitives {
-            mesh: self,
+            mesh: self.clone(),
             iter: self.json.primitives.iter().enumerate(),
         }
     }
@@ -148,7 +308,7 @@ impl<'a> Mesh<'a>  {
 impl<'a> Primitive<'a> {
     /// Constructs a `Primitive`.
     pub(crate) fn new(
-        mesh: &'a Mesh<'a>,
+        mesh: Mesh<'a>,
         index: usize,
         json: &'a json::mesh::Primitive,
     ) -> Self {
@@ -159,6 +319,16 @@ impl<'a> Primitive<'a> {
         }
     }
 
+    /// Returns the internal JSON index.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Returns the parent `Mesh`.
+    pub fn mesh(&self) -> Mesh<'a> {
+        self.mesh.clone()
+    }
+
     /// Returns the internal JSON item.
     #[doc(hidden)]
     pub fn as_json(&self) ->  &json::mesh::Primitive {
@@ -207,6 +377,33 @@ impl<'a> Primitive<'a> {
             .map(|index| self.mesh.gltf.accessors().nth(index.value()).unwrap())
     }
 
+    /// Returns the set of vertex attributes of the primitive.
+    ///
+    /// See `AttributeFlags` for the attributes represented.
+    pub fn attribute_flags(&self) -> AttributeFlags {
+        let mut flags = AttributeFlags::empty();
+        for semantic in self.json.attributes.keys() {
+            if let Checked::Valid(ref semantic) = *semantic {
+                if let Some(flag) = AttributeFlags::from_semantic(semantic) {
+                    flags |= flag;
+                }
+            }
+        }
+        flags
+    }
+
+    /// Returns the element count of every vertex attribute of the primitive.
+    ///
+    /// The specification requires every attribute to have the same count; use
+    /// `AttributeCounts::mismatches` to find attributes that do not.
+    pub fn vertex_attribute_counts(&self) -> AttributeCounts {
+        let mut counts: Vec<(Semantic, usize)> = self.attributes()
+            .map(|(semantic, accessor)| (semantic, accessor.count()))
+            .collect();
+        counts.sort_by_key(|&(ref semantic, _)| semantic.to_string());
+        AttributeCounts { counts }
+    }
+
     /// Returns an `Iterator` that visits the vertex attributes.
     pub fn attributes(&self) -> Attributes {
         Attributes {
@@ -229,8 +426,22 @@ impl<'a> Primitive<'a> {
         self.json.mode.unwrap()
     }
 
+    /// Returns an `Iterator` that visits the feature ID sets of the
+    /// `EXT_mesh_features` extension.
+    pub fn feature_ids(&self) -> FeatureIds<'a> {
+        let features = self.json.extensions.mesh_features.as_ref();
+        FeatureIds {
+            gltf: self.mesh.gltf,
+            primitive: self.json,
+            iter: features.map(|ext| ext.feature_ids.iter()).unwrap_or_else(|| [].iter()),
+        }
+    }
+
     /// Returns an `Iterator` that visits the morph targets of the primitive.
-    pub fn morph_targets(&self) -> MorphTargets {
+    ///
+    /// The targets borrow only the parent `Gltf`, so they outlive the
+    /// primitive and its mesh.
+    pub fn morph_targets(&self) -> MorphTargets<'a> {
         if let Some(slice) = self.json.targets.as_ref() {
             MorphTargets {
                 gltf: self.mesh.gltf,
@@ -284,7 +495,7 @@ impl<'a> ExactSizeIterator for Primitives<'a> {}
 impl<'a> Iterator for Primitives<'a> {
     type Item = Primitive<'a>;
     fn next(&mut self) -> Option<Self::Item> {
-        self.iter.next().map(|(index, json)| Primitive::new(self.mesh, index, json))
+        self.iter.next().map(|(index, json)| Primitive::new(self.mesh.clone(), index, json))
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
@@ -0,0 +1,348 @@
+use std::{collections, iter, slice};
+
+use json;
+use json::extensions::metadata as ext;
+
+use buffer::View;
+use texture::Texture;
+use {Accessor, Gltf};
+
+pub use json::extensions::metadata::{ComponentType, ElementType};
+
+/// A set of feature IDs of a primitive, from the `EXT_mesh_features`
+/// extension.
+#[derive(Clone, Debug)]
+pub struct FeatureId<'a> {
+    /// The parent `Gltf` struct.
+    gltf: &'a Gltf,
+
+    /// The JSON struct of the parent primitive.
+    #[allow(dead_code)]
+    primitive: &'a json::mesh::Primitive,
+
+    /// The corresponding JSON struct.
+    json: &'a json::extensions::mesh::FeatureId,
+}
+
+/// A texture holding feature IDs.
+#[derive(Clone, Debug)]
+pub struct FeatureIdTexture<'a> {
+    /// The referenced `Texture`.
+    texture: Texture<'a>,
+
+    /// The corresponding JSON struct.
+    json: &'a json::extensions::mesh::FeatureIdTexture,
+}
+
+/// An `Iterator` that visits the feature ID sets of a `Primitive`.
+#[derive(Clone, Debug)]
+pub struct FeatureIds<'a> {
+    /// The parent `Gltf` struct.
+    pub(crate) gltf: &'a Gltf,
+
+    /// The JSON struct of the parent primitive.
+    pub(crate) primitive: &'a json::mesh::Primitive,
+
+    /// The internal JSON iterator.
+    pub(crate) iter: slice::Iter<'a, json::extensions::mesh::FeatureId>,
+}
+
+/// A table of per-feature property values, from the `EXT_structural_metadata`
+/// extension.
+#[derive(Clone, Debug)]
+pub struct PropertyTable<'a> {
+    /// The parent `Gltf` struct.
+    gltf: &'a Gltf,
+
+    /// The corresponding JSON index.
+    index: usize,
+
+    /// The corresponding JSON struct.
+    json: &'a ext::PropertyTable,
+}
+
+/// An `Iterator` that visits every property table in a glTF asset.
+#[derive(Clone, Debug)]
+pub struct PropertyTables<'a> {
+    /// The parent `Gltf` struct.
+    pub(crate) gltf: &'a Gltf,
+
+    /// The internal JSON iterator.
+    pub(crate) iter: iter::Enumerate<slice::Iter<'a, ext::PropertyTable>>,
+}
+
+/// A single property, i.e. column, of a property table.
+#[derive(Clone, Debug)]
+pub struct Property<'a> {
+    /// The parent `PropertyTable` struct.
+    table: PropertyTable<'a>,
+
+    /// The property ID.
+    id: &'a str,
+
+    /// The corresponding JSON struct.
+    json: &'a ext::PropertyTableProperty,
+}
+
+/// An `Iterator` that visits the properties of a `PropertyTable`.
+#[derive(Clone, Debug)]
+pub struct Properties<'a> {
+    /// The parent `PropertyTable` struct.
+    table: PropertyTable<'a>,
+
+    /// The internal JSON iterator.
+    iter: collections::btree_map::Iter<'a, String, ext::PropertyTableProperty>,
+}
+
+impl<'a> FeatureId<'a> {
+    /// Returns the internal JSON item.
+    #[doc(hidden)]
+    pub fn as_json(&self) -> &json::extensions::mesh::FeatureId {
+        self.json
+    }
+
+    /// The number of unique features, excluding the null feature ID.
+    pub fn feature_count(&self) -> u32 {
+        self.json.feature_count
+    }
+
+    /// The ID marking vertices or texels without a feature.
+    pub fn null_feature_id(&self) -> Option<u32> {
+        self.json.null_feature_id
+    }
+
+    /// A label identifying the set of feature IDs.
+    pub fn label(&self) -> Option<&'a str> {
+        self.json.label.as_deref()
+    }
+
+    /// The set index `n` of the `_FEATURE_ID_n` vertex attribute holding the
+    /// IDs, if stored per vertex.
+    pub fn attribute(&self) -> Option<u32> {
+        self.json.attribute
+    }
+
+    /// Returns the accessor of the `_FEATURE_ID_n` vertex attribute holding the
+    /// IDs.
+    ///
+    /// Returns `None` if the IDs are not stored per vertex or the primitive
+    /// lacks the attribute.
+    #[cfg(feature = "extras")]
+    pub fn accessor(&self) -> Option<Accessor<'a>> {
+        let set = self.json.attribute?;
+        let semantic = json::mesh::Semantic::Extras(format!("FEATURE_ID_{}", set));
+        self.primitive.attributes
+            .get(&json::validation::Checked::Valid(semantic))
+            .map(|index| self.gltf.accessors().nth(index.value()).unwrap())
+    }
+
+    /// Returns the accessor of the `_FEATURE_ID_n` vertex attribute holding the
+    /// IDs.
+    ///
+    /// Without the `extras` feature, application specific attributes are not
+    /// parsed, so this always returns `None`.
+    #[cfg(not(feature = "extras"))]
+    pub fn accessor(&self) -> Option<Accessor<'a>> {
+        None
+    }
+
+    /// Returns the texture holding the IDs, if stored per texel.
+    pub fn texture(&self) -> Option<FeatureIdTexture<'a>> {
+        self.json.texture.as_ref().map(|json| {
+            let texture = self.gltf.textures().nth(json.index.value()).unwrap();
+            FeatureIdTexture { texture, json }
+        })
+    }
+
+    /// Returns `true` if the ID of each vertex is its index, i.e. the IDs are
+    /// stored neither in an attribute nor in a texture.
+    pub fn is_implicit(&self) -> bool {
+        self.json.attribute.is_none() && self.json.texture.is_none()
+    }
+
+    /// Returns the property table holding the properties of the features.
+    pub fn property_table(&self) -> Option<PropertyTable<'a>> {
+        self.json.property_table
+            .and_then(|index| self.gltf.property_tables().nth(index as usize))
+    }
+}
+
+impl<'a> FeatureIdTexture<'a> {
+    /// Returns the internal JSON item.
+    #[doc(hidden)]
+    pub fn as_json(&self) -> &json::extensions::mesh::FeatureIdTexture {
+        self.json
+    }
+
+    /// The set index of the texture's `TEXCOORD` attribute.
+    pub fn tex_coord(&self) -> u32 {
+        self.json.tex_coord
+    }
+
+    /// The channels holding the bytes of each ID, least significant first.
+    pub fn channels(&self) -> &'a [u32] {
+        &self.json.channels
+    }
+
+    /// Returns the referenced `Texture`.
+    pub fn texture(&self) -> Texture<'a> {
+        self.texture.clone()
+    }
+}
+
+impl<'a> PropertyTable<'a> {
+    /// Constructs a `PropertyTable`.
+    pub(crate) fn new(gltf: &'a Gltf, index: usize, json: &'a ext::PropertyTable) -> Self {
+        Self {
+            gltf: gltf,
+            index: index,
+            json: json,
+        }
+    }
+
+    /// Returns the internal JSON index.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Returns the internal JSON item.
+    #[doc(hidden)]
+    pub fn as_json(&self) -> &ext::PropertyTable {
+        self.json
+    }
+
+    /// The name of the table.
+    pub fn name(&self) -> Option<&'a str> {
+        self.json.name.as_deref()
+    }
+
+    /// The ID of the class of the features.
+    pub fn class_id(&self) -> &'a str {
+        &self.json.class
+    }
+
+    /// Returns the class of the features, if defined by an embedded schema.
+    pub fn class(&self) -> Option<&'a ext::Class> {
+        self.gltf.metadata_schema().and_then(|schema| schema.classes.get(&self.json.class))
+    }
+
+    /// The number of features, i.e. rows, of the table.
+    pub fn count(&self) -> usize {
+        self.json.count as usize
+    }
+
+    /// Returns the property with the given ID.
+    pub fn get(&self, id: &str) -> Option<Property<'a>> {
+        self.json.properties.get_key_value(id).map(|(id, json)| Property {
+            table: self.clone(),
+            id: id,
+            json: json,
+        })
+    }
+
+    /// Returns an `Iterator` that visits the properties of the table.
+    pub fn properties(&self) -> Properties<'a> {
+        Properties {
+            table: self.clone(),
+            iter: self.json.properties.iter(),
+        }
+    }
+}
+
+impl<'a> Property<'a> {
+    /// Returns the internal JSON item.
+    #[doc(hidden)]
+    pub fn as_json(&self) -> &ext::PropertyTableProperty {
+        self.json
+    }
+
+    /// The property ID.
+    pub fn id(&self) -> &'a str {
+        self.id
+    }
+
+    /// Returns the parent property table.
+    pub fn table(&self) -> PropertyTable<'a> {
+        self.table.clone()
+    }
+
+    /// Returns the definition of the property, if defined by an embedded
+    /// schema.
+    pub fn definition(&self) -> Option<&'a ext::ClassProperty> {
+        self.table.class().and_then(|class| class.properties.get(self.id))
+    }
+
+    /// Returns the enum of `ENUM` properties.
+    pub fn enum_type(&self) -> Option<&'a ext::Enum> {
+        let id = self.definition()?.enum_type.as_ref()?;
+        self.table.gltf.metadata_schema()?.enums.get(id)
+    }
+
+    /// Returns the buffer view holding the values.
+    pub fn values(&self) -> View<'a> {
+        self.view(&self.json.values)
+    }
+
+    /// Returns the buffer view holding the element offsets of variable length
+    /// arrays.
+    pub fn array_offsets(&self) -> Option<View<'a>> {
+        self.json.array_offsets.as_ref().map(|index| self.view(index))
+    }
+
+    /// The integer type of the array offsets.
+    pub fn array_offset_type(&self) -> ComponentType {
+        self.json.array_offset_type.unwrap()
+    }
+
+    /// Returns the buffer view holding the byte offsets of strings.
+    pub fn string_offsets(&self) -> Option<View<'a>> {
+        self.json.string_offsets.as_ref().map(|index| self.view(index))
+    }
+
+    /// The integer type of the string offsets.
+    pub fn string_offset_type(&self) -> ComponentType {
+        self.json.string_offset_type.unwrap()
+    }
+
+    fn view(&self, index: &json::Index<json::buffer::View>) -> View<'a> {
+        self.table.gltf.views().nth(index.value()).unwrap()
+    }
+}
+
+impl<'a> ExactSizeIterator for FeatureIds<'a> {}
+impl<'a> Iterator for FeatureIds<'a> {
+    type Item = FeatureId<'a>;
+    fn next(&mut self) -> Option<Self::Item> {
+        let (gltf, primitive) = (self.gltf, self.primitive);
+        self.iter.next().map(|json| FeatureId { gltf, primitive, json })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<'a> ExactSizeIterator for PropertyTables<'a> {}
+impl<'a> Iterator for PropertyTables<'a> {
+    type Item = PropertyTable<'a>;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|(index, json)| PropertyTable::new(self.gltf, index, json))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<'a> ExactSizeIterator for Properties<'a> {}
+impl<'a> Iterator for Properties<'a> {
+    type Item = Property<'a>;
+    fn next(&mut self) -> Option<Self::Item> {
+        let table = &self.table;
+        self.iter.next().map(|(id, json)| Property { table: table.clone(), id, json })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
@@ -0,0 +1,454 @@
+use std::collections::HashSet;
+use std::fmt::{self, Write};
+
+use image;
+use mesh::{Mode, Primitive};
+use {Accessor, Gltf, Mesh, Node, Texture};
+
+/// Returns the name of an object formatted for display, with a leading space,
+/// or an empty string if the object is unnamed.
+#[cfg(feature = "names")]
+macro_rules! name {
+    ($object:expr) => {
+        match $object.name() {
+            Some(name) => format!(" {:?}", name),
+            None => String::new(),
+        }
+    };
+}
+
+/// Returns the name of an object formatted for display, with a leading space,
+/// or an empty string if the object is unnamed.
+#[cfg(not(feature = "names"))]
+macro_rules! name {
+    ($object:expr) => {
+        String::new()
+    };
+}
+
+fn indent(out: &mut String, depth: usize) {
+    for _ in 0..depth {
+        out.push_str("  ");
+    }
+}
+
+fn print_mesh(out: &mut String, mesh: &Mesh, depth: usize) {
+    indent(out, depth);
+    let _ = writeln!(out, "Mesh {}{}", mesh.index(), name!(mesh));
+    for primitive in mesh.primitives() {
+        indent(out, depth + 1);
+        let _ = write!(
+            out,
+            "Primitive {}: {:?}, {} attributes",
+            primitive.index(),
+            primitive.mode(),
+            primitive.attributes().count(),
+        );
+        if let Some(indices) = primitive.indices() {
+            let _ = write!(out, ", {} indices", indices.count());
+        }
+        let material = primitive.material();
+        match material.index() {
+            Some(index) => {
+                let _ = writeln!(out, ", material {}{}", index, name!(material));
+            },
+            None => {
+                let _ = writeln!(out, ", default material");
+            },
+        }
+    }
+}
+
+fn print_node(out: &mut String, node: &Node, depth: usize) {
+    indent(out, depth);
+    let _ = write!(out, "Node {}{}", node.index(), name!(node));
+    let mut markers = vec![];
+    if let Some(mesh) = node.mesh() {
+        markers.push(format!("mesh {}", mesh.index()));
+    }
+    if let Some(camera) = node.camera() {
+        markers.push(format!("camera {}", camera.index()));
+    }
+    if let Some(skin) = node.skin() {
+        markers.push(format!("skin {}", skin.index()));
+    }
+    if markers.is_empty() {
+        out.push('\n');
+    } else {
+        let _ = writeln!(out, " [{}]", markers.join(", "));
+    }
+    if let Some(mesh) = node.mesh() {
+        print_mesh(out, &mesh, depth + 1);
+    }
+    for child in node.children() {
+        print_node(out, &child, depth + 1);
+    }
+}
+
+/// Returns an indented text representation of the node hierarchy of every
+/// scene, including mesh, camera, and skin references and a summary of each
+/// mesh primitive.
+///
+/// If the asset has no scenes then every root node is printed instead.
+///
+/// # Examples
+///
+/// ```
+/// # use std::{fs, io};
+/// let file = fs::File::open("examples/Box.gltf").unwrap();
+/// let gltf = gltf::Gltf::from_reader(io::BufReader::new(file))
+///     .unwrap()
+///     .validate_minimally()
+///     .unwrap();
+/// println!("{}", gltf::print::tree(&gltf));
+/// ```
+pub fn tree(gltf: &Gltf) -> String {
+    let mut out = String::new();
+    if gltf.scenes().len() == 0 {
+        let mut is_child = vec![false; gltf.nodes().len()];
+        for node in gltf.nodes() {
+            for child in node.children() {
+                is_child[child.index()] = true;
+            }
+        }
+        for node in gltf.nodes().filter(|node| !is_child[node.index()]) {
+            print_node(&mut out, &node, 0);
+        }
+    }
+    for scene in gltf.scenes() {
+        let _ = writeln!(out, "Scene {}{}", scene.index(), name!(scene));
+        for node in scene.nodes() {
+            print_node(&mut out, &node, 1);
+        }
+    }
+    out
+}
+
+/// Object counts and rendered geometry totals of an asset.
+///
+/// Returned by `stats`.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Stats {
+    /// The number of scenes.
+    pub scenes: usize,
+
+    /// The number of nodes.
+    pub nodes: usize,
+
+    /// The number of meshes.
+    pub meshes: usize,
+
+    /// The number of primitives across every mesh.
+    pub primitives: usize,
+
+    /// The number of materials.
+    pub materials: usize,
+
+    /// The number of textures.
+    pub textures: usize,
+
+    /// The number of images.
+    pub images: usize,
+
+    /// The number of animations.
+    pub animations: usize,
+
+    /// The number of skins.
+    pub skins: usize,
+
+    /// The number of cameras.
+    pub cameras: usize,
+
+    /// The number of primitives drawn when every scene is rendered, counting
+    /// each node instantiating a mesh separately.
+    pub draws: usize,
+
+    /// The number of vertices drawn when every scene is rendered.
+    pub vertices: usize,
+
+    /// The number of triangles drawn when every scene is rendered.
+    ///
+    /// Primitives with a point or line mode contribute no triangles.
+    pub triangles: usize,
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "scenes:     {}", self.scenes)?;
+        writeln!(f, "nodes:      {}", self.nodes)?;
+        writeln!(f, "meshes:     {}", self.meshes)?;
+        writeln!(f, "primitives: {}", self.primitives)?;
+        writeln!(f, "materials:  {}", self.materials)?;
+        writeln!(f, "textures:   {}", self.textures)?;
+        writeln!(f, "images:     {}", self.images)?;
+        writeln!(f, "animations: {}", self.animations)?;
+        writeln!(f, "skins:      {}", self.skins)?;
+        writeln!(f, "cameras:    {}", self.cameras)?;
+        writeln!(f, "draws:      {}", self.draws)?;
+        writeln!(f, "vertices:   {}", self.vertices)?;
+        writeln!(f, "triangles:  {}", self.triangles)
+    }
+}
+
+/// Returns the number of triangles a primitive draws.
+fn triangle_count(primitive: &Primitive) -> usize {
+    let count = match primitive.indices() {
+        Some(indices) => indices.count(),
+        None => primitive.vertex_attribute_counts().vertex_count().unwrap_or(0),
+    };
+    match primitive.mode() {
+        Mode::Triangles => count / 3,
+        Mode::TriangleStrip | Mode::TriangleFan => count.saturating_sub(2),
+        _ => 0,
+    }
+}
+
+fn count_node(stats: &mut Stats, node: &Node) {
+    if let Some(mesh) = node.mesh() {
+        for primitive in mesh.primitives() {
+            stats.draws += 1;
+            stats.vertices += primitive.vertex_attribute_counts().vertex_count().unwrap_or(0);
+            stats.triangles += triangle_count(&primitive);
+        }
+    }
+    for child in node.children() {
+        count_node(stats, &child);
+    }
+}
+
+/// Returns the object counts of an asset and the amount of geometry drawn by
+/// its scenes.
+///
+/// The `Display` implementation of the result prints one count per line.
+///
+/// # Examples
+///
+/// ```
+/// # use std::{fs, io};
+/// let file = fs::File::open("examples/Box.gltf").unwrap();
+/// let gltf = gltf::Gltf::from_reader(io::BufReader::new(file))
+///     .unwrap()
+///     .validate_minimally()
+///     .unwrap();
+/// let stats = gltf::print::stats(&gltf);
+/// assert_eq!(1, stats.draws);
+/// assert_eq!(12, stats.triangles);
+/// ```
+pub fn stats(gltf: &Gltf) -> Stats {
+    let mut stats = Stats {
+        scenes: gltf.scenes().len(),
+        nodes: gltf.nodes().len(),
+        meshes: gltf.meshes().len(),
+        primitives: gltf.primitives().count(),
+        materials: gltf.materials().len(),
+        textures: gltf.textures().len(),
+        images: gltf.images().len(),
+        animations: gltf.animations().len(),
+        skins: gltf.skins().len(),
+        cameras: gltf.cameras().len(),
+        ..Stats::default()
+    };
+    for scene in gltf.scenes() {
+        for node in scene.nodes() {
+            count_node(&mut stats, &node);
+        }
+    }
+    stats
+}
+
+/// Accumulates the vertices and edges of a DOT graph.
+#[derive(Default)]
+struct Graph {
+    /// Vertex declarations in insertion order.
+    vertices: Vec<String>,
+
+    /// Edge declarations in insertion order.
+    edges: Vec<String>,
+
+    /// Every edge added so far, used to avoid duplicates.
+    seen: HashSet<(String, String)>,
+}
+
+impl Graph {
+    fn vertex(&mut self, id: String, label: String) {
+        let label = label.replace('\\', "\\\\").replace('"', "\\\"");
+        self.vertices.push(format!("    {} [label=\"{}\"];", id, label));
+    }
+
+    fn edge(&mut self, from: String, to: String) {
+        if self.seen.insert((from.clone(), to.clone())) {
+            self.edges.push(format!("    {} -> {};", from, to));
+        }
+    }
+
+    fn accessor(&mut self, from: String, accessor: &Accessor) {
+        self.edge(from, format!("accessor{}", accessor.index()));
+    }
+
+    fn texture(&mut self, from: String, texture: &Texture) {
+        self.edge(from, format!("texture{}", texture.index()));
+    }
+}
+
+/// Returns the object reference graph of an asset in the GraphViz DOT format.
+///
+/// Every top-level object is a vertex and every reference from one object to
+/// another is an edge, e.g. from scenes to nodes, meshes to accessors, accessors
+/// to buffer views, buffer views to buffers, and materials to textures and
+/// images.
+///
+/// # Examples
+///
+/// ```
+/// # use std::{fs, io};
+/// let file = fs::File::open("examples/Box.gltf").unwrap();
+/// let gltf = gltf::Gltf::from_reader(io::BufReader::new(file))
+///     .unwrap()
+///     .validate_minimally()
+///     .unwrap();
+/// let dot = gltf::print::dot(&gltf);
+/// assert!(dot.contains("accessor0 -> view0;"));
+/// ```
+pub fn dot(gltf: &Gltf) -> String {
+    let mut graph = Graph::default();
+
+    for scene in gltf.scenes() {
+        let id = format!("scene{}", scene.index());
+        graph.vertex(id.clone(), format!("Scene {}{}", scene.index(), name!(scene)));
+        for node in scene.nodes() {
+            graph.edge(id.clone(), format!("node{}", node.index()));
+        }
+    }
+
+    for node in gltf.nodes() {
+        let id = format!("node{}", node.index());
+        graph.vertex(id.clone(), format!("Node {}{}", node.index(), name!(node)));
+        for child in node.children() {
+            graph.edge(id.clone(), format!("node{}", child.index()));
+        }
+        if let Some(mesh) = node.mesh() {
+            graph.edge(id.clone(), format!("mesh{}", mesh.index()));
+        }
+        if let Some(camera) = node.camera() {
+            graph.edge(id.clone(), format!("camera{}", camera.index()));
+        }
+        if let Some(skin) = node.skin() {
+            graph.edge(id.clone(), format!("skin{}", skin.index()));
+        }
+    }
+
+    for camera in gltf.cameras() {
+        let id = format!("camera{}", camera.index());
+        graph.vertex(id, format!("Camera {}{}", camera.index(), name!(camera)));
+    }
+
+    for mesh in gltf.meshes() {
+        let id = format!("mesh{}", mesh.index());
+        graph.vertex(id.clone(), format!("Mesh {}{}", mesh.index(), name!(mesh)));
+        for primitive in mesh.primitives() {
+            for (_, accessor) in primitive.attributes() {
+                graph.accessor(id.clone(), &accessor);
+            }
+            if let Some(accessor) = primitive.indices() {
+                graph.accessor(id.clone(), &accessor);
+            }
+            for target in primitive.morph_targets() {
+                let accessors = vec![target.positions(), target.normals(), target.tangents()];
+                for accessor in accessors.into_iter().flatten() {
+                    graph.accessor(id.clone(), &accessor);
+                }
+            }
+            if let Some(material) = primitive.material().index() {
+                graph.edge(id.clone(), format!("material{}", material));
+            }
+        }
+    }
+
+    for skin in gltf.skins() {
+        let id = format!("skin{}", skin.index());
+        graph.vertex(id.clone(), format!("Skin {}{}", skin.index(), name!(skin)));
+        if let Some(accessor) = skin.inverse_bind_matrices() {
+            graph.accessor(id.clone(), &accessor);
+        }
+        if let Some(skeleton) = skin.skeleton() {
+            graph.edge(id.clone(), format!("node{}", skeleton.index()));
+        }
+        for joint in skin.joints() {
+            graph.edge(id.clone(), format!("node{}", joint.index()));
+        }
+    }
+
+    for animation in gltf.animations() {
+        let id = format!("animation{}", animation.index());
+        graph.vertex(id.clone(), format!("Animation {}{}", animation.index(), name!(animation)));
+        for channel in animation.channels() {
+            let sampler = channel.sampler();
+            graph.accessor(id.clone(), &sampler.input());
+            graph.accessor(id.clone(), &sampler.output());
+            graph.edge(id.clone(), format!("node{}", channel.target().node().index()));
+        }
+    }
+
+    for accessor in gltf.accessors() {
+        let id = format!("accessor{}", accessor.index());
+        graph.vertex(id.clone(), format!("Accessor {}{}", accessor.index(), name!(accessor)));
+        if let Some(view) = accessor.view() {
+            graph.edge(id.clone(), format!("view{}", view.index()));
+        }
+        if let Some(sparse) = accessor.sparse() {
+            graph.edge(id.clone(), format!("view{}", sparse.indices().view().index()));
+            graph.edge(id.clone(), format!("view{}", sparse.values().view().index()));
+        }
+    }
+
+    for view in gltf.views() {
+        let id = format!("view{}", view.index());
+        graph.vertex(id.clone(), format!("View {}{}", view.index(), name!(view)));
+        graph.edge(id, format!("buffer{}", view.buffer().index()));
+    }
+
+    for buffer in gltf.buffers() {
+        let id = format!("buffer{}", buffer.index());
+        graph.vertex(id, format!("Buffer {}{}", buffer.index(), name!(buffer)));
+    }
+
+    for material in gltf.materials() {
+        let index = material.index().unwrap();
+        let id = format!("material{}", index);
+        graph.vertex(id.clone(), format!("Material {}{}", index, name!(material)));
+        for texture in material.textures() {
+            graph.texture(id.clone(), &texture.texture());
+        }
+    }
+
+    for texture in gltf.textures() {
+        let id = format!("texture{}", texture.index());
+        graph.vertex(id.clone(), format!("Texture {}{}", texture.index(), name!(texture)));
+        graph.edge(id.clone(), format!("image{}", texture.source().index()));
+        if let Some(sampler) = texture.sampler().index() {
+            graph.edge(id.clone(), format!("sampler{}", sampler));
+        }
+    }
+
+    for sampler in gltf.samplers() {
+        let index = sampler.index().unwrap();
+        let id = format!("sampler{}", index);
+        graph.vertex(id, format!("Sampler {}{}", index, name!(sampler)));
+    }
+
+    for image in gltf.images() {
+        let id = format!("image{}", image.index());
+        graph.vertex(id.clone(), format!("Image {}{}", image.index(), name!(image)));
+        if let image::Data::View { view, .. } = image.data() {
+            graph.edge(id, format!("view{}", view.index()));
+        }
+    }
+
+    let mut out = String::from("digraph gltf {\n");
+    for line in graph.vertices.iter().chain(graph.edges.iter()) {
+        out.push_str(line);
+        out.push('\n');
+    }
+    out.push_str("}\n");
+    out
+}
@@ -1,7 +1,7 @@
 use cgmath;
 use cgmath::prelude::*;
 use json;
-use std::{mem, slice};
+use std::slice;
 
 use {Camera, Gltf, Mesh, Skin};
 
@@ -9,6 +9,16 @@ type Matrix3 = cgmath::Matrix3<f32>;
 type Matrix4 = cgmath::Matrix4<f32>;
 type Quaternion = cgmath::Quaternion<f32>;
 
+/// Splits a column-major matrix into an array of columns.
+fn columns(m: [f32; 16]) -> [[f32; 4]; 4] {
+    [
+        [m[0], m[1], m[2], m[3]],
+        [m[4], m[5], m[6], m[7]],
+        [m[8], m[9], m[10], m[11]],
+        [m[12], m[13], m[14], m[15]],
+    ]
+}
+
 /// 4x4 identity matrix.
 const IDENTITY: [f32; 16] = {
     [1.0, 0.0, 0.0, 0.0,
@@ -17,6 +27,22 @@ const IDENTITY: [f32; 16] = {
      0.0, 0.0, 0.0, 1.0]
 };
 
+/// Name suffixes marking collision geometry, as used by Blender, Godot, and
+/// other exporters. Matching is case-insensitive.
+pub const COLLISION_SUFFIXES: &'static [&'static str] = &[
+    "_collider",
+    "_collision",
+    "-col",
+    "-colonly",
+];
+
+/// Name prefixes marking collision geometry, as used by Unreal Engine.
+/// Matching is case-sensitive.
+pub const COLLISION_PREFIXES: &'static [&'static str] = &["UCX_", "UBX_", "UCP_", "USP_"];
+
+/// Keys of `extras` marking collision geometry when set to `true`.
+pub const COLLISION_EXTRAS: &'static [&'static str] = &["collider", "collision"];
+
 /// The transform for a `Node`.
 #[derive(Clone, Debug)]
 pub enum Transform {
@@ -85,6 +111,51 @@ impl Transform {
             },
         }
     }
+
+    /// Returns the matrix transforming normal vectors, which is the inverse
+    /// transpose of the upper 3x3 part of the transform, in column-major order.
+    ///
+    /// Transformed normals keep facing away from the surface even when the
+    /// transform mirrors geometry, but must be renormalized. Singular
+    /// transforms, such as those with a zero scale, collapse normals to zero.
+    pub fn normal_matrix(self) -> [[f32; 3]; 3] {
+        normal_matrix(&Matrix4::from(self.matrix()))
+    }
+}
+
+/// Returns the inverse transpose of the upper 3x3 part of a matrix.
+fn normal_matrix(m: &Matrix4) -> [[f32; 3]; 3] {
+    let upper = Matrix3::from_cols(m.x.truncate(), m.y.truncate(), m.z.truncate());
+    upper
+        .invert()
+        .map(|inverse| inverse.transpose().into())
+        .unwrap_or([[0.0; 3]; 3])
+}
+
+/// The world transform of a node, as visited by `Scene::walk`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct World {
+    /// The 4x4 column-major matrix transforming from node space to world
+    /// space.
+    pub matrix: [[f32; 4]; 4],
+
+    /// The inverse transpose of the upper 3x3 part of `matrix`, for
+    /// transforming normal vectors. See `Transform::normal_matrix`.
+    pub normal_matrix: [[f32; 3]; 3],
+}
+
+impl World {
+    /// Returns whether the transform mirrors geometry, in which case the
+    /// winding order of its triangles must be reversed for back-face culling.
+    pub fn is_mirrored(&self) -> bool {
+        let m = &self.matrix;
+        let upper = Matrix3::new(
+            m[0][0], m[0][1], m[0][2],
+            m[1][0], m[1][1], m[1][2],
+            m[2][0], m[2][1], m[2][2],
+        );
+        upper.determinant() < 0.0
+    }
 }
 
 /// A node in the node hierarchy.
@@ -127,6 +198,34 @@ pub struct Nodes<'a> {
     iter: slice::Iter<'a, json::Index<json::scene::Node>>,
 }
 
+/// An `Iterator` that visits every node of a scene in depth-first order along
+/// with its world transform.
+#[derive(Clone, Debug)]
+pub struct Walk<'a> {
+    /// The parent `Gltf` struct.
+    gltf: &'a Gltf,
+
+    /// The nodes yet to be visited, with the world transforms of their
+    /// parents.
+    stack: Vec<(usize, Matrix4)>,
+
+    /// Whether each node has been visited, so that cyclic or shared children
+    /// are only visited once.
+    visited: Vec<bool>,
+}
+
+/// The nodes of a scene split into render and collision geometry, as returned
+/// by `Scene::split_collision`.
+#[derive(Clone, Debug)]
+pub struct CollisionSplit<'a> {
+    /// The nodes to render, with their world transforms, in depth-first order.
+    pub render: Vec<(Node<'a>, World)>,
+
+    /// The collision nodes and their descendants, with their world
+    /// transforms, in depth-first order.
+    pub collision: Vec<(Node<'a>, World)>,
+}
+
 /// An `Iterator` that visits the children of a node.
 #[derive(Clone, Debug)]
 pub struct Children<'a> {
@@ -182,6 +281,57 @@ impl<'a> Node<'a> {
         &self.json.extras
     }
 
+    /// Blender custom properties stored in `extras`.
+    #[cfg(feature = "extras")]
+    pub fn custom_properties(&self) -> json::extras::Properties {
+        json::extras::properties(&self.json.extras)
+    }
+
+    /// Returns `true` if the node holds collision geometry by a common
+    /// convention rather than geometry to render.
+    ///
+    /// With the `names` feature, nodes whose name ends with one of the
+    /// `COLLISION_SUFFIXES`, e.g. `"Wall_collider"`, or starts with one of the
+    /// `COLLISION_PREFIXES` match. With the `extras` feature, nodes whose
+    /// `extras` set one of the `COLLISION_EXTRAS` keys to `true`, e.g.
+    /// `{ "collider": true }`, match too. Without either feature no node
+    /// matches.
+    pub fn is_collision(&self) -> bool {
+        self.has_collision_name() || self.has_collision_extras()
+    }
+
+    #[cfg(feature = "names")]
+    fn has_collision_name(&self) -> bool {
+        match self.json.name {
+            Some(ref name) => {
+                let lowercase = name.to_lowercase();
+                COLLISION_SUFFIXES.iter().any(|suffix| lowercase.ends_with(suffix))
+                    || COLLISION_PREFIXES.iter().any(|prefix| name.starts_with(prefix))
+            },
+            None => false,
+        }
+    }
+
+    #[cfg(not(feature = "names"))]
+    fn has_collision_name(&self) -> bool {
+        false
+    }
+
+    #[cfg(feature = "extras")]
+    fn has_collision_extras(&self) -> bool {
+        match self.json.extras {
+            Some(json::Value::Object(ref extras)) => COLLISION_EXTRAS
+                .iter()
+                .any(|key| extras.get(*key) == Some(&json::Value::Bool(true))),
+            _ => false,
+        }
+    }
+
+    #[cfg(not(feature = "extras"))]
+    fn has_collision_extras(&self) -> bool {
+        false
+    }
+
     /// Returns the 4x4 column-major transformation matrix.
     #[deprecated(since = "0.9.1", note = "Use `transform().matrix()` instead")]
     pub fn matrix(&self) -> [f32; 16] {
@@ -223,10 +373,8 @@ impl<'a> Node<'a> {
     /// Returns the node's transform.
     pub fn transform(&self) -> Transform {
         if let Some(matrix) = self.json.matrix {
-            unsafe {
-                Transform::Matrix {
-                    matrix: mem::transmute(matrix),
-                }
+            Transform::Matrix {
+                matrix: columns(matrix),
             }
         } else {
             Transform::Decomposed {
@@ -237,6 +385,25 @@ impl<'a> Node<'a> {
         }
     }
 
+    /// Returns the positional audio emitter placed at this node, from the
+    /// `KHR_audio` extension.
+    #[cfg(feature = "audio")]
+    pub fn audio_emitter(&self) -> Option<::audio::Emitter<'a>> {
+        self.json.extensions.audio
+            .as_ref()
+            .map(|ext| ::audio::Emitter::from_index(self.gltf, &ext.emitter))
+    }
+
+    /// Returns the rigid body, collider, trigger, and joint of this node, from
+    /// the `KHR_physics_rigid_bodies` extension.
+    ///
+    /// Shapes, physics materials, collision filters, and joint definitions are
+    /// indices into `Gltf::implicit_shapes` and `Gltf::physics`.
+    #[cfg(feature = "physics")]
+    pub fn physics(&self) -> Option<&json::extensions::physics::RigidBody> {
+        self.json.extensions.physics.as_ref()
+    }
+
     /// Returns the skin referenced by this node.
     pub fn skin(&self) -> Option<Skin> {
         self.json.skin.as_ref().map(|index| {
@@ -280,12 +447,37 @@ impl<'a> Scene<'a> {
         &self.json.extras
     }
 
+    /// Returns the image based lighting environment stored in the `extras` of
+    /// the scene, or else in the `extras` of the root object.
+    ///
+    /// Malformed environments are ignored. See `json::extras::Environment`.
+    #[cfg(feature = "extras")]
+    pub fn environment(&self) -> Option<json::extras::Environment> {
+        json::extras::environment(&self.json.extras)
+            .ok()
+            .and_then(|environment| environment)
+            .or_else(|| json::extras::environment(&self.gltf.as_json().extras).ok()?)
+    }
+
     /// Optional user-defined name for this object.
     #[cfg(feature = "names")]
     pub fn name(&self) -> Option<&str> {
         self.json.name.as_ref().map(String::as_str)
     }
 
+    /// Returns the global audio emitters of the scene, from the `KHR_audio`
+    /// extension.
+    #[cfg(feature = "audio")]
+    pub fn audio_emitters(&self) -> Vec<::audio::Emitter<'a>> {
+        self.json.extensions.audio
+            .as_ref()
+            .map(|ext| &ext.emitters[..])
+            .unwrap_or(&[])
+            .iter()
+            .map(|index| ::audio::Emitter::from_index(self.gltf, index))
+            .collect()
+    }
+
     /// Returns an `Iterator` that visits each root node of the scene.
     pub fn nodes(&self) -> Nodes<'a> {
         Nodes {
@@ -293,6 +485,91 @@ impl<'a> Scene<'a> {
             iter: self.json.nodes.iter(),
         }
     }
+
+    /// Returns an `Iterator` that visits every node of the scene in depth-first
+    /// order, each before its children, along with its world transform and
+    /// normal matrix.
+    ///
+    /// Each node is visited at most once, so invalid assets whose node graph
+    /// has cycles, which minimal validation does not detect, still terminate.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn run() -> Result<(), Box<std::error::Error>> {
+    /// # let gltf = gltf::Gltf::from_slice(&std::fs::read("examples/Box.gltf")?)?
+    /// #     .validate_minimally()?;
+    /// for scene in gltf.scenes() {
+    ///     for (node, world) in scene.walk() {
+    ///         if world.is_mirrored() {
+    ///             println!("node {} reverses the winding order", node.index());
+    ///         }
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// # fn main() {
+    /// #     run().unwrap();
+    /// # }
+    /// ```
+    pub fn walk(&self) -> Walk<'a> {
+        let stack = self.json.nodes
+            .iter()
+            .rev()
+            .map(|index| (index.value(), Matrix4::identity()))
+            .collect();
+        Walk {
+            gltf: self.gltf,
+            stack: stack,
+            visited: vec![false; self.gltf.as_json().nodes.len()],
+        }
+    }
+
+    /// Splits the nodes of the scene into render and collision geometry.
+    ///
+    /// Nodes for which `Node::is_collision` returns `true` and all their
+    /// descendants are collision geometry; every other node is render
+    /// geometry. As with `Scene::walk`, each node is visited at most once.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn run() -> Result<(), Box<std::error::Error>> {
+    /// # let gltf = gltf::Gltf::from_slice(&std::fs::read("examples/Box.gltf")?)?
+    /// #     .validate_minimally()?;
+    /// for scene in gltf.scenes() {
+    ///     let split = scene.split_collision();
+    ///     println!("{} collision nodes", split.collision.len());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// # fn main() {
+    /// #     run().unwrap();
+    /// # }
+    /// ```
+    pub fn split_collision(&self) -> CollisionSplit<'a> {
+        let nodes = &self.gltf.as_json().nodes;
+        let mut collision = vec![false; nodes.len()];
+        let mut visited = vec![false; nodes.len()];
+        let mut stack: Vec<(usize, bool)> = self.json.nodes
+            .iter()
+            .map(|index| (index.value(), false))
+            .collect();
+        while let Some((index, parent)) = stack.pop() {
+            if visited[index] {
+                continue;
+            }
+            visited[index] = true;
+            let node = Node::new(self.gltf, index, &nodes[index]);
+            let is_collision = parent || node.is_collision();
+            collision[index] = is_collision;
+            for child in node.json.children.iter().flat_map(|children| children.iter()) {
+                stack.push((child.value(), is_collision));
+            }
+        }
+        let (collision, render) = self.walk().partition(|&(ref node, _)| collision[node.index()]);
+        CollisionSplit { render, collision }
+    }
 }
 
 impl<'a> ExactSizeIterator for Nodes<'a> {}
@@ -309,6 +586,29 @@ impl<'a> Iterator for Nodes<'a> {
     }
 }
 
+impl<'a> Iterator for Walk<'a> {
+    type Item = (Node<'a>, World);
+    fn next(&mut self) -> Option<Self::Item> {
+        let (index, parent) = loop {
+            let (index, parent) = self.stack.pop()?;
+            if !self.visited[index] {
+                break (index, parent);
+            }
+        };
+        self.visited[index] = true;
+        let node = Node::new(self.gltf, index, &self.gltf.as_json().nodes[index]);
+        let matrix = parent * Matrix4::from(node.transform().matrix());
+        for child in node.json.children.iter().flat_map(|children| children.iter().rev()) {
+            self.stack.push((child.value(), matrix));
+        }
+        let world = World {
+            matrix: matrix.into(),
+            normal_matrix: normal_matrix(&matrix),
+        };
+        Some((node, world))
+    }
+}
+
 impl<'a> ExactSizeIterator for Children<'a> {}
 impl<'a> Iterator for Children<'a> {
     type Item = Node<'a>;
@@ -658,4 +958,29 @@ mod tests {
         let scale = [10.0, 0.1, -0.1];
         test_decompose_scale(scale);
     }
+
+    #[test]
+    fn normal_matrix() {
+        let translation = [1.0, 2.0, 3.0];
+        let rotation = [0.0, 0.0, 0.0, 1.0];
+        let scale = [2.0, -1.0, 4.0];
+        let transform = Transform::Decomposed { translation, rotation, scale };
+        let expected = [[0.5, 0.0, 0.0], [0.0, -1.0, 0.0], [0.0, 0.0, 0.25]];
+        assert_eq!(expected, transform.normal_matrix());
+
+        let rotation = rotate(0.0, 0.0, 1.0, PI / 2.0);
+        let scale = [1.0, 1.0, 1.0];
+        let transform = Transform::Decomposed { translation, rotation, scale };
+        let n = transform.clone().normal_matrix();
+        let m = transform.matrix();
+        for i in 0..3 {
+            for j in 0..3 {
+                assert_relative_eq!(m[i][j], n[i][j], epsilon = 1e-6);
+            }
+        }
+
+        let scale = [0.0, 1.0, 1.0];
+        let transform = Transform::Decomposed { translation, rotation, scale };
+        assert_eq!([[0.0; 3]; 3], transform.normal_matrix());
+    }
 }
@@ -1,11 +1,7 @@
-use {image, json, Gltf};
+use {image, json, material, Gltf};
 
 pub use json::texture::{MagFilter, MinFilter, WrappingMode};
 
-lazy_static! {
-    static ref DEFAULT_SAMPLER: json::texture::Sampler = Default::default();
-}
-
 /// A reference to a `Texture`.
 #[derive(Clone, Debug)]
 pub struct Info<'a> {
@@ -62,7 +58,7 @@ impl<'a> Sampler<'a> {
         Self {
             gltf: gltf,
             index: None,
-            json: &DEFAULT_SAMPLER,
+            json: gltf.default_sampler(),
         }
     }
 
@@ -105,6 +101,29 @@ impl<'a> Sampler<'a> {
         self.json.wrap_t.unwrap()
     }
 
+    /// Maximum degree of anisotropic filtering, if hinted.
+    ///
+    /// The hint is read from the `EXT_texture_filter_anisotropic` extension
+    /// or, with the `extras` feature, from a numeric `anisotropy` property of
+    /// the sampler `extras`.
+    pub fn anisotropy(&self) -> Option<f32> {
+        let extension = self.json.extensions.texture_filter_anisotropic.as_ref();
+        extension.map(|ext| ext.anisotropy).or_else(|| self.extras_anisotropy())
+    }
+
+    #[cfg(feature = "extras")]
+    fn extras_anisotropy(&self) -> Option<f32> {
+        self.json.extras.as_ref()
+            .and_then(|extras| extras.get("anisotropy"))
+            .and_then(|value| value.as_f64())
+            .map(|value| value as f32)
+    }
+
+    #[cfg(not(feature = "extras"))]
+    fn extras_anisotropy(&self) -> Option<f32> {
+        None
+    }
+
     /// Optional application specific data.
     pub fn extras(&self) -> &json::Extras {
         &self.json.extras
@@ -155,6 +174,26 @@ impl<'a> Texture<'a> {
         self.gltf.images().nth(self.json.source.value() as usize).unwrap()
     }
 
+    /// Returns the material slots referencing this texture, in `Slot` order
+    /// and without duplicates.
+    ///
+    /// A texture referenced by both color and non-color slots, e.g. base color
+    /// and normal, needs separate sRGB and linear uploads; see
+    /// `Slot::is_srgb`. Images may also be shared between textures; see
+    /// `Image::usages`.
+    pub fn usages(&self) -> Vec<material::Slot> {
+        let mut slots = vec![];
+        for material in self.gltf.materials() {
+            for texture in material.textures() {
+                if texture.texture().index() == self.index && !slots.contains(&texture.slot()) {
+                    slots.push(texture.slot());
+                }
+            }
+        }
+        slots.sort();
+        slots
+    }
+
     /// Optional application specific data.
     pub fn extras(&self) -> &json::Extras {
         &self.json.extras
@@ -0,0 +1,65 @@
+extern crate gltf;
+
+use std::{fs, io};
+
+fn load(path: &str) -> gltf::Gltf {
+    let file = fs::File::open(path).unwrap();
+    gltf::Gltf::from_reader(io::BufReader::new(file))
+        .unwrap()
+        .validate_minimally()
+        .unwrap()
+}
+
+#[test]
+fn test_tree() {
+    let gltf = load("examples/Box.gltf");
+    let expected = if cfg!(feature = "names") {
+        "Scene 0\n\
+         \x20 Node 0\n\
+         \x20   Node 1 [mesh 0]\n\
+         \x20     Mesh 0 \"Mesh\"\n\
+         \x20       Primitive 0: Triangles, 2 attributes, 36 indices, material 0 \"Red\"\n"
+    } else {
+        "Scene 0\n\
+         \x20 Node 0\n\
+         \x20   Node 1 [mesh 0]\n\
+         \x20     Mesh 0\n\
+         \x20       Primitive 0: Triangles, 2 attributes, 36 indices, material 0\n"
+    };
+    assert_eq!(expected, gltf::print::tree(&gltf));
+}
+
+#[test]
+fn test_dot() {
+    let gltf = load("examples/Box.gltf");
+    let dot = gltf::print::dot(&gltf);
+    assert!(dot.starts_with("digraph gltf {\n"));
+    assert!(dot.ends_with("}\n"));
+    for edge in &[
+        "scene0 -> node0;",
+        "node0 -> node1;",
+        "node1 -> mesh0;",
+        "mesh0 -> accessor0;",
+        "mesh0 -> material0;",
+        "accessor1 -> view1;",
+        "accessor2 -> view1;",
+        "view1 -> buffer0;",
+    ] {
+        assert!(dot.contains(edge), "missing edge {}", edge);
+    }
+}
+
+#[test]
+fn test_stats() {
+    let gltf = load("examples/Box.gltf");
+    let stats = gltf::print::stats(&gltf);
+    assert_eq!(1, stats.scenes);
+    assert_eq!(2, stats.nodes);
+    assert_eq!(1, stats.meshes);
+    assert_eq!(1, stats.primitives);
+    assert_eq!(1, stats.materials);
+    assert_eq!(1, stats.draws);
+    assert_eq!(24, stats.vertices);
+    assert_eq!(12, stats.triangles);
+    assert!(stats.to_string().contains("triangles:  12\n"));
+}
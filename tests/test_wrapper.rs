@@ -21,3 +21,818 @@ fn test_accessor_bounds() {
 }
 
 
+
+#[test]
+fn test_view_accessors() {
+    let file = fs::File::open("examples/Box.gltf").unwrap();
+    let gltf = gltf::Gltf::from_reader(io::BufReader::new(file))
+        .unwrap()
+        .validate_minimally()
+        .unwrap();
+    let groups: Vec<Vec<usize>> = gltf.accessors_by_view()
+        .iter()
+        .map(|group| group.iter().map(|accessor| accessor.index()).collect())
+        .collect();
+    assert_eq!(vec![vec![0], vec![1, 2]], groups);
+
+    let view = gltf.views().nth(1).unwrap();
+    let accessors: Vec<usize> = view.accessors().map(|accessor| accessor.index()).collect();
+    assert_eq!(vec![1, 2], accessors);
+    assert_eq!(Some(gltf::buffer::Target::ArrayBuffer), view.target_hint());
+    assert_eq!(576, view.slice(&[0; 648]).len());
+}
+
+#[test]
+fn test_sampler_anisotropy() {
+    let json = r#"{
+        "asset": { "version": "2.0" },
+        "samplers": [
+            { "extensions": { "EXT_texture_filter_anisotropic": { "anisotropy": 8.0 } } },
+            {}
+        ]
+    }"#;
+    let gltf = gltf::Gltf::from_str(json).unwrap().validate_completely().unwrap();
+    let anisotropy: Vec<_> = gltf.samplers().map(|s| s.anisotropy()).collect();
+    assert_eq!(vec![Some(8.0), None], anisotropy);
+
+    let json = r#"{
+        "asset": { "version": "2.0" },
+        "samplers": [{ "extensions": { "EXT_texture_filter_anisotropic": { "anisotropy": 0.5 } } }]
+    }"#;
+    let errors = match gltf::Gltf::from_str(json).unwrap().validate_completely() {
+        Err(gltf::Error::Validation(errors)) => errors,
+        _ => panic!("expected a validation error"),
+    };
+    assert_eq!(
+        "samplers[0].extensions.EXT_texture_filter_anisotropic.anisotropy",
+        errors[0].0.as_str(),
+    );
+}
+
+#[test]
+fn test_asset() {
+    let json = r#"{
+        "asset": {
+            "version": "2.0",
+            "generator": "exporter",
+            "extras": { "metersPerUnit": 0.01, "upAxis": "Z" }
+        }
+    }"#;
+    let gltf = gltf::Gltf::from_str(json).unwrap().validate_completely().unwrap();
+    let asset = gltf.asset();
+    assert_eq!("2.0", asset.version());
+    assert_eq!(Some("exporter"), asset.generator());
+    assert_eq!(None, asset.copyright());
+    #[cfg(feature = "extras")]
+    {
+        assert_eq!(Some(0.01), asset.meters_per_unit());
+        assert_eq!(Some("Z"), asset.up_axis());
+    }
+}
+
+#[test]
+fn test_attribute_flags() {
+    use gltf::mesh::AttributeFlags;
+
+    let file = fs::File::open("examples/Box.gltf").unwrap();
+    let gltf = gltf::Gltf::from_reader(io::BufReader::new(file))
+        .unwrap()
+        .validate_minimally()
+        .unwrap();
+    let mesh = gltf.meshes().next().unwrap();
+    let primitive = mesh.primitives().next().unwrap();
+    assert_eq!(AttributeFlags::POSITION | AttributeFlags::NORMAL, primitive.attribute_flags());
+}
+
+#[test]
+fn test_vertex_attribute_counts() {
+    use gltf::Semantic;
+
+    let json = r#"{
+        "asset": { "version": "2.0" },
+        "buffers": [{ "byteLength": 60 }],
+        "bufferViews": [{ "buffer": 0, "byteLength": 60 }],
+        "accessors": [
+            {
+                "bufferView": 0, "componentType": 5126, "count": 3, "type": "VEC3",
+                "min": [0, 0, 0], "max": [1, 1, 1]
+            },
+            { "bufferView": 0, "componentType": 5126, "count": 2, "type": "VEC3" }
+        ],
+        "meshes": [{
+            "primitives": [{ "attributes": { "POSITION": 0, "NORMAL": 1 } }]
+        }]
+    }"#;
+    let gltf = gltf::Gltf::from_str(json).unwrap().validate_minimally().unwrap();
+    let mesh = gltf.meshes().next().unwrap();
+    let primitive = mesh.primitives().next().unwrap();
+    let counts = primitive.vertex_attribute_counts();
+    assert_eq!(vec![(Semantic::Normals, 2), (Semantic::Positions, 3)], counts.counts);
+    assert_eq!(Some(3), counts.vertex_count());
+    assert_eq!(vec![(Semantic::Normals, 2)], counts.mismatches());
+    assert!(!counts.is_consistent());
+}
+
+#[test]
+fn test_from_bytes() {
+    let glb = fs::read("examples/Box.glb").unwrap();
+    let (gltf, bin) = gltf::from_bytes(&glb).unwrap();
+    let gltf = gltf.validate_minimally().unwrap();
+    assert_eq!(1, gltf.meshes().count());
+    assert_eq!(Some(gltf.buffers().next().unwrap().length()), bin.map(|bin| bin.len()));
+
+    let mut json = b"\xEF\xBB\xBF".to_vec();
+    json.extend(fs::read("examples/Box.gltf").unwrap());
+    let (gltf, bin) = gltf::from_bytes(&json).unwrap();
+    assert_eq!(1, gltf.validate_minimally().unwrap().meshes().count());
+    assert!(bin.is_none());
+
+    assert!(gltf::from_bytes(b"glTF\x02\x00\x00\x00").is_err());
+}
+
+#[test]
+fn test_scene_walk() {
+    let json = r#"{
+        "asset": { "version": "2.0" },
+        "nodes": [
+            { "children": [1, 2], "translation": [1, 0, 0] },
+            { "scale": [-1, 1, 1], "children": [3] },
+            { "translation": [0, 1, 0] },
+            { "scale": [1, 2, 1] }
+        ],
+        "scenes": [{ "nodes": [0] }]
+    }"#;
+    let gltf = gltf::Gltf::from_str(json).unwrap().validate_minimally().unwrap();
+    let scene = gltf.scenes().next().unwrap();
+    let walked: Vec<_> = scene.walk().map(|(node, world)| (node.index(), world)).collect();
+    let order: Vec<_> = walked.iter().map(|&(index, _)| index).collect();
+    assert_eq!(vec![0, 1, 3, 2], order);
+    let mirrored: Vec<_> = walked.iter().map(|&(_, world)| world.is_mirrored()).collect();
+    assert_eq!(vec![false, true, true, false], mirrored);
+
+    let world = walked[2].1;
+    assert_eq!([1.0, 0.0, 0.0, 1.0], world.matrix[3]);
+    assert_eq!([[-1.0, 0.0, 0.0], [0.0, 0.5, 0.0], [0.0, 0.0, 1.0]], world.normal_matrix);
+    assert_eq!([1.0, 1.0, 0.0, 1.0], walked[3].1.matrix[3]);
+}
+
+#[test]
+fn test_scene_walk_cycle() {
+    let json = r#"{
+        "asset": { "version": "2.0" },
+        "nodes": [{ "children": [1] }, { "children": [0, 1] }],
+        "scenes": [{ "nodes": [0] }]
+    }"#;
+    let gltf = gltf::Gltf::from_str(json).unwrap().validate_minimally().unwrap();
+    let scene = gltf.scenes().next().unwrap();
+    let order: Vec<_> = scene.walk().map(|(node, _)| node.index()).collect();
+    assert_eq!(vec![0, 1], order);
+}
+
+#[cfg(all(feature = "names", feature = "extras"))]
+#[test]
+fn test_collision_split() {
+    let json = r#"{
+        "asset": { "version": "2.0" },
+        "nodes": [
+            { "name": "Wall", "children": [1, 2] },
+            { "name": "Wall_Collider", "children": [3] },
+            { "name": "Door", "extras": { "collider": true } },
+            { "name": "Box" },
+            { "name": "UCX_Floor" }
+        ],
+        "scenes": [{ "nodes": [0, 4] }]
+    }"#;
+    let gltf = gltf::Gltf::from_str(json).unwrap().validate_minimally().unwrap();
+    let flags: Vec<_> = gltf.nodes().map(|node| node.is_collision()).collect();
+    assert_eq!(vec![false, true, true, false, true], flags);
+
+    let split = gltf.scenes().next().unwrap().split_collision();
+    let render: Vec<_> = split.render.iter().map(|&(ref node, _)| node.index()).collect();
+    let collision: Vec<_> = split.collision.iter().map(|&(ref node, _)| node.index()).collect();
+    assert_eq!(vec![0], render);
+    assert_eq!(vec![1, 3, 2, 4], collision);
+}
+
+#[cfg(all(feature = "names", feature = "extras"))]
+#[test]
+fn test_collision_split_cycle() {
+    let json = r#"{
+        "asset": { "version": "2.0" },
+        "nodes": [
+            { "name": "Wall", "children": [1] },
+            { "name": "Wall_Collider", "children": [0] }
+        ],
+        "scenes": [{ "nodes": [0] }]
+    }"#;
+    let gltf = gltf::Gltf::from_str(json).unwrap().validate_minimally().unwrap();
+    let split = gltf.scenes().next().unwrap().split_collision();
+    assert_eq!(1, split.render.len());
+    assert_eq!(1, split.collision.len());
+}
+
+#[cfg(feature = "audio")]
+#[test]
+fn test_audio() {
+    use gltf::audio::Data;
+
+    let json = r#"{
+        "asset": { "version": "2.0" },
+        "extensionsUsed": ["KHR_audio"],
+        "buffers": [{ "byteLength": 1024 }],
+        "bufferViews": [{ "buffer": 0, "byteOffset": 256, "byteLength": 512 }],
+        "extensions": {
+            "KHR_audio": {
+                "audio": [
+                    { "bufferView": 0, "mimeType": "audio/mpeg" },
+                    { "uri": "wind.mp3" }
+                ],
+                "sources": [
+                    { "audio": 0, "autoPlay": true },
+                    { "audio": 1, "gain": 0.5, "loop": true }
+                ],
+                "emitters": [
+                    { "type": "global", "sources": [1] },
+                    {
+                        "type": "positional",
+                        "gain": 0.8,
+                        "sources": [0],
+                        "positional": { "coneInnerAngle": 0.5, "coneOuterAngle": 1.0 }
+                    }
+                ]
+            }
+        },
+        "scenes": [{ "nodes": [0], "extensions": { "KHR_audio": { "emitters": [0] } } }],
+        "nodes": [{ "extensions": { "KHR_audio": { "emitter": 1 } } }]
+    }"#;
+    let gltf = gltf::Gltf::from_str(json).unwrap().validate_completely().unwrap();
+    assert_eq!(2, gltf.audio_clips().len());
+
+    let scene = gltf.scenes().next().unwrap();
+    let global = scene.audio_emitters();
+    assert_eq!(1, global.len());
+    assert!(global[0].positional().is_none());
+    let looping = &global[0].sources()[0];
+    assert!(looping.looping() && !looping.auto_play());
+    assert_eq!(0.5, looping.gain());
+    match looping.clip().unwrap().data() {
+        Data::Uri { uri, mime_type } => assert_eq!(("wind.mp3", None), (uri, mime_type)),
+        Data::View { .. } => panic!("expected a URI"),
+    }
+
+    let emitter = scene.nodes().next().unwrap().audio_emitter().unwrap();
+    assert_eq!(1, emitter.index());
+    assert_eq!(0.8, emitter.gain());
+    let positional = emitter.positional().unwrap();
+    assert_eq!("inverse", positional.distance_model);
+    assert_eq!(1.0, positional.cone_outer_angle);
+    let source = &emitter.sources()[0];
+    assert!(source.auto_play());
+    match source.clip().unwrap().data() {
+        Data::View { view, mime_type } => {
+            assert_eq!((256, 512), (view.offset(), view.length()));
+            assert_eq!("audio/mpeg", mime_type);
+        },
+        Data::Uri { .. } => panic!("expected a buffer view"),
+    }
+
+    let invalid = json.replace(r#""emitter": 1"#, r#""emitter": 2"#);
+    assert!(gltf::Gltf::from_str(&invalid).unwrap().validate_minimally().is_err());
+    let invalid = json.replace(r#""mimeType": "audio/mpeg""#, r#""uri": "a.mp3""#);
+    assert!(gltf::Gltf::from_str(&invalid).unwrap().validate_completely().is_err());
+    let invalid = json.replace(r#""gain": 0.5"#, r#""gain": -0.5"#);
+    let errors = match gltf::Gltf::from_str(&invalid).unwrap().validate_completely() {
+        Err(gltf::Error::Validation(errors)) => errors,
+        _ => panic!("expected a validation error"),
+    };
+    assert_eq!("extensions.audio.sources[1].gain", errors[0].0.as_str());
+}
+
+#[cfg(feature = "physics")]
+#[test]
+fn test_physics() {
+    let json = r#"{
+        "asset": { "version": "2.0" },
+        "extensionsUsed": ["KHR_implicit_shapes", "KHR_physics_rigid_bodies"],
+        "extensions": {
+            "KHR_implicit_shapes": {
+                "shapes": [{ "type": "sphere", "sphere": { "radius": 2.0 } }]
+            },
+            "KHR_physics_rigid_bodies": {
+                "physicsMaterials": [{ "restitution": 0.5, "frictionCombine": "minimum" }],
+                "physicsJoints": [{
+                    "limits": [{ "linearAxes": [0, 1, 2], "min": 0, "max": 0 }]
+                }]
+            }
+        },
+        "nodes": [
+            {
+                "extensions": {
+                    "KHR_physics_rigid_bodies": {
+                        "motion": { "mass": 4.0 },
+                        "collider": { "geometry": { "shape": 0 }, "physicsMaterial": 0 }
+                    }
+                }
+            },
+            {
+                "extensions": {
+                    "KHR_physics_rigid_bodies": {
+                        "joint": { "connectedNode": 0, "joint": 0 }
+                    }
+                }
+            }
+        ]
+    }"#;
+    let gltf = gltf::Gltf::from_str(json).unwrap().validate_completely().unwrap();
+    let body = gltf.nodes().next().unwrap().physics().cloned().unwrap();
+    let motion = body.motion.unwrap();
+    assert_eq!(Some(4.0), motion.mass);
+    assert_eq!(1.0, motion.gravity_factor);
+    let collider = body.collider.unwrap();
+    let shape = &gltf.implicit_shapes()[collider.geometry.shape.unwrap().value()];
+    assert_eq!(2.0, shape.sphere.as_ref().unwrap().radius);
+    let physics = gltf.physics().unwrap();
+    let material = &physics.physics_materials[collider.physics_material.unwrap().value()];
+    assert_eq!(0.6, material.static_friction);
+    let joint = gltf.nodes().nth(1).unwrap().physics().unwrap().joint.clone().unwrap();
+    assert_eq!(0, joint.connected_node.value());
+    assert_eq!(3, physics.physics_joints[0].limits[0].linear_axes.len());
+
+    let invalid = json.replace(r#""shape": 0"#, r#""shape": 1"#);
+    assert!(gltf::Gltf::from_str(&invalid).unwrap().validate_minimally().is_err());
+    let invalid = json
+        .replace(r#""radius": 2.0"#, r#""radius": -2.0"#)
+        .replace(r#""restitution": 0.5"#, r#""restitution": -0.5"#);
+    let errors = match gltf::Gltf::from_str(&invalid).unwrap().validate_completely() {
+        Err(gltf::Error::Validation(errors)) => errors,
+        _ => panic!("expected a validation error"),
+    };
+    let paths: Vec<_> = errors.iter().map(|&(ref path, _)| path.as_str()).collect();
+    assert_eq!(
+        vec![
+            "extensions.implicitShapes.shapes[0].sphere.radius",
+            "extensions.physics.physicsMaterials[0].restitution",
+        ],
+        paths,
+    );
+}
+
+#[cfg(feature = "extras")]
+#[test]
+fn test_channel_material_target() {
+    let json = r#"{
+        "asset": { "version": "2.0" },
+        "buffers": [{ "byteLength": 8 }],
+        "bufferViews": [{ "buffer": 0, "byteLength": 8 }],
+        "accessors": [
+            { "bufferView": 0, "componentType": 5126, "count": 2, "type": "SCALAR" }
+        ],
+        "materials": [{}],
+        "nodes": [{}],
+        "animations": [{
+            "channels": [
+                {
+                    "sampler": 0,
+                    "target": {
+                        "node": 0,
+                        "path": "scale",
+                        "extras": { "targetPath": "/materials/0/emissiveFactor" }
+                    }
+                },
+                { "sampler": 0, "target": { "node": 0, "path": "scale" } }
+            ],
+            "samplers": [{ "input": 0, "output": 0 }]
+        }]
+    }"#;
+    let gltf = gltf::Gltf::from_str(json).unwrap().validate_completely().unwrap();
+    let animation = gltf.animations().next().unwrap();
+    let channels: Vec<_> = animation.channels().collect();
+    let (material, property) = channels[0].material_target().unwrap();
+    assert_eq!(Some(0), material.index());
+    assert_eq!("emissiveFactor", property);
+    assert_eq!(None, channels[1].pointer());
+}
+
+#[cfg(feature = "extras")]
+#[test]
+fn test_scene_environment() {
+    let json = r#"{
+        "asset": { "version": "2.0" },
+        "images": [{ "uri": "sky.png" }],
+        "scenes": [
+            { "nodes": [] },
+            { "nodes": [], "extras": { "environment": { "intensity": 0.5 } } }
+        ],
+        "extras": { "environment": { "specular": { "image": 0 } } }
+    }"#;
+    let gltf = gltf::Gltf::from_str(json).unwrap().validate_completely().unwrap();
+    let scenes: Vec<_> = gltf.scenes().collect();
+    let default = scenes[0].environment().unwrap();
+    assert_eq!(Some(0), default.specular.unwrap().image);
+    let own = scenes[1].environment().unwrap();
+    assert_eq!(0.5, own.intensity);
+    assert!(own.specular.is_none());
+}
+
+#[test]
+fn test_content_hash() {
+    let json = |attributes: &str, max: &str| format!(r#"{{
+        "asset": {{ "version": "2.0" }},
+        "buffers": [{{ "byteLength": 36 }}],
+        "bufferViews": [{{ "buffer": 0, "byteLength": 36 }}],
+        "accessors": [{{
+            "bufferView": 0, "componentType": 5126, "count": 3, "type": "VEC3",
+            "min": [0, 0, 0], "max": {}
+        }}],
+        "meshes": [{{ "primitives": [{{ "attributes": {} }}] }}]
+    }}"#, max, attributes);
+    let hash = |attributes: &str, max: &str| {
+        let gltf = gltf::Gltf::from_str(&json(attributes, max)).unwrap().skip_validation();
+        gltf.content_hash().unwrap()
+    };
+    let a = hash(r#"{ "POSITION": 0, "NORMAL": 0, "TEXCOORD_0": 0 }"#, "[1, 1, 0]");
+    let b = hash(r#"{ "TEXCOORD_0": 0, "POSITION": 0, "NORMAL": 0 }"#, "[1.0, 1e0, 0.0]");
+    assert_eq!(a, b);
+    assert_ne!(a, hash(r#"{ "POSITION": 0, "NORMAL": 0 }"#, "[1, 1, 0]"));
+    assert_ne!(a, hash(r#"{ "POSITION": 0, "NORMAL": 0, "TEXCOORD_0": 0 }"#, "[1, 2, 0]"));
+
+    let node = |node: &str| {
+        let json = format!(r#"{{ "asset": {{ "version": "2.0" }}, "nodes": [{}] }}"#, node);
+        gltf::Gltf::from_str(&json).unwrap().skip_validation().content_hash().unwrap()
+    };
+    let unknown = node(r#"{ "extensions": { "EXT_unknown": { "b": 1, "a": [2] } } }"#);
+    assert_eq!(unknown, node(r#"{ "extensions": { "EXT_unknown": { "a": [2], "b": 1 } } }"#));
+    assert_ne!(unknown, node("{}"));
+    // Float formatting is ignored outside accessor bounds too.
+    assert_eq!(unknown, node(r#"{ "extensions": { "EXT_unknown": { "b": 1.0, "a": [2e0] } } }"#));
+    assert_ne!(unknown, node(r#"{ "extensions": { "EXT_unknown": { "b": 1.5, "a": [2] } } }"#));
+
+    // Names discarded by a disabled `names` feature cannot be hashed.
+    let named = r#"{ "asset": { "version": "2.0" }, "nodes": [{ "name": "a" }] }"#;
+    let named = gltf::Gltf::from_str(named).unwrap().skip_validation().content_hash();
+    assert_eq!(cfg!(feature = "names"), named.is_ok());
+}
+
+#[test]
+fn test_clearcoat() {
+    let json = r#"{
+        "asset": { "version": "2.0" },
+        "images": [{ "uri": "normal.png" }],
+        "textures": [{ "source": 0 }],
+        "materials": [
+            {
+                "extensions": {
+                    "KHR_materials_clearcoat": {
+                        "clearcoatFactor": 1.0,
+                        "clearcoatNormalTexture": { "index": 0, "texCoord": 1, "scale": 0.5 }
+                    }
+                }
+            },
+            {}
+        ]
+    }"#;
+    let gltf = gltf::Gltf::from_str(json).unwrap().validate_completely().unwrap();
+    let clearcoat = gltf.materials().next().unwrap().clearcoat().unwrap();
+    assert_eq!(1.0, clearcoat.clearcoat_factor());
+    assert_eq!(0.0, clearcoat.clearcoat_roughness_factor());
+    assert!(clearcoat.clearcoat_texture().is_none());
+    let normal = clearcoat.clearcoat_normal_texture().unwrap();
+    assert_eq!((0, 1, 0.5), (normal.texture().index(), normal.tex_coord(), normal.scale()));
+    assert!(gltf.materials().nth(1).unwrap().clearcoat().is_none());
+
+    let json = r#"{
+        "asset": { "version": "2.0" },
+        "materials": [{
+            "extensions": {
+                "KHR_materials_clearcoat": { "clearcoatNormalTexture": { "index": 0 } }
+            }
+        }]
+    }"#;
+    let errors = match gltf::Gltf::from_str(json).unwrap().validate_minimally() {
+        Err(gltf::Error::Validation(errors)) => errors,
+        _ => panic!("expected a validation error"),
+    };
+    assert_eq!(
+        "materials[0].extensions.KHR_materials_clearcoat.clearcoatNormalTexture.index",
+        errors[0].0.as_str(),
+    );
+}
+
+#[test]
+fn test_material_textures() {
+    use gltf::material::Slot;
+
+    let json = r#"{
+        "asset": { "version": "2.0" },
+        "images": [{ "uri": "orm.png" }, { "uri": "normal.png" }],
+        "textures": [{ "source": 0 }, { "source": 1 }],
+        "materials": [{
+            "pbrMetallicRoughness": { "metallicRoughnessTexture": { "index": 0 } },
+            "occlusionTexture": { "index": 0 },
+            "normalTexture": { "index": 1, "texCoord": 1 },
+            "extensions": {
+                "KHR_materials_clearcoat": { "clearcoatNormalTexture": { "index": 1 } }
+            }
+        }]
+    }"#;
+    let gltf = gltf::Gltf::from_str(json).unwrap().validate_completely().unwrap();
+    let material = gltf.materials().next().unwrap();
+    let textures: Vec<_> = material.textures()
+        .map(|texture| (texture.slot(), texture.texture().index(), texture.tex_coord()))
+        .collect();
+    assert_eq!(
+        vec![
+            (Slot::MetallicRoughness, 0, 0),
+            (Slot::Normal, 1, 1),
+            (Slot::Occlusion, 0, 0),
+            (Slot::ClearcoatNormal, 1, 0),
+        ],
+        textures,
+    );
+    assert!(material.uses_texcoord_set(1));
+    assert!(!material.uses_texcoord_set(2));
+}
+
+#[test]
+fn test_texture_usages() {
+    use gltf::material::Slot;
+
+    let json = r#"{
+        "asset": { "version": "2.0" },
+        "images": [{ "uri": "a.png" }, { "uri": "b.png" }],
+        "textures": [{ "source": 0 }, { "source": 0 }, { "source": 1 }],
+        "materials": [
+            {
+                "emissiveTexture": { "index": 0 },
+                "normalTexture": { "index": 1 }
+            },
+            { "pbrMetallicRoughness": { "baseColorTexture": { "index": 0 } } }
+        ]
+    }"#;
+    let gltf = gltf::Gltf::from_str(json).unwrap().validate_completely().unwrap();
+    let textures: Vec<_> = gltf.textures().map(|texture| texture.usages()).collect();
+    assert_eq!(vec![vec![Slot::BaseColor, Slot::Emissive], vec![Slot::Normal], vec![]], textures);
+    assert!(textures[0].iter().all(Slot::is_srgb));
+
+    let images: Vec<_> = gltf.images().map(|image| image.usages()).collect();
+    assert_eq!(vec![Slot::BaseColor, Slot::Normal, Slot::Emissive], images[0]);
+    assert!(images[1].is_empty());
+}
+
+#[test]
+fn test_document_primitives() {
+    let json = r#"{
+        "asset": { "version": "2.0" },
+        "meshes": [
+            { "primitives": [{ "attributes": {} }, { "attributes": {} }] },
+            { "primitives": [] },
+            { "primitives": [{ "attributes": {} }] }
+        ],
+        "nodes": [{ "mesh": 2 }, {}, { "mesh": 0 }]
+    }"#;
+    let gltf = gltf::Gltf::from_str(json).unwrap().skip_validation();
+    let primitives: Vec<_> = gltf.primitives()
+        .map(|(mesh, primitive)| (mesh.index(), primitive.index()))
+        .collect();
+    assert_eq!(vec![(0, 0), (0, 1), (2, 0)], primitives);
+
+    let nodes: Vec<_> = gltf.nodes_with_meshes()
+        .map(|(node, mesh)| (node.index(), mesh.index()))
+        .collect();
+    assert_eq!(vec![(0, 2), (2, 0)], nodes);
+}
+
+#[cfg(feature = "names")]
+#[test]
+fn test_json_writer() {
+    let data = fs::read("examples/Box.gltf").unwrap();
+    let gltf = gltf::Gltf::from_slice(&data).unwrap().validate_completely().unwrap();
+    let written = gltf.to_string().unwrap();
+    let value: gltf::json::Value = gltf::json::from_str(&written).unwrap();
+    assert!(value["nodes"][0]["matrix"].is_array());
+    for property in &["translation", "rotation", "scale", "extensions"] {
+        assert!(value["nodes"][0].get(property).is_none());
+    }
+    assert!(value.get("extensions").is_none());
+    assert!(value["materials"][0].get("alphaCutoff").is_none());
+    assert!(value["materials"][0].get("extensions").is_none());
+
+    let reread = gltf::Gltf::from_str(&written).unwrap();
+    assert!(reread.validate_completely().is_ok());
+}
+
+#[cfg(all(feature = "names", feature = "extras"))]
+#[test]
+fn test_json_writer_keeps_unmodelled_data() {
+    let json = r#"{
+        "asset": { "version": "2.0" },
+        "extensionsUsed": ["KHR_lights_punctual"],
+        "extensionsRequired": ["KHR_lights_punctual"],
+        "extensions": { "KHR_lights_punctual": { "lights": [{ "type": "point" }] } },
+        "nodes": [{
+            "name": "Lamp",
+            "extras": { "tag": 1 },
+            "extensions": { "KHR_lights_punctual": { "light": 0 } }
+        }]
+    }"#;
+    let gltf = gltf::Gltf::from_str(json).unwrap().validate_completely().unwrap();
+    let value: gltf::json::Value = gltf::json::from_str(&gltf.to_string().unwrap()).unwrap();
+    assert_eq!("point", value["extensions"]["KHR_lights_punctual"]["lights"][0]["type"]);
+    assert_eq!("Lamp", value["nodes"][0]["name"]);
+    assert_eq!(1, value["nodes"][0]["extras"]["tag"]);
+    assert_eq!(0, value["nodes"][0]["extensions"]["KHR_lights_punctual"]["light"]);
+}
+
+#[cfg(not(feature = "names"))]
+#[test]
+fn test_json_writer_refuses_discarded_names() {
+    let json = r#"{"asset":{"version":"2.0"},"nodes":[{"name":"Lamp"}]}"#;
+    let gltf = gltf::Gltf::from_str(json).unwrap().validate_completely().unwrap();
+    match gltf.to_string() {
+        Err(gltf::Error::Serialize(_)) => {},
+        result => panic!("unexpected result {:?}", result.map(|_| ())),
+    }
+}
+
+#[test]
+fn test_glb_writer() {
+    let json = br#"{"asset":{"version":"2.0"},"buffers":[{"byteLength":5}] }"#;
+    assert_ne!(0, json.len() % 4);
+    let glb = gltf::Glb::new(json[..].into(), Some(vec![1, 2, 3, 4, 5].into())).unwrap();
+    let data = glb.to_vec().unwrap();
+    let json_length = (json.len() + 3) & !3;
+    assert_eq!(12 + 8 + json_length + 8 + 8, data.len());
+    assert_eq!(data.len() as u32, glb.header.length);
+    assert_eq!(b' ', data[12 + 8 + json_length - 1]);
+    assert_eq!(&[1, 2, 3, 4, 5, 0, 0, 0], &data[data.len() - 8..]);
+
+    let reread = gltf::Glb::from_slice(&data).unwrap();
+    assert_eq!(&data[20..20 + json_length], &*reread.json);
+    let gltf = gltf::Gltf::from_glb(&reread).unwrap().validate_completely().unwrap();
+    let written = gltf.to_glb(Some(&[1, 2, 3, 4, 5])).unwrap();
+    assert_eq!(&data[data.len() - 16..], &written[written.len() - 16..]);
+    assert!(gltf.to_glb(Some(&[1, 2])).is_err());
+    assert!(gltf.to_glb(Some(&[1, 2, 3, 4, 5, 6, 7, 8, 9])).is_err());
+}
+
+#[test]
+fn test_builder() {
+    use gltf::build::{MeshBuilder, NodeBuilder, PrimitiveBuilder, RootBuilder};
+
+    let mut builder = RootBuilder::new();
+    let material = builder.material(Default::default());
+    let primitive = PrimitiveBuilder::new()
+        .positions(&[[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 2.0, -1.0]])
+        .colors(0, &[[255u8, 0, 0], [0, 255, 0], [0, 0, 255]])
+        .indices(&[0u16, 1, 2])
+        .material(material);
+    let mesh = builder.mesh(MeshBuilder::new().primitive(primitive));
+    let child = builder.node(NodeBuilder::new().mesh(mesh));
+    let parent = builder.node(NodeBuilder::new().children(&[child]).scale([2.0; 3]));
+    builder.scene(&[parent]);
+    let (root, bin) = builder.build().unwrap();
+
+    assert_eq!(Some(0), root.scene.as_ref().map(|scene| scene.value()));
+    assert_eq!(36 + 12 + 8, bin.len());
+    let offsets: Vec<_> = root.buffer_views.iter().map(|view| view.byte_offset).collect();
+    assert_eq!(vec![0, 36, 48], offsets);
+    assert_eq!(Some(4), root.buffer_views[1].byte_stride.map(|stride| stride.0));
+    assert!(root.accessors[1].normalized);
+    assert_eq!(Some(gltf::json::Value::from(vec![1.0, 2.0, 0.0])), root.accessors[0].max);
+    assert_eq!(&[255, 0, 0, 0, 0, 255, 0, 0, 0, 0, 255], &bin[36..47]);
+
+    let gltf = gltf::Gltf::from_json(root).validate_completely().unwrap();
+    let node = gltf.nodes().nth(1).unwrap();
+    let child = node.children().next().unwrap();
+    let primitive = child.mesh().unwrap().primitives().next().unwrap();
+    assert_eq!(Some(0), primitive.material().index());
+    let bounds = Bounds { min: [0.0, 0.0, -1.0], max: [1.0, 2.0, 0.0] };
+    assert_eq!(Some(bounds), primitive.position_bounds());
+}
+
+#[test]
+fn test_builder_round_trip() {
+    use gltf::build::{MeshBuilder, NodeBuilder, PrimitiveBuilder, RootBuilder};
+
+    let positions = [[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 2.0, -1.0]];
+    let mut builder = RootBuilder::new();
+    let primitive = PrimitiveBuilder::new().positions(&positions).indices(&[0u32, 2, 1]);
+    let mesh = builder.mesh(MeshBuilder::new().primitive(primitive));
+    let node = builder.node(NodeBuilder::new().mesh(mesh));
+    builder.scene(&[node]);
+    let (root, bin) = builder.build().unwrap();
+    let glb = gltf::Gltf::from_json(root).validate_completely().unwrap().to_glb(Some(&bin));
+
+    let (gltf, reread) = gltf::from_bytes(&glb.unwrap()).unwrap();
+    let gltf = gltf.validate_completely().unwrap();
+    let reread = reread.unwrap();
+    let primitive = gltf.meshes().next().unwrap().primitives().next().unwrap();
+    let read = |accessor: gltf::Accessor| {
+        let view = accessor.view().unwrap();
+        let data = &reread[view.offset()..view.offset() + view.length()];
+        data.chunks(4)
+            .map(|bytes| [bytes[0], bytes[1], bytes[2], bytes[3]])
+            .collect::<Vec<_>>()
+    };
+    let read_positions: Vec<f32> = read(primitive.get(&gltf::Semantic::Positions).unwrap())
+        .into_iter()
+        .map(f32::from_le_bytes)
+        .collect();
+    let expected: Vec<f32> = positions.iter().flat_map(|p| p.iter().cloned()).collect();
+    assert_eq!(expected, read_positions);
+    let indices: Vec<u32> = read(primitive.indices().unwrap())
+        .into_iter()
+        .map(u32::from_le_bytes)
+        .collect();
+    assert_eq!(vec![0, 2, 1], indices);
+}
+
+#[test]
+fn test_edit() {
+    use gltf::json::validation::Error;
+    use gltf::Semantic;
+
+    let data = fs::read("examples/Box.gltf").unwrap();
+    let gltf = gltf::Gltf::from_slice(&data).unwrap().validate_completely().unwrap();
+    let mut edit = gltf.edit();
+    assert!(edit.node_mut(2).is_none());
+    edit.node_mut(1).unwrap().set_mesh(None).unwrap();
+    edit.node_mut(0).unwrap().set_mesh(Some(0)).unwrap();
+
+    {
+        let mut mesh = edit.mesh_mut(0).unwrap();
+        let copy = {
+            let mut primitive = mesh.primitive_mut(0).unwrap();
+            primitive.set_material(None).unwrap();
+            match primitive.set_attribute(Semantic::Positions, 3) {
+                Err(gltf::Error::Validation(errs)) => {
+                    assert_eq!(Error::IndexOutOfBounds, errs[0].1);
+                },
+                _ => panic!("expected a validation error"),
+            }
+            // Accessor 0 holds the `u16` indices.
+            match primitive.set_attribute(Semantic::Normals, 0) {
+                Err(gltf::Error::Validation(errs)) => {
+                    let path = "meshes[0].primitives[0].attributes[\"NORMAL\"]";
+                    assert_eq!(path, errs[0].0.as_str());
+                    assert_eq!(Error::Invalid, errs[0].1);
+                },
+                _ => panic!("expected a validation error"),
+            }
+            assert!(primitive.set_indices(Some(1)).is_err());
+            assert_eq!(Some(1), primitive.remove_attribute(&Semantic::Normals));
+            primitive.set_attribute(Semantic::Normals, 2).unwrap();
+            assert!(primitive.set_indices(Some(3)).is_err());
+            primitive.as_json().clone()
+        };
+        assert_eq!(1, mesh.push_primitive(copy).unwrap());
+        assert!(mesh.remove_primitive(2).is_err());
+        assert!(mesh.remove_primitive(1).is_ok());
+        match mesh.remove_primitive(0) {
+            Err(gltf::Error::Validation(errs)) => assert_eq!(Error::Invalid, errs[0].1),
+            _ => panic!("expected a validation error"),
+        }
+    }
+
+    assert!(edit.material_mut(1).is_none());
+    {
+        let mut material = edit.material_mut(0).unwrap();
+        material.set_alpha_mode(gltf::material::AlphaMode::Mask);
+        material.set_alpha_cutoff(0.25).unwrap();
+        assert!(material.set_alpha_cutoff(-1.0).is_err());
+        material.set_base_color_factor([1.0, 0.0, 0.0, 1.0]).unwrap();
+        assert!(material.set_emissive_factor([0.0, 2.0, 0.0]).is_err());
+        let texture = gltf::json::texture::Info::new(gltf::json::Index::new(0));
+        assert!(material.set_base_color_texture(Some(texture)).is_err());
+        material.set_double_sided(true);
+    }
+    assert!(edit.undo().is_some());
+    assert!(edit.redo().is_some());
+
+    // The edits are journaled and leave the original untouched.
+    use gltf::json::edit::Edit::{SetMaterial, SetNode};
+    match edit.as_document_edit().edits() {
+        [SetNode(1, _), .., SetMaterial(0, _)] => {},
+        edits => panic!("unexpected edits {:?}", edits),
+    }
+    assert_eq!(0, gltf.nodes().nth(1).unwrap().mesh().unwrap().index());
+
+    let edited = edit.commit();
+    assert!(edited.nodes().nth(1).unwrap().mesh().is_none());
+    assert_eq!(Some(0), edited.nodes().next().unwrap().mesh().map(|mesh| mesh.index()));
+    let mesh = edited.meshes().next().unwrap();
+    assert_eq!(1, mesh.primitives().count());
+    let primitive = mesh.primitives().next().unwrap();
+    assert_eq!(None, primitive.material().index());
+    assert_eq!(2, primitive.get(&Semantic::Positions).unwrap().index());
+    assert_eq!(2, primitive.get(&Semantic::Normals).unwrap().index());
+    let material = edited.materials().next().unwrap();
+    match material.alpha_mode() {
+        gltf::material::AlphaMode::Mask => {},
+        _ => panic!("expected the MASK alpha mode"),
+    }
+    assert_eq!(0.25, material.alpha_cutoff());
+    assert!(material.double_sided());
+    assert_eq!([1.0, 0.0, 0.0, 1.0], material.pbr_metallic_roughness().base_color_factor());
+    assert_eq!([0.0, 0.0, 0.0], material.emissive_factor());
+}